@@ -17,6 +17,7 @@ impl MyCustomElementBuilder {
     ) -> MyCustomElement {
         let el = ElementBuilder::new(MyCustomElementInternal::new())
             .builder_values(self.z_index, self.scissor_rect, None, window_cx)
+            .key(self.key)
             .rect(self.rect)
             .flags(ElementFlags::PAINTS)
             .build(window_cx);
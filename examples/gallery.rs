@@ -310,8 +310,9 @@ impl Application for MyApp {
                     self.main_window_elements.menu.open(None);
                 }
                 MyAction::ShowTooltip((info, _window_id)) => {
-                    self.main_window_elements.tooltip.show(
+                    self.main_window_elements.tooltip.show_with_shortcut(
                         &info.text,
+                        info.shortcut,
                         info.align,
                         info.element_bounds,
                         &mut cx.res,
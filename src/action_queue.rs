@@ -4,27 +4,89 @@ use std::sync::{
     mpsc,
 };
 
+use crate::element_system::ElementID;
+use crate::window::WindowID;
+
 pub fn action_channel<A: Clone + 'static>() -> (ActionSender<A>, ActionReceiver<A>) {
     let (sender, receiver) = mpsc::channel();
     (
         ActionSender {
             sender,
             action_sent: Arc::new(AtomicBool::new(false)),
+            window_id: None,
+            element_id: None,
         },
         ActionReceiver { receiver },
     )
 }
 
+/// Where an action was sent from.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ActionSource {
+    /// The window the action was sent from.
+    ///
+    /// This is `None` if the action was sent from `AppContext::action_sender`.
+    pub window_id: Option<WindowID>,
+    /// The element which sent the action.
+    ///
+    /// This is `None` if the action was not sent by an element (i.e. it was
+    /// sent from `AppContext::action_sender` or from a handle).
+    pub element_id: Option<ElementID>,
+}
+
 #[derive(Clone)]
 pub struct ActionSender<A: Clone + 'static> {
-    pub sender: mpsc::Sender<A>,
+    sender: mpsc::Sender<(A, ActionSource)>,
     action_sent: Arc<AtomicBool>,
+    window_id: Option<WindowID>,
+    /// The element whose event handler is currently running (if any).
+    element_id: Option<ElementID>,
 }
 
 impl<A: Clone + 'static> ActionSender<A> {
     pub fn send(&mut self, action: impl Into<A>) -> Result<(), mpsc::SendError<A>> {
         self.action_sent.store(true, Ordering::Relaxed);
-        self.sender.send(action.into())
+        self.sender
+            .send((
+                action.into(),
+                ActionSource {
+                    window_id: self.window_id,
+                    element_id: self.element_id,
+                },
+            ))
+            .map_err(|e| mpsc::SendError(e.0 .0))
+    }
+
+    /// The underlying channel, which sends each action along with its
+    /// [`ActionSource`].
+    ///
+    /// Actions sent directly through the channel do not trigger
+    /// `Application::on_action_emitted`, so prefer [`ActionSender::send`].
+    pub fn sender(&self) -> &mpsc::Sender<(A, ActionSource)> {
+        &self.sender
+    }
+
+    /// The window that actions sent with this sender are tagged with.
+    ///
+    /// This is `None` for the sender in `AppContext`.
+    pub fn window_id(&self) -> Option<WindowID> {
+        self.window_id
+    }
+
+    /// Create a sender for the same queue whose actions are tagged with the
+    /// given window.
+    pub fn for_window(&self, window_id: WindowID) -> Self {
+        Self {
+            sender: self.sender.clone(),
+            action_sent: Arc::clone(&self.action_sent),
+            window_id: Some(window_id),
+            element_id: None,
+        }
+    }
+
+    /// Tag all following actions with the given source element.
+    pub(crate) fn set_source_element(&mut self, element_id: Option<ElementID>) {
+        self.element_id = element_id;
     }
 
     pub(crate) fn any_action_sent(&mut self) -> bool {
@@ -33,15 +95,33 @@ impl<A: Clone + 'static> ActionSender<A> {
 }
 
 pub struct ActionReceiver<A: Clone + 'static> {
-    pub receiver: mpsc::Receiver<A>,
+    receiver: mpsc::Receiver<(A, ActionSource)>,
 }
 
 impl<A: Clone + 'static> ActionReceiver<A> {
+    /// The underlying channel, which receives each action along with its
+    /// [`ActionSource`].
+    pub fn receiver(&self) -> &mpsc::Receiver<(A, ActionSource)> {
+        &self.receiver
+    }
+
     pub fn try_recv(&mut self) -> Result<A, mpsc::TryRecvError> {
+        self.receiver.try_recv().map(|(action, _)| action)
+    }
+
+    /// Receive the next action along with the window and element it was
+    /// sent from.
+    pub fn try_recv_with_source(&mut self) -> Result<(A, ActionSource), mpsc::TryRecvError> {
         self.receiver.try_recv()
     }
 
-    pub fn try_iter(&mut self) -> mpsc::TryIter<A> {
+    pub fn try_iter(&mut self) -> impl Iterator<Item = A> + '_ {
+        self.receiver.try_iter().map(|(action, _)| action)
+    }
+
+    /// Iterate over the pending actions along with the window and element
+    /// each one was sent from.
+    pub fn try_iter_with_source(&mut self) -> impl Iterator<Item = (A, ActionSource)> + '_ {
         self.receiver.try_iter()
     }
 }
@@ -0,0 +1,144 @@
+//! Dispatches actions to per-component handler functions.
+//!
+//! In a large application the action type is usually an enum with one
+//! variant per component (i.e. `Action::Mixer(MixerAction)`). Instead of
+//! matching on every variant in `Application::on_action_emitted`, each
+//! component registers a handler with an [`ActionRouter`]:
+//!
+//! ```ignore
+//! let mut router = ActionRouter::<AppState, Action>::new();
+//!
+//! router.route(
+//!     |action| match action {
+//!         Action::Mixer(a) => Ok(a),
+//!         a => Err(a),
+//!     },
+//!     |state, action, source, cx| state.mixer.handle_action(action, source, cx),
+//! );
+//!
+//! // In `Application::on_action_emitted`:
+//! self.router.dispatch_all(&mut self.state, cx);
+//! ```
+
+use crate::action_queue::ActionSource;
+use crate::window::WindowID;
+use crate::AppContext;
+
+type RouteFn<S, A> = Box<dyn FnMut(&mut S, A, ActionSource, &mut AppContext<A>) -> Option<A>>;
+type HandlerFn<S, A> = Box<dyn FnMut(&mut S, A, ActionSource, &mut AppContext<A>)>;
+
+/// Dispatches actions to per-component handler functions.
+///
+/// `S` is the state passed to the handlers (i.e. a struct owning the element
+/// handles of all components).
+///
+/// Routes are tried in the order they were added. The first route which
+/// accepts an action handles it.
+pub struct ActionRouter<S: 'static, A: Clone + 'static> {
+    routes: Vec<RouteFn<S, A>>,
+    fallback: Option<HandlerFn<S, A>>,
+}
+
+impl<S: 'static, A: Clone + 'static> ActionRouter<S, A> {
+    pub fn new() -> Self {
+        Self {
+            routes: Vec::new(),
+            fallback: None,
+        }
+    }
+
+    /// Add a route for a sub-action type.
+    ///
+    /// * `extract` - Returns the sub-action if the action belongs to this
+    /// route, or the action itself otherwise.
+    /// * `handler` - Handles the extracted sub-action.
+    pub fn route<T, E, H>(&mut self, mut extract: E, mut handler: H)
+    where
+        E: FnMut(A) -> Result<T, A> + 'static,
+        H: FnMut(&mut S, T, ActionSource, &mut AppContext<A>) + 'static,
+    {
+        self.routes.push(Box::new(move |state, action, source, cx| {
+            match (extract)(action) {
+                Ok(sub_action) => {
+                    (handler)(state, sub_action, source, cx);
+                    None
+                }
+                Err(action) => Some(action),
+            }
+        }));
+    }
+
+    /// Add a route for all actions which were sent from the given window.
+    pub fn route_window<H>(&mut self, window_id: WindowID, mut handler: H)
+    where
+        H: FnMut(&mut S, A, ActionSource, &mut AppContext<A>) + 'static,
+    {
+        self.routes.push(Box::new(move |state, action, source, cx| {
+            if source.window_id == Some(window_id) {
+                (handler)(state, action, source, cx);
+                None
+            } else {
+                Some(action)
+            }
+        }));
+    }
+
+    /// Set the handler for actions which were not accepted by any route.
+    ///
+    /// If no fallback is set, then unhandled actions are discarded.
+    pub fn set_fallback<H>(&mut self, handler: H)
+    where
+        H: FnMut(&mut S, A, ActionSource, &mut AppContext<A>) + 'static,
+    {
+        self.fallback = Some(Box::new(handler));
+    }
+
+    /// Dispatch a single action.
+    ///
+    /// Returns `true` if a route or the fallback handled the action.
+    pub fn dispatch(
+        &mut self,
+        state: &mut S,
+        action: A,
+        source: ActionSource,
+        cx: &mut AppContext<A>,
+    ) -> bool {
+        let mut action = action;
+
+        for route in self.routes.iter_mut() {
+            match (route)(state, action, source, cx) {
+                Some(a) => action = a,
+                None => return true,
+            }
+        }
+
+        if let Some(fallback) = &mut self.fallback {
+            (fallback)(state, action, source, cx);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Dispatch all pending actions in the action queue of the application.
+    ///
+    /// This is meant to be called from `Application::on_action_emitted`.
+    ///
+    /// Returns the number of actions which were dispatched.
+    pub fn dispatch_all(&mut self, state: &mut S, cx: &mut AppContext<A>) -> usize {
+        let mut num_actions = 0;
+
+        while let Ok((action, source)) = cx.action_receiver.try_recv_with_source() {
+            self.dispatch(state, action, source, cx);
+            num_actions += 1;
+        }
+
+        num_actions
+    }
+}
+
+impl<S: 'static, A: Clone + 'static> Default for ActionRouter<S, A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,24 +1,31 @@
 use rootvg::{
     math::{PhysicalPoint, Size},
-    text::{glyphon::FontSystem, svg::SvgIconSystem},
-};
-use rustc_hash::FxHashMap;
-use std::{
-    error::Error,
-    time::{Duration, Instant},
+    text::{
+        glyphon::{fontdb::Source as FontSource, FontSystem},
+        svg::SvgIconSystem,
+        Attrs, Family, Style as FontStyle, Weight,
+    },
 };
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::{error::Error, sync::Arc, time::Duration};
+use web_time::Instant;
 
 use crate::{
-    event::{AppWindowEvent, KeyboardEvent},
+    event::{Announcement, AppWindowEvent, KeyboardEvent},
+    locale::Locale,
     prelude::{ActionReceiver, ActionSender},
-    style::StyleSystem,
+    style::{IconFontSystem, StyleSystem, TextLayoutCache},
     window::{
-        LinuxBackendType, OpenWindowError, PointerLockState, ScaleFactorConfig, WindowBackend,
-        WindowCloseRequest, WindowConfig, WindowContext, WindowID, WindowState,
+        LinuxBackendType, MonitorInfo, OpenWindowError, PointerLockState, PopupKind,
+        PopupWindowConfig, PresentMode, ScaleFactorConfig, WindowBackend, WindowCloseRequest,
+        WindowConfig, WindowContext, WindowControl, WindowID, WindowState,
     },
     MAIN_WINDOW,
 };
 
+#[cfg(feature = "image")]
+use crate::style::{DecodedImage, ImageID, ImageLoadError, ImageLoader, ImageSystem};
+
 pub trait Application: Sized {
     type Action: Clone + 'static;
 
@@ -48,6 +55,22 @@ pub trait Application: Sized {
     #[allow(unused)]
     fn on_tick(&mut self, dt: f64, cx: &mut AppContext<Self::Action>) {}
 
+    /// Called when an image requested with `ResourceCtx::image_loader` has
+    /// finished loading.
+    ///
+    /// Register a texture created from the decoded pixels with
+    /// `cx.res.image_system` to display it. Every window is redrawn after
+    /// this is called.
+    #[cfg(feature = "image")]
+    #[allow(unused)]
+    fn on_image_loaded(
+        &mut self,
+        id: ImageID,
+        result: Result<DecodedImage, ImageLoadError>,
+        cx: &mut AppContext<Self::Action>,
+    ) {
+    }
+
     #[allow(unused)]
     fn on_request_to_close_window(
         &mut self,
@@ -80,6 +103,29 @@ pub struct AppConfig {
     pub pointer_debounce_interval: TimerInterval,
     pub pointer_locking_enabled: bool,
     pub use_dark_theme: bool,
+    /// If this is `true`, then the tick timer only runs while at least one
+    /// element in any window is animating (see
+    /// `ElementContext::request_animation`) or is waiting on a timeout (i.e.
+    /// a hover timeout for a tooltip, a scroll wheel timeout, or a long
+    /// press), and `Application::on_tick` is not called while the app is
+    /// idle.
+    ///
+    /// By default this is set to `false`.
+    pub tick_only_while_animating: bool,
+    /// The maximum number of ticks per second, or `None` for no limit other
+    /// than `tick_timer_interval`. Lowering this reduces the GPU usage of
+    /// continuous animations (i.e. meters) at the cost of smoothness. This
+    /// can be changed later with `AppContext::set_max_frame_rate`.
+    ///
+    /// By default this is set to `None`.
+    pub max_frame_rate: Option<f64>,
+    /// Whether the user prefers reduced motion, in which case built-in
+    /// animations snap to their final state. Set to `None` to use the
+    /// preference of the OS (see [`system_prefers_reduced_motion`]). This
+    /// can be changed later with `AppContext::set_reduce_motion`.
+    ///
+    /// By default this is set to `None`.
+    pub reduce_motion: Option<bool>,
 }
 
 impl Default for AppConfig {
@@ -90,6 +136,9 @@ impl Default for AppConfig {
             pointer_debounce_interval: TimerInterval::PercentageOfFrameRate(2.0),
             pointer_locking_enabled: true,
             use_dark_theme: true,
+            tick_only_while_animating: false,
+            max_frame_rate: None,
+            reduce_motion: None,
         }
     }
 }
@@ -100,6 +149,22 @@ pub struct ResourceCtx {
     pub font_system: FontSystem,
     #[cfg(feature = "svg-icons")]
     pub svg_icon_system: SvgIconSystem,
+    pub icon_font_system: IconFontSystem,
+    #[cfg(feature = "image")]
+    pub image_system: ImageSystem,
+    /// Decodes images on a worker thread.
+    #[cfg(feature = "image")]
+    pub image_loader: ImageLoader,
+    /// A shared cache of shaped text used by labels and buttons.
+    pub text_layout_cache: TextLayoutCache,
+    /// The locale used by the built-in elements for translated strings and
+    /// number formatting.
+    pub locale: Locale,
+    /// Maps the names given to [`ResourceCtx::register_font`] to the actual
+    /// family names of the loaded fonts.
+    pub(crate) font_families: FxHashMap<String, &'static str>,
+    /// The leaked names of system fonts which have been resolved so far.
+    interned_family_names: FxHashSet<&'static str>,
 }
 
 impl ResourceCtx {
@@ -108,6 +173,154 @@ impl ResourceCtx {
             style_system: StyleSystem::new(use_dark_theme),
             font_system: FontSystem::new(),
             svg_icon_system: SvgIconSystem::default(),
+            icon_font_system: IconFontSystem::default(),
+            #[cfg(feature = "image")]
+            image_system: ImageSystem::default(),
+            #[cfg(feature = "image")]
+            image_loader: ImageLoader::default(),
+            text_layout_cache: TextLayoutCache::default(),
+            locale: Locale::default(),
+            font_families: FxHashMap::default(),
+            interned_family_names: FxHashSet::default(),
+        }
+    }
+
+    /// Load a custom font (i.e. a TTF or OTF file) from memory and register
+    /// it under the given family name.
+    ///
+    /// Styles can then select this font with [`ResourceCtx::font_attrs`] or
+    /// by using the returned name in `Family::Name`.
+    ///
+    /// Returns the actual family name of the loaded font, or `None` if no
+    /// font faces could be loaded from the data.
+    ///
+    /// Note, the family name is leaked so it can be used in `Attrs<'static>`.
+    pub fn register_font(
+        &mut self,
+        data: impl Into<Vec<u8>>,
+        family_name: impl Into<String>,
+    ) -> Option<&'static str> {
+        let data: Vec<u8> = data.into();
+        let ids = self
+            .font_system
+            .db_mut()
+            .load_font_source(FontSource::Binary(Arc::new(data)));
+
+        let loaded_name = ids.iter().find_map(|id| {
+            self.font_system
+                .db()
+                .face(*id)
+                .and_then(|face| face.families.first().map(|(name, _)| name.clone()))
+        })?;
+
+        let loaded_name: &'static str = match self
+            .font_families
+            .values()
+            .find(|name| **name == loaded_name)
+        {
+            Some(name) => name,
+            None => Box::leak(loaded_name.into_boxed_str()),
+        };
+
+        self.font_families.insert(family_name.into(), loaded_name);
+
+        // Text which fell back to another font may now be shaped differently.
+        self.text_layout_cache.clear();
+
+        Some(loaded_name)
+    }
+
+    /// The family of a font registered with [`ResourceCtx::register_font`],
+    /// or `None` if no font was registered with this name.
+    pub fn font_family(&self, family_name: &str) -> Option<Family<'static>> {
+        self.font_families
+            .get(family_name)
+            .map(|name| Family::Name(name))
+    }
+
+    /// Set the chain of fallback fonts used when the primary font of some
+    /// text does not contain all of its characters (i.e. CJK characters or
+    /// symbols).
+    ///
+    /// Each entry is either a name given to [`ResourceCtx::register_font`] or
+    /// the family name of a system font (i.e. "Noto Sans CJK JP"). The fonts
+    /// are tried in order.
+    ///
+    /// Note, text which has already been shaped is not updated. Set the
+    /// chain before building any elements, or rebuild the GUI afterwards.
+    pub fn set_font_fallback_chain(&mut self, chain: &[&str]) {
+        let chain = self.resolve_font_fallback_chain(chain);
+
+        self.text_layout_cache
+            .font_fallback_mut()
+            .set_default_chain(chain);
+    }
+
+    /// Set the chain of fallback fonts used for text whose primary font is
+    /// the given family, overriding the default chain. Styles can use this to
+    /// get their own fallback behavior by selecting a primary font with its
+    /// own chain.
+    ///
+    /// Pass an empty chain to use the default chain for this family again.
+    ///
+    /// See [`ResourceCtx::set_font_fallback_chain`] for details.
+    pub fn set_font_fallback_chain_for(&mut self, primary_family: &str, chain: &[&str]) {
+        let primary_family = self.resolve_family_name(primary_family);
+        let chain = self.resolve_font_fallback_chain(chain);
+
+        self.text_layout_cache
+            .font_fallback_mut()
+            .set_chain_for(primary_family, chain);
+    }
+
+    fn resolve_font_fallback_chain(&mut self, chain: &[&str]) -> Vec<&'static str> {
+        chain
+            .iter()
+            .map(|name| self.resolve_family_name(name))
+            .collect()
+    }
+
+    /// Map a name given to [`ResourceCtx::register_font`] to the actual
+    /// family name of the font, or else treat it as the name of a system
+    /// font.
+    ///
+    /// Note, the names of system fonts are leaked so they can be used in
+    /// `Attrs<'static>`. Each name is only leaked once.
+    fn resolve_family_name(&mut self, name: &str) -> &'static str {
+        if let Some(family_name) = self.font_families.get(name) {
+            return *family_name;
+        }
+
+        if let Some(family_name) = self.interned_family_names.get(name) {
+            return *family_name;
+        }
+
+        let family_name: &'static str = Box::leak(name.to_string().into_boxed_str());
+        self.interned_family_names.insert(family_name);
+        family_name
+    }
+
+    /// Text attributes which select a registered font by its family name,
+    /// weight, and style (i.e. for use in the `TextProperties` of a style).
+    ///
+    /// If no font was registered with this name, then the default font family
+    /// is used.
+    pub fn font_attrs(
+        &self,
+        family_name: &str,
+        weight: Weight,
+        style: FontStyle,
+    ) -> Attrs<'static> {
+        let attrs = Attrs::new().weight(weight).style(style);
+
+        if let Some(family) = self.font_family(family_name) {
+            attrs.family(family)
+        } else {
+            log::warn!(
+                "No font was registered with the family name {}",
+                family_name
+            );
+            attrs
         }
     }
 }
@@ -118,6 +331,8 @@ pub struct AppContext<A: Clone + 'static> {
     pub(crate) main_window: WindowState<A>,
     pub(crate) window_map: FxHashMap<WindowID, WindowState<A>>,
     pub(crate) linux_backend_type: Option<LinuxBackendType>,
+    pub(crate) popup_windows_supported: bool,
+    pub(crate) ui_zoom: f32,
     /// The global resource cx
     pub res: ResourceCtx,
 
@@ -125,6 +340,16 @@ pub struct AppContext<A: Clone + 'static> {
     pub action_sender: ActionSender<A>,
     /// The receiving end of the action queue.
     pub action_receiver: ActionReceiver<A>,
+
+    /// The number of native file dialogs which are currently open.
+    #[cfg(all(feature = "file-dialog", not(target_family = "wasm")))]
+    pub(crate) pending_file_dialogs: Arc<std::sync::atomic::AtomicUsize>,
+
+    #[cfg(all(feature = "tray", not(target_family = "wasm")))]
+    pub(crate) tray: Option<crate::tray::TrayState<A>>,
+    #[cfg(all(feature = "global-hotkey", not(target_family = "wasm")))]
+    pub(crate) global_hotkeys: crate::hotkey::GlobalHotkeys<A>,
+    pub(crate) shortcuts: crate::shortcut::Shortcuts<A>,
 }
 
 impl<A: Clone + 'static> AppContext<A> {
@@ -133,9 +358,17 @@ impl<A: Clone + 'static> AppContext<A> {
         action_sender: ActionSender<A>,
         action_receiver: ActionReceiver<A>,
         main_window: WindowState<A>,
-        res: ResourceCtx,
+        mut res: ResourceCtx,
         linux_backend_type: Option<LinuxBackendType>,
+        popup_windows_supported: bool,
     ) -> Self {
+        res.style_system.set_reduce_motion(
+            config
+                .reduce_motion
+                .or_else(system_prefers_reduced_motion)
+                .unwrap_or(false),
+        );
+
         Self {
             config,
             window_requests: Vec::new(),
@@ -143,8 +376,17 @@ impl<A: Clone + 'static> AppContext<A> {
             window_map: FxHashMap::default(),
             res,
             linux_backend_type,
+            popup_windows_supported,
+            ui_zoom: 1.0,
             action_sender,
             action_receiver,
+            #[cfg(all(feature = "file-dialog", not(target_family = "wasm")))]
+            pending_file_dialogs: Default::default(),
+            #[cfg(all(feature = "tray", not(target_family = "wasm")))]
+            tray: None,
+            #[cfg(all(feature = "global-hotkey", not(target_family = "wasm")))]
+            global_hotkeys: crate::hotkey::GlobalHotkeys::new(),
+            shortcuts: crate::shortcut::Shortcuts::new(),
         }
     }
 
@@ -166,6 +408,11 @@ impl<A: Clone + 'static> AppContext<A> {
         })
     }
 
+    /// The IDs of all currently open windows (including the main window).
+    pub fn window_ids(&self) -> impl Iterator<Item = WindowID> + '_ {
+        std::iter::once(MAIN_WINDOW).chain(self.window_map.keys().copied())
+    }
+
     pub fn resize_window(&mut self, window_id: WindowID, logical_size: Size) {
         self.window_requests
             .push((window_id, WindowRequest::Resize(logical_size)));
@@ -199,11 +446,292 @@ impl<A: Clone + 'static> AppContext<A> {
             .push((window_id, WindowRequest::SetScaleFactor(config)));
     }
 
+    /// Set a zoom factor which is applied to all windows on top of their
+    /// scale factor (i.e. `1.5` to make the UI 50% larger).
+    ///
+    /// This is independent of the scale factor of the OS, and the windows
+    /// are resized to keep their logical size.
+    pub fn set_ui_zoom(&mut self, zoom: f32) {
+        if zoom <= 0.0 || self.ui_zoom == zoom {
+            return;
+        }
+        self.ui_zoom = zoom;
+        self.res.text_layout_cache.clear();
+
+        for window_id in std::iter::once(MAIN_WINDOW).chain(self.window_map.keys().copied()) {
+            self.window_requests
+                .push((window_id, WindowRequest::SetUiZoom(zoom)));
+        }
+    }
+
+    pub fn ui_zoom(&self) -> f32 {
+        self.ui_zoom
+    }
+
+    /// The monitor the given window is currently on, or `None` if it is not
+    /// known (i.e. when using the baseview backend).
+    pub fn monitor(&self, window_id: WindowID) -> Option<&MonitorInfo> {
+        if window_id == MAIN_WINDOW {
+            self.main_window.monitor.as_ref()
+        } else {
+            self.window_map.get(&window_id)?.monitor.as_ref()
+        }
+    }
+
+    /// Set how rendered frames of the given window are presented to the
+    /// display (i.e. to turn vsync on or off).
+    pub fn set_present_mode(&mut self, window_id: WindowID, present_mode: PresentMode) {
+        if window_id == MAIN_WINDOW {
+            self.main_window.set_present_mode(present_mode);
+        } else if let Some(window_state) = self.window_map.get_mut(&window_id) {
+            window_state.set_present_mode(present_mode);
+        }
+    }
+
+    /// Limit the number of ticks per second (and therefore the frame rate of
+    /// animations), or `None` to remove the limit.
+    pub fn set_max_frame_rate(&mut self, max_frame_rate: Option<f64>) {
+        self.config.max_frame_rate = max_frame_rate.filter(|fps| *fps > 0.0);
+    }
+
+    pub fn max_frame_rate(&self) -> Option<f64> {
+        self.config.max_frame_rate
+    }
+
+    /// Set whether the user prefers reduced motion, or `None` to use the
+    /// preference of the OS.
+    ///
+    /// When enabled, built-in animations (style transitions, sliding
+    /// indicators, kinetic scrolling) snap to their final state.
+    pub fn set_reduce_motion(&mut self, reduce_motion: Option<bool>) {
+        self.config.reduce_motion = reduce_motion;
+
+        let reduce_motion = reduce_motion
+            .or_else(system_prefers_reduced_motion)
+            .unwrap_or(false);
+
+        if self.res.style_system.reduce_motion() != reduce_motion {
+            self.res.style_system.set_reduce_motion(reduce_motion);
+
+            // Let elements pick up the new setting.
+            self.notify_theme_change();
+        }
+    }
+
+    /// Returns `true` if built-in animations are currently disabled because
+    /// the user prefers reduced motion.
+    pub fn reduce_motion(&self) -> bool {
+        self.res.style_system.reduce_motion()
+    }
+
     pub fn open_window(&mut self, window_id: WindowID, config: WindowConfig) {
         self.window_requests
             .push((window_id, WindowRequest::Create(config)));
     }
 
+    /// Request to open a lightweight popup window (i.e. for a dropdown menu,
+    /// a context menu, or a tooltip) which can extend beyond the bounds of
+    /// its parent window.
+    ///
+    /// If the platform or host does not allow child windows (i.e. Wayland or
+    /// the baseview backend), then no window is opened and
+    /// `PopupKind::InWindowOverlay` is returned. In that case the popup
+    /// should be shown as an overlay in the parent window instead.
+    ///
+    /// If the window fails to open, then `AppWindowEvent::OpenWindowFailed`
+    /// is sent for the popup's window ID, and the popup should likewise fall
+    /// back to an in-window overlay.
+    pub fn open_popup_window(
+        &mut self,
+        window_id: WindowID,
+        config: PopupWindowConfig,
+    ) -> PopupKind {
+        if !self.popup_windows_supported {
+            return PopupKind::InWindowOverlay;
+        }
+
+        self.window_requests
+            .push((window_id, WindowRequest::CreatePopup(config)));
+
+        PopupKind::NativeWindow
+    }
+
+    /// Whether or not the platform and host support native popup windows.
+    ///
+    /// See [`AppContext::open_popup_window`].
+    pub fn popup_windows_supported(&self) -> bool {
+        self.popup_windows_supported
+    }
+
+    /// Show a native file open/save dialog without blocking the GUI thread.
+    ///
+    /// Once the dialog is closed, the action returned by `on_result` is sent
+    /// (with [`FileDialogResult::Cancelled`] if the user did not select
+    /// anything).
+    ///
+    /// This requires the `file-dialog` feature.
+    ///
+    /// [`FileDialogResult::Cancelled`]: crate::file_dialog::FileDialogResult::Cancelled
+    #[cfg(all(feature = "file-dialog", not(target_family = "wasm")))]
+    pub fn show_file_dialog<F>(
+        &mut self,
+        kind: crate::file_dialog::FileDialogKind,
+        config: crate::file_dialog::FileDialogConfig,
+        on_result: F,
+    ) where
+        A: Send,
+        F: FnOnce(crate::file_dialog::FileDialogResult) -> A + Send + 'static,
+    {
+        crate::file_dialog::show_file_dialog_inner(
+            kind,
+            config,
+            self.action_sender.clone(),
+            on_result,
+            Some(Arc::clone(&self.pending_file_dialogs)),
+        );
+    }
+
+    /// Show an icon with a menu in the system tray, replacing the previous
+    /// tray icon (if any).
+    ///
+    /// Clicks on the icon and selected menu items are mapped to actions with
+    /// `on_event` and sent through the action queue.
+    ///
+    /// This requires the `tray` feature.
+    #[cfg(all(feature = "tray", not(target_family = "wasm")))]
+    pub fn create_tray<F>(
+        &mut self,
+        config: crate::tray::TrayConfig,
+        on_event: F,
+    ) -> Result<(), crate::tray::TrayError>
+    where
+        F: FnMut(crate::tray::TrayEvent) -> A + 'static,
+    {
+        // Remove the old icon first so that both are never shown at once.
+        self.tray = None;
+        self.tray = Some(crate::tray::TrayState::new(config, Box::new(on_event))?);
+        Ok(())
+    }
+
+    /// Remove the icon from the system tray.
+    ///
+    /// This requires the `tray` feature.
+    #[cfg(all(feature = "tray", not(target_family = "wasm")))]
+    pub fn remove_tray(&mut self) {
+        self.tray = None;
+    }
+
+    /// Returns `true` if a tray icon was created with
+    /// [`AppContext::create_tray`].
+    ///
+    /// This requires the `tray` feature.
+    #[cfg(all(feature = "tray", not(target_family = "wasm")))]
+    pub fn has_tray(&self) -> bool {
+        self.tray.is_some()
+    }
+
+    /// Set the text shown when hovering over the tray icon.
+    ///
+    /// This requires the `tray` feature.
+    #[cfg(all(feature = "tray", not(target_family = "wasm")))]
+    pub fn set_tray_tooltip(
+        &mut self,
+        tooltip: Option<&str>,
+    ) -> Result<(), crate::tray::TrayError> {
+        match &mut self.tray {
+            Some(tray) => tray.set_tooltip(tooltip),
+            None => Ok(()),
+        }
+    }
+
+    /// Set the image of the tray icon (i.e. to show whether a recording is
+    /// running).
+    ///
+    /// This requires the `tray` feature.
+    #[cfg(all(feature = "tray", not(target_family = "wasm")))]
+    pub fn set_tray_icon(
+        &mut self,
+        icon: &crate::tray::TrayIconImage,
+    ) -> Result<(), crate::tray::TrayError> {
+        match &mut self.tray {
+            Some(tray) => tray.set_icon(icon),
+            None => Ok(()),
+        }
+    }
+
+    /// Show or hide the tray icon without removing it.
+    ///
+    /// This requires the `tray` feature.
+    #[cfg(all(feature = "tray", not(target_family = "wasm")))]
+    pub fn set_tray_visible(&mut self, visible: bool) -> Result<(), crate::tray::TrayError> {
+        match &mut self.tray {
+            Some(tray) => tray.set_visible(visible),
+            None => Ok(()),
+        }
+    }
+
+    /// Register a system-wide keyboard shortcut which sends the given action
+    /// when it is pressed, even while the application is not focused.
+    ///
+    /// This requires the `global-hotkey` feature.
+    #[cfg(all(feature = "global-hotkey", not(target_family = "wasm")))]
+    pub fn register_global_hotkey(
+        &mut self,
+        hotkey: crate::hotkey::Hotkey,
+        action: impl Into<A>,
+    ) -> Result<crate::hotkey::GlobalHotkeyID, crate::hotkey::GlobalHotkeyError> {
+        self.global_hotkeys.register(hotkey, action.into())
+    }
+
+    /// Unregister a shortcut registered with
+    /// [`AppContext::register_global_hotkey`].
+    ///
+    /// This requires the `global-hotkey` feature.
+    #[cfg(all(feature = "global-hotkey", not(target_family = "wasm")))]
+    pub fn unregister_global_hotkey(
+        &mut self,
+        id: crate::hotkey::GlobalHotkeyID,
+    ) -> Result<(), crate::hotkey::GlobalHotkeyError> {
+        self.global_hotkeys.unregister(id)
+    }
+
+    /// Register a keyboard shortcut which sends the given action when it is
+    /// pressed while a window of the application is focused.
+    ///
+    /// The shortcut only triggers if the key press was not captured by an
+    /// element (i.e. a focused text input), in which case
+    /// `Application::on_keyboard_event` is not called for it. Registering a
+    /// chord again replaces its action.
+    pub fn register_shortcut(&mut self, chord: crate::shortcut::KeyChord, action: impl Into<A>) {
+        self.shortcuts.register(chord, action.into());
+    }
+
+    /// Unregister a shortcut registered with [`AppContext::register_shortcut`].
+    ///
+    /// Returns `false` if the chord was not registered.
+    pub fn unregister_shortcut(&mut self, chord: crate::shortcut::KeyChord) -> bool {
+        self.shortcuts.unregister(chord)
+    }
+
+    /// Register the undo and redo shortcuts of the platform (see
+    /// [`UndoShortcut`](crate::undo::UndoShortcut)) with the given actions.
+    ///
+    /// Call `UndoStack::undo` and `UndoStack::redo` when handling the
+    /// actions.
+    pub fn register_undo_shortcuts(&mut self, undo: impl Into<A>, redo: impl Into<A>) {
+        use crate::undo::UndoShortcut;
+
+        let undo = undo.into();
+        let redo = redo.into();
+
+        for chord in UndoShortcut::Undo.key_chords() {
+            self.shortcuts.register(*chord, undo.clone());
+        }
+        for chord in UndoShortcut::Redo.key_chords() {
+            self.shortcuts.register(*chord, redo.clone());
+        }
+    }
+
     pub fn config(&self) -> &AppConfig {
         &self.config
     }
@@ -220,14 +748,44 @@ impl<A: Clone + 'static> AppContext<A> {
         if self.res.style_system.use_dark_theme != use_dark_theme {
             self.res.style_system.use_dark_theme = use_dark_theme;
 
-            for window_id in self.window_map.keys() {
-                self.window_requests
-                    .push((*window_id, WindowRequest::NotifyThemeChange));
-            }
+            self.notify_theme_change();
+        }
+    }
+
+    /// Load a new theme (i.e. `yarrow::theme::high_contrast::load`) and
+    /// update all elements in all windows to use the new styles.
+    pub fn load_theme<F: FnOnce(&mut ResourceCtx)>(&mut self, load: F) {
+        (load)(&mut self.res);
+
+        self.notify_theme_change();
+    }
+
+    fn notify_theme_change(&mut self) {
+        for window_id in std::iter::once(MAIN_WINDOW).chain(self.window_map.keys().copied()) {
+            self.window_requests
+                .push((window_id, WindowRequest::NotifyThemeChange));
         }
     }
 }
 
+impl<A: Clone + PartialEq + 'static> AppContext<A> {
+    /// The key chord of the shortcut registered with the given action (if
+    /// any), for displaying in menus and tooltips (see
+    /// `MenuEntry::option_with_shortcut` and `TooltipData::with_shortcut`).
+    ///
+    /// This looks up shortcuts registered with `AppContext::register_shortcut`
+    /// first, and then global hotkeys registered with
+    /// `AppContext::register_global_hotkey`.
+    pub fn shortcut_for_action(&self, action: &A) -> Option<crate::shortcut::KeyChord> {
+        let chord = self.shortcuts.chord_for_action(action);
+
+        #[cfg(all(feature = "global-hotkey", not(target_family = "wasm")))]
+        let chord = chord.or_else(|| self.global_hotkeys.chord_for_action(action));
+
+        chord
+    }
+}
+
 pub(crate) struct AppHandler<A: Application> {
     pub user_app: A,
     pub cx: AppContext<A::Action>,
@@ -242,6 +800,7 @@ impl<A: Application> AppHandler<A> {
         config: AppConfig,
         res: ResourceCtx,
         linux_backend_type: Option<LinuxBackendType>,
+        popup_windows_supported: bool,
     ) -> Result<Self, Box<dyn Error>> {
         let mut cx = AppContext::new(
             config,
@@ -250,6 +809,7 @@ impl<A: Application> AppHandler<A> {
             main_window,
             res,
             linux_backend_type,
+            popup_windows_supported,
         );
 
         let user_app = A::init(&mut cx)?;
@@ -275,9 +835,127 @@ impl<A: Application> AppHandler<A> {
         }
     }
 
+    /// The minimum duration between ticks set by `AppConfig::max_frame_rate`.
+    pub fn min_tick_interval(&self) -> Option<Duration> {
+        self.cx
+            .config
+            .max_frame_rate
+            .filter(|fps| *fps > 0.0)
+            .map(|fps| Duration::from_secs_f64(1.0 / fps))
+    }
+
+    /// Returns `false` if the tick timer can be paused because nothing is
+    /// animating and no timeouts are pending.
+    pub fn needs_ticks(&self) -> bool {
+        !self.cx.config.tick_only_while_animating
+            || self.cx.main_window.has_active_animations()
+            || self.cx.main_window.has_pending_timeouts()
+            || self
+                .cx
+                .window_map
+                .values()
+                .any(|w| w.has_active_animations() || w.has_pending_timeouts())
+            || self.has_pending_images()
+            || self.has_pending_file_dialogs()
+            || self.listens_to_system_events()
+    }
+
+    /// Returns `true` if a tray icon or a global hotkey is registered, whose
+    /// events are polled on every tick.
+    fn listens_to_system_events(&self) -> bool {
+        #[allow(unused_mut)]
+        let mut listens = false;
+
+        #[cfg(all(feature = "tray", not(target_family = "wasm")))]
+        {
+            listens |= self.cx.tray.is_some();
+        }
+
+        #[cfg(all(feature = "global-hotkey", not(target_family = "wasm")))]
+        {
+            listens |= !self.cx.global_hotkeys.is_empty();
+        }
+
+        listens
+    }
+
+    /// Send the action of the in-app shortcut triggered by the given keyboard
+    /// event (see `AppContext::register_shortcut`), or else pass the event to
+    /// the application.
+    ///
+    /// This should only be called with events which were not captured by an
+    /// element.
+    pub fn handle_uncaptured_keyboard_event(&mut self, event: KeyboardEvent, window_id: WindowID) {
+        if let Some(action) = self.cx.shortcuts.action_for_event(&event) {
+            let _ = self.cx.action_sender.send(action);
+            return;
+        }
+
+        self.user_app
+            .on_keyboard_event(event, window_id, &mut self.cx);
+    }
+
+    /// Send the actions for events from the tray icon and global hotkeys.
+    fn poll_system_events(&mut self) {
+        #[cfg(all(feature = "tray", not(target_family = "wasm")))]
+        if let Some(tray) = &mut self.cx.tray {
+            tray.poll_events(&mut self.cx.action_sender);
+        }
+
+        #[cfg(all(feature = "global-hotkey", not(target_family = "wasm")))]
+        self.cx
+            .global_hotkeys
+            .poll_events(&mut self.cx.action_sender);
+    }
+
+    #[cfg(all(feature = "file-dialog", not(target_family = "wasm")))]
+    fn has_pending_file_dialogs(&self) -> bool {
+        self.cx
+            .pending_file_dialogs
+            .load(std::sync::atomic::Ordering::Relaxed)
+            > 0
+    }
+
+    #[cfg(not(all(feature = "file-dialog", not(target_family = "wasm"))))]
+    fn has_pending_file_dialogs(&self) -> bool {
+        false
+    }
+
+    #[cfg(feature = "image")]
+    fn has_pending_images(&self) -> bool {
+        self.cx.res.image_loader.num_pending() > 0
+    }
+
+    #[cfg(not(feature = "image"))]
+    fn has_pending_images(&self) -> bool {
+        false
+    }
+
+    #[cfg(feature = "image")]
+    fn poll_loaded_images(&mut self) {
+        let loaded = self.cx.res.image_loader.take_loaded();
+        if loaded.is_empty() {
+            return;
+        }
+
+        for (id, result) in loaded {
+            self.user_app.on_image_loaded(id, result, &mut self.cx);
+        }
+
+        self.cx.main_window.mark_all_dirty();
+        for window_state in self.cx.window_map.values_mut() {
+            window_state.mark_all_dirty();
+        }
+    }
+
     pub fn process_updates<B: WindowBackend>(&mut self, backend: &mut B) {
         self.drain_pointer_moved_events(backend);
 
+        #[cfg(feature = "image")]
+        self.poll_loaded_images();
+
+        self.poll_system_events();
+
         loop {
             let any_actions_processed = self.poll_actions();
 
@@ -294,6 +972,38 @@ impl<A: Application> AppHandler<A> {
                     any_updates_processed = true;
                 }
 
+                if let Some(new_size) = window_state.requested_resize.take() {
+                    if backend
+                        .resize(*window_id, new_size, window_state.scale_factor)
+                        .is_err()
+                    {
+                        log::warn!(
+                            "Failed to set inner size {:?} for window {}",
+                            new_size,
+                            window_id
+                        );
+                    }
+                }
+
+                for control in window_state.new_window_control_requests() {
+                    match control {
+                        WindowControl::StartDragMove => backend.drag_window(*window_id),
+                        WindowControl::Minimize => backend.set_minimized(*window_id, true),
+                        WindowControl::ToggleMaximize => {
+                            let maximized = backend.is_maximized(*window_id);
+                            backend.set_maximized(*window_id, !maximized);
+                        }
+                        WindowControl::Close => {
+                            // Closing goes through the regular request queue so
+                            // that the application gets notified.
+                            self.cx
+                                .window_requests
+                                .push((*window_id, WindowRequest::Close));
+                            any_updates_processed = true;
+                        }
+                    }
+                }
+
                 if window_state.needs_repaint() {
                     backend.request_redraw(*window_id);
                 }
@@ -304,9 +1014,36 @@ impl<A: Application> AppHandler<A> {
             }
         }
 
+        self.dispatch_announcements();
+
         self.update_pointer_lock_and_cursor(backend);
     }
 
+    fn dispatch_announcements(&mut self) {
+        let mut announcements: Vec<(WindowID, Announcement)> = Vec::new();
+        for (window_id, window_state) in self
+            .cx
+            .window_map
+            .iter_mut()
+            .chain([(&MAIN_WINDOW, &mut self.cx.main_window)])
+        {
+            announcements.extend(
+                window_state
+                    .take_announcements()
+                    .into_iter()
+                    .map(|a| (*window_id, a)),
+            );
+        }
+
+        for (window_id, announcement) in announcements {
+            self.user_app.on_window_event(
+                AppWindowEvent::Announcement(announcement),
+                window_id,
+                &mut self.cx,
+            );
+        }
+    }
+
     fn drain_pointer_moved_events<B: WindowBackend>(&mut self, backend: &mut B) {
         for (window_id, window_state) in self
             .cx
@@ -381,6 +1118,42 @@ impl<A: Application> AppHandler<A> {
                 continue;
             }
 
+            if let WindowRequest::CreatePopup(config) = &request {
+                if window_id == MAIN_WINDOW || self.cx.window_map.contains_key(&window_id) {
+                    log::warn!("Ignored request to create popup window with ID {window_id}. Window with that ID already exists.");
+                    continue;
+                }
+
+                let parent_scale_factor = if config.parent == MAIN_WINDOW {
+                    self.cx.main_window.scale_factor
+                } else if let Some(parent_state) = self.cx.window_map.get(&config.parent) {
+                    parent_state.scale_factor
+                } else {
+                    log::warn!(
+                        "Ignored request to create popup window {}, parent window {} does not exist",
+                        window_id,
+                        config.parent
+                    );
+                    continue;
+                };
+
+                match backend.create_popup_window(
+                    window_id,
+                    config,
+                    parent_scale_factor,
+                    &self.cx.action_sender,
+                    &mut self.cx.res,
+                ) {
+                    Ok(window_state) => {
+                        self.cx.window_map.insert(window_id, window_state);
+                        successful_open_requests.push(window_id);
+                    }
+                    Err(e) => failed_open_requests.push((window_id, e)),
+                }
+
+                continue;
+            }
+
             let window_state = if window_id == MAIN_WINDOW {
                 &mut self.cx.main_window
             } else if let Some(window_state) = self.cx.window_map.get_mut(&window_id) {
@@ -396,6 +1169,8 @@ impl<A: Application> AppHandler<A> {
 
             match request {
                 WindowRequest::Resize(new_size) => {
+                    let new_size = window_state.size_constraints.constrain(new_size);
+
                     match backend.resize(window_id, new_size, window_state.scale_factor) {
                         Ok(_) => {}
                         Err(_) => {
@@ -436,6 +1211,20 @@ impl<A: Application> AppHandler<A> {
                         }
                     }
                 }
+                WindowRequest::SetUiZoom(zoom) => {
+                    if let Some(new_size) = window_state.set_ui_zoom(zoom) {
+                        if backend
+                            .resize(window_id, new_size, window_state.scale_factor)
+                            .is_err()
+                        {
+                            log::warn!(
+                                "Failed to set inner size {:?} for window {}",
+                                new_size,
+                                window_id
+                            );
+                        }
+                    }
+                }
                 WindowRequest::NotifyThemeChange => {
                     window_state.on_theme_changed(&mut self.cx.res);
                 }
@@ -443,10 +1232,28 @@ impl<A: Application> AppHandler<A> {
             }
         }
 
-        for window_id in windows_to_close {
-            self.cx.window_map.remove(&window_id);
+        for window_id in windows_to_close.iter() {
+            self.cx.window_map.remove(window_id);
+
+            backend.close_window(*window_id);
+        }
+
+        // The backend no longer tracks these windows, so notify the user here
+        // (i.e. so that a popup which was closed because it lost focus can
+        // update its state).
+        for window_id in windows_to_close.drain(..) {
+            self.user_app
+                .on_window_event(AppWindowEvent::WindowClosed, window_id, &mut self.cx);
+        }
 
-            backend.close_window(window_id);
+        if self.cx.ui_zoom != 1.0 {
+            for window_id in successful_open_requests.iter() {
+                if let Some(window_state) = self.cx.window_map.get_mut(window_id) {
+                    if let Some(new_size) = window_state.set_ui_zoom(self.cx.ui_zoom) {
+                        let _ = backend.resize(*window_id, new_size, window_state.scale_factor);
+                    }
+                }
+            }
         }
 
         for window_id in successful_open_requests.drain(..) {
@@ -526,6 +1333,302 @@ pub(crate) enum WindowRequest {
     Close,
     SetTitle(String),
     SetScaleFactor(ScaleFactorConfig),
+    SetUiZoom(f32),
     Create(WindowConfig),
+    CreatePopup(PopupWindowConfig),
     NotifyThemeChange,
 }
+
+/// Query whether the OS is set to reduce motion (i.e. with the "Animations"
+/// toggle in the GNOME accessibility settings).
+///
+/// The OS is only queried the first time this is called, and the result is
+/// cached for the rest of the lifetime of the process.
+///
+/// Returns `None` if the preference could not be determined on this
+/// platform. On Linux, the preference is only queried if the
+/// `reduced-motion-portal` feature is enabled.
+pub fn system_prefers_reduced_motion() -> Option<bool> {
+    static PREFERS_REDUCED_MOTION: std::sync::OnceLock<Option<bool>> = std::sync::OnceLock::new();
+
+    *PREFERS_REDUCED_MOTION.get_or_init(reduced_motion::query)
+}
+
+#[cfg(target_os = "windows")]
+mod reduced_motion {
+    use std::ffi::c_void;
+
+    const SPI_GETCLIENTAREAANIMATION: u32 = 0x1042;
+
+    #[link(name = "user32")]
+    extern "system" {
+        fn SystemParametersInfoW(
+            action: u32,
+            ui_param: u32,
+            pv_param: *mut c_void,
+            win_ini: u32,
+        ) -> i32;
+    }
+
+    pub fn query() -> Option<bool> {
+        let mut animations_enabled: i32 = 1;
+
+        // SAFETY: `SPI_GETCLIENTAREAANIMATION` writes a single `BOOL` into
+        // `pv_param`, which points to a valid `i32`.
+        let success = unsafe {
+            SystemParametersInfoW(
+                SPI_GETCLIENTAREAANIMATION,
+                0,
+                (&mut animations_enabled as *mut i32).cast(),
+                0,
+            )
+        };
+
+        (success != 0).then_some(animations_enabled == 0)
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod reduced_motion {
+    use std::ffi::{c_char, c_void};
+
+    #[link(name = "AppKit", kind = "framework")]
+    extern "C" {}
+
+    #[link(name = "objc")]
+    extern "C" {
+        fn objc_getClass(name: *const c_char) -> *mut c_void;
+        fn sel_registerName(name: *const c_char) -> *mut c_void;
+        fn objc_msgSend();
+    }
+
+    pub fn query() -> Option<bool> {
+        // SAFETY: `objc_msgSend` is cast to the signature of the methods it
+        // is used to call. `+[NSWorkspace sharedWorkspace]` returns an object
+        // and `-[NSWorkspace accessibilityDisplayShouldReduceMotion]` returns
+        // a `BOOL`, and both take no arguments. The names are null-terminated.
+        unsafe {
+            let send_id: unsafe extern "C" fn(*mut c_void, *mut c_void) -> *mut c_void =
+                std::mem::transmute(objc_msgSend as unsafe extern "C" fn());
+            let send_bool: unsafe extern "C" fn(*mut c_void, *mut c_void) -> u8 =
+                std::mem::transmute(objc_msgSend as unsafe extern "C" fn());
+
+            let class = objc_getClass(c"NSWorkspace".as_ptr());
+            if class.is_null() {
+                return None;
+            }
+
+            let workspace = send_id(class, sel_registerName(c"sharedWorkspace".as_ptr()));
+            if workspace.is_null() {
+                return None;
+            }
+
+            let reduce_motion = send_bool(
+                workspace,
+                sel_registerName(c"accessibilityDisplayShouldReduceMotion".as_ptr()),
+            );
+
+            Some(reduce_motion != 0)
+        }
+    }
+}
+
+#[cfg(all(target_os = "linux", feature = "reduced-motion-portal"))]
+mod reduced_motion {
+    use zbus::blocking::Connection;
+    use zbus::zvariant::{OwnedValue, Value};
+
+    pub fn query() -> Option<bool> {
+        let connection = Connection::session().ok()?;
+
+        // Newer portals expose a desktop-agnostic setting, where `1` means
+        // that the user prefers reduced motion.
+        if let Some(value) =
+            read_setting(&connection, "org.freedesktop.appearance", "reduced-motion")
+        {
+            if let Value::U32(reduced_motion) = unwrap_variant(&value) {
+                return Some(*reduced_motion == 1);
+            }
+        }
+
+        let value = read_setting(
+            &connection,
+            "org.gnome.desktop.interface",
+            "enable-animations",
+        )?;
+        match unwrap_variant(&value) {
+            Value::Bool(enable_animations) => Some(!enable_animations),
+            _ => None,
+        }
+    }
+
+    /// Read a setting from the settings portal over D-Bus.
+    fn read_setting(connection: &Connection, namespace: &str, key: &str) -> Option<OwnedValue> {
+        let call = |method: &str| {
+            connection
+                .call_method(
+                    Some("org.freedesktop.portal.Desktop"),
+                    "/org/freedesktop/portal/desktop",
+                    Some("org.freedesktop.portal.Settings"),
+                    method,
+                    &(namespace, key),
+                )
+                .ok()?
+                .body()
+                .deserialize::<OwnedValue>()
+                .ok()
+        };
+
+        // `ReadOne` is only available in version 2 of the portal, so fall
+        // back to the deprecated `Read` method.
+        call("ReadOne").or_else(|| call("Read"))
+    }
+
+    /// The deprecated `Read` method wraps the value in an extra variant.
+    fn unwrap_variant<'a>(value: &'a Value<'a>) -> &'a Value<'a> {
+        match value {
+            Value::Value(inner) => unwrap_variant(inner),
+            value => value,
+        }
+    }
+}
+
+#[cfg(target_family = "wasm")]
+mod reduced_motion {
+    pub fn query() -> Option<bool> {
+        web_sys::window()?
+            .match_media("(prefers-reduced-motion: reduce)")
+            .ok()
+            .flatten()
+            .map(|query| query.matches())
+    }
+}
+
+#[cfg(not(any(
+    target_os = "windows",
+    target_os = "macos",
+    all(target_os = "linux", feature = "reduced-motion-portal"),
+    target_family = "wasm"
+)))]
+mod reduced_motion {
+    pub fn query() -> Option<bool> {
+        None
+    }
+}
+
+/// The URL schemes which [`open_url`] is allowed to open.
+const OPEN_URL_SCHEMES: [&str; 3] = ["http", "https", "mailto"];
+
+/// Open the given URL with the default application of the OS (i.e. open a
+/// web page in the default browser).
+///
+/// Only `http`, `https`, and `mailto` URLs are opened, since URLs may come
+/// from untrusted content (i.e. links in a [`Markdown`](crate::elements::markdown::Markdown)
+/// document). Any other URL returns an error with the
+/// `std::io::ErrorKind::InvalidInput` kind.
+///
+/// This returns once the opener has been launched, not once the URL has been
+/// opened. On the web, the URL is opened in a new browser tab.
+pub fn open_url(url: &str) -> std::io::Result<()> {
+    validate_url(url)?;
+
+    #[cfg(target_family = "wasm")]
+    {
+        let window = web_sys::window().ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::Unsupported, "no browser window")
+        })?;
+
+        window
+            .open_with_url_and_target(url, "_blank")
+            .map(|_| ())
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, format!("{:?}", e)))
+    }
+
+    #[cfg(not(target_family = "wasm"))]
+    {
+        open_url_native(url)
+    }
+}
+
+fn validate_url(url: &str) -> std::io::Result<()> {
+    let scheme = url.split_once(':').map(|(scheme, _)| scheme).unwrap_or("");
+
+    let allowed = OPEN_URL_SCHEMES
+        .iter()
+        .any(|s| scheme.eq_ignore_ascii_case(s));
+
+    if !allowed || url.chars().any(|c| c.is_control()) {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("refusing to open URL {:?}", url),
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(target_os = "windows")]
+fn open_url_native(url: &str) -> std::io::Result<()> {
+    use std::ffi::c_void;
+
+    const SW_SHOWNORMAL: i32 = 1;
+
+    #[link(name = "shell32")]
+    extern "system" {
+        fn ShellExecuteW(
+            hwnd: *mut c_void,
+            operation: *const u16,
+            file: *const u16,
+            parameters: *const u16,
+            directory: *const u16,
+            show_cmd: i32,
+        ) -> *mut c_void;
+    }
+
+    let to_wide = |s: &str| -> Vec<u16> { s.encode_utf16().chain(std::iter::once(0)).collect() };
+    let operation = to_wide("open");
+    let file = to_wide(url);
+
+    // SAFETY: Both strings are null-terminated and outlive the call, and the
+    // other pointers are allowed to be null.
+    let result = unsafe {
+        ShellExecuteW(
+            std::ptr::null_mut(),
+            operation.as_ptr(),
+            file.as_ptr(),
+            std::ptr::null(),
+            std::ptr::null(),
+            SW_SHOWNORMAL,
+        )
+    };
+
+    // Values greater than 32 indicate success.
+    if result as isize > 32 {
+        Ok(())
+    } else {
+        Err(std::io::Error::last_os_error())
+    }
+}
+
+#[cfg(all(not(target_family = "wasm"), not(target_os = "windows")))]
+fn open_url_native(url: &str) -> std::io::Result<()> {
+    #[cfg(target_os = "macos")]
+    let mut command = std::process::Command::new("open");
+
+    #[cfg(not(target_os = "macos"))]
+    let mut command = std::process::Command::new("xdg-open");
+
+    let mut child = command
+        .arg(url)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()?;
+
+    // Reap the opener once it exits so it does not linger as a zombie.
+    std::thread::spawn(move || {
+        let _ = child.wait();
+    });
+
+    Ok(())
+}
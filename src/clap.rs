@@ -0,0 +1,352 @@
+//! An adapter for embedding a Yarrow application in the editor window of a
+//! CLAP plugin.
+//!
+//! [`ClapGui`] maps the callbacks of the CLAP `gui` extension onto
+//! [`run_parented`] and [`ParentedWindowHandle`]. Forward every callback of
+//! your `clap_plugin_gui` implementation to the method of the same name:
+//!
+//! ```ignore
+//! unsafe extern "C" fn gui_set_parent(
+//!     plugin: *const clap_plugin,
+//!     window: *const clap_window,
+//! ) -> bool {
+//!     let plugin = MyPlugin::from_raw(plugin);
+//!     plugin.gui.set_parent(window)
+//! }
+//! ```
+//!
+//! Only embedded (non-floating) windows are supported. All methods must be
+//! called from the main thread, as required by the CLAP specification.
+//!
+//! The embedded window is driven by its own event loop, which baseview runs
+//! on a frame timer of its own (even while the host is blocking in the
+//! plugin). Size and visibility requests from the host are stored in state
+//! shared with that event loop and applied on its next frame, and actions
+//! and window updates are processed there as well, so the host timer does
+//! not need to pump anything.
+//!
+//! Still, register a timer with the host's `timer-support` extension with a
+//! period of [`ClapGui::TIMER_PERIOD_MS`] and call [`ClapGui::on_timer`] from
+//! it, so that windows which were closed by the platform are cleaned up.
+//!
+//! This requires the `clap` feature.
+
+use std::ffi::CStr;
+use std::marker::PhantomData;
+
+use clap_sys::ext::gui::{
+    clap_gui_resize_hints, clap_window, CLAP_WINDOW_API_COCOA, CLAP_WINDOW_API_WIN32,
+    CLAP_WINDOW_API_X11,
+};
+
+use crate::application::Application;
+use crate::math::Size;
+use crate::window::{run_parented, ParentWindow, ParentedWindowHandle, ScaleFactorConfig};
+use crate::AppConfig;
+
+#[cfg(target_os = "macos")]
+const PLATFORM_API: &CStr = CLAP_WINDOW_API_COCOA;
+#[cfg(target_os = "windows")]
+const PLATFORM_API: &CStr = CLAP_WINDOW_API_WIN32;
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+const PLATFORM_API: &CStr = CLAP_WINDOW_API_X11;
+
+/// Maps the callbacks of the CLAP `gui` extension onto an embedded Yarrow
+/// window.
+///
+/// `config` is used every time the host asks for the GUI to be created. The
+/// initial size of the GUI is `config.main_window_config.size`.
+pub struct ClapGui<A: Application + 'static> {
+    config: AppConfig,
+    created: bool,
+    /// The scale factor set by the host (only used on Windows and Linux).
+    scale: Option<f64>,
+    /// The current logical size of the GUI.
+    size: Size,
+    visible: bool,
+    handle: Option<ParentedWindowHandle>,
+    _app: PhantomData<fn() -> A>,
+}
+
+impl<A: Application + 'static> ClapGui<A> {
+    /// The recommended period of the host timer which calls
+    /// [`ClapGui::on_timer`].
+    pub const TIMER_PERIOD_MS: u32 = 16;
+
+    pub fn new(config: AppConfig) -> Self {
+        let size = config.main_window_config.size;
+
+        Self {
+            config,
+            created: false,
+            scale: None,
+            size,
+            visible: true,
+            handle: None,
+            _app: PhantomData,
+        }
+    }
+
+    /// `clap_plugin_gui::is_api_supported`
+    pub fn is_api_supported(&self, api: &CStr, is_floating: bool) -> bool {
+        !is_floating && api == PLATFORM_API
+    }
+
+    /// `clap_plugin_gui::get_preferred_api`
+    ///
+    /// Returns the preferred window API and whether it is floating.
+    pub fn preferred_api(&self) -> (&'static CStr, bool) {
+        (PLATFORM_API, false)
+    }
+
+    /// `clap_plugin_gui::create`
+    pub fn create(&mut self, api: &CStr, is_floating: bool) -> bool {
+        if self.created || !self.is_api_supported(api, is_floating) {
+            return false;
+        }
+
+        self.created = true;
+        self.visible = true;
+        true
+    }
+
+    /// `clap_plugin_gui::destroy`
+    pub fn destroy(&mut self) {
+        if let Some(mut handle) = self.handle.take() {
+            handle.close();
+        }
+
+        self.created = false;
+    }
+
+    /// `clap_plugin_gui::set_scale`
+    ///
+    /// The scale factor can only be changed before the window is opened with
+    /// [`ClapGui::set_parent`]. On macOS this always returns `false`, since
+    /// the OS handles scaling.
+    pub fn set_scale(&mut self, scale: f64) -> bool {
+        if PLATFORM_API == CLAP_WINDOW_API_COCOA || self.handle.is_some() || scale <= 0.0 {
+            return false;
+        }
+
+        self.scale = Some(scale);
+        true
+    }
+
+    /// `clap_plugin_gui::get_size`
+    ///
+    /// The size is in physical pixels on Windows and Linux, and in logical
+    /// points on macOS.
+    pub fn size(&self) -> (u32, u32) {
+        self.to_host_size(self.size)
+    }
+
+    /// `clap_plugin_gui::can_resize`
+    pub fn can_resize(&self) -> bool {
+        let window_config = &self.config.main_window_config;
+        window_config.resizable && window_config.size_constraints.presets.len() != 1
+    }
+
+    /// `clap_plugin_gui::get_resize_hints`
+    pub fn resize_hints(&self) -> clap_gui_resize_hints {
+        let can_resize = self.can_resize();
+        let aspect_ratio = self.config.main_window_config.size_constraints.aspect_ratio;
+
+        let (aspect_ratio_width, aspect_ratio_height) = match aspect_ratio {
+            // CLAP expects the aspect ratio as a ratio of integers.
+            Some(ratio) if ratio > 0.0 => ((ratio * 1000.0).round() as u32, 1000),
+            _ => (0, 0),
+        };
+
+        clap_gui_resize_hints {
+            can_resize_horizontally: can_resize,
+            can_resize_vertically: can_resize,
+            preserve_aspect_ratio: aspect_ratio_width != 0,
+            aspect_ratio_width,
+            aspect_ratio_height,
+        }
+    }
+
+    /// `clap_plugin_gui::adjust_size`
+    ///
+    /// Returns the size closest to the given size which satisfies the size
+    /// constraints of the main window config.
+    pub fn adjust_size(&self, width: u32, height: u32) -> (u32, u32) {
+        let size = self.from_host_size(width, height);
+        let size = self
+            .config
+            .main_window_config
+            .size_constraints
+            .constrain(size);
+
+        self.to_host_size(size)
+    }
+
+    /// `clap_plugin_gui::set_size`
+    pub fn set_size(&mut self, width: u32, height: u32) -> bool {
+        if !self.can_resize() {
+            return self.size() == (width, height);
+        }
+
+        let size = self
+            .config
+            .main_window_config
+            .size_constraints
+            .constrain(self.from_host_size(width, height));
+        self.size = size;
+
+        if let Some(handle) = &self.handle {
+            handle.set_size(size);
+        }
+
+        true
+    }
+
+    /// `clap_plugin_gui::set_parent`
+    ///
+    /// # Safety
+    ///
+    /// `window` must be a valid pointer to a `clap_window` provided by the
+    /// host, whose API is the API passed to [`ClapGui::create`].
+    pub unsafe fn set_parent(&mut self, window: *const clap_window) -> bool {
+        if !self.created || self.handle.is_some() || window.is_null() {
+            return false;
+        }
+
+        let window = &*window;
+        if window.api.is_null() || CStr::from_ptr(window.api) != PLATFORM_API {
+            return false;
+        }
+
+        let parent = ParentWindow(parent_window_handle(window));
+
+        let mut config = self.config.clone();
+        config.main_window_config.size = self.size;
+        if let Some(scale) = self.scale {
+            config.main_window_config.scale_factor = ScaleFactorConfig::Custom(scale.into());
+        }
+
+        match run_parented::<_, A>(&parent, config) {
+            Ok(handle) => {
+                handle.set_visible(self.visible);
+                self.handle = Some(handle);
+                true
+            }
+            Err(e) => {
+                log::error!("Failed to open embedded window: {}", e);
+                false
+            }
+        }
+    }
+
+    /// `clap_plugin_gui::set_transient`
+    ///
+    /// Floating windows are not supported, so this always returns `false`.
+    pub fn set_transient(&mut self, _window: *const clap_window) -> bool {
+        false
+    }
+
+    /// `clap_plugin_gui::suggest_title`
+    ///
+    /// Floating windows are not supported, so this does nothing.
+    pub fn suggest_title(&mut self, _title: &CStr) {}
+
+    /// `clap_plugin_gui::show`
+    pub fn show(&mut self) -> bool {
+        self.set_visible(true)
+    }
+
+    /// `clap_plugin_gui::hide`
+    pub fn hide(&mut self) -> bool {
+        self.set_visible(false)
+    }
+
+    /// `clap_plugin_timer_support::on_timer`
+    ///
+    /// This only checks whether the window was closed by the platform.
+    /// Pending requests and updates are handled by the window's own event
+    /// loop (see the [module docs](self)).
+    ///
+    /// Returns `false` if the GUI is not open.
+    pub fn on_timer(&mut self) -> bool {
+        let Some(handle) = &self.handle else {
+            return false;
+        };
+
+        if !handle.is_open() {
+            // The window was closed by the platform.
+            self.handle = None;
+            return false;
+        }
+
+        true
+    }
+
+    /// Returns `true` if the embedded window is currently open.
+    pub fn is_open(&self) -> bool {
+        self.handle.as_ref().map(|h| h.is_open()).unwrap_or(false)
+    }
+
+    fn set_visible(&mut self, visible: bool) -> bool {
+        if !self.created {
+            return false;
+        }
+
+        self.visible = visible;
+
+        if let Some(handle) = &self.handle {
+            handle.set_visible(visible);
+        }
+
+        true
+    }
+
+    fn host_scale(&self) -> f64 {
+        self.scale.unwrap_or(1.0)
+    }
+
+    fn to_host_size(&self, size: Size) -> (u32, u32) {
+        let scale = self.host_scale();
+
+        (
+            (size.width as f64 * scale).round() as u32,
+            (size.height as f64 * scale).round() as u32,
+        )
+    }
+
+    fn from_host_size(&self, width: u32, height: u32) -> Size {
+        let scale = self.host_scale();
+
+        Size::new(
+            (width as f64 / scale) as f32,
+            (height as f64 / scale) as f32,
+        )
+    }
+}
+
+impl<A: Application + 'static> Drop for ClapGui<A> {
+    fn drop(&mut self) {
+        self.destroy();
+    }
+}
+
+#[cfg(target_os = "macos")]
+unsafe fn parent_window_handle(window: &clap_window) -> raw_window_handle::RawWindowHandle {
+    let mut handle = raw_window_handle::AppKitWindowHandle::empty();
+    handle.ns_view = window.specific.cocoa;
+    raw_window_handle::RawWindowHandle::AppKit(handle)
+}
+
+#[cfg(target_os = "windows")]
+unsafe fn parent_window_handle(window: &clap_window) -> raw_window_handle::RawWindowHandle {
+    let mut handle = raw_window_handle::Win32WindowHandle::empty();
+    handle.hwnd = window.specific.win32;
+    raw_window_handle::RawWindowHandle::Win32(handle)
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+unsafe fn parent_window_handle(window: &clap_window) -> raw_window_handle::RawWindowHandle {
+    let mut handle = raw_window_handle::XlibWindowHandle::empty();
+    handle.window = window.specific.x11 as _;
+    raw_window_handle::RawWindowHandle::Xlib(handle)
+}
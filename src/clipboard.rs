@@ -1,3 +1,9 @@
+use std::sync::Mutex;
+
+use rustc_hash::FxHashMap;
+
+use crate::window::CapturedFrame;
+
 /// The kind of [`Clipboard`].
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ClipboardKind {
@@ -20,10 +26,16 @@ impl Clipboard {
     /// Reads the current content of the [`Clipboard`] as text.
     pub fn read(&self, kind: ClipboardKind) -> Option<String> {
         let res = match &self.state {
+            #[cfg(not(target_family = "wasm"))]
             State::Connected(clipboard) => match kind {
                 ClipboardKind::Standard => clipboard.read().ok(),
                 ClipboardKind::Primary => clipboard.read_primary().and_then(Result::ok),
             },
+            #[cfg(target_family = "wasm")]
+            State::InProcess { standard, primary } => match kind {
+                ClipboardKind::Standard => Some(standard.clone()),
+                ClipboardKind::Primary => Some(primary.clone()),
+            },
             State::Unavailable => None,
         };
 
@@ -39,8 +51,17 @@ impl Clipboard {
     }
 
     /// Writes the given text contents to the [`Clipboard`].
+    ///
+    /// Writing to the standard clipboard replaces any image or custom data
+    /// previously written with [`Clipboard::write_image`] or
+    /// [`Clipboard::write_data`].
     pub fn write(&mut self, kind: ClipboardKind, contents: String) {
+        if kind == ClipboardKind::Standard {
+            RichContents::clear();
+        }
+
         match &mut self.state {
+            #[cfg(not(target_family = "wasm"))]
             State::Connected(clipboard) => {
                 let result = match kind {
                     ClipboardKind::Standard => clipboard.write(contents),
@@ -54,12 +75,230 @@ impl Clipboard {
                     }
                 }
             }
+            #[cfg(target_family = "wasm")]
+            State::InProcess { standard, primary } => match kind {
+                ClipboardKind::Standard => *standard = contents,
+                ClipboardKind::Primary => *primary = contents,
+            },
             State::Unavailable => {}
         }
     }
+
+    /// Writes an image to the [`Clipboard`], replacing any image or custom
+    /// data previously written.
+    ///
+    /// If the `clipboard-image` feature is enabled, then the image is also
+    /// written to the system clipboard so that it can be pasted into other
+    /// applications (not supported on the web). Otherwise it is only shared
+    /// within this process.
+    pub fn write_image(&mut self, image: ClipboardImage) {
+        if !image.is_valid() {
+            log::warn!(
+                "clipboard image of size {}x{} has {} bytes of pixel data, expected {}",
+                image.width,
+                image.height,
+                image.rgba.len(),
+                image.width as usize * image.height as usize * 4,
+            );
+            return;
+        }
+
+        #[cfg(all(feature = "clipboard-image", not(target_family = "wasm")))]
+        system_image::write(&image);
+
+        self.with_rich_contents(|contents| {
+            contents.data.clear();
+            contents.image = Some(image);
+        });
+    }
+
+    /// Reads an image from the [`Clipboard`].
+    ///
+    /// If the `clipboard-image` feature is enabled, then this reads images
+    /// copied from other applications as well. Otherwise, the image is
+    /// discarded once the text in the system clipboard changes (see
+    /// [`Clipboard::write_data`]).
+    pub fn read_image(&self) -> Option<ClipboardImage> {
+        #[cfg(all(feature = "clipboard-image", not(target_family = "wasm")))]
+        if let Some(image) = system_image::read() {
+            return Some(image);
+        }
+
+        self.with_rich_contents(|contents| contents.image.clone())
+    }
+
+    /// Writes structured data with a custom MIME type (i.e.
+    /// `"application/x-myplugin-preset"`) to the [`Clipboard`].
+    ///
+    /// Data with different MIME types can be written alongside each other,
+    /// i.e. a preset in a binary format and a fallback in JSON, but writing
+    /// text or an image replaces all of it.
+    ///
+    /// Custom data is only shared within this process, so it can be pasted
+    /// into any window of the application, and into other instances of a
+    /// plugin loaded in the same host.
+    ///
+    /// The data is discarded once the text in the system clipboard changes,
+    /// i.e. when the user copies something in another application. Note this
+    /// cannot be detected if the other application copies the same text or
+    /// only non-text content.
+    pub fn write_data(&mut self, mime_type: impl Into<String>, data: Vec<u8>) {
+        self.with_rich_contents(|contents| {
+            contents.image = None;
+            contents.data.insert(mime_type.into(), data);
+        });
+    }
+
+    /// Reads the data with the given MIME type from the [`Clipboard`].
+    ///
+    /// Returns `None` if the text in the system clipboard has changed since
+    /// the data was written (see [`Clipboard::write_data`]).
+    pub fn read_data(&self, mime_type: &str) -> Option<Vec<u8>> {
+        self.with_rich_contents(|contents| contents.data.get(mime_type).cloned())
+    }
+
+    /// Returns `true` if the [`Clipboard`] has data with the given MIME
+    /// type.
+    pub fn has_data(&self, mime_type: &str) -> bool {
+        self.with_rich_contents(|contents| contents.data.contains_key(mime_type))
+    }
+
+    /// The MIME types of the custom data currently in the [`Clipboard`].
+    pub fn data_mime_types(&self) -> Vec<String> {
+        self.with_rich_contents(|contents| contents.data.keys().cloned().collect())
+    }
+
+    /// Access the images and custom data written to the clipboard, discarding
+    /// them if another application has written to the system clipboard since.
+    fn with_rich_contents<T>(&self, f: impl FnOnce(&mut RichContents) -> T) -> T {
+        let system_text = self.read(ClipboardKind::Standard);
+
+        RichContents::with(|contents| {
+            if contents.system_text != system_text {
+                *contents = RichContents {
+                    system_text,
+                    ..Default::default()
+                };
+            }
+
+            (f)(contents)
+        })
+    }
+}
+
+/// An image stored in a [`Clipboard`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClipboardImage {
+    /// The width of the image in pixels.
+    pub width: u32,
+    /// The height of the image in pixels.
+    pub height: u32,
+    /// The pixels of the image in row-major order, with four bytes (red,
+    /// green, blue, and alpha) per pixel.
+    pub rgba: Vec<u8>,
+}
+
+impl ClipboardImage {
+    /// Create an image filled with a single color (i.e. for copying a color
+    /// swatch).
+    pub fn solid(width: u32, height: u32, color: crate::vg::color::RGBA8) -> Self {
+        let num_pixels = width as usize * height as usize;
+
+        let mut rgba = Vec::with_capacity(num_pixels * 4);
+        for _ in 0..num_pixels {
+            rgba.extend_from_slice(&[color.r, color.g, color.b, color.a]);
+        }
+
+        Self {
+            width,
+            height,
+            rgba,
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        self.rgba.len() == self.width as usize * self.height as usize * 4
+    }
+}
+
+impl From<CapturedFrame> for ClipboardImage {
+    fn from(frame: CapturedFrame) -> Self {
+        Self {
+            width: frame.width,
+            height: frame.height,
+            rgba: frame.pixels,
+        }
+    }
+}
+
+/// The images and custom data written to the clipboard. These are shared
+/// between all windows in the process.
+#[derive(Default)]
+struct RichContents {
+    image: Option<ClipboardImage>,
+    data: FxHashMap<String, Vec<u8>>,
+    /// The text in the system clipboard when these contents were written,
+    /// used to detect when another application has replaced them.
+    system_text: Option<String>,
+}
+
+static RICH_CONTENTS: Mutex<Option<RichContents>> = Mutex::new(None);
+
+impl RichContents {
+    fn with<T>(f: impl FnOnce(&mut RichContents) -> T) -> T {
+        let mut contents = RICH_CONTENTS.lock().unwrap_or_else(|e| e.into_inner());
+        (f)(contents.get_or_insert_with(RichContents::default))
+    }
+
+    fn clear() {
+        *RICH_CONTENTS.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+}
+
+#[cfg(all(feature = "clipboard-image", not(target_family = "wasm")))]
+mod system_image {
+    use std::borrow::Cow;
+
+    use super::ClipboardImage;
+
+    pub fn write(image: &ClipboardImage) {
+        let result = arboard::Clipboard::new().and_then(|mut clipboard| {
+            clipboard.set_image(arboard::ImageData {
+                width: image.width as usize,
+                height: image.height as usize,
+                bytes: Cow::Borrowed(&image.rgba),
+            })
+        });
+
+        if let Err(error) = result {
+            log::warn!("error writing image to clipboard: {error}");
+        }
+    }
+
+    pub fn read() -> Option<ClipboardImage> {
+        let image = arboard::Clipboard::new()
+            .and_then(|mut clipboard| clipboard.get_image())
+            .ok()?;
+
+        Some(ClipboardImage {
+            width: image.width as u32,
+            height: image.height as u32,
+            rgba: image.bytes.into_owned(),
+        })
+    }
 }
 
 pub(crate) enum State {
+    #[cfg(not(target_family = "wasm"))]
     Connected(window_clipboard::Clipboard),
+    /// The contents are only shared within the application.
+    ///
+    /// This is used on the web, where the system clipboard can only be
+    /// accessed asynchronously.
+    #[cfg(target_family = "wasm")]
+    InProcess {
+        standard: String,
+        primary: String,
+    },
     Unavailable,
 }
@@ -1,12 +1,16 @@
+use std::cmp::Ordering;
+use std::ops::RangeInclusive;
+use std::rc::Rc;
 use std::time::Duration;
-use std::time::Instant;
+use web_time::Instant;
 
 use element::ElementRenderCache;
-use keyboard_types::CompositionEvent;
+use keyboard_types::{Code, CompositionEvent, KeyState, Modifiers};
 use rootvg::color::PackedSrgb;
-use rootvg::math::PhysicalSizeI32;
 use rootvg::math::SizeI32;
 use rootvg::math::Vector;
+use rootvg::math::{PhysicalPoint, PhysicalPointI32, PhysicalRectI32, PhysicalSizeI32};
+use rootvg::text::{Attrs, Family, Metrics, RcTextBuffer, TextPrimitive, TextProperties};
 use rustc_hash::FxHashMap;
 use rustc_hash::FxHashSet;
 use smallvec::SmallVec;
@@ -14,31 +18,54 @@ use thunderdome::Arena;
 
 use crate::action_queue::ActionSender;
 use crate::clipboard::Clipboard;
-use crate::event::{CanvasEvent, ElementEvent, EventCaptureStatus, KeyboardEvent, PointerEvent};
+use crate::event::{
+    Announcement, CanvasEvent, DragEvent, DragPayload, ElementEvent, EventCaptureStatus,
+    InputEvent, KeyboardEvent, NavigationInput, PointerButton, PointerEvent, PointerType,
+};
 use crate::layout::Align2;
 use crate::math::{Point, PointI32, Rect, RectI32, ScaleFactor, Size, ZIndex};
 use crate::prelude::TooltipData;
 use crate::prelude::{ClassID, ResourceCtx};
+use crate::shortcut::KeyChord;
 use crate::stmpsc_queue;
+use crate::vg::quad::{Radius, SolidQuadBuilder};
 use crate::CursorIcon;
+use crate::WindowControl;
 use crate::WindowID;
 
+#[cfg(feature = "backdrop-blur")]
+mod backdrop_blur;
 mod cache;
+mod cvd;
 pub mod element;
+mod render_pass;
 mod scissor_rect;
+mod spatial_index;
+mod stats;
+mod transform_compositor;
 
+#[cfg(feature = "backdrop-blur")]
+pub use self::backdrop_blur::BackdropBlur;
+pub use self::cvd::ColorVisionDeficiency;
 use self::element::ChangeFocusRequest;
 use self::element::RenderContext;
+pub use self::element::{ElementID, ElementKey, ElementRef, ElementTransform};
+pub use self::render_pass::{CustomRenderPass, FrameTiming, RenderPassContext};
 pub use self::scissor_rect::ScissorRectID;
+pub use self::stats::FrameStats;
 
 use self::cache::{
-    sync_element_rect_cache, CachedElementPrimitives, CachedElementRectForPointerEvent,
+    sync_element_rect_cache, CachedElementPrimitives, CachedElementRectForPointerEvent, DrawnBounds,
 };
+use self::cvd::CvdFilter;
 use self::element::{
-    Element, ElementBuilder, ElementContext, ElementFlags, ElementHandle, ElementID,
-    ElementModification, ElementModificationType,
+    ActiveTween, CachedElementState, DragGhost, Element, ElementBuilder, ElementContext,
+    ElementFlags, ElementHandle, ElementModification, ElementModificationType, LifecycleHooks,
 };
+use self::render_pass::RenderPassEntry;
 use self::scissor_rect::ScissorRect;
+use self::spatial_index::PointerGrid;
+use self::transform_compositor::TransformCompositor;
 
 #[cfg(feature = "custom-shaders")]
 mod custom_shaders;
@@ -66,8 +93,38 @@ pub(crate) struct ElementSystemConfig {
     pub hover_timeout_duration: Duration,
 
     pub scroll_wheel_timeout_duration: Duration,
+
+    /// The duration a pointer button must be held down without moving before
+    /// the `PointerEvent::LongPress` event is sent, or `None` to disable
+    /// long-press events.
+    pub long_press_duration: Option<Duration>,
+
+    /// Whether to only re-render the regions of the window which have changed
+    /// since the last frame.
+    pub partial_redraw: bool,
+}
+
+/// The region of the window which needs to be redrawn in the next frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FrameDamage {
+    None,
+    Region(Rect),
+    Full,
+}
+
+impl FrameDamage {
+    /// Add the given rectangle to the damaged region.
+    fn add_region(&mut self, rect: Rect) {
+        *self = match *self {
+            Self::None => Self::Region(rect),
+            Self::Region(region) => Self::Region(region.union(&rect)),
+            Self::Full => Self::Full,
+        };
+    }
 }
 
+type EventFilterFn<A> = dyn FnMut(InputEvent, &mut ActionSender<A>) -> EventCaptureStatus;
+
 struct ElementSystemContext<A: Clone + 'static> {
     current_focus_info: Option<FocusInfo>,
     prev_element_with_exclusive_focus: Option<ElementID>,
@@ -78,14 +135,22 @@ struct ElementSystemContext<A: Clone + 'static> {
     cursor_icon: CursorIcon,
     pointer_lock_request: Option<bool>,
     pointer_locked: bool,
+    /// The element which has captured the pointer (if any).
+    pointer_capture: Option<ElementID>,
     window_id: WindowID,
+    /// Messages to announce to assistive technology.
+    announcements: Vec<Announcement>,
+    window_control_requests: Vec<WindowControl>,
 }
 
 pub(crate) struct ElementSystem<A: Clone + 'static> {
-    pub clear_color: PackedSrgb,
+    clear_color: PackedSrgb,
 
     context: ElementSystemContext<A>,
 
+    /// Every element instance in the window, indexed by `ElementID`. The
+    /// arena is generational, so a stale `ElementID` never refers to an
+    /// element that was added later.
     element_arena: Arena<ElementEntry<A>>,
     scissor_rect_id_to_index_map: FxHashMap<ScissorRectID, usize>,
     scissor_rects: Vec<ScissorRect>,
@@ -95,25 +160,89 @@ pub(crate) struct ElementSystem<A: Clone + 'static> {
     hovered_elements: FxHashMap<ElementID, Option<Instant>>,
     elements_with_scroll_wheel_timeout: FxHashMap<ElementID, Option<Instant>>,
     animating_elements: Vec<ElementID>,
+    /// Tweens started with `ElementHandle::animate` which are in progress.
+    tweens: Vec<ActiveTween>,
+    /// Elements whose handles have been dropped or destroyed. These are
+    /// removed after all other pending modifications have been processed.
+    pending_drops: Vec<ElementID>,
+    /// Elements which requested a single `ElementEvent::Tick` on the next frame.
+    elements_requesting_tick: FxHashSet<ElementID>,
 
     elements_listening_to_pointer_event: Vec<CachedElementRectForPointerEvent>,
     elements_listening_to_pointer_event_need_sorted: bool,
+    pointer_grid: PointerGrid,
     painted_elements: Vec<CachedElementPrimitives>,
     elements_listening_to_clicked_off: FxHashSet<ElementID>,
+    elements_listening_to_all_keys: FxHashSet<ElementID>,
+    keyed_elements: FxHashMap<ElementKey, ElementID>,
+    /// Elements anchored with `ElementHandle::set_anchor`, keyed by the
+    /// element they are anchored to.
+    anchored_elements: FxHashMap<ElementID, Vec<ElementID>>,
     element_with_active_tooltip: Option<ActiveTooltipInfo>,
+    active_drag: Option<ActiveDrag>,
 
     physical_size: PhysicalSizeI32,
     hover_timeout_duration: Duration,
     scroll_wheel_timeout_duration: Duration,
+    long_press_duration: Option<Duration>,
+    pending_long_press: Option<PendingLongPress>,
     prev_pointer_pos: Option<Point>,
+    prev_pointer_type: PointerType,
+    prev_modifiers: Modifiers,
+    /// Set when the layout has changed in a way which may have changed which
+    /// elements are under the pointer.
+    hover_needs_update: bool,
+    /// The region of the window in which the layout has changed in a way
+    /// which may have changed which elements are covered by opaque elements
+    /// or which elements are in each cell of the pointer grid.
+    occlusion_dirty: FrameDamage,
+    num_opaque_elements: usize,
+    any_occluded: bool,
 
     show_tooltip_action: Option<Box<dyn FnMut(TooltipInfo) -> A>>,
     hide_tooltip_action: Option<Box<dyn FnMut() -> A>>,
+    event_filter: Option<Box<EventFilterFn<A>>>,
 
     needs_repaint: bool,
     window_visible: bool,
 
+    partial_redraw: bool,
+    /// The regions of elements which have been removed since the last frame.
+    pending_damage: Option<Rect>,
+    full_redraw_needed: bool,
+    drag_ghost_drawn: bool,
+    /// The texture which frames are rendered into when partial redraw is
+    /// enabled. Its contents are preserved between frames and copied to the
+    /// surface.
+    backbuffer: Option<wgpu::Texture>,
+
     render_caches: FxHashMap<u32, Box<dyn ElementRenderCache>>,
+    /// Render passes added with `WindowContext::add_render_pass`.
+    render_passes: Vec<RenderPassEntry>,
+
+    /// The number of nested calls to `begin_batch`, and the sequence number
+    /// of the first modification sent after the outermost batch began.
+    batch_depth: u32,
+    batch_start: u64,
+
+    /// The statistics of the frame currently being prepared.
+    stats: FrameStats,
+    last_frame_stats: FrameStats,
+    show_stats_overlay: bool,
+
+    /// Whether the view draws a focus ring around focused elements which
+    /// don't draw their own.
+    view_focus_ring_enabled: bool,
+    /// Whether the current focus was given with keyboard navigation.
+    focus_visible: bool,
+    /// The bounds of the view focus ring in the last drawn frame.
+    focus_ring_drawn: Option<Rect>,
+
+    cvd_simulation: Option<ColorVisionDeficiency>,
+    cvd_filter: Option<CvdFilter>,
+
+    /// Created the first time an element with a transform is drawn.
+    transform_compositor: Option<TransformCompositor>,
 
     #[cfg(feature = "custom-shaders")]
     custom_pipelines: CustomPipelines,
@@ -132,6 +261,8 @@ impl<A: Clone + 'static> ElementSystem<A> {
             preallocate_for_this_many_elements,
             hover_timeout_duration,
             scroll_wheel_timeout_duration,
+            long_press_duration,
+            partial_redraw,
         } = config;
 
         assert!(scale_factor.0 > 0.0);
@@ -164,13 +295,16 @@ impl<A: Clone + 'static> ElementSystem<A> {
                 current_focus_info: None,
                 prev_element_with_exclusive_focus: None,
                 mod_queue_sender,
-                action_sender,
+                action_sender: action_sender.for_window(window_id),
                 scale_factor,
                 logical_size,
                 cursor_icon: CursorIcon::Default,
                 pointer_lock_request: None,
                 pointer_locked: false,
+                pointer_capture: None,
                 window_id,
+                announcements: Vec::new(),
+                window_control_requests: Vec::new(),
             },
 
             element_arena: Arena::with_capacity(capacity),
@@ -182,25 +316,64 @@ impl<A: Clone + 'static> ElementSystem<A> {
             hovered_elements: FxHashMap::default(),
             elements_with_scroll_wheel_timeout: FxHashMap::default(),
             animating_elements: Vec::with_capacity(capacity),
+            tweens: Vec::new(),
+            pending_drops: Vec::new(),
 
             elements_listening_to_pointer_event: Vec::new(),
             elements_listening_to_pointer_event_need_sorted: false,
+            pointer_grid: PointerGrid::default(),
             painted_elements: Vec::new(),
             elements_listening_to_clicked_off: FxHashSet::default(),
+            elements_listening_to_all_keys: FxHashSet::default(),
+            keyed_elements: FxHashMap::default(),
+            anchored_elements: FxHashMap::default(),
+            elements_requesting_tick: FxHashSet::default(),
             element_with_active_tooltip: None,
+            active_drag: None,
 
             physical_size,
             hover_timeout_duration,
             scroll_wheel_timeout_duration,
+            long_press_duration,
+            pending_long_press: None,
             prev_pointer_pos: None,
+            prev_pointer_type: PointerType::default(),
+            prev_modifiers: Modifiers::empty(),
+            hover_needs_update: false,
+            occlusion_dirty: FrameDamage::None,
+            num_opaque_elements: 0,
+            any_occluded: false,
 
             needs_repaint: true,
             window_visible: true,
 
+            partial_redraw,
+            pending_damage: None,
+            full_redraw_needed: true,
+            drag_ghost_drawn: false,
+            backbuffer: None,
+
             show_tooltip_action: None,
             hide_tooltip_action: None,
+            event_filter: None,
 
             render_caches: FxHashMap::default(),
+            render_passes: Vec::new(),
+
+            batch_depth: 0,
+            batch_start: 0,
+
+            stats: FrameStats::default(),
+
+            last_frame_stats: FrameStats::default(),
+
+            show_stats_overlay: false,
+            view_focus_ring_enabled: false,
+            focus_visible: false,
+            focus_ring_drawn: None,
+            cvd_simulation: None,
+            cvd_filter: None,
+            transform_compositor: None,
 
             #[cfg(feature = "custom-shaders")]
             custom_pipelines: CustomPipelines::new(),
@@ -216,6 +389,17 @@ impl<A: Clone + 'static> ElementSystem<A> {
         self.hide_tooltip_action = Some(Box::new(on_hide_tooltip));
     }
 
+    pub fn set_event_filter<F>(&mut self, filter: F)
+    where
+        F: FnMut(InputEvent, &mut ActionSender<A>) -> EventCaptureStatus + 'static,
+    {
+        self.event_filter = Some(Box::new(filter));
+    }
+
+    pub fn clear_event_filter(&mut self) {
+        self.event_filter = None;
+    }
+
     /// Get the current rectangle of the given scissoring rectangle.
     ///
     /// If a scissoring rectangle with the given ID does not exist, then
@@ -264,10 +448,39 @@ impl<A: Clone + 'static> ElementSystem<A> {
         self.scissor_rects[i].update(
             new_rect,
             new_scroll_offset,
+            None,
             &mut self.context.mod_queue_sender,
         );
     }
 
+    /// Get the current zoom factor of the given scissoring rectangle.
+    ///
+    /// If a scissoring rectangle with the given ID does not exist, then
+    /// one will be created.
+    pub fn scissor_rect_zoom(&mut self, scissor_rect_id: ScissorRectID) -> f32 {
+        let i = self.get_scissor_rect_index(scissor_rect_id);
+        self.scissor_rects[i].zoom()
+    }
+
+    /// Set the zoom factor of the given scissoring rectangle. The offsets
+    /// (relative to the origin of the scissoring rectangle) and the sizes of
+    /// all elements assigned to it are multiplied by this factor.
+    ///
+    /// If a scissoring rectangle with the given ID does not exist, then
+    /// one will be created.
+    ///
+    /// If `scissor_rect_id == ScissorRectID::DEFAULT`, then this
+    /// will do nothing.
+    pub fn set_scissor_rect_zoom(&mut self, scissor_rect_id: ScissorRectID, zoom: f32) {
+        if scissor_rect_id == ScissorRectID::DEFAULT || !zoom.is_finite() || zoom <= 0.0 {
+            return;
+        }
+
+        let i = self.get_scissor_rect_index(scissor_rect_id);
+
+        self.scissor_rects[i].update(None, None, Some(zoom), &mut self.context.mod_queue_sender);
+    }
+
     pub fn add_element(
         &mut self,
         element_builder: ElementBuilder<A>,
@@ -282,20 +495,36 @@ impl<A: Clone + 'static> ElementSystem<A> {
             scissor_rect,
             class,
             flags,
+            tab_index,
+            key,
+            lifecycle,
         } = element_builder;
 
+        if flags.contains(ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS) {
+            // The new element may cover an element which is currently hovered.
+            self.hover_needs_update = true;
+        }
+
+        if flags.contains(ElementFlags::PAINTS | ElementFlags::OPAQUE) {
+            self.num_opaque_elements += 1;
+        }
+
         let scissor_rect_index = self.get_scissor_rect_index(scissor_rect);
 
         let mut stack_data = EntryStackData {
             rect,
             visible_rect: None,
             offset_from_scissor_rect_origin: rect.origin.to_vector(),
+            unzoomed_size: rect.size,
             scissor_rect_index,
             z_index,
             flags,
             manually_hidden,
             class,
+            tab_index,
             animating: false,
+            opacity: 1.0,
+            transform: None,
             index_in_painted_list: 0,
             index_in_pointer_event_list: 0,
             index_in_animating_list: 0,
@@ -305,15 +534,42 @@ impl<A: Clone + 'static> ElementSystem<A> {
         stack_data.update_layout(&self.scissor_rects);
         stack_data.update_visibility(&self.scissor_rects, self.window_visible);
 
+        if let Some(visible_rect) = stack_data.visible_rect {
+            self.occlusion_dirty.add_region(visible_rect);
+        }
+
         if stack_data.visible() && stack_data.flags.contains(ElementFlags::PAINTS) {
             self.needs_repaint = true;
         }
 
+        let cached = Rc::new(CachedElementState::new(
+            rect,
+            z_index,
+            manually_hidden,
+            class,
+            tab_index,
+            flags.contains(ElementFlags::FOCUSABLE),
+        ));
+
         let element_id = ElementID(self.element_arena.insert(ElementEntry {
             stack_data,
             element,
+            key: key.clone(),
+            lifecycle,
+            cached: Rc::clone(&cached),
         }));
 
+        if let Some(key) = key {
+            if let Some(prev_element_id) = self.keyed_elements.insert(key.clone(), element_id) {
+                if self.element_arena.contains(prev_element_id.0) {
+                    log::warn!(
+                        "Element key {:?} was assigned to more than one element. The key now refers to the newest element.",
+                        key
+                    );
+                }
+            }
+        }
+
         let element_entry = self.element_arena.get_mut(element_id.0).unwrap();
 
         self.scissor_rects[scissor_rect_index]
@@ -328,14 +584,21 @@ impl<A: Clone + 'static> ElementSystem<A> {
                 self.elements_listening_to_pointer_event.len() as u32;
 
             self.elements_listening_to_pointer_event
-                .push(CachedElementRectForPointerEvent {
-                    z_index: element_entry.stack_data.z_index,
+                .push(CachedElementRectForPointerEvent::new(
                     element_id,
-                    visible_rect: element_entry.stack_data.visible_rect,
-                });
+                    &element_entry.stack_data,
+                ));
             self.elements_listening_to_pointer_event_need_sorted = true;
         }
 
+        if element_entry
+            .stack_data
+            .flags
+            .contains(ElementFlags::LISTENS_TO_ALL_KEYS)
+        {
+            self.elements_listening_to_all_keys.insert(element_id);
+        }
+
         if element_entry
             .stack_data
             .flags
@@ -366,6 +629,14 @@ impl<A: Clone + 'static> ElementSystem<A> {
             );
         }
 
+        if let Some(lifecycle) = &mut element_entry.lifecycle {
+            lifecycle.mounted(
+                element_id,
+                element_entry.stack_data.rect,
+                &mut self.context.action_sender,
+            );
+        }
+
         if let Some(render_cache_id) = element_entry.element.global_render_cache_id() {
             if !self.render_caches.contains_key(&render_cache_id) {
                 if let Some(render_cache) = element_entry.element.global_render_cache() {
@@ -374,14 +645,7 @@ impl<A: Clone + 'static> ElementSystem<A> {
             }
         }
 
-        self::element::new_element_handle(
-            element_id,
-            self.context.mod_queue_sender.clone(),
-            rect,
-            z_index,
-            manually_hidden,
-            class,
-        )
+        self::element::new_element_handle(element_id, self.context.mod_queue_sender.clone(), cached)
     }
 
     /// Returns the bounding rectangle of the given element, accounting for scroll offset.
@@ -393,6 +657,25 @@ impl<A: Clone + 'static> ElementSystem<A> {
             .map(|entry| entry.stack_data.rect)
     }
 
+    pub fn element_exists(&self, element_id: ElementID) -> bool {
+        self.element_arena.contains(element_id.0)
+    }
+
+    pub fn element_by_key(&self, key: &ElementKey) -> Option<ElementRef> {
+        let element_id = *self.keyed_elements.get(key)?;
+        let entry = self.element_arena.get(element_id.0)?;
+
+        Some(ElementRef::new(
+            element_id,
+            self.context.mod_queue_sender.clone(),
+            Rc::clone(&entry.cached),
+        ))
+    }
+
+    pub fn num_elements(&self) -> usize {
+        self.element_arena.len()
+    }
+
     pub fn auto_hide_tooltip(&mut self) {
         if let Some(info) = &mut self.element_with_active_tooltip {
             info.auto_hide = true;
@@ -403,18 +686,47 @@ impl<A: Clone + 'static> ElementSystem<A> {
         self.needs_repaint
     }
 
+    /// Whether only the changed regions of the window are redrawn.
+    pub fn partial_redraw(&self) -> bool {
+        self.partial_redraw
+    }
+
+    /// Returns `true` if any element needs to receive the next frame tick.
+    pub fn has_active_animations(&self) -> bool {
+        !self.animating_elements.is_empty()
+            || !self.elements_requesting_tick.is_empty()
+            || !self.tweens.is_empty()
+    }
+
+    /// Returns `true` if a hover timeout, scroll wheel timeout, long press, or
+    /// an auto-hiding tooltip is waiting to be handled on a future tick.
+    pub fn has_pending_timeouts(&self) -> bool {
+        self.hovered_elements.values().any(|i| i.is_some())
+            || self
+                .elements_with_scroll_wheel_timeout
+                .values()
+                .any(|i| i.is_some())
+            || (self.pending_long_press.is_some() && self.long_press_duration.is_some())
+            || self
+                .element_with_active_tooltip
+                .is_some_and(|info| info.auto_hide)
+    }
+
+    /// The element which currently has focus (if any).
+    pub fn focused_element(&self) -> Option<ElementID> {
+        self.context
+            .current_focus_info
+            .as_ref()
+            .map(|info| info.element_id)
+    }
+
     pub fn element_is_hovered(&self, element: &ElementHandle) -> bool {
         let Some(element_entry) = self.element_arena.get(element.id().0) else {
             return false;
         };
 
-        if let Some(pos) = self.prev_pointer_pos {
-            if let Some(visible_rect) = &element_entry.stack_data.visible_rect {
-                return visible_rect.contains(pos);
-            }
-        }
-
-        false
+        self.prev_pointer_pos
+            .is_some_and(|pos| element_entry.stack_data.contains_point(pos))
     }
 
     pub fn on_pointer_locked(&mut self, locked: bool) {
@@ -423,9 +735,18 @@ impl<A: Clone + 'static> ElementSystem<A> {
     }
 
     pub fn resize(&mut self, physical_size: PhysicalSizeI32, scale_factor: ScaleFactor) {
+        if self.context.scale_factor != scale_factor {
+            // Elements may snap their primitives to physical pixels, so they
+            // all need to be re-rendered at the new scale factor.
+            for cache in self.painted_elements.iter_mut() {
+                cache.dirty = true;
+            }
+        }
+
         self.physical_size = physical_size;
         self.context.scale_factor = scale_factor;
         self.context.logical_size = crate::math::to_logical_size_i32(physical_size, scale_factor);
+        self.full_redraw_needed = true;
 
         self.scissor_rects[0].update(
             Some(RectI32::new(
@@ -436,6 +757,7 @@ impl<A: Clone + 'static> ElementSystem<A> {
                 ),
             )),
             None,
+            None,
             &mut self.context.mod_queue_sender,
         );
 
@@ -469,6 +791,21 @@ impl<A: Clone + 'static> ElementSystem<A> {
         event: &CanvasEvent,
         res: &mut ResourceCtx,
         clipboard: &mut Clipboard,
+    ) -> EventCaptureStatus {
+        let start = Instant::now();
+
+        let status = self.handle_event_inner(event, res, clipboard);
+
+        self.stats.event_routing_time += start.elapsed();
+
+        status
+    }
+
+    fn handle_event_inner(
+        &mut self,
+        event: &CanvasEvent,
+        res: &mut ResourceCtx,
+        clipboard: &mut Clipboard,
     ) -> EventCaptureStatus {
         match event {
             CanvasEvent::Animation {
@@ -481,12 +818,24 @@ impl<A: Clone + 'static> ElementSystem<A> {
                 EventCaptureStatus::NotCaptured
             }
             CanvasEvent::Pointer(pointer_event) => {
+                if self.filter_pointer_event(pointer_event, res, clipboard) {
+                    return EventCaptureStatus::Captured;
+                }
+
                 self.handle_pointer_event(pointer_event, res, clipboard)
             }
             CanvasEvent::Keyboard(keyboard_event) => {
+                if self.filter_event(InputEvent::Keyboard(keyboard_event)) {
+                    return EventCaptureStatus::Captured;
+                }
+
                 self.handle_keyboard_event(keyboard_event, res, clipboard)
             }
             CanvasEvent::TextComposition(text_composition_event) => {
+                if self.filter_event(InputEvent::TextComposition(text_composition_event)) {
+                    return EventCaptureStatus::Captured;
+                }
+
                 self.handle_text_composition_event(text_composition_event, res, clipboard)
             }
             CanvasEvent::WindowHidden => {
@@ -508,6 +857,53 @@ impl<A: Clone + 'static> ElementSystem<A> {
         }
     }
 
+    /// Returns `true` if the event filter consumed the pointer event.
+    ///
+    /// The position of the pointer and the hover state are still updated
+    /// when a pointer move is consumed, so that elements don't get stuck in
+    /// a hovered state.
+    fn filter_pointer_event(
+        &mut self,
+        event: &PointerEvent,
+        res: &mut ResourceCtx,
+        clipboard: &mut Clipboard,
+    ) -> bool {
+        if self.event_filter.is_none() {
+            return false;
+        }
+
+        let hit_element = match event {
+            PointerEvent::PointerLeft => None,
+            _ => self.hit_test(event.position()),
+        };
+
+        if !self.filter_event(InputEvent::Pointer { event, hit_element }) {
+            return false;
+        }
+
+        match event {
+            PointerEvent::Moved { position, .. } => {
+                self.prev_pointer_pos = Some(*position);
+                self.unhover_elements_not_under(*position, res, clipboard);
+            }
+            PointerEvent::PointerLeft => {
+                self.handle_pointer_event(event, res, clipboard);
+            }
+            _ => {}
+        }
+
+        true
+    }
+
+    /// Returns `true` if the event filter consumed the event.
+    fn filter_event(&mut self, event: InputEvent) -> bool {
+        let Some(filter) = self.event_filter.as_mut() else {
+            return false;
+        };
+
+        (filter)(event, &mut self.context.action_sender) == EventCaptureStatus::Captured
+    }
+
     fn get_scissor_rect_index(&mut self, scissor_rect_id: ScissorRectID) -> usize {
         *self
             .scissor_rect_id_to_index_map
@@ -525,6 +921,7 @@ impl<A: Clone + 'static> ElementSystem<A> {
             return;
         }
         self.window_visible = true;
+        self.occlusion_dirty = FrameDamage::Full;
 
         let painted_elements: Vec<ElementID> =
             self.painted_elements.iter().map(|e| e.element_id).collect();
@@ -538,6 +935,7 @@ impl<A: Clone + 'static> ElementSystem<A> {
                     sync_element_rect_cache(
                         &mut element_entry.stack_data,
                         &mut self.elements_listening_to_pointer_event,
+                        &mut self.pointer_grid,
                         &mut self.painted_elements,
                         false,
                     );
@@ -556,6 +954,14 @@ impl<A: Clone + 'static> ElementSystem<A> {
                             clipboard,
                         );
                     }
+
+                    if let Some(lifecycle) = &mut element_entry.lifecycle {
+                        lifecycle.visibility_changed(
+                            *element_id,
+                            true,
+                            &mut self.context.action_sender,
+                        );
+                    }
                 }
             }
         }
@@ -566,6 +972,7 @@ impl<A: Clone + 'static> ElementSystem<A> {
             return;
         }
         self.window_visible = false;
+        self.occlusion_dirty = FrameDamage::Full;
 
         let mut visible_elements: Vec<ElementID> = Vec::new();
         for painted_element in self.painted_elements.iter() {
@@ -583,6 +990,7 @@ impl<A: Clone + 'static> ElementSystem<A> {
                 sync_element_rect_cache(
                     &mut element_entry.stack_data,
                     &mut self.elements_listening_to_pointer_event,
+                    &mut self.pointer_grid,
                     &mut self.painted_elements,
                     false,
                 );
@@ -601,6 +1009,14 @@ impl<A: Clone + 'static> ElementSystem<A> {
                         clipboard,
                     );
                 }
+
+                if let Some(lifecycle) = &mut element_entry.lifecycle {
+                    lifecycle.visibility_changed(
+                        *element_id,
+                        false,
+                        &mut self.context.action_sender,
+                    );
+                }
             }
         }
 
@@ -660,6 +1076,7 @@ impl<A: Clone + 'static> ElementSystem<A> {
             }
         }
 
+        self.context.pointer_capture = None;
         self.prev_pointer_pos = None;
 
         // TODO: Release exclusive focus if the pointer is locked.
@@ -672,6 +1089,32 @@ impl<A: Clone + 'static> ElementSystem<A> {
         res: &mut ResourceCtx,
         clipboard: &mut Clipboard,
     ) {
+        if !self.tweens.is_empty() {
+            let action_sender = &mut self.context.action_sender;
+            let reduce_motion = res.style_system.reduce_motion();
+
+            self.tweens.retain_mut(|tween| {
+                if !tween.advance(delta_seconds, reduce_motion) {
+                    return true;
+                }
+
+                if let Some(on_complete) = tween.request.on_complete.take() {
+                    match on_complete.downcast::<A>() {
+                        Ok(action) => {
+                            action_sender.set_source_element(Some(tween.element_id));
+                            action_sender.send(*action).unwrap();
+                            action_sender.set_source_element(None);
+                        }
+                        Err(_) => log::warn!(
+                            "Tween completion action does not match the action type of the window"
+                        ),
+                    }
+                }
+
+                false
+            });
+        }
+
         for element_id in self.animating_elements.iter() {
             let element_entry = self.element_arena.get_mut(element_id.0).unwrap();
 
@@ -685,26 +1128,39 @@ impl<A: Clone + 'static> ElementSystem<A> {
             );
         }
 
+        // Elements which want another tick will request it again while handling
+        // this one.
+        for element_id in std::mem::take(&mut self.elements_requesting_tick) {
+            if let Some(element_entry) = self.element_arena.get_mut(element_id.0) {
+                let _ = send_event_to_element(
+                    ElementEvent::Tick {
+                        delta_time: delta_seconds,
+                    },
+                    element_entry,
+                    element_id,
+                    &mut self.context,
+                    res,
+                    clipboard,
+                );
+            }
+        }
+
         let pos = pointer_position.unwrap_or_default();
         for (element_id, hover_start_instant) in self.hovered_elements.iter_mut() {
             if let Some(element_entry) = self.element_arena.get_mut(element_id.0) {
-                if let Some(visible_rect) = element_entry.stack_data.visible_rect {
-                    if visible_rect.contains(pos) {
-                        if let Some(instant) = hover_start_instant.take() {
-                            if instant.elapsed() >= self.hover_timeout_duration {
-                                send_event_to_element(
-                                    ElementEvent::Pointer(PointerEvent::HoverTimeout {
-                                        position: pos,
-                                    }),
-                                    element_entry,
-                                    *element_id,
-                                    &mut self.context,
-                                    res,
-                                    clipboard,
-                                );
-                            } else {
-                                *hover_start_instant = Some(instant)
-                            }
+                if element_entry.stack_data.contains_point(pos) {
+                    if let Some(instant) = hover_start_instant.take() {
+                        if instant.elapsed() >= self.hover_timeout_duration {
+                            send_event_to_element(
+                                ElementEvent::Pointer(PointerEvent::HoverTimeout { position: pos }),
+                                element_entry,
+                                *element_id,
+                                &mut self.context,
+                                res,
+                                clipboard,
+                            );
+                        } else {
+                            *hover_start_instant = Some(instant)
                         }
                     }
                 }
@@ -730,14 +1186,29 @@ impl<A: Clone + 'static> ElementSystem<A> {
             }
         }
 
+        if let (Some(press), Some(duration)) = (&self.pending_long_press, self.long_press_duration)
+        {
+            if press.instant.elapsed() >= duration {
+                let event = PointerEvent::LongPress {
+                    position: press.position,
+                    button: press.button,
+                    pointer_type: press.pointer_type,
+                    modifiers: press.modifiers,
+                };
+                self.pending_long_press = None;
+
+                self.handle_pointer_event(&event, res, clipboard);
+            }
+        }
+
         if let Some(info) = self.element_with_active_tooltip {
             let mut hide_tooltip = true;
 
             if info.auto_hide {
                 if let Some(element_entry) = self.element_arena.get(info.element_id.0) {
                     if let Some(pos) = self.prev_pointer_pos {
-                        if let Some(visible_rect) = &element_entry.stack_data.visible_rect {
-                            hide_tooltip = !visible_rect.contains(pos);
+                        if element_entry.stack_data.visible() {
+                            hide_tooltip = !element_entry.stack_data.contains_point(pos);
                         }
                     }
                 }
@@ -762,17 +1233,63 @@ impl<A: Clone + 'static> ElementSystem<A> {
     ) -> EventCaptureStatus {
         let pos = event.position();
 
+        self.update_occlusion();
+
         match event {
+            PointerEvent::ButtonJustPressed {
+                button,
+                pointer_type,
+                modifiers,
+                ..
+            } => {
+                if self.long_press_duration.is_some() {
+                    self.pending_long_press = Some(PendingLongPress {
+                        instant: Instant::now(),
+                        position: pos,
+                        button: *button,
+                        pointer_type: *pointer_type,
+                        modifiers: *modifiers,
+                    });
+                }
+            }
             PointerEvent::Moved { .. } => {
+                if let Some(press) = &self.pending_long_press {
+                    if (pos - press.position).length() > LONG_PRESS_MAX_DISTANCE {
+                        self.pending_long_press = None;
+                    }
+                }
+            }
+            PointerEvent::ButtonJustReleased { .. } | PointerEvent::PointerLeft => {
+                self.pending_long_press = None;
+            }
+            _ => {}
+        }
+
+        if self.active_drag.is_some() {
+            if let EventCaptureStatus::Captured =
+                self.handle_drag_pointer_event(event, res, clipboard)
+            {
+                return EventCaptureStatus::Captured;
+            }
+        }
+
+        match event {
+            PointerEvent::Moved {
+                pointer_type,
+                modifiers,
+                ..
+            } => {
                 self.context.cursor_icon = CursorIcon::Default;
+                self.prev_pointer_type = *pointer_type;
+                self.prev_modifiers = *modifiers;
 
                 if let Some(info) = self.element_with_active_tooltip {
                     if info.auto_hide {
                         let mut hide_tooltip = true;
 
                         if let Some(element_entry) = self.element_arena.get(info.element_id.0) {
-                            if let Some(visible_rect) = element_entry.stack_data.visible_rect {
-                                hide_tooltip = !visible_rect.contains(pos);
+                            if element_entry.stack_data.visible() {
+                                hide_tooltip = !element_entry.stack_data.contains_point(pos);
                             }
                         }
 
@@ -818,11 +1335,7 @@ impl<A: Clone + 'static> ElementSystem<A> {
         let mut unhovered_elements: SmallVec<[ElementID; 4]> = SmallVec::new();
         for (element_id, hover_start_instant) in self.hovered_elements.iter_mut() {
             if let Some(element_entry) = self.element_arena.get_mut(element_id.0) {
-                let unhovered = if let Some(visible_rect) = element_entry.stack_data.visible_rect {
-                    !visible_rect.contains(pos)
-                } else {
-                    true
-                };
+                let unhovered = !element_entry.stack_data.contains_point(pos);
 
                 if unhovered {
                     unhovered_elements.push(*element_id);
@@ -861,12 +1374,7 @@ impl<A: Clone + 'static> ElementSystem<A> {
             let mut clicked_off_elements: SmallVec<[ElementID; 4]> = SmallVec::new();
             for element_id in self.elements_listening_to_clicked_off.iter() {
                 if let Some(element_entry) = self.element_arena.get_mut(element_id.0) {
-                    let clicked_off =
-                        if let Some(visible_rect) = element_entry.stack_data.visible_rect {
-                            !visible_rect.contains(pos)
-                        } else {
-                            true
-                        };
+                    let clicked_off = !element_entry.stack_data.contains_point(pos);
 
                     if clicked_off {
                         clicked_off_elements.push(*element_id);
@@ -887,6 +1395,13 @@ impl<A: Clone + 'static> ElementSystem<A> {
             }
         }
 
+        // This needs to happen before `send_pointer_event` borrows `self`.
+        self.update_pointer_grid();
+
+        // The elements which received this event. Any hovered element which
+        // did not receive it is covered by an element which captured it.
+        let mut recipients: SmallVec<[ElementID; 8]> = SmallVec::new();
+
         let mut send_pointer_event = |element_entry: &mut ElementEntry<A>,
                                       element_id: ElementID,
                                       event: PointerEvent,
@@ -909,6 +1424,8 @@ impl<A: Clone + 'static> ElementSystem<A> {
                 _ => {}
             }
 
+            recipients.push(element_id);
+
             send_event_to_element(
                 ElementEvent::Pointer(event),
                 element_entry,
@@ -919,99 +1436,147 @@ impl<A: Clone + 'static> ElementSystem<A> {
             )
         };
 
-        // Focused elements get first priority.
-        if let Some(focused_data) = &self.context.current_focus_info {
-            if focused_data.listens_to_pointer_inside_bounds
-                || focused_data.listens_to_pointer_outside_bounds
-            {
-                let element_entry = self
-                    .element_arena
-                    .get_mut(focused_data.element_id.0)
-                    .unwrap();
-
-                if let Some(visible_rect) = element_entry.stack_data.visible_rect {
-                    let in_bounds = visible_rect.contains(pos);
-
-                    let send_event = if focused_data.listens_to_pointer_outside_bounds {
-                        true
-                    } else {
-                        in_bounds
-                    };
+        // The element which has captured the pointer, so that it does not
+        // receive the same event twice if it did not capture it.
+        let mut capturing_element = None;
 
-                    let mut did_just_enter = false;
-                    if in_bounds {
-                        self.hovered_elements
-                            .entry(focused_data.element_id)
-                            .or_insert_with(|| {
-                                did_just_enter = false;
-                                None
-                            });
-                    }
+        let capture_status = 'route: {
+            // The element that has captured the pointer gets first priority.
+            if let Some(element_id) = self.context.pointer_capture {
+                if let Some(element_entry) = self.element_arena.get_mut(element_id.0) {
+                    if element_entry.stack_data.visible() {
+                        capturing_element = Some(element_id);
 
-                    if send_event {
                         let capture_status = send_pointer_event(
                             element_entry,
-                            focused_data.element_id,
+                            element_id,
                             event.clone(),
-                            did_just_enter,
+                            false,
                             &mut self.context,
                         );
 
                         if let EventCaptureStatus::Captured = capture_status {
-                            return EventCaptureStatus::Captured;
+                            break 'route EventCaptureStatus::Captured;
                         }
                     }
+                } else {
+                    self.context.pointer_capture = None;
                 }
             }
-        }
 
-        if self.elements_listening_to_pointer_event_need_sorted {
-            self.elements_listening_to_pointer_event_need_sorted = false;
-            self.elements_listening_to_pointer_event
-                .sort_unstable_by(|a, b| a.z_index.cmp(&b.z_index));
+            // Focused elements get first priority.
+            if let Some(focused_data) = &self.context.current_focus_info {
+                if (focused_data.listens_to_pointer_inside_bounds
+                    || focused_data.listens_to_pointer_outside_bounds)
+                    && capturing_element != Some(focused_data.element_id)
+                {
+                    let element_entry = self
+                        .element_arena
+                        .get_mut(focused_data.element_id.0)
+                        .unwrap();
 
-            for (i, cache) in self.elements_listening_to_pointer_event.iter().enumerate() {
-                if let Some(element_entry) = self.element_arena.get_mut(cache.element_id.0) {
-                    element_entry.stack_data.index_in_pointer_event_list = i as u32;
-                }
-            }
-        }
+                    if element_entry.stack_data.visible() {
+                        let in_bounds = element_entry.stack_data.contains_point(pos);
 
-        // Iterate z indexes from highest to lowest.
-        for cached_rect in self.elements_listening_to_pointer_event.iter().rev() {
-            if let Some(visible_rect) = &cached_rect.visible_rect {
-                if !visible_rect.contains(pos) {
-                    continue;
+                        let send_event = if focused_data.listens_to_pointer_outside_bounds {
+                            true
+                        } else {
+                            in_bounds
+                        };
+
+                        let mut did_just_enter = false;
+                        if in_bounds {
+                            self.hovered_elements
+                                .entry(focused_data.element_id)
+                                .or_insert_with(|| {
+                                    did_just_enter = false;
+                                    None
+                                });
+                        }
+
+                        if send_event {
+                            let capture_status = send_pointer_event(
+                                element_entry,
+                                focused_data.element_id,
+                                event.clone(),
+                                did_just_enter,
+                                &mut self.context,
+                            );
+
+                            if let EventCaptureStatus::Captured = capture_status {
+                                break 'route EventCaptureStatus::Captured;
+                            }
+                        }
+                    }
                 }
+            }
 
-                let Some(element_entry) = self.element_arena.get_mut(cached_rect.element_id.0)
-                else {
-                    continue;
-                };
+            // Iterate z indexes from highest to lowest.
+            for index in self.pointer_grid.candidates(pos).iter().rev() {
+                let cached_rect = &self.elements_listening_to_pointer_event[*index as usize];
 
-                let mut did_just_enter = false;
-                self.hovered_elements
-                    .entry(cached_rect.element_id)
-                    .or_insert_with(|| {
-                        did_just_enter = true;
-                        None
-                    });
+                if cached_rect.visible_rect.is_some() {
+                    if !cached_rect.contains_point(pos)
+                        || capturing_element == Some(cached_rect.element_id)
+                    {
+                        continue;
+                    }
 
-                let capture_status = send_pointer_event(
-                    element_entry,
-                    cached_rect.element_id,
-                    event.clone(),
-                    did_just_enter,
-                    &mut self.context,
-                );
+                    let Some(element_entry) = self.element_arena.get_mut(cached_rect.element_id.0)
+                    else {
+                        continue;
+                    };
 
-                if let EventCaptureStatus::Captured = capture_status {
-                    return EventCaptureStatus::Captured;
+                    let mut did_just_enter = false;
+                    self.hovered_elements
+                        .entry(cached_rect.element_id)
+                        .or_insert_with(|| {
+                            did_just_enter = true;
+                            None
+                        });
+
+                    let capture_status = send_pointer_event(
+                        element_entry,
+                        cached_rect.element_id,
+                        event.clone(),
+                        did_just_enter,
+                        &mut self.context,
+                    );
+
+                    if let EventCaptureStatus::Captured = capture_status {
+                        break 'route EventCaptureStatus::Captured;
+                    }
+                }
+            }
+
+            EventCaptureStatus::NotCaptured
+        };
+
+        if let PointerEvent::Moved { .. } = event {
+            let occluded_elements: SmallVec<[ElementID; 4]> = self
+                .hovered_elements
+                .keys()
+                .filter(|element_id| !recipients.contains(element_id))
+                .copied()
+                .collect();
+
+            for element_id in occluded_elements.iter() {
+                self.hovered_elements.remove(element_id);
+
+                if let Some(element_entry) = self.element_arena.get_mut(element_id.0) {
+                    send_event_to_element(
+                        ElementEvent::Pointer(PointerEvent::PointerLeft),
+                        element_entry,
+                        *element_id,
+                        &mut self.context,
+                        res,
+                        clipboard,
+                    );
                 }
             }
         }
 
-        EventCaptureStatus::NotCaptured
+        capture_status
     }
 
     fn handle_keyboard_event(
@@ -1020,8 +1585,17 @@ impl<A: Clone + 'static> ElementSystem<A> {
         res: &mut ResourceCtx,
         clipboard: &mut Clipboard,
     ) -> EventCaptureStatus {
+        if self.active_drag.is_some() && event.state == KeyState::Down && event.code == Code::Escape
+        {
+            self.end_drag(false, res, clipboard);
+            return EventCaptureStatus::Captured;
+        }
+
+        let mut focused_element = None;
         if let Some(focused_data) = &self.context.current_focus_info {
             if focused_data.listens_to_keys {
+                focused_element = Some(focused_data.element_id);
+
                 let element_entry = self
                     .element_arena
                     .get_mut(focused_data.element_id.0)
@@ -1042,197 +1616,105 @@ impl<A: Clone + 'static> ElementSystem<A> {
             }
         }
 
-        EventCaptureStatus::NotCaptured
-    }
-
-    fn handle_text_composition_event(
-        &mut self,
-        event: &CompositionEvent,
-        res: &mut ResourceCtx,
-        clipboard: &mut Clipboard,
-    ) -> EventCaptureStatus {
-        if let Some(focused_data) = &self.context.current_focus_info {
-            if focused_data.listens_to_text_composition {
-                let element_entry = self
-                    .element_arena
-                    .get_mut(focused_data.element_id.0)
-                    .unwrap();
-
-                let capture_satus = send_event_to_element(
-                    ElementEvent::TextComposition(event.clone()),
-                    element_entry,
-                    focused_data.element_id,
-                    &mut self.context,
-                    res,
-                    clipboard,
-                );
+        for element_id in self.elements_listening_to_all_keys.iter() {
+            if focused_element == Some(*element_id) {
+                continue;
+            }
 
-                if let EventCaptureStatus::Captured = capture_satus {
-                    return EventCaptureStatus::Captured;
-                }
+            let Some(element_entry) = self.element_arena.get_mut(element_id.0) else {
+                continue;
+            };
+            if !element_entry.stack_data.visible() {
+                continue;
             }
-        }
 
-        EventCaptureStatus::NotCaptured
-    }
+            let capture_status = send_event_to_element(
+                ElementEvent::Keyboard(event.clone()),
+                element_entry,
+                *element_id,
+                &mut self.context,
+                res,
+                clipboard,
+            );
 
-    /// Returns `true` if any updates were processed.
-    pub fn process_updates(&mut self, res: &mut ResourceCtx, clipboard: &mut Clipboard) -> bool {
-        let mut processed_update = false;
-        while let Some(modification) = self.mod_queue_receiver.try_recv() {
-            processed_update = true;
-            match modification.type_ {
-                ElementModificationType::CustomStateChanged => {
-                    self.handle_element_custom_state_changed(
-                        modification.element_id,
-                        res,
-                        clipboard,
-                    );
-                }
-                ElementModificationType::MarkDirty => {
-                    self.mark_element_dirty(modification.element_id);
-                }
-                ElementModificationType::RectChanged(new_rect) => {
-                    self.update_element_rect(modification.element_id, new_rect, res, clipboard);
-                }
-                ElementModificationType::ScissorRectChanged => {
-                    self.handle_scissor_rect_changed_for_element(
-                        modification.element_id,
-                        res,
-                        clipboard,
-                    );
-                }
-                ElementModificationType::ZIndexChanged(new_z_index) => {
-                    self.update_element_z_index(
-                        modification.element_id,
-                        new_z_index,
-                        res,
-                        clipboard,
-                    );
-                }
-                ElementModificationType::ExplicitlyHiddenChanged(manually_hidden) => {
-                    self.update_element_manually_hidden(
-                        modification.element_id,
-                        manually_hidden,
-                        res,
-                        clipboard,
-                    );
-                }
-                ElementModificationType::ClassChanged(new_class) => {
-                    self.handle_element_class_changed(
-                        modification.element_id,
-                        new_class,
-                        res,
-                        clipboard,
-                    );
-                }
-                ElementModificationType::SetAnimating(animating) => {
-                    self.set_element_animating(modification.element_id, animating);
-                }
-                ElementModificationType::ChangeFocus(req) => match req {
-                    ChangeFocusRequest::StealFocus => {
-                        self.element_steal_focus(modification.element_id, false, res, clipboard);
-                    }
-                    ChangeFocusRequest::StealTemporaryFocus => {
-                        self.element_steal_focus(modification.element_id, true, res, clipboard);
-                    }
-                    ChangeFocusRequest::ReleaseFocus => {
-                        self.element_release_focus(modification.element_id, res, clipboard);
-                    }
-                },
-                ElementModificationType::HandleDropped => {
-                    self.drop_element(modification.element_id, res, clipboard);
-                }
-                ElementModificationType::ListenToClickOff => {
-                    self.handle_element_listen_to_click_off(modification.element_id);
-                }
-                ElementModificationType::StartHoverTimeout => {
-                    self.handle_element_start_hover_timeout(modification.element_id);
-                }
-                ElementModificationType::StartScrollWheelTimeout => {
-                    self.handle_element_start_scroll_wheel_timeout(modification.element_id);
-                }
-                ElementModificationType::ShowTooltip { data, auto_hide } => {
-                    self.handle_element_show_tooltip(modification.element_id, data, auto_hide);
-                }
-                ElementModificationType::UpdateScissorRect(req) => {
-                    self.update_scissor_rect(
-                        req.scissor_rect_id,
-                        req.new_rect,
-                        req.new_scroll_offset,
-                    );
-                }
+            if let EventCaptureStatus::Captured = capture_status {
+                return EventCaptureStatus::Captured;
             }
         }
 
-        processed_update
-    }
+        if event.state == KeyState::Down
+            && event.code == Code::Tab
+            && !event
+                .modifiers
+                .intersects(Modifiers::CONTROL | Modifiers::ALT | Modifiers::META)
+        {
+            let backwards = event.modifiers.contains(Modifiers::SHIFT);
 
-    fn handle_element_listen_to_click_off(&mut self, element_id: ElementID) {
-        if self.element_arena.contains(element_id.0) {
-            self.elements_listening_to_clicked_off.insert(element_id);
+            if self.focus_next(backwards, res, clipboard) {
+                return EventCaptureStatus::Captured;
+            }
         }
-    }
 
-    fn handle_element_start_hover_timeout(&mut self, element_id: ElementID) {
-        if self.element_arena.contains(element_id.0) {
-            if let Some(hover_start_instant) = self.hovered_elements.get_mut(&element_id) {
-                *hover_start_instant = Some(Instant::now());
+        if event.state == KeyState::Down
+            && !event.repeat
+            && matches!(event.code, Code::Enter | Code::NumpadEnter | Code::Space)
+        {
+            if self.activate_focused(res, clipboard) {
+                return EventCaptureStatus::Captured;
             }
         }
-    }
 
-    fn handle_element_start_scroll_wheel_timeout(&mut self, element_id: ElementID) {
-        if self.element_arena.contains(element_id.0) {
-            self.elements_with_scroll_wheel_timeout
-                .insert(element_id, Some(Instant::now()));
-        }
+        EventCaptureStatus::NotCaptured
     }
 
-    fn handle_element_show_tooltip(
+    /// Handle a navigation input from a gamepad or hardware controller.
+    ///
+    /// Returns `true` if the input was handled.
+    pub fn navigate(
         &mut self,
-        element_id: ElementID,
-        data: TooltipData,
-        auto_hide: bool,
-    ) {
-        let Some(element_entry) = self.element_arena.get(element_id.0) else {
-            // Element has been dropped. Do nothing and return.
-            return;
-        };
-
-        self.element_with_active_tooltip = Some(ActiveTooltipInfo {
-            element_id,
-            auto_hide,
-        });
-
-        if let Some(action) = self.show_tooltip_action.as_mut() {
-            let info = TooltipInfo {
-                text: data.text,
-                align: data.align,
-                element_bounds: element_entry.stack_data.rect,
-                window_id: self.context.window_id,
-            };
-
-            self.context.action_sender.send((action)(info)).unwrap();
+        input: NavigationInput,
+        res: &mut ResourceCtx,
+        clipboard: &mut Clipboard,
+    ) -> bool {
+        match input {
+            NavigationInput::Activate => self.activate_focused(res, clipboard),
+            NavigationInput::Next => self.focus_next(false, res, clipboard),
+            NavigationInput::Previous => self.focus_next(true, res, clipboard),
+            NavigationInput::Up => self.focus_in_direction(Vector::new(0.0, -1.0), res, clipboard),
+            NavigationInput::Down => self.focus_in_direction(Vector::new(0.0, 1.0), res, clipboard),
+            NavigationInput::Left => {
+                self.focus_in_direction(Vector::new(-1.0, 0.0), res, clipboard)
+            }
+            NavigationInput::Right => {
+                self.focus_in_direction(Vector::new(1.0, 0.0), res, clipboard)
+            }
         }
     }
 
-    fn handle_element_class_changed(
-        &mut self,
-        element_id: ElementID,
-        new_class: ClassID,
-        res: &mut ResourceCtx,
-        clipboard: &mut Clipboard,
-    ) {
+    /// Send `ElementEvent::Activate` to the focused element (if it is
+    /// focusable).
+    ///
+    /// Returns `true` if the element captured the event.
+    fn activate_focused(&mut self, res: &mut ResourceCtx, clipboard: &mut Clipboard) -> bool {
+        let Some(focus_info) = &self.context.current_focus_info else {
+            return false;
+        };
+        let element_id = focus_info.element_id;
+
         let Some(element_entry) = self.element_arena.get_mut(element_id.0) else {
-            // Element has been dropped. Do nothing and return.
-            return;
+            return false;
         };
 
-        element_entry.stack_data.class = new_class;
+        if !element_entry
+            .stack_data
+            .flags
+            .contains(ElementFlags::FOCUSABLE)
+        {
+            return false;
+        }
 
-        send_event_to_element(
-            ElementEvent::StyleChanged,
+        let status = send_event_to_element(
+            ElementEvent::Activate,
             element_entry,
             element_id,
             &mut self.context,
@@ -1240,618 +1722,2546 @@ impl<A: Clone + 'static> ElementSystem<A> {
             clipboard,
         );
 
-        self.mark_element_dirty(element_id);
+        status == EventCaptureStatus::Captured
     }
 
-    fn handle_element_custom_state_changed(
+    /// Move focus to the closest focusable element in the given direction
+    /// from the currently focused element.
+    ///
+    /// If no element has focus, then the first element in the keyboard
+    /// navigation order is focused.
+    ///
+    /// Returns `false` if there is no element to move focus to.
+    fn focus_in_direction(
         &mut self,
-        element_id: ElementID,
+        dir: Vector,
         res: &mut ResourceCtx,
         clipboard: &mut Clipboard,
-    ) {
-        let Some(element_entry) = self.element_arena.get_mut(element_id.0) else {
-            // Element has been dropped. Do nothing and return.
-            return;
+    ) -> bool {
+        let current = self.context.current_focus_info.as_ref().and_then(|info| {
+            self.element_arena
+                .get(info.element_id.0)
+                .map(|entry| (info.element_id, entry.stack_data.rect))
+        });
+
+        let Some((current_id, current_rect)) = current else {
+            return self.focus_next(false, res, clipboard);
         };
 
-        send_event_to_element(
-            ElementEvent::CustomStateChanged,
-            element_entry,
-            element_id,
-            &mut self.context,
-            res,
-            clipboard,
-        );
-    }
+        let from = current_rect.center();
 
-    fn mark_element_dirty(&mut self, element_id: ElementID) {
-        let Some(element_entry) = self.element_arena.get(element_id.0) else {
-            // Element has been dropped. Do nothing and return.
-            return;
-        };
+        let mut closest: Option<(ElementID, f32)> = None;
+        for element_id in self.tab_order() {
+            if element_id == current_id {
+                continue;
+            }
 
-        if !element_entry
-            .stack_data
-            .flags
-            .contains(ElementFlags::PAINTS)
-            || !element_entry.stack_data.visible()
-        {
-            return;
+            let rect = self
+                .element_arena
+                .get(element_id.0)
+                .unwrap()
+                .stack_data
+                .rect;
+            let offset = rect.center() - from;
+
+            // The distance along the direction of navigation.
+            let along = offset.dot(dir);
+            if along <= 0.0 {
+                continue;
+            }
+
+            // Penalize elements that are not in line with the current one so
+            // that navigation feels like moving along rows and columns.
+            let across = (offset - (dir * along)).length();
+            let score = along + (across * SPATIAL_NAVIGATION_CROSS_AXIS_WEIGHT);
+
+            if closest.map(|(_, s)| score < s).unwrap_or(true) {
+                closest = Some((element_id, score));
+            }
         }
 
-        self.painted_elements[element_entry.stack_data.index_in_painted_list as usize].dirty = true;
+        let Some((element_id, _)) = closest else {
+            return false;
+        };
 
-        self.needs_repaint = true;
+        self.element_steal_focus(element_id, false, res, clipboard);
+        self.set_focus_visible(true);
+
+        true
     }
 
-    fn update_element_rect(
+    /// Move exclusive focus to the next focusable element in the keyboard
+    /// navigation order (or the previous element if `backwards` is `true`),
+    /// wrapping around at the ends.
+    ///
+    /// Returns `false` if there are no visible focusable elements.
+    pub fn focus_next(
         &mut self,
-        element_id: ElementID,
-        new_rect: Rect,
+        backwards: bool,
         res: &mut ResourceCtx,
         clipboard: &mut Clipboard,
-    ) {
-        let Some(element_entry) = self.element_arena.get_mut(element_id.0) else {
-            // Element has been dropped. Do nothing and return.
-            return;
-        };
+    ) -> bool {
+        let order = self.tab_order();
+        if order.is_empty() {
+            return false;
+        }
 
-        let pos_changed =
-            element_entry.stack_data.offset_from_scissor_rect_origin != new_rect.origin.to_vector();
-        let size_changed = element_entry.stack_data.rect.size != new_rect.size;
+        let current = self
+            .context
+            .current_focus_info
+            .as_ref()
+            .and_then(|info| order.iter().position(|id| *id == info.element_id));
+
+        let next_i = match (current, backwards) {
+            (Some(i), false) => (i + 1) % order.len(),
+            (Some(i), true) => (i + order.len() - 1) % order.len(),
+            (None, false) => 0,
+            (None, true) => order.len() - 1,
+        };
 
-        if !(pos_changed || size_changed) {
-            return;
-        }
+        self.element_steal_focus(order[next_i], false, res, clipboard);
+        self.set_focus_visible(true);
 
-        element_entry.stack_data.offset_from_scissor_rect_origin = new_rect.origin.to_vector();
-        element_entry.stack_data.rect.size = new_rect.size;
-        element_entry.stack_data.update_layout(&self.scissor_rects);
+        true
+    }
 
-        let old_visibility = element_entry.stack_data.visible();
-        element_entry
-            .stack_data
-            .update_visibility(&self.scissor_rects, self.window_visible);
-        let visibility_changed = element_entry.stack_data.visible() != old_visibility;
+    fn set_focus_visible(&mut self, focus_visible: bool) {
+        if self.focus_visible != focus_visible {
+            self.focus_visible = focus_visible;
 
-        if visibility_changed && !element_entry.stack_data.visible() {
-            release_focus_for_element(element_id, element_entry, &mut self.context, res, clipboard);
+            if self.view_focus_ring_enabled {
+                self.needs_repaint = true;
+            }
         }
+    }
 
-        if size_changed
-            && element_entry
-                .stack_data
-                .flags
-                .contains(ElementFlags::LISTENS_TO_SIZE_CHANGE)
-        {
-            send_event_to_element(
-                ElementEvent::SizeChanged,
-                element_entry,
-                element_id,
-                &mut self.context,
-                res,
-                clipboard,
-            );
+    /// The visible focusable elements sorted in keyboard navigation order.
+    ///
+    /// Elements with an explicit tab index come first in ascending order,
+    /// followed by the rest in reading order (top to bottom, left to right).
+    fn tab_order(&self) -> Vec<ElementID> {
+        let mut focusable: Vec<(Option<u16>, Point, ElementID)> = self
+            .element_arena
+            .iter()
+            .filter(|(_, entry)| {
+                entry.stack_data.flags.contains(ElementFlags::FOCUSABLE)
+                    && entry.stack_data.visible()
+            })
+            .map(|(index, entry)| {
+                (
+                    entry.stack_data.tab_index,
+                    entry.stack_data.rect.origin,
+                    ElementID(index),
+                )
+            })
+            .collect();
+
+        focusable.sort_by(
+            |(a_index, a_pos, _), (b_index, b_pos, _)| match (a_index, b_index) {
+                (Some(a), Some(b)) => a.cmp(b),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => a_pos
+                    .y
+                    .total_cmp(&b_pos.y)
+                    .then(a_pos.x.total_cmp(&b_pos.x)),
+            },
+        );
+
+        focusable.into_iter().map(|(_, _, id)| id).collect()
+    }
+
+    fn start_drag(
+        &mut self,
+        source: ElementID,
+        payload: DragPayload,
+        ghost: Option<DragGhost>,
+        res: &mut ResourceCtx,
+        clipboard: &mut Clipboard,
+    ) {
+        // Cancel any drag that is already in progress.
+        if self.active_drag.is_some() {
+            self.end_drag(false, res, clipboard);
         }
 
-        if pos_changed
-            && element_entry
-                .stack_data
-                .flags
-                .contains(ElementFlags::LISTENS_TO_POSITION_CHANGE)
-        {
-            send_event_to_element(
-                ElementEvent::PositionChanged,
-                element_entry,
-                element_id,
-                &mut self.context,
-                res,
-                clipboard,
-            );
+        self.active_drag = Some(ActiveDrag {
+            source,
+            payload,
+            ghost,
+            position: self.prev_pointer_pos.unwrap_or_default(),
+            target: None,
+        });
+
+        if let Some(pos) = self.prev_pointer_pos {
+            self.update_drag_target(pos, res, clipboard);
         }
 
-        if visibility_changed
-            && element_entry
-                .stack_data
-                .flags
-                .contains(ElementFlags::LISTENS_TO_VISIBILITY_CHANGE)
-        {
-            let event = if element_entry.stack_data.visible() {
-                ElementEvent::Shown
-            } else {
-                ElementEvent::Hidden
-            };
+        self.needs_repaint = true;
+    }
 
-            send_event_to_element(
-                event,
-                element_entry,
-                element_id,
-                &mut self.context,
-                res,
-                clipboard,
-            );
+    fn handle_drag_pointer_event(
+        &mut self,
+        event: &PointerEvent,
+        res: &mut ResourceCtx,
+        clipboard: &mut Clipboard,
+    ) -> EventCaptureStatus {
+        match event {
+            PointerEvent::Moved { position, .. } => {
+                self.prev_pointer_pos = Some(*position);
+                self.update_drag_target(*position, res, clipboard);
+
+                if self
+                    .active_drag
+                    .as_ref()
+                    .map(|d| d.ghost.is_some())
+                    .unwrap_or(false)
+                {
+                    self.needs_repaint = true;
+                }
+
+                EventCaptureStatus::Captured
+            }
+            PointerEvent::ButtonJustReleased { position, .. } => {
+                self.update_drag_target(*position, res, clipboard);
+
+                let drag = self.active_drag.as_ref().unwrap();
+                let mut dropped = false;
+
+                if let Some(target) = drag.target {
+                    if let Some(element_entry) = self.element_arena.get_mut(target.0) {
+                        let status = send_event_to_element(
+                            ElementEvent::Drag(DragEvent::Dropped {
+                                position: *position
+                                    - element_entry.stack_data.rect.origin.to_vector(),
+                                payload: drag.payload.clone(),
+                            }),
+                            element_entry,
+                            target,
+                            &mut self.context,
+                            res,
+                            clipboard,
+                        );
+
+                        dropped = status == EventCaptureStatus::Captured;
+                    }
+                }
+
+                // The drop target has already received its final event.
+                self.active_drag.as_mut().unwrap().target = None;
+
+                self.end_drag(dropped, res, clipboard);
+
+                EventCaptureStatus::Captured
+            }
+            PointerEvent::PointerLeft => {
+                self.set_drag_target(None, None, res, clipboard);
+                EventCaptureStatus::NotCaptured
+            }
+            _ => EventCaptureStatus::NotCaptured,
         }
+    }
 
-        let mark_dirty = (visibility_changed && element_entry.stack_data.visible()) || size_changed;
+    /// Find the topmost visible element which accepts drops at the given
+    /// position and send it the appropriate drag events.
+    fn update_drag_target(&mut self, pos: Point, res: &mut ResourceCtx, clipboard: &mut Clipboard) {
+        let Some(drag) = &mut self.active_drag else {
+            return;
+        };
+        drag.position = pos;
+        let source = drag.source;
 
-        sync_element_rect_cache(
-            &element_entry.stack_data,
-            &mut self.elements_listening_to_pointer_event,
-            &mut self.painted_elements,
-            mark_dirty,
-        );
+        let mut new_target: Option<(ElementID, ZIndex)> = None;
+        for (index, entry) in self.element_arena.iter() {
+            let element_id = ElementID(index);
 
-        if element_entry.stack_data.visible() || visibility_changed {
-            self.needs_repaint = true;
+            if element_id == source || !entry.stack_data.flags.contains(ElementFlags::ACCEPTS_DROPS)
+            {
+                continue;
+            }
+
+            if entry.stack_data.contains_point(pos)
+                && new_target
+                    .map(|(_, z)| entry.stack_data.z_index >= z)
+                    .unwrap_or(true)
+            {
+                new_target = Some((element_id, entry.stack_data.z_index));
+            }
         }
+
+        self.set_drag_target(new_target.map(|(id, _)| id), Some(pos), res, clipboard);
     }
 
-    fn handle_scissor_rect_changed_for_element(
+    fn set_drag_target(
         &mut self,
-        element_id: ElementID,
+        new_target: Option<ElementID>,
+        pos: Option<Point>,
         res: &mut ResourceCtx,
         clipboard: &mut Clipboard,
     ) {
-        let Some(element_entry) = self.element_arena.get_mut(element_id.0) else {
-            // Element has been dropped. Do nothing and return.
+        let Some(drag) = &mut self.active_drag else {
             return;
         };
+        let prev_target = drag.target;
+        let payload = drag.payload.clone();
+        drag.target = new_target;
 
-        element_entry.stack_data.update_layout(&self.scissor_rects);
-
-        let old_visibility = element_entry.stack_data.visible();
-        element_entry
-            .stack_data
-            .update_visibility(&self.scissor_rects, self.window_visible);
-        let visibility_changed = element_entry.stack_data.visible() != old_visibility;
-
-        let mark_dirty = visibility_changed && element_entry.stack_data.visible();
+        if prev_target != new_target {
+            if let Some(prev_target) = prev_target {
+                if let Some(element_entry) = self.element_arena.get_mut(prev_target.0) {
+                    send_event_to_element(
+                        ElementEvent::Drag(DragEvent::Left),
+                        element_entry,
+                        prev_target,
+                        &mut self.context,
+                        res,
+                        clipboard,
+                    );
+                }
+            }
+        }
 
-        sync_element_rect_cache(
-            &element_entry.stack_data,
-            &mut self.elements_listening_to_pointer_event,
-            &mut self.painted_elements,
-            mark_dirty,
-        );
+        let (Some(target), Some(pos)) = (new_target, pos) else {
+            return;
+        };
 
-        if visibility_changed && !element_entry.stack_data.visible() {
-            release_focus_for_element(element_id, element_entry, &mut self.context, res, clipboard);
-        }
+        if let Some(element_entry) = self.element_arena.get_mut(target.0) {
+            let position = pos - element_entry.stack_data.rect.origin.to_vector();
 
-        if visibility_changed
-            && element_entry
-                .stack_data
-                .flags
-                .contains(ElementFlags::LISTENS_TO_VISIBILITY_CHANGE)
-        {
-            let event = if element_entry.stack_data.visible() {
-                ElementEvent::Shown
+            let event = if prev_target == new_target {
+                DragEvent::Moved { position, payload }
             } else {
-                ElementEvent::Hidden
+                DragEvent::Entered { position, payload }
             };
 
             send_event_to_element(
-                event,
+                ElementEvent::Drag(event),
                 element_entry,
-                element_id,
+                target,
                 &mut self.context,
                 res,
                 clipboard,
             );
         }
-
-        if element_entry.stack_data.visible() || visibility_changed {
-            self.needs_repaint = true;
-        }
     }
 
-    fn update_element_z_index(
-        &mut self,
-        element_id: ElementID,
-        new_z_index: ZIndex,
-        res: &mut ResourceCtx,
-        clipboard: &mut Clipboard,
-    ) {
-        let Some(element_entry) = self.element_arena.get_mut(element_id.0) else {
-            // Element has been dropped. Do nothing and return.
-            return;
-        };
+    /// End the current drag-and-drop operation (if there is one).
+    fn end_drag(&mut self, dropped: bool, res: &mut ResourceCtx, clipboard: &mut Clipboard) {
+        self.set_drag_target(None, None, res, clipboard);
 
-        if element_entry.stack_data.z_index == new_z_index {
+        let Some(drag) = self.active_drag.take() else {
             return;
-        }
-        element_entry.stack_data.z_index = new_z_index;
-
-        if element_entry
-            .stack_data
-            .flags
-            .contains(ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS)
-        {
-            self.elements_listening_to_pointer_event
-                [element_entry.stack_data.index_in_pointer_event_list as usize]
-                .z_index = new_z_index;
-            self.elements_listening_to_pointer_event_need_sorted = true;
-        }
-
-        if element_entry
-            .stack_data
-            .flags
-            .contains(ElementFlags::PAINTS)
-        {
-            self.painted_elements[element_entry.stack_data.index_in_painted_list as usize]
-                .z_index = new_z_index;
-        }
+        };
 
-        if element_entry
-            .stack_data
-            .flags
-            .contains(ElementFlags::LISTENS_TO_Z_INDEX_CHANGE)
-        {
+        if let Some(element_entry) = self.element_arena.get_mut(drag.source.0) {
             send_event_to_element(
-                ElementEvent::ZIndexChanged,
+                ElementEvent::Drag(DragEvent::Ended { dropped }),
                 element_entry,
-                element_id,
+                drag.source,
                 &mut self.context,
                 res,
                 clipboard,
             );
         }
 
-        // Detecting if a z index change requires a repaint or not would be very tricky,
-        // so just repaint regardless if the element is visible.
-        if element_entry.stack_data.visible() {
+        if drag.ghost.is_some() {
             self.needs_repaint = true;
         }
     }
 
-    fn update_element_manually_hidden(
+    fn handle_text_composition_event(
         &mut self,
-        element_id: ElementID,
-        manually_hidden: bool,
+        event: &CompositionEvent,
         res: &mut ResourceCtx,
         clipboard: &mut Clipboard,
-    ) {
-        let Some(element_entry) = self.element_arena.get_mut(element_id.0) else {
-            // Element has been dropped. Do nothing and return.
-            return;
-        };
+    ) -> EventCaptureStatus {
+        if let Some(focused_data) = &self.context.current_focus_info {
+            if focused_data.listens_to_text_composition {
+                let element_entry = self
+                    .element_arena
+                    .get_mut(focused_data.element_id.0)
+                    .unwrap();
 
-        if element_entry.stack_data.manually_hidden == manually_hidden {
-            return;
+                let capture_satus = send_event_to_element(
+                    ElementEvent::TextComposition(event.clone()),
+                    element_entry,
+                    focused_data.element_id,
+                    &mut self.context,
+                    res,
+                    clipboard,
+                );
+
+                if let EventCaptureStatus::Captured = capture_satus {
+                    return EventCaptureStatus::Captured;
+                }
+            }
         }
 
-        element_entry.stack_data.manually_hidden = manually_hidden;
+        EventCaptureStatus::NotCaptured
+    }
 
-        let old_visibility = element_entry.stack_data.visible();
-        element_entry
-            .stack_data
-            .update_visibility(&self.scissor_rects, self.window_visible);
-        let visibility_changed = element_entry.stack_data.visible() != old_visibility;
+    pub fn begin_batch(&mut self) {
+        if self.batch_depth == 0 {
+            self.batch_start = self.mod_queue_receiver.next_seq();
+        }
+        self.batch_depth += 1;
+    }
 
-        if !visibility_changed {
+    /// End a batch of modifications started with `begin_batch`.
+    ///
+    /// When the outermost batch ends, the modifications which were queued
+    /// during the batch are coalesced so that each element only receives
+    /// one update of each kind (i.e. setting the rect of an element 100
+    /// times only lays it out once with the final rect).
+    pub fn end_batch(&mut self) {
+        if self.batch_depth == 0 {
+            return;
+        }
+        self.batch_depth -= 1;
+        if self.batch_depth > 0 {
             return;
         }
 
-        let mark_dirty = visibility_changed && element_entry.stack_data.visible();
+        // Modifications may have been processed during the batch (i.e. the
+        // queue was flushed), so only the ones sent since the batch began
+        // which are still in the queue are coalesced.
+        let mut seen: FxHashSet<(ElementID, u8)> = FxHashSet::default();
+
+        // The modifications are visited in reverse so that only the last
+        // modification of each kind is kept.
+        self.mod_queue_receiver
+            .retain_since_rev(self.batch_start, |modification| {
+                let kind = match &modification.type_ {
+                    ElementModificationType::CustomStateChanged => Some(0),
+                    ElementModificationType::MarkDirty => Some(1),
+                    ElementModificationType::RectChanged(_) => Some(2),
+                    ElementModificationType::ZIndexChanged(_) => Some(3),
+                    ElementModificationType::ExplicitlyHiddenChanged(_) => Some(4),
+                    ElementModificationType::ClassChanged(_) => Some(5),
+                    ElementModificationType::TabIndexChanged(_) => Some(6),
+                    ElementModificationType::OpacityChanged(_) => Some(7),
+                    ElementModificationType::TransformChanged(_) => Some(8),
+                    ElementModificationType::FocusableChanged(_) => Some(9),
+                    // The order of the other modifications matters.
+                    _ => None,
+                };
 
-        sync_element_rect_cache(
-            &element_entry.stack_data,
-            &mut self.elements_listening_to_pointer_event,
-            &mut self.painted_elements,
-            mark_dirty,
-        );
+                let Some(kind) = kind else {
+                    return true;
+                };
 
-        if visibility_changed && !element_entry.stack_data.visible() {
-            release_focus_for_element(element_id, element_entry, &mut self.context, res, clipboard);
-        }
+                seen.insert((modification.element_id, kind))
+            });
+    }
 
-        if element_entry
-            .stack_data
-            .flags
-            .contains(ElementFlags::LISTENS_TO_VISIBILITY_CHANGE)
-        {
-            let event = if element_entry.stack_data.visible() {
-                ElementEvent::Shown
-            } else {
-                ElementEvent::Hidden
-            };
+    /// Returns `true` if any updates were processed.
+    pub fn process_updates(&mut self, res: &mut ResourceCtx, clipboard: &mut Clipboard) -> bool {
+        let start = Instant::now();
+        let mut processed_update = false;
+        let mut updated_hover = false;
+        loop {
+            self.process_modifications(&mut processed_update, res, clipboard);
+
+            // Only update the hover state once per call so that elements which
+            // change their layout when hovered can't cause an endless loop.
+            if updated_hover || !self.hover_needs_update {
+                break;
+            }
+            updated_hover = true;
+            self.hover_needs_update = false;
 
-            send_event_to_element(
-                event,
-                element_entry,
-                element_id,
-                &mut self.context,
-                res,
-                clipboard,
-            );
+            self.update_hover_after_layout_change(res, clipboard);
         }
 
-        self.needs_repaint = true;
+        self.stats.update_time += start.elapsed();
+
+        processed_update
     }
 
-    fn set_element_animating(&mut self, element_id: ElementID, animating: bool) {
-        let Some(element_entry) = self.element_arena.get_mut(element_id.0) else {
-            // Element has been dropped. Do nothing and return.
-            return;
-        };
+    fn process_modifications(
+        &mut self,
+        processed_update: &mut bool,
+        res: &mut ResourceCtx,
+        clipboard: &mut Clipboard,
+    ) {
+        loop {
+            while let Some(modification) = self.mod_queue_receiver.try_recv() {
+                *processed_update = true;
+                match modification.type_ {
+                    ElementModificationType::CustomStateChanged => {
+                        self.handle_element_custom_state_changed(
+                            modification.element_id,
+                            res,
+                            clipboard,
+                        );
+                    }
+                    ElementModificationType::MarkDirty => {
+                        self.mark_element_dirty(modification.element_id);
+                    }
+                    ElementModificationType::RectChanged(new_rect) => {
+                        self.mark_element_occlusion_dirty(modification.element_id);
+                        if self.update_element_rect(
+                            modification.element_id,
+                            new_rect,
+                            res,
+                            clipboard,
+                        ) {
+                            self.notify_anchored_elements(modification.element_id, res, clipboard);
+                        }
+                        self.hover_needs_update = true;
+                        self.mark_element_occlusion_dirty(modification.element_id);
+                    }
+                    ElementModificationType::ScissorRectChanged => {
+                        self.hover_needs_update = true;
+                        self.mark_element_occlusion_dirty(modification.element_id);
+                        self.handle_scissor_rect_changed_for_element(
+                            modification.element_id,
+                            res,
+                            clipboard,
+                        );
+                        self.mark_element_occlusion_dirty(modification.element_id);
+                    }
+                    ElementModificationType::ZIndexChanged(new_z_index) => {
+                        self.hover_needs_update = true;
+                        self.mark_element_occlusion_dirty(modification.element_id);
+                        self.update_element_z_index(
+                            modification.element_id,
+                            new_z_index,
+                            res,
+                            clipboard,
+                        );
+                    }
+                    ElementModificationType::ExplicitlyHiddenChanged(manually_hidden) => {
+                        self.hover_needs_update = true;
+                        self.mark_element_occlusion_dirty(modification.element_id);
+                        self.update_element_manually_hidden(
+                            modification.element_id,
+                            manually_hidden,
+                            res,
+                            clipboard,
+                        );
+                        self.mark_element_occlusion_dirty(modification.element_id);
+                    }
+                    ElementModificationType::ClassChanged(new_class) => {
+                        self.handle_element_class_changed(
+                            modification.element_id,
+                            new_class,
+                            res,
+                            clipboard,
+                        );
+                    }
+                    ElementModificationType::TabIndexChanged(tab_index) => {
+                        if let Some(element_entry) =
+                            self.element_arena.get_mut(modification.element_id.0)
+                        {
+                            element_entry.stack_data.tab_index = tab_index;
+                        }
+                    }
+                    ElementModificationType::FocusableChanged(focusable) => {
+                        self.update_element_focusable(
+                            modification.element_id,
+                            focusable,
+                            res,
+                            clipboard,
+                        );
+                    }
+                    ElementModificationType::SetAnimating(animating) => {
+                        self.set_element_animating(modification.element_id, animating);
+                    }
+                    ElementModificationType::ChangeFocus(req) => match req {
+                        ChangeFocusRequest::StealFocus => {
+                            self.element_steal_focus(
+                                modification.element_id,
+                                false,
+                                res,
+                                clipboard,
+                            );
+                        }
+                        ChangeFocusRequest::StealTemporaryFocus => {
+                            self.element_steal_focus(modification.element_id, true, res, clipboard);
+                        }
+                        ChangeFocusRequest::ReleaseFocus => {
+                            self.element_release_focus(modification.element_id, res, clipboard);
+                        }
+                    },
+                    ElementModificationType::HandleDropped => {
+                        self.pending_drops.push(modification.element_id);
+                    }
+                    ElementModificationType::ListenToClickOff => {
+                        self.handle_element_listen_to_click_off(modification.element_id);
+                    }
+                    ElementModificationType::StartHoverTimeout => {
+                        self.handle_element_start_hover_timeout(modification.element_id);
+                    }
+                    ElementModificationType::StartScrollWheelTimeout => {
+                        self.handle_element_start_scroll_wheel_timeout(modification.element_id);
+                    }
+                    ElementModificationType::ShowTooltip { data, auto_hide } => {
+                        self.handle_element_show_tooltip(modification.element_id, data, auto_hide);
+                    }
+                    ElementModificationType::UpdateScissorRect(req) => {
+                        self.update_scissor_rect(
+                            req.scissor_rect_id,
+                            req.new_rect,
+                            req.new_scroll_offset,
+                        );
+                        if let Some(zoom) = req.new_zoom {
+                            self.set_scissor_rect_zoom(req.scissor_rect_id, zoom);
+                        }
+                    }
+                    ElementModificationType::OpacityChanged(opacity) => {
+                        if let Some(element_entry) =
+                            self.element_arena.get_mut(modification.element_id.0)
+                        {
+                            if element_entry.stack_data.opacity != opacity {
+                                element_entry.stack_data.opacity = opacity;
+                                self.mark_element_occlusion_dirty(modification.element_id);
+                                self.mark_element_dirty(modification.element_id);
+                            }
+                        }
+                    }
+                    ElementModificationType::SetOpaque(opaque) => {
+                        self.update_element_opaque(modification.element_id, opaque);
+                    }
+                    ElementModificationType::TransformChanged(transform) => {
+                        self.hover_needs_update = true;
+                        self.mark_element_occlusion_dirty(modification.element_id);
+                        self.update_element_transform(modification.element_id, transform);
+                    }
+                    ElementModificationType::StartTween(req) => {
+                        if self.element_arena.contains(modification.element_id.0) {
+                            self.tweens.retain(|t| {
+                                t.element_id != modification.element_id
+                                    || t.request.kind != req.kind
+                            });
+                            self.tweens
+                                .push(ActiveTween::new(modification.element_id, *req));
+                        }
+                    }
+                    ElementModificationType::StopTween(kind) => {
+                        self.tweens.retain(|t| {
+                            t.element_id != modification.element_id
+                                || kind.is_some_and(|kind| t.request.kind != kind)
+                        });
+                    }
+                    ElementModificationType::RequestAnimation => {
+                        if self.element_arena.contains(modification.element_id.0) {
+                            self.elements_requesting_tick
+                                .insert(modification.element_id);
+                        }
+                    }
+                    ElementModificationType::SetPointerCapture(capture) => {
+                        if capture {
+                            if self
+                                .element_arena
+                                .get(modification.element_id.0)
+                                .is_some_and(|entry| entry.stack_data.visible())
+                            {
+                                self.context.pointer_capture = Some(modification.element_id);
+                            }
+                        } else if self.context.pointer_capture == Some(modification.element_id) {
+                            self.context.pointer_capture = None;
+                        }
+                    }
+                    ElementModificationType::SetAnchor(target) => {
+                        self.set_element_anchor(modification.element_id, target, res, clipboard);
+                    }
+                    ElementModificationType::StartDrag(req) => {
+                        self.start_drag(
+                            modification.element_id,
+                            req.payload,
+                            req.ghost,
+                            res,
+                            clipboard,
+                        );
+                    }
+                }
+            }
 
-        if element_entry.stack_data.animating == animating {
-            return;
-        }
+            if self.pending_drops.is_empty() {
+                break;
+            }
 
-        element_entry.stack_data.animating = animating;
+            // Elements are removed last so that the modifications which were queued
+            // before their handles were dropped are still processed. Removing an
+            // element can queue new modifications, so process the queue again.
+            for element_id in std::mem::take(&mut self.pending_drops) {
+                self.drop_element(element_id, res, clipboard);
+            }
+        }
+    }
 
-        if animating {
-            element_entry.stack_data.index_in_animating_list = self.animating_elements.len() as u32;
-            self.animating_elements.push(element_id);
-        } else {
-            let _ = self
-                .animating_elements
-                .swap_remove(element_entry.stack_data.index_in_animating_list as usize);
+    /// Sort the elements listening to pointer events by z index if needed, and
+    /// rebuild the pointer grid if the layout has changed.
+    fn update_pointer_grid(&mut self) {
+        if self.elements_listening_to_pointer_event_need_sorted {
+            self.elements_listening_to_pointer_event_need_sorted = false;
+            self.elements_listening_to_pointer_event
+                .sort_unstable_by(|a, b| a.z_index.cmp(&b.z_index));
 
-            // Update the index on the element that was swapped.
-            if let Some(swapped_element_id) = self
-                .animating_elements
-                .get(element_entry.stack_data.index_in_animating_list as usize)
-                .copied()
-            {
-                self.element_arena
-                    .get_mut(swapped_element_id.0)
-                    .as_mut()
-                    .unwrap()
-                    .stack_data
-                    .index_in_animating_list = element_entry.stack_data.index_in_animating_list;
+            for (i, cache) in self.elements_listening_to_pointer_event.iter().enumerate() {
+                if let Some(element_entry) = self.element_arena.get_mut(cache.element_id.0) {
+                    element_entry.stack_data.index_in_pointer_event_list = i as u32;
+                }
             }
+
+            self.pointer_grid.mark_dirty();
         }
+
+        self.pointer_grid
+            .update(&self.elements_listening_to_pointer_event);
     }
 
-    fn element_steal_focus(
+    /// The topmost element listening to pointer events which contains the
+    /// given point (if any).
+    fn hit_test(&mut self, pos: Point) -> Option<ElementID> {
+        self.update_occlusion();
+        self.update_pointer_grid();
+
+        self.pointer_grid
+            .candidates(pos)
+            .iter()
+            .rev()
+            .map(|index| &self.elements_listening_to_pointer_event[*index as usize])
+            .find(|cache| cache.visible_rect.is_some() && cache.contains_point(pos))
+            .map(|cache| cache.element_id)
+    }
+
+    /// Send `PointerEvent::PointerLeft` to the hovered elements which no
+    /// longer contain the given point.
+    fn unhover_elements_not_under(
         &mut self,
-        element_id: ElementID,
-        is_temporary: bool,
+        pos: Point,
         res: &mut ResourceCtx,
         clipboard: &mut Clipboard,
     ) {
-        if self.element_arena.get(element_id.0).is_none() {
-            // Element has been dropped. Do nothing and return.
-            return;
-        }
+        let unhovered_elements: SmallVec<[ElementID; 4]> = self
+            .hovered_elements
+            .keys()
+            .filter(|element_id| {
+                !self
+                    .element_arena
+                    .get(element_id.0)
+                    .is_some_and(|entry| entry.stack_data.contains_point(pos))
+            })
+            .copied()
+            .collect();
 
-        if let Some(focus_info) = &self.context.current_focus_info {
-            if focus_info.element_id == element_id {
-                // The element is already focused.
-                return;
-            }
-        }
+        for element_id in unhovered_elements.iter() {
+            self.hovered_elements.remove(element_id);
 
-        for id in self.elements_listening_to_clicked_off.iter() {
-            if let Some(element_entry) = self.element_arena.get_mut(id.0) {
+            if let Some(element_entry) = self.element_arena.get_mut(element_id.0) {
                 send_event_to_element(
-                    ElementEvent::ClickedOff,
+                    ElementEvent::Pointer(PointerEvent::PointerLeft),
                     element_entry,
-                    *id,
+                    *element_id,
                     &mut self.context,
                     res,
                     clipboard,
                 );
             }
         }
-        self.elements_listening_to_clicked_off.clear();
-
-        let prev_element_with_exclusive_focus =
-            self.context.prev_element_with_exclusive_focus.take();
+    }
 
-        // Release focus from the previously focused element.
-        if let Some(info) = &self.context.current_focus_info {
-            self.element_release_focus(info.element_id, res, clipboard);
+    /// Re-evaluate which elements are hovered after the layout has changed
+    /// without the pointer moving, so that elements which were hidden, moved
+    /// out from under the pointer, or covered by another element don't get
+    /// stuck in a hovered state.
+    fn update_hover_after_layout_change(
+        &mut self,
+        res: &mut ResourceCtx,
+        clipboard: &mut Clipboard,
+    ) {
+        let Some(pos) = self.prev_pointer_pos else {
+            return;
+        };
+        if self.context.pointer_locked || self.active_drag.is_some() {
+            return;
         }
 
-        let element_entry = self.element_arena.get_mut(element_id.0).unwrap();
-
-        self.context.current_focus_info = Some(FocusInfo {
-            element_id,
-            listens_to_pointer_inside_bounds: element_entry
-                .stack_data
-                .flags
-                .contains(ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS),
-            listens_to_pointer_outside_bounds: element_entry
-                .stack_data
-                .flags
-                .contains(ElementFlags::LISTENS_TO_POINTER_OUTSIDE_BOUNDS_WHEN_FOCUSED),
-            listens_to_text_composition: element_entry
-                .stack_data
-                .flags
-                .contains(ElementFlags::LISTENS_TO_TEXT_COMPOSITION_WHEN_FOCUSED),
-            listens_to_keys: element_entry
-                .stack_data
-                .flags
-                .contains(ElementFlags::LISTENS_TO_KEYS_WHEN_FOCUSED),
-        });
+        self.unhover_elements_not_under(pos, res, clipboard);
 
-        self.context.prev_element_with_exclusive_focus = if is_temporary {
-            prev_element_with_exclusive_focus
-        } else {
-            Some(element_id)
-        };
+        // If an element which is not hovered is now under the pointer, then it
+        // may be covering a hovered element, so run the hit test again.
+        let needs_hit_test = self
+            .elements_listening_to_pointer_event
+            .iter()
+            .any(|cache| {
+                cache.contains_point(pos) && !self.hovered_elements.contains_key(&cache.element_id)
+            });
 
-        if element_entry
-            .stack_data
-            .flags
-            .contains(ElementFlags::LISTENS_TO_FOCUS_CHANGE)
-        {
-            send_event_to_element(
-                ElementEvent::Focus(true),
-                element_entry,
-                element_id,
-                &mut self.context,
+        if needs_hit_test {
+            self.handle_pointer_event(
+                &PointerEvent::Moved {
+                    position: pos,
+                    delta: None,
+                    is_locked: false,
+                    pointer_type: self.prev_pointer_type,
+                    modifiers: self.prev_modifiers,
+                    just_entered: false,
+                },
                 res,
                 clipboard,
             );
         }
     }
 
-    fn element_release_focus(
+    fn handle_element_listen_to_click_off(&mut self, element_id: ElementID) {
+        if self.element_arena.contains(element_id.0) {
+            self.elements_listening_to_clicked_off.insert(element_id);
+        }
+    }
+
+    fn handle_element_start_hover_timeout(&mut self, element_id: ElementID) {
+        if self.element_arena.contains(element_id.0) {
+            if let Some(hover_start_instant) = self.hovered_elements.get_mut(&element_id) {
+                *hover_start_instant = Some(Instant::now());
+            }
+        }
+    }
+
+    fn handle_element_start_scroll_wheel_timeout(&mut self, element_id: ElementID) {
+        if self.element_arena.contains(element_id.0) {
+            self.elements_with_scroll_wheel_timeout
+                .insert(element_id, Some(Instant::now()));
+        }
+    }
+
+    fn handle_element_show_tooltip(
         &mut self,
         element_id: ElementID,
-        res: &mut ResourceCtx,
-        clipboard: &mut Clipboard,
+        data: TooltipData,
+        auto_hide: bool,
     ) {
-        let Some(element_entry) = self.element_arena.get_mut(element_id.0) else {
+        let Some(element_entry) = self.element_arena.get(element_id.0) else {
             // Element has been dropped. Do nothing and return.
             return;
         };
 
-        release_focus_for_element(element_id, element_entry, &mut self.context, res, clipboard);
-    }
+        self.element_with_active_tooltip = Some(ActiveTooltipInfo {
+            element_id,
+            auto_hide,
+        });
 
-    fn drop_element(
-        &mut self,
-        element_id: ElementID,
-        res: &mut ResourceCtx,
-        clipboard: &mut Clipboard,
-    ) {
-        if let Some(focus_info) = &self.context.current_focus_info {
-            if focus_info.element_id == element_id {
-                self.element_release_focus(element_id, res, clipboard);
-            }
+        if let Some(action) = self.show_tooltip_action.as_mut() {
+            let info = TooltipInfo {
+                text: data.text,
+                shortcut: data.shortcut,
+                align: data.align,
+                element_bounds: element_entry.stack_data.rect,
+                window_id: self.context.window_id,
+            };
+
+            self.context.action_sender.send((action)(info)).unwrap();
         }
+    }
 
-        let Some(mut element_entry) = self.element_arena.remove(element_id.0) else {
-            // Element has already been dropped. Do nothing and return.
+    fn handle_element_class_changed(
+        &mut self,
+        element_id: ElementID,
+        new_class: ClassID,
+        res: &mut ResourceCtx,
+        clipboard: &mut Clipboard,
+    ) {
+        let Some(element_entry) = self.element_arena.get_mut(element_id.0) else {
+            // Element has been dropped. Do nothing and return.
             return;
         };
 
-        release_focus_for_element(
+        element_entry.stack_data.class = new_class;
+
+        send_event_to_element(
+            ElementEvent::StyleChanged,
+            element_entry,
             element_id,
-            &mut element_entry,
             &mut self.context,
             res,
             clipboard,
         );
 
-        if element_entry
+        self.mark_element_dirty(element_id);
+    }
+
+    fn handle_element_custom_state_changed(
+        &mut self,
+        element_id: ElementID,
+        res: &mut ResourceCtx,
+        clipboard: &mut Clipboard,
+    ) {
+        let Some(element_entry) = self.element_arena.get_mut(element_id.0) else {
+            // Element has been dropped. Do nothing and return.
+            return;
+        };
+
+        send_event_to_element(
+            ElementEvent::CustomStateChanged,
+            element_entry,
+            element_id,
+            &mut self.context,
+            res,
+            clipboard,
+        );
+    }
+
+    fn mark_element_dirty(&mut self, element_id: ElementID) {
+        let Some(element_entry) = self.element_arena.get(element_id.0) else {
+            // Element has been dropped. Do nothing and return.
+            return;
+        };
+
+        if !element_entry
             .stack_data
             .flags
-            .contains(ElementFlags::LISTENS_TO_ON_DROPPED)
+            .contains(ElementFlags::PAINTS)
         {
-            element_entry
-                .element
-                .on_dropped(&mut self.context.action_sender);
+            return;
         }
 
-        if element_entry.stack_data.animating {
-            let _ = self
-                .animating_elements
-                .swap_remove(element_entry.stack_data.index_in_animating_list as usize);
+        // Hidden elements are marked dirty as well so that their cached
+        // primitives can be reused as-is when they become visible again.
+        self.painted_elements[element_entry.stack_data.index_in_painted_list as usize].dirty = true;
 
-            // Update the index on the element that was swapped.
-            if let Some(swapped_element_id) = self
-                .animating_elements
-                .get(element_entry.stack_data.index_in_animating_list as usize)
-                .copied()
-            {
-                self.element_arena
-                    .get_mut(swapped_element_id.0)
-                    .as_mut()
-                    .unwrap()
-                    .stack_data
-                    .index_in_animating_list = element_entry.stack_data.index_in_animating_list;
-            }
+        if element_entry.stack_data.visible() {
+            self.needs_repaint = true;
         }
+    }
 
-        if element_entry
-            .stack_data
-            .flags
-            .contains(ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS)
+    fn set_element_anchor(
+        &mut self,
+        element_id: ElementID,
+        target: Option<ElementID>,
+        res: &mut ResourceCtx,
+        clipboard: &mut Clipboard,
+    ) {
+        self.anchored_elements.retain(|_, anchored| {
+            anchored.retain(|id| *id != element_id);
+            !anchored.is_empty()
+        });
+
+        let Some(target) = target else {
+            return;
+        };
+
+        if target == element_id
+            || !self.element_arena.contains(element_id.0)
+            || !self.element_arena.contains(target.0)
         {
-            let _ = self
-                .elements_listening_to_pointer_event
-                .swap_remove(element_entry.stack_data.index_in_pointer_event_list as usize);
+            return;
+        }
 
-            // Update the index on the element that was swapped.
-            if let Some(swapped_element_id) = self
-                .elements_listening_to_pointer_event
-                .get(element_entry.stack_data.index_in_pointer_event_list as usize)
-                .map(|cache| cache.element_id)
-            {
-                self.element_arena
-                    .get_mut(swapped_element_id.0)
-                    .as_mut()
-                    .unwrap()
-                    .stack_data
-                    .index_in_pointer_event_list =
-                    element_entry.stack_data.index_in_pointer_event_list;
+        self.anchored_elements
+            .entry(target)
+            .or_default()
+            .push(element_id);
+
+        self.notify_anchored_elements(target, res, clipboard);
+    }
+
+    /// Send the current rectangle of `target` to all elements anchored to it.
+    fn notify_anchored_elements(
+        &mut self,
+        target: ElementID,
+        res: &mut ResourceCtx,
+        clipboard: &mut Clipboard,
+    ) {
+        let Some(anchored) = self.anchored_elements.get_mut(&target) else {
+            return;
+        };
+
+        let Some(target_entry) = self.element_arena.get(target.0) else {
+            self.anchored_elements.remove(&target);
+            return;
+        };
+
+        let target_rect = Rect::new(
+            target_entry
+                .stack_data
+                .offset_from_scissor_rect_origin
+                .to_point(),
+            target_entry.stack_data.unzoomed_size,
+        );
+
+        // Prune elements which have been dropped since they were anchored.
+        let element_arena = &self.element_arena;
+        anchored.retain(|id| element_arena.contains(id.0));
+
+        if anchored.is_empty() {
+            self.anchored_elements.remove(&target);
+            return;
+        }
+
+        let anchored = anchored.clone();
+        for element_id in anchored {
+            if let Some(element_entry) = self.element_arena.get_mut(element_id.0) {
+                send_event_to_element(
+                    ElementEvent::AnchorRectChanged(target_rect),
+                    element_entry,
+                    element_id,
+                    &mut self.context,
+                    res,
+                    clipboard,
+                );
             }
+        }
+    }
 
-            self.elements_listening_to_pointer_event_need_sorted = true;
+    /// Returns `true` if the rectangle has changed.
+    fn update_element_rect(
+        &mut self,
+        element_id: ElementID,
+        new_rect: Rect,
+        res: &mut ResourceCtx,
+        clipboard: &mut Clipboard,
+    ) -> bool {
+        let Some(element_entry) = self.element_arena.get_mut(element_id.0) else {
+            // Element has been dropped. Do nothing and return.
+            return false;
+        };
+
+        let pos_changed =
+            element_entry.stack_data.offset_from_scissor_rect_origin != new_rect.origin.to_vector();
+        let size_changed = element_entry.stack_data.unzoomed_size != new_rect.size;
+
+        if !(pos_changed || size_changed) {
+            return false;
         }
 
-        if element_entry
+        element_entry.stack_data.offset_from_scissor_rect_origin = new_rect.origin.to_vector();
+        element_entry.stack_data.unzoomed_size = new_rect.size;
+        element_entry.stack_data.update_layout(&self.scissor_rects);
+
+        let old_visibility = element_entry.stack_data.visible();
+        element_entry
             .stack_data
-            .flags
-            .contains(ElementFlags::PAINTS)
+            .update_visibility(&self.scissor_rects, self.window_visible);
+        let visibility_changed = element_entry.stack_data.visible() != old_visibility;
+
+        if visibility_changed && !element_entry.stack_data.visible() {
+            release_focus_for_element(element_id, element_entry, &mut self.context, res, clipboard);
+
+            if self.context.pointer_capture == Some(element_id) {
+                self.context.pointer_capture = None;
+            }
+        }
+
+        if size_changed
+            && element_entry
+                .stack_data
+                .flags
+                .contains(ElementFlags::LISTENS_TO_SIZE_CHANGE)
         {
-            let _ = self
-                .painted_elements
-                .swap_remove(element_entry.stack_data.index_in_painted_list as usize);
+            send_event_to_element(
+                ElementEvent::SizeChanged,
+                element_entry,
+                element_id,
+                &mut self.context,
+                res,
+                clipboard,
+            );
+        }
 
-            // Update the index on the element that was swapped.
-            if let Some(swapped_element_id) = self
-                .painted_elements
-                .get(element_entry.stack_data.index_in_painted_list as usize)
-                .map(|cache| cache.element_id)
-            {
-                self.element_arena
-                    .get_mut(swapped_element_id.0)
-                    .as_mut()
-                    .unwrap()
-                    .stack_data
-                    .index_in_painted_list = element_entry.stack_data.index_in_painted_list;
+        if pos_changed
+            && element_entry
+                .stack_data
+                .flags
+                .contains(ElementFlags::LISTENS_TO_POSITION_CHANGE)
+        {
+            send_event_to_element(
+                ElementEvent::PositionChanged,
+                element_entry,
+                element_id,
+                &mut self.context,
+                res,
+                clipboard,
+            );
+        }
+
+        if visibility_changed
+            && element_entry
+                .stack_data
+                .flags
+                .contains(ElementFlags::LISTENS_TO_VISIBILITY_CHANGE)
+        {
+            let event = if element_entry.stack_data.visible() {
+                ElementEvent::Shown
+            } else {
+                ElementEvent::Hidden
+            };
+
+            send_event_to_element(
+                event,
+                element_entry,
+                element_id,
+                &mut self.context,
+                res,
+                clipboard,
+            );
+        }
+
+        if let Some(lifecycle) = &mut element_entry.lifecycle {
+            lifecycle.rect_changed(
+                element_id,
+                element_entry.stack_data.rect,
+                &mut self.context.action_sender,
+            );
+
+            if visibility_changed {
+                lifecycle.visibility_changed(
+                    element_id,
+                    element_entry.stack_data.visible(),
+                    &mut self.context.action_sender,
+                );
+            }
+        }
+
+        // The cached primitives are still valid if only the position or the
+        // visibility of the element changed.
+        let mark_dirty = size_changed;
+
+        sync_element_rect_cache(
+            &element_entry.stack_data,
+            &mut self.elements_listening_to_pointer_event,
+            &mut self.pointer_grid,
+            &mut self.painted_elements,
+            mark_dirty,
+        );
+
+        if element_entry.stack_data.visible() || visibility_changed {
+            self.needs_repaint = true;
+        }
+
+        true
+    }
+
+    fn update_element_transform(
+        &mut self,
+        element_id: ElementID,
+        transform: Option<ElementTransform>,
+    ) {
+        let Some(element_entry) = self.element_arena.get_mut(element_id.0) else {
+            // Element has been dropped. Do nothing and return.
+            return;
+        };
+
+        if element_entry.stack_data.transform == transform {
+            return;
+        }
+
+        let was_visible = element_entry.stack_data.visible();
+        element_entry.stack_data.transform = transform;
+        element_entry
+            .stack_data
+            .update_visibility(&self.scissor_rects, self.window_visible);
+
+        // The rotated area may cover the same bounds as before, so always
+        // mark the element dirty so that it is included in the damaged
+        // region.
+        sync_element_rect_cache(
+            &element_entry.stack_data,
+            &mut self.elements_listening_to_pointer_event,
+            &mut self.pointer_grid,
+            &mut self.painted_elements,
+            true,
+        );
+
+        if was_visible || element_entry.stack_data.visible() {
+            self.needs_repaint = true;
+        }
+    }
+
+    fn handle_scissor_rect_changed_for_element(
+        &mut self,
+        element_id: ElementID,
+        res: &mut ResourceCtx,
+        clipboard: &mut Clipboard,
+    ) {
+        let Some(element_entry) = self.element_arena.get_mut(element_id.0) else {
+            // Element has been dropped. Do nothing and return.
+            return;
+        };
+
+        let old_size = element_entry.stack_data.rect.size;
+        element_entry.stack_data.update_layout(&self.scissor_rects);
+        // The size only changes when the zoom factor of the scissoring
+        // rectangle changes.
+        let size_changed = element_entry.stack_data.rect.size != old_size;
+
+        let old_visibility = element_entry.stack_data.visible();
+        element_entry
+            .stack_data
+            .update_visibility(&self.scissor_rects, self.window_visible);
+        let visibility_changed = element_entry.stack_data.visible() != old_visibility;
+
+        sync_element_rect_cache(
+            &element_entry.stack_data,
+            &mut self.elements_listening_to_pointer_event,
+            &mut self.pointer_grid,
+            &mut self.painted_elements,
+            size_changed,
+        );
+
+        if size_changed
+            && element_entry
+                .stack_data
+                .flags
+                .contains(ElementFlags::LISTENS_TO_SIZE_CHANGE)
+        {
+            send_event_to_element(
+                ElementEvent::SizeChanged,
+                element_entry,
+                element_id,
+                &mut self.context,
+                res,
+                clipboard,
+            );
+        }
+
+        if visibility_changed && !element_entry.stack_data.visible() {
+            release_focus_for_element(element_id, element_entry, &mut self.context, res, clipboard);
+
+            if self.context.pointer_capture == Some(element_id) {
+                self.context.pointer_capture = None;
+            }
+        }
+
+        if visibility_changed
+            && element_entry
+                .stack_data
+                .flags
+                .contains(ElementFlags::LISTENS_TO_VISIBILITY_CHANGE)
+        {
+            let event = if element_entry.stack_data.visible() {
+                ElementEvent::Shown
+            } else {
+                ElementEvent::Hidden
+            };
+
+            send_event_to_element(
+                event,
+                element_entry,
+                element_id,
+                &mut self.context,
+                res,
+                clipboard,
+            );
+        }
+
+        if visibility_changed {
+            if let Some(lifecycle) = &mut element_entry.lifecycle {
+                lifecycle.visibility_changed(
+                    element_id,
+                    element_entry.stack_data.visible(),
+                    &mut self.context.action_sender,
+                );
+            }
+        }
+
+        if element_entry.stack_data.visible() || visibility_changed {
+            self.needs_repaint = true;
+        }
+    }
+
+    fn update_element_z_index(
+        &mut self,
+        element_id: ElementID,
+        new_z_index: ZIndex,
+        res: &mut ResourceCtx,
+        clipboard: &mut Clipboard,
+    ) {
+        let Some(element_entry) = self.element_arena.get_mut(element_id.0) else {
+            // Element has been dropped. Do nothing and return.
+            return;
+        };
+
+        if element_entry.stack_data.z_index == new_z_index {
+            return;
+        }
+        element_entry.stack_data.z_index = new_z_index;
+
+        if element_entry
+            .stack_data
+            .flags
+            .contains(ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS)
+        {
+            self.elements_listening_to_pointer_event
+                [element_entry.stack_data.index_in_pointer_event_list as usize]
+                .z_index = new_z_index;
+            self.elements_listening_to_pointer_event_need_sorted = true;
+        }
+
+        if element_entry
+            .stack_data
+            .flags
+            .contains(ElementFlags::PAINTS)
+        {
+            self.painted_elements[element_entry.stack_data.index_in_painted_list as usize]
+                .z_index = new_z_index;
+        }
+
+        if element_entry
+            .stack_data
+            .flags
+            .contains(ElementFlags::LISTENS_TO_Z_INDEX_CHANGE)
+        {
+            send_event_to_element(
+                ElementEvent::ZIndexChanged,
+                element_entry,
+                element_id,
+                &mut self.context,
+                res,
+                clipboard,
+            );
+        }
+
+        // Detecting if a z index change requires a repaint or not would be very tricky,
+        // so just repaint regardless if the element is visible.
+        if element_entry.stack_data.visible() {
+            self.needs_repaint = true;
+        }
+    }
+
+    /// Reassign the z indexes of the given group of elements so that it is
+    /// stacked at the given position among the other elements whose z index
+    /// lies in `band`.
+    ///
+    /// The z indexes of the group relative to each other are kept. If there
+    /// is no room left in the band, then the z indexes of the other elements
+    /// in the band are compacted (keeping their order).
+    ///
+    /// Returns `true` if any z index was changed.
+    pub fn restack_elements(
+        &mut self,
+        group: &[ElementID],
+        band: RangeInclusive<ZIndex>,
+        position: StackPosition,
+        res: &mut ResourceCtx,
+        clipboard: &mut Clipboard,
+    ) -> bool {
+        let band_start = *band.start() as i64;
+        let band_end = *band.end() as i64;
+
+        let group_z: Vec<(ElementID, i64)> = group
+            .iter()
+            .filter_map(|id| {
+                self.element_arena
+                    .get(id.0)
+                    .map(|entry| (*id, entry.stack_data.z_index as i64))
+            })
+            .collect();
+
+        let (Some(group_min), Some(group_max)) = (
+            group_z.iter().map(|(_, z)| *z).min(),
+            group_z.iter().map(|(_, z)| *z).max(),
+        ) else {
+            return false;
+        };
+        let span = group_max - group_min;
+
+        // The distinct z indexes of the other elements in the band.
+        let mut layers: Vec<i64> = self
+            .element_arena
+            .iter()
+            .filter(|(index, _)| !group.contains(&ElementID(*index)))
+            .map(|(_, entry)| entry.stack_data.z_index as i64)
+            .filter(|z| (band_start..=band_end).contains(z))
+            .collect();
+        layers.sort_unstable();
+        layers.dedup();
+
+        // The other layers which should end up below the group.
+        let num_below = match position {
+            StackPosition::Front => layers.len(),
+            StackPosition::Back => 0,
+            StackPosition::Above(other) => {
+                if group.contains(&other) {
+                    return false;
+                }
+                let Some(other_z) = self
+                    .element_arena
+                    .get(other.0)
+                    .map(|entry| entry.stack_data.z_index as i64)
+                else {
+                    return false;
+                };
+                if !(band_start..=band_end).contains(&other_z) {
+                    return false;
+                }
+
+                layers.partition_point(|z| *z <= other_z)
+            }
+        };
+        let (below, above) = layers.split_at(num_below);
+
+        let already_stacked = group_min >= band_start
+            && group_max <= band_end
+            && below.last().map_or(true, |z| *z < group_min)
+            && above.first().map_or(true, |z| *z > group_max);
+        if already_stacked {
+            return false;
+        }
+
+        if span + 1 + layers.len() as i64 > band_end - band_start + 1 {
+            log::warn!("Yarrow: not enough z indexes in the band to restack elements");
+            return false;
+        }
+
+        // Try to fit the group into the gap between the layers below and
+        // above it before compacting the whole band.
+        let gap_start = below.last().map_or(band_start, |z| z + 1);
+        let gap_end = above.first().map_or(band_end, |z| z - 1);
+
+        let mut new_z: Vec<(ElementID, i64)> = Vec::new();
+        let new_group_min = if gap_end - gap_start >= span {
+            match position {
+                StackPosition::Back => gap_end - span,
+                _ => gap_start,
+            }
+        } else {
+            // Compact the layers of the band, leaving room for the group.
+            let layer_z = |i: usize| {
+                if i < num_below {
+                    band_start + i as i64
+                } else {
+                    band_start + i as i64 + span + 1
+                }
+            };
+
+            for (index, entry) in self.element_arena.iter() {
+                let element_id = ElementID(index);
+                if group.contains(&element_id) {
+                    continue;
+                }
+
+                if let Ok(i) = layers.binary_search(&(entry.stack_data.z_index as i64)) {
+                    new_z.push((element_id, layer_z(i)));
+                }
+            }
+
+            band_start + num_below as i64
+        };
+
+        new_z.extend(
+            group_z
+                .iter()
+                .map(|(id, z)| (*id, new_group_min + (z - group_min))),
+        );
+
+        let mut changed = false;
+        for (element_id, z) in new_z {
+            let z = z as ZIndex;
+            let Some(entry) = self.element_arena.get(element_id.0) else {
+                continue;
+            };
+            if entry.stack_data.z_index != z {
+                // Keep the z index cached in the element's handle in sync.
+                entry.cached.z_index.set(z);
+                self.update_element_z_index(element_id, z, res, clipboard);
+                changed = true;
+            }
+        }
+
+        changed
+    }
+
+    fn update_element_opaque(&mut self, element_id: ElementID, opaque: bool) {
+        let Some(element_entry) = self.element_arena.get_mut(element_id.0) else {
+            // Element has been dropped. Do nothing and return.
+            return;
+        };
+
+        let flags = &mut element_entry.stack_data.flags;
+        if flags.contains(ElementFlags::OPAQUE) == opaque {
+            return;
+        }
+        flags.set(ElementFlags::OPAQUE, opaque);
+
+        if flags.contains(ElementFlags::PAINTS) {
+            if opaque {
+                self.num_opaque_elements += 1;
+            } else {
+                self.num_opaque_elements -= 1;
+            }
+        }
+
+        self.mark_element_occlusion_dirty(element_id);
+    }
+
+    fn update_element_manually_hidden(
+        &mut self,
+        element_id: ElementID,
+        manually_hidden: bool,
+        res: &mut ResourceCtx,
+        clipboard: &mut Clipboard,
+    ) {
+        let Some(element_entry) = self.element_arena.get_mut(element_id.0) else {
+            // Element has been dropped. Do nothing and return.
+            return;
+        };
+
+        if element_entry.stack_data.manually_hidden == manually_hidden {
+            return;
+        }
+
+        element_entry.stack_data.manually_hidden = manually_hidden;
+
+        let old_visibility = element_entry.stack_data.visible();
+        element_entry
+            .stack_data
+            .update_visibility(&self.scissor_rects, self.window_visible);
+        let visibility_changed = element_entry.stack_data.visible() != old_visibility;
+
+        if !visibility_changed {
+            return;
+        }
+
+        sync_element_rect_cache(
+            &element_entry.stack_data,
+            &mut self.elements_listening_to_pointer_event,
+            &mut self.pointer_grid,
+            &mut self.painted_elements,
+            false,
+        );
+
+        if visibility_changed && !element_entry.stack_data.visible() {
+            release_focus_for_element(element_id, element_entry, &mut self.context, res, clipboard);
+
+            if self.context.pointer_capture == Some(element_id) {
+                self.context.pointer_capture = None;
+            }
+        }
+
+        if element_entry
+            .stack_data
+            .flags
+            .contains(ElementFlags::LISTENS_TO_VISIBILITY_CHANGE)
+        {
+            let event = if element_entry.stack_data.visible() {
+                ElementEvent::Shown
+            } else {
+                ElementEvent::Hidden
+            };
+
+            send_event_to_element(
+                event,
+                element_entry,
+                element_id,
+                &mut self.context,
+                res,
+                clipboard,
+            );
+        }
+
+        if let Some(lifecycle) = &mut element_entry.lifecycle {
+            lifecycle.visibility_changed(
+                element_id,
+                element_entry.stack_data.visible(),
+                &mut self.context.action_sender,
+            );
+        }
+
+        self.needs_repaint = true;
+    }
+
+    fn set_element_animating(&mut self, element_id: ElementID, animating: bool) {
+        let Some(element_entry) = self.element_arena.get_mut(element_id.0) else {
+            // Element has been dropped. Do nothing and return.
+            return;
+        };
+
+        if element_entry.stack_data.animating == animating {
+            return;
+        }
+
+        element_entry.stack_data.animating = animating;
+
+        if animating {
+            element_entry.stack_data.index_in_animating_list = self.animating_elements.len() as u32;
+            self.animating_elements.push(element_id);
+        } else {
+            let _ = self
+                .animating_elements
+                .swap_remove(element_entry.stack_data.index_in_animating_list as usize);
+
+            // Update the index on the element that was swapped.
+            if let Some(swapped_element_id) = self
+                .animating_elements
+                .get(element_entry.stack_data.index_in_animating_list as usize)
+                .copied()
+            {
+                self.element_arena
+                    .get_mut(swapped_element_id.0)
+                    .as_mut()
+                    .unwrap()
+                    .stack_data
+                    .index_in_animating_list = element_entry.stack_data.index_in_animating_list;
+            }
+        }
+    }
+
+    fn element_steal_focus(
+        &mut self,
+        element_id: ElementID,
+        is_temporary: bool,
+        res: &mut ResourceCtx,
+        clipboard: &mut Clipboard,
+    ) {
+        if self.element_arena.get(element_id.0).is_none() {
+            // Element has been dropped. Do nothing and return.
+            return;
+        }
+
+        if let Some(focus_info) = &self.context.current_focus_info {
+            if focus_info.element_id == element_id {
+                // The element is already focused.
+                return;
+            }
+        }
+
+        // Focus given by means other than keyboard navigation (i.e. clicking
+        // on an element) does not show the view focus ring.
+        self.set_focus_visible(false);
+
+        for id in self.elements_listening_to_clicked_off.iter() {
+            if let Some(element_entry) = self.element_arena.get_mut(id.0) {
+                send_event_to_element(
+                    ElementEvent::ClickedOff,
+                    element_entry,
+                    *id,
+                    &mut self.context,
+                    res,
+                    clipboard,
+                );
+            }
+        }
+        self.elements_listening_to_clicked_off.clear();
+
+        let prev_element_with_exclusive_focus =
+            self.context.prev_element_with_exclusive_focus.take();
+
+        // Release focus from the previously focused element.
+        if let Some(info) = &self.context.current_focus_info {
+            self.element_release_focus(info.element_id, res, clipboard);
+        }
+
+        let element_entry = self.element_arena.get_mut(element_id.0).unwrap();
+
+        self.context.current_focus_info = Some(FocusInfo {
+            element_id,
+            listens_to_pointer_inside_bounds: element_entry
+                .stack_data
+                .flags
+                .contains(ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS),
+            listens_to_pointer_outside_bounds: element_entry
+                .stack_data
+                .flags
+                .contains(ElementFlags::LISTENS_TO_POINTER_OUTSIDE_BOUNDS_WHEN_FOCUSED),
+            listens_to_text_composition: element_entry
+                .stack_data
+                .flags
+                .contains(ElementFlags::LISTENS_TO_TEXT_COMPOSITION_WHEN_FOCUSED),
+            listens_to_keys: element_entry
+                .stack_data
+                .flags
+                .contains(ElementFlags::LISTENS_TO_KEYS_WHEN_FOCUSED),
+        });
+
+        self.context.prev_element_with_exclusive_focus = if is_temporary {
+            prev_element_with_exclusive_focus
+        } else {
+            Some(element_id)
+        };
+
+        if element_entry
+            .stack_data
+            .flags
+            .contains(ElementFlags::LISTENS_TO_FOCUS_CHANGE)
+        {
+            send_event_to_element(
+                ElementEvent::Focus(true),
+                element_entry,
+                element_id,
+                &mut self.context,
+                res,
+                clipboard,
+            );
+        }
+    }
+
+    fn update_element_focusable(
+        &mut self,
+        element_id: ElementID,
+        focusable: bool,
+        res: &mut ResourceCtx,
+        clipboard: &mut Clipboard,
+    ) {
+        let Some(element_entry) = self.element_arena.get_mut(element_id.0) else {
+            return;
+        };
+
+        element_entry
+            .stack_data
+            .flags
+            .set(ElementFlags::FOCUSABLE, focusable);
+
+        let has_focus = self
+            .context
+            .current_focus_info
+            .as_ref()
+            .is_some_and(|info| info.element_id == element_id);
+
+        if !focusable && has_focus {
+            self.element_release_focus(element_id, res, clipboard);
+        }
+    }
+
+    fn element_release_focus(
+        &mut self,
+        element_id: ElementID,
+        res: &mut ResourceCtx,
+        clipboard: &mut Clipboard,
+    ) {
+        let Some(element_entry) = self.element_arena.get_mut(element_id.0) else {
+            // Element has been dropped. Do nothing and return.
+            return;
+        };
+
+        release_focus_for_element(element_id, element_entry, &mut self.context, res, clipboard);
+
+        self.set_focus_visible(false);
+    }
+
+    fn drop_element(
+        &mut self,
+        element_id: ElementID,
+        res: &mut ResourceCtx,
+        clipboard: &mut Clipboard,
+    ) {
+        if let Some(focus_info) = &self.context.current_focus_info {
+            if focus_info.element_id == element_id {
+                self.element_release_focus(element_id, res, clipboard);
+            }
+        }
+
+        if self.context.pointer_capture == Some(element_id) {
+            self.context.pointer_capture = None;
+        }
+
+        self.tweens.retain(|t| t.element_id != element_id);
+        self.remove_render_pass(element_id);
+        self.anchored_elements.remove(&element_id);
+
+        let Some(mut element_entry) = self.element_arena.remove(element_id.0) else {
+            // Element has already been dropped. Do nothing and return.
+            return;
+        };
+
+        release_focus_for_element(
+            element_id,
+            &mut element_entry,
+            &mut self.context,
+            res,
+            clipboard,
+        );
+
+        if element_entry
+            .stack_data
+            .flags
+            .contains(ElementFlags::LISTENS_TO_ON_DROPPED)
+        {
+            self.context
+                .action_sender
+                .set_source_element(Some(element_id));
+            element_entry
+                .element
+                .on_dropped(&mut self.context.action_sender);
+            self.context.action_sender.set_source_element(None);
+        }
+
+        if let Some(lifecycle) = &mut element_entry.lifecycle {
+            lifecycle.unmounted(element_id, &mut self.context.action_sender);
+        }
+
+        if element_entry.stack_data.animating {
+            let _ = self
+                .animating_elements
+                .swap_remove(element_entry.stack_data.index_in_animating_list as usize);
+
+            // Update the index on the element that was swapped.
+            if let Some(swapped_element_id) = self
+                .animating_elements
+                .get(element_entry.stack_data.index_in_animating_list as usize)
+                .copied()
+            {
+                self.element_arena
+                    .get_mut(swapped_element_id.0)
+                    .as_mut()
+                    .unwrap()
+                    .stack_data
+                    .index_in_animating_list = element_entry.stack_data.index_in_animating_list;
+            }
+        }
+
+        if element_entry
+            .stack_data
+            .flags
+            .contains(ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS)
+        {
+            let _ = self
+                .elements_listening_to_pointer_event
+                .swap_remove(element_entry.stack_data.index_in_pointer_event_list as usize);
+
+            // Update the index on the element that was swapped.
+            if let Some(swapped_element_id) = self
+                .elements_listening_to_pointer_event
+                .get(element_entry.stack_data.index_in_pointer_event_list as usize)
+                .map(|cache| cache.element_id)
+            {
+                self.element_arena
+                    .get_mut(swapped_element_id.0)
+                    .as_mut()
+                    .unwrap()
+                    .stack_data
+                    .index_in_pointer_event_list =
+                    element_entry.stack_data.index_in_pointer_event_list;
+            }
+
+            self.elements_listening_to_pointer_event_need_sorted = true;
+        }
+
+        if element_entry
+            .stack_data
+            .flags
+            .contains(ElementFlags::PAINTS | ElementFlags::OPAQUE)
+        {
+            self.num_opaque_elements -= 1;
+        }
+        if let Some(visible_rect) = element_entry.stack_data.visible_rect {
+            self.occlusion_dirty.add_region(visible_rect);
+        }
+
+        if element_entry
+            .stack_data
+            .flags
+            .contains(ElementFlags::PAINTS)
+        {
+            let removed_cache = self
+                .painted_elements
+                .swap_remove(element_entry.stack_data.index_in_painted_list as usize);
+
+            if let Some(drawn) = removed_cache.drawn {
+                union_damage(&mut self.pending_damage, drawn.visible_rect);
+            }
+
+            // Update the index on the element that was swapped.
+            if let Some(swapped_element_id) = self
+                .painted_elements
+                .get(element_entry.stack_data.index_in_painted_list as usize)
+                .map(|cache| cache.element_id)
+            {
+                self.element_arena
+                    .get_mut(swapped_element_id.0)
+                    .as_mut()
+                    .unwrap()
+                    .stack_data
+                    .index_in_painted_list = element_entry.stack_data.index_in_painted_list;
+            }
+        }
+
+        self.scissor_rects[usize::from(element_entry.stack_data.scissor_rect_index)]
+            .remove_element(&element_entry.stack_data, &mut self.element_arena);
+
+        if let Some(info) = &self.element_with_active_tooltip {
+            if element_id == info.element_id {
+                self.element_with_active_tooltip = None;
+
+                if let Some(action) = self.hide_tooltip_action.as_mut() {
+                    self.context.action_sender.send((action)()).unwrap();
+                }
+            }
+        }
+
+        self.hovered_elements.remove(&element_id);
+        self.elements_listening_to_all_keys.remove(&element_id);
+        self.elements_requesting_tick.remove(&element_id);
+
+        if let Some(key) = &element_entry.key {
+            if self.keyed_elements.get(key) == Some(&element_id) {
+                self.keyed_elements.remove(key);
+            }
+        }
+        self.elements_with_scroll_wheel_timeout.remove(&element_id);
+
+        if element_entry.stack_data.visible() {
+            self.needs_repaint = true;
+        }
+    }
+
+    pub fn mark_all_dirty(&mut self) {
+        for cache in self.painted_elements.iter_mut() {
+            cache.dirty = true;
+        }
+
+        self.full_redraw_needed = true;
+        self.needs_repaint = true;
+    }
+
+    pub fn clear_color(&self) -> PackedSrgb {
+        self.clear_color
+    }
+
+    pub fn set_clear_color(&mut self, color: PackedSrgb) {
+        if self.clear_color != color {
+            self.clear_color = color;
+            self.full_redraw_needed = true;
+            self.needs_repaint = true;
+        }
+    }
+
+    #[allow(unused)]
+    pub fn render<P: FnOnce()>(
+        &mut self,
+        surface: &wgpu::Surface,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        texture_format: wgpu::TextureFormat,
+        multisample: wgpu::MultisampleState,
+        vg: &mut rootvg::Canvas,
+        pre_present_notify: P,
+        res: &mut ResourceCtx,
+    ) -> Result<(), wgpu::SurfaceError> {
+        if !self.needs_repaint {
+            return Ok(());
+        }
+
+        self.update_occlusion();
+
+        if self.cvd_simulation.is_some() || self.needs_target_texture() {
+            return self.render_with_cvd_simulation(
+                self.cvd_simulation,
+                surface,
+                device,
+                queue,
+                texture_format,
+                multisample,
+                vg,
+                pre_present_notify,
+                res,
+            );
+        }
+
+        if !self.partial_redraw {
+            // Set up the frame.
+            let frame = surface.get_current_texture()?;
+            let view = frame
+                .texture
+                .create_view(&wgpu::TextureViewDescriptor::default());
+
+            let encoder = self.encode_frame(
+                &view,
+                Some(&frame.texture),
+                FrameDamage::Full,
+                device,
+                queue,
+                texture_format,
+                multisample,
+                vg,
+                res,
+            );
+
+            pre_present_notify();
+
+            // Submit the commands and present the frame.
+            let present_start = Instant::now();
+            queue.submit(Some(encoder.finish()));
+            frame.present();
+            self.stats.present_time = present_start.elapsed();
+
+            self.needs_repaint = false;
+            self.finish_frame_stats(res);
+
+            return Ok(());
+        }
+
+        let mut damage = self.take_damage(res);
+        if damage == FrameDamage::None {
+            // Nothing visible has changed, so skip this frame entirely.
+            self.needs_repaint = false;
+            return Ok(());
+        }
+
+        // Set up the frame.
+        let frame = match surface.get_current_texture() {
+            Ok(frame) => frame,
+            Err(e) => {
+                self.full_redraw_needed = true;
+                return Err(e);
+            }
+        };
+
+        let encoder = if frame
+            .texture
+            .usage()
+            .contains(wgpu::TextureUsages::COPY_DST)
+        {
+            let size = frame.texture.size();
+
+            let recreate_backbuffer = self.backbuffer.as_ref().map_or(true, |backbuffer| {
+                backbuffer.size() != size || backbuffer.format() != frame.texture.format()
+            });
+            if recreate_backbuffer {
+                self.backbuffer = Some(device.create_texture(&wgpu::TextureDescriptor {
+                    label: Some("yarrow backbuffer"),
+                    size,
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: wgpu::TextureDimension::D2,
+                    format: frame.texture.format(),
+                    usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+                    view_formats: &[],
+                }));
+
+                damage = FrameDamage::Full;
+            }
+
+            let backbuffer = self.backbuffer.take().unwrap();
+            let view = backbuffer.create_view(&wgpu::TextureViewDescriptor::default());
+
+            let mut encoder = self.encode_frame(
+                &view,
+                Some(&backbuffer),
+                damage,
+                device,
+                queue,
+                texture_format,
+                multisample,
+                vg,
+                res,
+            );
+
+            encoder.copy_texture_to_texture(
+                backbuffer.as_image_copy(),
+                frame.texture.as_image_copy(),
+                size,
+            );
+
+            self.backbuffer = Some(backbuffer);
+
+            encoder
+        } else {
+            // The surface doesn't support being copied into, so fall back to
+            // redrawing the whole frame.
+            self.backbuffer = None;
+
+            let view = frame
+                .texture
+                .create_view(&wgpu::TextureViewDescriptor::default());
+
+            self.encode_frame(
+                &view,
+                Some(&frame.texture),
+                FrameDamage::Full,
+                device,
+                queue,
+                texture_format,
+                multisample,
+                vg,
+                res,
+            )
+        };
+
+        pre_present_notify();
+
+        // Submit the commands and present the frame.
+        let present_start = Instant::now();
+        queue.submit(Some(encoder.finish()));
+        frame.present();
+        self.stats.present_time = present_start.elapsed();
+
+        self.needs_repaint = false;
+        self.finish_frame_stats(res);
+
+        Ok(())
+    }
+
+    /// Draw the given render pass inside the rect of the given placeholder
+    /// element. This replaces any render pass previously added for that
+    /// element.
+    ///
+    /// The render pass is removed when the placeholder element is dropped.
+    pub fn add_render_pass(&mut self, element_id: ElementID, pass: Box<dyn CustomRenderPass>) {
+        if !self.element_arena.contains(element_id.0) {
+            return;
+        }
+
+        self.render_passes.retain(|e| e.element_id != element_id);
+        self.render_passes
+            .push(RenderPassEntry::new(element_id, pass));
+
+        self.full_redraw_needed = true;
+        self.needs_repaint = true;
+    }
+
+    /// Remove the render pass of the given placeholder element.
+    ///
+    /// Returns `true` if the element had a render pass.
+    pub fn remove_render_pass(&mut self, element_id: ElementID) -> bool {
+        let len = self.render_passes.len();
+        self.render_passes.retain(|e| e.element_id != element_id);
+
+        if self.render_passes.len() == len {
+            return false;
+        }
+
+        self.full_redraw_needed = true;
+        self.needs_repaint = true;
+        true
+    }
+
+    /// The visible render passes, sorted by the z index of their placeholder
+    /// elements, along with the z index and visible rect of each.
+    fn visible_render_passes(&self) -> Vec<(usize, ZIndex, Rect)> {
+        let mut passes: Vec<(usize, ZIndex, Rect)> = self
+            .render_passes
+            .iter()
+            .enumerate()
+            .filter_map(|(i, entry)| {
+                let stack_data = &self.element_arena.get(entry.element_id.0)?.stack_data;
+                stack_data
+                    .visible_rect
+                    .map(|visible_rect| (i, stack_data.z_index, visible_rect))
+            })
+            .collect();
+
+        passes.sort_by_key(|(_, z_index, _)| *z_index);
+        passes
+    }
+
+    /// Whether a visible render pass needs to copy from the texture the frame
+    /// is rendered into.
+    fn needs_target_texture(&self) -> bool {
+        self.visible_render_passes()
+            .iter()
+            .any(|(i, _, _)| self.render_passes[*i].pass.needs_target_texture())
+    }
+
+    /// The statistics of the last frame which was presented.
+    pub fn frame_stats(&self) -> &FrameStats {
+        &self.last_frame_stats
+    }
+
+    pub fn show_stats_overlay(&self) -> bool {
+        self.show_stats_overlay
+    }
+
+    pub fn set_show_stats_overlay(&mut self, show: bool) {
+        if self.show_stats_overlay != show {
+            self.show_stats_overlay = show;
+            self.full_redraw_needed = true;
+            self.needs_repaint = true;
+        }
+    }
+
+    pub fn view_focus_ring_enabled(&self) -> bool {
+        self.view_focus_ring_enabled
+    }
+
+    pub fn set_view_focus_ring_enabled(&mut self, enabled: bool) {
+        if self.view_focus_ring_enabled != enabled {
+            self.view_focus_ring_enabled = enabled;
+            self.needs_repaint = true;
+        }
+    }
+
+    /// The bounds of the focused element and the index of its scissoring
+    /// rectangle, if the view should draw a focus ring around it.
+    fn view_focus_ring_target(&self) -> Option<(Rect, usize)> {
+        if !(self.view_focus_ring_enabled && self.focus_visible) {
+            return None;
+        }
+
+        let focus_info = self.context.current_focus_info.as_ref()?;
+        let stack_data = &self.element_arena.get(focus_info.element_id.0)?.stack_data;
+
+        if !stack_data.visible()
+            || !stack_data.flags.contains(ElementFlags::FOCUSABLE)
+            || stack_data
+                .flags
+                .contains(ElementFlags::DRAWS_OWN_FOCUS_RING)
+        {
+            return None;
+        }
+
+        Some((stack_data.rect, stack_data.scissor_rect_index))
+    }
+
+    /// The bounds of the focus ring drawn by the view (if any).
+    fn view_focus_ring_bounds(&self, res: &ResourceCtx) -> Option<Rect> {
+        let ring = res.style_system.focus_ring()?;
+        let (rect, _) = self.view_focus_ring_target()?;
+
+        let o = ring.offset + ring.width;
+        Some(rect.inflate(o, o))
+    }
+
+    /// Render a full frame into an intermediate texture, and then draw it to
+    /// the surface with the color vision deficiency simulation (if any)
+    /// applied.
+    fn render_with_cvd_simulation<P: FnOnce()>(
+        &mut self,
+        deficiency: Option<ColorVisionDeficiency>,
+        surface: &wgpu::Surface,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        texture_format: wgpu::TextureFormat,
+        multisample: wgpu::MultisampleState,
+        vg: &mut rootvg::Canvas,
+        pre_present_notify: P,
+        res: &mut ResourceCtx,
+    ) -> Result<(), wgpu::SurfaceError> {
+        let frame = surface.get_current_texture()?;
+        let frame_view = frame
+            .texture
+            .create_view(&wgpu::TextureViewDescriptor::default());
+
+        let mut filter = match self.cvd_filter.take() {
+            Some(filter) if filter.format() == frame.texture.format() => filter,
+            _ => CvdFilter::new(device, frame.texture.format()),
+        };
+
+        let source_view = filter.source_view(device, frame.texture.size());
+
+        let mut encoder = self.encode_frame(
+            &source_view,
+            filter.source_texture(),
+            FrameDamage::Full,
+            device,
+            queue,
+            texture_format,
+            multisample,
+            vg,
+            res,
+        );
+
+        filter.encode(&mut encoder, queue, &frame_view, deficiency);
+        self.cvd_filter = Some(filter);
+
+        pre_present_notify();
+
+        // Submit the commands and present the frame.
+        let present_start = Instant::now();
+        queue.submit(Some(encoder.finish()));
+        frame.present();
+        self.stats.present_time = present_start.elapsed();
+
+        // The backbuffer used for partial redraws was not updated.
+        self.pending_damage = None;
+        self.full_redraw_needed = true;
+
+        self.needs_repaint = false;
+        self.finish_frame_stats(res);
+
+        Ok(())
+    }
+
+    pub fn announce(&mut self, announcement: Announcement) {
+        self.context.announcements.push(announcement);
+    }
+
+    pub fn take_announcements(&mut self) -> Vec<Announcement> {
+        std::mem::take(&mut self.context.announcements)
+    }
+
+    pub fn color_vision_simulation(&self) -> Option<ColorVisionDeficiency> {
+        self.cvd_simulation
+    }
+
+    pub fn set_color_vision_simulation(&mut self, deficiency: Option<ColorVisionDeficiency>) {
+        if self.cvd_simulation != deficiency {
+            self.cvd_simulation = deficiency;
+
+            if deficiency.is_none() {
+                self.cvd_filter = None;
+            }
+
+            self.full_redraw_needed = true;
+            self.needs_repaint = true;
+        }
+    }
+
+    fn finish_frame_stats(&mut self, res: &ResourceCtx) {
+        self.stats.num_elements = self.element_arena.len();
+        self.stats.num_painted_elements = self.painted_elements.len();
+        self.stats.num_cached_text_layouts = res.text_layout_cache.len();
+
+        self.last_frame_stats = std::mem::take(&mut self.stats);
+
+        if self.show_stats_overlay {
+            // Keep the overlay up to date.
+            self.needs_repaint = true;
+        }
+
+        let animating_render_pass = self
+            .visible_render_passes()
+            .iter()
+            .any(|(i, _, _)| self.render_passes[*i].pass.is_animating());
+        if animating_render_pass {
+            self.needs_repaint = true;
+        }
+    }
+
+    /// Render the view into the given texture (i.e. an offscreen texture).
+    ///
+    /// Unlike `ElementSystem::render`, this always renders a full frame even
+    /// if nothing has changed. This does not count as presenting a frame, so
+    /// any pending changes will still be drawn to the window on the next call
+    /// to `ElementSystem::render`.
+    pub fn render_to_texture(
+        &mut self,
+        view: &wgpu::TextureView,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        texture_format: wgpu::TextureFormat,
+        multisample: wgpu::MultisampleState,
+        vg: &mut rootvg::Canvas,
+        res: &mut ResourceCtx,
+    ) {
+        self.update_occlusion();
+
+        if self.partial_redraw {
+            // Carry the damage over to the next presented frame.
+            match self.take_damage(res) {
+                FrameDamage::None => {}
+                FrameDamage::Region(rect) => union_damage(&mut self.pending_damage, rect),
+                FrameDamage::Full => self.full_redraw_needed = true,
+            }
+        }
+
+        let encoder = self.encode_frame(
+            view,
+            None,
+            FrameDamage::Full,
+            device,
+            queue,
+            texture_format,
+            multisample,
+            vg,
+            res,
+        );
+
+        let present_start = Instant::now();
+        queue.submit(Some(encoder.finish()));
+        self.stats.present_time = present_start.elapsed();
+
+        self.finish_frame_stats(res);
+    }
+
+    /// Mark the current visible rectangle of the given element as a region in
+    /// which occlusion needs to be updated.
+    fn mark_element_occlusion_dirty(&mut self, element_id: ElementID) {
+        if let Some(visible_rect) = self
+            .element_arena
+            .get(element_id.0)
+            .and_then(|entry| entry.stack_data.visible_rect)
+        {
+            self.occlusion_dirty.add_region(visible_rect);
+        }
+    }
+
+    /// Recompute which elements are completely covered by a visible opaque
+    /// element with a higher z index.
+    ///
+    /// This is only done when the layout has changed since the last time it
+    /// was computed, and only for the elements in the changed region.
+    fn update_occlusion(&mut self) {
+        // `None` means that the whole window needs to be updated.
+        let dirty_region = match std::mem::replace(&mut self.occlusion_dirty, FrameDamage::None) {
+            FrameDamage::None => return,
+            FrameDamage::Region(region) => Some(region),
+            FrameDamage::Full => None,
+        };
+
+        // Only elements which overlap the changed region can have become
+        // occluded or unoccluded, since an occluder must overlap the element
+        // it covers.
+        let in_dirty_region = |visible_rect: &Rect| {
+            dirty_region.map_or(true, |region| visible_rect.intersects(&region))
+        };
+
+        // Elements listening to pointer events which have moved have already
+        // been marked in the pointer grid, so only a change in occlusion needs
+        // to update it here.
+        if self.num_opaque_elements == 0 && !self.any_occluded {
+            return;
+        }
+
+        let occluders: Vec<(ZIndex, Rect)> = self
+            .painted_elements
+            .iter()
+            .filter(|cache| cache.visible)
+            .filter_map(|cache| {
+                let stack_data = &self.element_arena.get(cache.element_id.0)?.stack_data;
+
+                // A transformed element does not cover all of its visible
+                // rectangle.
+                if stack_data.flags.contains(ElementFlags::OPAQUE)
+                    && stack_data.opacity >= 1.0
+                    && stack_data.transform.is_none()
+                {
+                    stack_data
+                        .visible_rect
+                        .filter(&in_dirty_region)
+                        .map(|visible_rect| (cache.z_index, visible_rect))
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        // Elements which are not visible are never occluded, so always update
+        // them in case they were occluded before they were hidden.
+        let needs_test =
+            |visible_rect: Option<Rect>| visible_rect.map_or(true, |r| in_dirty_region(&r));
+
+        let is_occluded = |z_index: ZIndex, visible_rect: Option<Rect>| -> bool {
+            visible_rect.is_some_and(|visible_rect| {
+                occluders.iter().any(|(occluder_z_index, occluder_rect)| {
+                    *occluder_z_index > z_index && occluder_rect.contains_rect(&visible_rect)
+                })
+            })
+        };
+
+        for cache in self.painted_elements.iter_mut() {
+            let visible_rect = if cache.visible {
+                self.element_arena
+                    .get(cache.element_id.0)
+                    .and_then(|entry| entry.stack_data.visible_rect)
+            } else {
+                None
+            };
+
+            if !needs_test(visible_rect) {
+                continue;
+            }
+
+            let occluded = is_occluded(cache.z_index, visible_rect);
+            if cache.occluded != occluded {
+                cache.occluded = occluded;
+                self.needs_repaint = true;
+            }
+        }
+
+        for (i, cache) in self
+            .elements_listening_to_pointer_event
+            .iter_mut()
+            .enumerate()
+        {
+            if !needs_test(cache.visible_rect) {
+                continue;
+            }
+
+            let occluded = is_occluded(cache.z_index, cache.visible_rect);
+            if cache.occluded != occluded {
+                cache.occluded = occluded;
+                self.pointer_grid.mark_element_dirty(i as u32);
+            }
+        }
+
+        self.any_occluded = self.painted_elements.iter().any(|cache| cache.occluded)
+            || self
+                .elements_listening_to_pointer_event
+                .iter()
+                .any(|cache| cache.occluded);
+    }
+
+    /// Compute the region of the window which has changed since the last
+    /// frame, and mark the current state of all elements as drawn.
+    fn take_damage(&mut self, res: &ResourceCtx) -> FrameDamage {
+        let mut full = std::mem::take(&mut self.full_redraw_needed);
+        let mut damage = self.pending_damage.take();
+
+        let focus_ring = self.view_focus_ring_bounds(res);
+        if focus_ring != self.focus_ring_drawn {
+            if let Some(drawn) = self.focus_ring_drawn {
+                union_damage(&mut damage, drawn);
+            }
+            if let Some(focus_ring) = focus_ring {
+                union_damage(&mut damage, focus_ring);
             }
         }
+        self.focus_ring_drawn = focus_ring;
+
+        // The drag ghost can be drawn anywhere in the window.
+        let has_drag_ghost = self
+            .active_drag
+            .as_ref()
+            .is_some_and(|drag| drag.ghost.is_some());
+        if has_drag_ghost || self.drag_ghost_drawn || self.show_stats_overlay {
+            full = true;
+        }
 
-        self.scissor_rects[usize::from(element_entry.stack_data.scissor_rect_index)]
-            .remove_element(&element_entry.stack_data, &mut self.element_arena);
+        // The contents drawn by custom render passes are not tracked, so
+        // always redraw the whole frame while one is visible.
+        if !self.visible_render_passes().is_empty() {
+            full = true;
+        }
+        self.drag_ghost_drawn = has_drag_ghost;
 
-        if let Some(info) = &self.element_with_active_tooltip {
-            if element_id == info.element_id {
-                self.element_with_active_tooltip = None;
+        for cache in self.painted_elements.iter_mut() {
+            let current = if cache.visible && !cache.occluded {
+                self.element_arena
+                    .get(cache.element_id.0)
+                    .and_then(|entry| entry.stack_data.visible_rect)
+                    .map(|visible_rect| DrawnBounds {
+                        visible_rect,
+                        offset: cache.offset,
+                        z_index: cache.z_index,
+                        scissor_rect_index: cache.scissor_rect_index,
+                    })
+            } else {
+                None
+            };
 
-                if let Some(action) = self.hide_tooltip_action.as_mut() {
-                    self.context.action_sender.send((action)()).unwrap();
+            if cache.dirty || current != cache.drawn {
+                if let Some(drawn) = &cache.drawn {
+                    union_damage(&mut damage, drawn.visible_rect);
+                }
+                if let Some(current) = &current {
+                    union_damage(&mut damage, current.visible_rect);
                 }
             }
+
+            cache.drawn = current;
         }
 
-        self.hovered_elements.remove(&element_id);
-        self.elements_with_scroll_wheel_timeout.remove(&element_id);
+        if full {
+            return FrameDamage::Full;
+        }
 
-        if element_entry.stack_data.visible() {
-            self.needs_repaint = true;
+        let view_rect = Rect::from_size(self.context.logical_size);
+
+        match damage.and_then(|damage| {
+            // Add some padding to account for anti-aliasing and rounding to
+            // physical pixels.
+            damage.inflate(1.0, 1.0).intersection(&view_rect)
+        }) {
+            Some(damage) if damage.contains_rect(&view_rect) => FrameDamage::Full,
+            Some(damage) => FrameDamage::Region(damage),
+            None => FrameDamage::None,
         }
     }
 
-    #[allow(unused)]
-    pub fn render<P: FnOnce()>(
+    #[allow(clippy::too_many_arguments)]
+    fn encode_frame(
         &mut self,
-        surface: &wgpu::Surface,
+        view: &wgpu::TextureView,
+        target_texture: Option<&wgpu::Texture>,
+        damage: FrameDamage,
         device: &wgpu::Device,
         queue: &wgpu::Queue,
         texture_format: wgpu::TextureFormat,
         multisample: wgpu::MultisampleState,
         vg: &mut rootvg::Canvas,
-        pre_present_notify: P,
         res: &mut ResourceCtx,
-    ) -> Result<(), wgpu::SurfaceError> {
-        if !self.needs_repaint {
-            return Ok(());
-        }
-
-        // Set up the frame and wgpu encoder.
-        let frame = surface.get_current_texture()?;
-        let view = frame
-            .texture
-            .create_view(&wgpu::TextureViewDescriptor::default());
+    ) -> wgpu::CommandEncoder {
         let mut encoder =
             device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
 
@@ -1859,91 +4269,561 @@ impl<A: Clone + 'static> ElementSystem<A> {
             render_cache.pre_render();
         }
 
-        {
-            let mut vg = vg.begin(self.physical_size, self.context.scale_factor);
+        let damage_rect: Option<RectI32> = match damage {
+            FrameDamage::Region(rect) => Some(rect.round_out().cast()),
+            _ => None,
+        };
+        self.stats.partial = damage_rect.is_some();
 
-            for cache in self.painted_elements.iter_mut() {
-                if !cache.visible {
-                    continue;
-                }
+        // Transformed elements are rendered into their own textures up front,
+        // since the canvas can only render into one target at a time.
+        let transformed_elements = self.visible_transformed_elements(damage_rect);
+        self.render_transformed_elements(
+            &transformed_elements,
+            device,
+            queue,
+            texture_format,
+            multisample,
+            vg,
+            res,
+        );
+
+        // The frame is split into layers at the z indexes of the visible
+        // custom render passes and transformed elements, with each one drawn
+        // between two layers. Elements with the same z index as a layer break
+        // are drawn on top of it.
+        let mut layer_breaks: Vec<(ZIndex, LayerBreak)> = self
+            .visible_render_passes()
+            .into_iter()
+            .map(|(index, z_index, visible_rect)| {
+                (
+                    z_index,
+                    LayerBreak::RenderPass {
+                        index,
+                        visible_rect,
+                    },
+                )
+            })
+            .chain(transformed_elements.iter().map(|(painted_index, z_index)| {
+                (
+                    *z_index,
+                    LayerBreak::TransformedElement {
+                        painted_index: *painted_index,
+                    },
+                )
+            }))
+            .collect();
+        layer_breaks.sort_by_key(|(z_index, _)| *z_index);
+
+        for layer in 0..=layer_breaks.len() {
+            let min_z_index = layer.checked_sub(1).map(|i| layer_breaks[i].0);
+            let max_z_index = layer_breaks.get(layer).map(|(z_index, _)| *z_index);
+            let is_last_layer = layer == layer_breaks.len();
 
-                if cache.dirty {
-                    cache.dirty = false;
+            {
+                let mut vg = vg.begin(self.physical_size, self.context.scale_factor);
+
+                if let (0, Some(damage_rect)) = (layer, damage_rect) {
+                    // The previous contents of the damaged region are kept when
+                    // doing a partial redraw, so clear it manually.
+                    let mut clear_primitives = rootvg::PrimitiveGroup::new();
+                    clear_primitives.add_solid_quad_batch(vec![SolidQuadBuilder::new(
+                        damage_rect.size.cast(),
+                    )
+                    .position(damage_rect.origin.cast())
+                    .bg_color(self.clear_color)
+                    .into()]);
+
+                    vg.set_z_index(0);
+                    vg.set_scissor_rect(damage_rect);
+                    vg.add_group_with_offset(&clear_primitives, Vector::default());
+                }
 
-                    cache.primitives.clear();
+                for painted_index in 0..self.painted_elements.len() {
+                    let cache = &self.painted_elements[painted_index];
 
-                    let element_entry = self.element_arena.get_mut(cache.element_id.0).unwrap();
+                    if !cache.visible {
+                        continue;
+                    }
 
-                    let render_cache = if let Some(render_cache_id) =
-                        element_entry.element.global_render_cache_id()
+                    if min_z_index.is_some_and(|z_index| cache.z_index < z_index)
+                        || max_z_index.is_some_and(|z_index| cache.z_index >= z_index)
                     {
-                        self.render_caches.get_mut(&render_cache_id)
+                        continue;
+                    }
+
+                    if cache.occluded {
+                        self.stats.num_occluded_elements += 1;
+                        continue;
+                    }
+
+                    // Transformed elements are composited between layers.
+                    if cache.transformed {
+                        continue;
+                    }
+
+                    let scissor_rect = self.scissor_rects[cache.scissor_rect_index].rect();
+
+                    let scissor_rect = if let Some(damage_rect) = &damage_rect {
+                        let Some(scissor_rect) = scissor_rect.intersection(damage_rect) else {
+                            continue;
+                        };
+
+                        let Some(visible_rect) = self
+                            .element_arena
+                            .get(cache.element_id.0)
+                            .and_then(|entry| entry.stack_data.visible_rect)
+                        else {
+                            continue;
+                        };
+
+                        if !visible_rect.intersects(&damage_rect.cast()) {
+                            continue;
+                        }
+
+                        scissor_rect
                     } else {
-                        None
+                        scissor_rect
                     };
 
-                    element_entry.element.render(
-                        RenderContext {
+                    if cache.dirty {
+                        self.render_element_primitives(
+                            painted_index,
+                            &mut vg,
                             res,
-                            bounds_size: element_entry.stack_data.rect.size,
-                            bounds_origin: element_entry.stack_data.rect.origin,
-                            visible_bounds: element_entry.stack_data.visible_rect.unwrap(),
-                            scale: self.context.scale_factor,
-                            window_size: self.context.logical_size,
-                            render_cache,
-                            class: element_entry.stack_data.class,
-                            // For some reason the borrow checker doesn't like `vg` being
-                            // borrwed mutably here, even though it's fine with it being
-                            // borrwed mutably three times in the methods below.
-                            vg: &mut vg,
-                            #[cfg(feature = "custom-shaders")]
-                            custom_pipelines: &mut self.custom_pipelines,
-                            #[cfg(feature = "custom-shaders")]
                             device,
-                            #[cfg(feature = "custom-shaders")]
                             queue,
-                            #[cfg(feature = "custom-shaders")]
                             texture_format,
-                            #[cfg(feature = "custom-shaders")]
                             multisample,
-                        },
-                        &mut cache.primitives,
+                        );
+                    }
+
+                    self.stats.num_drawn_elements += 1;
+
+                    let cache = &self.painted_elements[painted_index];
+                    vg.set_z_index(cache.z_index);
+                    vg.set_scissor_rect(scissor_rect);
+                    vg.add_group_with_offset(&cache.primitives, cache.offset);
+                }
+
+                // The view focus ring is drawn on top of all elements, but is
+                // still clipped by the scissoring rectangle of the focused
+                // element.
+                if is_last_layer {
+                    if let (Some(ring), Some((rect, scissor_rect_index))) =
+                        (res.style_system.focus_ring(), self.view_focus_ring_target())
+                    {
+                        let mut ring_primitives = rootvg::PrimitiveGroup::new();
+                        ring_primitives.add(ring.create_primitive(rect, Radius::default()));
+
+                        let scissor_rect = self.scissor_rects[scissor_rect_index].rect();
+                        let scissor_rect = match &damage_rect {
+                            Some(damage_rect) => scissor_rect.intersection(damage_rect),
+                            None => Some(scissor_rect),
+                        };
+
+                        if let Some(scissor_rect) = scissor_rect {
+                            vg.set_z_index(ZIndex::MAX);
+                            vg.set_scissor_rect(scissor_rect);
+                            vg.add_group_with_offset(&ring_primitives, Vector::default());
+                        }
+                    }
+                }
+
+                // The drag ghost is drawn on top of everything else.
+                if let (true, Some(drag)) = (is_last_layer, &self.active_drag) {
+                    if let Some(ghost) = &drag.ghost {
+                        vg.set_z_index(ZIndex::MAX);
+                        vg.set_scissor_rect(self.scissor_rects[0].rect());
+                        vg.add_group_with_offset(
+                            &ghost.primitives,
+                            drag.position.to_vector() + ghost.offset,
+                        );
+                    }
+                }
+
+                if is_last_layer && self.show_stats_overlay {
+                    let primitives = stats_overlay_primitives(&self.last_frame_stats, res);
+
+                    vg.set_z_index(ZIndex::MAX);
+                    vg.set_scissor_rect(self.scissor_rects[0].rect());
+                    vg.add_group_with_offset(&primitives, Vector::default());
+                }
+            }
+
+            // Render the view to the target texture.
+            let render_start = Instant::now();
+            vg.render_to_target(
+                (layer == 0 && damage_rect.is_none()).then_some(self.clear_color),
+                device,
+                queue,
+                &mut encoder,
+                view,
+                self.physical_size,
+                &mut res.font_system,
+                #[cfg(feature = "svg-icons")]
+                &mut res.svg_icon_system,
+            )
+            .unwrap(); // TODO: handle this error properly.
+            self.stats.render_time += render_start.elapsed();
+
+            if let Some((_, layer_break)) = layer_breaks.get(layer).copied() {
+                let render_start = Instant::now();
+                match layer_break {
+                    LayerBreak::RenderPass {
+                        index,
+                        visible_rect,
+                    } => {
+                        self.encode_custom_render_pass(
+                            index,
+                            visible_rect,
+                            view,
+                            target_texture,
+                            &mut encoder,
+                            device,
+                            queue,
+                            texture_format,
+                        );
+                    }
+                    LayerBreak::TransformedElement { painted_index } => {
+                        self.encode_transformed_element(
+                            painted_index,
+                            view,
+                            damage_rect,
+                            &mut encoder,
+                            queue,
+                        );
+                    }
+                }
+                self.stats.render_time += render_start.elapsed();
+
+                // The canvas reuses its buffers for the next layer, so the
+                // commands of this layer must be submitted before they are
+                // overwritten.
+                let layer_encoder = std::mem::replace(
+                    &mut encoder,
+                    device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None }),
+                );
+                queue.submit(Some(layer_encoder.finish()));
+            }
+        }
+
+        for render_cache in self.render_caches.values_mut() {
+            render_cache.post_render();
+        }
+
+        if let Some(compositor) = &mut self.transform_compositor {
+            compositor.collect_unused();
+        }
+
+        encoder
+    }
+
+    /// Regenerate the cached primitives of the element at the given index in
+    /// the list of painted elements.
+    #[allow(clippy::too_many_arguments)]
+    #[cfg_attr(not(feature = "custom-shaders"), allow(unused_variables))]
+    fn render_element_primitives(
+        &mut self,
+        painted_index: usize,
+        vg: &mut rootvg::CanvasCtx<'_>,
+        res: &mut ResourceCtx,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        texture_format: wgpu::TextureFormat,
+        multisample: wgpu::MultisampleState,
+    ) {
+        let cache = &mut self.painted_elements[painted_index];
+        cache.dirty = false;
+
+        let primitive_start = Instant::now();
+        cache.primitives.clear();
+
+        let element_entry = self.element_arena.get_mut(cache.element_id.0).unwrap();
+
+        let render_cache =
+            if let Some(render_cache_id) = element_entry.element.global_render_cache_id() {
+                self.render_caches.get_mut(&render_cache_id)
+            } else {
+                None
+            };
+
+        // Transformed elements are always rendered in full into their own
+        // texture, so the whole element counts as visible.
+        let visible_bounds = if element_entry.stack_data.transform.is_some() {
+            element_entry.stack_data.rect
+        } else {
+            element_entry.stack_data.visible_rect.unwrap()
+        };
+
+        element_entry.element.render(
+            RenderContext {
+                res,
+                bounds_size: element_entry.stack_data.rect.size,
+                bounds_origin: element_entry.stack_data.rect.origin,
+                visible_bounds,
+                scale: self.context.scale_factor,
+                window_size: self.context.logical_size,
+                render_cache,
+                class: element_entry.stack_data.class,
+                opacity: element_entry.stack_data.opacity,
+                vg,
+                #[cfg(feature = "custom-shaders")]
+                custom_pipelines: &mut self.custom_pipelines,
+                #[cfg(feature = "custom-shaders")]
+                device,
+                #[cfg(feature = "custom-shaders")]
+                queue,
+                #[cfg(feature = "custom-shaders")]
+                texture_format,
+                #[cfg(feature = "custom-shaders")]
+                multisample,
+            },
+            &mut cache.primitives,
+        );
+
+        self.stats.primitive_time += primitive_start.elapsed();
+        self.stats.num_rerendered_elements += 1;
+    }
+
+    /// The indexes into the list of painted elements of the visible elements
+    /// with a transform which need to be drawn, sorted by z index.
+    fn visible_transformed_elements(&self, damage_rect: Option<RectI32>) -> Vec<(usize, ZIndex)> {
+        let mut elements: Vec<(usize, ZIndex)> = self
+            .painted_elements
+            .iter()
+            .enumerate()
+            .filter(|(_, cache)| cache.visible && cache.transformed && !cache.occluded)
+            .filter(|(_, cache)| {
+                damage_rect.map_or(true, |damage_rect| {
+                    self.element_arena
+                        .get(cache.element_id.0)
+                        .and_then(|entry| entry.stack_data.visible_rect)
+                        .is_some_and(|visible_rect| visible_rect.intersects(&damage_rect.cast()))
+                })
+            })
+            .map(|(i, cache)| (i, cache.z_index))
+            .collect();
+
+        elements.sort_by_key(|(_, z_index)| *z_index);
+        elements
+    }
+
+    /// Render each of the given transformed elements into its own texture at
+    /// its untransformed size.
+    #[allow(clippy::too_many_arguments)]
+    fn render_transformed_elements(
+        &mut self,
+        elements: &[(usize, ZIndex)],
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        texture_format: wgpu::TextureFormat,
+        multisample: wgpu::MultisampleState,
+        vg: &mut rootvg::Canvas,
+        res: &mut ResourceCtx,
+    ) {
+        if elements.is_empty() {
+            return;
+        }
+
+        let mut compositor = match self.transform_compositor.take() {
+            Some(compositor) if compositor.format() == texture_format => compositor,
+            _ => TransformCompositor::new(device, texture_format),
+        };
+
+        let scale_factor = self.context.scale_factor;
+
+        for (painted_index, _) in elements.iter().copied() {
+            let element_id = self.painted_elements[painted_index].element_id;
+            let Some(rect) = self
+                .element_arena
+                .get(element_id.0)
+                .map(|element_entry| element_entry.stack_data.rect)
+            else {
+                continue;
+            };
+
+            let physical_size = PhysicalSizeI32::new(
+                (rect.width() * scale_factor.0).ceil() as i32,
+                (rect.height() * scale_factor.0).ceil() as i32,
+            );
+            let layer_view = compositor.layer_view(device, element_id, physical_size);
+
+            {
+                let mut vg = vg.begin(physical_size, scale_factor);
+
+                if self.painted_elements[painted_index].dirty {
+                    self.render_element_primitives(
+                        painted_index,
+                        &mut vg,
+                        res,
+                        device,
+                        queue,
+                        texture_format,
+                        multisample,
                     );
                 }
 
-                vg.set_z_index(cache.z_index);
-                vg.set_scissor_rect(self.scissor_rects[cache.scissor_rect_index].rect());
-                vg.add_group_with_offset(&cache.primitives, cache.offset);
+                vg.set_z_index(0);
+                vg.set_scissor_rect(RectI32::new(
+                    PointI32::default(),
+                    SizeI32::new(rect.width().ceil() as i32, rect.height().ceil() as i32),
+                ));
+                vg.add_group_with_offset(
+                    &self.painted_elements[painted_index].primitives,
+                    Vector::default(),
+                );
             }
+
+            let render_start = Instant::now();
+            let mut encoder =
+                device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+            vg.render_to_target(
+                Some(PackedSrgb::TRANSPARENT),
+                device,
+                queue,
+                &mut encoder,
+                &layer_view,
+                physical_size,
+                &mut res.font_system,
+                #[cfg(feature = "svg-icons")]
+                &mut res.svg_icon_system,
+            )
+            .unwrap(); // TODO: handle this error properly.
+
+            // The canvas reuses its buffers for the next element.
+            queue.submit(Some(encoder.finish()));
+            self.stats.render_time += render_start.elapsed();
         }
 
-        // Render the view to the target texture.
-        vg.render_to_target(
-            Some(self.clear_color),
-            device,
+        self.transform_compositor = Some(compositor);
+    }
+
+    /// Composite a transformed element which was rendered with
+    /// [`ElementSystem::render_transformed_elements`] onto the frame.
+    fn encode_transformed_element(
+        &mut self,
+        painted_index: usize,
+        view: &wgpu::TextureView,
+        damage_rect: Option<RectI32>,
+        encoder: &mut wgpu::CommandEncoder,
+        queue: &wgpu::Queue,
+    ) {
+        let Some(compositor) = &self.transform_compositor else {
+            return;
+        };
+
+        let cache = &self.painted_elements[painted_index];
+        let Some(stack_data) = self
+            .element_arena
+            .get(cache.element_id.0)
+            .map(|element_entry| &element_entry.stack_data)
+        else {
+            return;
+        };
+        let Some(transform) = stack_data.transform else {
+            return;
+        };
+
+        let scissor_rect = self.scissor_rects[cache.scissor_rect_index].rect();
+        let scissor_rect = match &damage_rect {
+            Some(damage_rect) => scissor_rect.intersection(damage_rect),
+            None => Some(scissor_rect),
+        };
+        let Some(scissor_rect) = scissor_rect else {
+            return;
+        };
+
+        let scale = self.context.scale_factor.0;
+        let rect = stack_data.rect;
+        let to_window = transform.to_transform(rect);
+
+        let corners = [
+            rect.min(),
+            Point::new(rect.max_x(), rect.min_y()),
+            Point::new(rect.min_x(), rect.max_y()),
+            rect.max(),
+        ]
+        .map(|corner| {
+            let corner = to_window.transform_point(corner);
+            PhysicalPoint::new(corner.x * scale, corner.y * scale)
+        });
+
+        let min_x = (scissor_rect.min_x() as f32 * scale).floor() as i32;
+        let min_y = (scissor_rect.min_y() as f32 * scale).floor() as i32;
+        let max_x = (scissor_rect.max_x() as f32 * scale).ceil() as i32;
+        let max_y = (scissor_rect.max_y() as f32 * scale).ceil() as i32;
+
+        compositor.encode(
+            encoder,
             queue,
-            &mut encoder,
-            &view,
+            view,
             self.physical_size,
-            &mut res.font_system,
-            #[cfg(feature = "svg-icons")]
-            &mut res.svg_icon_system,
-        )
-        .unwrap(); // TODO: handle this error properly.
+            cache.element_id,
+            corners,
+            [rect.width() * scale, rect.height() * scale],
+            PhysicalRectI32::new(
+                PhysicalPointI32::new(min_x, min_y),
+                PhysicalSizeI32::new(max_x - min_x, max_y - min_y),
+            ),
+        );
 
-        for render_cache in self.render_caches.values_mut() {
-            render_cache.post_render();
-        }
+        self.stats.num_drawn_elements += 1;
+    }
 
-        pre_present_notify();
+    #[allow(clippy::too_many_arguments)]
+    fn encode_custom_render_pass(
+        &mut self,
+        index: usize,
+        visible_rect: Rect,
+        view: &wgpu::TextureView,
+        target_texture: Option<&wgpu::Texture>,
+        encoder: &mut wgpu::CommandEncoder,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        texture_format: wgpu::TextureFormat,
+    ) {
+        let scale = self.context.scale_factor.0;
+        let physical_size = self.physical_size;
 
-        // Submit the commands and present the frame.
-        queue.submit(Some(encoder.finish()));
-        frame.present();
+        let min_x = ((visible_rect.min_x() * scale).floor() as i32).clamp(0, physical_size.width);
+        let min_y = ((visible_rect.min_y() * scale).floor() as i32).clamp(0, physical_size.height);
+        let max_x =
+            ((visible_rect.max_x() * scale).ceil() as i32).clamp(min_x, physical_size.width);
+        let max_y =
+            ((visible_rect.max_y() * scale).ceil() as i32).clamp(min_y, physical_size.height);
 
-        self.needs_repaint = false;
+        if max_x == min_x || max_y == min_y {
+            return;
+        }
 
-        Ok(())
+        let entry = &mut self.render_passes[index];
+        let Some(bounds) = self
+            .element_arena
+            .get(entry.element_id.0)
+            .map(|element_entry| element_entry.stack_data.rect)
+        else {
+            return;
+        };
+        let timing = entry.next_timing();
+
+        entry.pass.render(RenderPassContext {
+            device,
+            queue,
+            encoder,
+            target: view,
+            target_texture: target_texture
+                .filter(|texture| texture.usage().contains(wgpu::TextureUsages::COPY_SRC)),
+            target_format: texture_format,
+            target_size: physical_size,
+            viewport: RectI32::new(
+                PointI32::new(min_x, min_y),
+                SizeI32::new(max_x - min_x, max_y - min_y),
+            ),
+            bounds,
+            scale_factor: self.context.scale_factor,
+            timing,
+        });
     }
 
     pub fn cursor_icon(&self) -> CursorIcon {
@@ -1953,11 +4833,63 @@ impl<A: Clone + 'static> ElementSystem<A> {
     pub fn pointer_lock_request(&mut self) -> Option<bool> {
         self.context.pointer_lock_request.take()
     }
+
+    pub fn take_window_control_requests(&mut self) -> Vec<WindowControl> {
+        std::mem::take(&mut self.context.window_control_requests)
+    }
+}
+
+fn stats_overlay_primitives(stats: &FrameStats, res: &mut ResourceCtx) -> rootvg::PrimitiveGroup {
+    const PADDING: f32 = 6.0;
+
+    let text_buffer = RcTextBuffer::new(
+        &stats.overlay_text(),
+        TextProperties {
+            metrics: Metrics {
+                font_size: 12.0,
+                line_height: 16.0,
+            },
+            attrs: Attrs::new().family(Family::Monospace),
+            ..Default::default()
+        },
+        None,
+        None,
+        false,
+        &mut res.font_system,
+    );
+    let text_size = text_buffer.measure();
+
+    let mut primitives = rootvg::PrimitiveGroup::new();
+    primitives.add_solid_quad_batch(vec![SolidQuadBuilder::new(Size::new(
+        text_size.width + (PADDING * 2.0),
+        text_size.height + (PADDING * 2.0),
+    ))
+    .bg_color(rootvg::color::RGBA8::new(0, 0, 0, 200))
+    .into()]);
+    primitives.add_text(TextPrimitive::new(
+        text_buffer,
+        Point::new(PADDING, PADDING),
+        rootvg::color::WHITE,
+        None,
+    ));
+
+    primitives
+}
+
+fn union_damage(damage: &mut Option<Rect>, rect: Rect) {
+    *damage = Some(match damage {
+        Some(damage) => damage.union(&rect),
+        None => rect,
+    });
 }
 
 struct ElementEntry<A: Clone + 'static> {
     pub stack_data: EntryStackData,
     pub element: Box<dyn Element<A>>,
+    pub key: Option<ElementKey>,
+    pub lifecycle: Option<Box<LifecycleHooks<A>>>,
+    /// The cached values shared with this element's handle and refs.
+    pub cached: Rc<CachedElementState>,
 }
 
 // Ideally the size of this struct should be as small as possible to
@@ -1970,17 +4902,31 @@ struct ElementEntry<A: Clone + 'static> {
 #[derive(Clone)]
 struct EntryStackData {
     rect: Rect,
+    /// The part of [`EntryStackData::drawn_rect`] inside the scissoring
+    /// rectangle, or `None` if the element is hidden.
+    ///
+    /// For an element with a transform this covers the whole transformed
+    /// area, so it is used as the pre-check for hit testing, the placement
+    /// in the pointer grid, and the damaged region for partial redraw.
     visible_rect: Option<Rect>,
     offset_from_scissor_rect_origin: Vector,
+    /// The size set by the user, before the zoom factor of the scissoring
+    /// rectangle is applied.
+    unzoomed_size: Size,
 
     scissor_rect_index: usize,
     z_index: ZIndex,
 
     class: ClassID,
+    tab_index: Option<u16>,
 
     flags: ElementFlags,
     manually_hidden: bool,
     animating: bool,
+    /// The opacity set with `ElementHandle::set_opacity`.
+    opacity: f32,
+    /// The transform set with `ElementHandle::set_transform`.
+    transform: Option<ElementTransform>,
 
     index_in_pointer_event_list: u32,
     index_in_painted_list: u32,
@@ -1994,8 +4940,11 @@ impl EntryStackData {
         let scissor_rect = &scissor_rects[self.scissor_rect_index];
         let scissor_rect_origin: Point = scissor_rect.origin().cast();
 
-        self.rect.origin = scissor_rect_origin + self.offset_from_scissor_rect_origin
+        let zoom = scissor_rect.zoom();
+
+        self.rect.origin = scissor_rect_origin + self.offset_from_scissor_rect_origin * zoom
             - scissor_rect.scroll_offset();
+        self.rect.size = self.unzoomed_size * zoom;
     }
 
     fn update_visibility(&mut self, scissor_rects: &[ScissorRect], window_visible: bool) {
@@ -2007,23 +4956,92 @@ impl EntryStackData {
             None
         } else {
             let scissor_rect: Rect = scissor_rects[self.scissor_rect_index].rect().cast();
-            scissor_rect.intersection(&self.rect)
+            scissor_rect.intersection(&self.drawn_rect())
         };
     }
 
     fn visible(&self) -> bool {
         self.visible_rect.is_some()
     }
+
+    /// The area of the window the element is drawn in, before clipping.
+    fn drawn_rect(&self) -> Rect {
+        match &self.transform {
+            Some(transform) => transform.outer_rect(self.rect),
+            None => self.rect,
+        }
+    }
+
+    /// Whether the given point in the window lies inside the visible part of
+    /// the element, taking its transform into account.
+    fn contains_point(&self, pos: Point) -> bool {
+        self.visible_rect.is_some_and(|visible_rect| {
+            visible_rect.contains(pos)
+                && self
+                    .transform
+                    .map(|transform| transform.contains(self.rect, pos))
+                    .unwrap_or(true)
+        })
+    }
+}
+
+/// What is drawn between two layers of a frame in
+/// `ElementSystem::encode_frame`.
+#[derive(Debug, Clone, Copy)]
+enum LayerBreak {
+    /// A custom render pass.
+    RenderPass { index: usize, visible_rect: Rect },
+    /// An element with a transform.
+    TransformedElement { painted_index: usize },
+}
+
+/// Where to move a group of elements with `ElementSystem::restack_elements`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum StackPosition {
+    /// Above all other elements in the band.
+    Front,
+    /// Below all other elements in the band.
+    Back,
+    /// Directly above the given element.
+    Above(ElementID),
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct TooltipInfo {
     pub text: String,
+    /// The keyboard shortcut to show next to the text (see
+    /// `Tooltip::show_with_shortcut`).
+    pub shortcut: Option<KeyChord>,
     pub element_bounds: Rect,
     pub align: Align2,
     pub window_id: WindowID,
 }
 
+/// How much the distance perpendicular to the direction of navigation counts
+/// towards picking the next element in spatial navigation.
+const SPATIAL_NAVIGATION_CROSS_AXIS_WEIGHT: f32 = 2.0;
+
+/// The maximum distance in logical points the pointer can move while being
+/// held down before a long-press is cancelled.
+const LONG_PRESS_MAX_DISTANCE: f32 = 8.0;
+
+struct PendingLongPress {
+    instant: Instant,
+    position: Point,
+    button: PointerButton,
+    pointer_type: PointerType,
+    modifiers: Modifiers,
+}
+
+struct ActiveDrag {
+    source: ElementID,
+    payload: DragPayload,
+    ghost: Option<DragGhost>,
+    /// The position of the pointer in window coordinates.
+    position: Point,
+    target: Option<ElementID>,
+}
+
 #[derive(Clone, Copy)]
 struct FocusInfo {
     element_id: ElementID,
@@ -2040,13 +5058,21 @@ struct ActiveTooltipInfo {
 }
 
 fn send_event_to_element<A: Clone + 'static>(
-    event: ElementEvent,
+    mut event: ElementEvent,
     element_entry: &mut ElementEntry<A>,
     element_id: ElementID,
     view_cx: &mut ElementSystemContext<A>,
     res: &mut ResourceCtx,
     clipboard: &mut Clipboard,
 ) -> EventCaptureStatus {
+    // Transformed elements receive pointer positions relative to their
+    // untransformed bounding rectangle.
+    if let (ElementEvent::Pointer(pointer_event), Some(transform)) =
+        (&mut event, &element_entry.stack_data.transform)
+    {
+        pointer_event.inverse_transform(transform, element_entry.stack_data.rect);
+    }
+
     let has_focus = view_cx
         .current_focus_info
         .as_ref()
@@ -2065,13 +5091,16 @@ fn send_event_to_element<A: Clone + 'static>(
         view_cx.cursor_icon,
         view_cx.window_id,
         view_cx.pointer_locked,
+        view_cx.pointer_capture == Some(element_id),
         element_entry.stack_data.class,
         &mut view_cx.action_sender,
         res,
         clipboard,
     );
 
+    el_cx.action_sender.set_source_element(Some(element_id));
     let capture_status = element_entry.element.on_event(event, &mut el_cx);
+    el_cx.action_sender.set_source_element(None);
 
     view_cx.cursor_icon = el_cx.cursor_icon;
 
@@ -2079,6 +5108,10 @@ fn send_event_to_element<A: Clone + 'static>(
         view_cx.pointer_lock_request = Some(req);
     }
 
+    view_cx
+        .window_control_requests
+        .append(&mut el_cx.window_control_requests);
+
     if el_cx.listen_to_pointer_clicked_off {
         view_cx.mod_queue_sender.send_to_front(ElementModification {
             element_id,
@@ -2107,6 +5140,13 @@ fn send_event_to_element<A: Clone + 'static>(
         });
     }
 
+    if el_cx.animation_requested {
+        view_cx.mod_queue_sender.send_to_front(ElementModification {
+            element_id,
+            type_: ElementModificationType::RequestAnimation,
+        });
+    }
+
     if let Some(new_rect) = el_cx.requested_rect {
         view_cx.mod_queue_sender.send_to_front(ElementModification {
             element_id,
@@ -2152,6 +5192,29 @@ fn send_event_to_element<A: Clone + 'static>(
         });
     }
 
+    if let Some(capture) = el_cx.pointer_capture_request {
+        view_cx.mod_queue_sender.send_to_front(ElementModification {
+            element_id,
+            type_: ElementModificationType::SetPointerCapture(capture),
+        });
+    }
+
+    if let Some(opaque) = el_cx.opaque_request {
+        view_cx.mod_queue_sender.send_to_front(ElementModification {
+            element_id,
+            type_: ElementModificationType::SetOpaque(opaque),
+        });
+    }
+
+    if let Some(req) = el_cx.start_drag_request {
+        view_cx.mod_queue_sender.send_to_front(ElementModification {
+            element_id,
+            type_: ElementModificationType::StartDrag(req),
+        });
+    }
+
+    view_cx.announcements.append(&mut el_cx.announcements);
+
     capture_status
 }
 
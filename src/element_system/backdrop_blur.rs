@@ -0,0 +1,422 @@
+use std::borrow::Cow;
+
+use super::render_pass::{CustomRenderPass, RenderPassContext};
+
+/// The largest blur radius in physical pixels. Larger radii are clamped to
+/// this to keep the cost of each pass bounded.
+const MAX_RADIUS_PX: i32 = 64;
+
+const SHADER: &str = r#"
+struct Params {
+    // The step between two samples in texels.
+    direction: vec2<f32>,
+    // The position of the source texture in the framebuffer.
+    origin: vec2<f32>,
+    // The size of the source texture in texels.
+    size: vec2<f32>,
+    sigma: f32,
+    radius: f32,
+};
+
+@group(0) @binding(0) var src_texture: texture_2d<f32>;
+@group(0) @binding(1) var<uniform> params: Params;
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> @builtin(position) vec4<f32> {
+    // A single triangle which covers the whole viewport.
+    let uv = vec2<f32>(f32((vertex_index << 1u) & 2u), f32(vertex_index & 2u));
+    return vec4<f32>(uv * vec2<f32>(2.0, -2.0) + vec2<f32>(-1.0, 1.0), 0.0, 1.0);
+}
+
+@fragment
+fn fs_main(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    let center = position.xy - params.origin;
+    let max_coord = vec2<i32>(params.size) - vec2<i32>(1);
+    let radius = i32(params.radius);
+    let denom = 2.0 * params.sigma * params.sigma;
+
+    var sum = vec4<f32>(0.0);
+    var weight_sum = 0.0;
+    for (var i = -radius; i <= radius; i++) {
+        let coord = clamp(
+            vec2<i32>(floor(center + params.direction * f32(i))),
+            vec2<i32>(0),
+            max_coord,
+        );
+        let weight = exp(-f32(i * i) / denom);
+
+        sum += textureLoad(src_texture, coord, 0) * weight;
+        weight_sum += weight;
+    }
+
+    return sum / weight_sum;
+}
+"#;
+
+/// A render pass which blurs everything drawn below its placeholder element,
+/// giving overlay panels (i.e. modal scrims and drop-down menu backgrounds)
+/// a frosted-glass look.
+///
+/// Add it with `WindowContext::add_render_pass` to a placeholder element
+/// which covers the panel and has the same z index as the panel. Elements
+/// with a lower z index are blurred, and the panel itself is drawn on top of
+/// the blur, so its background should be semi-transparent for the blur to be
+/// visible.
+///
+/// The blur is done with two separable gaussian passes over the region
+/// behind the placeholder element. While it is visible, the frame is rendered
+/// into an intermediate texture so that it can be sampled, which costs an
+/// extra copy of the whole frame. If the frame can't be sampled (i.e. when
+/// rendering into a texture provided by the application), then nothing is
+/// drawn.
+pub struct BackdropBlur {
+    radius: f32,
+    pipeline: Option<BlurPipeline>,
+}
+
+impl BackdropBlur {
+    /// Create a new backdrop blur with the given radius in logical points.
+    pub fn new(radius: f32) -> Self {
+        Self {
+            radius,
+            pipeline: None,
+        }
+    }
+
+    pub fn radius(&self) -> f32 {
+        self.radius
+    }
+
+    /// Set the blur radius in logical points.
+    ///
+    /// The radius is clamped to `64` physical pixels.
+    pub fn set_radius(&mut self, radius: f32) {
+        self.radius = radius;
+    }
+}
+
+impl CustomRenderPass for BackdropBlur {
+    fn render(&mut self, cx: RenderPassContext<'_>) {
+        let Some(target_texture) = cx.target_texture else {
+            return;
+        };
+
+        let radius_px = ((self.radius * cx.scale_factor.0).round() as i32).clamp(0, MAX_RADIUS_PX);
+        if radius_px == 0 || cx.viewport.size.width <= 0 || cx.viewport.size.height <= 0 {
+            return;
+        }
+
+        // Sample a margin around the viewport so that the edges of the blur
+        // include what is just outside of the panel.
+        let min_x = (cx.viewport.min_x() - radius_px).max(0);
+        let min_y = (cx.viewport.min_y() - radius_px).max(0);
+        let max_x = (cx.viewport.max_x() + radius_px).min(cx.target_size.width);
+        let max_y = (cx.viewport.max_y() + radius_px).min(cx.target_size.height);
+
+        let region_size = wgpu::Extent3d {
+            width: (max_x - min_x) as u32,
+            height: (max_y - min_y) as u32,
+            depth_or_array_layers: 1,
+        };
+
+        let format = target_texture.format();
+        let pipeline = match self.pipeline.take() {
+            Some(pipeline) if pipeline.format == format => pipeline,
+            _ => BlurPipeline::new(cx.device, format),
+        };
+        let pipeline = self.pipeline.insert(pipeline);
+
+        pipeline.prepare(cx.device, region_size);
+        let Some(scratch) = &pipeline.scratch else {
+            return;
+        };
+
+        let sigma = radius_px as f32 * 0.5;
+        let region_size_f = [region_size.width as f32, region_size.height as f32];
+        write_params(
+            cx.queue,
+            &pipeline.horizontal_params,
+            [1.0, 0.0],
+            [0.0, 0.0],
+            region_size_f,
+            sigma,
+            radius_px,
+        );
+        write_params(
+            cx.queue,
+            &pipeline.vertical_params,
+            [0.0, 1.0],
+            [min_x as f32, min_y as f32],
+            region_size_f,
+            sigma,
+            radius_px,
+        );
+
+        cx.encoder.copy_texture_to_texture(
+            wgpu::ImageCopyTexture {
+                texture: target_texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d {
+                    x: min_x as u32,
+                    y: min_y as u32,
+                    z: 0,
+                },
+                aspect: wgpu::TextureAspect::All,
+            },
+            scratch.source.as_image_copy(),
+            region_size,
+        );
+
+        {
+            let mut pass = cx.encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("yarrow backdrop blur horizontal pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &scratch.horizontal_view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+
+            pass.set_pipeline(&pipeline.pipeline);
+            pass.set_bind_group(0, &scratch.horizontal_bind_group, &[]);
+            pass.draw(0..3, 0..1);
+        }
+
+        let mut pass = cx.encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("yarrow backdrop blur vertical pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: cx.target,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        pass.set_viewport(
+            min_x as f32,
+            min_y as f32,
+            region_size.width as f32,
+            region_size.height as f32,
+            0.0,
+            1.0,
+        );
+        pass.set_scissor_rect(
+            cx.viewport.min_x() as u32,
+            cx.viewport.min_y() as u32,
+            cx.viewport.size.width as u32,
+            cx.viewport.size.height as u32,
+        );
+        pass.set_pipeline(&pipeline.pipeline);
+        pass.set_bind_group(0, &scratch.vertical_bind_group, &[]);
+        pass.draw(0..3, 0..1);
+    }
+
+    fn needs_target_texture(&self) -> bool {
+        true
+    }
+}
+
+struct BlurPipeline {
+    pipeline: wgpu::RenderPipeline,
+    bind_group_layout: wgpu::BindGroupLayout,
+    horizontal_params: wgpu::Buffer,
+    vertical_params: wgpu::Buffer,
+    format: wgpu::TextureFormat,
+    scratch: Option<ScratchTextures>,
+}
+
+/// The textures the region behind the placeholder element is copied into
+/// and blurred horizontally into.
+struct ScratchTextures {
+    source: wgpu::Texture,
+    horizontal_view: wgpu::TextureView,
+    horizontal_bind_group: wgpu::BindGroup,
+    vertical_bind_group: wgpu::BindGroup,
+}
+
+impl BlurPipeline {
+    fn new(device: &wgpu::Device, format: wgpu::TextureFormat) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("yarrow backdrop blur shader"),
+            source: wgpu::ShaderSource::Wgsl(Cow::Borrowed(SHADER)),
+        });
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("yarrow backdrop blur bind group layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("yarrow backdrop blur pipeline layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("yarrow backdrop blur pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: "vs_main",
+                compilation_options: Default::default(),
+                buffers: &[],
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: "fs_main",
+                compilation_options: Default::default(),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: None,
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        let create_params = |label| {
+            device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some(label),
+                size: 8 * std::mem::size_of::<f32>() as u64,
+                usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            })
+        };
+
+        Self {
+            pipeline,
+            bind_group_layout,
+            horizontal_params: create_params("yarrow backdrop blur horizontal params"),
+            vertical_params: create_params("yarrow backdrop blur vertical params"),
+            format,
+            scratch: None,
+        }
+    }
+
+    /// Make sure the scratch textures match the size of the blurred region.
+    fn prepare(&mut self, device: &wgpu::Device, size: wgpu::Extent3d) {
+        if self
+            .scratch
+            .as_ref()
+            .is_some_and(|scratch| scratch.source.size() == size)
+        {
+            return;
+        }
+
+        let create_texture = |label, usage| {
+            device.create_texture(&wgpu::TextureDescriptor {
+                label: Some(label),
+                size,
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: self.format,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING | usage,
+                view_formats: &[],
+            })
+        };
+
+        let source = create_texture("yarrow backdrop blur source", wgpu::TextureUsages::COPY_DST);
+        let horizontal = create_texture(
+            "yarrow backdrop blur horizontal",
+            wgpu::TextureUsages::RENDER_ATTACHMENT,
+        );
+
+        let source_view = source.create_view(&wgpu::TextureViewDescriptor::default());
+        let horizontal_view = horizontal.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let create_bind_group = |label, view, params: &wgpu::Buffer| {
+            device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some(label),
+                layout: &self.bind_group_layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::TextureView(view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: params.as_entire_binding(),
+                    },
+                ],
+            })
+        };
+
+        let horizontal_bind_group = create_bind_group(
+            "yarrow backdrop blur horizontal bind group",
+            &source_view,
+            &self.horizontal_params,
+        );
+        let vertical_bind_group = create_bind_group(
+            "yarrow backdrop blur vertical bind group",
+            &horizontal_view,
+            &self.vertical_params,
+        );
+
+        self.scratch = Some(ScratchTextures {
+            source,
+            horizontal_view,
+            horizontal_bind_group,
+            vertical_bind_group,
+        });
+    }
+}
+
+fn write_params(
+    queue: &wgpu::Queue,
+    buffer: &wgpu::Buffer,
+    direction: [f32; 2],
+    origin: [f32; 2],
+    size: [f32; 2],
+    sigma: f32,
+    radius_px: i32,
+) {
+    let params: [f32; 8] = [
+        direction[0],
+        direction[1],
+        origin[0],
+        origin[1],
+        size[0],
+        size[1],
+        sigma,
+        radius_px as f32,
+    ];
+    let bytes: Vec<u8> = params.iter().flat_map(|v| v.to_ne_bytes()).collect();
+    queue.write_buffer(buffer, 0, &bytes);
+}
@@ -1,13 +1,47 @@
 use rootvg::PrimitiveGroup;
 
-use crate::math::{Rect, Vector, ZIndex};
+use crate::math::{Point, Rect, Vector, ZIndex};
 
-use super::{ElementFlags, ElementID, EntryStackData};
+use super::spatial_index::PointerGrid;
+use super::{ElementFlags, ElementID, ElementTransform, EntryStackData};
 
 pub(super) struct CachedElementRectForPointerEvent {
     pub z_index: ZIndex,
     pub element_id: ElementID,
+    /// The visible part of the (transformed) area of the element (see
+    /// `EntryStackData::visible_rect`).
     pub visible_rect: Option<Rect>,
+    /// The transform of the element and its untransformed bounding rectangle.
+    pub transform: Option<(ElementTransform, Rect)>,
+    /// Whether this element is completely covered by an opaque element with
+    /// a higher z index.
+    pub occluded: bool,
+}
+
+impl CachedElementRectForPointerEvent {
+    pub fn new(element_id: ElementID, entry_stack_data: &EntryStackData) -> Self {
+        Self {
+            z_index: entry_stack_data.z_index,
+            element_id,
+            visible_rect: entry_stack_data.visible_rect,
+            transform: entry_stack_data
+                .transform
+                .map(|transform| (transform, entry_stack_data.rect)),
+            occluded: false,
+        }
+    }
+
+    /// Whether the given point in the window lies inside the visible part of
+    /// the element, taking its transform into account.
+    pub fn contains_point(&self, pos: Point) -> bool {
+        self.visible_rect.is_some_and(|visible_rect| {
+            visible_rect.contains(pos)
+                && self
+                    .transform
+                    .map(|(transform, rect)| transform.contains(rect, pos))
+                    .unwrap_or(true)
+        })
+    }
 }
 
 #[derive(Debug)]
@@ -17,8 +51,28 @@ pub(super) struct CachedElementPrimitives {
     pub z_index: ZIndex,
     pub scissor_rect_index: usize,
     pub visible: bool,
+    /// Whether this element is completely covered by an opaque element with
+    /// a higher z index. Occluded elements are not rendered, and their cached
+    /// primitives are only regenerated once they are uncovered.
+    pub occluded: bool,
+    /// Whether this element has a transform, in which case it is rendered
+    /// into its own texture and composited separately.
+    pub transformed: bool,
     pub dirty: bool,
     pub primitives: PrimitiveGroup,
+    /// Where this element was drawn in the last frame which was presented
+    /// with partial redraw enabled.
+    pub drawn: Option<DrawnBounds>,
+}
+
+/// The bounds of an element as of the last rendered frame, used to compute
+/// the damaged region of the window.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct DrawnBounds {
+    pub visible_rect: Rect,
+    pub offset: Vector,
+    pub z_index: ZIndex,
+    pub scissor_rect_index: usize,
 }
 
 impl CachedElementPrimitives {
@@ -35,8 +89,11 @@ impl CachedElementPrimitives {
             z_index,
             scissor_rect_index,
             visible,
+            occluded: false,
+            transformed: false,
             dirty: true,
             primitives: PrimitiveGroup::new(),
+            drawn: None,
         }
     }
 }
@@ -44,6 +101,7 @@ impl CachedElementPrimitives {
 pub(super) fn sync_element_rect_cache(
     entry_stack_data: &EntryStackData,
     elements_listening_to_pointer_event: &mut Vec<CachedElementRectForPointerEvent>,
+    pointer_grid: &mut PointerGrid,
     painted_elements: &mut Vec<CachedElementPrimitives>,
     mark_dirty: bool,
 ) {
@@ -51,9 +109,16 @@ pub(super) fn sync_element_rect_cache(
         .flags
         .contains(ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS)
     {
-        elements_listening_to_pointer_event
-            [entry_stack_data.index_in_pointer_event_list as usize]
-            .visible_rect = entry_stack_data.visible_rect;
+        let cache = &mut elements_listening_to_pointer_event
+            [entry_stack_data.index_in_pointer_event_list as usize];
+
+        if cache.visible_rect != entry_stack_data.visible_rect {
+            cache.visible_rect = entry_stack_data.visible_rect;
+            pointer_grid.mark_element_dirty(entry_stack_data.index_in_pointer_event_list);
+        }
+        cache.transform = entry_stack_data
+            .transform
+            .map(|transform| (transform, entry_stack_data.rect));
     }
 
     if entry_stack_data.flags.contains(ElementFlags::PAINTS) {
@@ -61,6 +126,7 @@ pub(super) fn sync_element_rect_cache(
 
         cache.offset = entry_stack_data.rect.origin.to_vector();
         cache.visible = entry_stack_data.visible();
+        cache.transformed = entry_stack_data.transform.is_some();
         cache.dirty |= mark_dirty;
     }
 }
@@ -0,0 +1,339 @@
+use std::borrow::Cow;
+
+/// A type of color vision deficiency which can be simulated over the whole
+/// rendered frame of a window (see
+/// `WindowContext::set_color_vision_simulation`).
+///
+/// This is meant as a debugging aid for checking that meter and state
+/// colors remain distinguishable for users with color blindness.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ColorVisionDeficiency {
+    /// The absence of red cones.
+    Protanopia,
+    /// The absence of green cones.
+    Deuteranopia,
+    /// The absence of blue cones.
+    Tritanopia,
+}
+
+impl ColorVisionDeficiency {
+    pub const ALL: [Self; 3] = [Self::Protanopia, Self::Deuteranopia, Self::Tritanopia];
+
+    /// The matrix which maps a linear RGB color to the color perceived with
+    /// this deficiency.
+    ///
+    /// These are the full-severity matrices from Machado, Oliveira, and
+    /// Fernandes, "A Physiologically-based Model for Simulation of Color
+    /// Vision Deficiency" (2009).
+    pub const fn matrix(&self) -> [[f32; 3]; 3] {
+        match self {
+            Self::Protanopia => [
+                [0.152286, 1.052583, -0.204868],
+                [0.114503, 0.786281, 0.099216],
+                [-0.003882, -0.048116, 1.051998],
+            ],
+            Self::Deuteranopia => [
+                [0.367322, 0.860646, -0.227968],
+                [0.280085, 0.672501, 0.047413],
+                [-0.011820, 0.042940, 0.968881],
+            ],
+            Self::Tritanopia => [
+                [1.255528, -0.076749, -0.178779],
+                [-0.078411, 0.930809, 0.147602],
+                [0.004733, 0.691367, 0.303900],
+            ],
+        }
+    }
+}
+
+const SHADER: &str = r#"
+struct Params {
+    row0: vec4<f32>,
+    row1: vec4<f32>,
+    row2: vec4<f32>,
+};
+
+@group(0) @binding(0) var src_texture: texture_2d<f32>;
+@group(0) @binding(1) var src_sampler: sampler;
+@group(0) @binding(2) var<uniform> params: Params;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    // A single triangle which covers the whole screen.
+    let uv = vec2<f32>(f32((vertex_index << 1u) & 2u), f32(vertex_index & 2u));
+
+    var out: VertexOutput;
+    out.position = vec4<f32>(uv * vec2<f32>(2.0, -2.0) + vec2<f32>(-1.0, 1.0), 0.0, 1.0);
+    out.uv = uv;
+    return out;
+}
+
+fn to_linear(c: vec3<f32>) -> vec3<f32> {
+    let low = c / 12.92;
+    let high = pow((c + 0.055) / 1.055, vec3<f32>(2.4));
+    return select(high, low, c <= vec3<f32>(0.04045));
+}
+
+fn to_srgb(c: vec3<f32>) -> vec3<f32> {
+    let low = c * 12.92;
+    let high = 1.055 * pow(c, vec3<f32>(1.0 / 2.4)) - 0.055;
+    return select(high, low, c <= vec3<f32>(0.0031308));
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let color = textureSample(src_texture, src_sampler, in.uv);
+
+    // The `w` component of the first row is set when the texture stores
+    // sRGB-encoded values without an sRGB format.
+    let encoded = params.row0.w > 0.5;
+
+    var rgb = color.rgb;
+    if encoded {
+        rgb = to_linear(rgb);
+    }
+
+    rgb = clamp(
+        vec3<f32>(dot(params.row0.xyz, rgb), dot(params.row1.xyz, rgb), dot(params.row2.xyz, rgb)),
+        vec3<f32>(0.0),
+        vec3<f32>(1.0),
+    );
+
+    if encoded {
+        rgb = to_srgb(rgb);
+    }
+
+    return vec4<f32>(rgb, color.a);
+}
+"#;
+
+/// Renders a frame through a color vision deficiency simulation.
+///
+/// This is also used to copy the frame to the surface without a simulation
+/// when it must be rendered into an intermediate texture for other reasons
+/// (see `CustomRenderPass::needs_target_texture`).
+pub(super) struct CvdFilter {
+    pipeline: wgpu::RenderPipeline,
+    bind_group_layout: wgpu::BindGroupLayout,
+    sampler: wgpu::Sampler,
+    params_buffer: wgpu::Buffer,
+    format: wgpu::TextureFormat,
+    /// The texture the frame is rendered into before being filtered.
+    source: Option<(wgpu::Texture, wgpu::BindGroup)>,
+}
+
+impl CvdFilter {
+    pub fn new(device: &wgpu::Device, format: wgpu::TextureFormat) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("yarrow cvd simulation shader"),
+            source: wgpu::ShaderSource::Wgsl(Cow::Borrowed(SHADER)),
+        });
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("yarrow cvd simulation bind group layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::NonFiltering),
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("yarrow cvd simulation pipeline layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("yarrow cvd simulation pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: "vs_main",
+                compilation_options: Default::default(),
+                buffers: &[],
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: "fs_main",
+                compilation_options: Default::default(),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: None,
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("yarrow cvd simulation sampler"),
+            mag_filter: wgpu::FilterMode::Nearest,
+            min_filter: wgpu::FilterMode::Nearest,
+            ..Default::default()
+        });
+
+        let params_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("yarrow cvd simulation params"),
+            size: 3 * 4 * std::mem::size_of::<f32>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        Self {
+            pipeline,
+            bind_group_layout,
+            sampler,
+            params_buffer,
+            format,
+            source: None,
+        }
+    }
+
+    pub fn format(&self) -> wgpu::TextureFormat {
+        self.format
+    }
+
+    /// The view of the texture to render the unfiltered frame into.
+    pub fn source_view(
+        &mut self,
+        device: &wgpu::Device,
+        size: wgpu::Extent3d,
+    ) -> wgpu::TextureView {
+        let needs_recreate = self
+            .source
+            .as_ref()
+            .map_or(true, |(texture, _)| texture.size() != size);
+
+        if needs_recreate {
+            let texture = device.create_texture(&wgpu::TextureDescriptor {
+                label: Some("yarrow cvd simulation source"),
+                size,
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: self.format,
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                    | wgpu::TextureUsages::TEXTURE_BINDING
+                    | wgpu::TextureUsages::COPY_SRC,
+                view_formats: &[],
+            });
+
+            let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+            let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("yarrow cvd simulation bind group"),
+                layout: &self.bind_group_layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::TextureView(&view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::Sampler(&self.sampler),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: self.params_buffer.as_entire_binding(),
+                    },
+                ],
+            });
+
+            self.source = Some((texture, bind_group));
+        }
+
+        self.source
+            .as_ref()
+            .unwrap()
+            .0
+            .create_view(&wgpu::TextureViewDescriptor::default())
+    }
+
+    /// The texture the unfiltered frame is rendered into, if it was created
+    /// with [`CvdFilter::source_view`].
+    pub fn source_texture(&self) -> Option<&wgpu::Texture> {
+        self.source.as_ref().map(|(texture, _)| texture)
+    }
+
+    /// Draw the source texture into `target` with the simulation applied.
+    ///
+    /// If `deficiency` is `None`, then the source texture is copied as-is.
+    pub fn encode(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        queue: &wgpu::Queue,
+        target: &wgpu::TextureView,
+        deficiency: Option<ColorVisionDeficiency>,
+    ) {
+        let Some((_, bind_group)) = &self.source else {
+            return;
+        };
+
+        let (m, encoded) = match deficiency {
+            Some(deficiency) => (
+                deficiency.matrix(),
+                if self.format.is_srgb() { 0.0 } else { 1.0 },
+            ),
+            None => ([[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]], 0.0),
+        };
+        let params: [f32; 12] = [
+            m[0][0], m[0][1], m[0][2], encoded, m[1][0], m[1][1], m[1][2], 0.0, m[2][0], m[2][1],
+            m[2][2], 0.0,
+        ];
+        let bytes: Vec<u8> = params.iter().flat_map(|v| v.to_ne_bytes()).collect();
+        queue.write_buffer(&self.params_buffer, 0, &bytes);
+
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("yarrow cvd simulation pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: target,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        pass.set_pipeline(&self.pipeline);
+        pass.set_bind_group(0, bind_group, &[]);
+        pass.draw(0..3, 0..1);
+    }
+}
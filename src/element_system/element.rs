@@ -1,28 +1,44 @@
 mod context;
 mod flags;
 mod handle;
+mod key;
+mod lifecycle;
+mod transform;
+mod tween;
 
 use std::any::Any;
+use std::rc::Rc;
 
-use context::UpdateScissorRectRequest;
-pub use context::{ElementContext, RenderContext};
+pub use context::{DragGhost, ElementContext, RenderContext};
+use context::{StartDragRequest, UpdateScissorRectRequest};
 pub use flags::ElementFlags;
+pub(crate) use handle::CachedElementState;
 pub use handle::ElementHandle;
+pub use key::{ElementKey, ElementRef};
+pub use lifecycle::LifecycleHooks;
 use rootvg::math::Point;
 use rootvg::PrimitiveGroup;
+pub use transform::ElementTransform;
+pub(crate) use tween::{ActiveTween, TweenRequest};
+pub use tween::{AnimatedProperty, AnimatedPropertyKind};
 
 use super::ScissorRectID;
 use crate::action_queue::ActionSender;
 use crate::event::{ElementEvent, EventCaptureStatus};
 use crate::math::{Rect, Size, ZIndex};
 use crate::prelude::TooltipData;
-use crate::style::ClassID;
+use crate::style::{ClassID, DisabledDerivation};
 use crate::{stmpsc_queue, WindowContext};
 
 pub(crate) use context::ChangeFocusRequest;
 
+/// A lightweight, copyable ID of an element instance in a window.
+///
+/// Elements are stored in a generational arena, so an ID of an element which
+/// has been dropped will never refer to a different element that was added
+/// later.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub(crate) struct ElementID(pub thunderdome::Index);
+pub struct ElementID(pub(crate) thunderdome::Index);
 
 pub trait Element<A: Clone + 'static> {
     #[allow(unused)]
@@ -37,6 +53,12 @@ pub trait Element<A: Clone + 'static> {
     #[allow(unused)]
     fn on_dropped(&mut self, action_sender: &mut ActionSender<A>) {}
 
+    /// Add the primitives for this element to `primitives`.
+    ///
+    /// The primitives are cached and reused until the element requests a
+    /// repaint, its size or class changes, the theme changes, or the scale
+    /// factor changes. Moving, hiding, or showing the element does not cause
+    /// it to be rendered again.
     #[allow(unused)]
     fn render(&mut self, cx: RenderContext, primitives: &mut PrimitiveGroup) {}
 
@@ -72,6 +94,20 @@ pub struct ElementBuilder<A: Clone + 'static> {
     pub scissor_rect: ScissorRectID,
     pub class: ClassID,
     pub flags: ElementFlags,
+    /// The explicit position of this element in the keyboard navigation order
+    /// (only has an effect if the element has the `ElementFlags::FOCUSABLE`
+    /// flag).
+    ///
+    /// Elements with an explicit tab index are visited first in ascending
+    /// order, followed by all other focusable elements in reading order
+    /// (top to bottom, left to right).
+    pub tab_index: Option<u16>,
+    /// The optional user-defined ID of this element, which can be used to
+    /// look up the element later with `WindowContext::element_by_key`.
+    pub key: Option<ElementKey>,
+    /// Optional callbacks for when the element is added, removed, shown,
+    /// hidden, or resized.
+    pub lifecycle: Option<Box<LifecycleHooks<A>>>,
 }
 
 impl<A: Clone + 'static> ElementBuilder<A> {
@@ -84,6 +120,9 @@ impl<A: Clone + 'static> ElementBuilder<A> {
             scissor_rect: ScissorRectID::DEFAULT,
             class: 0,
             flags: ElementFlags::empty(),
+            tab_index: None,
+            key: None,
+            lifecycle: None,
         }
     }
 
@@ -130,9 +169,66 @@ impl<A: Clone + 'static> ElementBuilder<A> {
         self
     }
 
+    pub const fn tab_index(mut self, tab_index: Option<u16>) -> Self {
+        self.tab_index = tab_index;
+        self
+    }
+
+    pub fn key(mut self, key: Option<ElementKey>) -> Self {
+        self.key = key;
+        self
+    }
+
+    /// Send an action when the element is added to the view.
+    ///
+    /// The callback receives the initial bounding rectangle of the element.
+    pub fn on_mount<F: FnMut(Rect) -> A + 'static>(mut self, f: F) -> Self {
+        self.lifecycle_mut().on_mount = Some(Box::new(f));
+        self
+    }
+
+    /// Send an action when the element is removed from the view (when its
+    /// handle is dropped).
+    pub fn on_unmount<F: FnMut() -> A + 'static>(mut self, f: F) -> Self {
+        self.lifecycle_mut().on_unmount = Some(Box::new(f));
+        self
+    }
+
+    /// Send an action when the element becomes visible.
+    ///
+    /// An element is visible if it is not hidden, has a non-zero size, lies
+    /// at least partially within its scissoring rectangle, and the window is
+    /// visible. This is not called when the element is first added.
+    pub fn on_shown<F: FnMut() -> A + 'static>(mut self, f: F) -> Self {
+        self.lifecycle_mut().on_shown = Some(Box::new(f));
+        self
+    }
+
+    /// Send an action when the element stops being visible.
+    ///
+    /// See [`ElementBuilder::on_shown`] for when an element is considered
+    /// visible.
+    pub fn on_hidden<F: FnMut() -> A + 'static>(mut self, f: F) -> Self {
+        self.lifecycle_mut().on_hidden = Some(Box::new(f));
+        self
+    }
+
+    /// Send an action when the bounding rectangle of the element is changed.
+    ///
+    /// This is not called when the element moves because a scroll area was
+    /// scrolled.
+    pub fn on_rect_changed<F: FnMut(Rect) -> A + 'static>(mut self, f: F) -> Self {
+        self.lifecycle_mut().on_rect_changed = Some(Box::new(f));
+        self
+    }
+
     pub fn build(self, window_cx: &mut WindowContext<A>) -> ElementHandle {
         window_cx.add_element(self)
     }
+
+    fn lifecycle_mut(&mut self) -> &mut LifecycleHooks<A> {
+        self.lifecycle.get_or_insert_with(Default::default)
+    }
 }
 
 pub trait ElementStyle: Default + Any {
@@ -145,6 +241,17 @@ pub trait ElementStyle: Default + Any {
     fn default_light_style() -> Self {
         Self::default()
     }
+
+    /// Returns a copy of this style where every disabled-state property set
+    /// to `Auto` is derived with the given derivation (see
+    /// [`StyleSystem::set_auto_disabled`](crate::style::StyleSystem::set_auto_disabled)).
+    ///
+    /// Styles without any disabled-state properties can return `None`, in
+    /// which case the style is used as is.
+    fn with_auto_disabled(&self, derivation: DisabledDerivation) -> Option<Self> {
+        let _ = derivation;
+        None
+    }
 }
 
 pub(super) struct ElementModification {
@@ -160,7 +267,10 @@ pub(super) enum ElementModificationType {
     ZIndexChanged(ZIndex),
     ExplicitlyHiddenChanged(bool),
     ClassChanged(ClassID),
+    TabIndexChanged(Option<u16>),
+    FocusableChanged(bool),
     SetAnimating(bool),
+    RequestAnimation,
     ChangeFocus(ChangeFocusRequest),
     HandleDropped,
     ListenToClickOff,
@@ -168,6 +278,14 @@ pub(super) enum ElementModificationType {
     StartScrollWheelTimeout,
     ShowTooltip { data: TooltipData, auto_hide: bool },
     UpdateScissorRect(UpdateScissorRectRequest),
+    StartDrag(StartDragRequest),
+    SetPointerCapture(bool),
+    SetOpaque(bool),
+    OpacityChanged(f32),
+    TransformChanged(Option<ElementTransform>),
+    StartTween(Box<TweenRequest>),
+    StopTween(Option<AnimatedPropertyKind>),
+    SetAnchor(Option<ElementID>),
 }
 
 // I get a warning about leaking `ElementID` if I make `ElementHandle::new()`
@@ -175,17 +293,7 @@ pub(super) enum ElementModificationType {
 pub(super) fn new_element_handle(
     element_id: ElementID,
     mod_queue_sender: stmpsc_queue::Sender<ElementModification>,
-    rect: Rect,
-    z_index: ZIndex,
-    manually_hidden: bool,
-    class: ClassID,
+    cached: Rc<CachedElementState>,
 ) -> ElementHandle {
-    ElementHandle::new(
-        element_id,
-        mod_queue_sender,
-        rect,
-        z_index,
-        manually_hidden,
-        class,
-    )
+    ElementHandle::new(element_id, mod_queue_sender, cached)
 }
@@ -1,11 +1,14 @@
 use std::sync::mpsc;
 
 use rootvg::math::{Point, Size, Vector};
+use rootvg::PrimitiveGroup;
 
 use crate::action_queue::ActionSender;
 use crate::clipboard::Clipboard;
+use crate::event::{AnnouncePriority, Announcement, DragPayload};
 use crate::math::{Rect, ScaleFactor, ZIndex};
 use crate::prelude::{ClassID, ResourceCtx, TooltipData};
+use crate::window::WindowControl;
 use crate::{CursorIcon, ScissorRectID, WindowID};
 
 use super::ElementRenderCache;
@@ -26,6 +29,25 @@ pub(crate) struct UpdateScissorRectRequest {
     pub scissor_rect_id: ScissorRectID,
     pub new_rect: Option<Rect>,
     pub new_scroll_offset: Option<Vector>,
+    pub new_zoom: Option<f32>,
+}
+
+pub(crate) struct StartDragRequest {
+    pub payload: DragPayload,
+    pub ghost: Option<DragGhost>,
+}
+
+/// The visual that follows the pointer during a drag-and-drop operation.
+///
+/// This is drawn on top of all other elements.
+pub struct DragGhost {
+    /// The primitives to draw.
+    pub primitives: PrimitiveGroup,
+    /// The offset of the primitives relative to the pointer position.
+    ///
+    /// For example, set this to `-(pointer position within the element)` to
+    /// make the ghost appear at the same spot where the drag started.
+    pub offset: Vector,
 }
 
 /// A context for this element instance. This is used to request actions from the
@@ -53,6 +75,7 @@ pub struct ElementContext<'a, A: Clone + 'static> {
     pub(crate) z_index: ZIndex,
     pub(crate) manually_hidden: bool,
     pub(crate) animating: bool,
+    pub(crate) animation_requested: bool,
     pub(crate) repaint_requested: bool,
     pub(crate) has_focus: bool,
     pub(crate) hover_timeout_requested: bool,
@@ -61,7 +84,13 @@ pub struct ElementContext<'a, A: Clone + 'static> {
     pub(crate) window_id: WindowID,
     pub(crate) pointer_lock_request: Option<bool>,
     pub(crate) update_scissor_rect_req: Option<UpdateScissorRectRequest>,
+    pub(crate) start_drag_request: Option<StartDragRequest>,
+    pub(crate) pointer_capture_request: Option<bool>,
+    pub(crate) opaque_request: Option<bool>,
+    pub(crate) announcements: Vec<Announcement>,
+    pub(crate) window_control_requests: Vec<WindowControl>,
     pointer_locked: bool,
+    has_pointer_capture: bool,
     class: ClassID,
 }
 
@@ -78,6 +107,7 @@ impl<'a, A: Clone + 'static> ElementContext<'a, A> {
         cursor_icon: CursorIcon,
         window_id: WindowID,
         pointer_locked: bool,
+        has_pointer_capture: bool,
         class: ClassID,
         action_sender: &'a mut ActionSender<A>,
         res: &'a mut ResourceCtx,
@@ -93,12 +123,16 @@ impl<'a, A: Clone + 'static> ElementContext<'a, A> {
             z_index,
             manually_hidden,
             animating,
+            animation_requested: false,
             repaint_requested: false,
             has_focus,
             scale_factor,
             window_id,
             pointer_lock_request: None,
             pointer_locked,
+            pointer_capture_request: None,
+            opaque_request: None,
+            has_pointer_capture,
             listen_to_pointer_clicked_off: false,
             hover_timeout_requested: false,
             scroll_wheel_timeout_requested: false,
@@ -106,6 +140,9 @@ impl<'a, A: Clone + 'static> ElementContext<'a, A> {
             requested_show_tooltip: None,
             change_focus_request: None,
             update_scissor_rect_req: None,
+            start_drag_request: None,
+            announcements: Vec::new(),
+            window_control_requests: Vec::new(),
             class,
             clipboard,
         }
@@ -186,6 +223,17 @@ impl<'a, A: Clone + 'static> ElementContext<'a, A> {
         self.animating = animating;
     }
 
+    /// Request to receive a single `ElementEvent::Tick` event on the next
+    /// frame.
+    ///
+    /// To keep animating, call this again while handling the tick event. The
+    /// window only keeps ticking while at least one element is animating, so
+    /// this is preferable to `ElementContext::set_animating` for animations
+    /// which end on their own.
+    pub fn request_animation(&mut self) {
+        self.animation_requested = true;
+    }
+
     /// Request to steal focus.
     ///
     /// If another element instance has focus, then that element will
@@ -246,6 +294,18 @@ impl<'a, A: Clone + 'static> ElementContext<'a, A> {
         self.action_sender.send(action)
     }
 
+    /// Queue a transient message (i.e. "Preset loaded") to be announced.
+    ///
+    /// The message is only delivered to the application as an
+    /// `AppWindowEvent::Announcement` event, and the application has to
+    /// forward it to assistive technology itself.
+    pub fn announce(&mut self, message: impl Into<String>, priority: AnnouncePriority) {
+        self.announcements.push(Announcement {
+            message: message.into(),
+            priority,
+        });
+    }
+
     pub fn start_hover_timeout(&mut self) {
         self.hover_timeout_requested = true;
     }
@@ -258,6 +318,21 @@ impl<'a, A: Clone + 'static> ElementContext<'a, A> {
         self.requested_show_tooltip = Some(ShowTooltipRequest { data, auto_hide });
     }
 
+    /// Start a drag-and-drop operation with the given payload.
+    ///
+    /// Elements with the `ElementFlags::ACCEPTS_DROPS` flag will receive
+    /// `ElementEvent::Drag` events as the pointer moves over them, and this
+    /// element will receive `DragEvent::Ended` once the pointer button is
+    /// released or the drag is cancelled with the escape key.
+    ///
+    /// While a drag is in progress, pointer move and button release events are
+    /// not sent to any elements.
+    ///
+    /// * `ghost` - An optional visual that follows the pointer.
+    pub fn start_drag(&mut self, payload: DragPayload, ghost: Option<DragGhost>) {
+        self.start_drag_request = Some(StartDragRequest { payload, ghost });
+    }
+
     /// The ID of the window this element belongs to.
     pub fn window_id(&self) -> WindowID {
         self.window_id
@@ -273,11 +348,55 @@ impl<'a, A: Clone + 'static> ElementContext<'a, A> {
         self.pointer_lock_request = Some(lock);
     }
 
+    /// Request to control the window this element belongs to (i.e. to move,
+    /// minimize, maximize, or close it).
+    ///
+    /// This is mainly useful for custom title bars in windows without
+    /// decorations. The backend may choose to ignore this request.
+    pub fn request_window_control(&mut self, control: WindowControl) {
+        self.window_control_requests.push(control);
+    }
+
     /// Whether or not the pointer is currently locked in place.
     pub fn is_pointer_locked(&self) -> bool {
         self.pointer_locked
     }
 
+    /// Request to capture the pointer.
+    ///
+    /// While captured, this element receives all pointer events first (even
+    /// when the pointer is outside of its bounds) until it calls
+    /// `ElementContext::release_pointer()`. Unlike
+    /// `ElementFlags::LISTENS_TO_POINTER_OUTSIDE_BOUNDS_WHEN_FOCUSED`, this does
+    /// not require the element to have focus.
+    ///
+    /// The pointer is automatically released when this element is dropped,
+    /// when it becomes hidden, or when the window loses focus. A hidden
+    /// element cannot capture the pointer.
+    pub fn capture_pointer(&mut self) {
+        self.pointer_capture_request = Some(true);
+    }
+
+    /// Request to release the pointer captured with
+    /// `ElementContext::capture_pointer()`.
+    pub fn release_pointer(&mut self) {
+        self.pointer_capture_request = Some(false);
+    }
+
+    /// Set whether or not this element completely fills its rectangular area
+    /// with opaque content (see `ElementFlags::OPAQUE`).
+    ///
+    /// This is useful for elements whose opaqueness depends on their style,
+    /// i.e. when handling `ElementEvent::StyleChanged`.
+    pub fn set_opaque(&mut self, opaque: bool) {
+        self.opaque_request = Some(opaque);
+    }
+
+    /// Whether or not this element has currently captured the pointer.
+    pub fn has_pointer_capture(&self) -> bool {
+        self.has_pointer_capture
+    }
+
     /// The current class ID.
     pub fn class(&self) -> ClassID {
         self.class
@@ -302,12 +421,47 @@ impl<'a, A: Clone + 'static> ElementContext<'a, A> {
         new_rect: Option<Rect>,
         new_scroll_offset: Option<Vector>,
     ) {
+        let new_zoom = self.pending_scissor_rect_zoom(scissor_rect_id);
+
         self.update_scissor_rect_req = Some(UpdateScissorRectRequest {
             scissor_rect_id,
             new_rect,
             new_scroll_offset,
+            new_zoom,
         });
     }
+
+    /// Set the zoom factor of the given scissoring rectangle. The offsets
+    /// (relative to the origin of the scissoring rectangle) and the sizes of
+    /// all elements assigned to it are multiplied by this factor.
+    ///
+    /// This can be combined with [`ElementContext::update_scissor_rect`] for
+    /// the same scissoring rectangle in the same event.
+    ///
+    /// If `scissor_rect_id == ScissorRectID::DEFAULT`, then this
+    /// will do nothing.
+    pub fn set_scissor_rect_zoom(&mut self, scissor_rect_id: ScissorRectID, zoom: f32) {
+        match &mut self.update_scissor_rect_req {
+            Some(req) if req.scissor_rect_id == scissor_rect_id => {
+                req.new_zoom = Some(zoom);
+            }
+            _ => {
+                self.update_scissor_rect_req = Some(UpdateScissorRectRequest {
+                    scissor_rect_id,
+                    new_rect: None,
+                    new_scroll_offset: None,
+                    new_zoom: Some(zoom),
+                });
+            }
+        }
+    }
+
+    fn pending_scissor_rect_zoom(&self, scissor_rect_id: ScissorRectID) -> Option<f32> {
+        self.update_scissor_rect_req
+            .as_ref()
+            .filter(|req| req.scissor_rect_id == scissor_rect_id)
+            .and_then(|req| req.new_zoom)
+    }
 }
 
 /// A context for this element instance for use in rendering primitives.
@@ -326,6 +480,13 @@ pub struct RenderContext<'a, 'b> {
     pub scale: ScaleFactor,
     /// The current class ID.
     pub class: ClassID,
+    /// The opacity of this element in the range `[0.0, 1.0]` (see
+    /// `ElementHandle::set_opacity`).
+    ///
+    /// Pass this to the methods of `QuadStyle` and `FocusRingStyle` which
+    /// create primitives. Any other colors should be passed through
+    /// `apply_opacity`.
+    pub opacity: f32,
     /// The size of the window. This can be useful to reposition/resize elements
     /// like drop-down menus to fit within the window.
     pub window_size: Size,
@@ -5,7 +5,7 @@ bitflags::bitflags! {
     ///
     /// By default all these flags are disabled.
     #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-    pub struct ElementFlags: u16 {
+    pub struct ElementFlags: u32 {
         /// Whether or not this element paints anything to the screen.
         const PAINTS = 1 << 0;
 
@@ -66,5 +66,48 @@ bitflags::bitflags! {
         /// Whether or not this element should receive an `init` event when it gets
         /// added to the view.
         const LISTENS_TO_INIT = 1 << 12;
+
+        /// Whether or not this element can receive exclusive focus with keyboard
+        /// navigation (Tab and Shift+Tab).
+        ///
+        /// Elements with this flag should also set `LISTENS_TO_FOCUS_CHANGE` so
+        /// they can draw a focus ring, or else rely on the focus ring drawn by the
+        /// view (see `ElementFlags::DRAWS_OWN_FOCUS_RING`).
+        ///
+        /// This flag can be toggled after the element is built with
+        /// `ElementHandle::set_focusable` (i.e. while the element is disabled).
+        const FOCUSABLE = 1 << 13;
+
+        /// Whether or not this element should receive drag-and-drop events when
+        /// a payload is dragged over it (see `ElementEvent::Drag`).
+        const ACCEPTS_DROPS = 1 << 14;
+
+        /// Whether or not this element should receive every raw key down and key
+        /// up event (including repeats) while it is visible, even when it does not
+        /// have exclusive focus.
+        ///
+        /// These events are sent after the focused element has had a chance to
+        /// capture them. This is useful for elements like a piano keyboard which
+        /// implement QWERTY note input.
+        const LISTENS_TO_ALL_KEYS = 1 << 15;
+
+        /// Whether or not this element completely fills its assigned rectangular
+        /// area with opaque content (i.e. a solid panel background).
+        ///
+        /// Elements which are completely covered by a visible opaque element with a
+        /// higher z index are not rendered and do not receive pointer events.
+        ///
+        /// This has no effect if the `ElementFlags::PAINTS` flag is not set. Use
+        /// `ElementContext::set_opaque` if this depends on the element's style.
+        const OPAQUE = 1 << 16;
+
+        /// Whether or not this element draws its own focus ring when it has
+        /// focus.
+        ///
+        /// If the view focus ring is enabled (see
+        /// `WindowContext::set_view_focus_ring_enabled`), then the view draws a
+        /// focus ring around focusable elements without this flag when they
+        /// receive focus with keyboard navigation.
+        const DRAWS_OWN_FOCUS_RING = 1 << 17;
     }
 }
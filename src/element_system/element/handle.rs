@@ -1,38 +1,76 @@
-use super::ElementModificationType;
+use std::any::Any;
+use std::cell::Cell;
+use std::rc::Rc;
+
+use super::{
+    AnimatedProperty, AnimatedPropertyKind, ElementModificationType, ElementRef, ElementTransform,
+    TweenRequest,
+};
 use crate::element_system::{ElementID, ElementModification};
 use crate::layout::Align2;
 use crate::math::{Point, Rect, Size, Vector, ZIndex};
 use crate::prelude::TooltipData;
 use crate::stmpsc_queue;
-use crate::style::ClassID;
+use crate::style::{ClassID, Transition};
 use crate::WindowContext;
 
+/// The values of an element instance which are cached on the user side.
+///
+/// This is shared between the [`ElementHandle`], any [`ElementRef`]s to the
+/// element, and the element system (which writes back values it changes on
+/// its own, i.e. when restacking elements), so that all of them agree on the
+/// current values.
+pub(crate) struct CachedElementState {
+    pub rect: Cell<Rect>,
+    pub z_index: Cell<ZIndex>,
+    pub manually_hidden: Cell<bool>,
+    pub class: Cell<ClassID>,
+    pub tab_index: Cell<Option<u16>>,
+    pub focusable: Cell<bool>,
+    pub opacity: Cell<f32>,
+    pub transform: Cell<Option<ElementTransform>>,
+}
+
+impl CachedElementState {
+    pub fn new(
+        rect: Rect,
+        z_index: ZIndex,
+        manually_hidden: bool,
+        class: ClassID,
+        tab_index: Option<u16>,
+        focusable: bool,
+    ) -> Self {
+        Self {
+            rect: Cell::new(rect),
+            z_index: Cell::new(z_index),
+            manually_hidden: Cell::new(manually_hidden),
+            class: Cell::new(class),
+            tab_index: Cell::new(tab_index),
+            focusable: Cell::new(focusable),
+            opacity: Cell::new(1.0),
+            transform: Cell::new(None),
+        }
+    }
+}
+
 pub struct ElementHandle {
     element_id: ElementID,
     mod_queue_sender: stmpsc_queue::Sender<ElementModification>,
-
-    rect: Rect,
-    z_index: ZIndex,
-    manually_hidden: bool,
-    class: ClassID,
+    cached: Rc<CachedElementState>,
+    destroyed: bool,
 }
 
 impl ElementHandle {
     pub(super) fn new(
         element_id: ElementID,
         mod_queue_sender: stmpsc_queue::Sender<ElementModification>,
-        rect: Rect,
-        z_index: ZIndex,
-        manually_hidden: bool,
-        class: ClassID,
+        cached: Rc<CachedElementState>,
     ) -> Self {
         Self {
             element_id,
             mod_queue_sender,
-            rect,
-            z_index,
-            manually_hidden,
-            class,
+            cached,
+            destroyed: false,
         }
     }
 
@@ -40,14 +78,14 @@ impl ElementHandle {
     ///
     /// This is cached directly in the handle so this is very cheap to call frequently.
     pub fn rect(&self) -> Rect {
-        self.rect
+        self.cached.rect.get()
     }
 
     /// Get the z index of this element instance.
     ///
     /// This is cached directly in the handle so this is very cheap to call frequently.
     pub fn z_index(&self) -> ZIndex {
-        self.z_index
+        self.cached.z_index.get()
     }
 
     /// Returns `true` if the element instance has been manually hidden.
@@ -57,7 +95,7 @@ impl ElementHandle {
     ///
     /// This is cached directly in the handle so this is very cheap to call frequently.
     pub fn manually_hidden(&self) -> bool {
-        self.manually_hidden
+        self.cached.manually_hidden.get()
     }
 
     /// Set the rectangular area of this element instance.
@@ -69,8 +107,12 @@ impl ElementHandle {
     /// This will *NOT* trigger an element update unless the value has changed,
     /// so this method is very cheap to call frequently.
     pub fn set_rect(&mut self, rect: Rect) -> bool {
-        if self.rect != rect {
-            self.rect = rect;
+        if self.destroyed {
+            return false;
+        }
+
+        if self.cached.rect.get() != rect {
+            self.cached.rect.set(rect);
             self.mod_queue_sender.send(ElementModification {
                 element_id: self.element_id,
                 type_: ElementModificationType::RectChanged(rect),
@@ -93,11 +135,18 @@ impl ElementHandle {
     /// This will *NOT* trigger an element update unless the value has changed,
     /// so this method is very cheap to call frequently.
     pub fn set_pos(&mut self, pos: Point) -> bool {
-        if self.rect.origin != pos {
-            self.rect.origin = pos;
+        if self.destroyed {
+            return false;
+        }
+
+        let mut rect = self.cached.rect.get();
+
+        if rect.origin != pos {
+            rect.origin = pos;
+            self.cached.rect.set(rect);
             self.mod_queue_sender.send(ElementModification {
                 element_id: self.element_id,
-                type_: ElementModificationType::RectChanged(self.rect),
+                type_: ElementModificationType::RectChanged(rect),
             });
             true
         } else {
@@ -117,11 +166,18 @@ impl ElementHandle {
     /// This will *NOT* trigger an element update unless the value has changed,
     /// so this method is very cheap to call frequently.
     pub fn set_size(&mut self, size: Size) -> bool {
-        if self.rect.size != size || true {
-            self.rect.size = size;
+        if self.destroyed {
+            return false;
+        }
+
+        let mut rect = self.cached.rect.get();
+
+        if rect.size != size || true {
+            rect.size = size;
+            self.cached.rect.set(rect);
             self.mod_queue_sender.send(ElementModification {
                 element_id: self.element_id,
-                type_: ElementModificationType::RectChanged(self.rect),
+                type_: ElementModificationType::RectChanged(rect),
             });
             true
         } else {
@@ -142,11 +198,18 @@ impl ElementHandle {
     /// This will *NOT* trigger an element update unless the value has changed,
     /// so this method is very cheap to call frequently.
     pub fn set_x(&mut self, x: f32) -> bool {
-        if self.rect.origin.x != x {
-            self.rect.origin.x = x;
+        if self.destroyed {
+            return false;
+        }
+
+        let mut rect = self.cached.rect.get();
+
+        if rect.origin.x != x {
+            rect.origin.x = x;
+            self.cached.rect.set(rect);
             self.mod_queue_sender.send(ElementModification {
                 element_id: self.element_id,
-                type_: ElementModificationType::RectChanged(self.rect),
+                type_: ElementModificationType::RectChanged(rect),
             });
             true
         } else {
@@ -167,11 +230,18 @@ impl ElementHandle {
     /// This will *NOT* trigger an element update unless the value has changed,
     /// so this method is very cheap to call frequently.
     pub fn set_y(&mut self, y: f32) -> bool {
-        if self.rect.origin.y != y {
-            self.rect.origin.y = y;
+        if self.destroyed {
+            return false;
+        }
+
+        let mut rect = self.cached.rect.get();
+
+        if rect.origin.y != y {
+            rect.origin.y = y;
+            self.cached.rect.set(rect);
             self.mod_queue_sender.send(ElementModification {
                 element_id: self.element_id,
-                type_: ElementModificationType::RectChanged(self.rect),
+                type_: ElementModificationType::RectChanged(rect),
             });
             true
         } else {
@@ -192,11 +262,18 @@ impl ElementHandle {
     /// This will *NOT* trigger an element update unless the value has changed,
     /// so this method is very cheap to call frequently.
     pub fn set_width(&mut self, width: f32) -> bool {
-        if self.rect.size.width != width {
-            self.rect.size.width = width;
+        if self.destroyed {
+            return false;
+        }
+
+        let mut rect = self.cached.rect.get();
+
+        if rect.size.width != width {
+            rect.size.width = width;
+            self.cached.rect.set(rect);
             self.mod_queue_sender.send(ElementModification {
                 element_id: self.element_id,
-                type_: ElementModificationType::RectChanged(self.rect),
+                type_: ElementModificationType::RectChanged(rect),
             });
             true
         } else {
@@ -217,11 +294,18 @@ impl ElementHandle {
     /// This will *NOT* trigger an element update unless the value has changed,
     /// so this method is very cheap to call frequently.
     pub fn set_height(&mut self, height: f32) -> bool {
-        if self.rect.size.height != height {
-            self.rect.size.height = height;
+        if self.destroyed {
+            return false;
+        }
+
+        let mut rect = self.cached.rect.get();
+
+        if rect.size.height != height {
+            rect.size.height = height;
+            self.cached.rect.set(rect);
             self.mod_queue_sender.send(ElementModification {
                 element_id: self.element_id,
-                type_: ElementModificationType::RectChanged(self.rect),
+                type_: ElementModificationType::RectChanged(rect),
             });
             true
         } else {
@@ -234,10 +318,16 @@ impl ElementHandle {
     /// Note, this will *always* cause an element update even if the offset
     /// is zero, so prefer to call this method sparingly.
     pub fn offset_pos(&mut self, offset: Vector) {
-        self.rect.origin += offset;
+        if self.destroyed {
+            return;
+        }
+
+        let mut rect = self.cached.rect.get();
+        rect.origin += offset;
+        self.cached.rect.set(rect);
         self.mod_queue_sender.send(ElementModification {
             element_id: self.element_id,
-            type_: ElementModificationType::RectChanged(self.rect),
+            type_: ElementModificationType::RectChanged(rect),
         });
     }
 
@@ -250,8 +340,12 @@ impl ElementHandle {
     /// This will *NOT* trigger an element update unless the value has changed,
     /// so this method is very cheap to call frequently.
     pub fn set_z_index(&mut self, z_index: ZIndex) -> bool {
-        if self.z_index != z_index {
-            self.z_index = z_index;
+        if self.destroyed {
+            return false;
+        }
+
+        if self.cached.z_index.get() != z_index {
+            self.cached.z_index.set(z_index);
             self.mod_queue_sender.send(ElementModification {
                 element_id: self.element_id,
                 type_: ElementModificationType::ZIndexChanged(z_index),
@@ -275,8 +369,12 @@ impl ElementHandle {
     /// This will *NOT* trigger an element update unless the value has changed,
     /// so this method is very cheap to call frequently.
     pub fn set_hidden(&mut self, hidden: bool) -> bool {
-        if self.manually_hidden != hidden {
-            self.manually_hidden = hidden;
+        if self.destroyed {
+            return false;
+        }
+
+        if self.cached.manually_hidden.get() != hidden {
+            self.cached.manually_hidden.set(hidden);
             self.mod_queue_sender.send(ElementModification {
                 element_id: self.element_id,
                 type_: ElementModificationType::ExplicitlyHiddenChanged(hidden),
@@ -294,13 +392,14 @@ impl ElementHandle {
     /// * `auto_hide` - Whether or not the tooltip should automatically hide when
     /// the mouse pointer is no longer over the element.
     pub fn show_tooltip(&mut self, text: impl Into<String>, align: Align2, auto_hide: bool) {
+        if self.destroyed {
+            return;
+        }
+
         self.mod_queue_sender.send(ElementModification {
             element_id: self.element_id,
             type_: ElementModificationType::ShowTooltip {
-                data: TooltipData {
-                    text: text.into(),
-                    align,
-                },
+                data: TooltipData::new(text, align),
                 auto_hide,
             },
         })
@@ -310,7 +409,7 @@ impl ElementHandle {
     ///
     /// This is cached directly in the handle so this is very cheap to call frequently.
     pub fn class(&self) -> ClassID {
-        self.class
+        self.cached.class.get()
     }
 
     /// Notify the system that this element's custom state has changed.
@@ -318,12 +417,218 @@ impl ElementHandle {
     /// Note, this will *always* cause an element update, so prefer to call this
     /// method sparingly.
     pub fn notify_custom_state_change(&mut self) {
+        if self.destroyed {
+            return;
+        }
+
         self.mod_queue_sender.send(ElementModification {
             element_id: self.element_id,
             type_: ElementModificationType::CustomStateChanged,
         });
     }
 
+    /// Request for the element to receive a single `ElementEvent::Tick` event
+    /// on the next frame.
+    pub fn request_animation(&mut self) {
+        if self.destroyed {
+            return;
+        }
+
+        self.mod_queue_sender.send(ElementModification {
+            element_id: self.element_id,
+            type_: ElementModificationType::RequestAnimation,
+        });
+    }
+
+    /// The opacity of this element instance in the range `[0.0, 1.0]`.
+    ///
+    /// If an opacity tween is in progress, then this returns the opacity the
+    /// tween has most recently applied.
+    ///
+    /// This is cached directly in the handle so this is very cheap to call frequently.
+    pub fn opacity(&self) -> f32 {
+        self.cached.opacity.get()
+    }
+
+    /// Set the opacity of this element instance in the range `[0.0, 1.0]`.
+    ///
+    /// The opacity is multiplied into the colors of the element's primitives
+    /// at render time, so this can be used to fade panels in and out (see
+    /// `ElementHandle::animate`) or to dim an element without defining extra
+    /// styles. Custom elements should pass `RenderContext::opacity` to the
+    /// styles they create primitives from, and any other colors through
+    /// `apply_opacity`.
+    ///
+    /// An update will only be sent to the view if the opacity has changed.
+    ///
+    /// Returns `true` if the opacity has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is very cheap to call frequently.
+    pub fn set_opacity(&mut self, opacity: f32) -> bool {
+        if self.destroyed {
+            return false;
+        }
+
+        let opacity = opacity.clamp(0.0, 1.0);
+
+        if self.cached.opacity.get() != opacity {
+            self.cached.opacity.set(opacity);
+            self.mod_queue_sender.send(ElementModification {
+                element_id: self.element_id,
+                type_: ElementModificationType::OpacityChanged(opacity),
+            });
+            true
+        } else {
+            false
+        }
+    }
+
+    /// The transform applied to how this element instance is drawn.
+    ///
+    /// This is cached directly in the handle so this is very cheap to call frequently.
+    pub fn transform(&self) -> Option<ElementTransform> {
+        self.cached.transform.get()
+    }
+
+    /// Rotate and/or scale how this element instance is drawn around a pivot
+    /// point, or `None` to draw it normally.
+    ///
+    /// The transform does not affect layout, it is only applied when
+    /// rendering and to hit testing (pointer events are only routed to the
+    /// element if the pointer is inside of the transformed bounding
+    /// rectangle). Positions (and movement deltas) in pointer events sent to
+    /// the element are mapped back into its untransformed bounding
+    /// rectangle, so elements do not need to be aware of their transform.
+    ///
+    /// Transformed elements are rendered into a separate texture which is then
+    /// composited onto the window, so primitives outside of the element's
+    /// bounding rectangle are clipped. An identity transform is treated the
+    /// same as `None`.
+    ///
+    /// An update will only be sent to the view if the transform has changed.
+    ///
+    /// Returns `true` if the transform has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is very cheap to call frequently.
+    pub fn set_transform(&mut self, transform: Option<ElementTransform>) -> bool {
+        if self.destroyed {
+            return false;
+        }
+
+        let transform = transform.filter(|t| !t.is_identity());
+
+        if self.cached.transform.get() != transform {
+            self.cached.transform.set(transform);
+            self.mod_queue_sender.send(ElementModification {
+                element_id: self.element_id,
+                type_: ElementModificationType::TransformChanged(transform),
+            });
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Smoothly animate a property of this element instance.
+    ///
+    /// The values cached in this handle follow the animation as it is
+    /// applied (i.e. `ElementHandle::rect()` returns the rectangle the
+    /// element was most recently moved to, not the one it is moving to).
+    /// Stopping or restarting an animation continues from that value.
+    ///
+    /// Starting a new animation of the same property replaces the previous
+    /// one. If the user prefers reduced motion, then the property will jump
+    /// to its final value on the next frame.
+    pub fn animate(&mut self, property: AnimatedProperty, transition: Transition) {
+        self.animate_inner(property, transition, None);
+    }
+
+    /// Smoothly animate a property of this element instance, and then send
+    /// the given action once the animation has finished.
+    ///
+    /// See [`ElementHandle::animate`] for more details.
+    pub fn animate_then<A: Clone + 'static>(
+        &mut self,
+        property: AnimatedProperty,
+        transition: Transition,
+        on_complete: A,
+    ) {
+        self.animate_inner(property, transition, Some(Box::new(on_complete)));
+    }
+
+    /// Stop the animation of the given property (or all properties if `None`)
+    /// at its current value. The completion action of a stopped animation is
+    /// not sent.
+    pub fn stop_animation(&mut self, kind: Option<AnimatedPropertyKind>) {
+        if self.destroyed {
+            return;
+        }
+
+        self.mod_queue_sender.send(ElementModification {
+            element_id: self.element_id,
+            type_: ElementModificationType::StopTween(kind),
+        });
+    }
+
+    fn animate_inner(
+        &mut self,
+        property: AnimatedProperty,
+        transition: Transition,
+        on_complete: Option<Box<dyn Any>>,
+    ) {
+        if self.destroyed {
+            return;
+        }
+
+        let mut el_ref = self.to_ref();
+
+        let apply: Box<dyn FnMut(f32)> = match property {
+            AnimatedProperty::Offset(offset) => {
+                let from = self.cached.rect.get();
+
+                Box::new(move |t| {
+                    el_ref.set_rect(Rect::new(from.origin + (offset * t), from.size));
+                })
+            }
+            AnimatedProperty::Opacity(opacity) => {
+                let from = self.cached.opacity.get();
+                let to = opacity.clamp(0.0, 1.0);
+
+                Box::new(move |t| {
+                    el_ref.set_opacity(from + ((to - from) * t));
+                })
+            }
+        };
+
+        self.start_tween(property.kind(), transition, apply, on_complete);
+    }
+
+    /// Start a tween managed by the element system. `apply` is called on every
+    /// animation frame with the eased progress in the range `[0.0, 1.0]`.
+    pub(crate) fn start_tween(
+        &mut self,
+        kind: AnimatedPropertyKind,
+        transition: Transition,
+        apply: Box<dyn FnMut(f32)>,
+        on_complete: Option<Box<dyn Any>>,
+    ) {
+        if self.destroyed {
+            return;
+        }
+
+        self.mod_queue_sender.send(ElementModification {
+            element_id: self.element_id,
+            type_: ElementModificationType::StartTween(Box::new(TweenRequest {
+                kind,
+                transition,
+                apply,
+                on_complete,
+            })),
+        });
+    }
+
     /// Set the class of this element instance.
     ///
     /// An update will only be sent to the view if the class has changed.
@@ -334,8 +639,12 @@ impl ElementHandle {
     /// so this method is relatively cheap to call frequently (although a
     /// string comparison is performed).
     pub fn set_class(&mut self, new_class: ClassID) -> bool {
-        if self.class != new_class {
-            self.class = new_class;
+        if self.destroyed {
+            return false;
+        }
+
+        if self.cached.class.get() != new_class {
+            self.cached.class.set(new_class);
             self.mod_queue_sender.send(ElementModification {
                 element_id: self.element_id,
                 type_: ElementModificationType::ClassChanged(new_class),
@@ -346,22 +655,148 @@ impl ElementHandle {
         }
     }
 
+    /// The explicit position of this element in the keyboard navigation order.
+    ///
+    /// This is cached directly in the handle so this is very cheap to call frequently.
+    pub fn tab_index(&self) -> Option<u16> {
+        self.cached.tab_index.get()
+    }
+
+    /// Set the explicit position of this element in the keyboard navigation
+    /// order (only has an effect if the element has the `ElementFlags::FOCUSABLE`
+    /// flag).
+    ///
+    /// Elements with an explicit tab index are visited first in ascending
+    /// order, followed by all other focusable elements in reading order.
+    ///
+    /// Returns `true` if the tab index has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn set_tab_index(&mut self, tab_index: Option<u16>) -> bool {
+        if self.destroyed {
+            return false;
+        }
+
+        if self.cached.tab_index.get() != tab_index {
+            self.cached.tab_index.set(tab_index);
+            self.mod_queue_sender.send(ElementModification {
+                element_id: self.element_id,
+                type_: ElementModificationType::TabIndexChanged(tab_index),
+            });
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Whether or not this element can currently receive focus with keyboard
+    /// navigation (see `ElementFlags::FOCUSABLE`).
+    ///
+    /// This is cached directly in the handle so this is very cheap to call frequently.
+    pub fn focusable(&self) -> bool {
+        self.cached.focusable.get()
+    }
+
+    /// Add or remove this element from the keyboard navigation order by
+    /// setting or clearing its `ElementFlags::FOCUSABLE` flag. Elements use
+    /// this to skip themselves while they are disabled.
+    ///
+    /// If the element currently has focus and becomes unfocusable, then it
+    /// will lose focus.
+    ///
+    /// Returns `true` if the value has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn set_focusable(&mut self, focusable: bool) -> bool {
+        if self.destroyed {
+            return false;
+        }
+
+        if self.cached.focusable.get() != focusable {
+            self.cached.focusable.set(focusable);
+            self.mod_queue_sender.send(ElementModification {
+                element_id: self.element_id,
+                type_: ElementModificationType::FocusableChanged(focusable),
+            });
+            true
+        } else {
+            false
+        }
+    }
+
     /// Get the actual bounding rectangle of this element, accounting for the offset
     /// introduced by its assigned scissoring rectangle.
+    ///
+    /// Returns an empty rectangle if the element has been destroyed.
     pub fn rect_in_window<A: Clone + 'static>(&self, window_cx: &WindowContext<'_, A>) -> Rect {
-        window_cx.element_rect(self).unwrap()
+        window_cx.element_rect(self).unwrap_or_default()
+    }
+
+    /// Anchor this element instance to another element instance, or remove
+    /// the anchor if `target` is `None`.
+    ///
+    /// While anchored, this element receives an `ElementEvent::AnchorRectChanged`
+    /// event whenever the rectangle of the target element changes (and once
+    /// when the anchor is set), so that it can reposition itself relative to
+    /// the target. This is used by decorations like [`Badge`](crate::elements::badge::Badge).
+    ///
+    /// Note, anchors must not form a cycle.
+    pub fn set_anchor(&mut self, target: Option<ElementID>) {
+        if self.destroyed {
+            return;
+        }
+
+        self.mod_queue_sender.send(ElementModification {
+            element_id: self.element_id,
+            type_: ElementModificationType::SetAnchor(target),
+        });
     }
 
-    pub(crate) fn id(&self) -> ElementID {
+    /// The ID of this element instance.
+    pub fn id(&self) -> ElementID {
         self.element_id
     }
+
+    /// Create a non-owning reference to this element instance.
+    pub fn to_ref(&self) -> ElementRef {
+        ElementRef::new(
+            self.element_id,
+            self.mod_queue_sender.clone(),
+            Rc::clone(&self.cached),
+        )
+    }
+
+    /// Remove this element instance from the view without dropping the
+    /// handle.
+    ///
+    /// The element is removed once the current batch of updates has been
+    /// processed, so it is safe to call this from within an action handler
+    /// (i.e. when closing a tab or removing a mixer channel).
+    ///
+    /// All methods of this handle stay safe to call after the element has
+    /// been destroyed. Setters do nothing and return `false`, and getters
+    /// return the last cached values.
+    pub fn destroy(&mut self) {
+        if !self.destroyed {
+            self.destroyed = true;
+            self.mod_queue_sender.send(ElementModification {
+                element_id: self.element_id,
+                type_: ElementModificationType::HandleDropped,
+            });
+        }
+    }
+
+    /// Returns `true` if [`ElementHandle::destroy`] has been called on this
+    /// handle.
+    pub fn is_destroyed(&self) -> bool {
+        self.destroyed
+    }
 }
 
 impl Drop for ElementHandle {
     fn drop(&mut self) {
-        self.mod_queue_sender.send(ElementModification {
-            element_id: self.element_id,
-            type_: ElementModificationType::HandleDropped,
-        });
+        self.destroy();
     }
 }
@@ -0,0 +1,260 @@
+use std::any::TypeId;
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+
+use smol_str::SmolStr;
+
+use super::handle::CachedElementState;
+use super::{ElementModificationType, ElementTransform};
+use crate::element_system::{ElementID, ElementModification};
+use crate::layout::Align2;
+use crate::math::{Rect, ZIndex};
+use crate::prelude::TooltipData;
+use crate::stmpsc_queue;
+use crate::style::ClassID;
+
+/// A user-defined ID which can be assigned to an element in its builder, and
+/// then used to look up that element later with
+/// [`WindowContext::element_by_key`](crate::WindowContext::element_by_key).
+///
+/// This is useful for reaching elements deep inside of composite widgets
+/// without having to thread their handles through the application state.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ElementKey {
+    /// A string ID.
+    Name(SmolStr),
+    /// A typed ID (see [`ElementKey::typed`]).
+    Typed { type_id: TypeId, hash: u64 },
+}
+
+impl ElementKey {
+    /// Create a string ID.
+    pub fn name(name: impl Into<SmolStr>) -> Self {
+        Self::Name(name.into())
+    }
+
+    /// Create a typed ID from any hashable value (i.e. a variant of an enum
+    /// defined by the application).
+    ///
+    /// Two typed IDs are equal if the values have the same type and the
+    /// same hash.
+    pub fn typed<K: Hash + 'static>(key: K) -> Self {
+        let mut hasher = rustc_hash::FxHasher::default();
+        key.hash(&mut hasher);
+
+        Self::Typed {
+            type_id: TypeId::of::<K>(),
+            hash: hasher.finish(),
+        }
+    }
+}
+
+impl From<&str> for ElementKey {
+    fn from(name: &str) -> Self {
+        Self::Name(SmolStr::new(name))
+    }
+}
+
+impl From<String> for ElementKey {
+    fn from(name: String) -> Self {
+        Self::Name(SmolStr::from(name))
+    }
+}
+
+impl From<SmolStr> for ElementKey {
+    fn from(name: SmolStr) -> Self {
+        Self::Name(name)
+    }
+}
+
+/// A non-owning reference to an element instance, obtained by looking up an
+/// [`ElementKey`] or with `ElementHandle::to_ref`.
+///
+/// Unlike an [`ElementHandle`](super::ElementHandle), dropping this does not
+/// remove the element. The cached values of the element are shared with its
+/// handle, so changes made through either one are seen by the other, and an
+/// update is only sent to the view if a value has changed. If the element
+/// has since been dropped, then these methods will have no effect.
+#[derive(Clone)]
+pub struct ElementRef {
+    element_id: ElementID,
+    mod_queue_sender: stmpsc_queue::Sender<ElementModification>,
+    cached: Rc<CachedElementState>,
+}
+
+impl ElementRef {
+    pub(crate) fn new(
+        element_id: ElementID,
+        mod_queue_sender: stmpsc_queue::Sender<ElementModification>,
+        cached: Rc<CachedElementState>,
+    ) -> Self {
+        Self {
+            element_id,
+            mod_queue_sender,
+            cached,
+        }
+    }
+
+    /// The ID of this element instance.
+    pub fn id(&self) -> ElementID {
+        self.element_id
+    }
+
+    /// The bounding rectangle of this element instance.
+    pub fn rect(&self) -> Rect {
+        self.cached.rect.get()
+    }
+
+    /// The z index of this element instance.
+    pub fn z_index(&self) -> ZIndex {
+        self.cached.z_index.get()
+    }
+
+    /// Returns `true` if the element instance has been manually hidden.
+    pub fn manually_hidden(&self) -> bool {
+        self.cached.manually_hidden.get()
+    }
+
+    /// The current style class of this element instance.
+    pub fn class(&self) -> ClassID {
+        self.cached.class.get()
+    }
+
+    /// The opacity of this element instance in the range `[0.0, 1.0]`.
+    pub fn opacity(&self) -> f32 {
+        self.cached.opacity.get()
+    }
+
+    /// The transform applied to how this element instance is drawn.
+    pub fn transform(&self) -> Option<ElementTransform> {
+        self.cached.transform.get()
+    }
+
+    /// Set the rectangular area of this element instance.
+    ///
+    /// Returns `true` if the rectangle has changed.
+    pub fn set_rect(&mut self, rect: Rect) -> bool {
+        if self.cached.rect.get() == rect {
+            return false;
+        }
+
+        self.cached.rect.set(rect);
+        self.send(ElementModificationType::RectChanged(rect));
+        true
+    }
+
+    /// Set the z index of this element instance.
+    ///
+    /// Returns `true` if the z index has changed.
+    pub fn set_z_index(&mut self, z_index: ZIndex) -> bool {
+        if self.cached.z_index.get() == z_index {
+            return false;
+        }
+
+        self.cached.z_index.set(z_index);
+        self.send(ElementModificationType::ZIndexChanged(z_index));
+        true
+    }
+
+    /// Set to hide or show this element instance.
+    ///
+    /// Returns `true` if the hidden state has changed.
+    pub fn set_hidden(&mut self, hidden: bool) -> bool {
+        if self.cached.manually_hidden.get() == hidden {
+            return false;
+        }
+
+        self.cached.manually_hidden.set(hidden);
+        self.send(ElementModificationType::ExplicitlyHiddenChanged(hidden));
+        true
+    }
+
+    /// Set the class of this element instance.
+    ///
+    /// Returns `true` if the class has changed.
+    pub fn set_class(&mut self, class: ClassID) -> bool {
+        if self.cached.class.get() == class {
+            return false;
+        }
+
+        self.cached.class.set(class);
+        self.send(ElementModificationType::ClassChanged(class));
+        true
+    }
+
+    /// Set the explicit position of this element in the keyboard navigation
+    /// order.
+    ///
+    /// Returns `true` if the tab index has changed.
+    pub fn set_tab_index(&mut self, tab_index: Option<u16>) -> bool {
+        if self.cached.tab_index.get() == tab_index {
+            return false;
+        }
+
+        self.cached.tab_index.set(tab_index);
+        self.send(ElementModificationType::TabIndexChanged(tab_index));
+        true
+    }
+
+    /// Show a tooltip on the element
+    ///
+    /// * `text` - The tooltip text
+    /// * `align` - Where to align the tooltip relative to this element
+    /// * `auto_hide` - Whether or not the tooltip should automatically hide when
+    /// the mouse pointer is no longer over the element.
+    pub fn show_tooltip(&mut self, text: impl Into<String>, align: Align2, auto_hide: bool) {
+        self.send(ElementModificationType::ShowTooltip {
+            data: TooltipData::new(text, align),
+            auto_hide,
+        });
+    }
+
+    /// Set the opacity of this element instance in the range `[0.0, 1.0]`.
+    ///
+    /// Returns `true` if the opacity has changed.
+    pub fn set_opacity(&mut self, opacity: f32) -> bool {
+        let opacity = opacity.clamp(0.0, 1.0);
+
+        if self.cached.opacity.get() == opacity {
+            return false;
+        }
+
+        self.cached.opacity.set(opacity);
+        self.send(ElementModificationType::OpacityChanged(opacity));
+        true
+    }
+
+    /// Rotate and/or scale how this element instance is drawn, or `None` to
+    /// draw it normally (see `ElementHandle::set_transform`).
+    ///
+    /// Returns `true` if the transform has changed.
+    pub fn set_transform(&mut self, transform: Option<ElementTransform>) -> bool {
+        let transform = transform.filter(|t| !t.is_identity());
+
+        if self.cached.transform.get() == transform {
+            return false;
+        }
+
+        self.cached.transform.set(transform);
+        self.send(ElementModificationType::TransformChanged(transform));
+        true
+    }
+
+    /// Notify the system that this element's custom state has changed.
+    pub fn notify_custom_state_change(&mut self) {
+        self.send(ElementModificationType::CustomStateChanged);
+    }
+
+    /// Request for the element to receive a single `ElementEvent::Tick` event
+    /// on the next frame.
+    pub fn request_animation(&mut self) {
+        self.send(ElementModificationType::RequestAnimation);
+    }
+
+    fn send(&mut self, type_: ElementModificationType) {
+        self.mod_queue_sender.send(ElementModification {
+            element_id: self.element_id,
+            type_,
+        });
+    }
+}
@@ -0,0 +1,80 @@
+use derive_where::derive_where;
+
+use crate::action_queue::ActionSender;
+use crate::element_system::ElementID;
+use crate::math::Rect;
+
+/// Optional callbacks which are called when an element is added to or
+/// removed from the view, shown, hidden, or resized.
+///
+/// This is useful for lazily starting and stopping expensive per-element
+/// work (i.e. an audio analysis tap which is only needed while a meter is
+/// visible).
+///
+/// See [`ElementBuilder::on_mount`](super::ElementBuilder::on_mount) and
+/// related methods.
+#[derive_where(Default)]
+pub struct LifecycleHooks<A: Clone + 'static> {
+    pub(crate) on_mount: Option<Box<dyn FnMut(Rect) -> A>>,
+    pub(crate) on_unmount: Option<Box<dyn FnMut() -> A>>,
+    pub(crate) on_shown: Option<Box<dyn FnMut() -> A>>,
+    pub(crate) on_hidden: Option<Box<dyn FnMut() -> A>>,
+    pub(crate) on_rect_changed: Option<Box<dyn FnMut(Rect) -> A>>,
+}
+
+impl<A: Clone + 'static> LifecycleHooks<A> {
+    pub(crate) fn mounted(
+        &mut self,
+        element_id: ElementID,
+        rect: Rect,
+        action_sender: &mut ActionSender<A>,
+    ) {
+        if let Some(f) = &mut self.on_mount {
+            send_from_element(element_id, (f)(rect), action_sender);
+        }
+    }
+
+    pub(crate) fn unmounted(&mut self, element_id: ElementID, action_sender: &mut ActionSender<A>) {
+        if let Some(f) = &mut self.on_unmount {
+            send_from_element(element_id, (f)(), action_sender);
+        }
+    }
+
+    pub(crate) fn visibility_changed(
+        &mut self,
+        element_id: ElementID,
+        visible: bool,
+        action_sender: &mut ActionSender<A>,
+    ) {
+        let f = if visible {
+            &mut self.on_shown
+        } else {
+            &mut self.on_hidden
+        };
+
+        if let Some(f) = f {
+            send_from_element(element_id, (f)(), action_sender);
+        }
+    }
+
+    pub(crate) fn rect_changed(
+        &mut self,
+        element_id: ElementID,
+        rect: Rect,
+        action_sender: &mut ActionSender<A>,
+    ) {
+        if let Some(f) = &mut self.on_rect_changed {
+            send_from_element(element_id, (f)(rect), action_sender);
+        }
+    }
+}
+
+fn send_from_element<A: Clone + 'static>(
+    element_id: ElementID,
+    action: A,
+    action_sender: &mut ActionSender<A>,
+) {
+    action_sender.set_source_element(Some(element_id));
+    action_sender.send(action).unwrap();
+    action_sender.set_source_element(None);
+}
@@ -0,0 +1,119 @@
+use crate::math::{point, Angle, Point, Rect, Transform};
+
+/// A 2D transform applied to how an element is drawn, set with
+/// `ElementHandle::set_transform`.
+///
+/// The element is scaled and then rotated around its pivot point. Hit testing
+/// applies the inverse of the transform to the pointer position, so only the
+/// transformed area of the element receives pointer events. The layout of the
+/// element (its bounding rectangle) is unaffected.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ElementTransform {
+    /// The clockwise rotation of the element around the pivot point.
+    pub rotation: Angle,
+    /// The horizontal scale of the element around the pivot point.
+    pub scale_x: f32,
+    /// The vertical scale of the element around the pivot point.
+    pub scale_y: f32,
+    /// The point the element is rotated and scaled around, normalized to
+    /// the range `[0.0, 1.0]` within the element's bounding rectangle.
+    ///
+    /// By default this is the center of the element `(0.5, 0.5)`.
+    pub pivot: Point,
+}
+
+impl ElementTransform {
+    /// A transform which does nothing.
+    pub const IDENTITY: Self = Self {
+        rotation: Angle { radians: 0.0 },
+        scale_x: 1.0,
+        scale_y: 1.0,
+        pivot: Point::new(0.5, 0.5),
+    };
+
+    /// A rotation around the center of the element.
+    pub fn rotation(rotation: Angle) -> Self {
+        Self {
+            rotation,
+            ..Self::IDENTITY
+        }
+    }
+
+    /// A scale around the center of the element.
+    pub fn scale(scale_x: f32, scale_y: f32) -> Self {
+        Self {
+            scale_x,
+            scale_y,
+            ..Self::IDENTITY
+        }
+    }
+
+    pub fn with_rotation(mut self, rotation: Angle) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    pub fn with_scale(mut self, scale_x: f32, scale_y: f32) -> Self {
+        self.scale_x = scale_x;
+        self.scale_y = scale_y;
+        self
+    }
+
+    pub fn with_pivot(mut self, pivot: Point) -> Self {
+        self.pivot = pivot;
+        self
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.rotation.radians == 0.0 && self.scale_x == 1.0 && self.scale_y == 1.0
+    }
+
+    /// The pivot point in window coordinates for an element with the given
+    /// bounding rectangle.
+    pub fn pivot_in(&self, bounds: Rect) -> Point {
+        point(
+            bounds.min_x() + bounds.width() * self.pivot.x,
+            bounds.min_y() + bounds.height() * self.pivot.y,
+        )
+    }
+
+    /// The transform which maps a point in the untransformed bounding
+    /// rectangle to where it is drawn in the window.
+    pub fn to_transform(&self, bounds: Rect) -> Transform {
+        let pivot = self.pivot_in(bounds).to_vector();
+
+        Transform::translation(-pivot.x, -pivot.y)
+            .then_scale(self.scale_x, self.scale_y)
+            .then_rotate(self.rotation)
+            .then_translate(pivot)
+    }
+
+    /// Map a point in the window back to the untransformed bounding rectangle.
+    ///
+    /// Returns `None` if the transform can not be inverted (i.e. one of the
+    /// scale factors is zero).
+    pub fn inverse_transform_point(&self, bounds: Rect, pos: Point) -> Option<Point> {
+        self.to_transform(bounds)
+            .inverse()
+            .map(|inverse| inverse.transform_point(pos))
+    }
+
+    /// Whether the given point in the window lies inside the transformed
+    /// bounding rectangle.
+    pub fn contains(&self, bounds: Rect, pos: Point) -> bool {
+        self.inverse_transform_point(bounds, pos)
+            .is_some_and(|pos| bounds.contains(pos))
+    }
+
+    /// The smallest axis-aligned rectangle which contains the transformed
+    /// bounding rectangle.
+    pub fn outer_rect(&self, bounds: Rect) -> Rect {
+        self.to_transform(bounds).outer_transformed_rect(&bounds)
+    }
+}
+
+impl Default for ElementTransform {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
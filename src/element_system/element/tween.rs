@@ -0,0 +1,89 @@
+use std::any::Any;
+
+use crate::element_system::ElementID;
+use crate::math::Vector;
+use crate::style::Transition;
+
+/// A property of an element which can be animated with
+/// [`ElementHandle::animate`](super::ElementHandle::animate).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AnimatedProperty {
+    /// Move the element by the given offset from its current position.
+    Offset(Vector),
+    /// Fade the element to the given opacity in the range `[0.0, 1.0]`.
+    ///
+    /// See `ElementHandle::set_opacity` for how the opacity is applied.
+    Opacity(f32),
+}
+
+impl AnimatedProperty {
+    pub fn kind(&self) -> AnimatedPropertyKind {
+        match self {
+            Self::Offset(_) => AnimatedPropertyKind::Offset,
+            Self::Opacity(_) => AnimatedPropertyKind::Opacity,
+        }
+    }
+}
+
+/// The kind of property animated by a tween.
+///
+/// An element can only have a single active tween of each kind. Starting a
+/// new tween replaces the previous one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AnimatedPropertyKind {
+    Offset,
+    Opacity,
+    /// The normalized value of a parameter element (i.e. a knob or slider).
+    NormalValue,
+}
+
+/// A request to start a tween, sent from an element handle to the element
+/// system.
+pub(crate) struct TweenRequest {
+    pub kind: AnimatedPropertyKind,
+    pub transition: Transition,
+    /// Called on every animation frame with the eased progress in the range
+    /// `[0.0, 1.0]`.
+    pub apply: Box<dyn FnMut(f32)>,
+    /// The action to send once the tween has finished (type-erased so that
+    /// handles do not need to be generic over the action type).
+    pub on_complete: Option<Box<dyn Any>>,
+}
+
+/// A tween which is currently in progress.
+pub(crate) struct ActiveTween {
+    pub element_id: ElementID,
+    pub request: TweenRequest,
+    pub elapsed_seconds: f64,
+}
+
+impl ActiveTween {
+    pub fn new(element_id: ElementID, request: TweenRequest) -> Self {
+        Self {
+            element_id,
+            request,
+            elapsed_seconds: 0.0,
+        }
+    }
+
+    /// Advance the tween by the given amount of time and apply the new value.
+    ///
+    /// If `reduce_motion` is `true`, then the tween jumps to its final value.
+    ///
+    /// Returns `true` if the tween has finished.
+    pub fn advance(&mut self, delta_seconds: f64, reduce_motion: bool) -> bool {
+        self.elapsed_seconds += delta_seconds;
+
+        let finished =
+            self.elapsed_seconds >= self.request.transition.duration.as_secs_f64() || reduce_motion;
+
+        let progress = if finished {
+            1.0
+        } else {
+            self.request.transition.progress(self.elapsed_seconds)
+        };
+        (self.request.apply)(progress);
+
+        finished
+    }
+}
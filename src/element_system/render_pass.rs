@@ -0,0 +1,117 @@
+use std::time::Duration;
+use web_time::Instant;
+
+use crate::math::{PhysicalSizeI32, Rect, RectI32, ScaleFactor};
+
+use super::element::ElementID;
+
+/// A user-provided wgpu render pass which is drawn inside the rect of a
+/// placeholder element (see `WindowContext::add_render_pass`).
+///
+/// The frame is split at the z index of the placeholder element. Elements
+/// with a lower z index are drawn before the render pass, and elements with
+/// the same or a higher z index are drawn on top of it. This makes it
+/// possible to draw i.e. a 3D visualizer below the controls of a view.
+pub trait CustomRenderPass: 'static {
+    /// Encode the render pass.
+    ///
+    /// The target texture already contains the elements drawn below the
+    /// placeholder element, so the render pass should load (not clear) it and
+    /// restrict drawing to [`RenderPassContext::viewport`].
+    fn render(&mut self, cx: RenderPassContext<'_>);
+
+    /// If this returns `true`, then the window is redrawn every frame while
+    /// the placeholder element is visible (i.e. for animations).
+    fn is_animating(&self) -> bool {
+        false
+    }
+
+    /// If this returns `true`, then while the placeholder element is visible
+    /// the frame is rendered into an intermediate texture which can be copied
+    /// from, so that [`RenderPassContext::target_texture`] is available (i.e.
+    /// for effects which sample what was drawn below them).
+    ///
+    /// This adds an extra copy of the whole frame, so only return `true` if
+    /// the target texture is actually needed.
+    fn needs_target_texture(&self) -> bool {
+        false
+    }
+}
+
+/// The context passed to [`CustomRenderPass::render`].
+pub struct RenderPassContext<'a> {
+    pub device: &'a wgpu::Device,
+    pub queue: &'a wgpu::Queue,
+    pub encoder: &'a mut wgpu::CommandEncoder,
+    /// The texture which the frame is being rendered into.
+    pub target: &'a wgpu::TextureView,
+    /// The texture which `target` is a view of, if it can be copied from
+    /// (`wgpu::TextureUsages::COPY_SRC`).
+    ///
+    /// This is always available when [`CustomRenderPass::needs_target_texture`]
+    /// returns `true`, except when rendering into a texture provided by the
+    /// application.
+    pub target_texture: Option<&'a wgpu::Texture>,
+    pub target_format: wgpu::TextureFormat,
+    /// The size of the target texture in physical pixels.
+    pub target_size: PhysicalSizeI32,
+    /// The visible part of the placeholder element in physical pixels. Pass
+    /// this to `wgpu::RenderPass::set_viewport` or
+    /// `wgpu::RenderPass::set_scissor_rect`.
+    ///
+    /// This is clipped to the scissor rect of the placeholder element.
+    pub viewport: RectI32,
+    /// The full rect of the placeholder element in logical points.
+    pub bounds: Rect,
+    pub scale_factor: ScaleFactor,
+    pub timing: FrameTiming,
+}
+
+/// The timing of the frame passed to [`CustomRenderPass::render`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrameTiming {
+    /// The time at which this frame is being rendered.
+    pub now: Instant,
+    /// The time since this render pass was last rendered, or zero if this is
+    /// the first time it is rendered.
+    pub delta: Duration,
+    /// The number of times this render pass has been rendered before.
+    pub frame: u64,
+}
+
+pub(super) struct RenderPassEntry {
+    pub element_id: ElementID,
+    pub pass: Box<dyn CustomRenderPass>,
+    pub last_rendered: Option<Instant>,
+    pub frame: u64,
+}
+
+impl RenderPassEntry {
+    pub fn new(element_id: ElementID, pass: Box<dyn CustomRenderPass>) -> Self {
+        Self {
+            element_id,
+            pass,
+            last_rendered: None,
+            frame: 0,
+        }
+    }
+
+    pub fn next_timing(&mut self) -> FrameTiming {
+        let now = Instant::now();
+        let delta = self
+            .last_rendered
+            .map(|last| now - last)
+            .unwrap_or_default();
+
+        let timing = FrameTiming {
+            now,
+            delta,
+            frame: self.frame,
+        };
+
+        self.last_rendered = Some(now);
+        self.frame += 1;
+
+        timing
+    }
+}
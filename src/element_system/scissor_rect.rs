@@ -25,6 +25,7 @@ impl Default for ScissorRectID {
 pub(super) struct ScissorRect {
     rect: RectI32,
     scroll_offset: Vector,
+    zoom: f32,
     assigned_elements: Vec<ElementID>,
 }
 
@@ -36,6 +37,7 @@ impl ScissorRect {
         Self {
             rect,
             scroll_offset,
+            zoom: 1.0,
             assigned_elements: Vec::new(),
         }
     }
@@ -53,6 +55,7 @@ impl ScissorRect {
         &mut self,
         mut new_rect: Option<RectI32>,
         new_scroll_offset: Option<Vector>,
+        new_zoom: Option<f32>,
         mod_queue_sender: &mut stmpsc_queue::Sender<ElementModification>,
     ) -> bool {
         let mut changed = false;
@@ -74,6 +77,13 @@ impl ScissorRect {
             }
         }
 
+        if let Some(new_zoom) = new_zoom {
+            if self.zoom != new_zoom {
+                self.zoom = new_zoom;
+                changed = true;
+            }
+        }
+
         if changed {
             for element_id in self.assigned_elements.iter() {
                 mod_queue_sender.send(ElementModification {
@@ -94,6 +104,12 @@ impl ScissorRect {
         self.scroll_offset
     }
 
+    /// The factor that the offsets and sizes of the assigned elements are
+    /// multiplied by.
+    pub fn zoom(&self) -> f32 {
+        self.zoom
+    }
+
     pub fn add_element(&mut self, entry_stack_data: &mut EntryStackData, element_id: ElementID) {
         entry_stack_data.index_in_scissor_rect_list = self.assigned_elements.len() as u32;
 
@@ -0,0 +1,176 @@
+use rustc_hash::FxHashMap;
+use smallvec::SmallVec;
+
+use crate::math::Point;
+
+use super::CachedElementRectForPointerEvent;
+
+/// The width and height of a cell in the grid in points.
+const CELL_SIZE: f32 = 64.0;
+
+/// A uniform grid which maps regions of the window to the elements which
+/// listen to pointer events in those regions.
+///
+/// This allows pointer events to only be tested against the elements near
+/// the pointer instead of every element in the window, which matters for
+/// UIs with thousands of cells (i.e. a step sequencer).
+#[derive(Default)]
+pub(super) struct PointerGrid {
+    /// The indexes into the list of elements listening to pointer events,
+    /// sorted from lowest to highest z index.
+    cells: FxHashMap<(i32, i32), SmallVec<[u32; 8]>>,
+    /// The range of cells each element is currently inserted into, in the
+    /// same order as the list of elements listening to pointer events.
+    placements: Vec<Option<CellRange>>,
+    /// The indexes of the elements whose cells need to be updated.
+    dirty_elements: Vec<u32>,
+    /// Whether the whole grid needs to be rebuilt.
+    dirty: bool,
+}
+
+impl PointerGrid {
+    /// Mark the whole grid as needing to be rebuilt.
+    ///
+    /// This must be called whenever the list of elements is added to,
+    /// removed from, or re-sorted, since the indexes in the grid are then no
+    /// longer valid.
+    pub fn mark_dirty(&mut self) {
+        self.dirty = true;
+    }
+
+    /// Mark the cells of the element at the given index in the list as
+    /// needing to be updated (i.e. because its rectangle, visibility, or
+    /// occlusion has changed).
+    pub fn mark_element_dirty(&mut self, index: u32) {
+        if self.dirty {
+            return;
+        }
+
+        // Rebuilding is cheaper than updating if most elements have changed
+        // since the last update.
+        if self.dirty_elements.len() >= self.placements.len() {
+            self.dirty = true;
+            self.dirty_elements.clear();
+            return;
+        }
+
+        self.dirty_elements.push(index);
+    }
+
+    /// Update the grid if the layout has changed since it was last updated.
+    ///
+    /// The given list must already be sorted by z index.
+    pub fn update(&mut self, elements: &[CachedElementRectForPointerEvent]) {
+        if self.dirty || self.placements.len() != elements.len() {
+            self.rebuild(elements);
+            return;
+        }
+
+        for index in std::mem::take(&mut self.dirty_elements) {
+            let Some(cache) = elements.get(index as usize) else {
+                continue;
+            };
+
+            let old_placement = self.placements[index as usize];
+            let new_placement = placement(cache);
+            if old_placement == new_placement {
+                continue;
+            }
+
+            if let Some(range) = old_placement {
+                for coord in range.coords() {
+                    let Some(cell) = self.cells.get_mut(&coord) else {
+                        continue;
+                    };
+
+                    if let Ok(i) = cell.binary_search(&index) {
+                        cell.remove(i);
+                    }
+                    if cell.is_empty() {
+                        self.cells.remove(&coord);
+                    }
+                }
+            }
+
+            if let Some(range) = new_placement {
+                // The indexes are in the same order as the z indexes, so
+                // keeping each cell sorted by index keeps it sorted by z index.
+                for coord in range.coords() {
+                    let cell = self.cells.entry(coord).or_default();
+                    if let Err(i) = cell.binary_search(&index) {
+                        cell.insert(i, index);
+                    }
+                }
+            }
+
+            self.placements[index as usize] = new_placement;
+        }
+    }
+
+    fn rebuild(&mut self, elements: &[CachedElementRectForPointerEvent]) {
+        self.dirty = false;
+        self.dirty_elements.clear();
+
+        for cell in self.cells.values_mut() {
+            cell.clear();
+        }
+
+        self.placements.clear();
+        self.placements.reserve(elements.len());
+
+        for (i, cache) in elements.iter().enumerate() {
+            let placement = placement(cache);
+            self.placements.push(placement);
+
+            if let Some(range) = placement {
+                for coord in range.coords() {
+                    self.cells.entry(coord).or_default().push(i as u32);
+                }
+            }
+        }
+
+        self.cells.retain(|_, cell| !cell.is_empty());
+    }
+
+    /// The indexes of the elements which may contain the given point, sorted
+    /// from lowest to highest z index.
+    pub fn candidates(&self, pos: Point) -> &[u32] {
+        self.cells
+            .get(&cell_coord(pos))
+            .map(|cell| cell.as_slice())
+            .unwrap_or(&[])
+    }
+}
+
+fn cell_coord(pos: Point) -> (i32, i32) {
+    (
+        (pos.x / CELL_SIZE).floor() as i32,
+        (pos.y / CELL_SIZE).floor() as i32,
+    )
+}
+
+/// An inclusive range of cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct CellRange {
+    min: (i32, i32),
+    max: (i32, i32),
+}
+
+impl CellRange {
+    fn coords(self) -> impl Iterator<Item = (i32, i32)> {
+        (self.min.1..=self.max.1).flat_map(move |y| (self.min.0..=self.max.0).map(move |x| (x, y)))
+    }
+}
+
+/// The range of cells the given element is inserted into, or `None` if it
+/// cannot receive pointer events.
+fn placement(cache: &CachedElementRectForPointerEvent) -> Option<CellRange> {
+    if cache.occluded {
+        return None;
+    }
+
+    cache.visible_rect.map(|rect| CellRange {
+        min: cell_coord(rect.min()),
+        max: cell_coord(rect.max()),
+    })
+}
@@ -0,0 +1,76 @@
+use std::time::Duration;
+
+/// Statistics about the last frame rendered in a window.
+///
+/// These can be used to find out which parts of a GUI are slow. All times
+/// are measured on the CPU.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct FrameStats {
+    /// The total number of elements in the window.
+    pub num_elements: usize,
+    /// The number of elements which paint to the screen.
+    pub num_painted_elements: usize,
+    /// The number of elements which were drawn in this frame.
+    pub num_drawn_elements: usize,
+    /// The number of elements whose primitives were regenerated in this
+    /// frame (the rest reused their cached primitives).
+    pub num_rerendered_elements: usize,
+    /// The number of elements which were skipped because they were
+    /// completely covered by an opaque element.
+    pub num_occluded_elements: usize,
+    /// The number of shaped text layouts in the shared text layout cache.
+    pub num_cached_text_layouts: usize,
+    /// Whether only part of the window was redrawn in this frame.
+    pub partial: bool,
+
+    /// The time spent routing events to elements since the previous frame.
+    pub event_routing_time: Duration,
+    /// The time spent processing element modifications (layout changes,
+    /// custom state changes, etc.) since the previous frame.
+    pub update_time: Duration,
+    /// The time spent regenerating the primitives of elements.
+    pub primitive_time: Duration,
+    /// The time spent preparing and encoding the render pass.
+    pub render_time: Duration,
+    /// The time spent submitting the commands to the GPU and presenting the
+    /// frame.
+    pub present_time: Duration,
+}
+
+impl FrameStats {
+    /// The total time spent on this frame.
+    pub fn total_time(&self) -> Duration {
+        self.event_routing_time
+            + self.update_time
+            + self.primitive_time
+            + self.render_time
+            + self.present_time
+    }
+
+    pub(super) fn overlay_text(&self) -> String {
+        fn ms(d: Duration) -> f64 {
+            d.as_secs_f64() * 1000.0
+        }
+
+        format!(
+            "elements: {} ({} painted, {} drawn, {} rerendered, {} occluded)\n\
+             text layouts: {}{}\n\
+             events: {:.2}ms  updates: {:.2}ms\n\
+             primitives: {:.2}ms  render: {:.2}ms  present: {:.2}ms\n\
+             total: {:.2}ms",
+            self.num_elements,
+            self.num_painted_elements,
+            self.num_drawn_elements,
+            self.num_rerendered_elements,
+            self.num_occluded_elements,
+            self.num_cached_text_layouts,
+            if self.partial { "  (partial)" } else { "" },
+            ms(self.event_routing_time),
+            ms(self.update_time),
+            ms(self.primitive_time),
+            ms(self.render_time),
+            ms(self.present_time),
+            ms(self.total_time()),
+        )
+    }
+}
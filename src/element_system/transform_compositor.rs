@@ -0,0 +1,347 @@
+use std::borrow::Cow;
+
+use rustc_hash::FxHashMap;
+
+use crate::math::{PhysicalPoint, PhysicalRectI32, PhysicalSizeI32};
+
+use super::ElementID;
+
+const SHADER: &str = r#"
+struct Params {
+    // The positions of the top-left and top-right corners in clip space.
+    top: vec4<f32>,
+    // The positions of the bottom-left and bottom-right corners in clip space.
+    bottom: vec4<f32>,
+    // The texture coordinates of the bottom-right corner.
+    uv_max: vec4<f32>,
+};
+
+@group(0) @binding(0) var src_texture: texture_2d<f32>;
+@group(0) @binding(1) var src_sampler: sampler;
+@group(0) @binding(2) var<uniform> params: Params;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    // A triangle strip with the corners in the order top-left, top-right,
+    // bottom-left, bottom-right.
+    let corner = vec2<f32>(f32(vertex_index & 1u), f32(vertex_index >> 1u));
+
+    var pos: vec2<f32>;
+    switch vertex_index {
+        case 0u: { pos = params.top.xy; }
+        case 1u: { pos = params.top.zw; }
+        case 2u: { pos = params.bottom.xy; }
+        default: { pos = params.bottom.zw; }
+    }
+
+    var out: VertexOutput;
+    out.position = vec4<f32>(pos, 0.0, 1.0);
+    out.uv = corner * params.uv_max.xy;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    return textureSample(src_texture, src_sampler, in.uv);
+}
+"#;
+
+/// The size of the uniform buffer of a layer in bytes.
+const PARAMS_SIZE: u64 = 3 * 4 * std::mem::size_of::<f32>() as u64;
+
+/// The texture a transformed element is rendered into.
+struct Layer {
+    texture: wgpu::Texture,
+    bind_group: wgpu::BindGroup,
+    params_buffer: wgpu::Buffer,
+    used: bool,
+}
+
+/// Composites elements with a transform (see `ElementHandle::set_transform`)
+/// onto the frame.
+///
+/// Each transformed element is rendered into its own texture at its
+/// untransformed size, and then that texture is drawn as a transformed quad.
+pub(super) struct TransformCompositor {
+    pipeline: wgpu::RenderPipeline,
+    bind_group_layout: wgpu::BindGroupLayout,
+    sampler: wgpu::Sampler,
+    format: wgpu::TextureFormat,
+    layers: FxHashMap<ElementID, Layer>,
+}
+
+impl TransformCompositor {
+    pub fn new(device: &wgpu::Device, format: wgpu::TextureFormat) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("yarrow transform compositor shader"),
+            source: wgpu::ShaderSource::Wgsl(Cow::Borrowed(SHADER)),
+        });
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("yarrow transform compositor bind group layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::VERTEX,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("yarrow transform compositor pipeline layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("yarrow transform compositor pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: "vs_main",
+                compilation_options: Default::default(),
+                buffers: &[],
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: "fs_main",
+                compilation_options: Default::default(),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    // The layers are cleared to transparent before being
+                    // rendered into, so their colors are premultiplied.
+                    blend: Some(wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleStrip,
+                ..Default::default()
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("yarrow transform compositor sampler"),
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        Self {
+            pipeline,
+            bind_group_layout,
+            sampler,
+            format,
+            layers: FxHashMap::default(),
+        }
+    }
+
+    pub fn format(&self) -> wgpu::TextureFormat {
+        self.format
+    }
+
+    /// The view of the texture to render the given element into.
+    ///
+    /// The texture is reused between frames as long as its size does not
+    /// change.
+    pub fn layer_view(
+        &mut self,
+        device: &wgpu::Device,
+        element_id: ElementID,
+        size: PhysicalSizeI32,
+    ) -> wgpu::TextureView {
+        let size = wgpu::Extent3d {
+            width: size.width.max(1) as u32,
+            height: size.height.max(1) as u32,
+            depth_or_array_layers: 1,
+        };
+
+        let needs_recreate = self
+            .layers
+            .get(&element_id)
+            .map_or(true, |layer| layer.texture.size() != size);
+
+        if needs_recreate {
+            let texture = device.create_texture(&wgpu::TextureDescriptor {
+                label: Some("yarrow transformed element layer"),
+                size,
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: self.format,
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                    | wgpu::TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            });
+
+            let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+            let params_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("yarrow transformed element layer params"),
+                size: PARAMS_SIZE,
+                usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            });
+
+            let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("yarrow transformed element layer bind group"),
+                layout: &self.bind_group_layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::TextureView(&view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::Sampler(&self.sampler),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: params_buffer.as_entire_binding(),
+                    },
+                ],
+            });
+
+            self.layers.insert(
+                element_id,
+                Layer {
+                    texture,
+                    bind_group,
+                    params_buffer,
+                    used: false,
+                },
+            );
+        }
+
+        let layer = self.layers.get_mut(&element_id).unwrap();
+        layer.used = true;
+        layer
+            .texture
+            .create_view(&wgpu::TextureViewDescriptor::default())
+    }
+
+    /// Draw the texture of the given element onto `target`.
+    ///
+    /// * `corners` - Where the top-left, top-right, bottom-left, and
+    /// bottom-right corners of the element are drawn, in physical pixels.
+    /// * `content_size` - The size of the element in physical pixels, which
+    /// may be slightly smaller than its texture.
+    /// * `scissor_rect` - The region of the target to draw in, in physical
+    /// pixels.
+    #[allow(clippy::too_many_arguments)]
+    pub fn encode(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        queue: &wgpu::Queue,
+        target: &wgpu::TextureView,
+        target_size: PhysicalSizeI32,
+        element_id: ElementID,
+        corners: [PhysicalPoint; 4],
+        content_size: [f32; 2],
+        scissor_rect: PhysicalRectI32,
+    ) {
+        let Some(layer) = self.layers.get(&element_id) else {
+            return;
+        };
+
+        let min_x = scissor_rect.min_x().clamp(0, target_size.width);
+        let min_y = scissor_rect.min_y().clamp(0, target_size.height);
+        let max_x = scissor_rect.max_x().clamp(min_x, target_size.width);
+        let max_y = scissor_rect.max_y().clamp(min_y, target_size.height);
+
+        if max_x == min_x || max_y == min_y {
+            return;
+        }
+
+        let to_clip = |p: PhysicalPoint| -> [f32; 2] {
+            [
+                p.x / target_size.width as f32 * 2.0 - 1.0,
+                1.0 - p.y / target_size.height as f32 * 2.0,
+            ]
+        };
+        let [tl, tr, bl, br] = corners.map(to_clip);
+
+        let texture_size = layer.texture.size();
+        let params: [f32; 12] = [
+            tl[0],
+            tl[1],
+            tr[0],
+            tr[1],
+            bl[0],
+            bl[1],
+            br[0],
+            br[1],
+            content_size[0] / texture_size.width as f32,
+            content_size[1] / texture_size.height as f32,
+            0.0,
+            0.0,
+        ];
+        let bytes: Vec<u8> = params.iter().flat_map(|v| v.to_ne_bytes()).collect();
+        queue.write_buffer(&layer.params_buffer, 0, &bytes);
+
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("yarrow transform compositor pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: target,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        pass.set_scissor_rect(
+            min_x as u32,
+            min_y as u32,
+            (max_x - min_x) as u32,
+            (max_y - min_y) as u32,
+        );
+        pass.set_pipeline(&self.pipeline);
+        pass.set_bind_group(0, &layer.bind_group, &[]);
+        pass.draw(0..4, 0..1);
+    }
+
+    /// Free the textures of elements which have not been rendered since the
+    /// last call to this method.
+    pub fn collect_unused(&mut self) {
+        self.layers
+            .retain(|_, layer| std::mem::take(&mut layer.used));
+    }
+}
@@ -0,0 +1,247 @@
+use crate::prelude::*;
+
+/// A slot of an [`AbCompare`] widget.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AbSlot {
+    #[default]
+    A,
+    B,
+}
+
+impl AbSlot {
+    /// The other slot.
+    pub const fn other(&self) -> Self {
+        match self {
+            Self::A => Self::B,
+            Self::B => Self::A,
+        }
+    }
+}
+
+/// A standard A/B compare control for plugin headers, with toggle buttons
+/// for the A and B slots and a button which copies the active slot to the
+/// other slot.
+///
+/// The widget only tracks which slot is active. Storing the state of each
+/// slot is up to the application. When the user presses one of the slot
+/// buttons, the switch action is sent and the application should call
+/// [`AbCompare::set_active`] in response (the same pattern as
+/// [`RadioButtonGroup`]).
+///
+/// ```ignore
+/// let mut ab = AbCompare::new(
+///     AbSlot::A,
+///     |slot| MyAction::SwitchAbSlot(slot),
+///     |from| MyAction::CopyAbSlot { from, to: from.other() },
+///     None,
+///     None,
+///     cx,
+/// );
+/// ab.set_rect(Rect::new(Point::new(8.0, 8.0), Size::new(ab.desired_width(), 24.0)));
+/// ```
+pub struct AbCompare {
+    core: WidgetCore,
+    a_button: ToggleButton,
+    b_button: ToggleButton,
+    copy_a_to_b: Button,
+    copy_b_to_a: Button,
+    active: AbSlot,
+    slot_button_width: f32,
+    copy_button_width: f32,
+    spacing: f32,
+}
+
+impl AbCompare {
+    /// Create a new A/B compare widget.
+    ///
+    /// * `active` - The initially active slot
+    /// * `on_switch` - The action to send when the user selects a slot
+    /// * `on_copy` - The action to send when the user presses the copy
+    /// button, called with the slot to copy from (the other slot is the slot
+    /// to copy to)
+    /// * `slot_button_class` - The class of the A and B toggle buttons
+    /// * `copy_button_class` - The class of the copy button
+    pub fn new<A: Clone + 'static, FS, FC>(
+        active: AbSlot,
+        mut on_switch: FS,
+        mut on_copy: FC,
+        slot_button_class: Option<ClassID>,
+        copy_button_class: Option<ClassID>,
+        window_cx: &mut WindowContext<'_, A>,
+    ) -> Self
+    where
+        FS: FnMut(AbSlot) -> A + 'static,
+        FC: FnMut(AbSlot) -> A + 'static,
+    {
+        let slot_button_class = slot_button_class.unwrap_or_else(|| window_cx.class());
+        let copy_button_class = copy_button_class.unwrap_or_else(|| window_cx.class());
+
+        let mut slot_button = |slot: AbSlot, text: &str, window_cx: &mut WindowContext<'_, A>| {
+            let action = (on_switch)(slot);
+
+            ToggleButton::builder()
+                .text(text)
+                .toggled(active == slot)
+                .on_toggled(move |_| action.clone())
+                .class(slot_button_class)
+                .build(window_cx)
+        };
+
+        let a_button = slot_button(AbSlot::A, "A", window_cx);
+        let b_button = slot_button(AbSlot::B, "B", window_cx);
+
+        let mut copy_button = |from: AbSlot, text: &str, window_cx: &mut WindowContext<'_, A>| {
+            Button::builder()
+                .text(text)
+                .on_select((on_copy)(from))
+                .class(copy_button_class)
+                .hidden(active != from)
+                .build(window_cx)
+        };
+
+        let copy_a_to_b = copy_button(AbSlot::A, "A \u{2192} B", window_cx);
+        let copy_b_to_a = copy_button(AbSlot::B, "B \u{2192} A", window_cx);
+
+        Self {
+            core: WidgetCore::default(),
+            a_button,
+            b_button,
+            copy_a_to_b,
+            copy_b_to_a,
+            active,
+            slot_button_width: 28.0,
+            copy_button_width: 56.0,
+            spacing: 4.0,
+        }
+    }
+
+    /// The width of each of the A and B buttons in points.
+    ///
+    /// By default this is set to `28.0`.
+    pub fn with_slot_button_width(mut self, width: f32) -> Self {
+        self.slot_button_width = width;
+        self.sync_copy_buttons();
+        self.layout();
+        self
+    }
+
+    /// The width of the copy button in points.
+    ///
+    /// By default this is set to `56.0`.
+    pub fn with_copy_button_width(mut self, width: f32) -> Self {
+        self.copy_button_width = width;
+        self.sync_copy_buttons();
+        self.layout();
+        self
+    }
+
+    /// The spacing between the buttons in points.
+    ///
+    /// By default this is set to `4.0`.
+    pub fn with_spacing(mut self, spacing: f32) -> Self {
+        self.spacing = spacing;
+        self.sync_copy_buttons();
+        self.layout();
+        self
+    }
+
+    /// The width needed to fit all buttons with their configured widths.
+    pub fn desired_width(&self) -> f32 {
+        (self.slot_button_width * 2.0) + self.copy_button_width + (self.spacing * 2.0)
+    }
+
+    /// Set the active slot.
+    ///
+    /// Returns `true` if the active slot has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn set_active(&mut self, active: AbSlot) -> bool {
+        // Always re-apply the toggle state since the user may have toggled
+        // off the active button.
+        self.a_button.set_toggled(active == AbSlot::A);
+        self.b_button.set_toggled(active == AbSlot::B);
+
+        if self.active == active {
+            return false;
+        }
+
+        self.active = active;
+        self.sync_copy_buttons();
+        true
+    }
+
+    pub fn active(&self) -> AbSlot {
+        self.active
+    }
+
+    /// Set the text of the A and B buttons.
+    pub fn set_slot_texts(&mut self, a: &str, b: &str, res: &mut ResourceCtx) {
+        self.a_button.set_text(Some(a), res);
+        self.b_button.set_text(Some(b), res);
+    }
+
+    /// Set the text of the copy button when A is active and when B is
+    /// active.
+    pub fn set_copy_texts(&mut self, a_to_b: &str, b_to_a: &str, res: &mut ResourceCtx) {
+        self.copy_a_to_b.set_text(Some(a_to_b), res);
+        self.copy_b_to_a.set_text(Some(b_to_a), res);
+    }
+
+    fn copy_button_layout(&self) -> PartLayout {
+        PartLayout::fill_height(Align2::CENTER_LEFT, self.copy_button_width).offset(Vector::new(
+            (self.slot_button_width + self.spacing) * 2.0,
+            0.0,
+        ))
+    }
+
+    /// Only the copy button for the active slot is listed as a part, so
+    /// the other one has to be updated manually.
+    fn sync_copy_buttons(&mut self) {
+        let rect = self.copy_button_layout().rect(self.rect());
+        let hidden = self.hidden();
+        let disabled = self.disabled();
+
+        let (active_button, inactive_button) = match self.active {
+            AbSlot::A => (&mut self.copy_a_to_b, &mut self.copy_b_to_a),
+            AbSlot::B => (&mut self.copy_b_to_a, &mut self.copy_a_to_b),
+        };
+
+        inactive_button.set_hidden(true);
+
+        active_button.set_rect(rect);
+        active_button.set_disabled(disabled);
+        active_button.set_hidden(hidden);
+    }
+}
+
+impl Widget for AbCompare {
+    fn core(&self) -> &WidgetCore {
+        &self.core
+    }
+
+    fn core_mut(&mut self) -> &mut WidgetCore {
+        &mut self.core
+    }
+
+    fn for_each_part(&mut self, f: &mut dyn FnMut(&mut dyn WidgetPart, PartLayout)) {
+        let copy_button_layout = self.copy_button_layout();
+
+        (f)(
+            &mut self.a_button,
+            PartLayout::fill_height(Align2::CENTER_LEFT, self.slot_button_width),
+        );
+        (f)(
+            &mut self.b_button,
+            PartLayout::fill_height(Align2::CENTER_LEFT, self.slot_button_width)
+                .offset(Vector::new(self.slot_button_width + self.spacing, 0.0)),
+        );
+
+        let copy_button = match self.active {
+            AbSlot::A => &mut self.copy_a_to_b,
+            AbSlot::B => &mut self.copy_b_to_a,
+        };
+        (f)(copy_button, copy_button_layout);
+    }
+}
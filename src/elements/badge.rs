@@ -0,0 +1,366 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::derive::*;
+use crate::prelude::*;
+use crate::vg::text::{Metrics, RcTextBuffer, TextPrimitive};
+
+/// The style of a [`Badge`] element.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BadgeStyle {
+    /// The style of the badge when it shows a count.
+    pub back_quad: QuadStyle,
+    /// The style of the badge when it shows a dot (no count).
+    ///
+    /// If this is `None`, then `back_quad` will be used.
+    pub dot_quad: Option<QuadStyle>,
+
+    pub text_properties: TextProperties,
+    pub text_color: RGBA8,
+
+    /// The height of the badge when it shows a count. This is also the
+    /// minimum width.
+    ///
+    /// By default this is set to `16.0`.
+    pub height: f32,
+    /// The padding between the text and the left and right edges of the
+    /// badge.
+    ///
+    /// By default this is set to `4.0`.
+    pub padding_x: f32,
+    /// The diameter of the badge when it shows a dot.
+    ///
+    /// By default this is set to `8.0`.
+    pub dot_size: f32,
+
+    /// The corner (or edge) of the target element's rectangle the center
+    /// of the badge is pinned to.
+    ///
+    /// By default this is set to `Align2::TOP_RIGHT`.
+    pub corner: Align2,
+    /// An additional offset applied to the badge after pinning it to the
+    /// corner.
+    ///
+    /// By default this is set to `Vector::new(-2.0, 2.0)`.
+    pub offset: Vector,
+}
+
+impl Default for BadgeStyle {
+    fn default() -> Self {
+        Self {
+            back_quad: QuadStyle {
+                bg: Background::Solid(rgb(220, 50, 50)),
+                border: BorderStyle {
+                    radius: radius(8.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            dot_quad: None,
+            text_properties: TextProperties {
+                metrics: Metrics {
+                    font_size: 10.0,
+                    line_height: 12.0,
+                },
+                wrap: Wrap::None,
+                ..Default::default()
+            },
+            text_color: color::WHITE,
+            height: 16.0,
+            padding_x: 4.0,
+            dot_size: 8.0,
+            corner: Align2::TOP_RIGHT,
+            offset: Vector::new(-2.0, 2.0),
+        }
+    }
+}
+
+impl ElementStyle for BadgeStyle {
+    const ID: &'static str = "badge";
+
+    fn default_dark_style() -> Self {
+        Self::default()
+    }
+
+    fn default_light_style() -> Self {
+        Self::default()
+    }
+}
+
+#[element_builder]
+#[element_builder_class]
+#[element_builder_hidden]
+pub struct BadgeBuilder {
+    pub count: Option<u32>,
+    pub max_count: u32,
+}
+
+impl Default for BadgeBuilder {
+    fn default() -> Self {
+        Self {
+            count: None,
+            max_count: 99,
+            class: None,
+            z_index: None,
+            manually_hidden: false,
+            scissor_rect: None,
+            key: None,
+        }
+    }
+}
+
+impl BadgeBuilder {
+    /// The count to show in the badge. If this is `None`, then a dot is
+    /// shown instead.
+    ///
+    /// By default this is set to `None`.
+    pub const fn count(mut self, count: Option<u32>) -> Self {
+        self.count = count;
+        self
+    }
+
+    /// Counts larger than this are shown as `"{max_count}+"`.
+    ///
+    /// By default this is set to `99`.
+    pub const fn max_count(mut self, max_count: u32) -> Self {
+        self.max_count = max_count;
+        self
+    }
+
+    /// Build the badge and attach it to the given target element.
+    ///
+    /// If no z index was set, then the badge is placed one z index above
+    /// the target. If no scissoring rectangle was set, then the current
+    /// scissoring rectangle from the window context is used, which should be
+    /// the same one the target element belongs to.
+    pub fn build<A: Clone + 'static>(
+        self,
+        target: &ElementHandle,
+        window_cx: &mut WindowContext<'_, A>,
+    ) -> Badge {
+        let BadgeBuilder {
+            count,
+            max_count,
+            class,
+            z_index,
+            manually_hidden,
+            scissor_rect,
+            key,
+        } = self;
+
+        let z_index = z_index.unwrap_or_else(|| target.z_index().saturating_add(1));
+
+        let shared_state = Rc::new(RefCell::new(SharedState { count, max_count }));
+
+        let mut el = ElementBuilder::new(BadgeElement {
+            shared_state: Rc::clone(&shared_state),
+            text_buffer: None,
+            target_rect: target.rect(),
+        })
+        .builder_values(Some(z_index), scissor_rect, class, window_cx)
+        .key(key)
+        .hidden(manually_hidden)
+        .flags(ElementFlags::PAINTS | ElementFlags::LISTENS_TO_INIT)
+        .build(window_cx);
+
+        el.set_anchor(Some(target.id()));
+
+        Badge { el, shared_state }
+    }
+}
+
+fn count_text(count: u32, max_count: u32) -> String {
+    if count > max_count {
+        format!("{max_count}+")
+    } else {
+        format!("{count}")
+    }
+}
+
+/// A small count or dot pinned to a corner of another element.
+struct BadgeElement {
+    shared_state: Rc<RefCell<SharedState>>,
+    text_buffer: Option<RcTextBuffer>,
+    target_rect: Rect,
+}
+
+impl BadgeElement {
+    fn sync_text_buffer<A: Clone + 'static>(
+        &mut self,
+        shared_state: &SharedState,
+        cx: &mut ElementContext<'_, A>,
+    ) {
+        self.text_buffer = shared_state.count.map(|count| {
+            let style = cx.res.style_system.get::<BadgeStyle>(cx.class());
+
+            RcTextBuffer::new(
+                &count_text(count, shared_state.max_count),
+                style.text_properties,
+                None,
+                None,
+                false,
+                &mut cx.res.font_system,
+            )
+        });
+    }
+
+    fn layout<A: Clone + 'static>(&self, cx: &mut ElementContext<'_, A>) {
+        let style = cx.res.style_system.get::<BadgeStyle>(cx.class());
+
+        let size = if let Some(text_buffer) = &self.text_buffer {
+            let text_width = text_buffer.measure().width;
+            Size::new(
+                (text_width + (style.padding_x * 2.0)).max(style.height),
+                style.height,
+            )
+        } else {
+            Size::new(style.dot_size, style.dot_size)
+        };
+
+        let x = match style.corner.horizontal {
+            Align::Start => self.target_rect.min_x(),
+            Align::Center => self.target_rect.center().x,
+            Align::End => self.target_rect.max_x(),
+        };
+        let y = match style.corner.vertical {
+            Align::Start => self.target_rect.min_y(),
+            Align::Center => self.target_rect.center().y,
+            Align::End => self.target_rect.max_y(),
+        };
+
+        cx.set_rect(centered_rect(Point::new(x, y) + style.offset, size));
+    }
+}
+
+impl<A: Clone + 'static> Element<A> for BadgeElement {
+    fn on_event(
+        &mut self,
+        event: ElementEvent,
+        cx: &mut ElementContext<'_, A>,
+    ) -> EventCaptureStatus {
+        match event {
+            ElementEvent::Init | ElementEvent::CustomStateChanged | ElementEvent::StyleChanged => {
+                let shared_state_rc = Rc::clone(&self.shared_state);
+                let shared_state = RefCell::borrow(&shared_state_rc);
+
+                self.sync_text_buffer(&shared_state, cx);
+                self.layout(cx);
+                cx.request_repaint();
+            }
+            ElementEvent::AnchorRectChanged(target_rect) => {
+                self.target_rect = target_rect;
+                self.layout(cx);
+            }
+            _ => {}
+        }
+
+        EventCaptureStatus::NotCaptured
+    }
+
+    fn render(&mut self, cx: RenderContext, primitives: &mut PrimitiveGroup) {
+        let style = cx.res.style_system.get::<BadgeStyle>(cx.class);
+
+        let bounds = Rect::from_size(cx.bounds_size);
+
+        if let Some(text_buffer) = &self.text_buffer {
+            if !style.back_quad.is_transparent() {
+                primitives.add(
+                    style
+                        .back_quad
+                        .create_primitive_with_opacity(bounds, cx.opacity),
+                );
+            }
+
+            let text_size = text_buffer.measure();
+            primitives.set_z_index(1);
+            primitives.add_text(TextPrimitive::new(
+                text_buffer.clone(),
+                bounds.center() - (text_size * 0.5).to_vector(),
+                apply_opacity(style.text_color, cx.opacity),
+                None,
+            ));
+        } else {
+            let quad_style = style.dot_quad.as_ref().unwrap_or(&style.back_quad);
+
+            if !quad_style.is_transparent() {
+                primitives.add(quad_style.create_primitive_with_opacity(bounds, cx.opacity));
+            }
+        }
+    }
+}
+
+struct SharedState {
+    count: Option<u32>,
+    max_count: u32,
+}
+
+/// A handle to a [`BadgeElement`], a small count or dot pinned to a corner
+/// of another element.
+///
+/// The badge is anchored to its target element with
+/// [`ElementHandle::set_anchor`], so it follows the target automatically
+/// whenever the target is moved or resized. Because the badge positions
+/// itself, the cached rectangle of this handle is not kept up to date.
+///
+/// Note, the badge does not follow the visibility of the target element, so
+/// it should be hidden along with the target.
+#[element_handle]
+#[element_handle_class]
+pub struct Badge {
+    shared_state: Rc<RefCell<SharedState>>,
+}
+
+impl Badge {
+    pub fn builder() -> BadgeBuilder {
+        BadgeBuilder::default()
+    }
+
+    /// Set the count to show in the badge. If this is `None`, then a dot is
+    /// shown instead.
+    ///
+    /// Returns `true` if the count has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn set_count(&mut self, count: Option<u32>) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if shared_state.count != count {
+            shared_state.count = count;
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn count(&self) -> Option<u32> {
+        RefCell::borrow(&self.shared_state).count
+    }
+
+    /// Set the count above which the badge shows `"{max_count}+"`.
+    ///
+    /// Returns `true` if the value has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn set_max_count(&mut self, max_count: u32) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if shared_state.max_count != max_count {
+            shared_state.max_count = max_count;
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Attach this badge to a different target element.
+    ///
+    /// Note, this does not change the z index of the badge. Use
+    /// `Badge::set_z_index` if the new target is on a different z index.
+    pub fn set_target(&mut self, target: &ElementHandle) {
+        self.el.set_anchor(Some(target.id()));
+    }
+}
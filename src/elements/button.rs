@@ -5,6 +5,7 @@ use std::rc::Rc;
 use crate::derive::*;
 use crate::prelude::*;
 use crate::theme::DEFAULT_ICON_SIZE;
+use crate::vg::quad::QuadPrimitive;
 
 use super::label::{LabelInner, LabelPaddingInfo, LabelPrimitives};
 
@@ -14,6 +15,8 @@ pub enum ButtonState {
     Hovered,
     Down,
     Disabled,
+    /// The element has keyboard focus and is not hovered or pressed.
+    Focused,
 }
 
 impl ButtonState {
@@ -24,6 +27,54 @@ impl ButtonState {
             Self::Idle
         }
     }
+
+    /// The resting state of an enabled element, depending on whether or not
+    /// it has keyboard focus.
+    pub fn resting(has_focus: bool) -> Self {
+        if has_focus {
+            Self::Focused
+        } else {
+            Self::Idle
+        }
+    }
+
+    /// Returns `true` if this is `ButtonState::Idle` or `ButtonState::Focused`.
+    pub fn is_resting(&self) -> bool {
+        *self == Self::Idle || *self == Self::Focused
+    }
+}
+
+/// The style overrides of an element when it has keyboard focus (and is not
+/// hovered or pressed).
+///
+/// A property set to `None` uses the idle style.
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct FocusedStyle {
+    pub text_color: Option<RGBA8>,
+    pub icon_color: Option<RGBA8>,
+    pub back_bg: Option<Background>,
+    pub back_border_color: Option<RGBA8>,
+    pub back_border_width: Option<f32>,
+}
+
+impl FocusedStyle {
+    pub fn apply(&self, style: &mut LabelStyle) {
+        if let Some(c) = self.text_color {
+            style.text_color = c;
+        }
+        if let Some(c) = self.icon_color.or(self.text_color) {
+            style.icon_color = Some(c);
+        }
+        if let Some(bg) = self.back_bg {
+            style.back_quad.bg = bg;
+        }
+        if let Some(c) = self.back_border_color {
+            style.back_quad.border.color = c;
+        }
+        if let Some(w) = self.back_border_width {
+            style.back_quad.border.width = w;
+        }
+    }
 }
 
 /// The style of a [`Button`] element
@@ -57,6 +108,10 @@ pub struct ButtonStyle {
     ///
     /// By default this set to `0.0`.
     pub text_icon_spacing: f32,
+    /// How to truncate text which doesn't fit inside the padded rectangle.
+    ///
+    /// By default this is set to `TextTruncation::Clip`.
+    pub text_truncation: TextTruncation,
 
     /// The color of the text
     ///
@@ -145,6 +200,11 @@ pub struct ButtonStyle {
 
     /// The border radius of the background quad.
     pub back_border_radius: Radius,
+    /// Optional per-edge overrides for the border on the background quad
+    /// (i.e. to remove the inner borders of a joined button group).
+    ///
+    /// By default this is set to `BorderEdges::NONE`.
+    pub back_border_edges: BorderEdges,
 
     /// The cursor icon to show when the user hovers over this element.
     ///
@@ -153,10 +213,20 @@ pub struct ButtonStyle {
     /// By default this is set to `None`.
     pub cursor_icon: Option<CursorIcon>,
 
+    /// The style overrides when the button has keyboard focus.
+    ///
+    /// By default this is set to `FocusedStyle::default()` (no overrides).
+    pub focused: FocusedStyle,
+
     /// Additional flags for the quad primitives.
     ///
     /// By default this is set to `QuadFlags::SNAP_ALL_TO_NEAREST_PIXEL`.
     pub quad_flags: QuadFlags,
+
+    /// How to animate changes between states (i.e. Idle -> Hovered).
+    ///
+    /// By default this is set to `StyleTransitions::NONE`.
+    pub transitions: StyleTransitions,
 }
 
 impl Default for ButtonStyle {
@@ -168,6 +238,7 @@ impl Default for ButtonStyle {
             text_padding: Default::default(),
             icon_padding: Default::default(),
             text_icon_spacing: 0.0,
+            text_truncation: TextTruncation::Clip,
             text_color: color::WHITE,
             text_color_hover: None,
             text_color_down: None,
@@ -188,8 +259,11 @@ impl Default for ButtonStyle {
             back_border_width_hover: None,
             back_border_width_down: None,
             back_border_radius: Default::default(),
+            back_border_edges: BorderEdges::NONE,
             cursor_icon: None,
+            focused: FocusedStyle::default(),
             quad_flags: QuadFlags::SNAP_ALL_TO_NEAREST_PIXEL,
+            transitions: StyleTransitions::NONE,
         }
     }
 }
@@ -206,7 +280,7 @@ impl ButtonStyle {
 
     pub fn label_style(&self, state: ButtonState) -> LabelStyle {
         let (text_color, icon_color, back_quad) = match state {
-            ButtonState::Idle => (
+            ButtonState::Idle | ButtonState::Focused => (
                 self.text_color,
                 self.icon_color,
                 QuadStyle {
@@ -215,6 +289,7 @@ impl ButtonStyle {
                         color: self.back_border_color,
                         width: self.back_border_width,
                         radius: self.back_border_radius,
+                        edges: self.back_border_edges,
                     },
                     flags: self.quad_flags,
                 },
@@ -239,6 +314,7 @@ impl ButtonStyle {
                             .back_border_width_hover
                             .unwrap_or(self.back_border_width),
                         radius: self.back_border_radius,
+                        edges: self.back_border_edges,
                     },
                     flags: self.quad_flags,
                 },
@@ -263,6 +339,7 @@ impl ButtonStyle {
                             .back_border_width_down
                             .unwrap_or(self.back_border_width),
                         radius: self.back_border_radius,
+                        edges: self.back_border_edges,
                     },
                     flags: self.quad_flags,
                 },
@@ -279,13 +356,16 @@ impl ButtonStyle {
                         color: self.back_border_color_disabled.get(self.back_border_color),
                         width: self.back_border_width,
                         radius: self.back_border_radius,
+                        edges: self
+                            .back_border_edges
+                            .map_colors(|c| self.back_border_color_disabled.get(c)),
                     },
                     flags: self.quad_flags,
                 },
             ),
         };
 
-        LabelStyle {
+        let mut label_style = LabelStyle {
             text_color,
             icon_color,
             snap_icon_to_physical_pixel: self.snap_icon_to_physical_pixel,
@@ -296,7 +376,25 @@ impl ButtonStyle {
             icon_padding: self.icon_padding,
             text_icon_spacing: self.text_icon_spacing,
             vertical_align: Align::Center,
+            text_truncation: self.text_truncation,
+            ..Default::default()
+        };
+
+        if state == ButtonState::Focused {
+            self.focused.apply(&mut label_style);
         }
+
+        label_style
+    }
+
+    /// Derive every disabled-state property set to `Auto` with the given
+    /// derivation (see
+    /// [`StyleSystem::set_auto_disabled`](crate::style::StyleSystem::set_auto_disabled)).
+    pub fn derive_auto_disabled(&mut self, derivation: DisabledDerivation) {
+        self.text_color_disabled.derive_auto(derivation);
+        self.icon_color_disabled.derive_auto(derivation);
+        self.back_bg_disabled.derive_auto(derivation);
+        self.back_border_color_disabled.derive_auto(derivation);
     }
 }
 
@@ -313,12 +411,22 @@ impl ElementStyle for ButtonStyle {
             ..Self::default()
         }
     }
+
+    fn with_auto_disabled(&self, derivation: DisabledDerivation) -> Option<Self> {
+        let mut style = self.clone();
+        style.derive_auto_disabled(derivation);
+        Some(style)
+    }
 }
 
 /// A reusable button struct that can be used by other elements.
 pub struct ButtonInner {
     state: ButtonState,
     label_inner: LabelInner,
+    transitions: StyleTransitions,
+    reduce_motion: bool,
+    transition: StyleTransitionState<ButtonState>,
+    has_focus: bool,
 }
 
 impl ButtonInner {
@@ -332,7 +440,9 @@ impl ButtonInner {
         disabled: bool,
         text_icon_layout: TextIconLayout,
         style: &ButtonStyle,
+        reduce_motion: bool,
         font_system: &mut FontSystem,
+        text_cache: &mut TextLayoutCache,
     ) -> Self {
         let state = ButtonState::new(disabled);
 
@@ -346,14 +456,51 @@ impl ButtonInner {
             text_icon_layout,
             &style.label_style(state),
             font_system,
+            text_cache,
         );
 
-        Self { label_inner, state }
+        Self {
+            label_inner,
+            state,
+            transitions: style.transitions,
+            reduce_motion,
+            transition: StyleTransitionState::default(),
+            has_focus: false,
+        }
+    }
+
+    /// Set the text direction of the label.
+    ///
+    /// Returns `true` if the text direction has changed.
+    pub fn set_text_direction(
+        &mut self,
+        text_direction: TextDirection,
+        style: &ButtonStyle,
+        font_system: &mut FontSystem,
+        text_cache: &mut TextLayoutCache,
+    ) -> bool {
+        self.label_inner.set_text_direction(
+            text_direction,
+            &style.label_style(self.state),
+            font_system,
+            text_cache,
+        )
     }
 
     /// Returns `true` if the state has changed.
+    ///
+    /// If the style has any transitions, then this will also start a
+    /// transition from the previous state. Use [`ButtonInner::is_transitioning`]
+    /// to check if the element should listen to animation events.
     pub fn set_state(&mut self, state: ButtonState) -> bool {
         if self.state != state {
+            if state == ButtonState::Disabled {
+                self.transition.stop();
+            } else {
+                self.transition
+                    .start(self.state, &self.transitions, self.reduce_motion);
+            }
+
             self.state = state;
             true
         } else {
@@ -365,9 +512,85 @@ impl ButtonInner {
         self.state
     }
 
-    pub fn sync_new_style(&mut self, style: &ButtonStyle, font_system: &mut FontSystem) {
+    /// Call this when the element gains or loses keyboard focus.
+    ///
+    /// Returns `true` if the element needs to be repainted.
+    pub fn set_focused(&mut self, has_focus: bool) -> bool {
+        if self.has_focus == has_focus {
+            return false;
+        }
+
+        self.has_focus = has_focus;
+
+        if self.state.is_resting() {
+            self.set_state(ButtonState::resting(has_focus));
+        }
+
+        true
+    }
+
+    pub fn has_focus(&self) -> bool {
+        self.has_focus
+    }
+
+    /// The state to return to when the pointer is no longer hovering over or
+    /// pressing this element.
+    pub fn resting_state(&self) -> ButtonState {
+        ButtonState::resting(self.has_focus)
+    }
+
+    /// Set the transitions used when the state changes (call this when the
+    /// style has changed).
+    ///
+    /// If `reduce_motion` is `true` (see `StyleSystem::reduce_motion`), then
+    /// state changes are not animated.
+    pub fn set_transitions(&mut self, transitions: StyleTransitions, reduce_motion: bool) {
+        if self.transitions != transitions || (reduce_motion && !self.reduce_motion) {
+            self.transition.stop();
+        }
+
+        self.transitions = transitions;
+        self.reduce_motion = reduce_motion;
+    }
+
+    /// Returns `true` if a style transition is currently in progress.
+    pub fn is_transitioning(&self) -> bool {
+        self.transition.is_active()
+    }
+
+    /// Advance the current style transition (call this on every
+    /// `ElementEvent::Animation` event).
+    ///
+    /// Returns `true` if the transition is still in progress.
+    pub fn advance_transition(&mut self, delta_seconds: f64) -> bool {
+        self.transition
+            .advance(delta_seconds, &self.transitions, self.reduce_motion)
+    }
+
+    /// The current label style, accounting for any in-progress transition.
+    pub fn label_style(&self, style: &ButtonStyle) -> LabelStyle {
+        let label_style = style.label_style(self.state);
+
+        if let Some(from) = self
+            .transition
+            .from_style(|s| style.label_style(s), LabelStyle::transition_from)
+        {
+            label_style.transition_from(&from, &self.transition.progress(&self.transitions))
+        } else {
+            label_style
+        }
+    }
+
+    pub fn sync_new_style(
+        &mut self,
+        style: &ButtonStyle,
+        font_system: &mut FontSystem,
+        text_cache: &mut TextLayoutCache,
+    ) {
+        self.set_transitions(style.transitions, self.reduce_motion);
+
         self.label_inner
-            .sync_new_style(&style.label_style(self.state), font_system);
+            .sync_new_style(&style.label_style(self.state), font_system, text_cache);
     }
 
     /// Returns the size of the padded background rectangle if it were to
@@ -384,15 +607,22 @@ impl ButtonInner {
         &mut self,
         text: Option<T>,
         font_system: &mut FontSystem,
+        text_cache: &mut TextLayoutCache,
         get_text_props: F,
     ) -> bool {
-        self.label_inner.set_text(text, font_system, get_text_props)
+        self.label_inner
+            .set_text(text, font_system, text_cache, get_text_props)
     }
 
     pub fn text(&self) -> Option<&str> {
         self.label_inner.text()
     }
 
+    /// The full text if it was truncated when it was last rendered.
+    pub fn truncated_text(&self) -> Option<&str> {
+        self.label_inner.truncated_text()
+    }
+
     pub fn set_icon(&mut self, icon: Option<IconID>) -> bool {
         self.label_inner.set_icon(icon)
     }
@@ -405,10 +635,20 @@ impl ButtonInner {
         &mut self,
         bounds: Rect,
         style: &ButtonStyle,
+        opacity: f32,
         font_system: &mut FontSystem,
+        text_cache: &mut TextLayoutCache,
+        icon_fonts: &IconFontSystem,
     ) -> LabelPrimitives {
-        self.label_inner
-            .render(bounds, &style.label_style(self.state), font_system)
+        let label_style = self.label_style(style);
+        self.label_inner.render(
+            bounds,
+            &label_style,
+            opacity,
+            font_system,
+            text_cache,
+            icon_fonts,
+        )
     }
 
     /// An offset that can be used mainly to correct the position of text.
@@ -470,10 +710,27 @@ impl ButtonInner {
     pub fn disabled(&self) -> bool {
         self.state == ButtonState::Disabled
     }
+
+    /// Create the focus ring primitive for this button if it has keyboard
+    /// focus and focus rings are enabled in the style system.
+    pub fn focus_ring_primitive(
+        &self,
+        bounds: Rect,
+        radius: Radius,
+        focus_ring: Option<&FocusRingStyle>,
+        opacity: f32,
+    ) -> Option<QuadPrimitive> {
+        if !self.has_focus || self.state == ButtonState::Disabled {
+            return None;
+        }
+
+        focus_ring.map(|ring| ring.create_primitive_with_opacity(bounds, radius, opacity))
+    }
 }
 
 #[element_builder]
 #[element_builder_class]
+#[element_builder_tab_index]
 #[element_builder_rect]
 #[element_builder_hidden]
 #[element_builder_disabled]
@@ -587,13 +844,16 @@ impl<A: Clone + 'static> ButtonBuilder<A> {
             text_icon_layout,
             disabled,
             class,
+            tab_index,
             z_index,
             rect,
             manually_hidden,
             scissor_rect,
+            key,
             tooltip_data,
         } = self;
 
+        let reduce_motion = window_cx.res.style_system.reduce_motion();
         let style = window_cx
             .res
             .style_system
@@ -611,21 +871,39 @@ impl<A: Clone + 'static> ButtonBuilder<A> {
                 disabled,
                 text_icon_layout,
                 &style,
+                reduce_motion,
                 &mut window_cx.res.font_system,
+                &mut window_cx.res.text_layout_cache,
             ),
             tooltip_inner: TooltipInner::new(tooltip_data),
         }));
 
-        let el = ElementBuilder::new(ButtonElement {
+        RefCell::borrow_mut(&shared_state).inner.set_text_direction(
+            window_cx.text_direction(),
+            &style,
+            &mut window_cx.res.font_system,
+            &mut window_cx.res.text_layout_cache,
+        );
+
+        let mut el = ElementBuilder::new(ButtonElement {
             shared_state: Rc::clone(&shared_state),
             on_select_action,
             cursor_icon,
         })
         .builder_values(z_index, scissor_rect, class, window_cx)
+        .key(key)
+        .tab_index(tab_index)
         .rect(rect)
         .hidden(manually_hidden)
-        .flags(ElementFlags::PAINTS | ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS)
+        .flags(
+            ElementFlags::PAINTS
+                | ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS
+                | ElementFlags::LISTENS_TO_FOCUS_CHANGE
+                | ElementFlags::FOCUSABLE
+                | ElementFlags::DRAWS_OWN_FOCUS_RING,
+        )
         .build(window_cx);
+        el.set_focusable(!disabled);
 
         Button { el, shared_state }
     }
@@ -646,17 +924,48 @@ impl<A: Clone + 'static> Element<A> for ButtonElement<A> {
     ) -> EventCaptureStatus {
         let mut shared_state = RefCell::borrow_mut(&self.shared_state);
 
-        shared_state
-            .tooltip_inner
-            .handle_event(&event, shared_state.inner.disabled(), cx);
+        shared_state.tooltip_inner.handle_event_with_auto_text(
+            &event,
+            shared_state.inner.disabled(),
+            shared_state.inner.truncated_text(),
+            cx,
+        );
 
         match event {
             ElementEvent::CustomStateChanged => {
                 cx.request_repaint();
+                cx.set_animating(shared_state.inner.is_transitioning());
             }
             ElementEvent::StyleChanged => {
                 let style = cx.res.style_system.get::<ButtonStyle>(cx.class());
                 self.cursor_icon = style.cursor_icon;
+                let transitions = style.transitions;
+                let reduce_motion = cx.res.style_system.reduce_motion();
+                shared_state
+                    .inner
+                    .set_transitions(transitions, reduce_motion);
+            }
+            ElementEvent::Animation { delta_seconds } => {
+                let animating = shared_state.inner.advance_transition(delta_seconds);
+                cx.set_animating(animating);
+                cx.request_repaint();
+            }
+            ElementEvent::Focus(has_focus) => {
+                if shared_state.inner.set_focused(has_focus) {
+                    cx.request_repaint();
+                    cx.set_animating(shared_state.inner.is_transitioning());
+                }
+            }
+            ElementEvent::Activate => {
+                if shared_state.inner.disabled() {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                if let Some(action) = &self.on_select_action {
+                    cx.send_action(action.clone()).unwrap();
+                }
+
+                return EventCaptureStatus::Captured;
             }
             ElementEvent::Pointer(PointerEvent::Moved { .. }) => {
                 if shared_state.inner.state == ButtonState::Disabled {
@@ -667,11 +976,12 @@ impl<A: Clone + 'static> Element<A> for ButtonElement<A> {
                     cx.cursor_icon = cursor_icon;
                 }
 
-                if shared_state.inner.state == ButtonState::Idle {
+                if shared_state.inner.state.is_resting() {
                     let needs_repaint = shared_state.inner.set_state(ButtonState::Hovered);
 
                     if needs_repaint {
                         cx.request_repaint();
+                        cx.set_animating(shared_state.inner.is_transitioning());
                     }
                 }
 
@@ -681,10 +991,12 @@ impl<A: Clone + 'static> Element<A> for ButtonElement<A> {
                 if shared_state.inner.state == ButtonState::Hovered
                     || shared_state.inner.state == ButtonState::Down
                 {
-                    let needs_repaint = shared_state.inner.set_state(ButtonState::Idle);
+                    let resting_state = shared_state.inner.resting_state();
+                    let needs_repaint = shared_state.inner.set_state(resting_state);
 
                     if needs_repaint {
                         cx.request_repaint();
+                        cx.set_animating(shared_state.inner.is_transitioning());
                     }
 
                     return EventCaptureStatus::Captured;
@@ -692,13 +1004,14 @@ impl<A: Clone + 'static> Element<A> for ButtonElement<A> {
             }
             ElementEvent::Pointer(PointerEvent::ButtonJustPressed { button, .. }) => {
                 if button == PointerButton::Primary
-                    && (shared_state.inner.state == ButtonState::Idle
+                    && (shared_state.inner.state.is_resting()
                         || shared_state.inner.state == ButtonState::Hovered)
                 {
                     let needs_repaint = shared_state.inner.set_state(ButtonState::Down);
 
                     if needs_repaint {
                         cx.request_repaint();
+                        cx.set_animating(shared_state.inner.is_transitioning());
                     }
 
                     if let Some(action) = &self.on_select_action {
@@ -718,13 +1031,14 @@ impl<A: Clone + 'static> Element<A> for ButtonElement<A> {
                     let new_state = if cx.is_point_within_visible_bounds(position) {
                         ButtonState::Hovered
                     } else {
-                        ButtonState::Idle
+                        shared_state.inner.resting_state()
                     };
 
                     let needs_repaint = shared_state.inner.set_state(new_state);
 
                     if needs_repaint {
                         cx.request_repaint();
+                        cx.set_animating(shared_state.inner.is_transitioning());
                     }
 
                     return EventCaptureStatus::Captured;
@@ -739,16 +1053,30 @@ impl<A: Clone + 'static> Element<A> for ButtonElement<A> {
     fn render(&mut self, cx: RenderContext, primitives: &mut PrimitiveGroup) {
         let mut shared_state = RefCell::borrow_mut(&self.shared_state);
 
+        let bounds = Rect::from_size(cx.bounds_size);
+
         let label_primitives = shared_state.inner.render(
-            Rect::from_size(cx.bounds_size),
+            bounds,
             cx.res.style_system.get(cx.class),
+            cx.opacity,
             &mut cx.res.font_system,
+            &mut cx.res.text_layout_cache,
+            &cx.res.icon_font_system,
         );
 
+        #[cfg(feature = "image")]
+        shared_state
+            .inner
+            .label_style(cx.res.style_system.get(cx.class))
+            .back_quad
+            .add_image_primitives(bounds, &cx.res.image_system, primitives);
+
         if let Some(quad_primitive) = label_primitives.bg_quad {
             primitives.add(quad_primitive);
         }
 
+        label_primitives.bg_overlay.add_to(primitives);
+
         if let Some(p) = label_primitives.text {
             primitives.set_z_index(1);
             primitives.add_text(p);
@@ -758,12 +1086,28 @@ impl<A: Clone + 'static> Element<A> for ButtonElement<A> {
             primitives.set_z_index(1);
             primitives.add_text(p);
         }
+
+        let radius = cx
+            .res
+            .style_system
+            .get::<ButtonStyle>(cx.class)
+            .back_border_radius;
+        if let Some(p) = shared_state.inner.focus_ring_primitive(
+            bounds,
+            radius,
+            cx.res.style_system.focus_ring(),
+            cx.opacity,
+        ) {
+            primitives.set_z_index(2);
+            primitives.add(p);
+        }
     }
 }
 
 /// A handle to a [`ButtonElement`], a button with a label.
 #[element_handle]
 #[element_handle_class]
+#[element_handle_tab_index]
 #[element_handle_set_rect]
 #[element_handle_set_tooltip]
 pub struct Button {
@@ -810,11 +1154,16 @@ impl Button {
     ) -> bool {
         let mut shared_state = RefCell::borrow_mut(&self.shared_state);
 
-        if shared_state.inner.set_text(text, &mut res.font_system, || {
-            res.style_system
-                .get::<ButtonStyle>(self.el.class())
-                .text_properties
-        }) {
+        if shared_state.inner.set_text(
+            text,
+            &mut res.font_system,
+            &mut res.text_layout_cache,
+            || {
+                res.style_system
+                    .get::<ButtonStyle>(self.el.class())
+                    .text_properties
+            },
+        ) {
             self.el.notify_custom_state_change();
             true
         } else {
@@ -936,13 +1285,15 @@ impl Button {
     /// so this method is relatively cheap to call frequently.
     pub fn set_disabled(&mut self, disabled: bool) -> bool {
         let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+        self.el.set_focusable(!disabled);
 
         if disabled && shared_state.inner.state != ButtonState::Disabled {
             shared_state.inner.set_state(ButtonState::Disabled);
             self.el.notify_custom_state_change();
             true
         } else if !disabled && shared_state.inner.state == ButtonState::Disabled {
-            shared_state.inner.set_state(ButtonState::Idle);
+            let resting_state = shared_state.inner.resting_state();
+            shared_state.inner.set_state(resting_state);
             self.el.notify_custom_state_change();
             true
         } else {
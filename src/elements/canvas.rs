@@ -0,0 +1,583 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use rootvg::PrimitiveGroup;
+
+use crate::derive::*;
+use crate::prelude::*;
+use crate::vg::quad::{SolidQuadBuilder, SolidQuadPrimitive};
+
+/// The style of a [`Canvas`] element.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CanvasStyle {
+    pub back_quad: QuadStyle,
+
+    /// The color of the grid lines.
+    pub grid_color: RGBA8,
+    /// The distance between grid lines in content points (before the zoom
+    /// factor is applied). Set to `0.0` to disable the grid.
+    ///
+    /// By default this is set to `0.0`.
+    pub grid_spacing: f32,
+    /// The width of the grid lines in points (this is not affected by the
+    /// zoom factor).
+    ///
+    /// By default this is set to `1.0`.
+    pub grid_line_width: f32,
+    /// If the distance between grid lines on screen is less than this many
+    /// points, then only every other line is drawn (and so on) so that the
+    /// grid doesn't become too dense when zoomed out.
+    ///
+    /// By default this is set to `8.0`.
+    pub grid_min_screen_spacing: f32,
+}
+
+impl Default for CanvasStyle {
+    fn default() -> Self {
+        Self {
+            back_quad: QuadStyle::TRANSPARENT,
+            grid_color: color::TRANSPARENT,
+            grid_spacing: 0.0,
+            grid_line_width: 1.0,
+            grid_min_screen_spacing: 8.0,
+        }
+    }
+}
+
+impl ElementStyle for CanvasStyle {
+    const ID: &'static str = "canvas";
+
+    fn default_dark_style() -> Self {
+        Self::default()
+    }
+
+    fn default_light_style() -> Self {
+        Self::default()
+    }
+}
+
+#[element_builder]
+#[element_builder_class]
+#[element_builder_rect]
+#[element_builder_hidden]
+#[element_builder_disabled]
+pub struct CanvasBuilder<A: Clone + 'static> {
+    pub view_changed_action: Option<Box<dyn FnMut(f32, Vector) -> A>>,
+    pub control_scissor_rect: Option<ScissorRectID>,
+    pub zoom: f32,
+    pub pan: Vector,
+    pub min_zoom: f32,
+    pub max_zoom: f32,
+    pub zoom_step: f32,
+    pub pan_with_scroll_wheel: bool,
+    pub points_per_line: f32,
+}
+
+impl<A: Clone + 'static> Default for CanvasBuilder<A> {
+    fn default() -> Self {
+        Self {
+            view_changed_action: None,
+            control_scissor_rect: None,
+            zoom: 1.0,
+            pan: Vector::zero(),
+            min_zoom: 0.1,
+            max_zoom: 10.0,
+            zoom_step: 1.1,
+            pan_with_scroll_wheel: true,
+            points_per_line: 24.0,
+            class: None,
+            z_index: None,
+            rect: Rect::default(),
+            manually_hidden: false,
+            disabled: false,
+            scissor_rect: None,
+            key: None,
+        }
+    }
+}
+
+impl<A: Clone + 'static> CanvasBuilder<A> {
+    /// The action sent when the zoom factor or the pan offset is changed by
+    /// the user.
+    pub fn on_view_changed<F: FnMut(f32, Vector) -> A + 'static>(mut self, f: F) -> Self {
+        self.view_changed_action = Some(Box::new(f));
+        self
+    }
+
+    /// Set the scissoring rectangle that this element will control. All
+    /// elements assigned to this scissoring rectangle are transformed by the
+    /// zoom factor and the pan offset of this canvas.
+    ///
+    /// If `scissor_rect_id == ScissorRectID::DEFAULT`, then this will
+    /// be ignored.
+    pub const fn control_scissor_rect(mut self, scissor_rect_id: ScissorRectID) -> Self {
+        self.control_scissor_rect = Some(scissor_rect_id);
+        self
+    }
+
+    /// The initial zoom factor.
+    ///
+    /// By default this is set to `1.0`.
+    pub const fn zoom(mut self, zoom: f32) -> Self {
+        self.zoom = zoom;
+        self
+    }
+
+    /// The initial pan offset in points.
+    ///
+    /// By default this is set to `Vector::zero()`.
+    pub const fn pan(mut self, pan: Vector) -> Self {
+        self.pan = pan;
+        self
+    }
+
+    /// The range the zoom factor is clamped to.
+    ///
+    /// By default this is set to `0.1..=10.0`.
+    pub const fn zoom_range(mut self, min_zoom: f32, max_zoom: f32) -> Self {
+        self.min_zoom = min_zoom;
+        self.max_zoom = max_zoom;
+        self
+    }
+
+    /// The factor the zoom is multiplied (or divided) by for each line the
+    /// scroll wheel is moved while the control key is held down.
+    ///
+    /// By default this is set to `1.1`.
+    pub const fn zoom_step(mut self, zoom_step: f32) -> Self {
+        self.zoom_step = zoom_step;
+        self
+    }
+
+    /// Whether or not the scroll wheel pans the view when the control key is
+    /// not held down.
+    ///
+    /// By default this is set to `true`.
+    pub const fn pan_with_scroll_wheel(mut self, do_pan: bool) -> Self {
+        self.pan_with_scroll_wheel = do_pan;
+        self
+    }
+
+    pub const fn points_per_line(mut self, points_per_line: f32) -> Self {
+        self.points_per_line = points_per_line;
+        self
+    }
+
+    pub fn build(self, window_cx: &mut WindowContext<'_, A>) -> Canvas {
+        let CanvasBuilder {
+            view_changed_action,
+            control_scissor_rect,
+            zoom,
+            pan,
+            min_zoom,
+            max_zoom,
+            zoom_step,
+            pan_with_scroll_wheel,
+            points_per_line,
+            class,
+            z_index,
+            rect,
+            manually_hidden,
+            disabled,
+            scissor_rect,
+            key,
+        } = self;
+
+        let min_zoom = min_zoom.max(f32::EPSILON);
+        let max_zoom = max_zoom.max(min_zoom);
+
+        let shared_state = Rc::new(RefCell::new(SharedState {
+            zoom: zoom.clamp(min_zoom, max_zoom),
+            pan,
+            min_zoom,
+            max_zoom,
+            disabled,
+        }));
+
+        let control_scissor_rect = control_scissor_rect.filter(|id| *id != ScissorRectID::DEFAULT);
+
+        let el = ElementBuilder::new(CanvasElement {
+            shared_state: Rc::clone(&shared_state),
+            control_scissor_rect,
+            view_changed_action,
+            zoom_step: zoom_step.max(1.0),
+            pan_with_scroll_wheel,
+            points_per_line,
+            drag_state: None,
+        })
+        .builder_values(z_index, scissor_rect, class, window_cx)
+        .key(key)
+        .rect(rect)
+        .hidden(manually_hidden)
+        .flags(
+            ElementFlags::PAINTS
+                | ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS
+                | ElementFlags::LISTENS_TO_POINTER_OUTSIDE_BOUNDS_WHEN_FOCUSED
+                | ElementFlags::LISTENS_TO_FOCUS_CHANGE
+                | ElementFlags::LISTENS_TO_SIZE_CHANGE
+                | ElementFlags::LISTENS_TO_POSITION_CHANGE
+                | ElementFlags::LISTENS_TO_INIT,
+        )
+        .build(window_cx);
+
+        Canvas { el, shared_state }
+    }
+}
+
+struct DragState {
+    drag_start_pos: Point,
+    drag_start_pan: Vector,
+}
+
+struct CanvasElement<A: Clone + 'static> {
+    shared_state: Rc<RefCell<SharedState>>,
+    control_scissor_rect: Option<ScissorRectID>,
+    view_changed_action: Option<Box<dyn FnMut(f32, Vector) -> A>>,
+    zoom_step: f32,
+    pan_with_scroll_wheel: bool,
+    points_per_line: f32,
+    drag_state: Option<DragState>,
+}
+
+fn update_scissor_rect<A: Clone + 'static>(
+    control_scissor_rect: Option<ScissorRectID>,
+    shared_state: &SharedState,
+    cx: &mut ElementContext<'_, A>,
+) {
+    if let Some(scissor_rect) = control_scissor_rect {
+        cx.update_scissor_rect(scissor_rect, Some(cx.rect()), Some(shared_state.pan));
+        cx.set_scissor_rect_zoom(scissor_rect, shared_state.zoom);
+    }
+}
+
+fn view_changed<A: Clone + 'static>(
+    view_changed_action: Option<&mut Box<dyn FnMut(f32, Vector) -> A>>,
+    control_scissor_rect: Option<ScissorRectID>,
+    shared_state: &SharedState,
+    cx: &mut ElementContext<'_, A>,
+) {
+    if let Some(action) = view_changed_action {
+        cx.send_action((action)(shared_state.zoom, shared_state.pan))
+            .unwrap();
+    }
+
+    cx.request_repaint();
+    update_scissor_rect(control_scissor_rect, shared_state, cx);
+}
+
+impl<A: Clone + 'static> Element<A> for CanvasElement<A> {
+    fn on_event(
+        &mut self,
+        event: ElementEvent,
+        cx: &mut ElementContext<'_, A>,
+    ) -> EventCaptureStatus {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        match event {
+            ElementEvent::Init | ElementEvent::PositionChanged | ElementEvent::SizeChanged => {
+                update_scissor_rect(self.control_scissor_rect, &shared_state, cx);
+                cx.request_repaint();
+            }
+            ElementEvent::CustomStateChanged => {
+                if shared_state.disabled && self.drag_state.take().is_some() {
+                    cx.release_focus();
+                }
+
+                update_scissor_rect(self.control_scissor_rect, &shared_state, cx);
+                cx.request_repaint();
+            }
+            ElementEvent::StyleChanged => {
+                cx.request_repaint();
+            }
+            ElementEvent::Pointer(PointerEvent::Moved { position, .. }) => {
+                let Some(drag_state) = &self.drag_state else {
+                    return EventCaptureStatus::NotCaptured;
+                };
+
+                let new_pan = drag_state.drag_start_pan - (position - drag_state.drag_start_pos);
+
+                if shared_state.pan != new_pan {
+                    shared_state.pan = new_pan;
+                    view_changed(
+                        self.view_changed_action.as_mut(),
+                        self.control_scissor_rect,
+                        &shared_state,
+                        cx,
+                    );
+                }
+
+                return EventCaptureStatus::Captured;
+            }
+            ElementEvent::Pointer(PointerEvent::ButtonJustPressed {
+                position, button, ..
+            }) => {
+                if shared_state.disabled
+                    || button != PointerButton::Auxiliary
+                    || !cx.rect().contains(position)
+                {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                self.drag_state = Some(DragState {
+                    drag_start_pos: position,
+                    drag_start_pan: shared_state.pan,
+                });
+
+                cx.steal_temporary_focus();
+
+                return EventCaptureStatus::Captured;
+            }
+            ElementEvent::Pointer(PointerEvent::ButtonJustReleased { button, .. }) => {
+                if button != PointerButton::Auxiliary || self.drag_state.is_none() {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                self.drag_state = None;
+                cx.release_focus();
+
+                return EventCaptureStatus::Captured;
+            }
+            ElementEvent::Pointer(PointerEvent::ScrollWheel {
+                position,
+                delta_type,
+                modifiers,
+                ..
+            }) => {
+                if shared_state.disabled || !cx.rect().contains(position) {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                let delta = delta_type.points(self.points_per_line, cx.rect().height());
+
+                if modifiers.contains(Modifiers::CONTROL) {
+                    let lines = -delta.y / self.points_per_line;
+                    let new_zoom = (shared_state.zoom * self.zoom_step.powf(lines))
+                        .clamp(shared_state.min_zoom, shared_state.max_zoom);
+
+                    // Keep the content point under the pointer in place.
+                    let anchor = position - cx.rect().origin.to_vector();
+                    if shared_state.zoom_around(new_zoom, anchor) {
+                        view_changed(
+                            self.view_changed_action.as_mut(),
+                            self.control_scissor_rect,
+                            &shared_state,
+                            cx,
+                        );
+                    }
+                } else if self.pan_with_scroll_wheel {
+                    if delta != Vector::zero() {
+                        shared_state.pan += delta;
+                        view_changed(
+                            self.view_changed_action.as_mut(),
+                            self.control_scissor_rect,
+                            &shared_state,
+                            cx,
+                        );
+                    }
+                } else {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                return EventCaptureStatus::Captured;
+            }
+            ElementEvent::Focus(false) => {
+                self.drag_state = None;
+            }
+            _ => {}
+        }
+
+        EventCaptureStatus::NotCaptured
+    }
+
+    fn render(&mut self, cx: RenderContext, primitives: &mut PrimitiveGroup) {
+        let shared_state = RefCell::borrow(&self.shared_state);
+        let style = cx.res.style_system.get::<CanvasStyle>(cx.class);
+
+        let bounds = Rect::from_size(cx.bounds_size);
+
+        style
+            .back_quad
+            .add_primitives_with_opacity(bounds, cx.opacity, primitives);
+
+        if style.grid_spacing <= 0.0 || style.grid_color.a == 0 {
+            return;
+        }
+
+        let mut spacing = style.grid_spacing * shared_state.zoom;
+        while spacing < style.grid_min_screen_spacing.max(1.0) {
+            spacing *= 2.0;
+        }
+
+        let mut lines: Vec<SolidQuadPrimitive> = Vec::new();
+
+        let mut x = (-shared_state.pan.x).rem_euclid(spacing);
+        while x < bounds.width() {
+            lines.push(
+                SolidQuadBuilder::new(Size::new(style.grid_line_width, bounds.height()))
+                    .bg_color(apply_opacity(style.grid_color, cx.opacity))
+                    .position(Point::new(x - (style.grid_line_width * 0.5), 0.0))
+                    .into(),
+            );
+            x += spacing;
+        }
+
+        let mut y = (-shared_state.pan.y).rem_euclid(spacing);
+        while y < bounds.height() {
+            lines.push(
+                SolidQuadBuilder::new(Size::new(bounds.width(), style.grid_line_width))
+                    .bg_color(apply_opacity(style.grid_color, cx.opacity))
+                    .position(Point::new(0.0, y - (style.grid_line_width * 0.5)))
+                    .into(),
+            );
+            y += spacing;
+        }
+
+        primitives.add_solid_quad_batch(lines);
+    }
+}
+
+struct SharedState {
+    zoom: f32,
+    pan: Vector,
+    min_zoom: f32,
+    max_zoom: f32,
+    disabled: bool,
+}
+
+impl SharedState {
+    /// Set the zoom factor while keeping the content point at `anchor` (in
+    /// points relative to the origin of the canvas) in place.
+    ///
+    /// Returns `true` if the view has changed.
+    fn zoom_around(&mut self, new_zoom: f32, anchor: Point) -> bool {
+        let new_zoom = new_zoom.clamp(self.min_zoom, self.max_zoom);
+        if self.zoom == new_zoom {
+            return false;
+        }
+
+        let content_pos = (anchor.to_vector() + self.pan) / self.zoom;
+
+        self.pan = (content_pos * new_zoom) - anchor.to_vector();
+        self.zoom = new_zoom;
+
+        true
+    }
+}
+
+/// A container which applies a zoom factor and a pan offset to all elements
+/// assigned to the scissoring rectangle it controls (see
+/// [`CanvasBuilder::control_scissor_rect`]). This is the foundation for node
+/// editors and arrangement views.
+///
+/// The rects of the child elements are given in content points. On screen,
+/// a content point `p` is shown at `canvas_origin + (p * zoom) - pan`. Note
+/// that only the rects of the child elements are transformed, so the
+/// elements are responsible for scaling their own contents (i.e. the size of
+/// their text) if desired.
+///
+/// The view is zoomed around the pointer by moving the scroll wheel while
+/// holding the control key, and it is panned by dragging with the middle
+/// pointer button or by moving the scroll wheel.
+#[element_handle]
+#[element_handle_class]
+#[element_handle_set_rect]
+#[element_handle_layout_aligned]
+pub struct Canvas {
+    shared_state: Rc<RefCell<SharedState>>,
+}
+
+impl Canvas {
+    pub fn builder<A: Clone + 'static>() -> CanvasBuilder<A> {
+        CanvasBuilder::default()
+    }
+
+    pub fn zoom(&self) -> f32 {
+        RefCell::borrow(&self.shared_state).zoom
+    }
+
+    pub fn pan(&self) -> Vector {
+        RefCell::borrow(&self.shared_state).pan
+    }
+
+    /// Set the zoom factor, keeping the content point at the top-left corner
+    /// of the canvas in place. The zoom factor is clamped to the range set
+    /// with [`CanvasBuilder::zoom_range`].
+    ///
+    /// Returns `true` if the zoom factor has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn set_zoom(&mut self, zoom: f32) -> bool {
+        self.zoom_around(zoom, Point::zero())
+    }
+
+    /// Set the zoom factor, keeping the content point at `anchor` (in points
+    /// relative to the origin of the canvas) in place. The zoom factor is
+    /// clamped to the range set with [`CanvasBuilder::zoom_range`].
+    ///
+    /// Returns `true` if the view has changed.
+    pub fn zoom_around(&mut self, zoom: f32, anchor: Point) -> bool {
+        let changed = RefCell::borrow_mut(&self.shared_state).zoom_around(zoom, anchor);
+
+        if changed {
+            self.el.notify_custom_state_change();
+        }
+
+        changed
+    }
+
+    /// Set the pan offset in points.
+    ///
+    /// Returns `true` if the pan offset has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn set_pan(&mut self, pan: Vector) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if shared_state.pan != pan {
+            shared_state.pan = pan;
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Convert a point in content points to a point relative to the origin
+    /// of the canvas.
+    pub fn content_to_view(&self, point: Point) -> Point {
+        let shared_state = RefCell::borrow(&self.shared_state);
+        (point * shared_state.zoom) - shared_state.pan
+    }
+
+    /// Convert a point relative to the origin of the canvas to a point in
+    /// content points.
+    pub fn view_to_content(&self, point: Point) -> Point {
+        let shared_state = RefCell::borrow(&self.shared_state);
+        (point + shared_state.pan) / shared_state.zoom
+    }
+
+    pub fn disabled(&self) -> bool {
+        RefCell::borrow(&self.shared_state).disabled
+    }
+
+    /// Set the disabled state of this element. While disabled, the view
+    /// cannot be zoomed or panned by the user.
+    ///
+    /// Returns `true` if the disabled state has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the state has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn set_disabled(&mut self, disabled: bool) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if shared_state.disabled != disabled {
+            shared_state.disabled = disabled;
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+}
@@ -0,0 +1,913 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::derive::*;
+use crate::prelude::*;
+use crate::vg::text::{RcTextBuffer, TextPrimitive};
+
+/// The style of a [`Carousel`] element.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CarouselStyle {
+    pub back_quad: QuadStyle,
+
+    /// The height of the strip at the bottom of the carousel which contains
+    /// the page-indicator dots and the arrow buttons. The pages are laid out
+    /// in the area above this strip.
+    ///
+    /// By default this is set to `20.0`.
+    pub indicator_height: f32,
+
+    /// The diameter of a page-indicator dot in points.
+    ///
+    /// By default this is set to `6.0`.
+    pub dot_size: f32,
+    /// The spacing between page-indicator dots in points.
+    ///
+    /// By default this is set to `6.0`.
+    pub dot_spacing: f32,
+    pub dot_color: RGBA8,
+    pub dot_color_hover: Option<RGBA8>,
+    pub dot_color_active: RGBA8,
+
+    /// The properties of the text used to draw the arrow buttons.
+    pub arrow_text_properties: TextProperties,
+    pub arrow_color: RGBA8,
+    pub arrow_color_hover: Option<RGBA8>,
+    pub arrow_color_disabled: DisabledColor,
+}
+
+impl Default for CarouselStyle {
+    fn default() -> Self {
+        Self {
+            back_quad: QuadStyle::TRANSPARENT,
+            indicator_height: 20.0,
+            dot_size: 6.0,
+            dot_spacing: 6.0,
+            dot_color: gray(90),
+            dot_color_hover: Some(gray(140)),
+            dot_color_active: gray(220),
+            arrow_text_properties: TextProperties::default(),
+            arrow_color: gray(180),
+            arrow_color_hover: Some(color::WHITE),
+            arrow_color_disabled: Default::default(),
+        }
+    }
+}
+
+impl ElementStyle for CarouselStyle {
+    const ID: &'static str = "carousel";
+
+    fn default_dark_style() -> Self {
+        Self::default()
+    }
+
+    fn default_light_style() -> Self {
+        Self {
+            dot_color: gray(190),
+            dot_color_hover: Some(gray(140)),
+            dot_color_active: gray(40),
+            arrow_color: gray(70),
+            arrow_color_hover: Some(color::BLACK),
+            ..Default::default()
+        }
+    }
+
+    fn with_auto_disabled(&self, derivation: DisabledDerivation) -> Option<Self> {
+        let mut style = self.clone();
+        style.arrow_color_disabled.derive_auto(derivation);
+        Some(style)
+    }
+}
+
+#[element_builder]
+#[element_builder_class]
+#[element_builder_rect]
+#[element_builder_hidden]
+#[element_builder_disabled]
+pub struct CarouselBuilder<A: Clone + 'static> {
+    pub page_changed_action: Option<Box<dyn FnMut(usize) -> A>>,
+    pub control_scissor_rect: Option<ScissorRectID>,
+    pub num_pages: usize,
+    pub page: usize,
+    pub show_arrows: bool,
+    pub swipe_threshold: f32,
+    pub transition_speed: f32,
+    pub points_per_line: f32,
+}
+
+impl<A: Clone + 'static> Default for CarouselBuilder<A> {
+    fn default() -> Self {
+        Self {
+            page_changed_action: None,
+            control_scissor_rect: None,
+            num_pages: 1,
+            page: 0,
+            show_arrows: true,
+            swipe_threshold: 0.2,
+            transition_speed: 14.0,
+            points_per_line: 24.0,
+            class: None,
+            z_index: None,
+            rect: Rect::default(),
+            manually_hidden: false,
+            disabled: false,
+            scissor_rect: None,
+            key: None,
+        }
+    }
+}
+
+impl<A: Clone + 'static> CarouselBuilder<A> {
+    /// The action sent when the current page is changed by the user.
+    pub fn on_page_changed<F: FnMut(usize) -> A + 'static>(mut self, f: F) -> Self {
+        self.page_changed_action = Some(Box::new(f));
+        self
+    }
+
+    /// Set the scissoring rectangle that this element will control. The
+    /// elements of page `n` should be assigned to this scissoring rectangle
+    /// and laid out inside of [`Carousel::page_rect`].
+    ///
+    /// If `scissor_rect_id == ScissorRectID::DEFAULT`, then this will
+    /// be ignored.
+    pub const fn control_scissor_rect(mut self, scissor_rect_id: ScissorRectID) -> Self {
+        self.control_scissor_rect = Some(scissor_rect_id);
+        self
+    }
+
+    /// The number of pages.
+    ///
+    /// By default this is set to `1`.
+    pub const fn num_pages(mut self, num_pages: usize) -> Self {
+        self.num_pages = num_pages;
+        self
+    }
+
+    /// The initially shown page.
+    ///
+    /// By default this is set to `0`.
+    pub const fn page(mut self, page: usize) -> Self {
+        self.page = page;
+        self
+    }
+
+    /// Whether or not to show the previous/next arrow buttons on either side
+    /// of the page-indicator dots.
+    ///
+    /// By default this is set to `true`.
+    pub const fn show_arrows(mut self, show: bool) -> Self {
+        self.show_arrows = show;
+        self
+    }
+
+    /// The fraction of the page width the user needs to swipe before the
+    /// carousel moves to the next or the previous page.
+    ///
+    /// By default this is set to `0.2`.
+    pub const fn swipe_threshold(mut self, threshold: f32) -> Self {
+        self.swipe_threshold = threshold;
+        self
+    }
+
+    /// How fast the page transition animation is. Higher values are faster.
+    /// Set this to `0.0` to disable the animation.
+    ///
+    /// By default this is set to `14.0`.
+    pub const fn transition_speed(mut self, speed: f32) -> Self {
+        self.transition_speed = speed;
+        self
+    }
+
+    pub const fn points_per_line(mut self, points_per_line: f32) -> Self {
+        self.points_per_line = points_per_line;
+        self
+    }
+
+    pub fn build(self, window_cx: &mut WindowContext<'_, A>) -> Carousel {
+        let CarouselBuilder {
+            page_changed_action,
+            control_scissor_rect,
+            num_pages,
+            page,
+            show_arrows,
+            swipe_threshold,
+            transition_speed,
+            points_per_line,
+            class,
+            z_index,
+            rect,
+            manually_hidden,
+            disabled,
+            scissor_rect,
+            key,
+        } = self;
+
+        let style = window_cx
+            .res
+            .style_system
+            .get::<CarouselStyle>(window_cx.builder_class(class));
+
+        let num_pages = num_pages.max(1);
+        let page = page.min(num_pages - 1);
+
+        let shared_state = Rc::new(RefCell::new(SharedState {
+            num_pages,
+            page,
+            position: page as f32,
+            pending_transition: None,
+            disabled,
+        }));
+
+        let control_scissor_rect = control_scissor_rect.filter(|id| *id != ScissorRectID::DEFAULT);
+
+        let arrow_buffers = [
+            RcTextBuffer::new(
+                "\u{2039}",
+                style.arrow_text_properties,
+                None,
+                None,
+                false,
+                &mut window_cx.res.font_system,
+            ),
+            RcTextBuffer::new(
+                "\u{203A}",
+                style.arrow_text_properties,
+                None,
+                None,
+                false,
+                &mut window_cx.res.font_system,
+            ),
+        ];
+
+        let el = ElementBuilder::new(CarouselElement {
+            shared_state: Rc::clone(&shared_state),
+            control_scissor_rect,
+            page_changed_action,
+            show_arrows,
+            swipe_threshold: swipe_threshold.max(0.0),
+            transition_speed: transition_speed.max(0.0),
+            points_per_line,
+            arrow_buffers,
+            drag_state: None,
+            hovered: None,
+            wheel_accumulator: 0.0,
+            animating: false,
+        })
+        .builder_values(z_index, scissor_rect, class, window_cx)
+        .key(key)
+        .rect(rect)
+        .hidden(manually_hidden)
+        .flags(
+            ElementFlags::PAINTS
+                | ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS
+                | ElementFlags::LISTENS_TO_POINTER_OUTSIDE_BOUNDS_WHEN_FOCUSED
+                | ElementFlags::LISTENS_TO_FOCUS_CHANGE
+                | ElementFlags::LISTENS_TO_SIZE_CHANGE
+                | ElementFlags::LISTENS_TO_POSITION_CHANGE
+                | ElementFlags::LISTENS_TO_INIT,
+        )
+        .build(window_cx);
+
+        Carousel { el, shared_state }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HoveredPart {
+    PrevArrow,
+    NextArrow,
+    Dot(usize),
+}
+
+struct DragState {
+    drag_start_pos: Point,
+    drag_start_position: f32,
+    drag_start_page: usize,
+}
+
+struct Layout {
+    page_area: Rect,
+    prev_arrow: Rect,
+    next_arrow: Rect,
+    first_dot_x: f32,
+    dots_y: f32,
+}
+
+impl Layout {
+    fn new(bounds: Rect, style: &CarouselStyle) -> Self {
+        let indicator_height = style.indicator_height.max(0.0).min(bounds.height());
+
+        let page_area = Rect::new(
+            bounds.origin,
+            Size::new(bounds.width(), bounds.height() - indicator_height),
+        );
+
+        let strip_y = page_area.max_y();
+        let prev_arrow = Rect::new(
+            Point::new(bounds.min_x(), strip_y),
+            Size::new(indicator_height, indicator_height),
+        );
+        let next_arrow = Rect::new(
+            Point::new(bounds.max_x() - indicator_height, strip_y),
+            Size::new(indicator_height, indicator_height),
+        );
+
+        Self {
+            page_area,
+            prev_arrow,
+            next_arrow,
+            first_dot_x: 0.0,
+            dots_y: strip_y + (indicator_height * 0.5),
+        }
+    }
+
+    fn with_dots(mut self, num_pages: usize, style: &CarouselStyle) -> Self {
+        let dots_width = (num_pages as f32 * style.dot_size)
+            + ((num_pages as f32 - 1.0).max(0.0) * style.dot_spacing);
+
+        self.first_dot_x = self.page_area.center().x - (dots_width * 0.5);
+        self
+    }
+
+    fn dot_rect(&self, i: usize, style: &CarouselStyle) -> Rect {
+        Rect::new(
+            Point::new(
+                self.first_dot_x + (i as f32 * (style.dot_size + style.dot_spacing)),
+                self.dots_y - (style.dot_size * 0.5),
+            ),
+            Size::new(style.dot_size, style.dot_size),
+        )
+    }
+
+    /// Returns the index of the dot at the given position. The hit area of a
+    /// dot extends vertically over the whole strip and horizontally halfway
+    /// into the spacing between dots.
+    fn dot_at(&self, position: Point, num_pages: usize, style: &CarouselStyle) -> Option<usize> {
+        if position.y < self.page_area.max_y() {
+            return None;
+        }
+
+        let stride = style.dot_size + style.dot_spacing;
+        if stride <= 0.0 {
+            return None;
+        }
+
+        let x = position.x - self.first_dot_x + (style.dot_spacing * 0.5);
+        if x < 0.0 {
+            return None;
+        }
+
+        let i = (x / stride).floor() as usize;
+        (i < num_pages).then_some(i)
+    }
+}
+
+struct CarouselElement<A: Clone + 'static> {
+    shared_state: Rc<RefCell<SharedState>>,
+    control_scissor_rect: Option<ScissorRectID>,
+    page_changed_action: Option<Box<dyn FnMut(usize) -> A>>,
+    show_arrows: bool,
+    swipe_threshold: f32,
+    transition_speed: f32,
+    points_per_line: f32,
+    arrow_buffers: [RcTextBuffer; 2],
+    drag_state: Option<DragState>,
+    hovered: Option<HoveredPart>,
+    wheel_accumulator: f32,
+    animating: bool,
+}
+
+fn update_scissor_rect<A: Clone + 'static>(
+    control_scissor_rect: Option<ScissorRectID>,
+    shared_state: &SharedState,
+    cx: &mut ElementContext<'_, A>,
+) {
+    if let Some(scissor_rect) = control_scissor_rect {
+        let style = cx.res.style_system.get::<CarouselStyle>(cx.class());
+        let page_area = Layout::new(cx.rect(), style).page_area;
+
+        cx.update_scissor_rect(
+            scissor_rect,
+            Some(page_area),
+            Some(Vector::new(shared_state.position * page_area.width(), 0.0)),
+        );
+    }
+}
+
+impl<A: Clone + 'static> CarouselElement<A> {
+    /// Move to the given page (with an animated transition if enabled).
+    fn go_to_page(
+        &mut self,
+        shared_state: &mut SharedState,
+        page: usize,
+        cx: &mut ElementContext<'_, A>,
+    ) {
+        let page = page.min(shared_state.num_pages - 1);
+
+        if shared_state.page != page {
+            shared_state.page = page;
+
+            if let Some(action) = self.page_changed_action.as_mut() {
+                cx.send_action((action)(page)).unwrap();
+            }
+        }
+
+        self.start_transition(shared_state, cx);
+    }
+
+    fn start_transition(&mut self, shared_state: &mut SharedState, cx: &mut ElementContext<'_, A>) {
+        let target = shared_state.page as f32;
+
+        if shared_state.position == target {
+            return;
+        }
+
+        if self.transition_speed > 0.0 {
+            if !self.animating {
+                self.animating = true;
+                cx.set_animating(true);
+            }
+        } else {
+            shared_state.position = target;
+            update_scissor_rect(self.control_scissor_rect, shared_state, cx);
+        }
+
+        cx.request_repaint();
+    }
+
+    fn stop_transition(&mut self, cx: &mut ElementContext<'_, A>) {
+        if self.animating {
+            self.animating = false;
+            cx.set_animating(false);
+        }
+    }
+}
+
+impl<A: Clone + 'static> Element<A> for CarouselElement<A> {
+    fn on_event(
+        &mut self,
+        event: ElementEvent,
+        cx: &mut ElementContext<'_, A>,
+    ) -> EventCaptureStatus {
+        let shared_state_rc = Rc::clone(&self.shared_state);
+        let mut shared_state = RefCell::borrow_mut(&shared_state_rc);
+
+        match event {
+            ElementEvent::Init | ElementEvent::PositionChanged | ElementEvent::SizeChanged => {
+                update_scissor_rect(self.control_scissor_rect, &shared_state, cx);
+                cx.request_repaint();
+            }
+            ElementEvent::CustomStateChanged => {
+                if shared_state.disabled {
+                    self.hovered = None;
+
+                    if self.drag_state.take().is_some() {
+                        cx.release_focus();
+                    }
+                }
+
+                match shared_state.pending_transition.take() {
+                    Some(true) => self.start_transition(&mut shared_state, cx),
+                    Some(false) => {
+                        self.stop_transition(cx);
+                        shared_state.position = shared_state.page as f32;
+                    }
+                    None => {
+                        shared_state.position = shared_state
+                            .position
+                            .min((shared_state.num_pages - 1) as f32);
+                    }
+                }
+
+                update_scissor_rect(self.control_scissor_rect, &shared_state, cx);
+                cx.request_repaint();
+            }
+            ElementEvent::StyleChanged => {
+                let style = cx.res.style_system.get::<CarouselStyle>(cx.class());
+
+                for (buffer, text) in self.arrow_buffers.iter_mut().zip(["\u{2039}", "\u{203A}"]) {
+                    *buffer = RcTextBuffer::new(
+                        text,
+                        style.arrow_text_properties,
+                        None,
+                        None,
+                        false,
+                        &mut cx.res.font_system,
+                    );
+                }
+
+                update_scissor_rect(self.control_scissor_rect, &shared_state, cx);
+                cx.request_repaint();
+            }
+            ElementEvent::Animation { delta_seconds } => {
+                if !self.animating {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                let target = shared_state.page as f32;
+                let t = 1.0 - (-(delta_seconds as f32) * self.transition_speed).exp();
+                shared_state.position += (target - shared_state.position) * t;
+
+                if (target - shared_state.position).abs() < 0.001 {
+                    shared_state.position = target;
+                    self.stop_transition(cx);
+                }
+
+                update_scissor_rect(self.control_scissor_rect, &shared_state, cx);
+                cx.request_repaint();
+            }
+            ElementEvent::Pointer(PointerEvent::Moved { position, .. }) => {
+                if shared_state.disabled {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                if let Some(drag_state) = &self.drag_state {
+                    let style = cx.res.style_system.get::<CarouselStyle>(cx.class());
+                    let page_width = Layout::new(cx.rect(), style).page_area.width().max(1.0);
+
+                    let max_position = (shared_state.num_pages - 1) as f32;
+                    let new_position = (drag_state.drag_start_position
+                        - ((position.x - drag_state.drag_start_pos.x) / page_width))
+                        .clamp(0.0, max_position);
+
+                    if shared_state.position != new_position {
+                        shared_state.position = new_position;
+                        update_scissor_rect(self.control_scissor_rect, &shared_state, cx);
+                        cx.request_repaint();
+                    }
+
+                    return EventCaptureStatus::Captured;
+                }
+
+                let style = cx.res.style_system.get::<CarouselStyle>(cx.class());
+                let layout = Layout::new(cx.rect(), style).with_dots(shared_state.num_pages, style);
+
+                let hovered = if self.show_arrows && layout.prev_arrow.contains(position) {
+                    Some(HoveredPart::PrevArrow)
+                } else if self.show_arrows && layout.next_arrow.contains(position) {
+                    Some(HoveredPart::NextArrow)
+                } else {
+                    layout
+                        .dot_at(position, shared_state.num_pages, style)
+                        .map(HoveredPart::Dot)
+                };
+
+                if self.hovered != hovered {
+                    self.hovered = hovered;
+                    cx.request_repaint();
+                }
+
+                if hovered.is_some() {
+                    cx.cursor_icon = CursorIcon::Pointer;
+                    return EventCaptureStatus::Captured;
+                }
+            }
+            ElementEvent::Pointer(PointerEvent::PointerLeft) => {
+                if self.hovered.take().is_some() {
+                    cx.request_repaint();
+                }
+            }
+            ElementEvent::Pointer(PointerEvent::ButtonJustPressed {
+                position, button, ..
+            }) => {
+                if shared_state.disabled
+                    || button != PointerButton::Primary
+                    || !cx.rect().contains(position)
+                {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                let style = cx.res.style_system.get::<CarouselStyle>(cx.class());
+                let layout = Layout::new(cx.rect(), style).with_dots(shared_state.num_pages, style);
+
+                if layout.page_area.contains(position) {
+                    self.stop_transition(cx);
+
+                    self.drag_state = Some(DragState {
+                        drag_start_pos: position,
+                        drag_start_position: shared_state.position,
+                        drag_start_page: shared_state.page,
+                    });
+
+                    cx.steal_temporary_focus();
+                    return EventCaptureStatus::Captured;
+                }
+
+                let page = shared_state.page;
+                if self.show_arrows && layout.prev_arrow.contains(position) {
+                    self.go_to_page(&mut shared_state, page.saturating_sub(1), cx);
+                } else if self.show_arrows && layout.next_arrow.contains(position) {
+                    self.go_to_page(&mut shared_state, page + 1, cx);
+                } else if let Some(i) = layout.dot_at(position, shared_state.num_pages, style) {
+                    self.go_to_page(&mut shared_state, i, cx);
+                }
+
+                return EventCaptureStatus::Captured;
+            }
+            ElementEvent::Pointer(PointerEvent::ButtonJustReleased { button, .. }) => {
+                if button != PointerButton::Primary {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                let Some(drag_state) = self.drag_state.take() else {
+                    return EventCaptureStatus::NotCaptured;
+                };
+
+                let delta = shared_state.position - drag_state.drag_start_position;
+                let page = if delta > self.swipe_threshold {
+                    drag_state.drag_start_page + 1
+                } else if delta < -self.swipe_threshold {
+                    drag_state.drag_start_page.saturating_sub(1)
+                } else {
+                    drag_state.drag_start_page
+                };
+
+                self.go_to_page(&mut shared_state, page, cx);
+                cx.release_focus();
+
+                return EventCaptureStatus::Captured;
+            }
+            ElementEvent::Pointer(PointerEvent::ScrollWheel {
+                position,
+                delta_type,
+                ..
+            }) => {
+                if shared_state.disabled
+                    || self.drag_state.is_some()
+                    || !cx.rect().contains(position)
+                {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                let delta = delta_type.points(self.points_per_line, cx.rect().height());
+                if delta.x.abs() <= delta.y.abs() {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                // Ignore the rest of the swipe gesture while a transition is
+                // in progress so that one swipe only moves one page.
+                if self.animating {
+                    self.wheel_accumulator = 0.0;
+                    return EventCaptureStatus::Captured;
+                }
+
+                self.wheel_accumulator += delta.x;
+
+                let style = cx.res.style_system.get::<CarouselStyle>(cx.class());
+                let page_width = Layout::new(cx.rect(), style).page_area.width().max(1.0);
+                let threshold = page_width * self.swipe_threshold;
+
+                let page = shared_state.page;
+                if self.wheel_accumulator > threshold {
+                    self.wheel_accumulator = 0.0;
+                    self.go_to_page(&mut shared_state, page + 1, cx);
+                } else if self.wheel_accumulator < -threshold {
+                    self.wheel_accumulator = 0.0;
+                    self.go_to_page(&mut shared_state, page.saturating_sub(1), cx);
+                }
+
+                return EventCaptureStatus::Captured;
+            }
+            ElementEvent::Focus(false) => {
+                if let Some(drag_state) = self.drag_state.take() {
+                    self.go_to_page(&mut shared_state, drag_state.drag_start_page, cx);
+                }
+            }
+            _ => {}
+        }
+
+        EventCaptureStatus::NotCaptured
+    }
+
+    fn render(&mut self, cx: RenderContext, primitives: &mut PrimitiveGroup) {
+        let shared_state = RefCell::borrow(&self.shared_state);
+        let style = cx.res.style_system.get::<CarouselStyle>(cx.class);
+
+        let bounds = Rect::from_size(cx.bounds_size);
+        let layout = Layout::new(bounds, style).with_dots(shared_state.num_pages, style);
+
+        if !style.back_quad.is_transparent() {
+            primitives.add(
+                style
+                    .back_quad
+                    .create_primitive_with_opacity(bounds, cx.opacity),
+            );
+        }
+
+        if layout.page_area.max_y() >= bounds.max_y() {
+            return;
+        }
+
+        primitives.set_z_index(1);
+
+        for i in 0..shared_state.num_pages {
+            let color = if i == shared_state.page {
+                style.dot_color_active
+            } else if self.hovered == Some(HoveredPart::Dot(i)) {
+                style.dot_color_hover.unwrap_or(style.dot_color)
+            } else {
+                style.dot_color
+            };
+
+            primitives.add(
+                QuadStyle {
+                    bg: Background::Solid(color),
+                    border: BorderStyle {
+                        radius: Radius::CIRCLE,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                }
+                .create_primitive_with_opacity(layout.dot_rect(i, style), cx.opacity),
+            );
+        }
+
+        if !self.show_arrows {
+            return;
+        }
+
+        let last_page = shared_state.num_pages - 1;
+        for (buffer, rect, part, enabled) in [
+            (
+                &self.arrow_buffers[0],
+                layout.prev_arrow,
+                HoveredPart::PrevArrow,
+                shared_state.page > 0,
+            ),
+            (
+                &self.arrow_buffers[1],
+                layout.next_arrow,
+                HoveredPart::NextArrow,
+                shared_state.page < last_page,
+            ),
+        ] {
+            let color = if shared_state.disabled || !enabled {
+                style.arrow_color_disabled.get(style.arrow_color)
+            } else if self.hovered == Some(part) {
+                style.arrow_color_hover.unwrap_or(style.arrow_color)
+            } else {
+                style.arrow_color
+            };
+
+            let size = buffer.measure();
+
+            primitives.add_text(TextPrimitive::new(
+                buffer.clone(),
+                rect.center() - (size * 0.5).to_vector(),
+                apply_opacity(color, cx.opacity),
+                None,
+            ));
+        }
+    }
+}
+
+struct SharedState {
+    num_pages: usize,
+    page: usize,
+    /// The currently shown (fractional) page while transitioning or dragging.
+    position: f32,
+    /// Set when the page is changed from the handle. The value is whether
+    /// or not the change should be animated.
+    pending_transition: Option<bool>,
+    disabled: bool,
+}
+
+/// A handle to a [`CarouselElement`], a paged container which shows one page
+/// at a time.
+///
+/// Each page is the size of [`Carousel::page_rect`]. The elements of page `n`
+/// should be assigned to the scissoring rectangle controlled by this carousel
+/// and laid out in the rectangle returned by `page_rect(n, ..)`. The user can
+/// move between pages by dragging or swiping horizontally, by clicking on the
+/// arrow buttons, or by clicking on the page-indicator dots.
+#[element_handle]
+#[element_handle_class]
+#[element_handle_set_rect]
+#[element_handle_layout_aligned]
+pub struct Carousel {
+    shared_state: Rc<RefCell<SharedState>>,
+}
+
+impl Carousel {
+    pub fn builder<A: Clone + 'static>() -> CarouselBuilder<A> {
+        CarouselBuilder::default()
+    }
+
+    /// The rectangle of the given page, relative to the scissoring rectangle
+    /// controlled by this carousel.
+    pub fn page_rect(&self, page: usize, res: &mut ResourceCtx) -> Rect {
+        let style = res.style_system.get::<CarouselStyle>(self.el.class());
+        let page_size = Layout::new(Rect::from_size(self.el.rect().size), style)
+            .page_area
+            .size;
+
+        Rect::new(Point::new(page as f32 * page_size.width, 0.0), page_size)
+    }
+
+    /// Move to the given page with an animated transition.
+    ///
+    /// Returns `true` if the page has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn go_to_page(&mut self, page: usize) -> bool {
+        self.set_page_inner(page, true)
+    }
+
+    /// Move to the given page immediately (without an animated transition).
+    ///
+    /// Returns `true` if the page has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn set_page(&mut self, page: usize) -> bool {
+        self.set_page_inner(page, false)
+    }
+
+    fn set_page_inner(&mut self, page: usize, animate: bool) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        let page = page.min(shared_state.num_pages - 1);
+
+        if shared_state.page != page {
+            shared_state.page = page;
+            shared_state.pending_transition = Some(animate);
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Move to the next page with an animated transition.
+    ///
+    /// Returns `true` if the page has changed.
+    pub fn next_page(&mut self) -> bool {
+        let page = self.page();
+        self.go_to_page(page + 1)
+    }
+
+    /// Move to the previous page with an animated transition.
+    ///
+    /// Returns `true` if the page has changed.
+    pub fn prev_page(&mut self) -> bool {
+        let page = self.page();
+        page > 0 && self.go_to_page(page - 1)
+    }
+
+    pub fn page(&self) -> usize {
+        RefCell::borrow(&self.shared_state).page
+    }
+
+    /// Set the number of pages. The current page is clamped to the new range.
+    ///
+    /// Returns `true` if the number of pages has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn set_num_pages(&mut self, num_pages: usize) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        let num_pages = num_pages.max(1);
+
+        if shared_state.num_pages != num_pages {
+            shared_state.num_pages = num_pages;
+
+            if shared_state.page >= num_pages {
+                shared_state.page = num_pages - 1;
+                shared_state.pending_transition = Some(false);
+            }
+
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn num_pages(&self) -> usize {
+        RefCell::borrow(&self.shared_state).num_pages
+    }
+
+    pub fn disabled(&self) -> bool {
+        RefCell::borrow(&self.shared_state).disabled
+    }
+
+    /// Set the disabled state of this element.
+    ///
+    /// Returns `true` if the disabled state has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn set_disabled(&mut self, disabled: bool) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if shared_state.disabled != disabled {
+            shared_state.disabled = disabled;
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+}
@@ -69,6 +69,7 @@ impl<A: Clone + 'static> ClickAreaBuilder<A> {
             z_index,
             disabled,
             scissor_rect,
+            key,
         } = self;
 
         let shared_state = Rc::new(RefCell::new(SharedState {
@@ -85,6 +86,7 @@ impl<A: Clone + 'static> ClickAreaBuilder<A> {
             shared_state: Rc::clone(&shared_state),
         })
         .builder_values(z_index, scissor_rect, None, window_cx)
+        .key(key)
         .rect(rect)
         .hidden(disabled)
         .flags(ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS)
@@ -0,0 +1,234 @@
+use std::cell::{Ref, RefCell};
+use std::rc::Rc;
+
+use rootvg::PrimitiveGroup;
+
+use crate::derive::*;
+use crate::prelude::*;
+
+/// A simplified version of the [`Element`] trait for quickly dropping a
+/// bespoke visualization or control into a view.
+///
+/// All methods except [`SimpleElement::paint`] have default implementations
+/// which do nothing. All pointer positions are relative to the top-left
+/// corner of the element.
+///
+/// The element automatically captures the pointer when a button press is
+/// captured, and releases it once the button is released again, so drags
+/// continue to be received when the pointer leaves the bounds of the element.
+///
+/// Use [`custom_element`] to add it to a view.
+pub trait SimpleElement<A: Clone + 'static>: 'static {
+    /// Add the primitives for this element to `primitives`.
+    ///
+    /// This is called again when the size of the element changes, when the
+    /// state is changed with [`CustomElement::update`], or when an event
+    /// handler calls `ElementContext::request_repaint`.
+    fn paint(&mut self, cx: RenderContext, primitives: &mut PrimitiveGroup);
+
+    #[allow(unused)]
+    fn on_pointer_down(
+        &mut self,
+        position: Point,
+        button: PointerButton,
+        cx: &mut ElementContext<'_, A>,
+    ) -> EventCaptureStatus {
+        EventCaptureStatus::NotCaptured
+    }
+
+    #[allow(unused)]
+    fn on_pointer_up(
+        &mut self,
+        position: Point,
+        button: PointerButton,
+        cx: &mut ElementContext<'_, A>,
+    ) -> EventCaptureStatus {
+        EventCaptureStatus::NotCaptured
+    }
+
+    #[allow(unused)]
+    fn on_pointer_moved(
+        &mut self,
+        position: Point,
+        cx: &mut ElementContext<'_, A>,
+    ) -> EventCaptureStatus {
+        EventCaptureStatus::NotCaptured
+    }
+
+    #[allow(unused)]
+    fn on_pointer_left(&mut self, cx: &mut ElementContext<'_, A>) {}
+
+    #[allow(unused)]
+    fn on_scroll(
+        &mut self,
+        position: Point,
+        delta: WheelDeltaType,
+        cx: &mut ElementContext<'_, A>,
+    ) -> EventCaptureStatus {
+        EventCaptureStatus::NotCaptured
+    }
+
+    /// The cursor icon to show while the pointer is hovering over this
+    /// element.
+    fn cursor_icon(&self) -> Option<CursorIcon> {
+        None
+    }
+}
+
+/// Create a builder for a [`CustomElement`] from the given
+/// [`SimpleElement`].
+pub fn custom_element<T>(element: T) -> CustomElementBuilder<T> {
+    CustomElementBuilder {
+        element,
+        class: None,
+        rect: Rect::default(),
+        manually_hidden: false,
+        z_index: None,
+        scissor_rect: None,
+        key: None,
+    }
+}
+
+#[element_builder]
+#[element_builder_class]
+#[element_builder_rect]
+#[element_builder_hidden]
+pub struct CustomElementBuilder<T> {
+    pub element: T,
+}
+
+impl<T> CustomElementBuilder<T> {
+    pub fn build<A: Clone + 'static>(self, window_cx: &mut WindowContext<'_, A>) -> CustomElement<T>
+    where
+        T: SimpleElement<A>,
+    {
+        let CustomElementBuilder {
+            element,
+            class,
+            z_index,
+            rect,
+            manually_hidden,
+            scissor_rect,
+            key,
+        } = self;
+
+        let shared_state = Rc::new(RefCell::new(element));
+
+        let el = ElementBuilder::new(CustomElementInternal {
+            shared_state: Rc::clone(&shared_state),
+            pressed_buttons: 0,
+        })
+        .builder_values(z_index, scissor_rect, class, window_cx)
+        .key(key)
+        .rect(rect)
+        .hidden(manually_hidden)
+        .flags(ElementFlags::PAINTS | ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS)
+        .build(window_cx);
+
+        CustomElement { el, shared_state }
+    }
+}
+
+struct CustomElementInternal<T> {
+    shared_state: Rc<RefCell<T>>,
+    /// The number of captured button presses which have not been released
+    /// yet.
+    pressed_buttons: usize,
+}
+
+impl<A: Clone + 'static, T: SimpleElement<A>> Element<A> for CustomElementInternal<T> {
+    fn on_event(
+        &mut self,
+        event: ElementEvent,
+        cx: &mut ElementContext<'_, A>,
+    ) -> EventCaptureStatus {
+        let mut element = RefCell::borrow_mut(&self.shared_state);
+        let origin = cx.rect().origin;
+
+        match event {
+            ElementEvent::CustomStateChanged => {
+                cx.request_repaint();
+            }
+            ElementEvent::Pointer(PointerEvent::Moved { position, .. }) => {
+                if let Some(icon) = element.cursor_icon() {
+                    if cx.rect().contains(position) {
+                        cx.cursor_icon = icon;
+                    }
+                }
+
+                return element.on_pointer_moved((position - origin).to_point(), cx);
+            }
+            ElementEvent::Pointer(PointerEvent::ButtonJustPressed {
+                position, button, ..
+            }) => {
+                let status = element.on_pointer_down((position - origin).to_point(), button, cx);
+
+                if status == EventCaptureStatus::Captured {
+                    self.pressed_buttons += 1;
+                    cx.capture_pointer();
+                }
+
+                return status;
+            }
+            ElementEvent::Pointer(PointerEvent::ButtonJustReleased {
+                position, button, ..
+            }) => {
+                let status = element.on_pointer_up((position - origin).to_point(), button, cx);
+
+                if self.pressed_buttons > 0 {
+                    self.pressed_buttons -= 1;
+
+                    if self.pressed_buttons == 0 {
+                        cx.release_pointer();
+                    }
+                }
+
+                return status;
+            }
+            ElementEvent::Pointer(PointerEvent::ScrollWheel {
+                position,
+                delta_type,
+                ..
+            }) => {
+                return element.on_scroll((position - origin).to_point(), delta_type, cx);
+            }
+            ElementEvent::Pointer(PointerEvent::PointerLeft) => {
+                element.on_pointer_left(cx);
+            }
+            _ => {}
+        }
+
+        EventCaptureStatus::NotCaptured
+    }
+
+    fn render(&mut self, cx: RenderContext, primitives: &mut PrimitiveGroup) {
+        RefCell::borrow_mut(&self.shared_state).paint(cx, primitives);
+    }
+}
+
+/// A handle to an element created from a [`SimpleElement`].
+#[element_handle]
+#[element_handle_class]
+#[element_handle_set_rect]
+#[element_handle_layout_aligned]
+pub struct CustomElement<T> {
+    shared_state: Rc<RefCell<T>>,
+}
+
+impl<T> CustomElement<T> {
+    pub fn builder(element: T) -> CustomElementBuilder<T> {
+        custom_element(element)
+    }
+
+    /// Borrow the state of the element.
+    pub fn state(&self) -> Ref<'_, T> {
+        RefCell::borrow(&self.shared_state)
+    }
+
+    /// Modify the state of the element and repaint it.
+    pub fn update<R>(&mut self, f: impl FnOnce(&mut T) -> R) -> R {
+        let res = (f)(&mut RefCell::borrow_mut(&self.shared_state));
+        self.el.notify_custom_state_change();
+        res
+    }
+}
@@ -0,0 +1,915 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::derive::*;
+use crate::prelude::*;
+
+use super::label::LabelInner;
+use super::text_input::{TextInputInner, TextInputUpdateResult};
+
+/// The style of a [`DragValue`] element
+#[derive(Debug, Clone, PartialEq)]
+pub struct DragValueStyle {
+    /// The style of the value when it is not being edited.
+    pub label: LabelStyle,
+    /// The style of the value while it is being dragged.
+    ///
+    /// If this is `None`, then `label` is used.
+    pub label_gesturing: Option<LabelStyle>,
+    /// The style of the inline text input when the value is being typed in.
+    pub text_input: TextInputStyle,
+
+    /// The cursor icon to show when the user hovers over this element.
+    ///
+    /// By default this is set to `Some(CursorIcon::EwResize)`.
+    pub cursor_icon: Option<CursorIcon>,
+}
+
+impl Default for DragValueStyle {
+    fn default() -> Self {
+        Self {
+            label: LabelStyle::default(),
+            label_gesturing: None,
+            text_input: TextInputStyle::default(),
+            cursor_icon: Some(CursorIcon::EwResize),
+        }
+    }
+}
+
+impl ElementStyle for DragValueStyle {
+    const ID: &'static str = "dragval";
+
+    fn default_dark_style() -> Self {
+        Self::default()
+    }
+
+    fn default_light_style() -> Self {
+        Self {
+            label: LabelStyle::default_light_style(),
+            text_input: TextInputStyle {
+                text_color: color::BLACK,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    fn with_auto_disabled(&self, derivation: DisabledDerivation) -> Option<Self> {
+        let mut style = self.clone();
+        style.text_input.derive_auto_disabled(derivation);
+        Some(style)
+    }
+}
+
+/// The default formatting of values in a [`DragValue`], with two decimal
+/// places and the locale's separators.
+pub fn default_drag_value_format(value: f64, locale: &Locale) -> String {
+    locale.format_number(value, 2)
+}
+
+/// The default parsing of values typed into a [`DragValue`], accepting the
+/// locale's separators.
+pub fn default_drag_value_parse(text: &str, locale: &Locale) -> Option<f64> {
+    locale.parse_number(text)
+}
+
+#[element_builder]
+#[element_builder_class]
+#[element_builder_rect]
+#[element_builder_hidden]
+#[element_builder_disabled]
+#[element_builder_tooltip]
+pub struct DragValueBuilder<A: Clone + 'static> {
+    pub changed_action: Option<Box<dyn FnMut(f64) -> A>>,
+    pub gesture_finished_action: Option<Box<dyn FnMut(f64) -> A>>,
+    pub value: f64,
+    pub min: f64,
+    pub max: f64,
+    pub speed: f64,
+    pub step: Option<f64>,
+    pub fine_adjustment_scalar: f64,
+    pub fine_adjustment_modifier: Option<Modifiers>,
+    pub format: fn(f64, &Locale) -> String,
+    pub parse: fn(&str, &Locale) -> Option<f64>,
+    pub text_offset: Vector,
+}
+
+impl<A: Clone + 'static> DragValueBuilder<A> {
+    pub fn new() -> Self {
+        Self {
+            changed_action: None,
+            gesture_finished_action: None,
+            value: 0.0,
+            min: f64::NEG_INFINITY,
+            max: f64::INFINITY,
+            speed: 1.0,
+            step: None,
+            fine_adjustment_scalar: 0.1,
+            fine_adjustment_modifier: Some(Modifiers::SHIFT),
+            format: default_drag_value_format,
+            parse: default_drag_value_parse,
+            text_offset: Vector::default(),
+            class: Default::default(),
+            z_index: Default::default(),
+            rect: Default::default(),
+            manually_hidden: Default::default(),
+            disabled: Default::default(),
+            scissor_rect: Default::default(),
+            key: Default::default(),
+            tooltip_data: Default::default(),
+        }
+    }
+
+    /// The action to send when the value is changed by the user (either by
+    /// dragging or by typing in a new value).
+    pub fn on_changed<F: FnMut(f64) -> A + 'static>(mut self, f: F) -> Self {
+        self.changed_action = Some(Box::new(f));
+        self
+    }
+
+    /// The action to send when the user releases the value after dragging it
+    /// or commits a typed in value (i.e. for creating a single undo step).
+    pub fn on_gesture_finished<F: FnMut(f64) -> A + 'static>(mut self, f: F) -> Self {
+        self.gesture_finished_action = Some(Box::new(f));
+        self
+    }
+
+    pub const fn value(mut self, value: f64) -> Self {
+        self.value = value;
+        self
+    }
+
+    /// The range the value is clamped to.
+    ///
+    /// By default the value is unbounded.
+    pub const fn range(mut self, min: f64, max: f64) -> Self {
+        self.min = min;
+        self.max = max;
+        self
+    }
+
+    /// How much the value changes per point the pointer is dragged
+    /// horizontally.
+    ///
+    /// By default this is set to `1.0`.
+    pub const fn speed(mut self, speed: f64) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// If set, then the value is rounded to the nearest multiple of this
+    /// step while dragging.
+    ///
+    /// By default this is set to `None`.
+    pub const fn step(mut self, step: Option<f64>) -> Self {
+        self.step = step;
+        self
+    }
+
+    /// The scalar applied to the speed when the fine adjustment modifier is
+    /// held down.
+    ///
+    /// By default this is set to `0.1`.
+    pub const fn fine_adjustment_scalar(mut self, scalar: f64) -> Self {
+        self.fine_adjustment_scalar = scalar;
+        self
+    }
+
+    /// The modifier key to use when making fine adjustments.
+    ///
+    /// Set this to `None` to disable the fine adjustment modifier.
+    ///
+    /// By default this is set to `Some(Modifiers::SHIFT)`
+    pub const fn fine_adjustment_modifier(mut self, modifier: Option<Modifiers>) -> Self {
+        self.fine_adjustment_modifier = modifier;
+        self
+    }
+
+    /// The function used to display the value, given the locale set on the
+    /// [`ResourceCtx`].
+    ///
+    /// By default this is set to [`default_drag_value_format`].
+    pub const fn format(mut self, format: fn(f64, &Locale) -> String) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// The function used to parse a typed in value. If this returns `None`,
+    /// then the typed in value is discarded.
+    ///
+    /// By default this is set to [`default_drag_value_parse`].
+    pub const fn parse(mut self, parse: fn(&str, &Locale) -> Option<f64>) -> Self {
+        self.parse = parse;
+        self
+    }
+
+    /// An offset that can be used mainly to correct the position of the text.
+    /// This does not effect the position of the background quad.
+    ///
+    /// By default this is set to an offset of zero.
+    pub const fn text_offset(mut self, offset: Vector) -> Self {
+        self.text_offset = offset;
+        self
+    }
+
+    pub fn build(self, window_cx: &mut WindowContext<'_, A>) -> DragValue {
+        let DragValueBuilder {
+            changed_action,
+            gesture_finished_action,
+            value,
+            min,
+            max,
+            speed,
+            step,
+            fine_adjustment_scalar,
+            fine_adjustment_modifier,
+            format,
+            parse,
+            text_offset,
+            class,
+            z_index,
+            rect,
+            manually_hidden,
+            disabled,
+            scissor_rect,
+            key,
+            tooltip_data,
+        } = self;
+
+        let style = window_cx
+            .res
+            .style_system
+            .get::<DragValueStyle>(window_cx.builder_class(class));
+
+        let max = max.max(min);
+        let value = value.clamp(min, max);
+
+        let mut label_inner = LabelInner::new(
+            Some((format)(value, &window_cx.res.locale)),
+            None,
+            text_offset,
+            Vector::default(),
+            None,
+            IconScale::default(),
+            TextIconLayout::default(),
+            &style.label,
+            &mut window_cx.res.font_system,
+            &mut window_cx.res.text_layout_cache,
+        );
+        label_inner.set_text_direction(
+            window_cx.text_direction(),
+            &style.label,
+            &mut window_cx.res.font_system,
+            &mut window_cx.res.text_layout_cache,
+        );
+
+        let shared_state = Rc::new(RefCell::new(SharedState {
+            label_inner,
+            input_inner: TextInputInner::new(
+                String::new(),
+                String::new(),
+                false,
+                64,
+                rect.size,
+                false,
+                true,
+                &style.text_input,
+                &mut window_cx.res.font_system,
+            ),
+            value,
+            min,
+            max,
+            format,
+            editing: false,
+            disabled,
+            tooltip_inner: TooltipInner::new(tooltip_data),
+        }));
+
+        let el = ElementBuilder::new(DragValueElement {
+            shared_state: Rc::clone(&shared_state),
+            changed_action,
+            gesture_finished_action,
+            speed,
+            step,
+            fine_adjustment_scalar,
+            fine_adjustment_modifier,
+            parse,
+            drag_state: None,
+            canceled: false,
+            hovered: false,
+        })
+        .builder_values(z_index, scissor_rect, class, window_cx)
+        .key(key)
+        .rect(rect)
+        .hidden(manually_hidden)
+        .flags(
+            ElementFlags::PAINTS
+                | ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS
+                | ElementFlags::LISTENS_TO_POINTER_OUTSIDE_BOUNDS_WHEN_FOCUSED
+                | ElementFlags::LISTENS_TO_TEXT_COMPOSITION_WHEN_FOCUSED
+                | ElementFlags::LISTENS_TO_KEYS_WHEN_FOCUSED
+                | ElementFlags::LISTENS_TO_SIZE_CHANGE
+                | ElementFlags::LISTENS_TO_FOCUS_CHANGE,
+        )
+        .build(window_cx);
+
+        DragValue { el, shared_state }
+    }
+}
+
+struct DragState {
+    /// The horizontal position of the pointer where the current drag
+    /// segment started.
+    start_x: f32,
+    /// The unstepped value at the start of the current drag segment.
+    start_value: f64,
+    /// The unstepped value at the current pointer position.
+    raw_value: f64,
+    fine: bool,
+}
+
+/// A number which changes when it is dragged horizontally, and which turns
+/// into an inline text input when it is double-clicked.
+struct DragValueElement<A: Clone + 'static> {
+    shared_state: Rc<RefCell<SharedState>>,
+    changed_action: Option<Box<dyn FnMut(f64) -> A>>,
+    gesture_finished_action: Option<Box<dyn FnMut(f64) -> A>>,
+    speed: f64,
+    step: Option<f64>,
+    fine_adjustment_scalar: f64,
+    fine_adjustment_modifier: Option<Modifiers>,
+    parse: fn(&str, &Locale) -> Option<f64>,
+    drag_state: Option<DragState>,
+    canceled: bool,
+    hovered: bool,
+}
+
+impl<A: Clone + 'static> DragValueElement<A> {
+    fn is_fine(&self, modifiers: Modifiers) -> bool {
+        self.fine_adjustment_modifier
+            .is_some_and(|m| modifiers.contains(m))
+    }
+
+    fn on_label_event(
+        &mut self,
+        shared_state: &mut SharedState,
+        event: ElementEvent,
+        cx: &mut ElementContext<'_, A>,
+    ) -> EventCaptureStatus {
+        shared_state
+            .tooltip_inner
+            .handle_event(&event, shared_state.disabled, cx);
+
+        match event {
+            ElementEvent::CustomStateChanged => {
+                if shared_state.disabled && self.drag_state.take().is_some() {
+                    cx.release_focus();
+                }
+
+                shared_state.update_label(cx.class(), cx.res);
+                cx.request_repaint();
+            }
+            ElementEvent::StyleChanged => {
+                cx.request_repaint();
+            }
+            ElementEvent::Pointer(PointerEvent::Moved {
+                position,
+                modifiers,
+                ..
+            }) => {
+                let fine = self.is_fine(modifiers);
+                let speed = self.speed;
+                let fine_adjustment_scalar = self.fine_adjustment_scalar;
+
+                let Some(drag_state) = self.drag_state.as_mut() else {
+                    if !shared_state.disabled && cx.rect().contains(position) {
+                        if let Some(cursor_icon) = cx
+                            .res
+                            .style_system
+                            .get::<DragValueStyle>(cx.class())
+                            .cursor_icon
+                        {
+                            cx.cursor_icon = cursor_icon;
+                        }
+                    }
+
+                    return EventCaptureStatus::NotCaptured;
+                };
+
+                if drag_state.fine != fine {
+                    // Start a new drag segment so the value doesn't jump when
+                    // the fine adjustment modifier is pressed or released.
+                    drag_state.start_x = position.x;
+                    drag_state.start_value = drag_state.raw_value;
+                    drag_state.fine = fine;
+                }
+
+                let speed = if fine {
+                    speed * fine_adjustment_scalar
+                } else {
+                    speed
+                };
+
+                drag_state.raw_value = (drag_state.start_value
+                    + ((position.x - drag_state.start_x) as f64 * speed))
+                    .clamp(shared_state.min, shared_state.max);
+
+                let new_value = snap_value(
+                    drag_state.raw_value,
+                    self.step,
+                    shared_state.min,
+                    shared_state.max,
+                );
+
+                if let Some(cursor_icon) = cx
+                    .res
+                    .style_system
+                    .get::<DragValueStyle>(cx.class())
+                    .cursor_icon
+                {
+                    cx.cursor_icon = cursor_icon;
+                }
+
+                if shared_state.value != new_value {
+                    shared_state.value = new_value;
+                    shared_state.update_label(cx.class(), cx.res);
+
+                    if let Some(action) = self.changed_action.as_mut() {
+                        cx.send_action((action)(new_value)).unwrap();
+                    }
+
+                    cx.request_repaint();
+                }
+
+                return EventCaptureStatus::Captured;
+            }
+            ElementEvent::Pointer(PointerEvent::ButtonJustPressed {
+                position,
+                button,
+                click_count,
+                modifiers,
+                ..
+            }) => {
+                if button != PointerButton::Primary
+                    || shared_state.disabled
+                    || !cx.rect().contains(position)
+                {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                if click_count == 2 {
+                    self.drag_state = None;
+                    Self::begin_editing(shared_state, cx);
+                } else {
+                    self.drag_state = Some(DragState {
+                        start_x: position.x,
+                        start_value: shared_state.value,
+                        raw_value: shared_state.value,
+                        fine: self.is_fine(modifiers),
+                    });
+
+                    cx.steal_temporary_focus();
+                    cx.request_repaint();
+                }
+
+                return EventCaptureStatus::Captured;
+            }
+            ElementEvent::Pointer(PointerEvent::ButtonJustReleased { button, .. }) => {
+                if button != PointerButton::Primary || self.drag_state.take().is_none() {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                if let Some(action) = self.gesture_finished_action.as_mut() {
+                    cx.send_action((action)(shared_state.value)).unwrap();
+                }
+
+                cx.release_focus();
+                cx.request_repaint();
+
+                return EventCaptureStatus::Captured;
+            }
+            ElementEvent::Focus(false) => {
+                if self.drag_state.take().is_some() {
+                    if let Some(action) = self.gesture_finished_action.as_mut() {
+                        cx.send_action((action)(shared_state.value)).unwrap();
+                    }
+
+                    cx.request_repaint();
+                }
+            }
+            _ => {}
+        }
+
+        EventCaptureStatus::NotCaptured
+    }
+
+    fn begin_editing(shared_state: &mut SharedState, cx: &mut ElementContext<'_, A>) {
+        let text = String::from(shared_state.label_inner.text().unwrap_or_default());
+
+        shared_state
+            .input_inner
+            .set_text(text, &mut cx.res.font_system, true);
+
+        shared_state.editing = true;
+        cx.steal_temporary_focus();
+        cx.request_repaint();
+    }
+}
+
+impl<A: Clone + 'static> Element<A> for DragValueElement<A> {
+    fn on_event(
+        &mut self,
+        event: ElementEvent,
+        cx: &mut ElementContext<'_, A>,
+    ) -> EventCaptureStatus {
+        let shared_state_rc = Rc::clone(&self.shared_state);
+        let mut shared_state = RefCell::borrow_mut(&shared_state_rc);
+
+        if let ElementEvent::SizeChanged = &event {
+            let bounds_size = cx.rect().size;
+            let style = cx.res.style_system.get::<DragValueStyle>(cx.class());
+            shared_state.input_inner.on_size_changed(
+                bounds_size,
+                &style.text_input,
+                &mut cx.res.font_system,
+            );
+        }
+
+        if !shared_state.editing {
+            return self.on_label_event(&mut shared_state, event, cx);
+        }
+
+        let res = match event {
+            ElementEvent::Animation { .. } => shared_state.input_inner.on_animation(),
+            ElementEvent::CustomStateChanged => shared_state
+                .input_inner
+                .on_custom_state_changed(cx.clipboard, &mut cx.res.font_system),
+            ElementEvent::Pointer(PointerEvent::Moved { position, .. }) => shared_state
+                .input_inner
+                .on_pointer_moved(position, cx.rect(), &mut cx.res.font_system),
+            ElementEvent::Pointer(PointerEvent::ButtonJustPressed {
+                position,
+                button,
+                click_count,
+                ..
+            }) => shared_state.input_inner.on_pointer_button_just_pressed(
+                position,
+                button,
+                click_count,
+                cx.rect(),
+                &mut cx.res.font_system,
+            ),
+            ElementEvent::Pointer(PointerEvent::ButtonJustReleased {
+                button, position, ..
+            }) => shared_state.input_inner.on_pointer_button_just_released(
+                position,
+                button,
+                cx.rect(),
+            ),
+            ElementEvent::Pointer(PointerEvent::PointerLeft) => {
+                shared_state.input_inner.on_pointer_left()
+            }
+            ElementEvent::Keyboard(key_event) => shared_state.input_inner.on_keyboard_event(
+                &key_event,
+                cx.clipboard,
+                &mut cx.res.font_system,
+            ),
+            ElementEvent::TextComposition(comp_event) => shared_state
+                .input_inner
+                .on_text_composition_event(&comp_event, &mut cx.res.font_system),
+            ElementEvent::Focus(has_focus) => {
+                if !has_focus {
+                    shared_state.editing = false;
+
+                    let canceled = std::mem::take(&mut self.canceled);
+                    let parsed = if canceled {
+                        None
+                    } else {
+                        (self.parse)(shared_state.input_inner.text(), &cx.res.locale)
+                    };
+
+                    // Let the user know why the typed in value was rejected
+                    // or clamped.
+                    if !canceled {
+                        let error = match parsed {
+                            None => Some(
+                                cx.res
+                                    .locale
+                                    .builtin(BuiltinString::InvalidNumber)
+                                    .into_owned(),
+                            ),
+                            Some(v) if v < shared_state.min || v > shared_state.max => {
+                                let min = (shared_state.format)(shared_state.min, &cx.res.locale);
+                                let max = (shared_state.format)(shared_state.max, &cx.res.locale);
+                                Some(cx.res.locale.builtin_with_args(
+                                    BuiltinString::NumberOutOfRange,
+                                    &[("min", &min), ("max", &max)],
+                                ))
+                            }
+                            _ => None,
+                        };
+
+                        if let Some(error) = error {
+                            cx.show_tooltip(TooltipData::new(error, Align2::TOP_CENTER), true);
+                        }
+                    }
+
+                    if let Some(new_value) =
+                        parsed.map(|v| v.clamp(shared_state.min, shared_state.max))
+                    {
+                        if shared_state.value != new_value {
+                            shared_state.value = new_value;
+
+                            if let Some(action) = self.changed_action.as_mut() {
+                                cx.send_action((action)(new_value)).unwrap();
+                            }
+                            if let Some(action) = self.gesture_finished_action.as_mut() {
+                                cx.send_action((action)(new_value)).unwrap();
+                            }
+                        }
+                    }
+
+                    shared_state.update_label(cx.class(), cx.res);
+                }
+
+                shared_state.input_inner.on_focus_changed(
+                    has_focus,
+                    cx.clipboard,
+                    &mut cx.res.font_system,
+                )
+            }
+            ElementEvent::ClickedOff => {
+                cx.release_focus();
+
+                shared_state.input_inner.on_clicked_off()
+            }
+            _ => TextInputUpdateResult::default(),
+        };
+
+        if res.needs_repaint {
+            cx.request_repaint();
+        }
+        if res.hovered {
+            self.hovered = true;
+            cx.cursor_icon = CursorIcon::Text;
+        } else {
+            self.hovered = false;
+        }
+        if res.listen_to_pointer_clicked_off {
+            cx.listen_to_pointer_clicked_off();
+        }
+        if let Some(animating) = res.set_animating {
+            cx.set_animating(animating);
+        }
+
+        if res.enter_key_pressed {
+            cx.release_focus();
+        } else if res.escape_key_pressed {
+            self.canceled = true;
+            cx.release_focus();
+        }
+
+        res.capture_status
+    }
+
+    fn render(&mut self, cx: RenderContext, primitives: &mut PrimitiveGroup) {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+        let style = cx.res.style_system.get::<DragValueStyle>(cx.class);
+        let bounds = Rect::from_size(cx.bounds_size);
+
+        if shared_state.editing {
+            let text_offset = shared_state.label_inner.text_offset;
+
+            let mut p = shared_state.input_inner.create_primitives(
+                &style.text_input,
+                bounds,
+                text_offset,
+                self.hovered,
+                cx.opacity,
+            );
+
+            if let Some(back_quad) = p.back_quad.take() {
+                primitives.add(back_quad);
+            }
+            if let Some(highlight_range) = p.highlight_range.take() {
+                primitives.set_z_index(1);
+                primitives.add_solid_quad(highlight_range);
+            }
+            if let Some(text) = p.text.take() {
+                primitives.set_z_index(2);
+                primitives.add_text(text);
+            }
+            if let Some(cursor) = p.cursor.take() {
+                primitives.set_z_index(3);
+                primitives.add_solid_quad(cursor);
+            }
+        } else {
+            let label_style = if self.drag_state.is_some() {
+                style.label_gesturing.as_ref().unwrap_or(&style.label)
+            } else {
+                &style.label
+            };
+
+            let label_primitives = shared_state.label_inner.render(
+                bounds,
+                label_style,
+                cx.opacity,
+                &mut cx.res.font_system,
+                &mut cx.res.text_layout_cache,
+                &cx.res.icon_font_system,
+            );
+
+            if let Some(quad_primitive) = label_primitives.bg_quad {
+                primitives.add(quad_primitive);
+            }
+
+            label_primitives.bg_overlay.add_to(primitives);
+
+            if let Some(text_primitive) = label_primitives.text {
+                primitives.set_z_index(1);
+                primitives.add_text(text_primitive);
+            }
+        }
+    }
+}
+
+/// Snap the value to the nearest multiple of `step` (if any) within the
+/// range `[min, max]`.
+fn snap_value(value: f64, step: Option<f64>, min: f64, max: f64) -> f64 {
+    match step {
+        Some(step) if step > 0.0 => ((value / step).round() * step).clamp(min, max),
+        _ => value,
+    }
+}
+
+struct SharedState {
+    label_inner: LabelInner,
+    input_inner: TextInputInner,
+    value: f64,
+    min: f64,
+    max: f64,
+    format: fn(f64, &Locale) -> String,
+    editing: bool,
+    disabled: bool,
+    tooltip_inner: TooltipInner,
+}
+
+impl SharedState {
+    fn update_label(&mut self, class: ClassID, res: &mut ResourceCtx) {
+        let text = (self.format)(self.value, &res.locale);
+
+        self.label_inner.set_text(
+            Some(text),
+            &mut res.font_system,
+            &mut res.text_layout_cache,
+            || {
+                res.style_system
+                    .get::<DragValueStyle>(class)
+                    .label
+                    .text_properties
+            },
+        );
+    }
+}
+
+/// A handle to a [`DragValueElement`], a number which changes when it is
+/// dragged horizontally (essentially a compact slider without a track).
+///
+/// Holding the fine adjustment modifier (`Shift` by default) while dragging
+/// makes smaller adjustments. Double-clicking the value turns it into an
+/// inline text input, where `Enter` (or clicking outside of the element)
+/// commits the typed in value and `Esc` reverts it.
+#[element_handle]
+#[element_handle_class]
+#[element_handle_set_rect]
+#[element_handle_set_tooltip]
+pub struct DragValue {
+    shared_state: Rc<RefCell<SharedState>>,
+}
+
+impl DragValue {
+    pub fn builder<A: Clone + 'static>() -> DragValueBuilder<A> {
+        DragValueBuilder::new()
+    }
+
+    /// Returns the size of the padded background rectangle of the label if
+    /// it were to cover the text.
+    ///
+    /// This size is automatically cached, so it should be relatively
+    /// inexpensive to call.
+    pub fn desired_size(&self, res: &mut ResourceCtx) -> Size {
+        RefCell::borrow_mut(&self.shared_state)
+            .label_inner
+            .desired_size(|| {
+                res.style_system
+                    .get::<DragValueStyle>(self.el.class())
+                    .label
+                    .padding_info()
+            })
+    }
+
+    /// Set the value. The value is clamped to the range of this element.
+    ///
+    /// Returns `true` if the value has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn set_value(&mut self, value: f64) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        let value = value.clamp(shared_state.min, shared_state.max);
+
+        if shared_state.value != value {
+            shared_state.value = value;
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn value(&self) -> f64 {
+        RefCell::borrow(&self.shared_state).value
+    }
+
+    /// Set the range the value is clamped to.
+    ///
+    /// Returns `true` if the range has changed.
+    pub fn set_range(&mut self, min: f64, max: f64) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        let max = max.max(min);
+
+        if shared_state.min != min || shared_state.max != max {
+            shared_state.min = min;
+            shared_state.max = max;
+            shared_state.value = shared_state.value.clamp(min, max);
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns `true` if the value is currently being typed in.
+    pub fn is_editing(&self) -> bool {
+        RefCell::borrow(&self.shared_state).editing
+    }
+
+    /// Set the disabled state of this element.
+    ///
+    /// A disabled value cannot be dragged or edited.
+    ///
+    /// Returns `true` if the disabled state has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn set_disabled(&mut self, disabled: bool) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if shared_state.disabled != disabled {
+            shared_state.disabled = disabled;
+            shared_state.input_inner.disabled = disabled;
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn disabled(&self) -> bool {
+        RefCell::borrow(&self.shared_state).disabled
+    }
+
+    /// Layout out the element (with the top-left corner of the bounds set to `origin`).
+    ///
+    /// Returns `true` if the layout has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn layout(&mut self, origin: Point, res: &mut ResourceCtx) -> bool {
+        let size = self.desired_size(res);
+        self.el.set_rect(Rect::new(origin, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snap_value() {
+        assert_eq!(snap_value(0.37, None, 0.0, 1.0), 0.37);
+        assert_eq!(snap_value(0.37, Some(0.0), 0.0, 1.0), 0.37);
+        assert_eq!(snap_value(0.37, Some(-0.1), 0.0, 1.0), 0.37);
+
+        assert_eq!(snap_value(121.4, Some(1.0), 20.0, 300.0), 121.0);
+        assert_eq!(snap_value(121.5, Some(1.0), 20.0, 300.0), 122.0);
+        assert_eq!(snap_value(-7.4, Some(5.0), -10.0, 10.0), -5.0);
+        assert!((snap_value(0.37, Some(0.25), 0.0, 1.0) - 0.25).abs() < 1e-9);
+
+        // The snapped value stays within the range.
+        assert_eq!(snap_value(10.0, Some(4.0), 0.0, 10.0), 10.0);
+        assert_eq!(snap_value(0.4, Some(4.0), 1.0, 10.0), 1.0);
+    }
+}
@@ -59,6 +59,26 @@ impl MenuEntry {
         }
     }
 
+    /// An option with a keyboard shortcut hint shown right-aligned in the
+    /// menu.
+    ///
+    /// The chord is formatted for the current platform. To show the
+    /// shortcut registered with an action, use
+    /// `AppContext::shortcut_for_action`.
+    pub fn option_with_shortcut(
+        text: impl Into<String>,
+        shortcut: Option<KeyChord>,
+        unique_id: usize,
+    ) -> Self {
+        Self::Option {
+            left_icon: None,
+            icon_scale: IconScale::default(),
+            left_text: text.into(),
+            right_text: shortcut.map(|chord| chord.to_string()),
+            unique_id,
+        }
+    }
+
     pub fn option_with_icon(
         text: impl Into<String>,
         icon_id: Option<impl Into<IconID>>,
@@ -372,6 +392,7 @@ impl<A: Clone + 'static> DropDownMenuBuilder<A> {
             z_index,
             position,
             scissor_rect,
+            key,
         } = self;
 
         let shared_state = Rc::new(RefCell::new(SharedState {
@@ -385,7 +406,12 @@ impl<A: Clone + 'static> DropDownMenuBuilder<A> {
             .get::<DropDownMenuStyle>(window_cx.builder_class(class));
         let cursor_icon = style.cursor_icon;
 
-        let mut entries = build_entries(entries, &style, &mut window_cx.res.font_system);
+        let mut entries = build_entries(
+            entries,
+            &style,
+            &mut window_cx.res.font_system,
+            &mut window_cx.res.text_layout_cache,
+        );
 
         let size = style.measure(&mut entries);
 
@@ -399,6 +425,7 @@ impl<A: Clone + 'static> DropDownMenuBuilder<A> {
             cursor_icon,
         })
         .builder_values(z_index, scissor_rect, class, window_cx)
+        .key(key)
         .rect(Rect::new(position, Size::zero()))
         .flags(
             ElementFlags::PAINTS
@@ -445,7 +472,12 @@ impl<A: Clone + 'static> Element<A> for DropDownMenuElement<A> {
                 if let Some(new_entries) = shared_state.new_entries.take() {
                     let style = cx.res.style_system.get(cx.class());
 
-                    self.entries = build_entries(new_entries, style, &mut cx.res.font_system);
+                    self.entries = build_entries(
+                        new_entries,
+                        style,
+                        &mut cx.res.font_system,
+                        &mut cx.res.text_layout_cache,
+                    );
 
                     self.size = style.measure(&mut self.entries);
 
@@ -613,7 +645,7 @@ impl<A: Clone + 'static> Element<A> for DropDownMenuElement<A> {
         primitives.add(
             style
                 .back_quad
-                .create_primitive(Rect::from_size(cx.bounds_size)),
+                .create_primitive_with_opacity(Rect::from_size(cx.bounds_size), cx.opacity),
         );
 
         for (i, entry) in self.entries.iter_mut().enumerate() {
@@ -632,10 +664,10 @@ impl<A: Clone + 'static> Element<A> for DropDownMenuElement<A> {
 
                     if hovered {
                         primitives.set_z_index(1);
-                        primitives.add(style.entry_bg_quad_hover.create_primitive(Rect::new(
-                            Point::new(style.outer_padding, *start_y),
-                            label_size,
-                        )));
+                        primitives.add(style.entry_bg_quad_hover.create_primitive_with_opacity(
+                            Rect::new(Point::new(style.outer_padding, *start_y), label_size),
+                            cx.opacity,
+                        ));
                     }
 
                     let left_primitives = left_label.render(
@@ -645,7 +677,10 @@ impl<A: Clone + 'static> Element<A> for DropDownMenuElement<A> {
                         } else {
                             &left_style_idle
                         },
+                        cx.opacity,
                         &mut cx.res.font_system,
+                        &mut cx.res.text_layout_cache,
+                        &cx.res.icon_font_system,
                     );
 
                     if let Some(p) = left_primitives.icon {
@@ -671,7 +706,10 @@ impl<A: Clone + 'static> Element<A> for DropDownMenuElement<A> {
                         let right_primitives = right_label.render(
                             Rect::new(Point::new(right_x, *start_y), label_size),
                             right_style,
+                            cx.opacity,
                             &mut cx.res.font_system,
+                            &mut cx.res.text_layout_cache,
+                            &cx.res.icon_font_system,
                         );
 
                         if let Some(p) = right_primitives.text {
@@ -681,7 +719,7 @@ impl<A: Clone + 'static> Element<A> for DropDownMenuElement<A> {
                 }
                 MenuEntryInner::Divider { y } => divider_primitives.push(
                     SolidQuadBuilder::new(Size::new(label_size.width, style.divider_width))
-                        .bg_color(style.divider_color)
+                        .bg_color(apply_opacity(style.divider_color, cx.opacity))
                         .position(Point::new(style.outer_padding, *y))
                         .into(),
                 ),
@@ -746,6 +784,7 @@ fn build_entries(
     entries: Vec<MenuEntry>,
     style: &DropDownMenuStyle,
     font_system: &mut FontSystem,
+    text_cache: &mut TextLayoutCache,
 ) -> Vec<MenuEntryInner> {
     let (left_style, right_style) = style.label_styles(false);
 
@@ -769,6 +808,7 @@ fn build_entries(
                     Default::default(),
                     &left_style,
                     font_system,
+                    text_cache,
                 ),
                 right_label: right_text.map(|text| {
                     LabelInner::new(
@@ -781,6 +821,7 @@ fn build_entries(
                         Default::default(),
                         &right_style,
                         font_system,
+                        text_cache,
                     )
                 }),
                 start_y: 0.0,
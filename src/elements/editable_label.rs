@@ -0,0 +1,591 @@
+use std::cell::{Ref, RefCell};
+use std::rc::Rc;
+
+use crate::derive::*;
+use crate::prelude::*;
+
+use super::label::LabelInner;
+use super::text_input::{TextInputInner, TextInputUpdateResult};
+
+/// The style of an [`EditableLabel`] element
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct EditableLabelStyle {
+    /// The style of the label when it is not being edited.
+    pub label: LabelStyle,
+    /// The style of the inline text input when the label is being edited.
+    pub text_input: TextInputStyle,
+}
+
+impl ElementStyle for EditableLabelStyle {
+    const ID: &'static str = "edlb";
+
+    fn default_dark_style() -> Self {
+        Self::default()
+    }
+
+    fn default_light_style() -> Self {
+        Self {
+            label: LabelStyle::default_light_style(),
+            text_input: TextInputStyle {
+                text_color: color::BLACK,
+                ..Default::default()
+            },
+        }
+    }
+
+    fn with_auto_disabled(&self, derivation: DisabledDerivation) -> Option<Self> {
+        let mut style = self.clone();
+        style.text_input.derive_auto_disabled(derivation);
+        Some(style)
+    }
+}
+
+#[element_builder]
+#[element_builder_class]
+#[element_builder_rect]
+#[element_builder_hidden]
+#[element_builder_disabled]
+#[element_builder_tooltip]
+pub struct EditableLabelBuilder<A: Clone + 'static> {
+    pub action: Option<Box<dyn FnMut(String) -> A>>,
+    pub text: String,
+    pub text_offset: Vector,
+    pub click_count: usize,
+    pub max_characters: usize,
+}
+
+impl<A: Clone + 'static> EditableLabelBuilder<A> {
+    pub fn new() -> Self {
+        Self {
+            action: None,
+            text: String::new(),
+            text_offset: Vector::default(),
+            click_count: 2,
+            max_characters: 256,
+            class: Default::default(),
+            z_index: Default::default(),
+            rect: Default::default(),
+            manually_hidden: Default::default(),
+            disabled: Default::default(),
+            scissor_rect: Default::default(),
+            key: Default::default(),
+            tooltip_data: Default::default(),
+        }
+    }
+
+    /// The action to send when the user commits a new text (by pressing
+    /// `Enter` or clicking outside of the text input).
+    ///
+    /// This is not sent if the text was not changed, or if the edit was
+    /// canceled with `Esc`.
+    pub fn on_committed<F: FnMut(String) -> A + 'static>(mut self, f: F) -> Self {
+        self.action = Some(Box::new(f));
+        self
+    }
+
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.text = text.into();
+        self
+    }
+
+    /// An offset that can be used mainly to correct the position of the text.
+    /// This does not effect the position of the background quad.
+    ///
+    /// By default this is set to an offset of zero.
+    pub const fn text_offset(mut self, offset: Vector) -> Self {
+        self.text_offset = offset;
+        self
+    }
+
+    /// The number of clicks needed to start editing the label.
+    ///
+    /// By default this is set to `2` (a double-click).
+    pub const fn click_count(mut self, count: usize) -> Self {
+        self.click_count = count;
+        self
+    }
+
+    /// The maximum characters that can be entered while editing.
+    ///
+    /// By default this is set to `256`.
+    pub const fn max_characters(mut self, max: usize) -> Self {
+        self.max_characters = max;
+        self
+    }
+
+    pub fn build(self, window_cx: &mut WindowContext<'_, A>) -> EditableLabel {
+        let EditableLabelBuilder {
+            action,
+            text,
+            text_offset,
+            click_count,
+            max_characters,
+            class,
+            z_index,
+            rect,
+            manually_hidden,
+            disabled,
+            scissor_rect,
+            key,
+            tooltip_data,
+        } = self;
+
+        let style = window_cx
+            .res
+            .style_system
+            .get::<EditableLabelStyle>(window_cx.builder_class(class));
+
+        let mut label_inner = LabelInner::new(
+            Some(text.clone()),
+            None,
+            text_offset,
+            Vector::default(),
+            None,
+            IconScale::default(),
+            TextIconLayout::default(),
+            &style.label,
+            &mut window_cx.res.font_system,
+            &mut window_cx.res.text_layout_cache,
+        );
+        label_inner.set_text_direction(
+            window_cx.text_direction(),
+            &style.label,
+            &mut window_cx.res.font_system,
+            &mut window_cx.res.text_layout_cache,
+        );
+
+        let shared_state = Rc::new(RefCell::new(SharedState {
+            label_inner,
+            input_inner: TextInputInner::new(
+                text,
+                String::new(),
+                false,
+                max_characters,
+                rect.size,
+                false,
+                true,
+                &style.text_input,
+                &mut window_cx.res.font_system,
+            ),
+            editing: false,
+            start_editing: false,
+            disabled,
+            tooltip_inner: TooltipInner::new(tooltip_data),
+        }));
+
+        let el = ElementBuilder::new(EditableLabelElement {
+            shared_state: Rc::clone(&shared_state),
+            action,
+            click_count,
+            canceled: false,
+            hovered: false,
+        })
+        .builder_values(z_index, scissor_rect, class, window_cx)
+        .key(key)
+        .rect(rect)
+        .hidden(manually_hidden)
+        .flags(
+            ElementFlags::PAINTS
+                | ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS
+                | ElementFlags::LISTENS_TO_POINTER_OUTSIDE_BOUNDS_WHEN_FOCUSED
+                | ElementFlags::LISTENS_TO_TEXT_COMPOSITION_WHEN_FOCUSED
+                | ElementFlags::LISTENS_TO_KEYS_WHEN_FOCUSED
+                | ElementFlags::LISTENS_TO_SIZE_CHANGE
+                | ElementFlags::LISTENS_TO_FOCUS_CHANGE,
+        )
+        .build(window_cx);
+
+        EditableLabel { el, shared_state }
+    }
+}
+
+/// A label which turns into an inline text input when it is double-clicked.
+struct EditableLabelElement<A: Clone + 'static> {
+    shared_state: Rc<RefCell<SharedState>>,
+    action: Option<Box<dyn FnMut(String) -> A>>,
+    click_count: usize,
+    canceled: bool,
+    hovered: bool,
+}
+
+impl<A: Clone + 'static> EditableLabelElement<A> {
+    fn on_label_event(
+        shared_state: &mut SharedState,
+        edit_click_count: usize,
+        event: ElementEvent,
+        cx: &mut ElementContext<'_, A>,
+    ) -> EventCaptureStatus {
+        shared_state.tooltip_inner.handle_event_with_auto_text(
+            &event,
+            shared_state.disabled,
+            shared_state.label_inner.truncated_text(),
+            cx,
+        );
+
+        match event {
+            ElementEvent::CustomStateChanged => {
+                if shared_state.start_editing {
+                    shared_state.start_editing = false;
+
+                    if !shared_state.disabled {
+                        Self::begin_editing(shared_state, cx);
+                    }
+                }
+
+                cx.request_repaint();
+            }
+            ElementEvent::Pointer(PointerEvent::ButtonJustPressed {
+                button,
+                click_count,
+                ..
+            }) => {
+                if button == PointerButton::Primary
+                    && click_count == edit_click_count
+                    && !shared_state.disabled
+                {
+                    Self::begin_editing(shared_state, cx);
+                    return EventCaptureStatus::Captured;
+                }
+            }
+            _ => {}
+        }
+
+        EventCaptureStatus::NotCaptured
+    }
+
+    fn begin_editing(shared_state: &mut SharedState, cx: &mut ElementContext<'_, A>) {
+        let text = String::from(shared_state.label_inner.text().unwrap_or_default());
+
+        shared_state
+            .input_inner
+            .set_text(text, &mut cx.res.font_system, true);
+
+        shared_state.editing = true;
+        cx.steal_temporary_focus();
+        cx.request_repaint();
+    }
+}
+
+impl<A: Clone + 'static> Element<A> for EditableLabelElement<A> {
+    fn on_event(
+        &mut self,
+        event: ElementEvent,
+        cx: &mut ElementContext<'_, A>,
+    ) -> EventCaptureStatus {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if let ElementEvent::SizeChanged = &event {
+            let bounds_size = cx.rect().size;
+            let style = cx.res.style_system.get::<EditableLabelStyle>(cx.class());
+            shared_state.input_inner.on_size_changed(
+                bounds_size,
+                &style.text_input,
+                &mut cx.res.font_system,
+            );
+        }
+
+        if !shared_state.editing {
+            return Self::on_label_event(&mut shared_state, self.click_count, event, cx);
+        }
+
+        let res = match event {
+            ElementEvent::Animation { .. } => shared_state.input_inner.on_animation(),
+            ElementEvent::CustomStateChanged => {
+                shared_state.start_editing = false;
+
+                shared_state
+                    .input_inner
+                    .on_custom_state_changed(cx.clipboard, &mut cx.res.font_system)
+            }
+            ElementEvent::Pointer(PointerEvent::Moved { position, .. }) => shared_state
+                .input_inner
+                .on_pointer_moved(position, cx.rect(), &mut cx.res.font_system),
+            ElementEvent::Pointer(PointerEvent::ButtonJustPressed {
+                position,
+                button,
+                click_count,
+                ..
+            }) => shared_state.input_inner.on_pointer_button_just_pressed(
+                position,
+                button,
+                click_count,
+                cx.rect(),
+                &mut cx.res.font_system,
+            ),
+            ElementEvent::Pointer(PointerEvent::ButtonJustReleased {
+                button, position, ..
+            }) => shared_state.input_inner.on_pointer_button_just_released(
+                position,
+                button,
+                cx.rect(),
+            ),
+            ElementEvent::Pointer(PointerEvent::PointerLeft) => {
+                shared_state.input_inner.on_pointer_left()
+            }
+            ElementEvent::Keyboard(key_event) => shared_state.input_inner.on_keyboard_event(
+                &key_event,
+                cx.clipboard,
+                &mut cx.res.font_system,
+            ),
+            ElementEvent::TextComposition(comp_event) => shared_state
+                .input_inner
+                .on_text_composition_event(&comp_event, &mut cx.res.font_system),
+            ElementEvent::Focus(has_focus) => {
+                if !has_focus {
+                    shared_state.editing = false;
+
+                    let canceled = std::mem::take(&mut self.canceled);
+                    let new_text = shared_state.input_inner.text();
+
+                    if !canceled && shared_state.label_inner.text() != Some(new_text) {
+                        let new_text = String::from(new_text);
+
+                        let SharedState { label_inner, .. } = &mut *shared_state;
+                        let style = cx.res.style_system.get::<EditableLabelStyle>(cx.class());
+                        label_inner.set_text(
+                            Some(new_text.as_str()),
+                            &mut cx.res.font_system,
+                            &mut cx.res.text_layout_cache,
+                            || style.label.text_properties,
+                        );
+
+                        if let Some(action) = self.action.as_mut() {
+                            cx.send_action((action)(new_text)).unwrap();
+                        }
+                    }
+                }
+
+                shared_state.input_inner.on_focus_changed(
+                    has_focus,
+                    cx.clipboard,
+                    &mut cx.res.font_system,
+                )
+            }
+            ElementEvent::ClickedOff => {
+                cx.release_focus();
+
+                shared_state.input_inner.on_clicked_off()
+            }
+            _ => TextInputUpdateResult::default(),
+        };
+
+        if res.needs_repaint {
+            cx.request_repaint();
+        }
+        if res.hovered {
+            self.hovered = true;
+            cx.cursor_icon = CursorIcon::Text;
+        } else {
+            self.hovered = false;
+        }
+        if res.listen_to_pointer_clicked_off {
+            cx.listen_to_pointer_clicked_off();
+        }
+        if let Some(animating) = res.set_animating {
+            cx.set_animating(animating);
+        }
+
+        if res.enter_key_pressed {
+            cx.release_focus();
+        } else if res.escape_key_pressed {
+            self.canceled = true;
+            cx.release_focus();
+        }
+
+        res.capture_status
+    }
+
+    fn render(&mut self, cx: RenderContext, primitives: &mut PrimitiveGroup) {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+        let style = cx.res.style_system.get::<EditableLabelStyle>(cx.class);
+        let bounds = Rect::from_size(cx.bounds_size);
+
+        if shared_state.editing {
+            let text_offset = shared_state.label_inner.text_offset;
+
+            let mut p = shared_state.input_inner.create_primitives(
+                &style.text_input,
+                bounds,
+                text_offset,
+                self.hovered,
+                cx.opacity,
+            );
+
+            if let Some(back_quad) = p.back_quad.take() {
+                primitives.add(back_quad);
+            }
+            if let Some(highlight_range) = p.highlight_range.take() {
+                primitives.set_z_index(1);
+                primitives.add_solid_quad(highlight_range);
+            }
+            if let Some(text) = p.text.take() {
+                primitives.set_z_index(2);
+                primitives.add_text(text);
+            }
+            if let Some(cursor) = p.cursor.take() {
+                primitives.set_z_index(3);
+                primitives.add_solid_quad(cursor);
+            }
+        } else {
+            let label_primitives = shared_state.label_inner.render(
+                bounds,
+                &style.label,
+                cx.opacity,
+                &mut cx.res.font_system,
+                &mut cx.res.text_layout_cache,
+                &cx.res.icon_font_system,
+            );
+
+            if let Some(quad_primitive) = label_primitives.bg_quad {
+                primitives.add(quad_primitive);
+            }
+
+            label_primitives.bg_overlay.add_to(primitives);
+
+            if let Some(text_primitive) = label_primitives.text {
+                primitives.set_z_index(1);
+                primitives.add_text(text_primitive);
+            }
+        }
+    }
+}
+
+struct SharedState {
+    label_inner: LabelInner,
+    input_inner: TextInputInner,
+    editing: bool,
+    start_editing: bool,
+    disabled: bool,
+    tooltip_inner: TooltipInner,
+}
+
+/// A handle to an [`EditableLabelElement`], a label which turns into an
+/// inline text input when it is double-clicked.
+///
+/// While editing, `Enter` (or clicking outside of the label) commits the new
+/// text and `Esc` reverts it.
+#[element_handle]
+#[element_handle_class]
+#[element_handle_set_rect]
+#[element_handle_set_tooltip]
+pub struct EditableLabel {
+    shared_state: Rc<RefCell<SharedState>>,
+}
+
+impl EditableLabel {
+    pub fn builder<A: Clone + 'static>() -> EditableLabelBuilder<A> {
+        EditableLabelBuilder::new()
+    }
+
+    /// Returns the size of the padded background rectangle of the label if
+    /// it were to cover the text.
+    ///
+    /// This size is automatically cached, so it should be relatively
+    /// inexpensive to call.
+    pub fn desired_size(&self, res: &mut ResourceCtx) -> Size {
+        RefCell::borrow_mut(&self.shared_state)
+            .label_inner
+            .desired_size(|| {
+                res.style_system
+                    .get::<EditableLabelStyle>(self.el.class())
+                    .label
+                    .padding_info()
+            })
+    }
+
+    /// Set the text.
+    ///
+    /// Returns `true` if the text has changed.
+    ///
+    /// Note, this has no effect on the text input if the label is currently
+    /// being edited.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently. However, this method still
+    /// involves a string comparison so you may want to call this method
+    /// sparingly.
+    pub fn set_text<T: AsRef<str> + Into<String>>(
+        &mut self,
+        text: T,
+        res: &mut ResourceCtx,
+    ) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if shared_state.label_inner.set_text(
+            Some(text),
+            &mut res.font_system,
+            &mut res.text_layout_cache,
+            || {
+                res.style_system
+                    .get::<EditableLabelStyle>(self.el.class())
+                    .label
+                    .text_properties
+            },
+        ) {
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn text<'a>(&'a self) -> Ref<'a, str> {
+        Ref::map(RefCell::borrow(&self.shared_state), |s| {
+            s.label_inner.text().unwrap_or_default()
+        })
+    }
+
+    /// Start editing the label (i.e. from a "Rename" context menu entry or
+    /// an `F2` shortcut).
+    ///
+    /// This does nothing if the element is disabled or already being edited.
+    pub fn start_editing(&mut self) {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if !shared_state.editing && !shared_state.disabled {
+            shared_state.start_editing = true;
+            self.el.notify_custom_state_change();
+        }
+    }
+
+    /// Returns `true` if the label is currently being edited.
+    pub fn is_editing(&self) -> bool {
+        RefCell::borrow(&self.shared_state).editing
+    }
+
+    /// Set the disabled state of this element.
+    ///
+    /// A disabled label cannot be edited.
+    ///
+    /// Returns `true` if the disabled state has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn set_disabled(&mut self, disabled: bool) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if shared_state.disabled != disabled {
+            shared_state.disabled = disabled;
+            shared_state.input_inner.disabled = disabled;
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn disabled(&self) -> bool {
+        RefCell::borrow(&self.shared_state).disabled
+    }
+
+    /// Layout out the element (with the top-left corner of the bounds set to `origin`).
+    ///
+    /// Returns `true` if the layout has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn layout(&mut self, origin: Point, res: &mut ResourceCtx) -> bool {
+        let size = self.desired_size(res);
+        self.el.set_rect(Rect::new(origin, size))
+    }
+}
@@ -0,0 +1,732 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use bytemuck::{Pod, Zeroable};
+use rootvg::buffer::Buffer;
+use rootvg::pipeline::{
+    CustomPipeline, CustomPipelinePrimitive, CustomPrimitive, DefaultConstantUniforms,
+};
+use rootvg::PrimitiveGroup;
+
+use crate::derive::*;
+use crate::math::{PhysicalSizeI32, ScaleFactor};
+use crate::prelude::*;
+
+/// How a texture is fitted into the bounds of an [`ExternalTexture`].
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TextureFit {
+    /// Scale the texture to fit inside the bounds while keeping its aspect
+    /// ratio, leaving empty space on two of the sides if the aspect ratios
+    /// differ.
+    #[default]
+    Contain,
+    /// Scale the texture to cover the bounds while keeping its aspect ratio,
+    /// cropping two of the sides if the aspect ratios differ.
+    Cover,
+    /// Stretch the texture to fill the bounds.
+    Stretch,
+}
+
+impl TextureFit {
+    /// Returns the rect (relative to `bounds`) in which the texture is drawn
+    /// and the region of the texture which is drawn (in normalized texture
+    /// coordinates).
+    pub fn layout(&self, bounds: Size, texture_size: Size) -> (Rect, Rect) {
+        let full_uv = Rect::new(Point::new(0.0, 0.0), Size::new(1.0, 1.0));
+
+        if texture_size.width <= 0.0 || texture_size.height <= 0.0 {
+            return (Rect::from_size(bounds), full_uv);
+        }
+
+        let scale_x = bounds.width / texture_size.width;
+        let scale_y = bounds.height / texture_size.height;
+
+        match self {
+            Self::Contain => {
+                let scale = scale_x.min(scale_y);
+                let size = Size::new(texture_size.width * scale, texture_size.height * scale);
+                let origin = Point::new(
+                    (bounds.width - size.width) * 0.5,
+                    (bounds.height - size.height) * 0.5,
+                );
+
+                (Rect::new(origin, size), full_uv)
+            }
+            Self::Cover => {
+                let scale = scale_x.max(scale_y);
+                let uv_size = Size::new(
+                    (bounds.width / (texture_size.width * scale)).min(1.0),
+                    (bounds.height / (texture_size.height * scale)).min(1.0),
+                );
+                let uv_origin =
+                    Point::new((1.0 - uv_size.width) * 0.5, (1.0 - uv_size.height) * 0.5);
+
+                (Rect::from_size(bounds), Rect::new(uv_origin, uv_size))
+            }
+            Self::Stretch => (Rect::from_size(bounds), full_uv),
+        }
+    }
+}
+
+/// The texture format of frames uploaded with
+/// [`ExternalTexture::upload_frame`].
+pub const UPLOAD_TEXTURE_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8UnormSrgb;
+
+#[element_builder]
+#[element_builder_class]
+#[element_builder_rect]
+#[element_builder_hidden]
+#[derive(Default)]
+pub struct ExternalTextureBuilder {
+    pub fit: TextureFit,
+}
+
+impl ExternalTextureBuilder {
+    /// How the texture is fitted into the bounds of the element.
+    ///
+    /// By default this is set to [`TextureFit::Contain`].
+    pub const fn fit(mut self, fit: TextureFit) -> Self {
+        self.fit = fit;
+        self
+    }
+
+    pub fn build<A: Clone + 'static>(
+        self,
+        window_cx: &mut WindowContext<'_, A>,
+    ) -> ExternalTexture {
+        let ExternalTextureBuilder {
+            fit,
+            class,
+            z_index,
+            rect,
+            manually_hidden,
+            scissor_rect,
+            key,
+        } = self;
+
+        let shared_state = Rc::new(RefCell::new(SharedState {
+            fit,
+            size: None,
+            generation: 0,
+            source: None,
+            pending_upload: None,
+            bind_group: None,
+        }));
+
+        let el = ElementBuilder::new(ExternalTextureInternal {
+            shared_state: Rc::clone(&shared_state),
+        })
+        .builder_values(z_index, scissor_rect, class, window_cx)
+        .key(key)
+        .rect(rect)
+        .hidden(manually_hidden)
+        .flags(ElementFlags::PAINTS)
+        .build(window_cx);
+
+        ExternalTexture { el, shared_state }
+    }
+}
+
+struct PendingUpload {
+    width: u32,
+    height: u32,
+    rgba: Vec<u8>,
+}
+
+enum TextureSource {
+    /// A texture created from frames uploaded with
+    /// `ExternalTexture::upload_frame`.
+    Uploaded {
+        texture: wgpu::Texture,
+        view: wgpu::TextureView,
+    },
+    /// A texture provided by the application.
+    External(wgpu::TextureView),
+}
+
+impl TextureSource {
+    fn view(&self) -> &wgpu::TextureView {
+        match self {
+            Self::Uploaded { view, .. } => view,
+            Self::External(view) => view,
+        }
+    }
+}
+
+struct SharedState {
+    fit: TextureFit,
+    /// The size of the current frame in pixels.
+    size: Option<(u32, u32)>,
+    /// Incremented every time the frame changes, so that the pipeline knows
+    /// when to prepare it again.
+    generation: u64,
+    source: Option<TextureSource>,
+    pending_upload: Option<PendingUpload>,
+    bind_group: Option<Rc<wgpu::BindGroup>>,
+}
+
+impl SharedState {
+    fn frame_changed(&mut self, size: Option<(u32, u32)>) {
+        self.size = size;
+        self.generation += 1;
+        self.bind_group = None;
+    }
+}
+
+struct ExternalTextureInternal {
+    shared_state: Rc<RefCell<SharedState>>,
+}
+
+impl<A: Clone + 'static> Element<A> for ExternalTextureInternal {
+    fn on_event(
+        &mut self,
+        event: ElementEvent,
+        cx: &mut ElementContext<'_, A>,
+    ) -> EventCaptureStatus {
+        if let ElementEvent::CustomStateChanged = event {
+            cx.request_repaint();
+        }
+
+        EventCaptureStatus::NotCaptured
+    }
+
+    fn render(&mut self, cx: RenderContext, primitives: &mut PrimitiveGroup) {
+        let (fit, size, generation) = {
+            let shared_state = RefCell::borrow(&self.shared_state);
+            (shared_state.fit, shared_state.size, shared_state.generation)
+        };
+
+        let Some((width, height)) = size else {
+            return;
+        };
+
+        let (rect, uv) = fit.layout(cx.bounds_size, Size::new(width as f32, height as f32));
+        if rect.is_empty() || uv.is_empty() {
+            return;
+        }
+
+        let pipeline_id = cx.custom_pipelines.get_id(
+            "yarrow_external_texture",
+            || ExternalTexturePipeline::new(cx.device, cx.texture_format, cx.multisample),
+            cx.vg,
+        );
+
+        primitives.add_custom_primitive(CustomPrimitive::new(
+            ExternalTexturePrimitive {
+                instance: ExternalTextureInstance {
+                    position: rect.origin.into(),
+                    size: rect.size.into(),
+                    uv_origin: uv.origin.into(),
+                    uv_size: uv.size.into(),
+                },
+                generation,
+                shared_state: Rc::clone(&self.shared_state),
+            },
+            pipeline_id,
+        ));
+    }
+}
+
+/// An element which displays a texture updated by the application, i.e. a
+/// video preview, a camera input, or the frames of another renderer.
+///
+/// Frames can either be uploaded as raw RGBA pixels with
+/// [`ExternalTexture::upload_frame`], or a texture created on the same wgpu
+/// device as the window (see `WindowContext::gpu`) can be displayed directly
+/// with [`ExternalTexture::set_texture`].
+///
+/// Nothing is drawn outside of the fitted texture, so place a quad behind
+/// this element to get letterboxing bars.
+///
+/// This requires the `custom-shaders` feature.
+#[element_handle]
+#[element_handle_class]
+#[element_handle_set_rect]
+#[element_handle_layout_aligned]
+pub struct ExternalTexture {
+    shared_state: Rc<RefCell<SharedState>>,
+}
+
+impl ExternalTexture {
+    pub fn builder() -> ExternalTextureBuilder {
+        ExternalTextureBuilder::default()
+    }
+
+    /// Upload a new frame of tightly-packed 8-bit sRGB RGBA pixels.
+    ///
+    /// The pixels are copied to the GPU the next time the window is
+    /// rendered. If this is called multiple times before that, then only the
+    /// latest frame is uploaded. The texture is reused as long as the size
+    /// of the frames stays the same.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `rgba` is not `width * height * 4`.
+    pub fn upload_frame(&mut self, width: u32, height: u32, rgba: Vec<u8>) {
+        assert_eq!(
+            rgba.len(),
+            width as usize * height as usize * 4,
+            "the frame must contain width * height tightly-packed RGBA pixels"
+        );
+
+        {
+            let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+            if let Some(TextureSource::External(_)) = &shared_state.source {
+                shared_state.source = None;
+            }
+
+            shared_state.pending_upload = Some(PendingUpload {
+                width,
+                height,
+                rgba,
+            });
+            shared_state.frame_changed(Some((width, height)));
+        }
+
+        self.el.notify_custom_state_change();
+    }
+
+    /// Display a texture created by the application.
+    ///
+    /// The texture must have been created on the same device as the window,
+    /// it must be a filterable float texture, and it must be created with
+    /// `wgpu::TextureUsages::TEXTURE_BINDING`.
+    ///
+    /// * `width` - The width of the texture in pixels.
+    /// * `height` - The height of the texture in pixels.
+    ///
+    /// If the contents of the texture are updated in place, call
+    /// [`ExternalTexture::notify_frame_updated`] to redraw it.
+    pub fn set_texture(&mut self, view: wgpu::TextureView, width: u32, height: u32) {
+        {
+            let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+            shared_state.pending_upload = None;
+            shared_state.source = Some(TextureSource::External(view));
+            shared_state.frame_changed(Some((width, height)));
+        }
+
+        self.el.notify_custom_state_change();
+    }
+
+    /// Redraw the element after the contents of the texture passed to
+    /// [`ExternalTexture::set_texture`] have been updated.
+    pub fn notify_frame_updated(&mut self) {
+        RefCell::borrow_mut(&self.shared_state).generation += 1;
+        self.el.notify_custom_state_change();
+    }
+
+    /// Remove the current frame, drawing nothing.
+    pub fn clear(&mut self) {
+        {
+            let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+            if shared_state.size.is_none() {
+                return;
+            }
+
+            shared_state.pending_upload = None;
+            shared_state.source = None;
+            shared_state.frame_changed(None);
+        }
+
+        self.el.notify_custom_state_change();
+    }
+
+    /// The size of the current frame in pixels.
+    pub fn frame_size(&self) -> Option<(u32, u32)> {
+        RefCell::borrow(&self.shared_state).size
+    }
+
+    pub fn fit(&self) -> TextureFit {
+        RefCell::borrow(&self.shared_state).fit
+    }
+
+    /// Set how the texture is fitted into the bounds of the element.
+    ///
+    /// Returns `true` if the fit has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn set_fit(&mut self, fit: TextureFit) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if shared_state.fit == fit {
+            return false;
+        }
+
+        shared_state.fit = fit;
+        self.el.notify_custom_state_change();
+        true
+    }
+}
+
+struct ExternalTexturePrimitive {
+    instance: ExternalTextureInstance,
+    generation: u64,
+    shared_state: Rc<RefCell<SharedState>>,
+}
+
+impl PartialEq for ExternalTexturePrimitive {
+    fn eq(&self, other: &Self) -> bool {
+        self.instance == other.instance
+            && self.generation == other.generation
+            && Rc::ptr_eq(&self.shared_state, &other.shared_state)
+    }
+}
+
+impl Clone for ExternalTexturePrimitive {
+    fn clone(&self) -> Self {
+        Self {
+            instance: self.instance,
+            generation: self.generation,
+            shared_state: Rc::clone(&self.shared_state),
+        }
+    }
+}
+
+impl std::fmt::Debug for ExternalTexturePrimitive {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ExternalTexturePrimitive")
+            .field("instance", &self.instance)
+            .field("generation", &self.generation)
+            .finish_non_exhaustive()
+    }
+}
+
+#[repr(C)]
+#[derive(Default, Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+struct ExternalTextureInstance {
+    position: [f32; 2],
+    size: [f32; 2],
+    uv_origin: [f32; 2],
+    uv_size: [f32; 2],
+}
+
+const INITIAL_INSTANCES: usize = 4;
+
+const SHADER: &str = r#"
+struct Globals {
+    screen_size_recip: vec2f,
+    scale_factor: f32,
+}
+
+@group(0) @binding(0) var<uniform> globals: Globals;
+@group(1) @binding(0) var frame_texture: texture_2d<f32>;
+@group(1) @binding(1) var frame_sampler: sampler;
+
+struct VertexInput {
+    @builtin(vertex_index) vertex_index: u32,
+    @location(0) pos: vec2f,
+    @location(1) size: vec2f,
+    @location(2) uv_origin: vec2f,
+    @location(3) uv_size: vec2f,
+}
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4f,
+    @location(0) uv: vec2f,
+}
+
+@vertex
+fn vs_main(input: VertexInput) -> VertexOutput {
+    var out: VertexOutput;
+
+    let corner = vec2f(f32(input.vertex_index & 1u), f32(input.vertex_index >> 1u));
+    let screen_pos = (input.pos + (corner * input.size)) * globals.scale_factor;
+
+    out.clip_position = vec4f(
+        (screen_pos.x * globals.screen_size_recip.x) - 1.0,
+        1.0 - (screen_pos.y * globals.screen_size_recip.y),
+        0.0,
+        1.0
+    );
+    out.uv = input.uv_origin + (corner * input.uv_size);
+
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4f {
+    return textureSample(frame_texture, frame_sampler, in.uv);
+}
+"#;
+
+struct ExternalTexturePipeline {
+    pipeline: wgpu::RenderPipeline,
+
+    constants_buffer: wgpu::Buffer,
+    constants_bind_group: wgpu::BindGroup,
+
+    texture_layout: wgpu::BindGroupLayout,
+    sampler: wgpu::Sampler,
+    /// The bind group of the texture of each prepared primitive.
+    texture_bind_groups: Vec<Option<Rc<wgpu::BindGroup>>>,
+
+    instance_buffer: Buffer<ExternalTextureInstance>,
+}
+
+impl ExternalTexturePipeline {
+    fn new(
+        device: &wgpu::Device,
+        format: wgpu::TextureFormat,
+        multisample: wgpu::MultisampleState,
+    ) -> Self {
+        let (constants_layout, constants_buffer, constants_bind_group) =
+            DefaultConstantUniforms::layout_buffer_and_bind_group(device);
+
+        let texture_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("yarrow external texture layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("yarrow external texture sampler"),
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("yarrow external texture pipeline layout"),
+            push_constant_ranges: &[],
+            bind_group_layouts: &[&constants_layout, &texture_layout],
+        });
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("yarrow external texture shader"),
+            source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("yarrow external texture pipeline"),
+            layout: Some(&layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: "vs_main",
+                buffers: &[wgpu::VertexBufferLayout {
+                    array_stride: std::mem::size_of::<ExternalTextureInstance>() as u64,
+                    step_mode: wgpu::VertexStepMode::Instance,
+                    attributes: &wgpu::vertex_attr_array!(
+                        // Position
+                        0 => Float32x2,
+                        // Size
+                        1 => Float32x2,
+                        // UV origin
+                        2 => Float32x2,
+                        // UV size
+                        3 => Float32x2,
+                    ),
+                }],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: "fs_main",
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleStrip,
+                ..Default::default()
+            },
+            depth_stencil: None,
+            multisample,
+            multiview: None,
+            cache: None,
+        });
+
+        let instance_buffer = Buffer::new(
+            device,
+            "yarrow external texture instance buffer",
+            INITIAL_INSTANCES,
+            wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+        );
+
+        Self {
+            pipeline,
+            constants_buffer,
+            constants_bind_group,
+            texture_layout,
+            sampler,
+            texture_bind_groups: Vec::new(),
+            instance_buffer,
+        }
+    }
+
+    /// Upload any pending frame and return the bind group of the current
+    /// texture.
+    fn prepare_texture(
+        &self,
+        shared_state: &mut SharedState,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+    ) -> Option<Rc<wgpu::BindGroup>> {
+        if let Some(upload) = shared_state.pending_upload.take() {
+            let size = wgpu::Extent3d {
+                width: upload.width,
+                height: upload.height,
+                depth_or_array_layers: 1,
+            };
+
+            let reuse_texture = match &shared_state.source {
+                Some(TextureSource::Uploaded { texture, .. }) => texture.size() == size,
+                _ => false,
+            };
+
+            if !reuse_texture {
+                let texture = device.create_texture(&wgpu::TextureDescriptor {
+                    label: Some("yarrow external texture"),
+                    size,
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: wgpu::TextureDimension::D2,
+                    format: UPLOAD_TEXTURE_FORMAT,
+                    usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+                    view_formats: &[],
+                });
+                let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+                shared_state.source = Some(TextureSource::Uploaded { texture, view });
+                shared_state.bind_group = None;
+            }
+
+            if let Some(TextureSource::Uploaded { texture, .. }) = &shared_state.source {
+                queue.write_texture(
+                    wgpu::ImageCopyTexture {
+                        texture,
+                        mip_level: 0,
+                        origin: wgpu::Origin3d::ZERO,
+                        aspect: wgpu::TextureAspect::All,
+                    },
+                    &upload.rgba,
+                    wgpu::ImageDataLayout {
+                        offset: 0,
+                        bytes_per_row: Some(upload.width * 4),
+                        rows_per_image: Some(upload.height),
+                    },
+                    size,
+                );
+            }
+        }
+
+        if shared_state.bind_group.is_none() {
+            if let Some(source) = &shared_state.source {
+                shared_state.bind_group = Some(Rc::new(device.create_bind_group(
+                    &wgpu::BindGroupDescriptor {
+                        label: Some("yarrow external texture bind group"),
+                        layout: &self.texture_layout,
+                        entries: &[
+                            wgpu::BindGroupEntry {
+                                binding: 0,
+                                resource: wgpu::BindingResource::TextureView(source.view()),
+                            },
+                            wgpu::BindGroupEntry {
+                                binding: 1,
+                                resource: wgpu::BindingResource::Sampler(&self.sampler),
+                            },
+                        ],
+                    },
+                )));
+            }
+        }
+
+        shared_state.bind_group.clone()
+    }
+}
+
+impl CustomPipeline for ExternalTexturePipeline {
+    fn prepare(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        screen_size: PhysicalSizeI32,
+        scale_factor: ScaleFactor,
+        primitives: &[CustomPipelinePrimitive],
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        DefaultConstantUniforms::prepare_buffer(
+            &self.constants_buffer,
+            screen_size,
+            scale_factor,
+            queue,
+        );
+
+        self.texture_bind_groups.clear();
+
+        let mut instances: Vec<ExternalTextureInstance> = Vec::with_capacity(primitives.len());
+        for p in primitives.iter() {
+            let primitive = p
+                .primitive
+                .downcast_ref::<ExternalTexturePrimitive>()
+                .unwrap();
+
+            let bind_group = self.prepare_texture(
+                &mut RefCell::borrow_mut(&primitive.shared_state),
+                device,
+                queue,
+            );
+            self.texture_bind_groups.push(bind_group);
+
+            let mut instance = primitive.instance;
+            instance.position[0] += p.offset.x;
+            instance.position[1] += p.offset.y;
+            instances.push(instance);
+        }
+
+        if instances.is_empty() {
+            return Ok(());
+        }
+
+        self.instance_buffer
+            .expand_to_fit_new_size(device, instances.len());
+        self.instance_buffer.write(queue, 0, &instances);
+
+        Ok(())
+    }
+
+    fn render_primitive<'pass>(
+        &'pass self,
+        primitive_index: usize,
+        render_pass: &mut wgpu::RenderPass<'pass>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let Some(Some(bind_group)) = self.texture_bind_groups.get(primitive_index) else {
+            return Ok(());
+        };
+
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, &self.constants_bind_group, &[]);
+        render_pass.set_bind_group(1, bind_group.as_ref(), &[]);
+
+        render_pass.set_vertex_buffer(
+            0,
+            self.instance_buffer
+                .slice(primitive_index..primitive_index + 1),
+        );
+        render_pass.draw(0..4, 0..1);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,906 @@
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use crate::derive::*;
+use crate::file_dialog::FileFilter;
+use crate::prelude::*;
+use crate::vg::text::{RcTextBuffer, TextPrimitive};
+
+/// The style of a [`FileBrowser`] element
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileBrowserStyle {
+    /// The properties of the text.
+    ///
+    /// By default this uses no wrapping. The line height of these
+    /// properties is the height of each row.
+    pub text_properties: TextProperties,
+
+    /// The color of the rows of files.
+    ///
+    /// By default this is set to `color::WHITE`.
+    pub file_color: RGBA8,
+    /// The color of the rows of folders.
+    ///
+    /// By default this is set to `RGBA8::new(140, 190, 255, 255)`.
+    pub folder_color: RGBA8,
+    /// The color of the path of the current directory in the header.
+    ///
+    /// By default this is set to `RGBA8::new(170, 170, 170, 255)`.
+    pub header_color: RGBA8,
+    /// The color of the message shown when the directory could not be read.
+    ///
+    /// By default this is set to `RGBA8::new(255, 110, 100, 255)`.
+    pub error_color: RGBA8,
+
+    /// The style of the background quad.
+    ///
+    /// By default this is set to `QuadStyle::TRANSPARENT`.
+    pub back_quad: QuadStyle,
+    /// The style of the background quad of the header showing the current
+    /// directory.
+    ///
+    /// By default this is set to a semi-transparent gray.
+    pub header_quad: QuadStyle,
+    /// The style of the quad behind the row under the pointer.
+    ///
+    /// By default this is set to a semi-transparent white.
+    pub row_hover_quad: QuadStyle,
+    /// The style of the quad behind the selected row.
+    ///
+    /// By default this is set to a semi-transparent accent color.
+    pub row_selected_quad: QuadStyle,
+
+    /// The style of the scroll bar slider.
+    ///
+    /// By default this is set to a semi-transparent gray.
+    pub slider_quad: QuadStyle,
+    /// The width of the scroll bar slider.
+    ///
+    /// By default this is set to `8.0`.
+    pub slider_width: f32,
+
+    /// The horizontal padding of the text in each row.
+    ///
+    /// By default this is set to `6.0`.
+    pub row_text_padding: f32,
+
+    /// The padding between the contents and the bounding rectangle.
+    ///
+    /// By default this has all values set to `4.0`.
+    pub padding: Padding,
+}
+
+impl Default for FileBrowserStyle {
+    fn default() -> Self {
+        Self {
+            text_properties: TextProperties {
+                wrap: Wrap::None,
+                ..Default::default()
+            },
+            file_color: color::WHITE,
+            folder_color: RGBA8::new(140, 190, 255, 255),
+            header_color: RGBA8::new(170, 170, 170, 255),
+            error_color: RGBA8::new(255, 110, 100, 255),
+            back_quad: QuadStyle::TRANSPARENT,
+            header_quad: QuadStyle {
+                bg: Background::Solid(RGBA8::new(255, 255, 255, 15)),
+                border: BorderStyle::default(),
+                flags: QuadFlags::SNAP_ALL_TO_NEAREST_PIXEL,
+            },
+            row_hover_quad: QuadStyle {
+                bg: Background::Solid(RGBA8::new(255, 255, 255, 20)),
+                border: BorderStyle {
+                    radius: 2.0.into(),
+                    ..Default::default()
+                },
+                flags: QuadFlags::SNAP_ALL_TO_NEAREST_PIXEL,
+            },
+            row_selected_quad: QuadStyle {
+                bg: Background::Solid(RGBA8::new(50, 110, 200, 140)),
+                border: BorderStyle {
+                    radius: 2.0.into(),
+                    ..Default::default()
+                },
+                flags: QuadFlags::SNAP_ALL_TO_NEAREST_PIXEL,
+            },
+            slider_quad: QuadStyle {
+                bg: Background::Solid(RGBA8::new(150, 150, 150, 100)),
+                border: BorderStyle {
+                    radius: 4.0.into(),
+                    ..Default::default()
+                },
+                flags: QuadFlags::SNAP_ALL_TO_NEAREST_PIXEL,
+            },
+            slider_width: 8.0,
+            row_text_padding: 6.0,
+            padding: Padding::new(4.0, 4.0, 4.0, 4.0),
+        }
+    }
+}
+
+impl ElementStyle for FileBrowserStyle {
+    const ID: &'static str = "filebrowser";
+
+    fn default_dark_style() -> Self {
+        Self::default()
+    }
+
+    fn default_light_style() -> Self {
+        Self {
+            file_color: color::BLACK,
+            folder_color: RGBA8::new(20, 90, 190, 255),
+            header_color: RGBA8::new(80, 80, 80, 255),
+            error_color: RGBA8::new(200, 30, 20, 255),
+            header_quad: QuadStyle {
+                bg: Background::Solid(RGBA8::new(0, 0, 0, 15)),
+                border: BorderStyle::default(),
+                flags: QuadFlags::SNAP_ALL_TO_NEAREST_PIXEL,
+            },
+            row_hover_quad: QuadStyle {
+                bg: Background::Solid(RGBA8::new(0, 0, 0, 20)),
+                border: BorderStyle {
+                    radius: 2.0.into(),
+                    ..Default::default()
+                },
+                flags: QuadFlags::SNAP_ALL_TO_NEAREST_PIXEL,
+            },
+            row_selected_quad: QuadStyle {
+                bg: Background::Solid(RGBA8::new(50, 120, 220, 90)),
+                border: BorderStyle {
+                    radius: 2.0.into(),
+                    ..Default::default()
+                },
+                flags: QuadFlags::SNAP_ALL_TO_NEAREST_PIXEL,
+            },
+            ..Default::default()
+        }
+    }
+}
+
+/// A single entry in the current directory of a [`FileBrowser`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileBrowserEntry {
+    pub name: String,
+    pub path: PathBuf,
+    pub is_dir: bool,
+}
+
+#[element_builder]
+#[element_builder_class]
+#[element_builder_rect]
+#[element_builder_hidden]
+pub struct FileBrowserBuilder<A: Clone + 'static> {
+    pub activated_action: Option<Box<dyn FnMut(PathBuf) -> A>>,
+    pub directory_changed_action: Option<Box<dyn FnMut(PathBuf) -> A>>,
+    pub directory: Option<PathBuf>,
+    pub filters: Vec<FileFilter>,
+    pub show_hidden: bool,
+    pub folders_only: bool,
+    pub points_per_line: Option<f32>,
+}
+
+impl<A: Clone + 'static> FileBrowserBuilder<A> {
+    pub fn new() -> Self {
+        Self {
+            activated_action: None,
+            directory_changed_action: None,
+            directory: None,
+            filters: Vec::new(),
+            show_hidden: false,
+            folders_only: false,
+            points_per_line: None,
+            class: Default::default(),
+            z_index: Default::default(),
+            rect: Default::default(),
+            manually_hidden: Default::default(),
+            scissor_rect: Default::default(),
+            key: Default::default(),
+        }
+    }
+
+    /// The action to send when a file is double-clicked (or a folder if
+    /// `folders_only` is set to `true`). Double-clicking a folder otherwise
+    /// navigates into it.
+    pub fn on_activated<F: FnMut(PathBuf) -> A + 'static>(mut self, f: F) -> Self {
+        self.activated_action = Some(Box::new(f));
+        self
+    }
+
+    /// The action to send when the user navigates to a different directory.
+    pub fn on_directory_changed<F: FnMut(PathBuf) -> A + 'static>(mut self, f: F) -> Self {
+        self.directory_changed_action = Some(Box::new(f));
+        self
+    }
+
+    /// The directory to show initially.
+    ///
+    /// By default this is set to `None` (the current working directory).
+    pub fn directory(mut self, directory: impl Into<PathBuf>) -> Self {
+        self.directory = Some(directory.into());
+        self
+    }
+
+    /// Only show files which match one of these filters. If this is empty,
+    /// then all files are shown. Folders are always shown.
+    ///
+    /// By default this is empty.
+    pub fn filters(mut self, filters: Vec<FileFilter>) -> Self {
+        self.filters = filters;
+        self
+    }
+
+    /// Add a filter (see [`FileBrowserBuilder::filters`]).
+    pub fn filter(mut self, name: impl Into<String>, extensions: &[&str]) -> Self {
+        self.filters.push(FileFilter::new(name, extensions));
+        self
+    }
+
+    /// Whether or not to show files and folders whose name starts with a
+    /// `.`.
+    ///
+    /// By default this is set to `false`.
+    pub const fn show_hidden(mut self, show: bool) -> Self {
+        self.show_hidden = show;
+        self
+    }
+
+    /// If `true`, then only folders are shown, and double-clicking one sends
+    /// the activated action instead of navigating into it (use the parent
+    /// row to navigate instead).
+    ///
+    /// By default this is set to `false`.
+    pub const fn folders_only(mut self, folders_only: bool) -> Self {
+        self.folders_only = folders_only;
+        self
+    }
+
+    /// How many points to scroll per line of the scroll wheel.
+    ///
+    /// By default this is set to three times the line height.
+    pub const fn points_per_line(mut self, points_per_line: f32) -> Self {
+        self.points_per_line = Some(points_per_line);
+        self
+    }
+
+    pub fn build(self, window_cx: &mut WindowContext<'_, A>) -> FileBrowser {
+        let FileBrowserBuilder {
+            activated_action,
+            directory_changed_action,
+            directory,
+            filters,
+            show_hidden,
+            folders_only,
+            points_per_line,
+            class,
+            z_index,
+            rect,
+            manually_hidden,
+            scissor_rect,
+            key,
+        } = self;
+
+        let directory = directory
+            .or_else(|| std::env::current_dir().ok())
+            .unwrap_or_else(|| PathBuf::from("/"));
+
+        let mut shared_state = SharedState {
+            directory,
+            entries: Vec::new(),
+            error: None,
+            filters,
+            show_hidden,
+            folders_only,
+            selected: None,
+            scroll_offset: 0.0,
+            generation: 0,
+        };
+        shared_state.refresh();
+
+        let shared_state = Rc::new(RefCell::new(shared_state));
+
+        let el = ElementBuilder::new(FileBrowserElement {
+            shared_state: Rc::clone(&shared_state),
+            activated_action,
+            directory_changed_action,
+            points_per_line,
+            hovered_row: None,
+            header: None,
+            rows: Vec::new(),
+        })
+        .builder_values(z_index, scissor_rect, class, window_cx)
+        .key(key)
+        .rect(rect)
+        .hidden(manually_hidden)
+        .flags(
+            ElementFlags::PAINTS
+                | ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS
+                | ElementFlags::LISTENS_TO_SIZE_CHANGE,
+        )
+        .build(window_cx);
+
+        FileBrowser { el, shared_state }
+    }
+}
+
+struct SharedState {
+    directory: PathBuf,
+    entries: Vec<FileBrowserEntry>,
+    /// The error message if the directory could not be read.
+    error: Option<String>,
+    filters: Vec<FileFilter>,
+    show_hidden: bool,
+    folders_only: bool,
+    /// The index of the selected row.
+    selected: Option<usize>,
+    scroll_offset: f32,
+    /// Incremented every time the entries change so that the cached text
+    /// buffers are updated.
+    generation: u64,
+}
+
+impl SharedState {
+    /// The number of rows, including the row for the parent directory.
+    fn num_rows(&self) -> usize {
+        self.entries.len() + usize::from(self.has_parent_row())
+    }
+
+    fn has_parent_row(&self) -> bool {
+        self.directory.parent().is_some()
+    }
+
+    /// The entry shown in the given row, or `None` for the parent directory
+    /// row.
+    fn entry(&self, row: usize) -> Option<&FileBrowserEntry> {
+        if self.has_parent_row() {
+            row.checked_sub(1).and_then(|i| self.entries.get(i))
+        } else {
+            self.entries.get(row)
+        }
+    }
+
+    /// Re-read the entries of the current directory.
+    fn refresh(&mut self) {
+        self.entries.clear();
+        self.error = None;
+        self.selected = None;
+        self.generation += 1;
+
+        let read_dir = match std::fs::read_dir(&self.directory) {
+            Ok(read_dir) => read_dir,
+            Err(e) => {
+                self.error = Some(e.to_string());
+                return;
+            }
+        };
+
+        for entry in read_dir.flatten() {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if !self.show_hidden && name.starts_with('.') {
+                continue;
+            }
+
+            let path = entry.path();
+            // Follow symlinks so that linked folders can be navigated into.
+            let is_dir = path.is_dir();
+
+            if is_dir {
+                self.entries.push(FileBrowserEntry { name, path, is_dir });
+            } else if !self.folders_only
+                && (self.filters.is_empty() || self.filters.iter().any(|f| f.matches(&path)))
+            {
+                self.entries.push(FileBrowserEntry { name, path, is_dir });
+            }
+        }
+
+        // Folders first, then sorted by name ignoring case.
+        self.entries.sort_by(|a, b| {
+            b.is_dir
+                .cmp(&a.is_dir)
+                .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+        });
+    }
+
+    fn set_directory(&mut self, directory: PathBuf) {
+        self.directory = directory;
+        self.scroll_offset = 0.0;
+        self.refresh();
+    }
+
+    fn layout(&self, bounds_size: Size, style: &FileBrowserStyle) -> ViewLayout {
+        let line_height = style.text_properties.metrics.line_height;
+
+        let header_rect = Rect::new(
+            Point::zero(),
+            Size::new(
+                bounds_size.width,
+                line_height + style.padding.top + style.padding.bottom,
+            ),
+        );
+
+        let bounds_content_height =
+            (bounds_size.height - header_rect.height() - style.padding.top - style.padding.bottom)
+                .max(0.0);
+        let num_rows = if self.error.is_some() {
+            1
+        } else {
+            self.num_rows()
+        };
+        let content_height = num_rows as f32 * line_height;
+        let max_scroll_offset = (content_height - bounds_content_height).max(0.0);
+
+        let show_slider = max_scroll_offset > 0.0 && bounds_content_height > 0.0;
+        let slider_width = if show_slider { style.slider_width } else { 0.0 };
+
+        let content_rect = Rect::new(
+            Point::new(style.padding.left, header_rect.max_y() + style.padding.top),
+            Size::new(
+                (bounds_size.width - style.padding.left - style.padding.right - slider_width)
+                    .max(0.0),
+                bounds_content_height,
+            ),
+        );
+
+        let slider_rect = if show_slider {
+            let ratio = bounds_content_height / content_height;
+
+            Some(Rect::new(
+                Point::new(
+                    bounds_size.width - style.slider_width,
+                    content_rect.min_y()
+                        + (self.scroll_offset.clamp(0.0, max_scroll_offset) * ratio),
+                ),
+                Size::new(style.slider_width, bounds_content_height * ratio),
+            ))
+        } else {
+            None
+        };
+
+        ViewLayout {
+            line_height,
+            header_rect,
+            content_rect,
+            max_scroll_offset,
+            slider_rect,
+        }
+    }
+
+    /// The row at the given position relative to the element.
+    fn row_at(&self, pos: Point, layout: &ViewLayout) -> Option<usize> {
+        if self.error.is_some() || !layout.content_rect.contains(pos) || layout.line_height <= 0.0 {
+            return None;
+        }
+
+        let row = ((pos.y - layout.content_rect.min_y() + self.scroll_offset) / layout.line_height)
+            .floor() as usize;
+
+        (row < self.num_rows()).then_some(row)
+    }
+}
+
+/// The layout of the header, the scroll bar, and the visible rows.
+struct ViewLayout {
+    line_height: f32,
+    header_rect: Rect,
+    content_rect: Rect,
+    max_scroll_offset: f32,
+    slider_rect: Option<Rect>,
+}
+
+/// A buffer for a single visible row.
+struct Row {
+    /// The row index and generation this buffer is currently displaying.
+    key: Option<(usize, u64)>,
+    buffer: RcTextBuffer,
+}
+
+struct FileBrowserElement<A: Clone + 'static> {
+    shared_state: Rc<RefCell<SharedState>>,
+    activated_action: Option<Box<dyn FnMut(PathBuf) -> A>>,
+    directory_changed_action: Option<Box<dyn FnMut(PathBuf) -> A>>,
+    points_per_line: Option<f32>,
+    hovered_row: Option<usize>,
+    /// The buffer of the header and the generation it is displaying.
+    header: Option<(u64, RcTextBuffer)>,
+    /// The buffers of the visible rows. Only the rows that are visible are
+    /// ever shaped.
+    rows: Vec<Row>,
+}
+
+impl<A: Clone + 'static> Element<A> for FileBrowserElement<A> {
+    fn on_event(
+        &mut self,
+        event: ElementEvent,
+        cx: &mut ElementContext<'_, A>,
+    ) -> EventCaptureStatus {
+        match event {
+            ElementEvent::CustomStateChanged | ElementEvent::SizeChanged => {
+                cx.request_repaint();
+            }
+            ElementEvent::StyleChanged => {
+                // Force all of the text to be rebuilt with the new style.
+                self.header = None;
+                self.rows.clear();
+                cx.request_repaint();
+            }
+            ElementEvent::Pointer(PointerEvent::ScrollWheel {
+                position,
+                delta_type,
+                ..
+            }) => {
+                if !cx.rect().contains(position) {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                let style = cx.res.style_system.get::<FileBrowserStyle>(cx.class());
+                let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+                let layout = shared_state.layout(cx.rect().size, style);
+
+                let points_per_line = self.points_per_line.unwrap_or(layout.line_height * 3.0);
+                let delta = delta_type.points(points_per_line, layout.content_rect.height());
+
+                let prev_offset = shared_state.scroll_offset;
+                shared_state.scroll_offset =
+                    (prev_offset + delta.y).clamp(0.0, layout.max_scroll_offset);
+
+                if shared_state.scroll_offset != prev_offset {
+                    cx.request_repaint();
+                }
+
+                return EventCaptureStatus::Captured;
+            }
+            ElementEvent::Pointer(PointerEvent::Moved { position, .. }) => {
+                let style = cx.res.style_system.get::<FileBrowserStyle>(cx.class());
+                let shared_state = RefCell::borrow(&self.shared_state);
+                let layout = shared_state.layout(cx.rect().size, style);
+
+                let hovered_row =
+                    shared_state.row_at(position - cx.rect().origin.to_vector(), &layout);
+                if self.hovered_row != hovered_row {
+                    self.hovered_row = hovered_row;
+                    cx.request_repaint();
+                }
+            }
+            ElementEvent::Pointer(PointerEvent::PointerLeft) => {
+                if self.hovered_row.take().is_some() {
+                    cx.request_repaint();
+                }
+            }
+            ElementEvent::Pointer(PointerEvent::ButtonJustPressed {
+                position,
+                button,
+                click_count,
+                ..
+            }) => {
+                if button != PointerButton::Primary {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                let style = cx.res.style_system.get::<FileBrowserStyle>(cx.class());
+                let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+                let layout = shared_state.layout(cx.rect().size, style);
+
+                let Some(row) =
+                    shared_state.row_at(position - cx.rect().origin.to_vector(), &layout)
+                else {
+                    return EventCaptureStatus::NotCaptured;
+                };
+
+                shared_state.selected = Some(row);
+                cx.request_repaint();
+
+                if click_count != 2 {
+                    return EventCaptureStatus::Captured;
+                }
+
+                let new_directory = match shared_state.entry(row) {
+                    None => shared_state.directory.parent().map(Path::to_path_buf),
+                    Some(entry) if entry.is_dir && !shared_state.folders_only => {
+                        Some(entry.path.clone())
+                    }
+                    Some(entry) => {
+                        let path = entry.path.clone();
+                        if let Some(action) = self.activated_action.as_mut() {
+                            cx.send_action((action)(path)).unwrap();
+                        }
+                        None
+                    }
+                };
+
+                if let Some(directory) = new_directory {
+                    shared_state.set_directory(directory.clone());
+                    self.hovered_row = None;
+
+                    if let Some(action) = self.directory_changed_action.as_mut() {
+                        cx.send_action((action)(directory)).unwrap();
+                    }
+                }
+
+                return EventCaptureStatus::Captured;
+            }
+            _ => {}
+        }
+
+        EventCaptureStatus::NotCaptured
+    }
+
+    fn render(&mut self, cx: RenderContext, primitives: &mut PrimitiveGroup) {
+        let style: &FileBrowserStyle = cx.res.style_system.get(cx.class);
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        let bounds = Rect::from_size(cx.bounds_size);
+
+        if !style.back_quad.is_transparent() {
+            primitives.add(
+                style
+                    .back_quad
+                    .create_primitive_with_opacity(bounds, cx.opacity),
+            );
+        }
+
+        let layout = shared_state.layout(cx.bounds_size, style);
+        shared_state.scroll_offset = shared_state
+            .scroll_offset
+            .clamp(0.0, layout.max_scroll_offset);
+
+        if layout.line_height <= 0.0 {
+            return;
+        }
+
+        // Header
+        if !style.header_quad.is_transparent() {
+            primitives.add(
+                style
+                    .header_quad
+                    .create_primitive_with_opacity(layout.header_rect, cx.opacity),
+            );
+        }
+
+        let header_buffer = match &mut self.header {
+            Some((generation, buffer)) => {
+                if *generation != shared_state.generation {
+                    *generation = shared_state.generation;
+                    buffer.set_text(
+                        &shared_state.directory.to_string_lossy(),
+                        &mut cx.res.font_system,
+                    );
+                }
+                buffer.clone()
+            }
+            None => {
+                let buffer = RcTextBuffer::new(
+                    &shared_state.directory.to_string_lossy(),
+                    style.text_properties,
+                    None,
+                    None,
+                    false,
+                    &mut cx.res.font_system,
+                );
+                self.header = Some((shared_state.generation, buffer.clone()));
+                buffer
+            }
+        };
+
+        let text_padding = style.row_text_padding;
+        let row_width = layout.content_rect.width();
+        let text_clip_rect = Rect::new(
+            Point::new(0.0, -1.0),
+            Size::new(
+                (row_width - (text_padding * 2.0)).max(0.0),
+                layout.line_height + 2.0,
+            ),
+        );
+
+        primitives.set_z_index(2);
+        primitives.add_text(TextPrimitive::new(
+            header_buffer,
+            Point::new(
+                style.padding.left + text_padding,
+                layout.header_rect.min_y() + style.padding.top,
+            ),
+            apply_opacity(style.header_color, cx.opacity),
+            Some(text_clip_rect),
+        ));
+
+        if layout.content_rect.height() <= 0.0 {
+            return;
+        }
+
+        let row_text = |row: usize| -> (String, RGBA8) {
+            if let Some(error) = &shared_state.error {
+                return (error.clone(), style.error_color);
+            }
+
+            match shared_state.entry(row) {
+                None => (String::from(".."), style.folder_color),
+                Some(entry) if entry.is_dir => (
+                    format!("{}{}", entry.name, std::path::MAIN_SEPARATOR),
+                    style.folder_color,
+                ),
+                Some(entry) => (entry.name.clone(), style.file_color),
+            }
+        };
+
+        let num_rows = if shared_state.error.is_some() {
+            1
+        } else {
+            shared_state.num_rows()
+        };
+
+        let first_visible = (shared_state.scroll_offset / layout.line_height).floor() as usize;
+        let num_visible_rows =
+            (layout.content_rect.height() / layout.line_height).ceil() as usize + 1;
+
+        while self.rows.len() < num_visible_rows {
+            self.rows.push(Row {
+                key: None,
+                buffer: RcTextBuffer::new(
+                    "",
+                    style.text_properties,
+                    None,
+                    None,
+                    false,
+                    &mut cx.res.font_system,
+                ),
+            });
+        }
+        self.rows.truncate(num_visible_rows);
+
+        let row_y = |row: usize| {
+            layout.content_rect.min_y() + (row as f32 * layout.line_height)
+                - shared_state.scroll_offset
+        };
+        let row_rect = |row: usize| {
+            Rect::new(
+                Point::new(layout.content_rect.min_x(), row_y(row)),
+                Size::new(row_width, layout.line_height),
+            )
+        };
+
+        primitives.set_z_index(0);
+        if let Some(selected) = shared_state.selected.filter(|r| *r < num_rows) {
+            primitives.add(
+                style
+                    .row_selected_quad
+                    .create_primitive_with_opacity(row_rect(selected), cx.opacity),
+            );
+        }
+
+        primitives.set_z_index(1);
+        if let Some(hovered) = self.hovered_row.filter(|r| *r < num_rows) {
+            if shared_state.selected != Some(hovered) {
+                primitives.add(
+                    style
+                        .row_hover_quad
+                        .create_primitive_with_opacity(row_rect(hovered), cx.opacity),
+                );
+            }
+        }
+
+        primitives.set_z_index(2);
+
+        for (i, row) in self.rows.iter_mut().enumerate() {
+            let row_i = first_visible + i;
+            if row_i >= num_rows {
+                break;
+            }
+
+            let (text, color) = (row_text)(row_i);
+
+            // Only reshape the row if it now displays a different entry.
+            let key = (row_i, shared_state.generation);
+            if row.key != Some(key) {
+                row.key = Some(key);
+                row.buffer.set_text(&text, &mut cx.res.font_system);
+            }
+
+            primitives.add_text(TextPrimitive::new(
+                row.buffer.clone(),
+                Point::new(layout.content_rect.min_x() + text_padding, row_y(row_i)),
+                apply_opacity(color, cx.opacity),
+                Some(text_clip_rect),
+            ));
+        }
+
+        if let Some(slider_rect) = layout.slider_rect {
+            primitives.set_z_index(3);
+            primitives.add(
+                style
+                    .slider_quad
+                    .create_primitive_with_opacity(slider_rect, cx.opacity),
+            );
+        }
+    }
+}
+
+/// A handle to a [`FileBrowserElement`], a list of the files and folders in
+/// a directory which can be navigated with the pointer.
+///
+/// This is a fallback for native file dialogs (see
+/// `AppContext::show_file_dialog`) which is drawn entirely by Yarrow, i.e.
+/// for embedding a sample browser in a view or for plugin hosts where
+/// native dialogs are not available.
+///
+/// Double-clicking a folder navigates into it, double-clicking the `..` row
+/// navigates to the parent directory, and double-clicking a file sends the
+/// action set with `FileBrowserBuilder::on_activated`.
+#[element_handle]
+#[element_handle_class]
+#[element_handle_set_rect]
+#[element_handle_layout_aligned]
+pub struct FileBrowser {
+    shared_state: Rc<RefCell<SharedState>>,
+}
+
+impl FileBrowser {
+    pub fn builder<A: Clone + 'static>() -> FileBrowserBuilder<A> {
+        FileBrowserBuilder::new()
+    }
+
+    /// The directory which is currently shown.
+    pub fn directory(&self) -> PathBuf {
+        RefCell::borrow(&self.shared_state).directory.clone()
+    }
+
+    /// Show a different directory.
+    pub fn set_directory(&mut self, directory: impl Into<PathBuf>) {
+        RefCell::borrow_mut(&self.shared_state).set_directory(directory.into());
+        self.el.notify_custom_state_change();
+    }
+
+    /// Re-read the contents of the current directory (i.e. after a file was
+    /// saved into it).
+    pub fn refresh(&mut self) {
+        RefCell::borrow_mut(&self.shared_state).refresh();
+        self.el.notify_custom_state_change();
+    }
+
+    /// The entries of the current directory (not including the `..` row).
+    pub fn entries(&self) -> Vec<FileBrowserEntry> {
+        RefCell::borrow(&self.shared_state).entries.clone()
+    }
+
+    /// The entry which is currently selected, or `None` if no entry or the
+    /// `..` row is selected.
+    pub fn selected(&self) -> Option<FileBrowserEntry> {
+        let shared_state = RefCell::borrow(&self.shared_state);
+        shared_state
+            .selected
+            .and_then(|row| shared_state.entry(row))
+            .cloned()
+    }
+
+    /// The error message if the current directory could not be read.
+    pub fn error(&self) -> Option<String> {
+        RefCell::borrow(&self.shared_state).error.clone()
+    }
+
+    /// Set the filters of which files to show (see
+    /// [`FileBrowserBuilder::filters`]).
+    pub fn set_filters(&mut self, filters: Vec<FileFilter>) {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if shared_state.filters != filters {
+            shared_state.filters = filters;
+            shared_state.refresh();
+            self.el.notify_custom_state_change();
+        }
+    }
+
+    /// Set whether or not to show files and folders whose name starts with
+    /// a `.`.
+    ///
+    /// Returns `true` if the value has changed.
+    pub fn set_show_hidden(&mut self, show: bool) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if shared_state.show_hidden != show {
+            shared_state.show_hidden = show;
+            shared_state.refresh();
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+}
@@ -0,0 +1,594 @@
+use derive_where::derive_where;
+use std::cell::{Ref, RefCell};
+use std::rc::Rc;
+
+use crate::derive::*;
+use crate::prelude::*;
+use crate::vg::quad::QuadPrimitive;
+
+use super::label::{LabelInner, LabelPaddingInfo, LabelStyle};
+
+/// When to draw the underline of a [`Hyperlink`].
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum UnderlineMode {
+    /// Never draw the underline.
+    Never,
+    /// Only draw the underline while the pointer hovers over the link.
+    #[default]
+    OnHover,
+    /// Always draw the underline.
+    Always,
+}
+
+/// The style of a [`Hyperlink`] element
+#[derive(Debug, Clone, PartialEq)]
+pub struct HyperlinkStyle {
+    /// The properties of the text.
+    pub text_properties: TextProperties,
+
+    /// The color of the text
+    ///
+    /// By default this is set to `RGBA8::new(110, 170, 255, 255)`.
+    pub text_color: RGBA8,
+    /// The color of the text when the pointer hovers over the link.
+    ///
+    /// If this is `None`, then `text_color` will be used.
+    ///
+    /// By default this is set to `Some(RGBA8::new(150, 195, 255, 255))`.
+    pub text_color_hover: Option<RGBA8>,
+    /// The color of the text when the link is disabled.
+    ///
+    /// If this is `None`, then `text_color` will be used.
+    ///
+    /// By default this is set to `Some(RGBA8::new(110, 170, 255, 100))`.
+    pub text_color_disabled: Option<RGBA8>,
+
+    /// When to draw the underline.
+    ///
+    /// By default this is set to `UnderlineMode::OnHover`.
+    pub underline: UnderlineMode,
+    /// The thickness of the underline in points.
+    ///
+    /// By default this is set to `1.0`.
+    pub underline_thickness: f32,
+    /// The distance between the bottom of the line of text and the bottom of
+    /// the underline in points.
+    ///
+    /// By default this is set to `2.0`.
+    pub underline_offset: f32,
+
+    /// The padding around the text.
+    ///
+    /// By default this has all values set to `0.0`.
+    pub text_padding: Padding,
+
+    /// The vertical alignment.
+    ///
+    /// By default this is set to `Align::Center`.
+    pub vertical_align: crate::layout::Align,
+
+    /// The cursor icon to show when the user hovers over the link.
+    ///
+    /// If this is `None`, then the cursor icon will not be changed.
+    ///
+    /// By default this is set to `Some(CursorIcon::Pointer)`.
+    pub cursor_icon: Option<CursorIcon>,
+}
+
+impl HyperlinkStyle {
+    pub fn padding_info(&self) -> LabelPaddingInfo {
+        LabelPaddingInfo {
+            default_icon_size: 0.0,
+            text_padding: self.text_padding,
+            icon_padding: Padding::default(),
+            text_icon_spacing: 0.0,
+        }
+    }
+
+    pub fn label_style(&self, hovered: bool, disabled: bool) -> LabelStyle {
+        let text_color = if disabled {
+            self.text_color_disabled.unwrap_or(self.text_color)
+        } else if hovered {
+            self.text_color_hover.unwrap_or(self.text_color)
+        } else {
+            self.text_color
+        };
+
+        LabelStyle {
+            text_properties: self.text_properties,
+            text_color,
+            text_padding: self.text_padding,
+            vertical_align: self.vertical_align,
+            ..Default::default()
+        }
+    }
+
+    /// Create the underline primitive for the given text rectangle if it
+    /// should be drawn in the given state.
+    pub fn underline_primitive(
+        &self,
+        text_rect: Rect,
+        color: RGBA8,
+        hovered: bool,
+        opacity: f32,
+    ) -> Option<QuadPrimitive> {
+        let show = match self.underline {
+            UnderlineMode::Never => false,
+            UnderlineMode::OnHover => hovered,
+            UnderlineMode::Always => true,
+        };
+
+        if !show || self.underline_thickness <= 0.0 || text_rect.width() <= 0.0 {
+            return None;
+        }
+
+        let rect = Rect::new(
+            Point::new(
+                text_rect.min_x(),
+                text_rect.max_y() - self.underline_offset - self.underline_thickness,
+            ),
+            Size::new(text_rect.width(), self.underline_thickness),
+        );
+
+        Some(
+            QuadStyle {
+                bg: Background::Solid(color),
+                border: BorderStyle::default(),
+                flags: QuadFlags::SNAP_ALL_TO_NEAREST_PIXEL,
+            }
+            .create_primitive_with_opacity(rect, opacity),
+        )
+    }
+}
+
+impl Default for HyperlinkStyle {
+    fn default() -> Self {
+        Self {
+            text_properties: Default::default(),
+            text_color: RGBA8::new(110, 170, 255, 255),
+            text_color_hover: Some(RGBA8::new(150, 195, 255, 255)),
+            text_color_disabled: Some(RGBA8::new(110, 170, 255, 100)),
+            underline: UnderlineMode::OnHover,
+            underline_thickness: 1.0,
+            underline_offset: 2.0,
+            text_padding: Padding::default(),
+            vertical_align: crate::layout::Align::Center,
+            cursor_icon: Some(CursorIcon::Pointer),
+        }
+    }
+}
+
+impl ElementStyle for HyperlinkStyle {
+    const ID: &'static str = "hlnk";
+
+    fn default_dark_style() -> Self {
+        Self::default()
+    }
+
+    fn default_light_style() -> Self {
+        Self {
+            text_color: RGBA8::new(20, 90, 200, 255),
+            text_color_hover: Some(RGBA8::new(10, 60, 160, 255)),
+            text_color_disabled: Some(RGBA8::new(20, 90, 200, 100)),
+            ..Default::default()
+        }
+    }
+}
+
+#[element_builder]
+#[element_builder_class]
+#[element_builder_tab_index]
+#[element_builder_rect]
+#[element_builder_hidden]
+#[element_builder_disabled]
+#[element_builder_tooltip]
+#[derive_where(Default)]
+pub struct HyperlinkBuilder<A: Clone + 'static> {
+    pub on_select_action: Option<A>,
+    pub url: Option<String>,
+    pub text: String,
+    pub text_offset: Vector,
+}
+
+impl<A: Clone + 'static> HyperlinkBuilder<A> {
+    /// The action to send when the link is clicked.
+    pub fn on_select(mut self, action: A) -> Self {
+        self.on_select_action = Some(action);
+        self
+    }
+
+    pub fn on_select_optional(mut self, action: Option<A>) -> Self {
+        self.on_select_action = action;
+        self
+    }
+
+    /// The URL to open with the default application of the OS (i.e. the web
+    /// browser) when the link is clicked.
+    ///
+    /// If an action is also set, then both the action is sent and the URL is
+    /// opened.
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+
+    /// The text of the link
+    ///
+    /// If this method isn't used, then the URL is used as the text.
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.text = text.into();
+        self
+    }
+
+    /// An offset that can be used mainly to correct the position of the text.
+    ///
+    /// By default this is set to an offset of zero.
+    pub const fn text_offset(mut self, offset: Vector) -> Self {
+        self.text_offset = offset;
+        self
+    }
+
+    pub fn build(self, window_cx: &mut WindowContext<'_, A>) -> Hyperlink {
+        let HyperlinkBuilder {
+            on_select_action,
+            url,
+            text,
+            text_offset,
+            disabled,
+            class,
+            tab_index,
+            z_index,
+            rect,
+            manually_hidden,
+            scissor_rect,
+            key,
+            tooltip_data,
+        } = self;
+
+        let style = window_cx
+            .res
+            .style_system
+            .get::<HyperlinkStyle>(window_cx.builder_class(class));
+        let cursor_icon = style.cursor_icon;
+
+        let text = if text.is_empty() {
+            url.clone().unwrap_or_default()
+        } else {
+            text
+        };
+
+        let label_style = style.label_style(false, disabled);
+
+        let mut inner = LabelInner::new(
+            Some(text),
+            None,
+            text_offset,
+            Vector::default(),
+            None,
+            IconScale::default(),
+            TextIconLayout::default(),
+            &label_style,
+            &mut window_cx.res.font_system,
+            &mut window_cx.res.text_layout_cache,
+        );
+        inner.set_text_direction(
+            window_cx.text_direction(),
+            &label_style,
+            &mut window_cx.res.font_system,
+            &mut window_cx.res.text_layout_cache,
+        );
+
+        let shared_state = Rc::new(RefCell::new(SharedState {
+            inner,
+            url,
+            disabled,
+            tooltip_inner: TooltipInner::new(tooltip_data),
+        }));
+
+        let mut el = ElementBuilder::new(HyperlinkElement {
+            shared_state: Rc::clone(&shared_state),
+            on_select_action,
+            cursor_icon,
+            hovered: false,
+            has_focus: false,
+        })
+        .builder_values(z_index, scissor_rect, class, window_cx)
+        .key(key)
+        .tab_index(tab_index)
+        .rect(rect)
+        .hidden(manually_hidden)
+        .flags(
+            ElementFlags::PAINTS
+                | ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS
+                | ElementFlags::LISTENS_TO_FOCUS_CHANGE
+                | ElementFlags::FOCUSABLE
+                | ElementFlags::DRAWS_OWN_FOCUS_RING,
+        )
+        .build(window_cx);
+        el.set_focusable(!disabled);
+
+        Hyperlink { el, shared_state }
+    }
+}
+
+/// A text link which opens a URL and/or sends an action when clicked.
+struct HyperlinkElement<A: Clone + 'static> {
+    shared_state: Rc<RefCell<SharedState>>,
+    on_select_action: Option<A>,
+    cursor_icon: Option<CursorIcon>,
+    hovered: bool,
+    has_focus: bool,
+}
+
+impl<A: Clone + 'static> HyperlinkElement<A> {
+    fn select(&self, cx: &mut ElementContext<'_, A>) {
+        if let Some(action) = &self.on_select_action {
+            cx.send_action(action.clone()).unwrap();
+        }
+
+        if let Some(url) = &RefCell::borrow(&self.shared_state).url {
+            if let Err(e) = open_url(url) {
+                log::error!("Failed to open URL {}: {}", url, e);
+            }
+        }
+    }
+}
+
+impl<A: Clone + 'static> Element<A> for HyperlinkElement<A> {
+    fn on_event(
+        &mut self,
+        event: ElementEvent,
+        cx: &mut ElementContext<'_, A>,
+    ) -> EventCaptureStatus {
+        let disabled = {
+            let shared_state = RefCell::borrow(&self.shared_state);
+            shared_state.tooltip_inner.handle_event_with_auto_text(
+                &event,
+                shared_state.disabled,
+                shared_state.inner.truncated_text(),
+                cx,
+            );
+            shared_state.disabled
+        };
+
+        match event {
+            ElementEvent::CustomStateChanged => {
+                if disabled {
+                    self.hovered = false;
+                }
+                cx.request_repaint();
+            }
+            ElementEvent::StyleChanged => {
+                let style = cx.res.style_system.get::<HyperlinkStyle>(cx.class());
+                self.cursor_icon = style.cursor_icon;
+            }
+            ElementEvent::Focus(has_focus) => {
+                if self.has_focus != has_focus {
+                    self.has_focus = has_focus;
+                    cx.request_repaint();
+                }
+            }
+            ElementEvent::Activate => {
+                if disabled {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                self.select(cx);
+
+                return EventCaptureStatus::Captured;
+            }
+            ElementEvent::Pointer(PointerEvent::Moved { .. }) => {
+                if disabled {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                if let Some(cursor_icon) = self.cursor_icon {
+                    cx.cursor_icon = cursor_icon;
+                }
+
+                if !self.hovered {
+                    self.hovered = true;
+                    cx.request_repaint();
+                }
+
+                return EventCaptureStatus::Captured;
+            }
+            ElementEvent::Pointer(PointerEvent::PointerLeft) => {
+                if self.hovered {
+                    self.hovered = false;
+                    cx.request_repaint();
+
+                    return EventCaptureStatus::Captured;
+                }
+            }
+            ElementEvent::Pointer(PointerEvent::ButtonJustPressed { button, .. }) => {
+                if button == PointerButton::Primary && !disabled {
+                    self.select(cx);
+
+                    return EventCaptureStatus::Captured;
+                }
+            }
+            _ => {}
+        }
+
+        EventCaptureStatus::NotCaptured
+    }
+
+    fn render(&mut self, cx: RenderContext, primitives: &mut PrimitiveGroup) {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+        let disabled = shared_state.disabled;
+
+        let bounds = Rect::from_size(cx.bounds_size);
+        let style = cx.res.style_system.get::<HyperlinkStyle>(cx.class);
+        let label_style = style.label_style(self.hovered, disabled);
+
+        let label_primitives = shared_state.inner.render(
+            bounds,
+            &label_style,
+            cx.opacity,
+            &mut cx.res.font_system,
+            &mut cx.res.text_layout_cache,
+            &cx.res.icon_font_system,
+        );
+
+        if let Some(p) = label_primitives.text {
+            primitives.add_text(p);
+        }
+
+        if let Some(p) = style.underline_primitive(
+            shared_state
+                .inner
+                .text_bounds_rect()
+                .translate(shared_state.inner.text_offset),
+            label_style.text_color,
+            self.hovered && !disabled,
+            cx.opacity,
+        ) {
+            primitives.add(p);
+        }
+
+        if self.has_focus && !disabled {
+            if let Some(ring) = cx.res.style_system.focus_ring() {
+                primitives.set_z_index(1);
+                primitives.add(ring.create_primitive_with_opacity(
+                    bounds,
+                    Radius::default(),
+                    cx.opacity,
+                ));
+            }
+        }
+    }
+}
+
+struct SharedState {
+    inner: LabelInner,
+    url: Option<String>,
+    disabled: bool,
+    tooltip_inner: TooltipInner,
+}
+
+/// A handle to a [`HyperlinkElement`], a text link which opens a URL and/or
+/// sends an action when clicked.
+#[element_handle]
+#[element_handle_class]
+#[element_handle_tab_index]
+#[element_handle_set_rect]
+#[element_handle_set_tooltip]
+pub struct Hyperlink {
+    shared_state: Rc<RefCell<SharedState>>,
+}
+
+impl Hyperlink {
+    pub fn builder<A: Clone + 'static>() -> HyperlinkBuilder<A> {
+        HyperlinkBuilder::default()
+    }
+
+    /// Returns the size of the padded text.
+    ///
+    /// This size is automatically cached, so it should be relatively
+    /// inexpensive to call.
+    pub fn desired_size(&self, res: &mut ResourceCtx) -> Size {
+        RefCell::borrow_mut(&self.shared_state)
+            .inner
+            .desired_size(|| {
+                res.style_system
+                    .get::<HyperlinkStyle>(self.el.class())
+                    .padding_info()
+            })
+    }
+
+    /// Set the text.
+    ///
+    /// Returns `true` if the text has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently. However, this method still
+    /// involves a string comparison so you may want to call this method
+    /// sparingly.
+    pub fn set_text<T: AsRef<str> + Into<String>>(
+        &mut self,
+        text: T,
+        res: &mut ResourceCtx,
+    ) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if shared_state.inner.set_text(
+            Some(text),
+            &mut res.font_system,
+            &mut res.text_layout_cache,
+            || {
+                res.style_system
+                    .get::<HyperlinkStyle>(self.el.class())
+                    .text_properties
+            },
+        ) {
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn text<'a>(&'a self) -> Ref<'a, str> {
+        Ref::map(RefCell::borrow(&self.shared_state), |s| {
+            s.inner.text().unwrap_or_default()
+        })
+    }
+
+    /// Set the URL which is opened when the link is clicked.
+    ///
+    /// Note, this does not change the text of the link.
+    pub fn set_url(&mut self, url: Option<impl Into<String>>) {
+        RefCell::borrow_mut(&self.shared_state).url = url.map(|u| u.into());
+    }
+
+    pub fn url<'a>(&'a self) -> Option<Ref<'a, str>> {
+        Ref::filter_map(RefCell::borrow(&self.shared_state), |s| s.url.as_deref()).ok()
+    }
+
+    /// Set the disabled state of this element.
+    ///
+    /// Returns `true` if the disabled state has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn set_disabled(&mut self, disabled: bool) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+        self.el.set_focusable(!disabled);
+
+        if shared_state.disabled != disabled {
+            shared_state.disabled = disabled;
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn disabled(&self) -> bool {
+        RefCell::borrow(&self.shared_state).disabled
+    }
+
+    /// Layout out the element (with the top-left corner of the bounds set to `origin`).
+    ///
+    /// Returns `true` if the layout has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn layout(&mut self, origin: Point, res: &mut ResourceCtx) -> bool {
+        let size = self.desired_size(res);
+        self.el.set_rect(Rect::new(origin, size))
+    }
+
+    /// Layout out the element aligned to the given point.
+    ///
+    /// Returns `true` if the layout has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn layout_aligned(&mut self, point: Point, align: Align2, res: &mut ResourceCtx) -> bool {
+        let size = self.desired_size(res);
+        self.el.set_rect(align.align_rect_to_point(point, size))
+    }
+}
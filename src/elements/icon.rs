@@ -165,7 +165,7 @@ impl IconInner {
         self.size_needs_calculated = true;
     }
 
-    pub fn render(&mut self, bounds: Rect, style: &IconStyle) -> IconPrimitives {
+    pub fn render(&mut self, bounds: Rect, style: &IconStyle, opacity: f32) -> IconPrimitives {
         let icon_rect = self.padded_icon_rect(style, bounds.size);
         let size = self
             .icon_size
@@ -184,7 +184,7 @@ impl IconInner {
             icon: TextPrimitive::new_with_icons(
                 None,
                 bounds.origin + icon_rect.origin.to_vector() + self.offset,
-                style.color,
+                apply_opacity(style.color, opacity),
                 None,
                 smallvec::smallvec![CustomGlyph {
                     id: self.icon_id,
@@ -198,7 +198,11 @@ impl IconInner {
                 }],
             ),
             bg_quad: if !style.back_quad.is_transparent() {
-                Some(style.back_quad.create_primitive(bounds))
+                Some(
+                    style
+                        .back_quad
+                        .create_primitive_with_opacity(bounds, opacity),
+                )
             } else {
                 None
             },
@@ -256,6 +260,7 @@ impl IconBuilder {
             rect,
             manually_hidden,
             scissor_rect,
+            key,
         } = self;
 
         let shared_state = Rc::new(RefCell::new(SharedState {
@@ -266,6 +271,7 @@ impl IconBuilder {
             shared_state: Rc::clone(&shared_state),
         })
         .builder_values(z_index, scissor_rect, class, window_cx)
+        .key(key)
         .rect(rect)
         .hidden(manually_hidden)
         .flags(ElementFlags::PAINTS)
@@ -299,6 +305,7 @@ impl<A: Clone + 'static> Element<A> for IconElement {
         let icon_primitives = shared_state.inner.render(
             Rect::from_size(cx.bounds_size),
             cx.res.style_system.get(cx.class),
+            cx.opacity,
         );
 
         if let Some(quad_primitive) = icon_primitives.bg_quad {
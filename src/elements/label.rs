@@ -1,12 +1,15 @@
+use smallvec::SmallVec;
 use std::cell::{Ref, RefCell};
 use std::rc::Rc;
+use unicode_segmentation::UnicodeSegmentation;
 
+use crate::clipboard::ClipboardKind;
 use crate::derive::*;
 use crate::prelude::*;
-use crate::theme::DEFAULT_ICON_SIZE;
+use crate::theme::{DEFAULT_ACCENT_COLOR, DEFAULT_ICON_SIZE};
 use crate::vg::{
-    quad::QuadPrimitive,
-    text::{RcTextBuffer, TextPrimitive},
+    quad::{QuadPrimitive, SolidQuadBuilder, SolidQuadPrimitive},
+    text::{glyphon::cosmic_text::Cursor, Metrics, RcTextBuffer, TextPrimitive},
 };
 
 #[cfg(feature = "svg-icons")]
@@ -66,8 +69,39 @@ pub struct LabelStyle {
     ///
     /// By default this is set to `Align::Center`.
     pub vertical_align: crate::layout::Align,
+
+    /// How to truncate text which doesn't fit inside the padded rectangle.
+    ///
+    /// By default this is set to `TextTruncation::Clip`.
+    pub text_truncation: TextTruncation,
+
+    /// The color of the highlight behind selected text (only used by
+    /// selectable labels).
+    ///
+    /// By default this is set to `DEFAULT_ACCENT_COLOR`.
+    pub selection_bg_color: RGBA8,
 }
 
+/// How to truncate text which doesn't fit inside of its bounds.
+///
+/// When the text of a label or button is truncated, the full text is shown
+/// in a tooltip when the pointer hovers over it (unless the element already
+/// has a tooltip).
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TextTruncation {
+    /// Clip the text at the edge of its bounds.
+    #[default]
+    Clip,
+    /// Replace the end of the text with an ellipsis (i.e. "Long preset na…").
+    End,
+    /// Replace the middle of the text with an ellipsis (i.e.
+    /// "Long pre…name.wav"). This keeps file extensions visible.
+    Middle,
+}
+
+const ELLIPSIS: &str = "\u{2026}";
+
 impl LabelStyle {
     pub fn padding_info(&self) -> LabelPaddingInfo {
         LabelPaddingInfo {
@@ -77,6 +111,21 @@ impl LabelStyle {
             text_icon_spacing: self.text_icon_spacing,
         }
     }
+
+    /// Interpolate the colors of this style from the style `from` using the
+    /// given transition progress.
+    ///
+    /// All non-color properties are taken from `self`.
+    pub fn transition_from(mut self, from: &LabelStyle, progress: &TransitionProgress) -> Self {
+        self.icon_color = Some(lerp_color(
+            from.icon_color.unwrap_or(from.text_color),
+            self.icon_color.unwrap_or(self.text_color),
+            progress.text,
+        ));
+        self.text_color = lerp_color(from.text_color, self.text_color, progress.text);
+        self.back_quad = self.back_quad.transition_from(&from.back_quad, progress);
+        self
+    }
 }
 
 impl Default for LabelStyle {
@@ -92,6 +141,8 @@ impl Default for LabelStyle {
             text_icon_spacing: 0.0,
             back_quad: QuadStyle::TRANSPARENT,
             vertical_align: crate::layout::Align::Center,
+            text_truncation: TextTruncation::Clip,
+            selection_bg_color: DEFAULT_ACCENT_COLOR,
         }
     }
 }
@@ -123,6 +174,21 @@ pub enum TextIconLayout {
     LeftAlignTextRightAlignIcon,
 }
 
+impl TextIconLayout {
+    /// The equivalent layout with the left and right sides swapped (i.e. for
+    /// right-to-left languages).
+    pub fn mirrored(self) -> Self {
+        match self {
+            Self::LeftAlignIconThenText => Self::RightAlignTextThenIcon,
+            Self::LeftAlignTextThenIcon => Self::RightAlignIconThenText,
+            Self::RightAlignIconThenText => Self::LeftAlignTextThenIcon,
+            Self::RightAlignTextThenIcon => Self::LeftAlignIconThenText,
+            Self::LeftAlignIconRightAlignText => Self::LeftAlignTextRightAlignIcon,
+            Self::LeftAlignTextRightAlignIcon => Self::LeftAlignIconRightAlignText,
+        }
+    }
+}
+
 // Information used to calculate label padding.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct LabelPaddingInfo {
@@ -137,10 +203,164 @@ pub struct LabelPrimitives {
     pub icon: Option<TextPrimitive>,
     pub text: Option<TextPrimitive>,
     pub bg_quad: Option<QuadPrimitive>,
+    /// Additional primitives drawn on top of the background quad (a radial
+    /// gradient and per-edge borders).
+    pub bg_overlay: QuadOverlay,
+    /// The highlights behind selected text (one per line).
+    pub selection: SmallVec<[SolidQuadPrimitive; 1]>,
 }
 
 struct TextInner {
     text: String,
+    text_properties: TextProperties,
+    max_width: Option<f32>,
+    /// This buffer may be shared with other elements through the
+    /// `TextLayoutCache`, so it must not be modified.
+    text_buffer: RcTextBuffer,
+    /// The size of the text before truncation.
+    unclipped_size: Size,
+    truncated: bool,
+    /// The anchor and the head of the selected text in the buffer.
+    selection: Option<(Cursor, Cursor)>,
+}
+
+impl TextInner {
+    fn new(
+        text: String,
+        text_properties: TextProperties,
+        font_system: &mut FontSystem,
+        text_cache: &mut TextLayoutCache,
+    ) -> Self {
+        let text_buffer = text_cache.get(&text, &text_properties, None, font_system);
+        let unclipped_size = text_buffer.measure();
+
+        Self {
+            text,
+            text_properties,
+            max_width: None,
+            text_buffer,
+            unclipped_size,
+            truncated: false,
+            selection: None,
+        }
+    }
+
+    fn update_buffer(
+        &mut self,
+        truncation: TextTruncation,
+        font_system: &mut FontSystem,
+        text_cache: &mut TextLayoutCache,
+    ) {
+        self.text_buffer = text_cache.get(
+            &self.text,
+            &self.text_properties,
+            self.max_width,
+            font_system,
+        );
+        self.unclipped_size = self.text_buffer.measure();
+        self.truncated = false;
+        self.selection = None;
+
+        let Some(max_width) = self.max_width else {
+            return;
+        };
+        if self.unclipped_size.width <= max_width {
+            return;
+        }
+
+        if let Some(truncated_text) = truncate_text(
+            &self.text,
+            &self.text_properties,
+            max_width,
+            truncation,
+            font_system,
+            text_cache,
+        ) {
+            self.text_buffer = text_cache.get(
+                &truncated_text,
+                &self.text_properties,
+                self.max_width,
+                font_system,
+            );
+            self.truncated = true;
+        }
+    }
+}
+
+/// Shorten the text with an ellipsis so that it fits within `max_width`.
+///
+/// Returns `None` if the text should not be truncated.
+fn truncate_text(
+    text: &str,
+    text_properties: &TextProperties,
+    max_width: f32,
+    truncation: TextTruncation,
+    font_system: &mut FontSystem,
+    text_cache: &mut TextLayoutCache,
+) -> Option<String> {
+    // Only single lines of text are truncated.
+    if truncation == TextTruncation::Clip || text.contains('\n') {
+        return None;
+    }
+
+    let ellipsis_width = text_cache
+        .get(ELLIPSIS, text_properties, None, font_system)
+        .measure()
+        .width;
+
+    let available_width = max_width - ellipsis_width;
+    if available_width <= 0.0 {
+        return Some(ELLIPSIS.to_string());
+    }
+
+    let full_buffer = text_cache.get(text, text_properties, None, font_system);
+    let raw_buffer = full_buffer.raw_buffer();
+    let run = raw_buffer.layout_runs().next()?;
+
+    let prefix_end = |width: f32| -> usize {
+        run.glyphs
+            .iter()
+            .take_while(|glyph| glyph.x + glyph.w <= width)
+            .last()
+            .map(|glyph| glyph.end)
+            .unwrap_or(0)
+    };
+
+    match truncation {
+        TextTruncation::Clip => None,
+        TextTruncation::End => {
+            let end = prefix_end(available_width);
+
+            Some(format!("{}{}", text[..end].trim_end(), ELLIPSIS))
+        }
+        TextTruncation::Middle => {
+            let half_width = available_width * 0.5;
+
+            let end = prefix_end(half_width);
+            let start = run
+                .glyphs
+                .iter()
+                .rev()
+                .take_while(|glyph| glyph.x >= run.line_w - half_width)
+                .last()
+                .map(|glyph| glyph.start)
+                .unwrap_or(text.len())
+                .max(end);
+
+            Some(format!(
+                "{}{}{}",
+                text[..end].trim_end(),
+                ELLIPSIS,
+                text[start..].trim_start()
+            ))
+        }
+    }
+}
+
+/// The cached text buffer of an icon drawn from an icon font.
+struct FontIconInner {
+    icon: FontIcon,
+    size: Size,
     text_buffer: RcTextBuffer,
 }
 
@@ -163,7 +383,10 @@ pub struct LabelInner {
     padded_size: Size,
     padded_size_needs_calculated: bool,
     text_icon_layout: TextIconLayout,
+    text_direction: TextDirection,
+    truncation: TextTruncation,
     icon: Option<IconID>,
+    font_icon_inner: Option<FontIconInner>,
 }
 
 impl LabelInner {
@@ -177,6 +400,7 @@ impl LabelInner {
         text_icon_layout: TextIconLayout,
         style: &LabelStyle,
         font_system: &mut FontSystem,
+        text_cache: &mut TextLayoutCache,
     ) -> Self {
         let text_inner = text.map(|text| {
             let text: String = text.into();
@@ -189,10 +413,7 @@ impl LabelInner {
                 _ => rootvg::text::Align::Right,
             });
 
-            let text_buffer =
-                RcTextBuffer::new(&text, text_properties, None, None, false, font_system);
-
-            TextInner { text, text_buffer }
+            TextInner::new(text, text_properties, font_system, text_cache)
         });
 
         Self {
@@ -212,6 +433,43 @@ impl LabelInner {
             padded_size: Size::default(),
             padded_size_needs_calculated: true,
             text_icon_layout,
+            text_direction: TextDirection::LeftToRight,
+            truncation: style.text_truncation,
+            font_icon_inner: None,
+        }
+    }
+
+    /// Set the text direction, mirroring the text/icon layout and the
+    /// horizontal padding if it is right-to-left.
+    ///
+    /// Returns `true` if the text direction has changed.
+    pub fn set_text_direction(
+        &mut self,
+        text_direction: TextDirection,
+        style: &LabelStyle,
+        font_system: &mut FontSystem,
+        text_cache: &mut TextLayoutCache,
+    ) -> bool {
+        if self.text_direction == text_direction {
+            return false;
+        }
+        self.text_direction = text_direction;
+
+        // Update the alignment of the text.
+        self.sync_new_style(style, font_system, text_cache);
+
+        true
+    }
+
+    pub fn text_direction(&self) -> TextDirection {
+        self.text_direction
+    }
+
+    fn effective_text_icon_layout(&self) -> TextIconLayout {
+        if self.text_direction.is_rtl() {
+            self.text_icon_layout.mirrored()
+        } else {
+            self.text_icon_layout
         }
     }
 
@@ -281,7 +539,7 @@ impl LabelInner {
             self.unclipped_text_size = self
                 .text_inner
                 .as_mut()
-                .map(|i| i.text_buffer.measure())
+                .map(|i| i.unclipped_size)
                 .unwrap_or(Size::default());
         }
 
@@ -293,6 +551,7 @@ impl LabelInner {
         &mut self,
         text: Option<T>,
         font_system: &mut FontSystem,
+        text_cache: &mut TextLayoutCache,
         get_text_props: F,
     ) -> bool {
         if let Some(inner) = &mut self.text_inner {
@@ -302,7 +561,7 @@ impl LabelInner {
                     self.text_size_needs_calculated = true;
                     self.padded_size_needs_calculated = true;
 
-                    inner.text_buffer.set_text(&inner.text, font_system);
+                    inner.update_buffer(self.truncation, font_system, text_cache);
 
                     true
                 } else {
@@ -319,20 +578,19 @@ impl LabelInner {
 
             let mut text_properties = (get_text_props)();
 
-            text_properties.align = Some(match self.text_icon_layout {
+            text_properties.align = Some(match self.effective_text_icon_layout() {
                 TextIconLayout::LeftAlignTextThenIcon
                 | TextIconLayout::LeftAlignIconThenText
                 | TextIconLayout::LeftAlignTextRightAlignIcon => rootvg::text::Align::Left,
                 _ => rootvg::text::Align::Right,
             });
 
-            let text_buffer =
-                RcTextBuffer::new(&new_text, text_properties, None, None, false, font_system);
-
-            self.text_inner = Some(TextInner {
-                text: new_text,
-                text_buffer,
-            });
+            self.text_inner = Some(TextInner::new(
+                new_text,
+                text_properties,
+                font_system,
+                text_cache,
+            ));
 
             true
         } else {
@@ -344,6 +602,233 @@ impl LabelInner {
         self.text_inner.as_ref().map(|i| i.text.as_str())
     }
 
+    /// Returns `true` if the text was truncated with an ellipsis when it
+    /// was last rendered.
+    pub fn is_truncated(&self) -> bool {
+        self.text_inner.as_ref().is_some_and(|i| i.truncated)
+    }
+
+    /// The full text if it was truncated when it was last rendered (i.e.
+    /// to show in a tooltip).
+    pub fn truncated_text(&self) -> Option<&str> {
+        self.text_inner
+            .as_ref()
+            .filter(|i| i.truncated)
+            .map(|i| i.text.as_str())
+    }
+
+    /// The rectangle of the text relative to the bounds of the element, as
+    /// of when it was last rendered.
+    pub fn text_bounds_rect(&self) -> Rect {
+        self.text_bounds_rect
+    }
+
+    /// Map a point relative to the bounds of the element to a cursor in the
+    /// text buffer.
+    fn hit(&self, pos: Point) -> Option<Cursor> {
+        let inner = self.text_inner.as_ref()?;
+
+        let pos = pos - self.text_bounds_rect.origin.to_vector() - self.text_offset;
+
+        inner.text_buffer.raw_buffer().hit(pos.x, pos.y)
+    }
+
+    /// Move the head of the selection to the given point relative to the
+    /// bounds of the element.
+    ///
+    /// If `extend` is `false`, then the anchor of the selection is moved as
+    /// well.
+    ///
+    /// Returns `true` if the selection has changed.
+    pub fn select_to(&mut self, pos: Point, extend: bool) -> bool {
+        let Some(cursor) = self.hit(pos) else {
+            return false;
+        };
+        let Some(inner) = &mut self.text_inner else {
+            return false;
+        };
+
+        let anchor = match inner.selection {
+            Some((anchor, _)) if extend => anchor,
+            _ => cursor,
+        };
+
+        let new_selection = Some((anchor, cursor));
+        if inner.selection != new_selection {
+            inner.selection = new_selection;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Select the word at the given point relative to the bounds of the
+    /// element.
+    ///
+    /// Returns `true` if the selection has changed.
+    pub fn select_word_at(&mut self, pos: Point) -> bool {
+        let Some(cursor) = self.hit(pos) else {
+            return false;
+        };
+        let Some(inner) = &mut self.text_inner else {
+            return false;
+        };
+
+        let raw_buffer = inner.text_buffer.raw_buffer();
+        let Some(line) = raw_buffer.lines.get(cursor.line) else {
+            return false;
+        };
+
+        let Some((start, word)) = line
+            .text()
+            .split_word_bound_indices()
+            .find(|(i, word)| cursor.index >= *i && cursor.index < *i + word.len())
+        else {
+            return false;
+        };
+
+        let new_selection = Some((
+            Cursor::new(cursor.line, start),
+            Cursor::new(cursor.line, start + word.len()),
+        ));
+        if inner.selection != new_selection {
+            inner.selection = new_selection;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns `true` if the selection has changed.
+    pub fn select_all(&mut self) -> bool {
+        let Some(inner) = &mut self.text_inner else {
+            return false;
+        };
+
+        let raw_buffer = inner.text_buffer.raw_buffer();
+        let Some(last_line) = raw_buffer.lines.len().checked_sub(1) else {
+            return false;
+        };
+
+        let new_selection = Some((
+            Cursor::new(0, 0),
+            Cursor::new(last_line, raw_buffer.lines[last_line].text().len()),
+        ));
+        if inner.selection != new_selection {
+            inner.selection = new_selection;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns `true` if there was a selection.
+    pub fn clear_selection(&mut self) -> bool {
+        self.text_inner
+            .as_mut()
+            .and_then(|i| i.selection.take())
+            .is_some()
+    }
+
+    /// The selected text, or `None` if no text is selected.
+    ///
+    /// Note, if the text is truncated, then this is the selected portion of
+    /// the displayed (truncated) text.
+    pub fn selected_text(&self) -> Option<String> {
+        let inner = self.text_inner.as_ref()?;
+        let (start, end) = ordered_selection(inner.selection?);
+        if start == end {
+            return None;
+        }
+
+        let raw_buffer = inner.text_buffer.raw_buffer();
+
+        let mut text = String::new();
+        for line_i in start.line..=end.line {
+            let Some(line) = raw_buffer.lines.get(line_i) else {
+                break;
+            };
+            let line_text = line.text();
+
+            let start_i = if line_i == start.line { start.index } else { 0 };
+            let end_i = if line_i == end.line {
+                end.index
+            } else {
+                line_text.len()
+            };
+
+            if line_i != start.line {
+                text.push('\n');
+            }
+            text.push_str(line_text.get(start_i..end_i).unwrap_or_default());
+        }
+
+        Some(text)
+    }
+
+    fn selection_primitives(
+        &self,
+        text_origin: Point,
+        color: RGBA8,
+    ) -> SmallVec<[SolidQuadPrimitive; 1]> {
+        let mut primitives = SmallVec::new();
+
+        let Some(inner) = &self.text_inner else {
+            return primitives;
+        };
+        let Some((start, end)) = inner.selection.map(ordered_selection) else {
+            return primitives;
+        };
+        if start == end {
+            return primitives;
+        }
+
+        let line_height = inner.text_properties.metrics.line_height;
+
+        for run in inner.text_buffer.raw_buffer().layout_runs() {
+            if run.line_i < start.line || run.line_i > end.line {
+                continue;
+            }
+
+            let start_i = if run.line_i == start.line {
+                start.index
+            } else {
+                0
+            };
+            let end_i = if run.line_i == end.line {
+                end.index
+            } else {
+                usize::MAX
+            };
+
+            let mut range: Option<(f32, f32)> = None;
+            for glyph in run
+                .glyphs
+                .iter()
+                .filter(|g| g.end > start_i && g.start < end_i)
+            {
+                range = Some(match range {
+                    Some((min, max)) => (min.min(glyph.x), max.max(glyph.x + glyph.w)),
+                    None => (glyph.x, glyph.x + glyph.w),
+                });
+            }
+
+            if let Some((min_x, max_x)) = range {
+                primitives.push(
+                    SolidQuadBuilder::new(Size::new(max_x - min_x, line_height))
+                        .position(Point::new(
+                            text_origin.x + min_x,
+                            text_origin.y + run.line_top,
+                        ))
+                        .bg_color(color)
+                        .into(),
+                );
+            }
+        }
+
+        primitives
+    }
+
     pub fn set_icon(&mut self, icon: Option<IconID>) -> bool {
         if self.icon == icon {
             false
@@ -376,19 +861,25 @@ impl LabelInner {
         self.icon_size
     }
 
-    pub fn sync_new_style(&mut self, style: &LabelStyle, font_system: &mut FontSystem) {
+    pub fn sync_new_style(
+        &mut self,
+        style: &LabelStyle,
+        font_system: &mut FontSystem,
+        text_cache: &mut TextLayoutCache,
+    ) {
+        self.truncation = style.text_truncation;
+
         if let Some(inner) = &mut self.text_inner {
             let mut text_properties = style.text_properties.clone();
-            text_properties.align = Some(match self.text_icon_layout {
+            text_properties.align = Some(match self.effective_text_icon_layout() {
                 TextIconLayout::LeftAlignTextThenIcon
                 | TextIconLayout::LeftAlignIconThenText
                 | TextIconLayout::LeftAlignTextRightAlignIcon => rootvg::text::Align::Left,
                 _ => rootvg::text::Align::Right,
             });
 
-            inner
-                .text_buffer
-                .set_text_and_props(&inner.text, text_properties, font_system);
+            inner.text_properties = text_properties;
+            inner.update_buffer(self.truncation, font_system, text_cache);
 
             self.text_size_needs_calculated = true;
         }
@@ -396,11 +887,17 @@ impl LabelInner {
         self.padded_size_needs_calculated = true;
     }
 
+    /// * `icon_fonts` - If the icon of this label is registered in this
+    /// registry, then the glyph from the icon font is drawn instead of the
+    /// SVG icon.
     pub fn render(
         &mut self,
         bounds: Rect,
         style: &LabelStyle,
+        opacity: f32,
         font_system: &mut FontSystem,
+        text_cache: &mut TextLayoutCache,
+        icon_fonts: &IconFontSystem,
     ) -> LabelPrimitives {
         let mut needs_layout = self.text_size_needs_calculated || self.padded_size_needs_calculated;
 
@@ -421,7 +918,8 @@ impl LabelInner {
                 self.unclipped_text_size,
                 self.icon,
                 icon_size,
-                self.text_icon_layout,
+                self.effective_text_icon_layout(),
+                self.text_direction,
                 style,
             );
 
@@ -429,19 +927,22 @@ impl LabelInner {
             self.icon_bounds_rect = layout_res.icon_bounds_rect;
 
             if let Some(inner) = &mut self.text_inner {
-                inner.text_buffer.set_bounds(
-                    Some(self.text_bounds_rect.width()),
-                    None,
-                    font_system,
-                );
+                let max_width = Some(self.text_bounds_rect.width());
+                if inner.max_width != max_width {
+                    inner.max_width = max_width;
+                    inner.update_buffer(self.truncation, font_system, text_cache);
+                }
             }
         }
 
+        let text_origin =
+            bounds.origin + self.text_bounds_rect.origin.to_vector() + self.text_offset;
+
         let text = if let Some(inner) = &self.text_inner {
             Some(TextPrimitive::new(
                 inner.text_buffer.clone(),
-                bounds.origin + self.text_bounds_rect.origin.to_vector() + self.text_offset,
-                style.text_color,
+                text_origin,
+                apply_opacity(style.text_color, opacity),
                 Some(Rect::new(
                     Point::new(-1.0, -1.0),
                     Size::new(bounds.width() + 2.0, bounds.height() + 2.0),
@@ -451,8 +952,117 @@ impl LabelInner {
             None
         };
 
+        let font_icon = self.icon.and_then(|id| icon_fonts.get(id).copied());
+
+        let icon = if let Some(font_icon) = font_icon {
+            Some(self.font_icon_primitive(font_icon, bounds, style, opacity, font_system))
+        } else {
+            self.svg_icon_primitive(bounds, style, opacity)
+        };
+
+        let bg_quad = if !style.back_quad.is_transparent() {
+            Some(
+                style
+                    .back_quad
+                    .create_primitive_with_opacity(bounds, opacity),
+            )
+        } else {
+            None
+        };
+
+        let bg_overlay = style
+            .back_quad
+            .create_overlay_primitives_with_opacity(bounds, opacity);
+
+        let selection = self.selection_primitives(
+            text_origin,
+            apply_opacity(style.selection_bg_color, opacity),
+        );
+
+        LabelPrimitives {
+            text,
+            icon,
+            bg_quad,
+            bg_overlay,
+            selection,
+        }
+    }
+
+    fn font_icon_primitive(
+        &mut self,
+        font_icon: FontIcon,
+        bounds: Rect,
+        style: &LabelStyle,
+        opacity: f32,
+        font_system: &mut FontSystem,
+    ) -> TextPrimitive {
+        let size = self
+            .icon_size
+            .unwrap_or(Size::new(style.default_icon_size, style.default_icon_size))
+            * self.icon_scale.0;
+
+        let needs_new_buffer = match &self.font_icon_inner {
+            Some(inner) => inner.icon != font_icon || inner.size != size,
+            None => true,
+        };
+
+        if needs_new_buffer {
+            let mut glyph = [0; 4];
+            let glyph = font_icon.codepoint.encode_utf8(&mut glyph);
+
+            let text_buffer = RcTextBuffer::new(
+                glyph,
+                TextProperties {
+                    metrics: Metrics {
+                        font_size: size.height * font_icon.scale,
+                        line_height: size.height,
+                    },
+                    attrs: Attrs::new().family(Family::Name(font_icon.family)),
+                    align: Some(rootvg::text::Align::Center),
+                    ..Default::default()
+                },
+                Some(size.width),
+                Some(size.height),
+                false,
+                font_system,
+            );
+
+            self.font_icon_inner = Some(FontIconInner {
+                icon: font_icon,
+                size,
+                text_buffer,
+            });
+        }
+
+        let inner = self.font_icon_inner.as_ref().unwrap();
+
+        // Keep the scaled icon centered within its bounds.
+        let scale_offset = ((self.icon_bounds_rect.size - size) * 0.5).to_vector();
+
+        TextPrimitive::new(
+            inner.text_buffer.clone(),
+            bounds.origin
+                + self.icon_bounds_rect.origin.to_vector()
+                + scale_offset
+                + self.icon_offset
+                + font_icon.offset,
+            apply_opacity(style.icon_color.unwrap_or(style.text_color), opacity),
+            Some(Rect::new(
+                Point::new(-1.0, -1.0),
+                Size::new(bounds.width() + 2.0, bounds.height() + 2.0),
+            )),
+        )
+    }
+
+    #[allow(unused)]
+    fn svg_icon_primitive(
+        &self,
+        bounds: Rect,
+        style: &LabelStyle,
+        opacity: f32,
+    ) -> Option<TextPrimitive> {
         #[cfg(feature = "svg-icons")]
-        let icon = if let Some(icon) = self.icon {
+        if let Some(icon) = self.icon {
             let size = self
                 .icon_size
                 .unwrap_or(Size::new(style.default_icon_size, style.default_icon_size));
@@ -466,10 +1076,10 @@ impl LabelInner {
                 (size, Vector::zero())
             };
 
-            Some(TextPrimitive::new_with_icons(
+            return Some(TextPrimitive::new_with_icons(
                 None,
                 bounds.origin + self.icon_bounds_rect.origin.to_vector() + self.icon_offset,
-                style.icon_color.unwrap_or(style.text_color),
+                apply_opacity(style.icon_color.unwrap_or(style.text_color), opacity),
                 Some(Rect::new(
                     Point::new(-1.0, -1.0),
                     Size::new(bounds.width() + 2.0, bounds.height() + 2.0),
@@ -484,25 +1094,10 @@ impl LabelInner {
                     color: None,
                     metadata: 0,
                 }],
-            ))
-        } else {
-            None
-        };
-
-        #[cfg(not(feature = "svg-icons"))]
-        let icon = None;
-
-        let bg_quad = if !style.back_quad.is_transparent() {
-            Some(style.back_quad.create_primitive(bounds))
-        } else {
-            None
-        };
-
-        LabelPrimitives {
-            text,
-            icon,
-            bg_quad,
+            ));
         }
+
+        None
     }
 }
 
@@ -519,6 +1114,7 @@ pub struct LabelBuilder {
     pub text_offset: Vector,
     pub icon_offset: Vector,
     pub text_icon_layout: TextIconLayout,
+    pub selectable: bool,
 }
 
 impl LabelBuilder {
@@ -533,6 +1129,10 @@ impl LabelBuilder {
 
     /// The icon of the label
     ///
+    /// If this icon ID is registered in `ResourceCtx::icon_font_system`, then
+    /// the glyph from the icon font will be drawn. Otherwise the SVG icon with
+    /// this ID will be drawn.
+    ///
     /// If this method isn't used, then the label will have no icon (unless
     /// [`LabelBulder::icon_optional`] is used).
     pub fn icon(mut self, icon: impl Into<IconID>) -> Self {
@@ -596,6 +1196,15 @@ impl LabelBuilder {
         self
     }
 
+    /// Whether or not the user can select the text with the pointer and copy
+    /// it to the clipboard (i.e. for error messages and version strings).
+    ///
+    /// By default this is set to `false`.
+    pub const fn selectable(mut self, selectable: bool) -> Self {
+        self.selectable = selectable;
+        self
+    }
+
     pub fn build<A: Clone + 'static>(self, window_cx: &mut WindowContext<'_, A>) -> Label {
         let LabelBuilder {
             text,
@@ -605,11 +1214,13 @@ impl LabelBuilder {
             text_offset,
             icon_offset,
             text_icon_layout,
+            selectable,
             class,
             z_index,
             rect,
             manually_hidden,
             scissor_rect,
+            key,
         } = self;
 
         let style = window_cx
@@ -628,16 +1239,41 @@ impl LabelBuilder {
                 text_icon_layout,
                 &style,
                 &mut window_cx.res.font_system,
+                &mut window_cx.res.text_layout_cache,
             ),
         }));
 
+        RefCell::borrow_mut(&shared_state).inner.set_text_direction(
+            window_cx.text_direction(),
+            &style,
+            &mut window_cx.res.font_system,
+            &mut window_cx.res.text_layout_cache,
+        );
+
+        // Only listen to the pointer if the text is selectable or if the full
+        // text of truncated text needs to be shown in a tooltip.
+        let flags = if selectable {
+            ElementFlags::PAINTS
+                | ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS
+                | ElementFlags::LISTENS_TO_POINTER_OUTSIDE_BOUNDS_WHEN_FOCUSED
+                | ElementFlags::LISTENS_TO_KEYS_WHEN_FOCUSED
+                | ElementFlags::LISTENS_TO_FOCUS_CHANGE
+        } else if style.text_truncation == TextTruncation::Clip {
+            ElementFlags::PAINTS
+        } else {
+            ElementFlags::PAINTS | ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS
+        };
+
         let el = ElementBuilder::new(LabelElement {
             shared_state: Rc::clone(&shared_state),
+            selectable,
+            dragging: false,
         })
         .builder_values(z_index, scissor_rect, class, window_cx)
+        .key(key)
         .rect(rect)
         .hidden(manually_hidden)
-        .flags(ElementFlags::PAINTS)
+        .flags(flags)
         .build(window_cx);
 
         Label { el, shared_state }
@@ -647,6 +1283,115 @@ impl LabelBuilder {
 /// A label element with an optional quad background.
 struct LabelElement {
     shared_state: Rc<RefCell<SharedState>>,
+    selectable: bool,
+    dragging: bool,
+}
+
+impl LabelElement {
+    fn on_selection_event<A: Clone + 'static>(
+        &mut self,
+        event: &ElementEvent,
+        cx: &mut ElementContext<'_, A>,
+    ) -> EventCaptureStatus {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        match event {
+            ElementEvent::Pointer(PointerEvent::Moved { position, .. }) => {
+                if cx.is_point_within_visible_bounds(*position) {
+                    cx.cursor_icon = CursorIcon::Text;
+                }
+
+                if self.dragging {
+                    let pos = *position - cx.rect().origin.to_vector();
+                    if shared_state.inner.select_to(pos, true) {
+                        cx.request_repaint();
+                    }
+                }
+
+                return EventCaptureStatus::Captured;
+            }
+            ElementEvent::Pointer(PointerEvent::ButtonJustPressed {
+                position,
+                button,
+                click_count,
+                modifiers,
+                ..
+            }) => {
+                if *button != PointerButton::Primary
+                    || !cx.is_point_within_visible_bounds(*position)
+                {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                let pos = *position - cx.rect().origin.to_vector();
+
+                let changed = match click_count {
+                    2 => shared_state.inner.select_word_at(pos),
+                    3 => shared_state.inner.select_all(),
+                    _ => {
+                        self.dragging = true;
+                        shared_state
+                            .inner
+                            .select_to(pos, modifiers.contains(Modifiers::SHIFT))
+                    }
+                };
+                if changed {
+                    cx.request_repaint();
+                }
+
+                if !cx.has_focus() {
+                    cx.steal_focus();
+                }
+
+                return EventCaptureStatus::Captured;
+            }
+            ElementEvent::Pointer(PointerEvent::ButtonJustReleased { button, .. }) => {
+                if *button == PointerButton::Primary && self.dragging {
+                    self.dragging = false;
+                    return EventCaptureStatus::Captured;
+                }
+            }
+            ElementEvent::Keyboard(key_event) => {
+                if key_event.state == KeyState::Up
+                    || !key_event.modifiers.contains(Modifiers::CONTROL)
+                {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                match key_event.code {
+                    Code::KeyC => {
+                        if let Some(text) = shared_state.inner.selected_text() {
+                            cx.clipboard.write(ClipboardKind::Standard, text);
+                        }
+                        return EventCaptureStatus::Captured;
+                    }
+                    Code::KeyA => {
+                        if shared_state.inner.select_all() {
+                            cx.request_repaint();
+                        }
+                        return EventCaptureStatus::Captured;
+                    }
+                    _ => {}
+                }
+            }
+            ElementEvent::Focus(has_focus) => {
+                if *has_focus {
+                    cx.listen_to_pointer_clicked_off();
+                } else {
+                    self.dragging = false;
+                    if shared_state.inner.clear_selection() {
+                        cx.request_repaint();
+                    }
+                }
+            }
+            ElementEvent::ClickedOff => {
+                cx.release_focus();
+            }
+            _ => {}
+        }
+
+        EventCaptureStatus::NotCaptured
+    }
 }
 
 impl<A: Clone + 'static> Element<A> for LabelElement {
@@ -655,11 +1400,30 @@ impl<A: Clone + 'static> Element<A> for LabelElement {
         event: ElementEvent,
         cx: &mut ElementContext<'_, A>,
     ) -> EventCaptureStatus {
-        if let ElementEvent::CustomStateChanged = event {
-            cx.request_repaint();
+        let mut status = EventCaptureStatus::NotCaptured;
+        if self.selectable {
+            status = self.on_selection_event(&event, cx);
         }
 
-        EventCaptureStatus::NotCaptured
+        match event {
+            ElementEvent::CustomStateChanged => {
+                cx.request_repaint();
+            }
+            ElementEvent::Pointer(PointerEvent::Moved { just_entered, .. }) => {
+                if just_entered && RefCell::borrow(&self.shared_state).inner.is_truncated() {
+                    cx.start_hover_timeout();
+                }
+            }
+            ElementEvent::Pointer(PointerEvent::HoverTimeout { .. }) => {
+                // Show the full text of truncated labels.
+                if let Some(text) = RefCell::borrow(&self.shared_state).inner.truncated_text() {
+                    cx.show_tooltip(TooltipData::new(text, Align2::TOP_CENTER), true);
+                }
+            }
+            _ => {}
+        }
+
+        status
     }
 
     fn render(&mut self, cx: RenderContext, primitives: &mut PrimitiveGroup) {
@@ -668,13 +1432,22 @@ impl<A: Clone + 'static> Element<A> for LabelElement {
         let label_primitives = shared_state.inner.render(
             Rect::from_size(cx.bounds_size),
             cx.res.style_system.get(cx.class),
+            cx.opacity,
             &mut cx.res.font_system,
+            &mut cx.res.text_layout_cache,
+            &cx.res.icon_font_system,
         );
 
         if let Some(quad_primitive) = label_primitives.bg_quad {
             primitives.add(quad_primitive);
         }
 
+        label_primitives.bg_overlay.add_to(primitives);
+
+        for quad_primitive in label_primitives.selection {
+            primitives.add_solid_quad(quad_primitive);
+        }
+
         if let Some(text_primitive) = label_primitives.text {
             primitives.set_z_index(1);
             primitives.add_text(text_primitive);
@@ -734,11 +1507,16 @@ impl Label {
     ) -> bool {
         let mut shared_state = RefCell::borrow_mut(&self.shared_state);
 
-        if shared_state.inner.set_text(text, &mut res.font_system, || {
-            res.style_system
-                .get::<LabelStyle>(self.el.class())
-                .text_properties
-        }) {
+        if shared_state.inner.set_text(
+            text,
+            &mut res.font_system,
+            &mut res.text_layout_cache,
+            || {
+                res.style_system
+                    .get::<LabelStyle>(self.el.class())
+                    .text_properties
+            },
+        ) {
             self.el.notify_custom_state_change();
             true
         } else {
@@ -773,6 +1551,12 @@ impl Label {
         RefCell::borrow(&self.shared_state).inner.icon
     }
 
+    /// The text selected by the user, or `None` if no text is selected (or
+    /// if this label is not selectable).
+    pub fn selected_text(&self) -> Option<String> {
+        RefCell::borrow(&self.shared_state).inner.selected_text()
+    }
+
     /// An offset that can be used mainly to correct the position of the text.
     ///
     /// This does not effect the position of the background quad.
@@ -880,6 +1664,15 @@ impl Label {
     }
 }
 
+/// Returns the selection with the start before the end.
+fn ordered_selection((anchor, head): (Cursor, Cursor)) -> (Cursor, Cursor) {
+    if (head.line, head.index) < (anchor.line, anchor.index) {
+        (head, anchor)
+    } else {
+        (anchor, head)
+    }
+}
+
 struct LayoutResult {
     text_bounds_rect: Rect,
     icon_bounds_rect: Rect,
@@ -891,14 +1684,18 @@ fn layout(
     icon: Option<IconID>,
     icon_size: Size,
     text_icon_layout: TextIconLayout,
+    text_direction: TextDirection,
     style: &LabelStyle,
 ) -> LayoutResult {
+    let text_padding = text_direction.mirror_padding(style.text_padding);
+    let icon_padding = text_direction.mirror_padding(style.icon_padding);
+
     if icon.is_none() {
         return LayoutResult {
             text_bounds_rect: layout_label_only(
                 bounds_size,
                 unclipped_text_size,
-                style.text_padding,
+                text_padding,
                 style.vertical_align,
             ),
             icon_bounds_rect: Rect::zero(),
@@ -908,7 +1705,7 @@ fn layout(
     if unclipped_text_size.is_empty() {
         return LayoutResult {
             text_bounds_rect: Rect::zero(),
-            icon_bounds_rect: layout_icon_only(icon_size, &style.icon_padding, bounds_size),
+            icon_bounds_rect: layout_icon_only(icon_size, &icon_padding, bounds_size),
         };
     }
 
@@ -916,19 +1713,18 @@ fn layout(
         TextIconLayout::LeftAlignIconRightAlignText
         | TextIconLayout::LeftAlignIconThenText
         | TextIconLayout::RightAlignIconThenText => {
-            let mut icon_padding = style.icon_padding;
+            let mut icon_padding = icon_padding;
             icon_padding.right += style.text_icon_spacing;
 
             icon_padding
         }
         _ => {
-            let mut icon_padding = style.icon_padding;
+            let mut icon_padding = icon_padding;
             icon_padding.left += style.text_icon_spacing;
 
             icon_padding
         }
     };
-    let text_padding = style.text_padding;
 
     let text_padded_width = unclipped_text_size.width + text_padding.left + text_padding.right;
     let icon_padded_width = icon_size.width + icon_padding.left + icon_padding.right;
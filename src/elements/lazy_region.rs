@@ -0,0 +1,122 @@
+use crate::prelude::*;
+
+/// The contents of a [`LazyRegion`] (i.e. a struct holding the elements of a
+/// tab page).
+pub trait LazyContents {
+    /// Show or hide all of the elements in the contents.
+    fn set_hidden(&mut self, hidden: bool);
+
+    /// Lay out the elements in the contents inside of the given bounds.
+    ///
+    /// This is called when the contents are built and whenever the bounds
+    /// of the region change.
+    #[allow(unused)]
+    fn layout(&mut self, bounds: Rect, res: &mut ResourceCtx) {}
+}
+
+/// A helper struct which defers building a group of elements until the
+/// group is first shown (i.e. a tab page the user may never open).
+///
+/// The contents can be dropped again while hidden with
+/// [`LazyRegion::unload`] to free up memory, in which case they will be
+/// rebuilt the next time the region is shown.
+pub struct LazyRegion<T: LazyContents, A: Clone + 'static> {
+    contents: Option<T>,
+    build: Box<dyn FnMut(&mut WindowContext<A>) -> T>,
+    bounds: Rect,
+    hidden: bool,
+}
+
+impl<T: LazyContents, A: Clone + 'static> LazyRegion<T, A> {
+    /// Create a new lazy region.
+    ///
+    /// * `hidden` - Whether or not the region is initially hidden. If it is
+    /// not hidden, then the contents are built immediately.
+    /// * `build` - The function which builds the contents.
+    pub fn new<F>(hidden: bool, build: F, window_cx: &mut WindowContext<A>) -> Self
+    where
+        F: FnMut(&mut WindowContext<A>) -> T + 'static,
+    {
+        let mut new_self = Self {
+            contents: None,
+            build: Box::new(build),
+            bounds: Rect::default(),
+            hidden: true,
+        };
+
+        new_self.set_hidden(hidden, window_cx);
+
+        new_self
+    }
+
+    /// Show or hide the region, building its contents if they have not been
+    /// built yet.
+    ///
+    /// Returns `true` if the hidden state has changed.
+    pub fn set_hidden(&mut self, hidden: bool, window_cx: &mut WindowContext<A>) -> bool {
+        if self.hidden == hidden {
+            return false;
+        }
+        self.hidden = hidden;
+
+        if let Some(contents) = &mut self.contents {
+            contents.set_hidden(hidden);
+        } else if !hidden {
+            let mut contents = (self.build)(window_cx);
+            contents.layout(self.bounds, window_cx.res);
+            contents.set_hidden(false);
+
+            self.contents = Some(contents);
+        }
+
+        true
+    }
+
+    /// Set the bounds of the region.
+    ///
+    /// If the contents are built, then they are laid out again.
+    pub fn set_bounds(&mut self, bounds: Rect, res: &mut ResourceCtx) {
+        if self.bounds == bounds {
+            return;
+        }
+        self.bounds = bounds;
+
+        if let Some(contents) = &mut self.contents {
+            contents.layout(bounds, res);
+        }
+    }
+
+    /// Drop the contents of the region if it is currently hidden.
+    ///
+    /// The contents will be rebuilt the next time the region is shown.
+    ///
+    /// Returns `true` if the contents were dropped.
+    pub fn unload(&mut self) -> bool {
+        if self.hidden {
+            self.contents.take().is_some()
+        } else {
+            false
+        }
+    }
+
+    pub fn hidden(&self) -> bool {
+        self.hidden
+    }
+
+    pub fn bounds(&self) -> Rect {
+        self.bounds
+    }
+
+    /// Returns `true` if the contents are currently built.
+    pub fn is_loaded(&self) -> bool {
+        self.contents.is_some()
+    }
+
+    pub fn contents(&self) -> Option<&T> {
+        self.contents.as_ref()
+    }
+
+    pub fn contents_mut(&mut self) -> Option<&mut T> {
+        self.contents.as_mut()
+    }
+}
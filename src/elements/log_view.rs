@@ -0,0 +1,683 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+use crate::derive::*;
+use crate::prelude::*;
+use crate::vg::text::{RcTextBuffer, TextPrimitive};
+
+pub use log::Level;
+
+/// The style of a [`LogView`] element
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogViewStyle {
+    /// The properties of the text.
+    ///
+    /// By default this uses a monospace font with no wrapping. The line
+    /// height of these properties is the height of each row.
+    pub text_properties: TextProperties,
+
+    /// The color of lines with a severity of [`Level::Error`].
+    ///
+    /// By default this is set to `RGBA8::new(255, 110, 100, 255)`.
+    pub error_color: RGBA8,
+    /// The color of lines with a severity of [`Level::Warn`].
+    ///
+    /// By default this is set to `RGBA8::new(255, 200, 80, 255)`.
+    pub warn_color: RGBA8,
+    /// The color of lines with a severity of [`Level::Info`].
+    ///
+    /// By default this is set to `color::WHITE`.
+    pub info_color: RGBA8,
+    /// The color of lines with a severity of [`Level::Debug`].
+    ///
+    /// By default this is set to `RGBA8::new(150, 150, 150, 255)`.
+    pub debug_color: RGBA8,
+    /// The color of lines with a severity of [`Level::Trace`].
+    ///
+    /// By default this is set to `RGBA8::new(110, 110, 110, 255)`.
+    pub trace_color: RGBA8,
+
+    /// The style of the background quad.
+    ///
+    /// By default this is set to `QuadStyle::TRANSPARENT`.
+    pub back_quad: QuadStyle,
+
+    /// The style of the scroll bar slider.
+    ///
+    /// By default this is set to a semi-transparent gray.
+    pub slider_quad: QuadStyle,
+    /// The width of the scroll bar slider.
+    ///
+    /// By default this is set to `8.0`.
+    pub slider_width: f32,
+
+    /// The padding between the text and the bounding rectangle.
+    ///
+    /// By default this has all values set to `4.0`.
+    pub padding: Padding,
+}
+
+impl LogViewStyle {
+    pub fn level_color(&self, level: Level) -> RGBA8 {
+        match level {
+            Level::Error => self.error_color,
+            Level::Warn => self.warn_color,
+            Level::Info => self.info_color,
+            Level::Debug => self.debug_color,
+            Level::Trace => self.trace_color,
+        }
+    }
+}
+
+impl Default for LogViewStyle {
+    fn default() -> Self {
+        Self {
+            text_properties: TextProperties {
+                attrs: Attrs::new().family(Family::Monospace),
+                wrap: Wrap::None,
+                ..Default::default()
+            },
+            error_color: RGBA8::new(255, 110, 100, 255),
+            warn_color: RGBA8::new(255, 200, 80, 255),
+            info_color: color::WHITE,
+            debug_color: RGBA8::new(150, 150, 150, 255),
+            trace_color: RGBA8::new(110, 110, 110, 255),
+            back_quad: QuadStyle::TRANSPARENT,
+            slider_quad: QuadStyle {
+                bg: Background::Solid(RGBA8::new(150, 150, 150, 100)),
+                border: BorderStyle {
+                    radius: 4.0.into(),
+                    ..Default::default()
+                },
+                flags: QuadFlags::SNAP_ALL_TO_NEAREST_PIXEL,
+            },
+            slider_width: 8.0,
+            padding: Padding::new(4.0, 4.0, 4.0, 4.0),
+        }
+    }
+}
+
+impl ElementStyle for LogViewStyle {
+    const ID: &'static str = "logview";
+
+    fn default_dark_style() -> Self {
+        Self::default()
+    }
+
+    fn default_light_style() -> Self {
+        Self {
+            error_color: RGBA8::new(200, 30, 20, 255),
+            warn_color: RGBA8::new(160, 100, 0, 255),
+            info_color: color::BLACK,
+            debug_color: RGBA8::new(90, 90, 90, 255),
+            trace_color: RGBA8::new(130, 130, 130, 255),
+            ..Default::default()
+        }
+    }
+}
+
+/// A single line in a [`LogView`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogLine {
+    pub text: String,
+    pub level: Level,
+}
+
+impl LogLine {
+    pub fn new(text: impl Into<String>, level: Level) -> Self {
+        Self {
+            text: text.into(),
+            level,
+        }
+    }
+}
+
+/// A buffer for a single visible row.
+struct Row {
+    /// The serial number of the line this row is currently displaying.
+    line_serial: Option<u64>,
+    buffer: RcTextBuffer,
+}
+
+#[element_builder]
+#[element_builder_class]
+#[element_builder_rect]
+#[element_builder_hidden]
+pub struct LogViewBuilder {
+    pub max_lines: usize,
+    pub auto_scroll: bool,
+    pub points_per_line: Option<f32>,
+}
+
+impl Default for LogViewBuilder {
+    fn default() -> Self {
+        Self {
+            max_lines: 10_000,
+            auto_scroll: true,
+            points_per_line: None,
+            class: Default::default(),
+            z_index: Default::default(),
+            rect: Default::default(),
+            manually_hidden: Default::default(),
+            scissor_rect: Default::default(),
+            key: Default::default(),
+        }
+    }
+}
+
+impl LogViewBuilder {
+    /// The maximum number of lines to store. Once this is reached, the oldest
+    /// lines are discarded when new ones are pushed.
+    ///
+    /// By default this is set to `10_000`.
+    pub const fn max_lines(mut self, max_lines: usize) -> Self {
+        self.max_lines = max_lines;
+        self
+    }
+
+    /// Whether or not to automatically scroll to the bottom when new lines
+    /// are pushed.
+    ///
+    /// While this is enabled, scrolling up will pause the auto-scrolling
+    /// until the view is scrolled back to the bottom.
+    ///
+    /// By default this is set to `true`.
+    pub const fn auto_scroll(mut self, auto_scroll: bool) -> Self {
+        self.auto_scroll = auto_scroll;
+        self
+    }
+
+    /// How many points to scroll per line of the scroll wheel.
+    ///
+    /// By default this is set to three times the line height.
+    pub const fn points_per_line(mut self, points_per_line: f32) -> Self {
+        self.points_per_line = Some(points_per_line);
+        self
+    }
+
+    pub fn build<A: Clone + 'static>(self, window_cx: &mut WindowContext<'_, A>) -> LogView {
+        let LogViewBuilder {
+            max_lines,
+            auto_scroll,
+            points_per_line,
+            class,
+            z_index,
+            rect,
+            manually_hidden,
+            scissor_rect,
+            key,
+        } = self;
+
+        let max_lines = max_lines.max(1);
+
+        let shared_state = Rc::new(RefCell::new(SharedState {
+            lines: VecDeque::with_capacity(max_lines.min(1024)),
+            first_line_serial: 0,
+            max_lines,
+            auto_scroll,
+            stuck_to_bottom: true,
+            scroll_offset: 0.0,
+        }));
+
+        let el = ElementBuilder::new(LogViewElement {
+            shared_state: Rc::clone(&shared_state),
+            rows: Vec::new(),
+            points_per_line,
+            drag_state: None,
+        })
+        .builder_values(z_index, scissor_rect, class, window_cx)
+        .key(key)
+        .rect(rect)
+        .hidden(manually_hidden)
+        .flags(
+            ElementFlags::PAINTS
+                | ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS
+                | ElementFlags::LISTENS_TO_POINTER_OUTSIDE_BOUNDS_WHEN_FOCUSED
+                | ElementFlags::LISTENS_TO_FOCUS_CHANGE
+                | ElementFlags::LISTENS_TO_SIZE_CHANGE,
+        )
+        .build(window_cx);
+
+        LogView { el, shared_state }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct DragState {
+    start_pos_y: f32,
+    start_scroll_offset: f32,
+}
+
+/// The layout of the scroll bar and the visible lines.
+struct ViewLayout {
+    line_height: f32,
+    content_rect: Rect,
+    max_scroll_offset: f32,
+    slider_rect: Option<Rect>,
+    slider_to_content_ratio: f32,
+}
+
+struct LogViewElement {
+    shared_state: Rc<RefCell<SharedState>>,
+    /// The buffers of the visible rows. Only the lines that are visible are
+    /// ever shaped.
+    rows: Vec<Row>,
+    points_per_line: Option<f32>,
+    drag_state: Option<DragState>,
+}
+
+impl LogViewElement {
+    fn set_scroll_offset(shared_state: &mut SharedState, offset: f32, max_scroll_offset: f32) {
+        shared_state.scroll_offset = offset.clamp(0.0, max_scroll_offset);
+        shared_state.stuck_to_bottom = shared_state.scroll_offset >= max_scroll_offset - 0.5;
+    }
+}
+
+impl<A: Clone + 'static> Element<A> for LogViewElement {
+    fn on_event(
+        &mut self,
+        event: ElementEvent,
+        cx: &mut ElementContext<'_, A>,
+    ) -> EventCaptureStatus {
+        match event {
+            ElementEvent::CustomStateChanged | ElementEvent::SizeChanged => {
+                cx.request_repaint();
+            }
+            ElementEvent::StyleChanged => {
+                // Force all of the rows to be rebuilt with the new style.
+                self.rows.clear();
+                cx.request_repaint();
+            }
+            ElementEvent::Pointer(PointerEvent::ScrollWheel {
+                position,
+                delta_type,
+                ..
+            }) => {
+                if !cx.rect().contains(position) {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                let style = cx.res.style_system.get::<LogViewStyle>(cx.class());
+                let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+                let layout = shared_state.layout(cx.rect().size, style);
+
+                let points_per_line = self.points_per_line.unwrap_or(layout.line_height * 3.0);
+                let delta = delta_type.points(points_per_line, layout.content_rect.height());
+
+                let prev_offset = shared_state.scroll_offset;
+                Self::set_scroll_offset(
+                    &mut shared_state,
+                    prev_offset + delta.y,
+                    layout.max_scroll_offset,
+                );
+
+                if shared_state.scroll_offset != prev_offset {
+                    cx.request_repaint();
+                }
+
+                return EventCaptureStatus::Captured;
+            }
+            ElementEvent::Pointer(PointerEvent::ButtonJustPressed {
+                position, button, ..
+            }) => {
+                if button != PointerButton::Primary {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                let style = cx.res.style_system.get::<LogViewStyle>(cx.class());
+                let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+                let layout = shared_state.layout(cx.rect().size, style);
+
+                let slider_rect = match layout.slider_rect {
+                    Some(r) => r,
+                    None => return EventCaptureStatus::NotCaptured,
+                };
+
+                let pos = position - cx.rect().origin.to_vector();
+                let bar_rect = Rect::new(
+                    Point::new(slider_rect.min_x(), 0.0),
+                    Size::new(slider_rect.width(), cx.rect().height()),
+                );
+
+                if !bar_rect.contains(pos) {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                if !slider_rect.contains(pos) {
+                    // Jump so that the slider is centered on the pointer.
+                    let offset =
+                        (pos.y - (slider_rect.height() * 0.5)) / layout.slider_to_content_ratio;
+                    Self::set_scroll_offset(&mut shared_state, offset, layout.max_scroll_offset);
+                }
+
+                self.drag_state = Some(DragState {
+                    start_pos_y: pos.y,
+                    start_scroll_offset: shared_state.scroll_offset,
+                });
+
+                cx.steal_temporary_focus();
+                cx.request_repaint();
+
+                return EventCaptureStatus::Captured;
+            }
+            ElementEvent::Pointer(PointerEvent::Moved { position, .. }) => {
+                let drag_state = match self.drag_state {
+                    Some(d) => d,
+                    None => return EventCaptureStatus::NotCaptured,
+                };
+
+                let style = cx.res.style_system.get::<LogViewStyle>(cx.class());
+                let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+                let layout = shared_state.layout(cx.rect().size, style);
+
+                let pos = position - cx.rect().origin.to_vector();
+                let offset = drag_state.start_scroll_offset
+                    + ((pos.y - drag_state.start_pos_y) / layout.slider_to_content_ratio);
+
+                let prev_offset = shared_state.scroll_offset;
+                Self::set_scroll_offset(&mut shared_state, offset, layout.max_scroll_offset);
+
+                if shared_state.scroll_offset != prev_offset {
+                    cx.request_repaint();
+                }
+
+                return EventCaptureStatus::Captured;
+            }
+            ElementEvent::Pointer(PointerEvent::ButtonJustReleased { button, .. }) => {
+                if button == PointerButton::Primary && self.drag_state.take().is_some() {
+                    cx.release_focus();
+                    return EventCaptureStatus::Captured;
+                }
+            }
+            ElementEvent::Focus(false) => {
+                self.drag_state = None;
+            }
+            _ => {}
+        }
+
+        EventCaptureStatus::NotCaptured
+    }
+
+    fn render(&mut self, cx: RenderContext, primitives: &mut PrimitiveGroup) {
+        let style: &LogViewStyle = cx.res.style_system.get(cx.class);
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        let bounds = Rect::from_size(cx.bounds_size);
+
+        if !style.back_quad.is_transparent() {
+            primitives.add(
+                style
+                    .back_quad
+                    .create_primitive_with_opacity(bounds, cx.opacity),
+            );
+        }
+
+        let layout = shared_state.layout(cx.bounds_size, style);
+
+        if shared_state.auto_scroll && shared_state.stuck_to_bottom {
+            shared_state.scroll_offset = layout.max_scroll_offset;
+        } else {
+            shared_state.scroll_offset = shared_state
+                .scroll_offset
+                .clamp(0.0, layout.max_scroll_offset);
+        }
+
+        if layout.line_height <= 0.0 || layout.content_rect.height() <= 0.0 {
+            return;
+        }
+
+        let first_visible = (shared_state.scroll_offset / layout.line_height).floor() as usize;
+        let num_visible_rows =
+            (layout.content_rect.height() / layout.line_height).ceil() as usize + 1;
+
+        while self.rows.len() < num_visible_rows {
+            self.rows.push(Row {
+                line_serial: None,
+                buffer: RcTextBuffer::new(
+                    "",
+                    style.text_properties,
+                    None,
+                    None,
+                    false,
+                    &mut cx.res.font_system,
+                ),
+            });
+        }
+        self.rows.truncate(num_visible_rows);
+
+        let clip_rect = Rect::new(
+            Point::new(0.0, -1.0),
+            Size::new(layout.content_rect.width(), layout.line_height + 2.0),
+        );
+
+        primitives.set_z_index(1);
+
+        for (row_i, row) in self.rows.iter_mut().enumerate() {
+            let line_i = first_visible + row_i;
+            let line = match shared_state.lines.get(line_i) {
+                Some(l) => l,
+                None => break,
+            };
+
+            // Only reshape the row if it now displays a different line.
+            let line_serial = shared_state.first_line_serial + line_i as u64;
+            if row.line_serial != Some(line_serial) {
+                row.line_serial = Some(line_serial);
+                row.buffer.set_text(&line.text, &mut cx.res.font_system);
+            }
+
+            let y = layout.content_rect.min_y() + (line_i as f32 * layout.line_height)
+                - shared_state.scroll_offset;
+
+            primitives.add_text(TextPrimitive::new(
+                row.buffer.clone(),
+                Point::new(layout.content_rect.min_x(), y),
+                apply_opacity(style.level_color(line.level), cx.opacity),
+                Some(clip_rect),
+            ));
+        }
+
+        if let Some(slider_rect) = layout.slider_rect {
+            primitives.set_z_index(2);
+            primitives.add(
+                style
+                    .slider_quad
+                    .create_primitive_with_opacity(slider_rect, cx.opacity),
+            );
+        }
+    }
+}
+
+struct SharedState {
+    lines: VecDeque<LogLine>,
+    /// The serial number of the first line in `lines`, which is incremented
+    /// every time the oldest line is discarded.
+    first_line_serial: u64,
+    max_lines: usize,
+    auto_scroll: bool,
+    stuck_to_bottom: bool,
+    scroll_offset: f32,
+}
+
+impl SharedState {
+    fn push(&mut self, line: LogLine) {
+        if self.lines.len() >= self.max_lines {
+            self.lines.pop_front();
+            self.first_line_serial += 1;
+        }
+
+        self.lines.push_back(line);
+    }
+
+    fn layout(&self, bounds_size: Size, style: &LogViewStyle) -> ViewLayout {
+        let line_height = style.text_properties.metrics.line_height;
+
+        let bounds_content_height =
+            (bounds_size.height - style.padding.top - style.padding.bottom).max(0.0);
+        let content_height = self.lines.len() as f32 * line_height;
+        let max_scroll_offset = (content_height - bounds_content_height).max(0.0);
+
+        let show_slider = max_scroll_offset > 0.0 && bounds_size.height > 0.0;
+        let slider_width = if show_slider { style.slider_width } else { 0.0 };
+
+        let content_rect = Rect::new(
+            Point::new(style.padding.left, style.padding.top),
+            Size::new(
+                (bounds_size.width - style.padding.left - style.padding.right - slider_width)
+                    .max(0.0),
+                bounds_content_height,
+            ),
+        );
+
+        let (slider_rect, slider_to_content_ratio) = if show_slider {
+            let ratio =
+                bounds_size.height / (content_height + style.padding.top + style.padding.bottom);
+
+            (
+                Some(Rect::new(
+                    Point::new(
+                        bounds_size.width - style.slider_width,
+                        self.scroll_offset.clamp(0.0, max_scroll_offset) * ratio,
+                    ),
+                    Size::new(style.slider_width, bounds_size.height * ratio),
+                )),
+                ratio,
+            )
+        } else {
+            (None, 1.0)
+        };
+
+        ViewLayout {
+            line_height,
+            content_rect,
+            max_scroll_offset,
+            slider_rect,
+            slider_to_content_ratio,
+        }
+    }
+}
+
+/// A handle to a [`LogViewElement`], a monospace view of log/console lines
+/// which is optimized for appending lines.
+///
+/// Lines are stored in a ring buffer, and only the lines that are currently
+/// visible are laid out and rendered, so the view stays cheap even with a
+/// large number of lines.
+#[element_handle]
+#[element_handle_class]
+#[element_handle_set_rect]
+pub struct LogView {
+    shared_state: Rc<RefCell<SharedState>>,
+}
+
+impl LogView {
+    pub fn builder() -> LogViewBuilder {
+        LogViewBuilder::default()
+    }
+
+    /// Append a line to the end of the log.
+    ///
+    /// If the maximum number of lines has been reached, then the oldest line
+    /// will be discarded.
+    pub fn push_line(&mut self, text: impl Into<String>, level: Level) {
+        RefCell::borrow_mut(&self.shared_state).push(LogLine::new(text, level));
+        self.el.notify_custom_state_change();
+    }
+
+    /// Append multiple lines to the end of the log.
+    ///
+    /// If the maximum number of lines has been reached, then the oldest lines
+    /// will be discarded.
+    pub fn push_lines(&mut self, lines: impl IntoIterator<Item = LogLine>) {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+        for line in lines {
+            shared_state.push(line);
+        }
+
+        self.el.notify_custom_state_change();
+    }
+
+    /// Remove all lines from the log.
+    pub fn clear(&mut self) {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if !shared_state.lines.is_empty() {
+            let num_lines = shared_state.lines.len() as u64;
+            shared_state.lines.clear();
+            shared_state.first_line_serial += num_lines;
+            shared_state.scroll_offset = 0.0;
+            shared_state.stuck_to_bottom = true;
+
+            self.el.notify_custom_state_change();
+        }
+    }
+
+    /// The number of lines currently stored.
+    pub fn num_lines(&self) -> usize {
+        RefCell::borrow(&self.shared_state).lines.len()
+    }
+
+    /// Set the maximum number of lines to store.
+    ///
+    /// If there are more lines than this, then the oldest lines will be
+    /// discarded.
+    pub fn set_max_lines(&mut self, max_lines: usize) {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+        let max_lines = max_lines.max(1);
+
+        if shared_state.max_lines != max_lines {
+            shared_state.max_lines = max_lines;
+
+            let mut changed = false;
+            while shared_state.lines.len() > max_lines {
+                shared_state.lines.pop_front();
+                shared_state.first_line_serial += 1;
+                changed = true;
+            }
+
+            if changed {
+                self.el.notify_custom_state_change();
+            }
+        }
+    }
+
+    pub fn max_lines(&self) -> usize {
+        RefCell::borrow(&self.shared_state).max_lines
+    }
+
+    /// Set whether or not to automatically scroll to the bottom when new lines
+    /// are pushed.
+    ///
+    /// Enabling this will also scroll to the bottom.
+    ///
+    /// Returns `true` if the value has changed.
+    pub fn set_auto_scroll(&mut self, auto_scroll: bool) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if shared_state.auto_scroll != auto_scroll {
+            shared_state.auto_scroll = auto_scroll;
+            if auto_scroll {
+                shared_state.stuck_to_bottom = true;
+            }
+
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn auto_scroll(&self) -> bool {
+        RefCell::borrow(&self.shared_state).auto_scroll
+    }
+
+    /// Scroll to the bottom of the log.
+    pub fn scroll_to_bottom(&mut self) {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+        shared_state.stuck_to_bottom = true;
+        shared_state.scroll_offset = f32::MAX;
+
+        self.el.notify_custom_state_change();
+    }
+}
@@ -0,0 +1,1130 @@
+use std::cell::{Ref, RefCell};
+use std::ops::Range;
+use std::rc::Rc;
+
+use crate::derive::*;
+use crate::prelude::*;
+use crate::vg::text::glyphon::{cosmic_text::Color, Edit};
+use crate::vg::text::{EditorBorrowStatus, Metrics, RcTextBuffer, TextPrimitive};
+
+/// The style of a [`Markdown`] element
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarkdownStyle {
+    /// The properties of body text. Headings are scaled from these.
+    pub text_properties: TextProperties,
+
+    /// The color of body text.
+    ///
+    /// By default this is set to `color::WHITE`.
+    pub text_color: RGBA8,
+    /// The color of headings.
+    ///
+    /// If this is `None`, then `text_color` will be used.
+    ///
+    /// By default this is set to `None`.
+    pub heading_color: Option<RGBA8>,
+    /// The color of links.
+    ///
+    /// By default this is set to `RGBA8::new(110, 170, 255, 255)`.
+    pub link_color: RGBA8,
+    /// The color of code spans and code blocks.
+    ///
+    /// If this is `None`, then `text_color` will be used.
+    ///
+    /// By default this is set to `None`.
+    pub code_color: Option<RGBA8>,
+
+    /// The font family of code spans and code blocks.
+    ///
+    /// By default this is set to `Family::Monospace`.
+    pub code_family: Family<'static>,
+
+    /// The font size of each heading level (`#` to `######`) relative to
+    /// the font size of body text.
+    ///
+    /// By default this is set to `[1.6, 1.35, 1.15, 1.0, 1.0, 1.0]`.
+    pub heading_scales: [f32; 6],
+
+    /// The vertical spacing between blocks (paragraphs, headings, lists, and
+    /// code blocks) in points.
+    ///
+    /// By default this is set to `10.0`.
+    pub block_spacing: f32,
+    /// The vertical spacing between consecutive list items in points.
+    ///
+    /// By default this is set to `4.0`.
+    pub list_item_spacing: f32,
+    /// The indentation of each level of a list in points.
+    ///
+    /// By default this is set to `20.0`.
+    pub list_indent: f32,
+
+    /// The style of the background rectangle behind code blocks.
+    ///
+    /// By default this is set to a dim transparent gray.
+    pub code_block_back_quad: QuadStyle,
+    /// The padding between the text of a code block and its background
+    /// rectangle.
+    ///
+    /// By default this has all values set to `6.0`.
+    pub code_block_padding: Padding,
+
+    /// The padding between the text and the bounding rectangle.
+    ///
+    /// By default this has all values set to `0.0`.
+    pub padding: Padding,
+}
+
+impl Default for MarkdownStyle {
+    fn default() -> Self {
+        Self {
+            text_properties: TextProperties {
+                shaping: Shaping::Advanced,
+                wrap: Wrap::WordOrGlyph,
+                ..Default::default()
+            },
+            text_color: color::WHITE,
+            heading_color: None,
+            link_color: RGBA8::new(110, 170, 255, 255),
+            code_color: None,
+            code_family: Family::Monospace,
+            heading_scales: [1.6, 1.35, 1.15, 1.0, 1.0, 1.0],
+            block_spacing: 10.0,
+            list_item_spacing: 4.0,
+            list_indent: 20.0,
+            code_block_back_quad: QuadStyle {
+                bg: Background::Solid(RGBA8::new(150, 150, 150, 30)),
+                border: BorderStyle::default(),
+                flags: QuadFlags::SNAP_ALL_TO_NEAREST_PIXEL,
+            },
+            code_block_padding: Padding::new(6.0, 6.0, 6.0, 6.0),
+            padding: Padding::default(),
+        }
+    }
+}
+
+impl ElementStyle for MarkdownStyle {
+    const ID: &'static str = "mkdn";
+
+    fn default_dark_style() -> Self {
+        Self::default()
+    }
+
+    fn default_light_style() -> Self {
+        Self {
+            text_color: color::BLACK,
+            link_color: RGBA8::new(20, 90, 200, 255),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ListMarker {
+    Bullet,
+    Number(u64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BlockKind {
+    Heading(usize),
+    Paragraph,
+    ListItem { depth: usize, marker: ListMarker },
+    CodeBlock,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+struct InlineSpan {
+    text: String,
+    bold: bool,
+    italic: bool,
+    code: bool,
+    link: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct ParsedBlock {
+    kind: BlockKind,
+    spans: Vec<InlineSpan>,
+    links: Vec<String>,
+}
+
+/// Parse the supported subset of Markdown: ATX headings, paragraphs,
+/// bulleted and numbered lists, fenced code blocks, and the inline
+/// `**bold**`, `*italic*`, `` `code` ``, and `[link](url)` styles.
+fn parse(source: &str) -> Vec<ParsedBlock> {
+    let mut blocks = Vec::new();
+
+    let mut paragraph = String::new();
+    let mut code_block: Option<String> = None;
+
+    let flush_paragraph = |paragraph: &mut String, blocks: &mut Vec<ParsedBlock>| {
+        if !paragraph.is_empty() {
+            blocks.push(parse_inline(BlockKind::Paragraph, paragraph));
+            paragraph.clear();
+        }
+    };
+
+    for line in source.lines() {
+        let trimmed = line.trim_start();
+
+        if let Some(code) = &mut code_block {
+            if trimmed.starts_with("```") {
+                let code = code_block.take().unwrap();
+                blocks.push(ParsedBlock {
+                    kind: BlockKind::CodeBlock,
+                    spans: vec![InlineSpan {
+                        text: code,
+                        code: true,
+                        ..Default::default()
+                    }],
+                    links: Vec::new(),
+                });
+            } else {
+                if !code.is_empty() {
+                    code.push('\n');
+                }
+                code.push_str(line);
+            }
+            continue;
+        }
+
+        if trimmed.starts_with("```") {
+            flush_paragraph(&mut paragraph, &mut blocks);
+            code_block = Some(String::new());
+            continue;
+        }
+
+        if trimmed.is_empty() {
+            flush_paragraph(&mut paragraph, &mut blocks);
+            continue;
+        }
+
+        let heading_level = trimmed.chars().take_while(|c| *c == '#').count();
+        if (1..=6).contains(&heading_level) && trimmed[heading_level..].starts_with(' ') {
+            flush_paragraph(&mut paragraph, &mut blocks);
+            blocks.push(parse_inline(
+                BlockKind::Heading(heading_level),
+                trimmed[heading_level..].trim(),
+            ));
+            continue;
+        }
+
+        if let Some((marker, text)) = parse_list_marker(trimmed) {
+            flush_paragraph(&mut paragraph, &mut blocks);
+
+            let indent = line.len() - trimmed.len();
+            blocks.push(parse_inline(
+                BlockKind::ListItem {
+                    depth: indent / 2,
+                    marker,
+                },
+                text,
+            ));
+            continue;
+        }
+
+        // Lazy continuation of the previous list item.
+        if paragraph.is_empty() && line.starts_with(' ') {
+            if let Some(last) = blocks.last_mut() {
+                if let BlockKind::ListItem { .. } = last.kind {
+                    let continued = parse_inline(last.kind, trimmed);
+                    let num_links = last.links.len();
+                    last.spans.push(InlineSpan {
+                        text: String::from(" "),
+                        ..Default::default()
+                    });
+                    last.spans
+                        .extend(continued.spans.into_iter().map(|mut span| {
+                            span.link = span.link.map(|i| i + num_links);
+                            span
+                        }));
+                    last.links.extend(continued.links);
+                    continue;
+                }
+            }
+        }
+
+        if !paragraph.is_empty() {
+            paragraph.push(' ');
+        }
+        paragraph.push_str(trimmed.trim_end());
+    }
+
+    flush_paragraph(&mut paragraph, &mut blocks);
+
+    // An unclosed code block runs until the end of the text.
+    if let Some(code) = code_block {
+        blocks.push(ParsedBlock {
+            kind: BlockKind::CodeBlock,
+            spans: vec![InlineSpan {
+                text: code,
+                code: true,
+                ..Default::default()
+            }],
+            links: Vec::new(),
+        });
+    }
+
+    blocks
+}
+
+fn parse_list_marker(line: &str) -> Option<(ListMarker, &str)> {
+    for bullet in ["- ", "* ", "+ "] {
+        if let Some(text) = line.strip_prefix(bullet) {
+            return Some((ListMarker::Bullet, text.trim_start()));
+        }
+    }
+
+    let num_digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+    if num_digits > 0 && num_digits <= 9 {
+        if let Some(text) = line[num_digits..]
+            .strip_prefix(". ")
+            .or_else(|| line[num_digits..].strip_prefix(") "))
+        {
+            let n = line[..num_digits].parse().ok()?;
+            return Some((ListMarker::Number(n), text.trim_start()));
+        }
+    }
+
+    None
+}
+
+fn parse_inline(kind: BlockKind, text: &str) -> ParsedBlock {
+    let mut spans: Vec<InlineSpan> = Vec::new();
+    let mut links: Vec<String> = Vec::new();
+
+    let mut current = InlineSpan::default();
+
+    let push_current = |current: &mut InlineSpan, spans: &mut Vec<InlineSpan>| {
+        if !current.text.is_empty() {
+            let next = InlineSpan {
+                text: String::new(),
+                ..current.clone()
+            };
+            spans.push(std::mem::replace(current, next));
+        }
+    };
+
+    let mut i = 0;
+    while i < text.len() {
+        let rest = &text[i..];
+        let c = rest.chars().next().unwrap();
+
+        // Escaped characters.
+        if c == '\\' {
+            if let Some(escaped) = rest[1..].chars().next() {
+                if escaped.is_ascii_punctuation() {
+                    current.text.push(escaped);
+                    i += 1 + escaped.len_utf8();
+                    continue;
+                }
+            }
+        }
+
+        // Code spans.
+        if c == '`' {
+            if let Some(end) = rest[1..].find('`') {
+                push_current(&mut current, &mut spans);
+                spans.push(InlineSpan {
+                    text: String::from(&rest[1..1 + end]),
+                    code: true,
+                    ..Default::default()
+                });
+                i += end + 2;
+                continue;
+            }
+        }
+
+        // Links.
+        if c == '[' {
+            if let Some((label, url, len)) = parse_link(rest) {
+                push_current(&mut current, &mut spans);
+                spans.push(InlineSpan {
+                    text: String::from(label),
+                    bold: current.bold,
+                    italic: current.italic,
+                    code: false,
+                    link: Some(links.len()),
+                });
+                links.push(String::from(url));
+                i += len;
+                continue;
+            }
+        }
+
+        // Emphasis. Openers are only treated as such if they are closed
+        // later on, and underscores only count at word boundaries.
+        if c == '*' || c == '_' {
+            let double = rest[1..].starts_with(c);
+            let marker = if double { &rest[..2] } else { &rest[..1] };
+            let is_open = if double { current.bold } else { current.italic };
+
+            let prev = text[..i].chars().last();
+            let next = rest[marker.len()..].chars().next();
+            let at_boundary = c == '*'
+                || if is_open {
+                    !next.is_some_and(|c| c.is_alphanumeric())
+                } else {
+                    !prev.is_some_and(|c| c.is_alphanumeric())
+                };
+
+            let valid = at_boundary
+                && (is_open
+                    || (next.is_some_and(|c| !c.is_whitespace())
+                        && rest[marker.len()..].contains(marker)));
+
+            if valid {
+                push_current(&mut current, &mut spans);
+                if double {
+                    current.bold = !current.bold;
+                } else {
+                    current.italic = !current.italic;
+                }
+                i += marker.len();
+                continue;
+            }
+        }
+
+        current.text.push(c);
+        i += c.len_utf8();
+    }
+
+    push_current(&mut current, &mut spans);
+
+    ParsedBlock { kind, spans, links }
+}
+
+/// Parse `[label](url)` at the start of the text, returning the label, the
+/// url, and the length of the whole link in bytes.
+fn parse_link(text: &str) -> Option<(&str, &str, usize)> {
+    let label_end = text.find("](")?;
+    let label = &text[1..label_end];
+    if label.contains('[') {
+        return None;
+    }
+
+    let url_start = label_end + 2;
+    let url_len = text[url_start..].find(')')?;
+    let url = text[url_start..url_start + url_len].trim();
+
+    Some((label, url, url_start + url_len + 1))
+}
+
+struct BlockInner {
+    kind: BlockKind,
+    buffer: RcTextBuffer,
+    marker: Option<RcTextBuffer>,
+    line_height: f32,
+    /// The byte ranges of the links in the text of the buffer, along with
+    /// their URLs.
+    links: Vec<(Range<usize>, String)>,
+    /// The rectangle of the text relative to the content rectangle.
+    text_rect: Rect,
+}
+
+impl BlockInner {
+    fn new(parsed: &ParsedBlock, style: &MarkdownStyle, font_system: &mut FontSystem) -> Self {
+        let mut properties = style.text_properties;
+
+        let scale = match parsed.kind {
+            BlockKind::Heading(level) => style.heading_scales[level.clamp(1, 6) - 1],
+            _ => 1.0,
+        };
+        properties.metrics = Metrics {
+            font_size: properties.metrics.font_size * scale,
+            line_height: properties.metrics.line_height * scale,
+        };
+        if let BlockKind::Heading(_) = parsed.kind {
+            properties.attrs = properties.attrs.weight(Weight::BOLD);
+        }
+
+        let base_attrs = properties.attrs;
+        let span_attrs = |span: &InlineSpan| -> Attrs<'static> {
+            let mut attrs = base_attrs;
+            if span.bold {
+                attrs = attrs.weight(Weight::BOLD);
+            }
+            if span.italic {
+                attrs = attrs.style(FontStyle::Italic);
+            }
+            if span.code {
+                attrs = attrs.family(style.code_family);
+                if let Some(c) = style.code_color {
+                    attrs = attrs.color(Color::rgba(c.r, c.g, c.b, c.a));
+                }
+            }
+            if span.link.is_some() {
+                let c = style.link_color;
+                attrs = attrs.color(Color::rgba(c.r, c.g, c.b, c.a));
+            }
+            attrs
+        };
+
+        let mut links = Vec::new();
+        let mut offset = 0;
+        for span in parsed.spans.iter() {
+            if let Some(link_i) = span.link {
+                links.push((
+                    offset..offset + span.text.len(),
+                    parsed.links[link_i].clone(),
+                ));
+            }
+            offset += span.text.len();
+        }
+
+        let mut buffer = RcTextBuffer::new("", properties, None, None, true, font_system);
+        buffer.with_editor_mut(
+            |editor, font_system| -> EditorBorrowStatus {
+                editor.with_buffer_mut(|buffer| {
+                    buffer.set_rich_text(
+                        font_system,
+                        parsed
+                            .spans
+                            .iter()
+                            .map(|span| (span.text.as_str(), span_attrs(span))),
+                        base_attrs,
+                        properties.shaping,
+                    );
+                });
+                editor.shape_as_needed(font_system, true);
+
+                EditorBorrowStatus {
+                    text_changed: true,
+                    has_text: offset > 0,
+                }
+            },
+            font_system,
+        );
+
+        let marker = match parsed.kind {
+            BlockKind::ListItem { depth, marker } => {
+                let text = match marker {
+                    ListMarker::Bullet => String::from(if depth % 2 == 0 {
+                        "\u{2022}"
+                    } else {
+                        "\u{25E6}"
+                    }),
+                    ListMarker::Number(n) => format!("{}.", n),
+                };
+
+                Some(RcTextBuffer::new(
+                    &text,
+                    properties,
+                    None,
+                    None,
+                    false,
+                    font_system,
+                ))
+            }
+            _ => None,
+        };
+
+        Self {
+            kind: parsed.kind,
+            buffer,
+            marker,
+            line_height: properties.metrics.line_height,
+            links,
+            text_rect: Rect::default(),
+        }
+    }
+
+    /// Returns the URL of the link at the given point relative to the
+    /// content rectangle.
+    fn link_at(&self, pos: Point) -> Option<&str> {
+        if self.links.is_empty() || !self.text_rect.contains(pos) {
+            return None;
+        }
+
+        let pos = pos - self.text_rect.origin.to_vector();
+
+        let raw_buffer = self.buffer.raw_buffer();
+        for run in raw_buffer.layout_runs() {
+            if pos.y < run.line_top || pos.y >= run.line_top + self.line_height {
+                continue;
+            }
+
+            let glyph = run
+                .glyphs
+                .iter()
+                .find(|g| pos.x >= g.x && pos.x < g.x + g.w)?;
+
+            return self
+                .links
+                .iter()
+                .find(|(range, _)| range.contains(&glyph.start))
+                .map(|(_, url)| url.as_str());
+        }
+
+        None
+    }
+}
+
+/// A reusable struct that lays out Markdown text which can be used by
+/// other elements.
+pub struct MarkdownInner {
+    source: String,
+    parsed: Vec<ParsedBlock>,
+    blocks: Vec<BlockInner>,
+    bounds_width: f32,
+    content_height: f32,
+    needs_layout: bool,
+}
+
+impl MarkdownInner {
+    pub fn new(
+        source: impl Into<String>,
+        style: &MarkdownStyle,
+        bounds_width: f32,
+        font_system: &mut FontSystem,
+    ) -> Self {
+        let source: String = source.into();
+        let parsed = parse(&source);
+
+        let mut new_self = Self {
+            source,
+            parsed,
+            blocks: Vec::new(),
+            bounds_width,
+            content_height: 0.0,
+            needs_layout: true,
+        };
+
+        new_self.sync_new_style(style, font_system);
+
+        new_self
+    }
+
+    /// Returns `true` if the text has changed.
+    pub fn set_text<T: AsRef<str> + Into<String>>(
+        &mut self,
+        text: T,
+        style: &MarkdownStyle,
+        font_system: &mut FontSystem,
+    ) -> bool {
+        if self.source.as_str() == text.as_ref() {
+            return false;
+        }
+
+        self.source = text.into();
+        self.parsed = parse(&self.source);
+        self.sync_new_style(style, font_system);
+
+        true
+    }
+
+    pub fn text(&self) -> &str {
+        &self.source
+    }
+
+    pub fn set_bounds_width(&mut self, bounds_width: f32) {
+        if self.bounds_width != bounds_width {
+            self.bounds_width = bounds_width;
+            self.needs_layout = true;
+        }
+    }
+
+    pub fn bounds_width(&self) -> f32 {
+        self.bounds_width
+    }
+
+    pub fn sync_new_style(&mut self, style: &MarkdownStyle, font_system: &mut FontSystem) {
+        self.blocks = self
+            .parsed
+            .iter()
+            .map(|parsed| BlockInner::new(parsed, style, font_system))
+            .collect();
+        self.needs_layout = true;
+    }
+
+    /// Returns the size of the bounding rectangle which fits all of the
+    /// laid out text (including padding).
+    pub fn desired_size(&mut self, style: &MarkdownStyle, font_system: &mut FontSystem) -> Size {
+        self.layout(style, font_system);
+
+        Size::new(
+            self.bounds_width,
+            self.content_height + style.padding.top + style.padding.bottom,
+        )
+    }
+
+    fn layout(&mut self, style: &MarkdownStyle, font_system: &mut FontSystem) {
+        if !self.needs_layout {
+            return;
+        }
+        self.needs_layout = false;
+
+        let content_width = (self.bounds_width - style.padding.left - style.padding.right).max(0.0);
+
+        let mut y = 0.0;
+        let mut prev_kind: Option<BlockKind> = None;
+
+        for block in self.blocks.iter_mut() {
+            if let Some(prev_kind) = prev_kind {
+                let both_list_items = matches!(prev_kind, BlockKind::ListItem { .. })
+                    && matches!(block.kind, BlockKind::ListItem { .. });
+
+                y += if both_list_items {
+                    style.list_item_spacing
+                } else {
+                    style.block_spacing
+                };
+            }
+            prev_kind = Some(block.kind);
+
+            let (x, extra_padding) = match block.kind {
+                BlockKind::ListItem { depth, .. } => {
+                    (style.list_indent * (depth + 1) as f32, Padding::default())
+                }
+                BlockKind::CodeBlock => (0.0, style.code_block_padding),
+                _ => (0.0, Padding::default()),
+            };
+
+            let text_width =
+                (content_width - x - extra_padding.left - extra_padding.right).max(0.0);
+            block.buffer.set_bounds(Some(text_width), None, font_system);
+
+            let text_size = block.buffer.measure();
+
+            block.text_rect = Rect::new(
+                Point::new(x + extra_padding.left, y + extra_padding.top),
+                Size::new(text_width, text_size.height),
+            );
+
+            y += text_size.height + extra_padding.top + extra_padding.bottom;
+        }
+
+        self.content_height = y;
+    }
+
+    /// Returns the URL of the link at the given point relative to the
+    /// bounds of the element.
+    pub fn link_at(&self, pos: Point, style: &MarkdownStyle) -> Option<&str> {
+        let pos = pos - Vector::new(style.padding.left, style.padding.top);
+
+        self.blocks.iter().find_map(|block| block.link_at(pos))
+    }
+
+    pub fn render(
+        &mut self,
+        bounds: Rect,
+        style: &MarkdownStyle,
+        opacity: f32,
+        font_system: &mut FontSystem,
+        primitives: &mut PrimitiveGroup,
+    ) {
+        self.layout(style, font_system);
+
+        let content_origin = bounds.origin + Vector::new(style.padding.left, style.padding.top);
+        let content_width = (bounds.width() - style.padding.left - style.padding.right).max(0.0);
+
+        for block in self.blocks.iter() {
+            let text_origin = content_origin + block.text_rect.origin.to_vector();
+
+            let text_color = match block.kind {
+                BlockKind::Heading(_) => style.heading_color.unwrap_or(style.text_color),
+                BlockKind::CodeBlock => style.code_color.unwrap_or(style.text_color),
+                _ => style.text_color,
+            };
+
+            if block.kind == BlockKind::CodeBlock {
+                let back_rect = Rect::new(
+                    Point::new(
+                        content_origin.x,
+                        text_origin.y - style.code_block_padding.top,
+                    ),
+                    Size::new(
+                        content_width,
+                        block.text_rect.height()
+                            + style.code_block_padding.top
+                            + style.code_block_padding.bottom,
+                    ),
+                );
+
+                primitives.set_z_index(0);
+                style
+                    .code_block_back_quad
+                    .add_primitives_with_opacity(back_rect, opacity, primitives);
+            }
+
+            primitives.set_z_index(1);
+
+            if let Some(marker) = &block.marker {
+                let marker_width = marker.measure().width;
+
+                primitives.add_text(TextPrimitive::new(
+                    marker.clone(),
+                    Point::new(
+                        text_origin.x - marker_width - style.list_indent * 0.3,
+                        text_origin.y,
+                    ),
+                    apply_opacity(text_color, opacity),
+                    None,
+                ));
+            }
+
+            primitives.add_text(TextPrimitive::new(
+                block.buffer.clone(),
+                text_origin,
+                apply_opacity(text_color, opacity),
+                None,
+            ));
+        }
+    }
+}
+
+#[element_builder]
+#[element_builder_class]
+#[element_builder_rect]
+#[element_builder_hidden]
+#[derive_where::derive_where(Default)]
+pub struct MarkdownBuilder<A: Clone + 'static> {
+    pub link_action: Option<Box<dyn FnMut(String) -> A>>,
+    pub text: String,
+    pub bounds_width: Option<f32>,
+}
+
+impl<A: Clone + 'static> MarkdownBuilder<A> {
+    /// The Markdown source text
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.text = text.into();
+        self
+    }
+
+    /// The width of the element
+    ///
+    /// If this method isn't used, then the width of the bounding rectangle will
+    /// be used instead.
+    pub const fn bounds_width(mut self, width: f32) -> Self {
+        self.bounds_width = Some(width);
+        self
+    }
+
+    /// The action to send when a link is clicked.
+    ///
+    /// If this is not set, then the URL of the link will be opened with the
+    /// default application of the OS (i.e. the web browser).
+    pub fn on_link_clicked<F: FnMut(String) -> A + 'static>(mut self, f: F) -> Self {
+        self.link_action = Some(Box::new(f));
+        self
+    }
+
+    pub fn build(self, window_cx: &mut WindowContext<'_, A>) -> Markdown {
+        let MarkdownBuilder {
+            link_action,
+            text,
+            bounds_width,
+            class,
+            z_index,
+            rect,
+            manually_hidden,
+            scissor_rect,
+            key,
+        } = self;
+
+        let style = window_cx
+            .res
+            .style_system
+            .get::<MarkdownStyle>(window_cx.builder_class(class));
+
+        let bounds_width = bounds_width.unwrap_or(rect.width());
+
+        let shared_state = Rc::new(RefCell::new(SharedState {
+            inner: MarkdownInner::new(text, &style, bounds_width, &mut window_cx.res.font_system),
+        }));
+
+        let el = ElementBuilder::new(MarkdownElement {
+            shared_state: Rc::clone(&shared_state),
+            link_action,
+        })
+        .builder_values(z_index, scissor_rect, class, window_cx)
+        .key(key)
+        .rect(rect)
+        .hidden(manually_hidden)
+        .flags(ElementFlags::PAINTS | ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS)
+        .build(window_cx);
+
+        Markdown { el, shared_state }
+    }
+}
+
+/// An element which renders a subset of Markdown.
+struct MarkdownElement<A: Clone + 'static> {
+    shared_state: Rc<RefCell<SharedState>>,
+    link_action: Option<Box<dyn FnMut(String) -> A>>,
+}
+
+impl<A: Clone + 'static> Element<A> for MarkdownElement<A> {
+    fn on_event(
+        &mut self,
+        event: ElementEvent,
+        cx: &mut ElementContext<'_, A>,
+    ) -> EventCaptureStatus {
+        match event {
+            ElementEvent::CustomStateChanged => {
+                cx.request_repaint();
+            }
+            ElementEvent::StyleChanged => {
+                let style = cx.res.style_system.get::<MarkdownStyle>(cx.class());
+                RefCell::borrow_mut(&self.shared_state)
+                    .inner
+                    .sync_new_style(style, &mut cx.res.font_system);
+                cx.request_repaint();
+            }
+            ElementEvent::Pointer(PointerEvent::Moved { position, .. }) => {
+                let pos = position - cx.rect().origin.to_vector();
+                let style = cx.res.style_system.get::<MarkdownStyle>(cx.class());
+
+                if RefCell::borrow(&self.shared_state)
+                    .inner
+                    .link_at(pos, style)
+                    .is_some()
+                {
+                    cx.cursor_icon = CursorIcon::Pointer;
+                    return EventCaptureStatus::Captured;
+                }
+            }
+            ElementEvent::Pointer(PointerEvent::ButtonJustPressed {
+                position, button, ..
+            }) => {
+                if button != PointerButton::Primary {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                let pos = position - cx.rect().origin.to_vector();
+                let style = cx.res.style_system.get::<MarkdownStyle>(cx.class());
+
+                let url = RefCell::borrow(&self.shared_state)
+                    .inner
+                    .link_at(pos, style)
+                    .map(String::from);
+
+                if let Some(url) = url {
+                    if let Some(action) = self.link_action.as_mut() {
+                        cx.send_action((action)(url)).unwrap();
+                    } else if let Err(e) = open_url(&url) {
+                        log::error!("Failed to open URL {}: {}", url, e);
+                    }
+
+                    return EventCaptureStatus::Captured;
+                }
+            }
+            _ => {}
+        }
+
+        EventCaptureStatus::NotCaptured
+    }
+
+    fn render(&mut self, cx: RenderContext, primitives: &mut PrimitiveGroup) {
+        RefCell::borrow_mut(&self.shared_state).inner.render(
+            Rect::from_size(cx.bounds_size),
+            cx.res.style_system.get(cx.class),
+            cx.opacity,
+            &mut cx.res.font_system,
+            primitives,
+        );
+    }
+}
+
+struct SharedState {
+    inner: MarkdownInner,
+}
+
+/// A handle to a [`MarkdownElement`], an element which renders a subset of
+/// Markdown (headings, paragraphs, lists, code, bold/italic text, and links).
+///
+/// The text is wrapped to the bounds width. To scroll long text (i.e. a
+/// changelog), place this element inside of a [`ScrollArea`] and set the
+/// content size of the scroll area to [`Markdown::desired_size`].
+#[element_handle]
+#[element_handle_class]
+#[element_handle_set_rect]
+pub struct Markdown {
+    shared_state: Rc<RefCell<SharedState>>,
+}
+
+impl Markdown {
+    pub fn builder<A: Clone + 'static>() -> MarkdownBuilder<A> {
+        MarkdownBuilder::default()
+    }
+
+    /// Returns the size of the bounding rectangle which fits all of the
+    /// laid out text at the current bounds width.
+    pub fn desired_size(&self, res: &mut ResourceCtx) -> Size {
+        RefCell::borrow_mut(&self.shared_state)
+            .inner
+            .desired_size(res.style_system.get(self.el.class()), &mut res.font_system)
+    }
+
+    /// Set the Markdown source text.
+    ///
+    /// Returns `true` if the text has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed.
+    /// However, calling this method can be expensive if the text is particuarly
+    /// long, so prefer to call this method sparingly.
+    pub fn set_text<T: AsRef<str> + Into<String>>(
+        &mut self,
+        text: T,
+        res: &mut ResourceCtx,
+    ) -> bool {
+        let changed = RefCell::borrow_mut(&self.shared_state).inner.set_text(
+            text,
+            res.style_system.get(self.el.class()),
+            &mut res.font_system,
+        );
+
+        if changed {
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn text<'a>(&'a self) -> Ref<'a, str> {
+        Ref::map(RefCell::borrow(&self.shared_state), |s| s.inner.text())
+    }
+
+    /// Set the width of the bounding rectangle while correctly wrapping
+    /// the text.
+    ///
+    /// Returns `true` if the bounds width has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn set_bounds_width(&mut self, width: f32) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if shared_state.inner.bounds_width() != width {
+            shared_state.inner.set_bounds_width(width);
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn bounds_width(&self) -> f32 {
+        RefCell::borrow(&self.shared_state).inner.bounds_width()
+    }
+
+    /// Layout out the element (with the top-left corner of the bounds set to `origin`).
+    ///
+    /// Returns `true` if the layout has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn layout(&mut self, origin: Point, res: &mut ResourceCtx) -> bool {
+        let size = self.desired_size(res);
+        self.el.set_rect(Rect::new(origin, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(text: &str) -> InlineSpan {
+        InlineSpan {
+            text: String::from(text),
+            ..Default::default()
+        }
+    }
+
+    fn spans(text: &str) -> Vec<InlineSpan> {
+        parse_inline(BlockKind::Paragraph, text).spans
+    }
+
+    #[test]
+    fn test_parse_inline_emphasis() {
+        assert_eq!(
+            spans("a **b** *c*"),
+            vec![
+                span("a "),
+                InlineSpan {
+                    bold: true,
+                    ..span("b")
+                },
+                span(" "),
+                InlineSpan {
+                    italic: true,
+                    ..span("c")
+                },
+            ]
+        );
+
+        assert_eq!(
+            spans("__bold _both_ bold__"),
+            vec![
+                InlineSpan {
+                    bold: true,
+                    ..span("bold ")
+                },
+                InlineSpan {
+                    bold: true,
+                    italic: true,
+                    ..span("both")
+                },
+                InlineSpan {
+                    bold: true,
+                    ..span(" bold")
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_inline_literal_markers() {
+        // Unclosed markers, markers followed by whitespace, and underscores
+        // inside of words are kept as text.
+        assert_eq!(spans("2 * 3"), vec![span("2 * 3")]);
+        assert_eq!(spans("*open"), vec![span("*open")]);
+        assert_eq!(spans("snake_case_name"), vec![span("snake_case_name")]);
+        assert_eq!(spans(r"\*not italic\*"), vec![span("*not italic*")]);
+    }
+
+    #[test]
+    fn test_parse_inline_code_and_links() {
+        assert_eq!(
+            spans("run `cargo *test*` now"),
+            vec![
+                span("run "),
+                InlineSpan {
+                    code: true,
+                    ..span("cargo *test*")
+                },
+                span(" now"),
+            ]
+        );
+
+        let block = parse_inline(
+            BlockKind::Paragraph,
+            "see [the docs](https://a.b) or **[c](d)**",
+        );
+        assert_eq!(
+            block.spans,
+            vec![
+                span("see "),
+                InlineSpan {
+                    link: Some(0),
+                    ..span("the docs")
+                },
+                span(" or "),
+                InlineSpan {
+                    bold: true,
+                    link: Some(1),
+                    ..span("c")
+                },
+            ]
+        );
+        assert_eq!(
+            block.links,
+            vec![String::from("https://a.b"), String::from("d")]
+        );
+
+        // Not a link.
+        assert_eq!(spans("[a] (b)"), vec![span("[a] (b)")]);
+    }
+}
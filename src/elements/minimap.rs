@@ -0,0 +1,691 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::derive::*;
+use crate::prelude::*;
+use crate::vg::quad::{SolidQuadBuilder, SolidQuadPrimitive};
+
+use super::canvas::Canvas;
+use super::scroll_area::ScrollArea;
+
+/// The style of a [`Minimap`] element
+#[derive(Debug, Clone, PartialEq)]
+pub struct MinimapStyle {
+    pub back_quad: QuadStyle,
+
+    /// The quad showing the visible area of the linked container.
+    pub viewport_quad: QuadStyle,
+    pub viewport_quad_hover: Option<QuadStyle>,
+    pub viewport_quad_dragging: Option<QuadStyle>,
+
+    /// The color of items which don't have a color of their own.
+    pub item_color: RGBA8,
+
+    /// The space between the edges of the element and the overview.
+    ///
+    /// By default this is set to `4.0`.
+    pub padding: f32,
+}
+
+impl Default for MinimapStyle {
+    fn default() -> Self {
+        Self {
+            back_quad: QuadStyle {
+                bg: Background::Solid(RGBA8::new(25, 25, 28, 255)),
+                ..Default::default()
+            },
+            viewport_quad: QuadStyle {
+                bg: Background::Solid(RGBA8::new(255, 255, 255, 20)),
+                border: BorderStyle {
+                    color: RGBA8::new(255, 255, 255, 120),
+                    width: 1.0,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            viewport_quad_hover: Some(QuadStyle {
+                bg: Background::Solid(RGBA8::new(255, 255, 255, 30)),
+                border: BorderStyle {
+                    color: RGBA8::new(255, 255, 255, 170),
+                    width: 1.0,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }),
+            viewport_quad_dragging: Some(QuadStyle {
+                bg: Background::Solid(RGBA8::new(255, 255, 255, 40)),
+                border: BorderStyle {
+                    color: RGBA8::new(255, 255, 255, 220),
+                    width: 1.0,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }),
+            item_color: RGBA8::new(120, 120, 130, 255),
+            padding: 4.0,
+        }
+    }
+}
+
+impl MinimapStyle {
+    fn viewport_quad(&self, state: ViewportState) -> &QuadStyle {
+        match state {
+            ViewportState::Idle => &self.viewport_quad,
+            ViewportState::Hovered => self
+                .viewport_quad_hover
+                .as_ref()
+                .unwrap_or(&self.viewport_quad),
+            ViewportState::Dragging => self
+                .viewport_quad_dragging
+                .as_ref()
+                .or(self.viewport_quad_hover.as_ref())
+                .unwrap_or(&self.viewport_quad),
+        }
+    }
+}
+
+impl ElementStyle for MinimapStyle {
+    const ID: &'static str = "minimap";
+
+    fn default_dark_style() -> Self {
+        Self::default()
+    }
+
+    fn default_light_style() -> Self {
+        Self {
+            back_quad: QuadStyle {
+                bg: Background::Solid(RGBA8::new(235, 235, 238, 255)),
+                ..Default::default()
+            },
+            viewport_quad: QuadStyle {
+                bg: Background::Solid(RGBA8::new(0, 0, 0, 15)),
+                border: BorderStyle {
+                    color: RGBA8::new(0, 0, 0, 110),
+                    width: 1.0,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            viewport_quad_hover: Some(QuadStyle {
+                bg: Background::Solid(RGBA8::new(0, 0, 0, 25)),
+                border: BorderStyle {
+                    color: RGBA8::new(0, 0, 0, 160),
+                    width: 1.0,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }),
+            viewport_quad_dragging: Some(QuadStyle {
+                bg: Background::Solid(RGBA8::new(0, 0, 0, 35)),
+                border: BorderStyle {
+                    color: RGBA8::new(0, 0, 0, 210),
+                    width: 1.0,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }),
+            item_color: RGBA8::new(150, 150, 160, 255),
+            ..Default::default()
+        }
+    }
+}
+
+/// A rectangle shown in the overview of a [`Minimap`] (i.e. a clip in a
+/// timeline or a node in a node graph).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MinimapItem {
+    /// The rectangle of the item in content coordinates.
+    pub rect: Rect,
+    /// The color of the item. If this is `None`, then the color from the
+    /// style is used.
+    pub color: Option<RGBA8>,
+}
+
+impl MinimapItem {
+    pub const fn new(rect: Rect, color: Option<RGBA8>) -> Self {
+        Self { rect, color }
+    }
+}
+
+#[element_builder]
+#[element_builder_class]
+#[element_builder_rect]
+#[element_builder_hidden]
+#[element_builder_disabled]
+pub struct MinimapBuilder<A: Clone + 'static> {
+    pub viewport_moved_action: Option<Box<dyn FnMut(Point) -> A>>,
+    pub content_rect: Rect,
+    pub viewport: Rect,
+    pub items: Vec<MinimapItem>,
+    pub clamp_viewport: bool,
+}
+
+impl<A: Clone + 'static> Default for MinimapBuilder<A> {
+    fn default() -> Self {
+        Self {
+            viewport_moved_action: None,
+            content_rect: Rect::default(),
+            viewport: Rect::default(),
+            items: Vec::new(),
+            clamp_viewport: true,
+            class: None,
+            z_index: None,
+            rect: Rect::default(),
+            manually_hidden: false,
+            disabled: false,
+            scissor_rect: None,
+            key: None,
+        }
+    }
+}
+
+impl<A: Clone + 'static> MinimapBuilder<A> {
+    /// The action sent when the user moves the viewport, with the new origin
+    /// of the viewport in content coordinates.
+    pub fn on_viewport_moved<F: FnMut(Point) -> A + 'static>(mut self, f: F) -> Self {
+        self.viewport_moved_action = Some(Box::new(f));
+        self
+    }
+
+    /// The bounds of the content of the linked container in content
+    /// coordinates.
+    pub const fn content_rect(mut self, rect: Rect) -> Self {
+        self.content_rect = rect;
+        self
+    }
+
+    /// The visible area of the linked container in content coordinates.
+    pub const fn viewport(mut self, viewport: Rect) -> Self {
+        self.viewport = viewport;
+        self
+    }
+
+    pub fn items(mut self, items: Vec<MinimapItem>) -> Self {
+        self.items = items;
+        self
+    }
+
+    /// Whether or not the viewport is kept inside of the content rectangle
+    /// while it is dragged.
+    ///
+    /// By default this is set to `true`.
+    pub const fn clamp_viewport(mut self, clamp: bool) -> Self {
+        self.clamp_viewport = clamp;
+        self
+    }
+
+    pub fn build(self, window_cx: &mut WindowContext<'_, A>) -> Minimap {
+        let MinimapBuilder {
+            viewport_moved_action,
+            content_rect,
+            viewport,
+            items,
+            clamp_viewport,
+            class,
+            z_index,
+            rect,
+            manually_hidden,
+            disabled,
+            scissor_rect,
+            key,
+        } = self;
+
+        let padding = window_cx
+            .res
+            .style_system
+            .get::<MinimapStyle>(window_cx.builder_class(class))
+            .padding;
+
+        let shared_state = Rc::new(RefCell::new(SharedState {
+            content_rect,
+            viewport,
+            items,
+            disabled,
+        }));
+
+        let el = ElementBuilder::new(MinimapElement {
+            shared_state: Rc::clone(&shared_state),
+            viewport_moved_action,
+            clamp_viewport,
+            padding,
+            state: ViewportState::Idle,
+            drag_offset: Vector::zero(),
+        })
+        .builder_values(z_index, scissor_rect, class, window_cx)
+        .key(key)
+        .rect(rect)
+        .hidden(manually_hidden)
+        .flags(
+            ElementFlags::PAINTS
+                | ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS
+                | ElementFlags::LISTENS_TO_POINTER_OUTSIDE_BOUNDS_WHEN_FOCUSED
+                | ElementFlags::LISTENS_TO_FOCUS_CHANGE
+                | ElementFlags::LISTENS_TO_SIZE_CHANGE,
+        )
+        .build(window_cx);
+
+        Minimap { el, shared_state }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ViewportState {
+    Idle,
+    Hovered,
+    Dragging,
+}
+
+/// The mapping from content coordinates to the overview.
+#[derive(Debug, Clone, Copy)]
+struct OverviewTransform {
+    content_origin: Point,
+    offset: Vector,
+    scale: f32,
+}
+
+impl OverviewTransform {
+    fn new(content_rect: Rect, bounds: Rect) -> Option<Self> {
+        if content_rect.width() <= 0.0
+            || content_rect.height() <= 0.0
+            || bounds.width() <= 0.0
+            || bounds.height() <= 0.0
+        {
+            return None;
+        }
+
+        let scale =
+            (bounds.width() / content_rect.width()).min(bounds.height() / content_rect.height());
+        let scaled_size = content_rect.size * scale;
+
+        Some(Self {
+            content_origin: content_rect.origin,
+            offset: bounds.origin.to_vector() + ((bounds.size - scaled_size) * 0.5).to_vector(),
+            scale,
+        })
+    }
+
+    fn to_overview(&self, rect: Rect) -> Rect {
+        Rect::new(
+            ((rect.origin - self.content_origin) * self.scale).to_point() + self.offset,
+            rect.size * self.scale,
+        )
+    }
+
+    fn to_content(&self, pos: Point) -> Point {
+        self.content_origin + ((pos - self.offset).to_vector() / self.scale)
+    }
+}
+
+struct MinimapElement<A: Clone + 'static> {
+    shared_state: Rc<RefCell<SharedState>>,
+    viewport_moved_action: Option<Box<dyn FnMut(Point) -> A>>,
+    clamp_viewport: bool,
+    padding: f32,
+    state: ViewportState,
+    /// The offset from the pointer to the origin of the viewport in content
+    /// coordinates while dragging.
+    drag_offset: Vector,
+}
+
+impl<A: Clone + 'static> MinimapElement<A> {
+    fn transform(&self, shared_state: &SharedState, size: Size) -> Option<OverviewTransform> {
+        OverviewTransform::new(
+            shared_state.overview_rect(),
+            Rect::from_size(size).inflate(-self.padding, -self.padding),
+        )
+    }
+}
+
+impl<A: Clone + 'static> Element<A> for MinimapElement<A> {
+    fn on_event(
+        &mut self,
+        event: ElementEvent,
+        cx: &mut ElementContext<'_, A>,
+    ) -> EventCaptureStatus {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        match event {
+            ElementEvent::CustomStateChanged => {
+                if shared_state.disabled && self.state != ViewportState::Idle {
+                    if self.state == ViewportState::Dragging {
+                        cx.release_focus();
+                    }
+                    self.state = ViewportState::Idle;
+                }
+
+                cx.request_repaint();
+            }
+            ElementEvent::SizeChanged => {
+                cx.request_repaint();
+            }
+            ElementEvent::StyleChanged => {
+                self.padding = cx.res.style_system.get::<MinimapStyle>(cx.class()).padding;
+                cx.request_repaint();
+            }
+            ElementEvent::Pointer(PointerEvent::Moved { position, .. }) => {
+                if shared_state.disabled {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                let Some(transform) = self.transform(&shared_state, cx.rect().size) else {
+                    return EventCaptureStatus::NotCaptured;
+                };
+
+                let pos = position - cx.rect().origin.to_vector();
+
+                if self.state == ViewportState::Dragging {
+                    let origin = transform.to_content(pos) + self.drag_offset;
+
+                    if shared_state.move_viewport(origin, self.clamp_viewport) {
+                        if let Some(action) = self.viewport_moved_action.as_mut() {
+                            cx.send_action((action)(shared_state.viewport.origin))
+                                .unwrap();
+                        }
+
+                        cx.request_repaint();
+                    }
+
+                    return EventCaptureStatus::Captured;
+                }
+
+                let new_state = if transform.to_overview(shared_state.viewport).contains(pos) {
+                    ViewportState::Hovered
+                } else {
+                    ViewportState::Idle
+                };
+
+                if self.state != new_state {
+                    self.state = new_state;
+                    cx.request_repaint();
+                }
+            }
+            ElementEvent::Pointer(PointerEvent::PointerLeft) => {
+                if self.state == ViewportState::Hovered {
+                    self.state = ViewportState::Idle;
+                    cx.request_repaint();
+                }
+            }
+            ElementEvent::Pointer(PointerEvent::ButtonJustPressed {
+                position, button, ..
+            }) => {
+                if shared_state.disabled
+                    || button != PointerButton::Primary
+                    || !cx.rect().contains(position)
+                {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                let Some(transform) = self.transform(&shared_state, cx.rect().size) else {
+                    return EventCaptureStatus::NotCaptured;
+                };
+
+                let pos = position - cx.rect().origin.to_vector();
+                let content_pos = transform.to_content(pos);
+
+                if !transform.to_overview(shared_state.viewport).contains(pos) {
+                    // Center the viewport on the pressed position.
+                    let origin = content_pos - (shared_state.viewport.size * 0.5).to_vector();
+
+                    if shared_state.move_viewport(origin, self.clamp_viewport) {
+                        if let Some(action) = self.viewport_moved_action.as_mut() {
+                            cx.send_action((action)(shared_state.viewport.origin))
+                                .unwrap();
+                        }
+                    }
+                }
+
+                self.drag_offset = shared_state.viewport.origin - content_pos;
+                self.state = ViewportState::Dragging;
+
+                cx.steal_temporary_focus();
+                cx.request_repaint();
+
+                return EventCaptureStatus::Captured;
+            }
+            ElementEvent::Pointer(PointerEvent::ButtonJustReleased {
+                position, button, ..
+            }) => {
+                if self.state != ViewportState::Dragging || button != PointerButton::Primary {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                let pos = position - cx.rect().origin.to_vector();
+                let hovered = self
+                    .transform(&shared_state, cx.rect().size)
+                    .is_some_and(|t| t.to_overview(shared_state.viewport).contains(pos));
+
+                self.state = if hovered {
+                    ViewportState::Hovered
+                } else {
+                    ViewportState::Idle
+                };
+
+                cx.release_focus();
+                cx.request_repaint();
+
+                return EventCaptureStatus::Captured;
+            }
+            ElementEvent::Focus(false) => {
+                if self.state == ViewportState::Dragging {
+                    self.state = ViewportState::Idle;
+                    cx.request_repaint();
+                }
+            }
+            _ => {}
+        }
+
+        EventCaptureStatus::NotCaptured
+    }
+
+    fn render(&mut self, cx: RenderContext, primitives: &mut PrimitiveGroup) {
+        let style: &MinimapStyle = cx.res.style_system.get(cx.class);
+        let shared_state = RefCell::borrow(&self.shared_state);
+
+        let bounds = Rect::from_size(cx.bounds_size);
+
+        if !style.back_quad.is_transparent() {
+            primitives.add(
+                style
+                    .back_quad
+                    .create_primitive_with_opacity(bounds, cx.opacity),
+            );
+        }
+
+        let Some(transform) = self.transform(&shared_state, cx.bounds_size) else {
+            return;
+        };
+
+        let items: Vec<SolidQuadPrimitive> = shared_state
+            .items
+            .iter()
+            .map(|item| {
+                let rect = transform.to_overview(item.rect);
+
+                // Keep tiny items visible.
+                SolidQuadBuilder::new(Size::new(rect.width().max(1.0), rect.height().max(1.0)))
+                    .bg_color(apply_opacity(
+                        item.color.unwrap_or(style.item_color),
+                        cx.opacity,
+                    ))
+                    .position(rect.origin)
+                    .into()
+            })
+            .collect();
+
+        primitives.set_z_index(1);
+        primitives.add_solid_quad_batch(items);
+
+        primitives.set_z_index(2);
+        primitives.add(
+            style
+                .viewport_quad(self.state)
+                .create_primitive_with_opacity(
+                    transform.to_overview(shared_state.viewport),
+                    cx.opacity,
+                ),
+        );
+    }
+}
+
+struct SharedState {
+    content_rect: Rect,
+    viewport: Rect,
+    items: Vec<MinimapItem>,
+    disabled: bool,
+}
+
+impl SharedState {
+    /// The area shown in the overview, which always includes the viewport.
+    fn overview_rect(&self) -> Rect {
+        if self.viewport.is_empty() {
+            self.content_rect
+        } else {
+            self.content_rect.union(&self.viewport)
+        }
+    }
+
+    /// Returns `true` if the viewport has moved.
+    fn move_viewport(&mut self, mut origin: Point, clamp: bool) -> bool {
+        if clamp {
+            let max = self.content_rect.max() - self.viewport.size.to_vector();
+
+            origin.x = origin.x.min(max.x).max(self.content_rect.min_x());
+            origin.y = origin.y.min(max.y).max(self.content_rect.min_y());
+        }
+
+        if self.viewport.origin != origin {
+            self.viewport.origin = origin;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// A scaled overview of a scrollable or zoomable container (i.e. a long
+/// timeline or a large node graph) with a draggable rectangle showing the
+/// visible area of the container.
+///
+/// The minimap is kept in sync with the container by the application: update
+/// the minimap whenever the container is scrolled or zoomed (i.e. with
+/// [`Minimap::follow_scroll_area`] or [`Minimap::follow_canvas`]), and move
+/// the container when the minimap sends its `on_viewport_moved` action:
+///
+/// ```ignore
+/// // For a `ScrollArea`:
+/// scroll_area.set_scroll_offset(origin.to_vector());
+/// // For a `Canvas`:
+/// canvas.set_pan(origin.to_vector() * canvas.zoom());
+/// ```
+#[element_handle]
+#[element_handle_class]
+#[element_handle_set_rect]
+#[element_handle_layout_aligned]
+pub struct Minimap {
+    shared_state: Rc<RefCell<SharedState>>,
+}
+
+impl Minimap {
+    pub fn builder<A: Clone + 'static>() -> MinimapBuilder<A> {
+        MinimapBuilder::default()
+    }
+
+    /// Set the bounds of the content of the linked container in content
+    /// coordinates.
+    ///
+    /// Returns `true` if the rectangle has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn set_content_rect(&mut self, rect: Rect) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if shared_state.content_rect != rect {
+            shared_state.content_rect = rect;
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn content_rect(&self) -> Rect {
+        RefCell::borrow(&self.shared_state).content_rect
+    }
+
+    /// Set the visible area of the linked container in content coordinates.
+    ///
+    /// Returns `true` if the viewport has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn set_viewport(&mut self, viewport: Rect) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if shared_state.viewport != viewport {
+            shared_state.viewport = viewport;
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn viewport(&self) -> Rect {
+        RefCell::borrow(&self.shared_state).viewport
+    }
+
+    /// Set the rectangles shown in the overview.
+    pub fn set_items(&mut self, items: Vec<MinimapItem>) {
+        RefCell::borrow_mut(&self.shared_state).items = items;
+        self.el.notify_custom_state_change();
+    }
+
+    /// Update the content rectangle and the viewport from the given scroll
+    /// area.
+    ///
+    /// Returns `true` if either has changed.
+    pub fn follow_scroll_area(&mut self, scroll_area: &ScrollArea) -> bool {
+        let content_changed = self.set_content_rect(Rect::from_size(scroll_area.content_size()));
+        let viewport_changed = self.set_viewport(Rect::new(
+            scroll_area.scroll_offset().to_point(),
+            scroll_area.size(),
+        ));
+
+        content_changed || viewport_changed
+    }
+
+    /// Update the viewport from the given canvas. The content rectangle must
+    /// be set separately since a canvas has no bounds.
+    ///
+    /// Returns `true` if the viewport has changed.
+    pub fn follow_canvas(&mut self, canvas: &Canvas) -> bool {
+        self.set_viewport(Rect::new(
+            canvas.view_to_content(Point::zero()),
+            canvas.size() / canvas.zoom(),
+        ))
+    }
+
+    pub fn disabled(&self) -> bool {
+        RefCell::borrow(&self.shared_state).disabled
+    }
+
+    /// Set the disabled state of this element. While disabled, the viewport
+    /// cannot be dragged.
+    ///
+    /// Returns `true` if the disabled state has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the state has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn set_disabled(&mut self, disabled: bool) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if shared_state.disabled != disabled {
+            shared_state.disabled = disabled;
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+}
@@ -1,19 +1,49 @@
+pub mod ab_compare;
+pub mod badge;
 pub mod button;
+pub mod canvas;
+pub mod carousel;
 pub mod click_area;
+pub mod custom;
+pub mod drag_value;
 pub mod drop_down_menu;
+pub mod editable_label;
+#[cfg(feature = "custom-shaders")]
+pub mod external_texture;
+pub mod file_browser;
+pub mod hyperlink;
 pub mod label;
+pub mod lazy_region;
+pub mod log_view;
+pub mod markdown;
+pub mod minimap;
+pub mod notification_panel;
 pub mod paragraph;
 pub mod quad;
 pub mod radio_button;
+#[cfg(feature = "svg-icons")]
+pub mod rating;
 pub mod resize_handle;
 pub mod scroll_area;
+pub mod scrubber;
+pub mod segment_display;
 pub mod separator;
+pub mod status_bar;
+pub mod stepper;
 pub mod switch;
 pub mod tab;
 pub mod text_input;
+pub mod title_bar;
 pub mod toggle_button;
 pub mod tooltip;
+pub mod tuner;
 pub mod virtual_slider;
 
 #[cfg(feature = "svg-icons")]
 pub mod icon;
+#[cfg(feature = "tessellation")]
+pub mod node_graph;
+#[cfg(feature = "tessellation")]
+pub mod plot;
+#[cfg(feature = "custom-shaders")]
+pub mod shader_quad;
@@ -0,0 +1,1442 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use rootvg::tessellation::{
+    fill::FillStyle,
+    path::PathBuilder,
+    stroke::{LineCap, LineDash, LineJoin, Stroke},
+    Tessellator,
+};
+
+use crate::derive::*;
+use crate::prelude::*;
+use crate::vg::text::{Metrics, TextPrimitive};
+
+use super::plot::TextCache;
+
+/// The number of line segments a cable is approximated with.
+const CABLE_SEGMENTS: usize = 24;
+/// The minimum horizontal distance between the end points of a cable and the
+/// control points of its bezier curve.
+const CABLE_MIN_HANDLE_LENGTH: f32 = 30.0;
+
+/// The style of a [`NodeGraph`] element
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeGraphStyle {
+    /// The properties of the node titles and the port labels.
+    pub text_properties: TextProperties,
+    pub title_color: RGBA8,
+    pub label_color: RGBA8,
+
+    pub back_quad: QuadStyle,
+    pub node_quad: QuadStyle,
+    pub node_quad_selected: QuadStyle,
+    /// The quad drawn behind the title of each node.
+    pub title_quad: QuadStyle,
+    /// The quad of the selection marquee.
+    pub marquee_quad: QuadStyle,
+
+    /// The colors of the ports and cables, indexed by the port type (wrapping
+    /// around if there are more port types than colors).
+    pub port_colors: Vec<RGBA8>,
+    pub cable_width: f32,
+
+    pub node_width: f32,
+    pub title_height: f32,
+    /// The height of each row of ports.
+    pub port_row_height: f32,
+    pub port_radius: f32,
+    /// The horizontal distance between the edge of a node and its port
+    /// labels.
+    pub label_padding: f32,
+    /// The space below the last row of ports.
+    pub bottom_padding: f32,
+}
+
+impl Default for NodeGraphStyle {
+    fn default() -> Self {
+        Self {
+            text_properties: TextProperties {
+                metrics: Metrics {
+                    font_size: 12.0,
+                    line_height: 16.0,
+                },
+                wrap: Wrap::None,
+                ..Default::default()
+            },
+            title_color: RGBA8::new(230, 230, 230, 255),
+            label_color: RGBA8::new(180, 180, 180, 255),
+            back_quad: QuadStyle::TRANSPARENT,
+            node_quad: QuadStyle {
+                bg: Background::Solid(RGBA8::new(45, 45, 50, 255)),
+                border: BorderStyle {
+                    color: RGBA8::new(70, 70, 76, 255),
+                    width: 1.0,
+                    radius: radius(4.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            node_quad_selected: QuadStyle {
+                bg: Background::Solid(RGBA8::new(45, 45, 50, 255)),
+                border: BorderStyle {
+                    color: RGBA8::new(80, 160, 255, 255),
+                    width: 2.0,
+                    radius: radius(4.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            title_quad: QuadStyle {
+                bg: Background::Solid(RGBA8::new(62, 62, 70, 255)),
+                border: BorderStyle {
+                    radius: radius_top(4.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            marquee_quad: QuadStyle {
+                bg: Background::Solid(RGBA8::new(80, 160, 255, 40)),
+                border: BorderStyle {
+                    color: RGBA8::new(80, 160, 255, 160),
+                    width: 1.0,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            port_colors: vec![
+                RGBA8::new(230, 200, 80, 255),
+                RGBA8::new(80, 160, 255, 255),
+                RGBA8::new(100, 210, 120, 255),
+                RGBA8::new(230, 90, 110, 255),
+                RGBA8::new(180, 130, 240, 255),
+            ],
+            cable_width: 2.0,
+            node_width: 140.0,
+            title_height: 24.0,
+            port_row_height: 20.0,
+            port_radius: 5.0,
+            label_padding: 10.0,
+            bottom_padding: 6.0,
+        }
+    }
+}
+
+impl NodeGraphStyle {
+    /// The color of ports and cables of the given type.
+    pub fn port_color(&self, port_type: u32) -> RGBA8 {
+        if self.port_colors.is_empty() {
+            color::WHITE
+        } else {
+            self.port_colors[port_type as usize % self.port_colors.len()]
+        }
+    }
+
+    fn metrics(&self) -> NodeMetrics {
+        NodeMetrics {
+            node_width: self.node_width,
+            title_height: self.title_height,
+            port_row_height: self.port_row_height,
+            port_radius: self.port_radius,
+            bottom_padding: self.bottom_padding,
+        }
+    }
+}
+
+impl ElementStyle for NodeGraphStyle {
+    const ID: &'static str = "nodegraph";
+
+    fn default_dark_style() -> Self {
+        Self::default()
+    }
+
+    fn default_light_style() -> Self {
+        Self {
+            title_color: RGBA8::new(30, 30, 30, 255),
+            label_color: RGBA8::new(70, 70, 70, 255),
+            node_quad: QuadStyle {
+                bg: Background::Solid(RGBA8::new(245, 245, 245, 255)),
+                border: BorderStyle {
+                    color: RGBA8::new(190, 190, 195, 255),
+                    width: 1.0,
+                    radius: radius(4.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            node_quad_selected: QuadStyle {
+                bg: Background::Solid(RGBA8::new(245, 245, 245, 255)),
+                border: BorderStyle {
+                    color: RGBA8::new(40, 120, 230, 255),
+                    width: 2.0,
+                    radius: radius(4.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            title_quad: QuadStyle {
+                bg: Background::Solid(RGBA8::new(222, 222, 228, 255)),
+                border: BorderStyle {
+                    radius: radius_top(4.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+}
+
+/// The ID of a node in a [`NodeGraph`], assigned by the user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeID(pub u64);
+
+/// An input or output port of a node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodePort {
+    pub label: String,
+    /// Only ports of the same type can be connected to each other (i.e.
+    /// audio, note, or control signals).
+    pub port_type: u32,
+}
+
+impl NodePort {
+    pub fn new(label: impl Into<String>, port_type: u32) -> Self {
+        Self {
+            label: label.into(),
+            port_type,
+        }
+    }
+}
+
+/// A node in a [`NodeGraph`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphNode {
+    pub id: NodeID,
+    pub title: String,
+    /// The position of the top-left corner of the node in graph coordinates.
+    pub position: Point,
+    pub inputs: Vec<NodePort>,
+    pub outputs: Vec<NodePort>,
+}
+
+impl GraphNode {
+    pub fn new(id: NodeID, title: impl Into<String>, position: Point) -> Self {
+        Self {
+            id,
+            title: title.into(),
+            position,
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+        }
+    }
+
+    /// Add an input port.
+    pub fn input(mut self, label: impl Into<String>, port_type: u32) -> Self {
+        self.inputs.push(NodePort::new(label, port_type));
+        self
+    }
+
+    /// Add an output port.
+    pub fn output(mut self, label: impl Into<String>, port_type: u32) -> Self {
+        self.outputs.push(NodePort::new(label, port_type));
+        self
+    }
+}
+
+/// A reference to a port of a node, where `port` is the index into the
+/// inputs or the outputs of the node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PortRef {
+    pub node: NodeID,
+    pub port: usize,
+}
+
+impl PortRef {
+    pub const fn new(node: NodeID, port: usize) -> Self {
+        Self { node, port }
+    }
+}
+
+/// A cable from an output port to an input port.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cable {
+    pub from: PortRef,
+    pub to: PortRef,
+}
+
+impl Cable {
+    pub const fn new(from: PortRef, to: PortRef) -> Self {
+        Self { from, to }
+    }
+}
+
+/// An edit to a [`NodeGraph`] made by the user.
+///
+/// The edit has already been applied to the element when the action is sent.
+#[derive(Debug, Clone, PartialEq)]
+pub enum NodeGraphEdit {
+    /// The given nodes were dragged by `delta` (in graph coordinates).
+    NodesMoved { nodes: Vec<NodeID>, delta: Vector },
+    /// A cable was connected.
+    Connected(Cable),
+    /// A cable was disconnected, either because it was dragged off of its
+    /// input port or because another cable was connected to the same input
+    /// port.
+    Disconnected(Cable),
+    /// The selected nodes have changed.
+    SelectionChanged(Vec<NodeID>),
+}
+
+#[element_builder]
+#[element_builder_class]
+#[element_builder_rect]
+#[element_builder_hidden]
+#[element_builder_disabled]
+pub struct NodeGraphBuilder<A: Clone + 'static> {
+    pub edit_action: Option<Box<dyn FnMut(NodeGraphEdit) -> A>>,
+    pub nodes: Vec<GraphNode>,
+    pub cables: Vec<Cable>,
+    pub snap_distance: f32,
+}
+
+impl<A: Clone + 'static> Default for NodeGraphBuilder<A> {
+    fn default() -> Self {
+        Self {
+            edit_action: None,
+            nodes: Vec::new(),
+            cables: Vec::new(),
+            snap_distance: 16.0,
+            class: None,
+            z_index: None,
+            rect: Rect::default(),
+            manually_hidden: false,
+            disabled: false,
+            scissor_rect: None,
+            key: None,
+        }
+    }
+}
+
+impl<A: Clone + 'static> NodeGraphBuilder<A> {
+    /// The action sent when the user edits the graph.
+    pub fn on_edit<F: FnMut(NodeGraphEdit) -> A + 'static>(mut self, f: F) -> Self {
+        self.edit_action = Some(Box::new(f));
+        self
+    }
+
+    pub fn nodes(mut self, nodes: Vec<GraphNode>) -> Self {
+        self.nodes = nodes;
+        self
+    }
+
+    /// The initial cables. Cables between ports which don't exist or which
+    /// have different types are ignored.
+    pub fn cables(mut self, cables: Vec<Cable>) -> Self {
+        self.cables = cables;
+        self
+    }
+
+    /// The distance in points from a compatible port at which a dragged
+    /// cable snaps to it.
+    ///
+    /// By default this is set to `16.0`.
+    pub const fn snap_distance(mut self, distance: f32) -> Self {
+        self.snap_distance = distance;
+        self
+    }
+
+    pub fn build(self, window_cx: &mut WindowContext<'_, A>) -> NodeGraph {
+        let NodeGraphBuilder {
+            edit_action,
+            nodes,
+            cables,
+            snap_distance,
+            class,
+            z_index,
+            rect,
+            manually_hidden,
+            disabled,
+            scissor_rect,
+            key,
+        } = self;
+
+        let metrics = window_cx
+            .res
+            .style_system
+            .get::<NodeGraphStyle>(window_cx.builder_class(class))
+            .metrics();
+
+        let mut shared_state = SharedState {
+            nodes,
+            cables: Vec::new(),
+            selection: Vec::new(),
+            pan: Vector::zero(),
+            disabled,
+        };
+        for cable in cables {
+            shared_state.connect(cable);
+        }
+
+        let shared_state = Rc::new(RefCell::new(shared_state));
+
+        let el = ElementBuilder::new(NodeGraphElement {
+            shared_state: Rc::clone(&shared_state),
+            edit_action,
+            snap_distance,
+            metrics,
+            gesture: Gesture::None,
+            text_cache: TextCache::default(),
+        })
+        .builder_values(z_index, scissor_rect, class, window_cx)
+        .key(key)
+        .rect(rect)
+        .hidden(manually_hidden)
+        .flags(
+            ElementFlags::PAINTS
+                | ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS
+                | ElementFlags::LISTENS_TO_POINTER_OUTSIDE_BOUNDS_WHEN_FOCUSED
+                | ElementFlags::LISTENS_TO_FOCUS_CHANGE
+                | ElementFlags::LISTENS_TO_SIZE_CHANGE,
+        )
+        .build(window_cx);
+
+        NodeGraph { el, shared_state }
+    }
+}
+
+/// The layout values of the style which are needed to handle events.
+#[derive(Debug, Clone, Copy)]
+struct NodeMetrics {
+    node_width: f32,
+    title_height: f32,
+    port_row_height: f32,
+    port_radius: f32,
+    bottom_padding: f32,
+}
+
+impl NodeMetrics {
+    /// The rectangle of the node relative to the origin of the element.
+    fn node_rect(&self, node: &GraphNode, pan: Vector) -> Rect {
+        let rows = node.inputs.len().max(node.outputs.len()) as f32;
+
+        Rect::new(
+            node.position - pan,
+            Size::new(
+                self.node_width,
+                self.title_height + (rows * self.port_row_height) + self.bottom_padding,
+            ),
+        )
+    }
+
+    /// The center of the port relative to the origin of the element.
+    fn port_pos(&self, node: &GraphNode, pan: Vector, index: usize, is_output: bool) -> Point {
+        let rect = self.node_rect(node, pan);
+
+        Point::new(
+            if is_output {
+                rect.max_x()
+            } else {
+                rect.min_x()
+            },
+            rect.min_y() + self.title_height + ((index as f32 + 0.5) * self.port_row_height),
+        )
+    }
+}
+
+/// One end of a cable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct PortEnd {
+    port: PortRef,
+    is_output: bool,
+}
+
+enum Gesture {
+    None,
+    MovingNodes {
+        start_pos: Point,
+        delta: Vector,
+        start_positions: Vec<(NodeID, Point)>,
+    },
+    DraggingCable {
+        from: PortEnd,
+        /// The position of the pointer relative to the origin of the element.
+        pointer: Point,
+        snapped: Option<PortEnd>,
+        /// The cable which was dragged off of its input port.
+        detached: Option<Cable>,
+    },
+    Marquee {
+        start: Point,
+        current: Point,
+        /// The nodes which stay selected regardless of the marquee.
+        base_selection: Vec<NodeID>,
+        initial_selection: Vec<NodeID>,
+    },
+    Panning {
+        start_pos: Point,
+        start_pan: Vector,
+    },
+}
+
+struct NodeGraphElement<A: Clone + 'static> {
+    shared_state: Rc<RefCell<SharedState>>,
+    edit_action: Option<Box<dyn FnMut(NodeGraphEdit) -> A>>,
+    snap_distance: f32,
+    metrics: NodeMetrics,
+    gesture: Gesture,
+    text_cache: TextCache,
+}
+
+fn send_edit<A: Clone + 'static>(
+    edit_action: &mut Option<Box<dyn FnMut(NodeGraphEdit) -> A>>,
+    edit: NodeGraphEdit,
+    cx: &mut ElementContext<'_, A>,
+) {
+    if let Some(action) = edit_action.as_mut() {
+        cx.send_action((action)(edit)).unwrap();
+    }
+}
+
+impl<A: Clone + 'static> NodeGraphElement<A> {
+    fn on_primary_pressed(
+        &mut self,
+        pos: Point,
+        modifiers: Modifiers,
+        cx: &mut ElementContext<'_, A>,
+    ) {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if let Some(end) = shared_state.port_at(&self.metrics, pos, self.metrics.port_radius * 2.0)
+        {
+            let (from, detached) = if end.is_output {
+                (end, None)
+            } else if let Some(i) = shared_state.cable_into(end.port) {
+                // Pick up the cable which is plugged into this input.
+                let cable = shared_state.cables.remove(i);
+                (
+                    PortEnd {
+                        port: cable.from,
+                        is_output: true,
+                    },
+                    Some(cable),
+                )
+            } else {
+                (end, None)
+            };
+
+            self.gesture = Gesture::DraggingCable {
+                from,
+                pointer: pos,
+                snapped: None,
+                detached,
+            };
+        } else if let Some(i) = shared_state.node_at(&self.metrics, pos) {
+            // Bring the node to the front.
+            let node = shared_state.nodes.remove(i);
+            let id = node.id;
+            shared_state.nodes.push(node);
+
+            let shift = modifiers.contains(Modifiers::SHIFT);
+            let was_selected = shared_state.selection.contains(&id);
+
+            if shift {
+                if was_selected {
+                    shared_state.selection.retain(|n| *n != id);
+                } else {
+                    shared_state.selection.push(id);
+                }
+            } else if !was_selected {
+                shared_state.selection = vec![id];
+            }
+
+            if shift || !was_selected {
+                send_edit(
+                    &mut self.edit_action,
+                    NodeGraphEdit::SelectionChanged(shared_state.selection.clone()),
+                    cx,
+                );
+            }
+
+            if shared_state.selection.contains(&id) {
+                let start_positions = shared_state
+                    .nodes
+                    .iter()
+                    .filter(|n| shared_state.selection.contains(&n.id))
+                    .map(|n| (n.id, n.position))
+                    .collect();
+
+                self.gesture = Gesture::MovingNodes {
+                    start_pos: pos,
+                    delta: Vector::zero(),
+                    start_positions,
+                };
+            }
+        } else {
+            let initial_selection = shared_state.selection.clone();
+            let base_selection = if modifiers.contains(Modifiers::SHIFT) {
+                initial_selection.clone()
+            } else {
+                Vec::new()
+            };
+
+            shared_state.selection = base_selection.clone();
+
+            self.gesture = Gesture::Marquee {
+                start: pos,
+                current: pos,
+                base_selection,
+                initial_selection,
+            };
+        }
+    }
+
+    fn on_pointer_moved(&mut self, pos: Point) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        match &mut self.gesture {
+            Gesture::None => return false,
+            Gesture::MovingNodes {
+                start_pos,
+                delta,
+                start_positions,
+            } => {
+                *delta = pos - *start_pos;
+
+                for (id, start_position) in start_positions.iter() {
+                    if let Some(node) = shared_state.nodes.iter_mut().find(|n| n.id == *id) {
+                        node.position = *start_position + *delta;
+                    }
+                }
+            }
+            Gesture::DraggingCable {
+                from,
+                pointer,
+                snapped,
+                ..
+            } => {
+                *pointer = pos;
+                *snapped = shared_state.snap_target(&self.metrics, *from, pos, self.snap_distance);
+            }
+            Gesture::Marquee {
+                start,
+                current,
+                base_selection,
+                ..
+            } => {
+                *current = pos;
+
+                let marquee = rect_from_points(*start, *current);
+                let pan = shared_state.pan;
+
+                let mut selection = base_selection.clone();
+                for node in shared_state.nodes.iter() {
+                    if !selection.contains(&node.id)
+                        && self.metrics.node_rect(node, pan).intersects(&marquee)
+                    {
+                        selection.push(node.id);
+                    }
+                }
+
+                shared_state.selection = selection;
+            }
+            Gesture::Panning {
+                start_pos,
+                start_pan,
+            } => {
+                shared_state.pan = *start_pan - (pos - *start_pos);
+            }
+        }
+
+        true
+    }
+
+    fn finish_gesture(&mut self, cx: &mut ElementContext<'_, A>) {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        match std::mem::replace(&mut self.gesture, Gesture::None) {
+            Gesture::None | Gesture::Panning { .. } => {}
+            Gesture::MovingNodes {
+                delta,
+                start_positions,
+                ..
+            } => {
+                if delta != Vector::zero() {
+                    send_edit(
+                        &mut self.edit_action,
+                        NodeGraphEdit::NodesMoved {
+                            nodes: start_positions.iter().map(|(id, _)| *id).collect(),
+                            delta,
+                        },
+                        cx,
+                    );
+                }
+            }
+            Gesture::DraggingCable {
+                from,
+                snapped,
+                detached,
+                ..
+            } => {
+                let new_cable = snapped.map(|to| cable_between(from, to));
+
+                if let (Some(detached), Some(new_cable)) = (detached, new_cable) {
+                    if detached == new_cable {
+                        // The cable was plugged back into the same port.
+                        shared_state.cables.push(detached);
+                        return;
+                    }
+                }
+
+                if let Some(cable) = detached {
+                    send_edit(
+                        &mut self.edit_action,
+                        NodeGraphEdit::Disconnected(cable),
+                        cx,
+                    );
+                }
+
+                if let Some(cable) = new_cable {
+                    if let Some(replaced) = shared_state.connect(cable) {
+                        send_edit(
+                            &mut self.edit_action,
+                            NodeGraphEdit::Disconnected(replaced),
+                            cx,
+                        );
+                    }
+
+                    send_edit(&mut self.edit_action, NodeGraphEdit::Connected(cable), cx);
+                }
+            }
+            Gesture::Marquee {
+                initial_selection, ..
+            } => {
+                if shared_state.selection != initial_selection {
+                    send_edit(
+                        &mut self.edit_action,
+                        NodeGraphEdit::SelectionChanged(shared_state.selection.clone()),
+                        cx,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Revert any changes made by the current gesture.
+    fn cancel_gesture(&mut self) {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        match std::mem::replace(&mut self.gesture, Gesture::None) {
+            Gesture::None => {}
+            Gesture::MovingNodes {
+                start_positions, ..
+            } => {
+                for (id, start_position) in start_positions {
+                    if let Some(node) = shared_state.nodes.iter_mut().find(|n| n.id == id) {
+                        node.position = start_position;
+                    }
+                }
+            }
+            Gesture::DraggingCable { detached, .. } => {
+                if let Some(cable) = detached {
+                    shared_state.cables.push(cable);
+                }
+            }
+            Gesture::Marquee {
+                initial_selection, ..
+            } => {
+                shared_state.selection = initial_selection;
+            }
+            Gesture::Panning { start_pan, .. } => {
+                shared_state.pan = start_pan;
+            }
+        }
+    }
+}
+
+impl<A: Clone + 'static> Element<A> for NodeGraphElement<A> {
+    fn on_event(
+        &mut self,
+        event: ElementEvent,
+        cx: &mut ElementContext<'_, A>,
+    ) -> EventCaptureStatus {
+        match event {
+            ElementEvent::CustomStateChanged => {
+                if RefCell::borrow(&self.shared_state).disabled
+                    && !matches!(self.gesture, Gesture::None)
+                {
+                    self.cancel_gesture();
+                    cx.release_focus();
+                }
+
+                cx.request_repaint();
+            }
+            ElementEvent::SizeChanged => {
+                cx.request_repaint();
+            }
+            ElementEvent::StyleChanged => {
+                self.metrics = cx
+                    .res
+                    .style_system
+                    .get::<NodeGraphStyle>(cx.class())
+                    .metrics();
+
+                // Force all of the labels to be rebuilt with the new style.
+                self.text_cache = TextCache::default();
+                cx.request_repaint();
+            }
+            ElementEvent::Pointer(PointerEvent::Moved { position, .. }) => {
+                let pos = position - cx.rect().origin.to_vector();
+
+                if self.on_pointer_moved(pos) {
+                    cx.request_repaint();
+                    return EventCaptureStatus::Captured;
+                }
+            }
+            ElementEvent::Pointer(PointerEvent::ButtonJustPressed {
+                position,
+                button,
+                modifiers,
+                ..
+            }) => {
+                if RefCell::borrow(&self.shared_state).disabled
+                    || !matches!(self.gesture, Gesture::None)
+                    || !cx.rect().contains(position)
+                {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                let pos = position - cx.rect().origin.to_vector();
+
+                match button {
+                    PointerButton::Primary => self.on_primary_pressed(pos, modifiers, cx),
+                    PointerButton::Auxiliary => {
+                        self.gesture = Gesture::Panning {
+                            start_pos: pos,
+                            start_pan: RefCell::borrow(&self.shared_state).pan,
+                        };
+                    }
+                    _ => return EventCaptureStatus::NotCaptured,
+                }
+
+                cx.steal_temporary_focus();
+                cx.request_repaint();
+
+                return EventCaptureStatus::Captured;
+            }
+            ElementEvent::Pointer(PointerEvent::ButtonJustReleased { button, .. }) => {
+                let gesture_button = match &self.gesture {
+                    Gesture::None => return EventCaptureStatus::NotCaptured,
+                    Gesture::Panning { .. } => PointerButton::Auxiliary,
+                    _ => PointerButton::Primary,
+                };
+
+                if button != gesture_button {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                self.finish_gesture(cx);
+
+                cx.release_focus();
+                cx.request_repaint();
+
+                return EventCaptureStatus::Captured;
+            }
+            ElementEvent::Focus(false) => {
+                if !matches!(self.gesture, Gesture::None) {
+                    self.cancel_gesture();
+                    cx.request_repaint();
+                }
+            }
+            _ => {}
+        }
+
+        EventCaptureStatus::NotCaptured
+    }
+
+    fn render(&mut self, cx: RenderContext, primitives: &mut PrimitiveGroup) {
+        let style: &NodeGraphStyle = cx.res.style_system.get(cx.class);
+        let shared_state = RefCell::borrow(&self.shared_state);
+
+        let metrics = style.metrics();
+        let pan = shared_state.pan;
+        let bounds = Rect::from_size(cx.bounds_size);
+
+        if !style.back_quad.is_transparent() {
+            primitives.add(
+                style
+                    .back_quad
+                    .create_primitive_with_opacity(bounds, cx.opacity),
+            );
+        }
+
+        // Cables
+        primitives.set_z_index(1);
+
+        for cable in shared_state.cables.iter() {
+            let (Some(from), Some(to)) = (
+                shared_state.port_pos(&metrics, cable.from, true),
+                shared_state.port_pos(&metrics, cable.to, false),
+            ) else {
+                continue;
+            };
+
+            let port_type = shared_state
+                .port(cable.from, true)
+                .map(|p| p.port_type)
+                .unwrap_or(0);
+
+            add_cable(
+                primitives,
+                from,
+                to,
+                style.port_color(port_type),
+                style.cable_width,
+            );
+        }
+
+        if let Gesture::DraggingCable {
+            from,
+            pointer,
+            snapped,
+            ..
+        } = &self.gesture
+        {
+            if let Some(start) = shared_state.port_pos(&metrics, from.port, from.is_output) {
+                let end = snapped
+                    .and_then(|to| shared_state.port_pos(&metrics, to.port, to.is_output))
+                    .unwrap_or(*pointer);
+
+                let port_type = shared_state
+                    .port(from.port, from.is_output)
+                    .map(|p| p.port_type)
+                    .unwrap_or(0);
+
+                // Cables always curve from the output to the input.
+                let (start, end) = if from.is_output {
+                    (start, end)
+                } else {
+                    (end, start)
+                };
+
+                add_cable(
+                    primitives,
+                    start,
+                    end,
+                    style.port_color(port_type),
+                    style.cable_width,
+                );
+            }
+        }
+
+        // Nodes
+        self.text_cache.begin();
+
+        let line_height = style.text_properties.metrics.line_height;
+        let mut z_index: ZIndex = 2;
+
+        for node in shared_state.nodes.iter() {
+            let rect = metrics.node_rect(node, pan);
+            if !rect.intersects(&bounds) {
+                continue;
+            }
+
+            primitives.set_z_index(z_index);
+
+            let node_quad = if shared_state.selection.contains(&node.id) {
+                &style.node_quad_selected
+            } else {
+                &style.node_quad
+            };
+            primitives.add(node_quad.create_primitive_with_opacity(rect, cx.opacity));
+
+            primitives.add(style.title_quad.create_primitive_with_opacity(
+                Rect::new(rect.origin, Size::new(rect.width(), metrics.title_height)),
+                cx.opacity,
+            ));
+
+            let mut texts = Vec::with_capacity(1 + node.inputs.len() + node.outputs.len());
+
+            let buffer =
+                self.text_cache
+                    .get(&node.title, style.text_properties, &mut cx.res.font_system);
+            texts.push(TextPrimitive::new(
+                buffer,
+                Point::new(
+                    rect.min_x() + style.label_padding,
+                    rect.min_y() + ((metrics.title_height - line_height) * 0.5),
+                ),
+                apply_opacity(style.title_color, cx.opacity),
+                None,
+            ));
+
+            for (ports, is_output) in [(&node.inputs, false), (&node.outputs, true)] {
+                for (i, port) in ports.iter().enumerate() {
+                    let center = metrics.port_pos(node, pan, i, is_output);
+
+                    primitives.add(
+                        QuadStyle {
+                            bg: Background::Solid(style.port_color(port.port_type)),
+                            border: BorderStyle {
+                                radius: Radius::CIRCLE,
+                                ..Default::default()
+                            },
+                            ..Default::default()
+                        }
+                        .create_primitive_with_opacity(
+                            crate::layout::centered_rect(
+                                center,
+                                Size::new(metrics.port_radius * 2.0, metrics.port_radius * 2.0),
+                            ),
+                            cx.opacity,
+                        ),
+                    );
+
+                    if port.label.is_empty() {
+                        continue;
+                    }
+
+                    let buffer = self.text_cache.get(
+                        &port.label,
+                        style.text_properties,
+                        &mut cx.res.font_system,
+                    );
+                    let width = buffer.measure().width;
+
+                    let x = if is_output {
+                        rect.max_x() - style.label_padding - width
+                    } else {
+                        rect.min_x() + style.label_padding
+                    };
+
+                    texts.push(TextPrimitive::new(
+                        buffer,
+                        Point::new(x, center.y - (line_height * 0.5)),
+                        apply_opacity(style.label_color, cx.opacity),
+                        None,
+                    ));
+                }
+            }
+
+            primitives.set_z_index(z_index.saturating_add(1));
+            primitives.add_text_batch(texts);
+
+            z_index = z_index.saturating_add(2);
+        }
+
+        self.text_cache.end();
+
+        if let Gesture::Marquee { start, current, .. } = &self.gesture {
+            primitives.set_z_index(z_index);
+            primitives.add(
+                style
+                    .marquee_quad
+                    .create_primitive_with_opacity(rect_from_points(*start, *current), cx.opacity),
+            );
+        }
+    }
+}
+
+struct SharedState {
+    /// The nodes in the order they are drawn in (the last node is drawn on
+    /// top).
+    nodes: Vec<GraphNode>,
+    cables: Vec<Cable>,
+    selection: Vec<NodeID>,
+    pan: Vector,
+    disabled: bool,
+}
+
+impl SharedState {
+    fn node(&self, id: NodeID) -> Option<&GraphNode> {
+        self.nodes.iter().find(|n| n.id == id)
+    }
+
+    fn port(&self, port: PortRef, is_output: bool) -> Option<&NodePort> {
+        let node = self.node(port.node)?;
+
+        if is_output {
+            node.outputs.get(port.port)
+        } else {
+            node.inputs.get(port.port)
+        }
+    }
+
+    fn port_pos(&self, metrics: &NodeMetrics, port: PortRef, is_output: bool) -> Option<Point> {
+        let node = self.node(port.node)?;
+        let num_ports = if is_output {
+            node.outputs.len()
+        } else {
+            node.inputs.len()
+        };
+
+        (port.port < num_ports).then(|| metrics.port_pos(node, self.pan, port.port, is_output))
+    }
+
+    /// The index of the topmost node at the given position.
+    fn node_at(&self, metrics: &NodeMetrics, pos: Point) -> Option<usize> {
+        self.nodes
+            .iter()
+            .rposition(|n| metrics.node_rect(n, self.pan).contains(pos))
+    }
+
+    /// The closest port within `max_distance` of the given position which
+    /// satisfies `filter`.
+    fn closest_port(
+        &self,
+        metrics: &NodeMetrics,
+        pos: Point,
+        max_distance: f32,
+        mut filter: impl FnMut(PortEnd, &NodePort) -> bool,
+    ) -> Option<PortEnd> {
+        let mut closest: Option<(PortEnd, f32)> = None;
+
+        for node in self.nodes.iter() {
+            for (ports, is_output) in [(&node.inputs, false), (&node.outputs, true)] {
+                for (i, port) in ports.iter().enumerate() {
+                    let end = PortEnd {
+                        port: PortRef::new(node.id, i),
+                        is_output,
+                    };
+
+                    let distance = (metrics.port_pos(node, self.pan, i, is_output) - pos).length();
+
+                    if distance <= max_distance
+                        && closest.map(|(_, d)| distance < d).unwrap_or(true)
+                        && filter(end, port)
+                    {
+                        closest = Some((end, distance));
+                    }
+                }
+            }
+        }
+
+        closest.map(|(end, _)| end)
+    }
+
+    fn port_at(&self, metrics: &NodeMetrics, pos: Point, max_distance: f32) -> Option<PortEnd> {
+        self.closest_port(metrics, pos, max_distance, |_, _| true)
+    }
+
+    /// The closest port a cable dragged from `from` can be connected to.
+    fn snap_target(
+        &self,
+        metrics: &NodeMetrics,
+        from: PortEnd,
+        pos: Point,
+        snap_distance: f32,
+    ) -> Option<PortEnd> {
+        let port_type = self.port(from.port, from.is_output)?.port_type;
+
+        self.closest_port(metrics, pos, snap_distance, |end, port| {
+            end.is_output != from.is_output
+                && end.port.node != from.port.node
+                && port.port_type == port_type
+        })
+    }
+
+    /// The index of the cable plugged into the given input port.
+    fn cable_into(&self, input: PortRef) -> Option<usize> {
+        self.cables.iter().position(|c| c.to == input)
+    }
+
+    fn is_valid(&self, cable: &Cable) -> bool {
+        if cable.from.node == cable.to.node {
+            return false;
+        }
+
+        match (self.port(cable.from, true), self.port(cable.to, false)) {
+            (Some(from), Some(to)) => from.port_type == to.port_type,
+            _ => false,
+        }
+    }
+
+    /// Add the cable if it is valid, replacing any cable plugged into the
+    /// same input port.
+    ///
+    /// Returns the replaced cable.
+    fn connect(&mut self, cable: Cable) -> Option<Cable> {
+        if !self.is_valid(&cable) || self.cables.contains(&cable) {
+            return None;
+        }
+
+        let replaced = self.cable_into(cable.to).map(|i| self.cables.remove(i));
+        self.cables.push(cable);
+
+        replaced
+    }
+
+    /// Remove cables whose ports no longer exist and selected nodes which no
+    /// longer exist.
+    fn remove_dangling(&mut self) {
+        let cables = std::mem::take(&mut self.cables);
+        self.cables = cables.into_iter().filter(|c| self.is_valid(c)).collect();
+
+        let selection = std::mem::take(&mut self.selection);
+        self.selection = selection
+            .into_iter()
+            .filter(|id| self.node(*id).is_some())
+            .collect();
+    }
+}
+
+fn cable_between(a: PortEnd, b: PortEnd) -> Cable {
+    if a.is_output {
+        Cable::new(a.port, b.port)
+    } else {
+        Cable::new(b.port, a.port)
+    }
+}
+
+fn rect_from_points(a: Point, b: Point) -> Rect {
+    Rect::new(
+        Point::new(a.x.min(b.x), a.y.min(b.y)),
+        Size::new((a.x - b.x).abs(), (a.y - b.y).abs()),
+    )
+}
+
+fn cubic_bezier(p0: Point, p1: Point, p2: Point, p3: Point, t: f32) -> Point {
+    let u = 1.0 - t;
+    let a = u * u * u;
+    let b = 3.0 * u * u * t;
+    let c = 3.0 * u * t * t;
+    let d = t * t * t;
+
+    Point::new(
+        (a * p0.x) + (b * p1.x) + (c * p2.x) + (d * p3.x),
+        (a * p0.y) + (b * p1.y) + (c * p2.y) + (d * p3.y),
+    )
+}
+
+/// Add a cable which curves horizontally out of `from` and into `to`.
+fn add_cable(primitives: &mut PrimitiveGroup, from: Point, to: Point, color: RGBA8, width: f32) {
+    let handle_length = ((to.x - from.x).abs() * 0.5).max(CABLE_MIN_HANDLE_LENGTH);
+    let c1 = Point::new(from.x + handle_length, from.y);
+    let c2 = Point::new(to.x - handle_length, to.y);
+
+    let mut path = PathBuilder::new().move_to(from);
+    for i in 1..=CABLE_SEGMENTS {
+        let t = i as f32 / CABLE_SEGMENTS as f32;
+        path = path.line_to(cubic_bezier(from, c1, c2, to, t));
+    }
+    let path = path.build();
+
+    if let Some(mesh) = Tessellator::new()
+        .stroke(
+            &path,
+            Stroke {
+                style: FillStyle::Solid(color.into()),
+                width,
+                line_cap: LineCap::Round,
+                line_join: LineJoin::Round,
+                line_dash: LineDash::default(),
+            },
+        )
+        .into_primitive()
+    {
+        primitives.add_mesh(mesh);
+    }
+}
+
+/// An editor for a graph of nodes with typed input and output ports, i.e.
+/// for modular-synth-style routing.
+///
+/// Nodes can be dragged with the primary pointer button, and cables are
+/// connected by dragging from an output port to an input port of the same
+/// type (or the other way around). Dragging a cable off of an input port
+/// disconnects it. Dragging over empty space selects the nodes inside the
+/// marquee, and the view can be panned by dragging with the middle pointer
+/// button. Every edit is sent as a [`NodeGraphEdit`] action.
+///
+/// This requires the `tessellation` feature.
+#[element_handle]
+#[element_handle_class]
+#[element_handle_set_rect]
+#[element_handle_layout_aligned]
+pub struct NodeGraph {
+    shared_state: Rc<RefCell<SharedState>>,
+}
+
+impl NodeGraph {
+    pub fn builder<A: Clone + 'static>() -> NodeGraphBuilder<A> {
+        NodeGraphBuilder::default()
+    }
+
+    /// Replace all of the nodes. Cables and selected nodes which refer to
+    /// nodes or ports which no longer exist are removed.
+    pub fn set_nodes(&mut self, nodes: Vec<GraphNode>) {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        shared_state.nodes = nodes;
+        shared_state.remove_dangling();
+
+        self.el.notify_custom_state_change();
+    }
+
+    /// Add a node, replacing any existing node with the same ID.
+    pub fn add_node(&mut self, node: GraphNode) {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if let Some(existing) = shared_state.nodes.iter_mut().find(|n| n.id == node.id) {
+            *existing = node;
+            shared_state.remove_dangling();
+        } else {
+            shared_state.nodes.push(node);
+        }
+
+        self.el.notify_custom_state_change();
+    }
+
+    /// Remove a node along with all of its cables.
+    ///
+    /// Returns `true` if the node existed.
+    pub fn remove_node(&mut self, id: NodeID) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        let Some(i) = shared_state.nodes.iter().position(|n| n.id == id) else {
+            return false;
+        };
+
+        shared_state.nodes.remove(i);
+        shared_state.remove_dangling();
+
+        self.el.notify_custom_state_change();
+        true
+    }
+
+    /// Move a node to the given position in graph coordinates.
+    ///
+    /// Returns `true` if the position has changed.
+    pub fn set_node_position(&mut self, id: NodeID, position: Point) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        let Some(node) = shared_state.nodes.iter_mut().find(|n| n.id == id) else {
+            return false;
+        };
+
+        if node.position != position {
+            node.position = position;
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// The node with the given ID.
+    pub fn node(&self, id: NodeID) -> Option<GraphNode> {
+        RefCell::borrow(&self.shared_state).node(id).cloned()
+    }
+
+    /// All nodes in the order they are drawn in (the last node is drawn on
+    /// top).
+    pub fn nodes(&self) -> Vec<GraphNode> {
+        RefCell::borrow(&self.shared_state).nodes.clone()
+    }
+
+    /// Replace all of the cables. Cables between ports which don't exist or
+    /// which have different types are ignored.
+    pub fn set_cables(&mut self, cables: Vec<Cable>) {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        shared_state.cables.clear();
+        for cable in cables {
+            shared_state.connect(cable);
+        }
+
+        self.el.notify_custom_state_change();
+    }
+
+    /// Connect a cable, replacing any cable plugged into the same input port.
+    ///
+    /// Returns `true` if the cable was added (it is not added if it already
+    /// exists, or if its ports don't exist or have different types).
+    pub fn connect(&mut self, cable: Cable) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if shared_state.is_valid(&cable) && !shared_state.cables.contains(&cable) {
+            shared_state.connect(cable);
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns `true` if the cable existed.
+    pub fn disconnect(&mut self, cable: Cable) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        let Some(i) = shared_state.cables.iter().position(|c| *c == cable) else {
+            return false;
+        };
+
+        shared_state.cables.remove(i);
+        self.el.notify_custom_state_change();
+        true
+    }
+
+    pub fn cables(&self) -> Vec<Cable> {
+        RefCell::borrow(&self.shared_state).cables.clone()
+    }
+
+    pub fn selection(&self) -> Vec<NodeID> {
+        RefCell::borrow(&self.shared_state).selection.clone()
+    }
+
+    /// Set the selected nodes. IDs of nodes which don't exist are ignored.
+    pub fn set_selection(&mut self, selection: Vec<NodeID>) {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        shared_state.selection = selection;
+        shared_state.remove_dangling();
+
+        self.el.notify_custom_state_change();
+    }
+
+    /// The offset of the view in graph coordinates.
+    pub fn pan(&self) -> Vector {
+        RefCell::borrow(&self.shared_state).pan
+    }
+
+    /// Set the offset of the view in graph coordinates.
+    ///
+    /// Returns `true` if the offset has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn set_pan(&mut self, pan: Vector) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if shared_state.pan != pan {
+            shared_state.pan = pan;
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn disabled(&self) -> bool {
+        RefCell::borrow(&self.shared_state).disabled
+    }
+
+    /// Set the disabled state of this element. While disabled, the graph
+    /// cannot be edited by the user.
+    ///
+    /// Returns `true` if the disabled state has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the state has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn set_disabled(&mut self, disabled: bool) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if shared_state.disabled != disabled {
+            shared_state.disabled = disabled;
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+}
@@ -0,0 +1,551 @@
+use web_time::Instant;
+
+use crate::prelude::*;
+
+/// The ID of a notification in a [`NotificationPanel`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NotificationID(u64);
+
+/// The classes of the elements of a [`NotificationPanel`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct NotificationPanelClasses {
+    /// The class of the [`QuadElement`] behind the panel.
+    pub panel: ClassID,
+    /// The class of the [`Label`] with the title of the panel.
+    pub header: ClassID,
+    /// The class of the [`Label`] of each message.
+    pub message: ClassID,
+    /// The class of the [`Label`] of each timestamp.
+    pub timestamp: ClassID,
+    /// The class of the [`Button`] which dismisses a single notification.
+    pub dismiss_button: ClassID,
+    /// The class of the [`Button`] which dismisses all notifications.
+    pub clear_button: ClassID,
+    /// The class of the [`ToggleButton`] which opens and closes the panel.
+    pub toggle_button: ClassID,
+    /// The class of the [`Label`] with the number of unread notifications.
+    pub badge: ClassID,
+}
+
+/// Format the age of a notification, i.e. "just now" or "5 min ago".
+fn format_age(seconds: u64) -> String {
+    if seconds < 60 {
+        String::from("just now")
+    } else if seconds < 60 * 60 {
+        format!("{} min ago", seconds / 60)
+    } else if seconds < 60 * 60 * 24 {
+        format!("{} h ago", seconds / (60 * 60))
+    } else {
+        format!("{} d ago", seconds / (60 * 60 * 24))
+    }
+}
+
+struct NotificationRow {
+    id: NotificationID,
+    created: Instant,
+    unread: bool,
+    age_text: String,
+    message: Label,
+    timestamp: Label,
+    dismiss_button: Button,
+}
+
+impl NotificationRow {
+    fn set_hidden(&mut self, hidden: bool) {
+        self.message.set_hidden(hidden);
+        self.timestamp.set_hidden(hidden);
+        self.dismiss_button.set_hidden(hidden);
+    }
+}
+
+/// A panel which slides in from the right edge of the window and collects
+/// past notifications with their timestamps, with a toggle button that
+/// shows the number of unread notifications in a badge.
+///
+/// Opening and dismissing works the same way as the other composites: the
+/// toggle, dismiss, and clear buttons send actions, and the application
+/// responds by calling [`NotificationPanel::set_open`],
+/// [`NotificationPanel::dismiss`], or [`NotificationPanel::clear`].
+///
+/// The slide animation and the timestamps are advanced by
+/// [`NotificationPanel::tick`], which should be called from
+/// [`Application::on_tick`].
+///
+/// ```ignore
+/// let mut panel = NotificationPanel::new(
+///     |open| MyAction::SetNotificationsOpen(open),
+///     |id| MyAction::DismissNotification(id),
+///     MyAction::ClearNotifications,
+///     NotificationPanelClasses::default(),
+///     cx,
+/// );
+/// panel.dock(cx.logical_size());
+/// panel.layout_toggle(Rect::new(Point::new(8.0, 8.0), Size::new(110.0, 24.0)));
+///
+/// panel.push("Preset saved", cx);
+/// ```
+pub struct NotificationPanel<A: Clone + 'static> {
+    core: WidgetCore,
+    background: QuadElement,
+    header: Label,
+    clear_button: Button,
+    toggle_button: ToggleButton,
+    badge: Label,
+    rows: Vec<NotificationRow>,
+    on_dismiss: Box<dyn FnMut(NotificationID) -> A>,
+    classes: NotificationPanelClasses,
+    z_index: ZIndex,
+    scissor_rect: ScissorRectID,
+
+    width: f32,
+    header_height: f32,
+    row_height: f32,
+    padding: f32,
+    badge_size: f32,
+    max_notifications: usize,
+    slide_speed: f32,
+
+    window_size: Size,
+    open: bool,
+    open_amount: f32,
+    secs_since_timestamp_update: f64,
+    next_id: u64,
+}
+
+impl<A: Clone + 'static> NotificationPanel<A> {
+    /// Create a new notification panel.
+    ///
+    /// * `on_toggle` - The action to send when the user presses the toggle
+    /// button (called with the requested open state)
+    /// * `on_dismiss` - The action to send when the user dismisses a single
+    /// notification
+    /// * `on_clear_all` - The action to send when the user presses the clear
+    /// button
+    /// * `classes` - The classes of the elements of the panel
+    ///
+    /// The panel is drawn with the z index and scissor rect of `window_cx`
+    /// at the time this is called. Use a z index above the rest of the
+    /// window so the panel slides in over the content.
+    pub fn new<FT>(
+        on_toggle: FT,
+        on_dismiss: impl FnMut(NotificationID) -> A + 'static,
+        on_clear_all: A,
+        classes: NotificationPanelClasses,
+        window_cx: &mut WindowContext<'_, A>,
+    ) -> Self
+    where
+        FT: FnMut(bool) -> A + 'static,
+    {
+        let z_index = window_cx.z_index();
+        let scissor_rect = window_cx.scissor_rect();
+
+        let notifications_text = window_cx
+            .res
+            .locale
+            .builtin(BuiltinString::Notifications)
+            .into_owned();
+        let clear_all_text = window_cx
+            .res
+            .locale
+            .builtin(BuiltinString::ClearAllNotifications);
+
+        let background = QuadElement::builder()
+            .class(classes.panel)
+            .hidden(true)
+            .build(window_cx);
+        let header = Label::builder()
+            .text(notifications_text.as_str())
+            .class(classes.header)
+            .hidden(true)
+            .build(window_cx);
+        let clear_button = Button::builder()
+            .text(clear_all_text)
+            .on_select(on_clear_all)
+            .class(classes.clear_button)
+            .hidden(true)
+            .build(window_cx);
+        let toggle_button = ToggleButton::builder()
+            .text(notifications_text)
+            .on_toggled(on_toggle)
+            .class(classes.toggle_button)
+            .build(window_cx);
+        let badge = Label::builder()
+            .class(classes.badge)
+            .hidden(true)
+            .build(window_cx);
+
+        let mut new_self = Self {
+            core: WidgetCore::default().hidden(true),
+            background,
+            header,
+            clear_button,
+            toggle_button,
+            badge,
+            rows: Vec::new(),
+            on_dismiss: Box::new(on_dismiss),
+            classes,
+            z_index,
+            scissor_rect,
+            width: 300.0,
+            header_height: 36.0,
+            row_height: 48.0,
+            padding: 8.0,
+            badge_size: 16.0,
+            max_notifications: 50,
+            slide_speed: 14.0,
+            window_size: window_cx.logical_size(),
+            open: false,
+            open_amount: 0.0,
+            secs_since_timestamp_update: 0.0,
+            next_id: 0,
+        };
+
+        new_self.apply_slide();
+        new_self
+    }
+
+    /// The width of the panel in points.
+    ///
+    /// By default this is set to `300.0`.
+    pub fn with_width(mut self, width: f32) -> Self {
+        self.width = width;
+        self.apply_slide();
+        self
+    }
+
+    /// The height of each notification in points.
+    ///
+    /// By default this is set to `48.0`.
+    pub fn with_row_height(mut self, row_height: f32) -> Self {
+        self.row_height = row_height;
+        self.apply_slide();
+        self.sync_rows();
+        self
+    }
+
+    /// The maximum number of notifications to keep. When this is exceeded,
+    /// the oldest notifications are removed.
+    ///
+    /// By default this is set to `50`.
+    pub fn with_max_notifications(mut self, max: usize) -> Self {
+        self.max_notifications = max;
+        self.truncate();
+        self
+    }
+
+    /// How quickly the panel slides in and out. Higher values are faster.
+    /// Set this to `0.0` to disable the animation.
+    ///
+    /// By default this is set to `14.0`.
+    pub fn with_slide_speed(mut self, speed: f32) -> Self {
+        self.slide_speed = speed;
+        self
+    }
+
+    /// Add a notification to the top of the panel.
+    ///
+    /// If the panel is closed, the notification is counted as unread.
+    pub fn push(
+        &mut self,
+        message: impl Into<String>,
+        window_cx: &mut WindowContext<'_, A>,
+    ) -> NotificationID {
+        let id = NotificationID(self.next_id);
+        self.next_id += 1;
+
+        window_cx.with_z_index_and_scissor_rect(self.z_index, self.scissor_rect, |window_cx| {
+            let age_text = format_age(0);
+
+            let row = NotificationRow {
+                id,
+                created: Instant::now(),
+                unread: !self.open,
+                message: Label::builder()
+                    .text(message)
+                    .class(self.classes.message)
+                    .hidden(true)
+                    .build(window_cx),
+                timestamp: Label::builder()
+                    .text(age_text.as_str())
+                    .class(self.classes.timestamp)
+                    .hidden(true)
+                    .build(window_cx),
+                dismiss_button: Button::builder()
+                    .text("\u{00D7}")
+                    .on_select((self.on_dismiss)(id))
+                    .class(self.classes.dismiss_button)
+                    .hidden(true)
+                    .build(window_cx),
+                age_text,
+            };
+
+            self.rows.insert(0, row);
+        });
+
+        self.truncate();
+        self.sync_rows();
+        self.update_badge(window_cx.res);
+
+        id
+    }
+
+    /// Remove a notification. This drops its elements.
+    ///
+    /// Returns `true` if the notification existed.
+    pub fn dismiss(&mut self, id: NotificationID, res: &mut ResourceCtx) -> bool {
+        let Some(i) = self.rows.iter().position(|row| row.id == id) else {
+            return false;
+        };
+
+        self.rows.remove(i);
+        self.sync_rows();
+        self.update_badge(res);
+        true
+    }
+
+    /// Remove all notifications.
+    pub fn clear(&mut self, res: &mut ResourceCtx) {
+        self.rows.clear();
+        self.update_badge(res);
+    }
+
+    /// The number of notifications in the panel.
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// The number of notifications which were added while the panel was
+    /// closed.
+    pub fn unread_count(&self) -> usize {
+        self.rows.iter().filter(|row| row.unread).count()
+    }
+
+    /// Open or close the panel. Opening the panel marks all notifications as
+    /// read.
+    ///
+    /// Returns `true` if the open state has changed.
+    pub fn set_open(&mut self, open: bool, res: &mut ResourceCtx) -> bool {
+        self.toggle_button.set_toggled(open);
+
+        if self.open == open {
+            return false;
+        }
+
+        self.open = open;
+
+        if open {
+            for row in self.rows.iter_mut() {
+                row.unread = false;
+            }
+            self.update_badge(res);
+        }
+
+        if self.slide_speed <= 0.0 {
+            self.open_amount = if open { 1.0 } else { 0.0 };
+            self.apply_slide();
+        }
+
+        true
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Advance the slide animation and refresh the timestamps.
+    ///
+    /// Call this from [`Application::on_tick`].
+    ///
+    /// Returns `true` if the panel is still animating.
+    pub fn tick(&mut self, dt: f64, res: &mut ResourceCtx) -> bool {
+        let target = if self.open { 1.0 } else { 0.0 };
+
+        if self.open_amount != target {
+            let t = 1.0 - (-(dt as f32) * self.slide_speed).exp();
+            self.open_amount += (target - self.open_amount) * t;
+
+            if (target - self.open_amount).abs() < 0.001 {
+                self.open_amount = target;
+            }
+
+            self.apply_slide();
+        }
+
+        self.secs_since_timestamp_update += dt;
+        if self.secs_since_timestamp_update >= 1.0 {
+            self.secs_since_timestamp_update = 0.0;
+
+            for row in self.rows.iter_mut() {
+                let age_text = format_age(row.created.elapsed().as_secs());
+
+                if row.age_text != age_text {
+                    row.timestamp.set_text(Some(age_text.as_str()), res);
+                    row.age_text = age_text;
+                }
+            }
+        }
+
+        self.open_amount != target
+    }
+
+    /// Dock the panel to the right edge of a window with the given size.
+    ///
+    /// Call this from the layout function of the window whenever the window
+    /// is resized.
+    pub fn dock(&mut self, window_size: Size) {
+        if self.window_size != window_size {
+            self.window_size = window_size;
+            self.apply_slide();
+            self.sync_rows();
+        }
+    }
+
+    /// Lay out the toggle button (and its unread badge) which opens and
+    /// closes the panel.
+    pub fn layout_toggle(&mut self, rect: Rect) {
+        self.toggle_button.set_rect(rect);
+        self.badge.set_rect(centered_rect(
+            Point::new(rect.max_x(), rect.min_y()),
+            Size::new(self.badge_size, self.badge_size),
+        ));
+    }
+
+    /// The button which opens and closes the panel.
+    pub fn toggle_button_mut(&mut self) -> &mut ToggleButton {
+        &mut self.toggle_button
+    }
+
+    fn truncate(&mut self) {
+        if self.rows.len() > self.max_notifications {
+            self.rows.truncate(self.max_notifications);
+        }
+    }
+
+    fn update_badge(&mut self, res: &mut ResourceCtx) {
+        let unread = self.unread_count();
+
+        if unread == 0 {
+            self.badge.set_hidden(true);
+        } else {
+            let text = if unread > 9 {
+                String::from("9+")
+            } else {
+                unread.to_string()
+            };
+
+            self.badge.set_text(Some(text), res);
+            self.badge.set_hidden(false);
+        }
+    }
+
+    fn apply_slide(&mut self) {
+        let width = self.width.min(self.window_size.width).max(0.0);
+
+        Widget::set_rect(
+            self,
+            Rect::new(
+                Point::new(self.window_size.width - (width * self.open_amount), 0.0),
+                Size::new(width, self.window_size.height),
+            ),
+        );
+
+        let hidden = self.open_amount <= 0.0;
+        if self.hidden() != hidden {
+            Widget::set_hidden(self, hidden);
+            self.sync_rows();
+        }
+    }
+
+    /// The number of notifications which fit in the panel.
+    fn num_visible_rows(&self) -> usize {
+        if self.row_height <= 0.0 {
+            return 0;
+        }
+
+        ((self.window_size.height - self.header_height - self.padding) / self.row_height)
+            .max(0.0)
+            .floor() as usize
+    }
+
+    /// Only the rows which fit in the panel are listed as parts, so the
+    /// rest have to be hidden manually.
+    fn sync_rows(&mut self) {
+        let num_visible = self.num_visible_rows();
+
+        for row in self.rows.iter_mut().skip(num_visible) {
+            row.set_hidden(true);
+        }
+
+        self.sync();
+    }
+}
+
+impl<A: Clone + 'static> Widget for NotificationPanel<A> {
+    fn core(&self) -> &WidgetCore {
+        &self.core
+    }
+
+    fn core_mut(&mut self) -> &mut WidgetCore {
+        &mut self.core
+    }
+
+    fn for_each_part(&mut self, f: &mut dyn FnMut(&mut dyn WidgetPart, PartLayout)) {
+        let padding = self.padding;
+        let width = self.rect().width();
+        let clear_button_width = 72.0;
+        let dismiss_size = 20.0;
+        let num_visible = self.num_visible_rows();
+
+        (f)(&mut self.background, PartLayout::fill());
+        (f)(
+            &mut self.header,
+            PartLayout::fixed(
+                Align2::TOP_LEFT,
+                Size::new(
+                    (width - clear_button_width - (padding * 3.0)).max(0.0),
+                    self.header_height - padding,
+                ),
+            )
+            .offset(Vector::new(padding, padding)),
+        );
+        (f)(
+            &mut self.clear_button,
+            PartLayout::fixed(
+                Align2::TOP_RIGHT,
+                Size::new(clear_button_width, self.header_height - padding),
+            )
+            .offset(Vector::new(-padding, padding)),
+        );
+
+        let text_width = (width - dismiss_size - (padding * 3.0)).max(0.0);
+        let message_height = self.row_height * 0.55;
+
+        for (i, row) in self.rows.iter_mut().take(num_visible).enumerate() {
+            let y = self.header_height + padding + (self.row_height * i as f32);
+
+            (f)(
+                &mut row.message,
+                PartLayout::fixed(Align2::TOP_LEFT, Size::new(text_width, message_height))
+                    .offset(Vector::new(padding, y)),
+            );
+            (f)(
+                &mut row.timestamp,
+                PartLayout::fixed(
+                    Align2::TOP_LEFT,
+                    Size::new(text_width, self.row_height - message_height),
+                )
+                .offset(Vector::new(padding, y + message_height)),
+            );
+            (f)(
+                &mut row.dismiss_button,
+                PartLayout::fixed(Align2::TOP_RIGHT, Size::new(dismiss_size, dismiss_size)).offset(
+                    Vector::new(-padding, y + ((self.row_height - dismiss_size) * 0.5)),
+                ),
+            );
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use smallvec::SmallVec;
 use std::cell::{Ref, RefCell};
 use std::rc::Rc;
 
@@ -217,7 +218,12 @@ impl ParagraphInner {
         self.padded_size_needs_calculated = true;
     }
 
-    pub fn render(&mut self, bounds: Rect, style: &ParagraphStyle) -> LabelPrimitives {
+    pub fn render(
+        &mut self,
+        bounds: Rect,
+        style: &ParagraphStyle,
+        opacity: f32,
+    ) -> LabelPrimitives {
         let mut needs_layout = self.text_size_needs_calculated;
 
         if self.prev_bounds_size != bounds.size {
@@ -244,7 +250,7 @@ impl ParagraphInner {
             Some(TextPrimitive::new(
                 self.text_buffer.clone(),
                 bounds.origin + self.text_bounds_rect.origin.to_vector() + self.text_offset,
-                style.text_color,
+                apply_opacity(style.text_color, opacity),
                 None,
             ))
         } else {
@@ -252,7 +258,11 @@ impl ParagraphInner {
         };
 
         let bg_quad = if !style.back_quad.is_transparent() {
-            Some(style.back_quad.create_primitive(bounds))
+            Some(
+                style
+                    .back_quad
+                    .create_primitive_with_opacity(bounds, opacity),
+            )
         } else {
             None
         };
@@ -261,6 +271,10 @@ impl ParagraphInner {
             icon: None,
             text,
             bg_quad,
+            bg_overlay: style
+                .back_quad
+                .create_overlay_primitives_with_opacity(bounds, opacity),
+            selection: SmallVec::new(),
         }
     }
 }
@@ -311,6 +325,7 @@ impl ParagraphBuilder {
             rect,
             manually_hidden,
             scissor_rect,
+            key,
         } = self;
 
         let style = window_cx
@@ -334,6 +349,7 @@ impl ParagraphBuilder {
             shared_state: Rc::clone(&shared_state),
         })
         .builder_values(z_index, scissor_rect, class, window_cx)
+        .key(key)
         .rect(rect)
         .hidden(manually_hidden)
         .flags(ElementFlags::PAINTS)
@@ -367,12 +383,15 @@ impl<A: Clone + 'static> Element<A> for ParagraphElement {
         let paragraph_primitives = shared_state.inner.render(
             Rect::from_size(cx.bounds_size),
             cx.res.style_system.get(cx.class),
+            cx.opacity,
         );
 
         if let Some(quad_primitive) = paragraph_primitives.bg_quad {
             primitives.add(quad_primitive);
         }
 
+        paragraph_primitives.bg_overlay.add_to(primitives);
+
         if let Some(text_primitive) = paragraph_primitives.text {
             primitives.set_z_index(1);
             primitives.add_text(text_primitive);
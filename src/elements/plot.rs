@@ -0,0 +1,1484 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+use rootvg::tessellation::{
+    fill::{Fill, FillRule, FillStyle},
+    path::PathBuilder,
+    stroke::{LineCap, LineDash, LineJoin, Stroke},
+    Tessellator,
+};
+
+use crate::derive::*;
+use crate::prelude::*;
+use crate::vg::quad::{SolidQuadBuilder, SolidQuadPrimitive};
+use crate::vg::text::{Metrics, RcTextBuffer, TextPrimitive};
+
+/// The style of a [`Plot`] element
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlotStyle {
+    /// The properties of the tick labels, the legend, and the hover readout.
+    ///
+    /// By default this uses a font size of `11.0`.
+    pub text_properties: TextProperties,
+    /// The color of the tick labels, the legend, and the hover readout.
+    ///
+    /// By default this is set to `RGBA8::new(200, 200, 200, 255)`.
+    pub label_color: RGBA8,
+
+    /// The style of the background quad.
+    ///
+    /// By default this is set to `QuadStyle::TRANSPARENT`.
+    pub back_quad: QuadStyle,
+
+    /// The color of the grid lines at each tick.
+    ///
+    /// By default this is set to `RGBA8::new(255, 255, 255, 25)`.
+    pub grid_color: RGBA8,
+    /// The color of the lines along the bottom and left edges of the plot
+    /// area.
+    ///
+    /// By default this is set to `RGBA8::new(255, 255, 255, 80)`.
+    pub axis_color: RGBA8,
+    /// The width of grid and axis lines.
+    ///
+    /// By default this is set to `1.0`.
+    pub grid_line_width: f32,
+
+    /// The colors of the series which don't have a color assigned to them,
+    /// in the order the series were added.
+    pub series_colors: Vec<RGBA8>,
+    /// The width of the series lines.
+    ///
+    /// By default this is set to `1.5`.
+    pub line_width: f32,
+    /// The opacity of the filled region of area series (multiplied with the
+    /// color of the series).
+    ///
+    /// By default this is set to `0.25`.
+    pub area_opacity: f32,
+
+    /// The width of the space left of the plot area for the y axis tick
+    /// labels.
+    ///
+    /// By default this is set to `44.0`.
+    pub y_axis_width: f32,
+    /// The height of the space below the plot area for the x axis tick
+    /// labels.
+    ///
+    /// By default this is set to `18.0`.
+    pub x_axis_height: f32,
+    /// The space between the tick labels and the plot area.
+    ///
+    /// By default this is set to `4.0`.
+    pub tick_label_spacing: f32,
+
+    /// The style of the background quad of the legend and the hover readout.
+    ///
+    /// By default this is set to a semi-transparent dark gray.
+    pub overlay_back_quad: QuadStyle,
+    /// The padding inside of the legend and the hover readout.
+    ///
+    /// By default this has all values set to `4.0`.
+    pub overlay_padding: Padding,
+    /// The size of the color swatch next to each series in the legend.
+    ///
+    /// By default this is set to `8.0`.
+    pub legend_swatch_size: f32,
+    /// The color of the vertical line drawn at the pointer while hovering
+    /// over the plot area.
+    ///
+    /// By default this is set to `RGBA8::new(255, 255, 255, 100)`.
+    pub hover_line_color: RGBA8,
+
+    /// The padding between the contents and the bounding rectangle.
+    ///
+    /// By default this has all values set to `4.0`.
+    pub padding: Padding,
+}
+
+impl Default for PlotStyle {
+    fn default() -> Self {
+        Self {
+            text_properties: TextProperties {
+                metrics: Metrics {
+                    font_size: 11.0,
+                    line_height: 14.0,
+                },
+                wrap: Wrap::None,
+                ..Default::default()
+            },
+            label_color: RGBA8::new(200, 200, 200, 255),
+            back_quad: QuadStyle::TRANSPARENT,
+            grid_color: RGBA8::new(255, 255, 255, 25),
+            axis_color: RGBA8::new(255, 255, 255, 80),
+            grid_line_width: 1.0,
+            series_colors: vec![
+                RGBA8::new(80, 160, 255, 255),
+                RGBA8::new(255, 150, 60, 255),
+                RGBA8::new(100, 210, 120, 255),
+                RGBA8::new(230, 90, 110, 255),
+                RGBA8::new(180, 130, 240, 255),
+                RGBA8::new(230, 210, 80, 255),
+            ],
+            line_width: 1.5,
+            area_opacity: 0.25,
+            y_axis_width: 44.0,
+            x_axis_height: 18.0,
+            tick_label_spacing: 4.0,
+            overlay_back_quad: QuadStyle {
+                bg: Background::Solid(RGBA8::new(20, 20, 20, 220)),
+                border: BorderStyle {
+                    radius: 3.0.into(),
+                    ..Default::default()
+                },
+                flags: QuadFlags::SNAP_ALL_TO_NEAREST_PIXEL,
+            },
+            overlay_padding: Padding::new(4.0, 4.0, 4.0, 4.0),
+            legend_swatch_size: 8.0,
+            hover_line_color: RGBA8::new(255, 255, 255, 100),
+            padding: Padding::new(4.0, 4.0, 4.0, 4.0),
+        }
+    }
+}
+
+impl PlotStyle {
+    /// The color of the series with the given index which doesn't have a
+    /// color assigned to it.
+    pub fn series_color(&self, index: usize) -> RGBA8 {
+        if self.series_colors.is_empty() {
+            self.label_color
+        } else {
+            self.series_colors[index % self.series_colors.len()]
+        }
+    }
+}
+
+impl ElementStyle for PlotStyle {
+    const ID: &'static str = "plot";
+
+    fn default_dark_style() -> Self {
+        Self::default()
+    }
+
+    fn default_light_style() -> Self {
+        Self {
+            label_color: RGBA8::new(60, 60, 60, 255),
+            grid_color: RGBA8::new(0, 0, 0, 25),
+            axis_color: RGBA8::new(0, 0, 0, 90),
+            series_colors: vec![
+                RGBA8::new(20, 110, 220, 255),
+                RGBA8::new(220, 110, 20, 255),
+                RGBA8::new(30, 150, 60, 255),
+                RGBA8::new(200, 40, 70, 255),
+                RGBA8::new(130, 70, 200, 255),
+                RGBA8::new(170, 140, 0, 255),
+            ],
+            overlay_back_quad: QuadStyle {
+                bg: Background::Solid(RGBA8::new(250, 250, 250, 230)),
+                border: BorderStyle {
+                    radius: 3.0.into(),
+                    color: RGBA8::new(0, 0, 0, 40),
+                    width: 1.0,
+                },
+                flags: QuadFlags::SNAP_ALL_TO_NEAREST_PIXEL,
+            },
+            hover_line_color: RGBA8::new(0, 0, 0, 90),
+            ..Default::default()
+        }
+    }
+}
+
+/// The scale of an axis of a [`Plot`].
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PlotScale {
+    #[default]
+    Linear,
+    /// A base-10 logarithmic scale. Values which are less than or equal to
+    /// zero are not drawn.
+    Log,
+}
+
+impl PlotScale {
+    /// Map a value in the range `[min, max]` to the range `[0.0, 1.0]`.
+    pub fn to_normal(&self, value: f64, min: f64, max: f64) -> f64 {
+        match self {
+            Self::Linear => (value - min) / (max - min),
+            Self::Log => {
+                let min = min.log10();
+                (value.log10() - min) / (max.log10() - min)
+            }
+        }
+    }
+
+    /// Map a value in the range `[0.0, 1.0]` to the range `[min, max]`.
+    pub fn from_normal(&self, normal: f64, min: f64, max: f64) -> f64 {
+        match self {
+            Self::Linear => min + (normal * (max - min)),
+            Self::Log => {
+                let min = min.log10();
+                10.0f64.powf(min + (normal * (max.log10() - min)))
+            }
+        }
+    }
+
+    fn is_valid(&self, value: f64) -> bool {
+        match self {
+            Self::Linear => value.is_finite(),
+            Self::Log => value.is_finite() && value > 0.0,
+        }
+    }
+
+    /// The positions of the ticks in the range `[min, max]`, with at most
+    /// about `max_ticks` ticks.
+    fn ticks(&self, min: f64, max: f64, max_ticks: usize) -> Vec<f64> {
+        let max_ticks = max_ticks.max(2);
+        let mut ticks = Vec::new();
+
+        match self {
+            Self::Linear => {
+                let step = nice_step((max - min) / max_ticks as f64);
+                if !(step > 0.0) {
+                    return ticks;
+                }
+
+                let first = (min / step).ceil() * step;
+                // The step is too small to be represented at this magnitude
+                // (i.e. a short span of large timestamps).
+                if first + step == first {
+                    return ticks;
+                }
+
+                let num_steps = ((max + (step * 1e-6) - first) / step).floor();
+                if num_steps.is_nan() || num_steps < 0.0 {
+                    return ticks;
+                }
+                let num_steps = (num_steps as usize).min(max_ticks * 2);
+
+                for k in 0..=num_steps {
+                    ticks.push(first + (k as f64 * step));
+                }
+            }
+            Self::Log => {
+                let first_decade = min.log10().ceil() as i32;
+                let last_decade = max.log10().floor() as i32;
+                let num_decades = (last_decade - first_decade + 1).max(1) as usize;
+                let stride = num_decades.div_ceil(max_ticks).max(1);
+
+                for decade in (first_decade..=last_decade).step_by(stride) {
+                    ticks.push(10.0f64.powi(decade));
+                }
+            }
+        }
+
+        ticks
+    }
+}
+
+/// Returns a "nice" step size (1, 2, or 5 times a power of ten) which is
+/// close to `raw_step`.
+fn nice_step(raw_step: f64) -> f64 {
+    if !raw_step.is_finite() || raw_step <= 0.0 {
+        return 0.0;
+    }
+
+    let magnitude = 10.0f64.powf(raw_step.log10().floor());
+    let fraction = raw_step / magnitude;
+
+    let nice = if fraction <= 1.0 {
+        1.0
+    } else if fraction <= 2.0 {
+        2.0
+    } else if fraction <= 5.0 {
+        5.0
+    } else {
+        10.0
+    };
+
+    nice * magnitude
+}
+
+/// The default formatter for tick labels and the hover readout.
+pub fn default_value_format(value: f64) -> String {
+    let abs = value.abs();
+
+    if abs == 0.0 {
+        String::from("0")
+    } else if abs >= 1_000_000.0 || abs < 0.001 {
+        format!("{:.1e}", value)
+    } else if abs >= 100.0 {
+        format!("{:.0}", value)
+    } else if abs >= 1.0 {
+        trim_zeros(format!("{:.2}", value))
+    } else {
+        trim_zeros(format!("{:.3}", value))
+    }
+}
+
+fn trim_zeros(s: String) -> String {
+    if s.contains('.') {
+        s.trim_end_matches('0').trim_end_matches('.').to_string()
+    } else {
+        s
+    }
+}
+
+/// How a series of a [`Plot`] is drawn.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SeriesKind {
+    /// A line through the points.
+    #[default]
+    Line,
+    /// A line through the points with the region between the line and the
+    /// bottom of the plot area filled in.
+    Area,
+}
+
+/// The ID of a series in a [`Plot`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SeriesID(usize);
+
+struct Series {
+    name: String,
+    kind: SeriesKind,
+    color: Option<RGBA8>,
+    hidden: bool,
+    /// The points of the series, sorted by their x value.
+    points: VecDeque<(f64, f64)>,
+}
+
+#[element_builder]
+#[element_builder_class]
+#[element_builder_rect]
+#[element_builder_hidden]
+pub struct PlotBuilder {
+    pub x_scale: PlotScale,
+    pub y_scale: PlotScale,
+    pub x_range: Option<(f64, f64)>,
+    pub y_range: Option<(f64, f64)>,
+    pub max_points: usize,
+    pub show_legend: bool,
+    pub x_format: fn(f64) -> String,
+    pub y_format: fn(f64) -> String,
+}
+
+impl Default for PlotBuilder {
+    fn default() -> Self {
+        Self {
+            x_scale: PlotScale::Linear,
+            y_scale: PlotScale::Linear,
+            x_range: None,
+            y_range: None,
+            max_points: 10_000,
+            show_legend: true,
+            x_format: default_value_format,
+            y_format: default_value_format,
+            class: Default::default(),
+            z_index: Default::default(),
+            rect: Default::default(),
+            manually_hidden: Default::default(),
+            scissor_rect: Default::default(),
+            key: Default::default(),
+        }
+    }
+}
+
+impl PlotBuilder {
+    /// The scale of the x axis.
+    ///
+    /// By default this is set to [`PlotScale::Linear`].
+    pub const fn x_scale(mut self, scale: PlotScale) -> Self {
+        self.x_scale = scale;
+        self
+    }
+
+    /// The scale of the y axis.
+    ///
+    /// By default this is set to [`PlotScale::Linear`].
+    pub const fn y_scale(mut self, scale: PlotScale) -> Self {
+        self.y_scale = scale;
+        self
+    }
+
+    /// The visible range of the x axis, or `None` to fit the range to the
+    /// points of all visible series.
+    ///
+    /// By default this is set to `None`.
+    pub const fn x_range(mut self, range: Option<(f64, f64)>) -> Self {
+        self.x_range = range;
+        self
+    }
+
+    /// The visible range of the y axis, or `None` to fit the range to the
+    /// points of all visible series.
+    ///
+    /// By default this is set to `None`.
+    pub const fn y_range(mut self, range: Option<(f64, f64)>) -> Self {
+        self.y_range = range;
+        self
+    }
+
+    /// The maximum number of points to store per series. Once this is
+    /// reached, the oldest points are discarded when new ones are pushed.
+    ///
+    /// By default this is set to `10_000`.
+    pub const fn max_points(mut self, max_points: usize) -> Self {
+        self.max_points = max_points;
+        self
+    }
+
+    /// Whether or not to show a legend with the names of the series.
+    ///
+    /// By default this is set to `true`.
+    pub const fn show_legend(mut self, show: bool) -> Self {
+        self.show_legend = show;
+        self
+    }
+
+    /// The function used to format x values in tick labels and the hover
+    /// readout (i.e. to append a unit).
+    ///
+    /// By default this is set to [`default_value_format`].
+    pub const fn x_format(mut self, format: fn(f64) -> String) -> Self {
+        self.x_format = format;
+        self
+    }
+
+    /// The function used to format y values in tick labels and the hover
+    /// readout (i.e. to append a unit).
+    ///
+    /// By default this is set to [`default_value_format`].
+    pub const fn y_format(mut self, format: fn(f64) -> String) -> Self {
+        self.y_format = format;
+        self
+    }
+
+    pub fn build<A: Clone + 'static>(self, window_cx: &mut WindowContext<'_, A>) -> Plot {
+        let PlotBuilder {
+            x_scale,
+            y_scale,
+            x_range,
+            y_range,
+            max_points,
+            show_legend,
+            x_format,
+            y_format,
+            class,
+            z_index,
+            rect,
+            manually_hidden,
+            scissor_rect,
+            key,
+        } = self;
+
+        let shared_state = Rc::new(RefCell::new(SharedState {
+            series: Vec::new(),
+            x_scale,
+            y_scale,
+            x_range,
+            y_range,
+            max_points: max_points.max(2),
+            show_legend,
+            x_format,
+            y_format,
+        }));
+
+        let el = ElementBuilder::new(PlotElement {
+            shared_state: Rc::clone(&shared_state),
+            hover_pos: None,
+            text_cache: TextCache::default(),
+        })
+        .builder_values(z_index, scissor_rect, class, window_cx)
+        .key(key)
+        .rect(rect)
+        .hidden(manually_hidden)
+        .flags(
+            ElementFlags::PAINTS
+                | ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS
+                | ElementFlags::LISTENS_TO_SIZE_CHANGE,
+        )
+        .build(window_cx);
+
+        Plot { el, shared_state }
+    }
+}
+
+struct SharedState {
+    series: Vec<Series>,
+    x_scale: PlotScale,
+    y_scale: PlotScale,
+    x_range: Option<(f64, f64)>,
+    y_range: Option<(f64, f64)>,
+    max_points: usize,
+    show_legend: bool,
+    x_format: fn(f64) -> String,
+    y_format: fn(f64) -> String,
+}
+
+/// The visible ranges of the axes and the rectangle of the plot area.
+struct PlotLayout {
+    plot_rect: Rect,
+    x_range: (f64, f64),
+    y_range: (f64, f64),
+}
+
+impl SharedState {
+    fn push(&mut self, id: SeriesID, x: f64, y: f64) {
+        let max_points = self.max_points;
+        let Some(series) = self.series.get_mut(id.0) else {
+            return;
+        };
+
+        // Keep the points sorted by their x value.
+        if series.points.back().is_some_and(|(last_x, _)| x < *last_x) {
+            let i = series.points.partition_point(|(px, _)| *px <= x);
+            series.points.insert(i, (x, y));
+        } else {
+            series.points.push_back((x, y));
+        }
+
+        while series.points.len() > max_points {
+            series.points.pop_front();
+        }
+    }
+
+    fn auto_range(&self, scale: PlotScale, value: impl Fn(&(f64, f64)) -> f64) -> (f64, f64) {
+        let mut min = f64::INFINITY;
+        let mut max = f64::NEG_INFINITY;
+
+        for series in self.series.iter().filter(|s| !s.hidden) {
+            for point in series.points.iter() {
+                let v = (value)(point);
+                if scale.is_valid(v) {
+                    min = min.min(v);
+                    max = max.max(v);
+                }
+            }
+        }
+
+        sanitize_range(scale, min, max)
+    }
+
+    fn layout(&self, bounds_size: Size, style: &PlotStyle) -> PlotLayout {
+        let x_range = match self.x_range {
+            Some((min, max)) => sanitize_range(self.x_scale, min, max),
+            None => self.auto_range(self.x_scale, |p| p.0),
+        };
+
+        let y_range = match self.y_range {
+            Some((min, max)) => sanitize_range(self.y_scale, min, max),
+            None => {
+                let (min, max) = self.auto_range(self.y_scale, |p| p.1);
+
+                // Leave some room above and below the points.
+                match self.y_scale {
+                    PlotScale::Linear => {
+                        let margin = (max - min) * 0.05;
+                        (min - margin, max + margin)
+                    }
+                    PlotScale::Log => (min, max),
+                }
+            }
+        };
+
+        let plot_rect = Rect::new(
+            Point::new(style.padding.left + style.y_axis_width, style.padding.top),
+            Size::new(
+                (bounds_size.width - style.padding.left - style.padding.right - style.y_axis_width)
+                    .max(0.0),
+                (bounds_size.height
+                    - style.padding.top
+                    - style.padding.bottom
+                    - style.x_axis_height)
+                    .max(0.0),
+            ),
+        );
+
+        PlotLayout {
+            plot_rect,
+            x_range,
+            y_range,
+        }
+    }
+
+    fn to_pos(&self, layout: &PlotLayout, x: f64, y: f64) -> Point {
+        let nx = self
+            .x_scale
+            .to_normal(x, layout.x_range.0, layout.x_range.1);
+        let ny = self
+            .y_scale
+            .to_normal(y, layout.y_range.0, layout.y_range.1);
+
+        Point::new(
+            layout.plot_rect.min_x() + (nx as f32 * layout.plot_rect.width()),
+            layout.plot_rect.max_y() - (ny as f32 * layout.plot_rect.height()),
+        )
+    }
+
+    /// The positions of the points of a series within the plot area.
+    ///
+    /// If there are many more points than horizontal pixels, then only the
+    /// minimum and maximum of each column of pixels are kept.
+    fn series_positions(&self, series: &Series, layout: &PlotLayout) -> Vec<Point> {
+        let (x_min, x_max) = layout.x_range;
+        let rect = layout.plot_rect;
+
+        // Include one point on either side of the visible range so that the
+        // line continues to the edges of the plot area.
+        let start = series
+            .points
+            .partition_point(|(x, _)| *x < x_min)
+            .saturating_sub(1);
+        let end =
+            (series.points.partition_point(|(x, _)| *x <= x_max) + 1).min(series.points.len());
+
+        let clamp = |p: Point| {
+            Point::new(
+                p.x.clamp(rect.min_x(), rect.max_x()),
+                p.y.clamp(rect.min_y(), rect.max_y()),
+            )
+        };
+
+        let visible = series
+            .points
+            .range(start..end)
+            .filter(|(x, y)| self.x_scale.is_valid(*x) && self.y_scale.is_valid(*y))
+            .map(|(x, y)| clamp(self.to_pos(layout, *x, *y)));
+
+        let num_columns = rect.width().ceil().max(1.0) as usize;
+        if end - start <= num_columns * 2 {
+            return visible.collect();
+        }
+
+        let mut positions = Vec::with_capacity(num_columns * 2);
+        let mut column: Option<(i32, Point, Point)> = None;
+
+        for pos in visible {
+            let col = pos.x.floor() as i32;
+
+            match &mut column {
+                Some((c, min, max)) if *c == col => {
+                    if pos.y < min.y {
+                        *min = pos;
+                    }
+                    if pos.y > max.y {
+                        *max = pos;
+                    }
+                }
+                _ => {
+                    if let Some((_, min, max)) = column.take() {
+                        positions.push(min);
+                        positions.push(max);
+                    }
+                    column = Some((col, pos, pos));
+                }
+            }
+        }
+        if let Some((_, min, max)) = column {
+            positions.push(min);
+            positions.push(max);
+        }
+
+        positions
+    }
+
+    /// The point of each visible series which is closest to the given x
+    /// value.
+    fn nearest_points(&self, x: f64) -> Vec<(usize, (f64, f64))> {
+        self.series
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| !s.hidden && !s.points.is_empty())
+            .filter_map(|(i, s)| {
+                let idx = s.points.partition_point(|(px, _)| *px < x);
+
+                let before = idx.checked_sub(1).and_then(|i| s.points.get(i));
+                let after = s.points.get(idx);
+
+                let nearest = match (before, after) {
+                    (Some(b), Some(a)) => {
+                        if (x - b.0).abs() <= (a.0 - x).abs() {
+                            *b
+                        } else {
+                            *a
+                        }
+                    }
+                    (Some(p), None) | (None, Some(p)) => *p,
+                    (None, None) => return None,
+                };
+
+                Some((i, nearest))
+            })
+            .collect()
+    }
+}
+
+fn sanitize_range(scale: PlotScale, min: f64, max: f64) -> (f64, f64) {
+    let (min, max) = if min <= max { (min, max) } else { (max, min) };
+
+    match scale {
+        PlotScale::Linear => {
+            if !min.is_finite() || !max.is_finite() {
+                (0.0, 1.0)
+            } else if min == max {
+                (min - 1.0, max + 1.0)
+            } else {
+                (min, max)
+            }
+        }
+        PlotScale::Log => {
+            if !min.is_finite() || !max.is_finite() || max <= 0.0 {
+                (1.0, 10.0)
+            } else {
+                let min = if min <= 0.0 { max / 1000.0 } else { min };
+
+                if min == max {
+                    (min / 10.0, max * 10.0)
+                } else {
+                    (min, max)
+                }
+            }
+        }
+    }
+}
+
+/// Reuses the text buffers of labels between frames.
+#[derive(Default)]
+pub(super) struct TextCache {
+    buffers: Vec<(String, RcTextBuffer)>,
+    used: usize,
+}
+
+impl TextCache {
+    pub(super) fn begin(&mut self) {
+        self.used = 0;
+    }
+
+    pub(super) fn end(&mut self) {
+        self.buffers.truncate(self.used);
+    }
+
+    pub(super) fn get(
+        &mut self,
+        text: &str,
+        properties: TextProperties,
+        font_system: &mut FontSystem,
+    ) -> RcTextBuffer {
+        let i = self.used;
+        self.used += 1;
+
+        if let Some((cached_text, buffer)) = self.buffers.get_mut(i) {
+            if cached_text != text {
+                cached_text.clear();
+                cached_text.push_str(text);
+                buffer.set_text(text, font_system);
+            }
+
+            return buffer.clone();
+        }
+
+        let buffer = RcTextBuffer::new(text, properties, None, None, false, font_system);
+        self.buffers.push((text.to_string(), buffer.clone()));
+        buffer
+    }
+}
+
+struct PlotElement {
+    shared_state: Rc<RefCell<SharedState>>,
+    /// The position of the pointer relative to the element while it is
+    /// hovering over the plot.
+    hover_pos: Option<Point>,
+    text_cache: TextCache,
+}
+
+impl<A: Clone + 'static> Element<A> for PlotElement {
+    fn on_event(
+        &mut self,
+        event: ElementEvent,
+        cx: &mut ElementContext<'_, A>,
+    ) -> EventCaptureStatus {
+        match event {
+            ElementEvent::CustomStateChanged | ElementEvent::SizeChanged => {
+                cx.request_repaint();
+            }
+            ElementEvent::StyleChanged => {
+                // Force all of the labels to be rebuilt with the new style.
+                self.text_cache = TextCache::default();
+                cx.request_repaint();
+            }
+            ElementEvent::Pointer(PointerEvent::Moved { position, .. }) => {
+                let hover_pos = if cx.rect().contains(position) {
+                    Some(position - cx.rect().origin.to_vector())
+                } else {
+                    None
+                };
+
+                if self.hover_pos != hover_pos {
+                    self.hover_pos = hover_pos;
+                    cx.request_repaint();
+                }
+            }
+            ElementEvent::Pointer(PointerEvent::PointerLeft) => {
+                if self.hover_pos.take().is_some() {
+                    cx.request_repaint();
+                }
+            }
+            _ => {}
+        }
+
+        EventCaptureStatus::NotCaptured
+    }
+
+    fn render(&mut self, cx: RenderContext, primitives: &mut PrimitiveGroup) {
+        let style: &PlotStyle = cx.res.style_system.get(cx.class);
+        let shared_state = RefCell::borrow(&self.shared_state);
+
+        let bounds = Rect::from_size(cx.bounds_size);
+
+        if !style.back_quad.is_transparent() {
+            primitives.add(
+                style
+                    .back_quad
+                    .create_primitive_with_opacity(bounds, cx.opacity),
+            );
+        }
+
+        let layout = shared_state.layout(cx.bounds_size, style);
+        let rect = layout.plot_rect;
+        if rect.width() <= 0.0 || rect.height() <= 0.0 {
+            return;
+        }
+
+        self.text_cache.begin();
+
+        let line_height = style.text_properties.metrics.line_height;
+        let mut lines: Vec<SolidQuadPrimitive> = Vec::new();
+        let mut labels: Vec<TextPrimitive> = Vec::new();
+
+        // Grid lines and tick labels
+        let x_ticks = shared_state.x_scale.ticks(
+            layout.x_range.0,
+            layout.x_range.1,
+            (rect.width() / 80.0) as usize,
+        );
+        for x in x_ticks {
+            let pos_x = shared_state.to_pos(&layout, x, layout.y_range.0).x;
+
+            lines.push(
+                SolidQuadBuilder::new(Size::new(style.grid_line_width, rect.height()))
+                    .bg_color(apply_opacity(style.grid_color, cx.opacity))
+                    .position(Point::new(
+                        pos_x - (style.grid_line_width * 0.5),
+                        rect.min_y(),
+                    ))
+                    .into(),
+            );
+
+            let text = (shared_state.x_format)(x);
+            let buffer = self
+                .text_cache
+                .get(&text, style.text_properties, &mut cx.res.font_system);
+            let size = buffer.measure();
+
+            labels.push(TextPrimitive::new(
+                buffer,
+                Point::new(
+                    (pos_x - (size.width * 0.5)).clamp(0.0, (bounds.width() - size.width).max(0.0)),
+                    rect.max_y() + style.tick_label_spacing,
+                ),
+                apply_opacity(style.label_color, cx.opacity),
+                None,
+            ));
+        }
+
+        let y_ticks = shared_state.y_scale.ticks(
+            layout.y_range.0,
+            layout.y_range.1,
+            (rect.height() / (line_height * 2.5)) as usize,
+        );
+        for y in y_ticks {
+            let pos_y = shared_state.to_pos(&layout, layout.x_range.0, y).y;
+
+            lines.push(
+                SolidQuadBuilder::new(Size::new(rect.width(), style.grid_line_width))
+                    .bg_color(apply_opacity(style.grid_color, cx.opacity))
+                    .position(Point::new(
+                        rect.min_x(),
+                        pos_y - (style.grid_line_width * 0.5),
+                    ))
+                    .into(),
+            );
+
+            let text = (shared_state.y_format)(y);
+            let buffer = self
+                .text_cache
+                .get(&text, style.text_properties, &mut cx.res.font_system);
+            let size = buffer.measure();
+
+            labels.push(TextPrimitive::new(
+                buffer,
+                Point::new(
+                    (rect.min_x() - style.tick_label_spacing - size.width).max(0.0),
+                    (pos_y - (size.height * 0.5))
+                        .clamp(0.0, (bounds.height() - size.height).max(0.0)),
+                ),
+                apply_opacity(style.label_color, cx.opacity),
+                None,
+            ));
+        }
+
+        // Axes
+        lines.push(
+            SolidQuadBuilder::new(Size::new(style.grid_line_width, rect.height()))
+                .bg_color(apply_opacity(style.axis_color, cx.opacity))
+                .position(rect.origin)
+                .into(),
+        );
+        lines.push(
+            SolidQuadBuilder::new(Size::new(rect.width(), style.grid_line_width))
+                .bg_color(apply_opacity(style.axis_color, cx.opacity))
+                .position(Point::new(
+                    rect.min_x(),
+                    rect.max_y() - style.grid_line_width,
+                ))
+                .into(),
+        );
+
+        primitives.add_solid_quad_batch(lines);
+
+        // Series
+        primitives.set_z_index(1);
+
+        for (i, series) in shared_state.series.iter().enumerate() {
+            if series.hidden {
+                continue;
+            }
+
+            let positions = shared_state.series_positions(series, &layout);
+            if positions.len() < 2 {
+                continue;
+            }
+
+            let color = series.color.unwrap_or_else(|| style.series_color(i));
+
+            if series.kind == SeriesKind::Area {
+                let mut area_path =
+                    PathBuilder::new().move_to(Point::new(positions[0].x, rect.max_y()));
+                for pos in positions.iter() {
+                    area_path = area_path.line_to(*pos);
+                }
+                let area_path = area_path
+                    .line_to(Point::new(positions[positions.len() - 1].x, rect.max_y()))
+                    .close()
+                    .build();
+
+                let mut area_color = color;
+                area_color.a = (area_color.a as f32 * style.area_opacity).round() as u8;
+
+                if let Some(mesh) = Tessellator::new()
+                    .fill(
+                        &area_path,
+                        Fill {
+                            style: FillStyle::Solid(area_color.into()),
+                            rule: FillRule::NonZero,
+                        },
+                    )
+                    .into_primitive()
+                {
+                    primitives.add_mesh(mesh);
+                }
+            }
+
+            let mut line_path = PathBuilder::new().move_to(positions[0]);
+            for pos in positions.iter().skip(1) {
+                line_path = line_path.line_to(*pos);
+            }
+            let line_path = line_path.build();
+
+            if let Some(mesh) = Tessellator::new()
+                .stroke(
+                    &line_path,
+                    Stroke {
+                        style: FillStyle::Solid(color.into()),
+                        width: style.line_width,
+                        line_cap: LineCap::Round,
+                        line_join: LineJoin::Round,
+                        line_dash: LineDash::default(),
+                    },
+                )
+                .into_primitive()
+            {
+                primitives.add_mesh(mesh);
+            }
+        }
+
+        primitives.set_z_index(2);
+        primitives.add_text_batch(labels);
+
+        // Legend
+        if shared_state.show_legend {
+            self.render_legend(
+                &shared_state,
+                style,
+                cx.opacity,
+                rect,
+                &mut cx.res.font_system,
+                primitives,
+            );
+        }
+
+        // Hover readout
+        if let Some(hover_pos) = self.hover_pos.filter(|p| rect.contains(*p)) {
+            self.render_readout(
+                &shared_state,
+                style,
+                cx.opacity,
+                &layout,
+                hover_pos,
+                &mut cx.res.font_system,
+                primitives,
+            );
+        }
+
+        self.text_cache.end();
+    }
+}
+
+impl PlotElement {
+    fn render_legend(
+        &mut self,
+        shared_state: &SharedState,
+        style: &PlotStyle,
+        opacity: f32,
+        rect: Rect,
+        font_system: &mut FontSystem,
+        primitives: &mut PrimitiveGroup,
+    ) {
+        let line_height = style.text_properties.metrics.line_height;
+        let swatch_spacing = style.legend_swatch_size * 0.5;
+
+        let entries: Vec<(RGBA8, RcTextBuffer)> = shared_state
+            .series
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| !s.hidden && !s.name.is_empty())
+            .map(|(i, s)| {
+                (
+                    s.color.unwrap_or_else(|| style.series_color(i)),
+                    self.text_cache
+                        .get(&s.name, style.text_properties, font_system),
+                )
+            })
+            .collect();
+
+        if entries.is_empty() {
+            return;
+        }
+
+        let text_width = entries
+            .iter()
+            .map(|(_, buffer)| buffer.measure().width)
+            .fold(0.0, f32::max);
+
+        let legend_size = Size::new(
+            style.overlay_padding.left
+                + style.legend_swatch_size
+                + swatch_spacing
+                + text_width
+                + style.overlay_padding.right,
+            style.overlay_padding.top
+                + (entries.len() as f32 * line_height)
+                + style.overlay_padding.bottom,
+        );
+        let legend_rect = Rect::new(
+            Point::new(
+                rect.max_x() - legend_size.width - style.overlay_padding.right,
+                rect.min_y() + style.overlay_padding.top,
+            ),
+            legend_size,
+        );
+
+        primitives.set_z_index(3);
+        primitives.add(
+            style
+                .overlay_back_quad
+                .create_primitive_with_opacity(legend_rect, opacity),
+        );
+
+        primitives.set_z_index(4);
+
+        let mut swatches: Vec<SolidQuadPrimitive> = Vec::with_capacity(entries.len());
+        let mut texts: Vec<TextPrimitive> = Vec::with_capacity(entries.len());
+
+        for (i, (color, buffer)) in entries.into_iter().enumerate() {
+            let y = legend_rect.min_y() + style.overlay_padding.top + (i as f32 * line_height);
+            let x = legend_rect.min_x() + style.overlay_padding.left;
+
+            swatches.push(
+                SolidQuadBuilder::new(Size::new(
+                    style.legend_swatch_size,
+                    style.legend_swatch_size,
+                ))
+                .bg_color(apply_opacity(color, opacity))
+                .position(Point::new(
+                    x,
+                    y + ((line_height - style.legend_swatch_size) * 0.5),
+                ))
+                .into(),
+            );
+
+            texts.push(TextPrimitive::new(
+                buffer,
+                Point::new(x + style.legend_swatch_size + swatch_spacing, y),
+                apply_opacity(style.label_color, opacity),
+                None,
+            ));
+        }
+
+        primitives.add_solid_quad_batch(swatches);
+        primitives.add_text_batch(texts);
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn render_readout(
+        &mut self,
+        shared_state: &SharedState,
+        style: &PlotStyle,
+        opacity: f32,
+        layout: &PlotLayout,
+        hover_pos: Point,
+        font_system: &mut FontSystem,
+        primitives: &mut PrimitiveGroup,
+    ) {
+        let rect = layout.plot_rect;
+        let line_height = style.text_properties.metrics.line_height;
+
+        let normal_x = ((hover_pos.x - rect.min_x()) / rect.width()) as f64;
+        let x = shared_state
+            .x_scale
+            .from_normal(normal_x, layout.x_range.0, layout.x_range.1);
+
+        let nearest = shared_state.nearest_points(x);
+
+        let mut markers: Vec<SolidQuadPrimitive> =
+            vec![
+                SolidQuadBuilder::new(Size::new(style.grid_line_width, rect.height()))
+                    .bg_color(apply_opacity(style.hover_line_color, opacity))
+                    .position(Point::new(hover_pos.x, rect.min_y()))
+                    .into(),
+            ];
+
+        let mut rows: Vec<(RGBA8, RcTextBuffer)> = Vec::with_capacity(nearest.len() + 1);
+        rows.push((
+            style.label_color,
+            self.text_cache.get(
+                &(shared_state.x_format)(x),
+                style.text_properties,
+                font_system,
+            ),
+        ));
+
+        let marker_size = style.line_width * 3.0;
+
+        for (i, (px, py)) in nearest {
+            let series = &shared_state.series[i];
+            let color = series.color.unwrap_or_else(|| style.series_color(i));
+
+            let pos = shared_state.to_pos(layout, px, py);
+            if rect.contains(pos) {
+                markers.push(
+                    SolidQuadBuilder::new(Size::new(marker_size, marker_size))
+                        .bg_color(apply_opacity(color, opacity))
+                        .position(pos - Vector::new(marker_size * 0.5, marker_size * 0.5))
+                        .into(),
+                );
+            }
+
+            let text = if series.name.is_empty() {
+                (shared_state.y_format)(py)
+            } else {
+                format!("{}: {}", series.name, (shared_state.y_format)(py))
+            };
+
+            rows.push((
+                color,
+                self.text_cache
+                    .get(&text, style.text_properties, font_system),
+            ));
+        }
+
+        let text_width = rows
+            .iter()
+            .map(|(_, buffer)| buffer.measure().width)
+            .fold(0.0, f32::max);
+
+        let readout_size = Size::new(
+            style.overlay_padding.left + text_width + style.overlay_padding.right,
+            style.overlay_padding.top
+                + (rows.len() as f32 * line_height)
+                + style.overlay_padding.bottom,
+        );
+
+        // Place the readout next to the pointer, flipping it to the other
+        // side if it would go past the edge of the plot area.
+        let offset = 8.0;
+        let mut readout_x = hover_pos.x + offset;
+        if readout_x + readout_size.width > rect.max_x() {
+            readout_x = hover_pos.x - offset - readout_size.width;
+        }
+        let readout_y = (hover_pos.y - readout_size.height * 0.5).clamp(
+            rect.min_y(),
+            (rect.max_y() - readout_size.height).max(rect.min_y()),
+        );
+
+        let readout_rect = Rect::new(
+            Point::new(readout_x.max(rect.min_x()), readout_y),
+            readout_size,
+        );
+
+        primitives.set_z_index(5);
+        primitives.add_solid_quad_batch(markers);
+
+        primitives.set_z_index(6);
+        primitives.add(
+            style
+                .overlay_back_quad
+                .create_primitive_with_opacity(readout_rect, opacity),
+        );
+
+        primitives.set_z_index(7);
+        primitives.add_text_batch(
+            rows.into_iter()
+                .enumerate()
+                .map(|(i, (color, buffer))| {
+                    TextPrimitive::new(
+                        buffer,
+                        Point::new(
+                            readout_rect.min_x() + style.overlay_padding.left,
+                            readout_rect.min_y()
+                                + style.overlay_padding.top
+                                + (i as f32 * line_height),
+                        ),
+                        apply_opacity(color, opacity),
+                        None,
+                    )
+                })
+                .collect(),
+        );
+    }
+}
+
+/// A handle to a [`PlotElement`], a line/area chart with multiple series,
+/// axes with tick labels, a legend, and a readout of the values under the
+/// pointer.
+///
+/// Points are stored in a ring buffer per series, so appending points (i.e.
+/// once per frame for a loudness history) is cheap. If a series has many
+/// more points than the plot area has horizontal pixels, then the line is
+/// simplified to the minimum and maximum of each column of pixels.
+///
+/// This requires the `tessellation` feature.
+#[element_handle]
+#[element_handle_class]
+#[element_handle_set_rect]
+#[element_handle_layout_aligned]
+pub struct Plot {
+    shared_state: Rc<RefCell<SharedState>>,
+}
+
+impl Plot {
+    pub fn builder() -> PlotBuilder {
+        PlotBuilder::default()
+    }
+
+    /// Add a new series to the plot.
+    ///
+    /// The `name` is shown in the legend and the hover readout.
+    pub fn add_series(&mut self, name: impl Into<String>, kind: SeriesKind) -> SeriesID {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        shared_state.series.push(Series {
+            name: name.into(),
+            kind,
+            color: None,
+            hidden: false,
+            points: VecDeque::new(),
+        });
+
+        self.el.notify_custom_state_change();
+        SeriesID(shared_state.series.len() - 1)
+    }
+
+    /// Append a point to a series.
+    ///
+    /// Points are expected to be appended in order of increasing x values.
+    /// Points which are out of order are inserted at the correct position,
+    /// which is more expensive.
+    pub fn push(&mut self, series: SeriesID, x: f64, y: f64) {
+        RefCell::borrow_mut(&self.shared_state).push(series, x, y);
+        self.el.notify_custom_state_change();
+    }
+
+    /// Append multiple points to a series.
+    ///
+    /// See [`Plot::push`].
+    pub fn extend(&mut self, series: SeriesID, points: impl IntoIterator<Item = (f64, f64)>) {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        for (x, y) in points {
+            shared_state.push(series, x, y);
+        }
+
+        self.el.notify_custom_state_change();
+    }
+
+    /// Replace all points of a series.
+    pub fn set_points(&mut self, series: SeriesID, points: impl IntoIterator<Item = (f64, f64)>) {
+        {
+            let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+            if let Some(s) = shared_state.series.get_mut(series.0) {
+                s.points.clear();
+            }
+            for (x, y) in points {
+                shared_state.push(series, x, y);
+            }
+        }
+
+        self.el.notify_custom_state_change();
+    }
+
+    /// Remove all points from a series.
+    pub fn clear(&mut self, series: SeriesID) {
+        if let Some(s) = RefCell::borrow_mut(&self.shared_state)
+            .series
+            .get_mut(series.0)
+        {
+            s.points.clear();
+        }
+
+        self.el.notify_custom_state_change();
+    }
+
+    /// The number of points in a series.
+    pub fn num_points(&self, series: SeriesID) -> usize {
+        RefCell::borrow(&self.shared_state)
+            .series
+            .get(series.0)
+            .map(|s| s.points.len())
+            .unwrap_or(0)
+    }
+
+    /// Set the color of a series, or `None` to use the color from
+    /// [`PlotStyle::series_colors`].
+    pub fn set_series_color(&mut self, series: SeriesID, color: Option<RGBA8>) -> bool {
+        self.update_series(series, |s| {
+            if s.color == color {
+                return false;
+            }
+            s.color = color;
+            true
+        })
+    }
+
+    /// Hide or show a series.
+    pub fn set_series_hidden(&mut self, series: SeriesID, hidden: bool) -> bool {
+        self.update_series(series, |s| {
+            if s.hidden == hidden {
+                return false;
+            }
+            s.hidden = hidden;
+            true
+        })
+    }
+
+    pub fn set_series_name(&mut self, series: SeriesID, name: &str) -> bool {
+        self.update_series(series, |s| {
+            if s.name == name {
+                return false;
+            }
+            s.name = name.to_string();
+            true
+        })
+    }
+
+    pub fn set_series_kind(&mut self, series: SeriesID, kind: SeriesKind) -> bool {
+        self.update_series(series, |s| {
+            if s.kind == kind {
+                return false;
+            }
+            s.kind = kind;
+            true
+        })
+    }
+
+    /// Set the visible range of the x axis, or `None` to fit the range to the
+    /// points of all visible series.
+    pub fn set_x_range(&mut self, range: Option<(f64, f64)>) -> bool {
+        self.update(|s| {
+            if s.x_range == range {
+                return false;
+            }
+            s.x_range = range;
+            true
+        })
+    }
+
+    /// Set the visible range of the y axis, or `None` to fit the range to the
+    /// points of all visible series.
+    pub fn set_y_range(&mut self, range: Option<(f64, f64)>) -> bool {
+        self.update(|s| {
+            if s.y_range == range {
+                return false;
+            }
+            s.y_range = range;
+            true
+        })
+    }
+
+    pub fn set_x_scale(&mut self, scale: PlotScale) -> bool {
+        self.update(|s| {
+            if s.x_scale == scale {
+                return false;
+            }
+            s.x_scale = scale;
+            true
+        })
+    }
+
+    pub fn set_y_scale(&mut self, scale: PlotScale) -> bool {
+        self.update(|s| {
+            if s.y_scale == scale {
+                return false;
+            }
+            s.y_scale = scale;
+            true
+        })
+    }
+
+    pub fn set_show_legend(&mut self, show: bool) -> bool {
+        self.update(|s| {
+            if s.show_legend == show {
+                return false;
+            }
+            s.show_legend = show;
+            true
+        })
+    }
+
+    fn update(&mut self, f: impl FnOnce(&mut SharedState) -> bool) -> bool {
+        let changed = (f)(&mut RefCell::borrow_mut(&self.shared_state));
+
+        if changed {
+            self.el.notify_custom_state_change();
+        }
+
+        changed
+    }
+
+    fn update_series(&mut self, series: SeriesID, f: impl FnOnce(&mut Series) -> bool) -> bool {
+        self.update(|s| s.series.get_mut(series.0).map(f).unwrap_or(false))
+    }
+}
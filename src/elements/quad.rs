@@ -18,13 +18,26 @@ impl QuadElementBuilder {
             rect,
             manually_hidden,
             scissor_rect,
+            key,
         } = self;
 
+        let class = class.unwrap_or_else(|| window_cx.class());
+        let mut flags = ElementFlags::PAINTS;
+        if window_cx
+            .res
+            .style_system
+            .get::<QuadStyle>(class)
+            .is_opaque()
+        {
+            flags.insert(ElementFlags::OPAQUE);
+        }
+
         let el = ElementBuilder::new(QuadElementInternal)
-            .builder_values(z_index, scissor_rect, class, window_cx)
+            .builder_values(z_index, scissor_rect, Some(class), window_cx)
+            .key(key)
             .rect(rect)
             .hidden(manually_hidden)
-            .flags(ElementFlags::PAINTS)
+            .flags(flags)
             .build(window_cx);
 
         QuadElement { el }
@@ -40,20 +53,28 @@ impl<A: Clone + 'static> Element<A> for QuadElementInternal {
         event: ElementEvent,
         cx: &mut ElementContext<'_, A>,
     ) -> EventCaptureStatus {
-        if let ElementEvent::CustomStateChanged = event {
-            cx.request_repaint();
+        match event {
+            ElementEvent::CustomStateChanged => {
+                cx.request_repaint();
+            }
+            ElementEvent::StyleChanged => {
+                let opaque = cx.res.style_system.get::<QuadStyle>(cx.class()).is_opaque();
+                cx.set_opaque(opaque);
+            }
+            _ => {}
         }
 
         EventCaptureStatus::NotCaptured
     }
 
     fn render(&mut self, cx: RenderContext, primitives: &mut PrimitiveGroup) {
-        primitives.add(
-            cx.res
-                .style_system
-                .get::<QuadStyle>(cx.class)
-                .create_primitive(Rect::from_size(cx.bounds_size)),
-        );
+        let style = *cx.res.style_system.get::<QuadStyle>(cx.class);
+        let bounds = Rect::from_size(cx.bounds_size);
+
+        #[cfg(feature = "image")]
+        style.add_image_primitives(bounds, &cx.res.image_system, primitives);
+
+        style.add_primitives_with_opacity(bounds, cx.opacity, primitives);
     }
 }
 
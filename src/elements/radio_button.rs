@@ -86,6 +86,18 @@ impl ElementStyle for RadioButtonStyle {
     fn default_light_style() -> Self {
         Self::default()
     }
+
+    fn with_auto_disabled(&self, derivation: DisabledDerivation) -> Option<Self> {
+        let mut style = self.clone();
+        style
+            .outer_border_color_off_disabled
+            .derive_auto(derivation);
+        style.outer_border_color_on_disabled.derive_auto(derivation);
+        style.off_bg_disabled.derive_auto(derivation);
+        style.on_bg_disabled.derive_auto(derivation);
+        style.dot_bg_disabled.derive_auto(derivation);
+        Some(style)
+    }
 }
 
 #[element_builder]
@@ -122,6 +134,7 @@ impl<A: Clone + 'static> RadioButtonBuilder<A> {
             manually_hidden,
             disabled,
             scissor_rect,
+            key,
         } = self;
 
         let style = window_cx
@@ -143,6 +156,7 @@ impl<A: Clone + 'static> RadioButtonBuilder<A> {
             cursor_icon,
         })
         .builder_values(z_index, scissor_rect, class, window_cx)
+        .key(key)
         .rect(rect)
         .hidden(manually_hidden)
         .flags(ElementFlags::PAINTS | ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS)
@@ -251,6 +265,7 @@ impl<A: Clone + 'static> Element<A> for RadioButtonElement<A> {
                         ),
                         width: style.outer_border_width,
                         radius: style.radius,
+                        edges: BorderEdges::NONE,
                     },
                     flags: style.quad_flags,
                 }
@@ -263,6 +278,7 @@ impl<A: Clone + 'static> Element<A> for RadioButtonElement<A> {
                             .get(style.outer_border_color_off),
                         width: style.outer_border_width,
                         radius: style.radius,
+                        edges: BorderEdges::NONE,
                     },
                     flags: style.quad_flags,
                 }
@@ -281,6 +297,7 @@ impl<A: Clone + 'static> Element<A> for RadioButtonElement<A> {
                         ),
                         width: style.outer_border_width,
                         radius: style.radius,
+                        edges: BorderEdges::NONE,
                     },
                     flags: style.quad_flags,
                 }
@@ -293,6 +310,7 @@ impl<A: Clone + 'static> Element<A> for RadioButtonElement<A> {
                             .unwrap_or(style.outer_border_color_off),
                         width: style.outer_border_width,
                         radius: style.radius,
+                        edges: BorderEdges::NONE,
                     },
                     flags: style.quad_flags,
                 }
@@ -307,6 +325,7 @@ impl<A: Clone + 'static> Element<A> for RadioButtonElement<A> {
                             .unwrap_or(style.outer_border_color_off),
                         width: style.outer_border_width,
                         radius: style.radius,
+                        edges: BorderEdges::NONE,
                     },
                     flags: style.quad_flags,
                 }
@@ -317,6 +336,7 @@ impl<A: Clone + 'static> Element<A> for RadioButtonElement<A> {
                         color: style.outer_border_color_off,
                         width: style.outer_border_width,
                         radius: style.radius,
+                        edges: BorderEdges::NONE,
                     },
                     flags: style.quad_flags,
                 }
@@ -328,7 +348,7 @@ impl<A: Clone + 'static> Element<A> for RadioButtonElement<A> {
 
         let bg_bounds = centered_rect(bounds_rect.center(), Size::new(size, size));
 
-        primitives.add(bg_quad_style.create_primitive(bg_bounds));
+        primitives.add(bg_quad_style.create_primitive_with_opacity(bg_bounds, cx.opacity));
 
         if shared_state.toggled {
             let quad_style = if shared_state.disabled {
@@ -359,7 +379,7 @@ impl<A: Clone + 'static> Element<A> for RadioButtonElement<A> {
             );
 
             primitives.set_z_index(1);
-            primitives.add(quad_style.create_primitive(dot_bounds));
+            primitives.add(quad_style.create_primitive_with_opacity(dot_bounds, cx.opacity));
         }
     }
 }
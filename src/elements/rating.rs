@@ -0,0 +1,556 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::derive::*;
+use crate::prelude::*;
+use crate::theme::DEFAULT_ICON_SIZE;
+use crate::vg::text::{CustomGlyph, TextPrimitive};
+
+/// The style of a [`Rating`] element
+#[derive(Debug, Clone, PartialEq)]
+pub struct RatingStyle {
+    /// The width and height of each icon in points.
+    ///
+    /// By default this is set to `20.0`.
+    pub icon_size: f32,
+
+    /// The spacing between each icon in points.
+    ///
+    /// By default this is set to `2.0`.
+    pub spacing: f32,
+
+    /// Whether or not the icons should be snapped to the nearset physical
+    /// pixel when rendering.
+    ///
+    /// By default this is set to `true`.
+    pub snap_to_physical_pixel: bool,
+
+    /// The color of the filled part of the rating.
+    pub filled_color: RGBA8,
+    /// The color of the filled part of the rating while the user is
+    /// previewing a new rating by hovering over the element.
+    pub filled_color_hover: Option<RGBA8>,
+    pub filled_color_disabled: DisabledColor,
+
+    /// The color of the empty part of the rating.
+    pub empty_color: RGBA8,
+    pub empty_color_hover: Option<RGBA8>,
+    pub empty_color_disabled: DisabledColor,
+
+    /// The cursor icon to show when the user hovers over this element.
+    ///
+    /// If this is `None`, then the cursor icon will not be changed.
+    ///
+    /// By default this is set to `Some(CursorIcon::Pointer)`.
+    pub cursor_icon: Option<CursorIcon>,
+}
+
+impl Default for RatingStyle {
+    fn default() -> Self {
+        Self {
+            icon_size: DEFAULT_ICON_SIZE,
+            spacing: 2.0,
+            snap_to_physical_pixel: true,
+            filled_color: rgb(255, 196, 40),
+            filled_color_hover: Some(rgb(255, 214, 110)),
+            filled_color_disabled: Default::default(),
+            empty_color: gray(90),
+            empty_color_hover: None,
+            empty_color_disabled: Default::default(),
+            cursor_icon: Some(CursorIcon::Pointer),
+        }
+    }
+}
+
+impl ElementStyle for RatingStyle {
+    const ID: &'static str = "rating";
+
+    fn default_dark_style() -> Self {
+        Self::default()
+    }
+
+    fn default_light_style() -> Self {
+        Self {
+            filled_color: rgb(235, 165, 0),
+            filled_color_hover: Some(rgb(245, 190, 60)),
+            empty_color: gray(190),
+            ..Default::default()
+        }
+    }
+
+    fn with_auto_disabled(&self, derivation: DisabledDerivation) -> Option<Self> {
+        let mut style = self.clone();
+        style.filled_color_disabled.derive_auto(derivation);
+        style.empty_color_disabled.derive_auto(derivation);
+        Some(style)
+    }
+}
+
+#[element_builder]
+#[element_builder_class]
+#[element_builder_rect]
+#[element_builder_hidden]
+#[element_builder_disabled]
+#[element_builder_tooltip]
+pub struct RatingBuilder<A: Clone + 'static> {
+    pub action: Option<Box<dyn FnMut(u8) -> A>>,
+    pub rating: u8,
+    pub num_icons: u8,
+    pub half_steps: bool,
+    pub allow_clear: bool,
+    pub filled_icon: IconID,
+    pub empty_icon: Option<IconID>,
+}
+
+impl<A: Clone + 'static> RatingBuilder<A> {
+    pub fn new() -> Self {
+        Self {
+            action: None,
+            rating: 0,
+            num_icons: 5,
+            half_steps: false,
+            allow_clear: true,
+            filled_icon: IconID::default(),
+            empty_icon: None,
+            class: Default::default(),
+            z_index: Default::default(),
+            rect: Default::default(),
+            manually_hidden: Default::default(),
+            disabled: Default::default(),
+            scissor_rect: Default::default(),
+            key: Default::default(),
+            tooltip_data: Default::default(),
+        }
+    }
+
+    /// The action to send when the user clicks on a new rating.
+    ///
+    /// If half steps are enabled, then the rating is in units of half icons.
+    pub fn on_rated<F: FnMut(u8) -> A + 'static>(mut self, f: F) -> Self {
+        self.action = Some(Box::new(f));
+        self
+    }
+
+    /// The initial rating.
+    ///
+    /// If half steps are enabled, then the rating is in units of half icons
+    /// (i.e. a rating of `7` with five icons is shown as three and a half
+    /// icons).
+    pub const fn rating(mut self, rating: u8) -> Self {
+        self.rating = rating;
+        self
+    }
+
+    /// The number of icons to show.
+    ///
+    /// By default this is set to `5`.
+    pub const fn num_icons(mut self, num_icons: u8) -> Self {
+        self.num_icons = num_icons;
+        self
+    }
+
+    /// Whether or not the user can select half of an icon.
+    ///
+    /// By default this is set to `false`.
+    pub const fn half_steps(mut self, half_steps: bool) -> Self {
+        self.half_steps = half_steps;
+        self
+    }
+
+    /// Whether or not clicking on the current rating clears it back to `0`.
+    ///
+    /// By default this is set to `true`.
+    pub const fn allow_clear(mut self, allow_clear: bool) -> Self {
+        self.allow_clear = allow_clear;
+        self
+    }
+
+    /// The icon used for the filled part of the rating (i.e. a star or a
+    /// heart).
+    pub fn filled_icon(mut self, icon: impl Into<IconID>) -> Self {
+        self.filled_icon = icon.into();
+        self
+    }
+
+    /// The icon used for the empty part of the rating (i.e. an outlined
+    /// star).
+    ///
+    /// If this is `None`, then the filled icon is drawn with the empty color.
+    ///
+    /// By default this is set to `None`.
+    pub fn empty_icon(mut self, icon: Option<impl Into<IconID>>) -> Self {
+        self.empty_icon = icon.map(|i| i.into());
+        self
+    }
+
+    pub fn build(self, window_cx: &mut WindowContext<'_, A>) -> Rating {
+        let RatingBuilder {
+            action,
+            rating,
+            num_icons,
+            half_steps,
+            allow_clear,
+            filled_icon,
+            empty_icon,
+            class,
+            z_index,
+            rect,
+            manually_hidden,
+            disabled,
+            scissor_rect,
+            key,
+            tooltip_data,
+        } = self;
+
+        let shared_state = Rc::new(RefCell::new(SharedState {
+            rating: rating.min(max_rating(num_icons, half_steps)),
+            num_icons,
+            half_steps,
+            filled_icon,
+            empty_icon,
+            disabled,
+            tooltip_inner: TooltipInner::new(tooltip_data),
+        }));
+
+        let el = ElementBuilder::new(RatingElement {
+            shared_state: Rc::clone(&shared_state),
+            action,
+            allow_clear,
+            hovered_rating: None,
+        })
+        .builder_values(z_index, scissor_rect, class, window_cx)
+        .key(key)
+        .rect(rect)
+        .hidden(manually_hidden)
+        .flags(ElementFlags::PAINTS | ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS)
+        .build(window_cx);
+
+        Rating { el, shared_state }
+    }
+}
+
+fn max_rating(num_icons: u8, half_steps: bool) -> u8 {
+    if half_steps {
+        num_icons.saturating_mul(2)
+    } else {
+        num_icons
+    }
+}
+
+/// A row of icons (i.e. stars or hearts) used to give a rating.
+struct RatingElement<A: Clone + 'static> {
+    shared_state: Rc<RefCell<SharedState>>,
+    action: Option<Box<dyn FnMut(u8) -> A>>,
+    allow_clear: bool,
+    hovered_rating: Option<u8>,
+}
+
+impl<A: Clone + 'static> RatingElement<A> {
+    /// Returns the rating under the given pointer position.
+    fn rating_at(
+        shared_state: &SharedState,
+        position: Point,
+        cx: &mut ElementContext<'_, A>,
+    ) -> u8 {
+        let style = cx.res.style_system.get::<RatingStyle>(cx.class());
+
+        let icon_size = style.icon_size.max(1.0);
+        let stride = icon_size + style.spacing.max(0.0);
+        let x = (position.x - cx.rect().min_x()).max(0.0);
+
+        let index = ((x / stride).floor() as u32).min(shared_state.num_icons as u32) as u8;
+
+        let rating = if shared_state.half_steps {
+            let fract = (x - (index as f32 * stride)) / icon_size;
+            index.saturating_mul(2) + if fract < 0.5 { 1 } else { 2 }
+        } else {
+            index + 1
+        };
+
+        rating.min(max_rating(shared_state.num_icons, shared_state.half_steps))
+    }
+}
+
+impl<A: Clone + 'static> Element<A> for RatingElement<A> {
+    fn on_event(
+        &mut self,
+        event: ElementEvent,
+        cx: &mut ElementContext<'_, A>,
+    ) -> EventCaptureStatus {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        shared_state
+            .tooltip_inner
+            .handle_event(&event, shared_state.disabled, cx);
+
+        match event {
+            ElementEvent::CustomStateChanged => {
+                if shared_state.disabled {
+                    self.hovered_rating = None;
+                }
+
+                cx.request_repaint();
+            }
+            ElementEvent::StyleChanged => {
+                cx.request_repaint();
+            }
+            ElementEvent::Pointer(PointerEvent::Moved { position, .. }) => {
+                if shared_state.disabled {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                if let Some(cursor_icon) = cx
+                    .res
+                    .style_system
+                    .get::<RatingStyle>(cx.class())
+                    .cursor_icon
+                {
+                    cx.cursor_icon = cursor_icon;
+                }
+
+                let hovered_rating = Some(Self::rating_at(&shared_state, position, cx));
+                if self.hovered_rating != hovered_rating {
+                    self.hovered_rating = hovered_rating;
+                    cx.request_repaint();
+                }
+
+                return EventCaptureStatus::Captured;
+            }
+            ElementEvent::Pointer(PointerEvent::PointerLeft) => {
+                if self.hovered_rating.take().is_some() {
+                    cx.request_repaint();
+                }
+            }
+            ElementEvent::Pointer(PointerEvent::ButtonJustPressed {
+                position, button, ..
+            }) => {
+                if shared_state.disabled || button != PointerButton::Primary {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                let mut new_rating = Self::rating_at(&shared_state, position, cx);
+                if self.allow_clear && new_rating == shared_state.rating {
+                    new_rating = 0;
+                }
+
+                if shared_state.rating != new_rating {
+                    shared_state.rating = new_rating;
+                    cx.request_repaint();
+
+                    if let Some(action) = &mut self.action {
+                        cx.send_action((action)(new_rating)).unwrap();
+                    }
+                }
+
+                return EventCaptureStatus::Captured;
+            }
+            _ => {}
+        }
+
+        EventCaptureStatus::NotCaptured
+    }
+
+    fn render(&mut self, cx: RenderContext, primitives: &mut PrimitiveGroup) {
+        let shared_state = RefCell::borrow(&self.shared_state);
+        let style = cx.res.style_system.get::<RatingStyle>(cx.class);
+
+        let previewing = self.hovered_rating.is_some();
+        let rating = self.hovered_rating.unwrap_or(shared_state.rating);
+
+        let (filled_color, empty_color) = if shared_state.disabled {
+            (
+                style.filled_color_disabled.get(style.filled_color),
+                style.empty_color_disabled.get(style.empty_color),
+            )
+        } else if previewing {
+            (
+                style.filled_color_hover.unwrap_or(style.filled_color),
+                style.empty_color_hover.unwrap_or(style.empty_color),
+            )
+        } else {
+            (style.filled_color, style.empty_color)
+        };
+
+        let empty_icon = shared_state.empty_icon.unwrap_or(shared_state.filled_icon);
+        let steps_per_icon: u8 = if shared_state.half_steps { 2 } else { 1 };
+        let icon_size = Size::new(style.icon_size, style.icon_size);
+        let y = ((cx.bounds_size.height - style.icon_size) * 0.5).max(0.0);
+
+        let icon_primitive = |icon: IconID, x: f32, color: RGBA8, clip_width: f32| {
+            TextPrimitive::new_with_icons(
+                None,
+                Point::new(x, y),
+                apply_opacity(color, cx.opacity),
+                Some(Rect::new(
+                    Point::new(-1.0, -1.0),
+                    Size::new(clip_width + 1.0, icon_size.height + 2.0),
+                )),
+                smallvec::smallvec![CustomGlyph {
+                    id: icon,
+                    left: 0.0,
+                    top: 0.0,
+                    width: icon_size.width,
+                    height: icon_size.height,
+                    snap_to_physical_pixel: style.snap_to_physical_pixel,
+                    color: None,
+                    metadata: 0,
+                }],
+            )
+        };
+
+        let mut empty_icons = Vec::new();
+        let mut filled_icons = Vec::new();
+
+        for i in 0..shared_state.num_icons {
+            let x = i as f32 * (style.icon_size + style.spacing);
+            let filled_steps = rating
+                .saturating_sub(i.saturating_mul(steps_per_icon))
+                .min(steps_per_icon);
+
+            if filled_steps < steps_per_icon {
+                empty_icons.push(icon_primitive(
+                    empty_icon,
+                    x,
+                    empty_color,
+                    icon_size.width + 1.0,
+                ));
+            }
+
+            if filled_steps == steps_per_icon {
+                filled_icons.push(icon_primitive(
+                    shared_state.filled_icon,
+                    x,
+                    filled_color,
+                    icon_size.width + 1.0,
+                ));
+            } else if filled_steps > 0 {
+                // Only show the left half of the filled icon.
+                filled_icons.push(icon_primitive(
+                    shared_state.filled_icon,
+                    x,
+                    filled_color,
+                    icon_size.width * 0.5,
+                ));
+            }
+        }
+
+        primitives.add_text_batch(empty_icons);
+        primitives.set_z_index(1);
+        primitives.add_text_batch(filled_icons);
+    }
+}
+
+struct SharedState {
+    rating: u8,
+    num_icons: u8,
+    half_steps: bool,
+    filled_icon: IconID,
+    empty_icon: Option<IconID>,
+    disabled: bool,
+    tooltip_inner: TooltipInner,
+}
+
+/// A handle to a [`RatingElement`], a row of icons (i.e. stars or hearts)
+/// used to give a rating.
+///
+/// Hovering over the element previews the new rating, and clicking on it
+/// commits the rating.
+#[element_handle]
+#[element_handle_class]
+#[element_handle_set_rect]
+#[element_handle_set_tooltip]
+pub struct Rating {
+    shared_state: Rc<RefCell<SharedState>>,
+}
+
+impl Rating {
+    pub fn builder<A: Clone + 'static>() -> RatingBuilder<A> {
+        RatingBuilder::new()
+    }
+
+    pub fn desired_size(&self, res: &mut ResourceCtx) -> Size {
+        let style = res.style_system.get::<RatingStyle>(self.el.class());
+        let num_icons = RefCell::borrow(&self.shared_state).num_icons as f32;
+
+        Size::new(
+            (num_icons * style.icon_size) + ((num_icons - 1.0).max(0.0) * style.spacing),
+            style.icon_size,
+        )
+    }
+
+    /// Set the rating.
+    ///
+    /// If half steps are enabled, then the rating is in units of half icons.
+    ///
+    /// Returns `true` if the rating has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn set_rating(&mut self, rating: u8) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        let rating = rating.min(max_rating(shared_state.num_icons, shared_state.half_steps));
+
+        if shared_state.rating != rating {
+            shared_state.rating = rating;
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn rating(&self) -> u8 {
+        RefCell::borrow(&self.shared_state).rating
+    }
+
+    /// The maximum possible rating.
+    pub fn max_rating(&self) -> u8 {
+        let shared_state = RefCell::borrow(&self.shared_state);
+        max_rating(shared_state.num_icons, shared_state.half_steps)
+    }
+
+    /// Set the disabled state of this element.
+    ///
+    /// Returns `true` if the disabled state has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn set_disabled(&mut self, disabled: bool) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if shared_state.disabled != disabled {
+            shared_state.disabled = disabled;
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn disabled(&self) -> bool {
+        RefCell::borrow(&self.shared_state).disabled
+    }
+
+    /// Layout out the element (with the top-left corner of the bounds set to `origin`).
+    ///
+    /// Returns `true` if the layout has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn layout(&mut self, origin: Point, res: &mut ResourceCtx) -> bool {
+        let size = self.desired_size(res);
+        self.el.set_rect(Rect::new(origin, size))
+    }
+
+    /// Layout out the element aligned to the given point.
+    ///
+    /// Returns `true` if the layout has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn layout_aligned(&mut self, point: Point, align: Align2, res: &mut ResourceCtx) -> bool {
+        let size = self.desired_size(res);
+        self.el.set_rect(align.align_rect_to_point(point, size))
+    }
+}
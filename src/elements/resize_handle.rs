@@ -119,6 +119,7 @@ impl<A: Clone + 'static> Default for ResizeHandleBuilder<A> {
             z_index: None,
             manually_hidden: false,
             scissor_rect: None,
+            key: None,
             disabled: false,
         }
     }
@@ -179,6 +180,7 @@ impl<A: Clone + 'static> ResizeHandleBuilder<A> {
             z_index,
             manually_hidden,
             scissor_rect,
+            key,
             disabled,
         } = self;
 
@@ -214,6 +216,7 @@ impl<A: Clone + 'static> ResizeHandleBuilder<A> {
             show_drag_handle: false,
         })
         .builder_values(z_index, scissor_rect, class, window_cx)
+        .key(key)
         .rect(rect)
         .hidden(manually_hidden)
         .flags(
@@ -471,7 +474,7 @@ impl<A: Clone + 'static> Element<A> for ResizeHandleElement<A> {
 
             primitives.add_solid_quad(
                 SolidQuadBuilder::new(handle_rect.size)
-                    .bg_color(handle_opts.color)
+                    .bg_color(apply_opacity(handle_opts.color, cx.opacity))
                     .position(handle_rect.origin)
                     .flags(style.quad_flags),
             );
@@ -1,5 +1,7 @@
 use std::cell::RefCell;
 use std::rc::Rc;
+use std::time::Duration;
+use web_time::Instant;
 
 use crate::derive::*;
 use crate::prelude::*;
@@ -86,6 +88,47 @@ impl ElementStyle for ScrollBarStyle {
     }
 }
 
+/// The configuration of kinetic scrolling in a [`ScrollArea`] element.
+///
+/// When enabled, the content can be dragged with the primary pointer button
+/// (or a touch), and it will keep scrolling with decelerating momentum after
+/// it is released.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KineticScrollConfig {
+    /// How quickly the momentum decays, where the velocity is multiplied by
+    /// `e^(-friction * seconds)` every frame.
+    ///
+    /// By default this is set to `4.0`.
+    pub friction: f32,
+    /// The speed in points per second below which the momentum stops.
+    ///
+    /// By default this is set to `20.0`.
+    pub min_velocity: f32,
+    /// The maximum distance in points the content can be pulled past its
+    /// edges before it springs back. Set to `0.0` to disable overscroll.
+    ///
+    /// By default this is set to `80.0`.
+    pub max_overscroll: f32,
+    /// How quickly overscrolled content springs back to the edge, where the
+    /// overscroll is multiplied by `e^(-spring_stiffness * seconds)` every
+    /// frame.
+    ///
+    /// By default this is set to `12.0`.
+    pub spring_stiffness: f32,
+}
+
+impl Default for KineticScrollConfig {
+    fn default() -> Self {
+        Self {
+            friction: 4.0,
+            min_velocity: 20.0,
+            max_overscroll: 80.0,
+            spring_stiffness: 12.0,
+        }
+    }
+}
+
 #[element_builder]
 #[element_builder_class]
 #[element_builder_rect]
@@ -102,6 +145,7 @@ pub struct ScrollAreaBuilder<A: Clone + 'static> {
     pub show_slider_when_content_fits: bool,
     pub capture_scroll_wheel: bool,
     pub points_per_line: f32,
+    pub kinetic_scrolling: Option<KineticScrollConfig>,
 }
 
 impl<A: Clone + 'static> Default for ScrollAreaBuilder<A> {
@@ -117,12 +161,14 @@ impl<A: Clone + 'static> Default for ScrollAreaBuilder<A> {
             show_slider_when_content_fits: false,
             capture_scroll_wheel: true,
             points_per_line: 24.0,
+            kinetic_scrolling: None,
             class: None,
             z_index: None,
             rect: Rect::default(),
             manually_hidden: false,
             disabled: false,
             scissor_rect: None,
+            key: None,
         }
     }
 }
@@ -182,6 +228,14 @@ impl<A: Clone + 'static> ScrollAreaBuilder<A> {
         self
     }
 
+    /// Enable kinetic scrolling with the given configuration.
+    ///
+    /// By default kinetic scrolling is disabled.
+    pub const fn kinetic_scrolling(mut self, config: KineticScrollConfig) -> Self {
+        self.kinetic_scrolling = Some(config);
+        self
+    }
+
     pub fn build(self, window_cx: &mut WindowContext<'_, A>) -> ScrollArea {
         let ScrollAreaBuilder {
             scrolled_action,
@@ -195,12 +249,14 @@ impl<A: Clone + 'static> ScrollAreaBuilder<A> {
             show_slider_when_content_fits,
             capture_scroll_wheel,
             points_per_line,
+            kinetic_scrolling,
 
             class,
             z_index,
             rect,
             manually_hidden,
             scissor_rect,
+            key,
             disabled,
         } = self;
 
@@ -250,9 +306,11 @@ impl<A: Clone + 'static> ScrollAreaBuilder<A> {
             horizontal_state: ScrollBarState::Idle,
             sliders_state: res,
             drag_state: None,
+            kinetic: kinetic_scrolling.map(KineticState::new),
             slider_width,
         })
         .builder_values(z_index, scissor_rect, class, window_cx)
+        .key(key)
         .rect(rect)
         .hidden(manually_hidden)
         .flags(
@@ -275,6 +333,95 @@ struct DragState {
     drag_start_scroll_offset: Vector,
 }
 
+/// If the pointer is held still for longer than this before being released,
+/// then the content will not keep scrolling.
+const KINETIC_RELEASE_TIMEOUT: Duration = Duration::from_millis(80);
+
+struct KineticState {
+    config: KineticScrollConfig,
+    /// The last pointer position and time while the content is being dragged.
+    content_drag: Option<(Point, Instant)>,
+    /// The current velocity in points per second.
+    velocity: Vector,
+    /// How far the content is currently pulled past its edges.
+    overscroll: Vector,
+}
+
+impl KineticState {
+    fn new(config: KineticScrollConfig) -> Self {
+        Self {
+            config,
+            content_drag: None,
+            velocity: Vector::zero(),
+            overscroll: Vector::zero(),
+        }
+    }
+
+    fn is_moving(&self) -> bool {
+        self.velocity.length() >= self.config.min_velocity || self.overscroll != Vector::zero()
+    }
+
+    fn stop(&mut self) {
+        self.content_drag = None;
+        self.velocity = Vector::zero();
+        self.overscroll = Vector::zero();
+    }
+
+    /// Apply a drag delta along one axis, returning the new scroll offset and
+    /// overscroll for that axis.
+    fn drag_axis(&self, offset: f32, overscroll: f32, delta: f32, max_offset: f32) -> (f32, f32) {
+        let max_overscroll = self.config.max_overscroll;
+
+        // Add resistance the further the content is pulled past its edge.
+        let delta = if overscroll != 0.0 && max_overscroll > 0.0 {
+            delta * (1.0 - (overscroll.abs() / max_overscroll)).max(0.0)
+        } else {
+            delta
+        };
+
+        let target = offset + overscroll + delta;
+        let new_offset = target.clamp(0.0, max_offset);
+        let new_overscroll = (target - new_offset).clamp(-max_overscroll, max_overscroll);
+
+        (new_offset, new_overscroll)
+    }
+
+    /// Advance the momentum and spring along one axis, returning the new
+    /// scroll offset, overscroll, and velocity for that axis.
+    fn step_axis(
+        &self,
+        offset: f32,
+        overscroll: f32,
+        velocity: f32,
+        max_offset: f32,
+        dt: f32,
+    ) -> (f32, f32, f32) {
+        if overscroll != 0.0 {
+            let mut overscroll = overscroll * (-self.config.spring_stiffness * dt).exp();
+            if overscroll.abs() < 0.5 {
+                overscroll = 0.0;
+            }
+
+            return (offset, overscroll, 0.0);
+        }
+
+        let target = offset + (velocity * dt);
+        let new_offset = target.clamp(0.0, max_offset);
+
+        if new_offset != target {
+            // The content hit an edge, so bounce it past the edge by however
+            // far it would have traveled.
+            let overscroll = (target - new_offset)
+                .clamp(-self.config.max_overscroll, self.config.max_overscroll);
+            return (new_offset, overscroll, 0.0);
+        }
+
+        let velocity = velocity * (-self.config.friction * dt).exp();
+
+        (new_offset, 0.0, velocity)
+    }
+}
+
 struct ScrollAreaElement<A: Clone + 'static> {
     shared_state: Rc<RefCell<SharedState>>,
 
@@ -294,6 +441,7 @@ struct ScrollAreaElement<A: Clone + 'static> {
 
     sliders_state: SlidersState,
     drag_state: Option<DragState>,
+    kinetic: Option<KineticState>,
 
     slider_width: f32,
 }
@@ -333,6 +481,11 @@ impl<A: Clone + 'static> Element<A> for ScrollAreaElement<A> {
                     self.drag_state = None;
                     self.vertical_state = ScrollBarState::Idle;
                     self.horizontal_state = ScrollBarState::Idle;
+
+                    if let Some(kinetic) = self.kinetic.as_mut() {
+                        kinetic.stop();
+                        cx.set_animating(false);
+                    }
                 }
 
                 if let Some(scissor_rect) = self.control_scissor_rect {
@@ -389,6 +542,16 @@ impl<A: Clone + 'static> Element<A> for ScrollAreaElement<A> {
                     return EventCaptureStatus::NotCaptured;
                 }
 
+                if self
+                    .kinetic
+                    .as_ref()
+                    .is_some_and(|k| k.content_drag.is_some())
+                {
+                    drop(shared_state);
+                    self.on_content_dragged(position, cx);
+                    return EventCaptureStatus::Captured;
+                }
+
                 let relative_pos = position - cx.rect().origin.to_vector();
 
                 if let Some(drag_state) = self.drag_state.as_mut() {
@@ -523,6 +686,11 @@ impl<A: Clone + 'static> Element<A> for ScrollAreaElement<A> {
                     return EventCaptureStatus::NotCaptured;
                 }
 
+                if let Some(kinetic) = self.kinetic.as_mut() {
+                    // Pressing anywhere stops the momentum.
+                    kinetic.velocity = Vector::zero();
+                }
+
                 let relative_pos = position - cx.rect().origin.to_vector();
 
                 if self.scroll_vertically {
@@ -642,6 +810,17 @@ impl<A: Clone + 'static> Element<A> for ScrollAreaElement<A> {
                         return EventCaptureStatus::Captured;
                     }
                 }
+
+                if cx.rect().contains(position) {
+                    if let Some(kinetic) = self.kinetic.as_mut() {
+                        kinetic.content_drag = Some((position, Instant::now()));
+
+                        cx.set_animating(false);
+                        cx.steal_temporary_focus();
+
+                        return EventCaptureStatus::Captured;
+                    }
+                }
             }
             ElementEvent::Pointer(PointerEvent::ButtonJustReleased {
                 button, position, ..
@@ -650,6 +829,20 @@ impl<A: Clone + 'static> Element<A> for ScrollAreaElement<A> {
                     return EventCaptureStatus::NotCaptured;
                 }
 
+                if let Some(kinetic) = self.kinetic.as_mut() {
+                    if let Some((_, last_moved_instant)) = kinetic.content_drag.take() {
+                        if last_moved_instant.elapsed() > KINETIC_RELEASE_TIMEOUT {
+                            kinetic.velocity = Vector::zero();
+                        }
+
+                        if kinetic.is_moving() {
+                            cx.set_animating(true);
+                        } else {
+                            kinetic.velocity = Vector::zero();
+                        }
+                    }
+                }
+
                 cx.release_focus();
 
                 self.drag_state = None;
@@ -716,6 +909,20 @@ impl<A: Clone + 'static> Element<A> for ScrollAreaElement<A> {
                     return EventCaptureStatus::NotCaptured;
                 }
 
+                if let Some(kinetic) = self.kinetic.as_mut() {
+                    kinetic.velocity = Vector::zero();
+
+                    // Let overscrolled content spring back before scrolling
+                    // it with the wheel.
+                    if kinetic.overscroll != Vector::zero() {
+                        return if self.capture_scroll_wheel {
+                            EventCaptureStatus::Captured
+                        } else {
+                            EventCaptureStatus::NotCaptured
+                        };
+                    }
+                }
+
                 let delta = delta_type.points(self.points_per_line, cx.rect().height());
 
                 let new_scroll_offset = Vector::new(
@@ -759,8 +966,20 @@ impl<A: Clone + 'static> Element<A> for ScrollAreaElement<A> {
                     return EventCaptureStatus::Captured;
                 }
             }
+            ElementEvent::Animation { delta_seconds } => {
+                drop(shared_state);
+                self.on_kinetic_animation(delta_seconds as f32, cx);
+            }
             ElementEvent::Focus(false) => {
                 self.drag_state = None;
+
+                if let Some(kinetic) = self.kinetic.as_mut() {
+                    kinetic.content_drag = None;
+
+                    if kinetic.overscroll != Vector::zero() {
+                        cx.set_animating(true);
+                    }
+                }
             }
             _ => {}
         }
@@ -779,6 +998,7 @@ impl<A: Clone + 'static> Element<A> for ScrollAreaElement<A> {
                         color: style.back_quad_border_color,
                         width: style.back_quad_border_width,
                         radius: style.radius,
+                        edges: BorderEdges::NONE,
                     },
                     flags: style.quad_flags,
                 },
@@ -794,6 +1014,7 @@ impl<A: Clone + 'static> Element<A> for ScrollAreaElement<A> {
                             .back_quad_border_width_content_hover
                             .unwrap_or(style.back_quad_border_width),
                         radius: style.radius,
+                        edges: BorderEdges::NONE,
                     },
                     flags: style.quad_flags,
                 },
@@ -809,6 +1030,7 @@ impl<A: Clone + 'static> Element<A> for ScrollAreaElement<A> {
                             .back_quad_border_width_slider_hover
                             .unwrap_or(style.back_quad_border_width),
                         radius: style.radius,
+                        edges: BorderEdges::NONE,
                     },
                     flags: style.quad_flags,
                 },
@@ -819,7 +1041,10 @@ impl<A: Clone + 'static> Element<A> for ScrollAreaElement<A> {
             let bg_style = bg_style(self.vertical_state);
 
             if !bg_style.is_transparent() {
-                primitives.add(bg_style.create_primitive(self.sliders_state.vertical_bg_bounds));
+                primitives.add(bg_style.create_primitive_with_opacity(
+                    self.sliders_state.vertical_bg_bounds,
+                    cx.opacity,
+                ));
             }
         }
 
@@ -827,7 +1052,10 @@ impl<A: Clone + 'static> Element<A> for ScrollAreaElement<A> {
             let bg_style = bg_style(self.horizontal_state);
 
             if !bg_style.is_transparent() {
-                primitives.add(bg_style.create_primitive(self.sliders_state.horizontal_bg_bounds));
+                primitives.add(bg_style.create_primitive_with_opacity(
+                    self.sliders_state.horizontal_bg_bounds,
+                    cx.opacity,
+                ));
             }
         }
 
@@ -839,6 +1067,7 @@ impl<A: Clone + 'static> Element<A> for ScrollAreaElement<A> {
                         color: style.slider_border_color,
                         width: style.slider_border_width,
                         radius: style.radius,
+                        edges: BorderEdges::NONE,
                     },
                     flags: style.quad_flags,
                 },
@@ -852,6 +1081,7 @@ impl<A: Clone + 'static> Element<A> for ScrollAreaElement<A> {
                             .slider_border_width_content_hover
                             .unwrap_or(style.slider_border_width),
                         radius: style.radius,
+                        edges: BorderEdges::NONE,
                     },
                     flags: style.quad_flags,
                 },
@@ -871,6 +1101,7 @@ impl<A: Clone + 'static> Element<A> for ScrollAreaElement<A> {
                                 .unwrap_or(style.slider_border_width),
                         ),
                         radius: style.radius,
+                        edges: BorderEdges::NONE,
                     },
                     flags: style.quad_flags,
                 },
@@ -896,6 +1127,7 @@ impl<A: Clone + 'static> Element<A> for ScrollAreaElement<A> {
                             ),
                         ),
                         radius: style.radius,
+                        edges: BorderEdges::NONE,
                     },
                     flags: style.quad_flags,
                 },
@@ -907,8 +1139,10 @@ impl<A: Clone + 'static> Element<A> for ScrollAreaElement<A> {
 
             if !slider_style.is_transparent() {
                 primitives.set_z_index(1);
-                primitives
-                    .add(slider_style.create_primitive(self.sliders_state.vertical_slider_bounds));
+                primitives.add(slider_style.create_primitive_with_opacity(
+                    self.sliders_state.vertical_slider_bounds,
+                    cx.opacity,
+                ));
             }
         }
 
@@ -917,14 +1151,139 @@ impl<A: Clone + 'static> Element<A> for ScrollAreaElement<A> {
 
             if !slider_style.is_transparent() {
                 primitives.set_z_index(1);
-                primitives.add(
-                    slider_style.create_primitive(self.sliders_state.horizontal_slider_bounds),
-                );
+                primitives.add(slider_style.create_primitive_with_opacity(
+                    self.sliders_state.horizontal_slider_bounds,
+                    cx.opacity,
+                ));
             }
         }
     }
 }
 
+impl<A: Clone + 'static> ScrollAreaElement<A> {
+    fn on_content_dragged(&mut self, position: Point, cx: &mut ElementContext<'_, A>) {
+        let Some(kinetic) = self.kinetic.as_mut() else {
+            return;
+        };
+        let Some((prev_pos, prev_instant)) = kinetic.content_drag else {
+            return;
+        };
+
+        let now = Instant::now();
+
+        // Dragging the content up scrolls down.
+        let mut delta = prev_pos - position;
+        if !self.scroll_horizontally {
+            delta.x = 0.0;
+        }
+        if !self.scroll_vertically {
+            delta.y = 0.0;
+        }
+
+        let dt = (now - prev_instant).as_secs_f32();
+        if dt > 0.0 {
+            // Smooth the velocity so that a single jittery event doesn't
+            // dominate the fling.
+            kinetic.velocity = kinetic.velocity.lerp(delta / dt, 0.8);
+        }
+        kinetic.content_drag = Some((position, now));
+
+        if cx.res.style_system.reduce_motion() {
+            // Snap to the final position instead of gliding to it.
+            kinetic.velocity = Vector::zero();
+            kinetic.overscroll = Vector::zero();
+            cx.set_animating(false);
+
+            let offset = self.sliders_state.scroll_offset;
+            self.set_kinetic_scroll_offset(offset, cx);
+            return;
+        }
+
+        let mut offset = self.sliders_state.scroll_offset;
+        let mut overscroll = kinetic.overscroll;
+        let max_offset = self.sliders_state.max_scroll_offset;
+
+        if self.scroll_horizontally {
+            (offset.x, overscroll.x) =
+                kinetic.drag_axis(offset.x, overscroll.x, delta.x, max_offset.x);
+        }
+        if self.scroll_vertically {
+            (offset.y, overscroll.y) =
+                kinetic.drag_axis(offset.y, overscroll.y, delta.y, max_offset.y);
+        }
+
+        kinetic.overscroll = overscroll;
+
+        self.set_kinetic_scroll_offset(offset, cx);
+    }
+
+    fn on_kinetic_animation(&mut self, dt: f32, cx: &mut ElementContext<'_, A>) {
+        let Some(kinetic) = self.kinetic.as_mut() else {
+            cx.set_animating(false);
+            return;
+        };
+
+        let mut offset = self.sliders_state.scroll_offset;
+        let mut overscroll = kinetic.overscroll;
+        let mut velocity = kinetic.velocity;
+        let max_offset = self.sliders_state.max_scroll_offset;
+
+        (offset.x, overscroll.x, velocity.x) =
+            kinetic.step_axis(offset.x, overscroll.x, velocity.x, max_offset.x, dt);
+        (offset.y, overscroll.y, velocity.y) =
+            kinetic.step_axis(offset.y, overscroll.y, velocity.y, max_offset.y, dt);
+
+        kinetic.overscroll = overscroll;
+        kinetic.velocity = velocity;
+
+        if !kinetic.is_moving() {
+            kinetic.velocity = Vector::zero();
+            cx.set_animating(false);
+        }
+
+        self.set_kinetic_scroll_offset(offset, cx);
+    }
+
+    fn set_kinetic_scroll_offset(&mut self, offset: Vector, cx: &mut ElementContext<'_, A>) {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if self.sliders_state.scroll_offset != offset {
+            shared_state.scroll_offset = offset;
+
+            self.sliders_state = update_sliders_state(
+                cx.rect().size,
+                shared_state.content_size,
+                shared_state.scroll_offset,
+                self.slider_width,
+                self.scroll_horizontally,
+                self.scroll_vertically,
+                self.show_slider_when_content_fits,
+            );
+
+            if let Some(action) = self.scrolled_action.as_mut() {
+                cx.send_action((action)(shared_state.scroll_offset))
+                    .unwrap();
+            }
+        }
+
+        cx.request_repaint();
+
+        if let Some(scissor_rect) = self.control_scissor_rect {
+            let overscroll = self
+                .kinetic
+                .as_ref()
+                .map(|k| k.overscroll)
+                .unwrap_or_default();
+
+            cx.update_scissor_rect(
+                scissor_rect,
+                None,
+                Some(shared_state.scroll_offset + overscroll),
+            );
+        }
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 enum ScrollBarState {
     Idle,
@@ -0,0 +1,580 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::derive::*;
+use crate::prelude::*;
+
+/// A seek gesture sent by a [`Scrubber`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScrubberUpdate {
+    /// The position to seek to in seconds.
+    pub position: f64,
+    /// The current state of the seek gesture.
+    ///
+    /// A click sends a `GestureStarted` update followed by a
+    /// `GestureFinished` update, and dragging sends `Gesturing` updates in
+    /// between.
+    pub gesture_state: GestureState,
+}
+
+impl ScrubberUpdate {
+    pub fn is_gesturing(&self) -> bool {
+        self.gesture_state.is_gesturing()
+    }
+}
+
+/// Format a position in seconds as `m:ss`, or as `h:mm:ss` if it is at
+/// least an hour.
+pub fn default_scrubber_format(seconds: f64) -> String {
+    let total = seconds.max(0.0).floor() as u64;
+    let (h, m, s) = (total / 3600, (total / 60) % 60, total % 60);
+
+    if h > 0 {
+        format!("{}:{:02}:{:02}", h, m, s)
+    } else {
+        format!("{}:{:02}", m, s)
+    }
+}
+
+/// The style of a [`Scrubber`] element.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScrubberStyle {
+    /// The height of the track.
+    ///
+    /// By default this is set to `4.0`.
+    pub track_height: f32,
+    pub track_quad: QuadStyle,
+    /// The quad of the part of the track before the playhead.
+    pub progress_quad: QuadStyle,
+    pub progress_quad_disabled: Option<QuadStyle>,
+
+    /// The diameter of the playhead.
+    ///
+    /// By default this is set to `12.0`.
+    pub playhead_size: f32,
+    pub playhead_quad: QuadStyle,
+    /// Whether the playhead is only shown while the scrubber is hovered or
+    /// being dragged.
+    ///
+    /// By default this is set to `false`.
+    pub playhead_on_hover_only: bool,
+
+    /// Where to align the timestamp tooltip relative to this element.
+    ///
+    /// By default this is set to `Align2::TOP_CENTER`.
+    pub tooltip_align: Align2,
+}
+
+impl Default for ScrubberStyle {
+    fn default() -> Self {
+        Self {
+            track_height: 4.0,
+            track_quad: QuadStyle {
+                bg: Background::Solid(gray(60)),
+                border: BorderStyle {
+                    radius: radius(2.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            progress_quad: QuadStyle {
+                bg: Background::Solid(rgb(80, 160, 230)),
+                border: BorderStyle {
+                    radius: radius(2.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            progress_quad_disabled: Some(QuadStyle {
+                bg: Background::Solid(gray(90)),
+                border: BorderStyle {
+                    radius: radius(2.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }),
+            playhead_size: 12.0,
+            playhead_quad: QuadStyle {
+                bg: Background::Solid(color::WHITE),
+                border: BorderStyle {
+                    radius: Radius::CIRCLE,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            playhead_on_hover_only: false,
+            tooltip_align: Align2::TOP_CENTER,
+        }
+    }
+}
+
+impl ElementStyle for ScrubberStyle {
+    const ID: &'static str = "scrubber";
+
+    fn default_dark_style() -> Self {
+        Self::default()
+    }
+
+    fn default_light_style() -> Self {
+        Self {
+            track_quad: QuadStyle {
+                bg: Background::Solid(gray(200)),
+                border: BorderStyle {
+                    radius: radius(2.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            progress_quad_disabled: Some(QuadStyle {
+                bg: Background::Solid(gray(160)),
+                border: BorderStyle {
+                    radius: radius(2.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }),
+            playhead_quad: QuadStyle {
+                bg: Background::Solid(color::WHITE),
+                border: BorderStyle {
+                    color: gray(150),
+                    width: 1.0,
+                    radius: Radius::CIRCLE,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+}
+
+#[element_builder]
+#[element_builder_class]
+#[element_builder_rect]
+#[element_builder_hidden]
+#[element_builder_disabled]
+pub struct ScrubberBuilder<A: Clone + 'static> {
+    pub seek_action: Option<Box<dyn FnMut(ScrubberUpdate) -> A>>,
+    pub position: f64,
+    pub duration: f64,
+    pub format: Box<dyn Fn(f64) -> String>,
+}
+
+impl<A: Clone + 'static> Default for ScrubberBuilder<A> {
+    fn default() -> Self {
+        Self {
+            seek_action: None,
+            position: 0.0,
+            duration: 0.0,
+            format: Box::new(default_scrubber_format),
+            class: None,
+            z_index: None,
+            rect: Rect::default(),
+            manually_hidden: false,
+            disabled: false,
+            scissor_rect: None,
+            key: None,
+        }
+    }
+}
+
+impl<A: Clone + 'static> ScrubberBuilder<A> {
+    /// The action sent when the user seeks by clicking or dragging.
+    pub fn on_seek<F: FnMut(ScrubberUpdate) -> A + 'static>(mut self, f: F) -> Self {
+        self.seek_action = Some(Box::new(f));
+        self
+    }
+
+    /// The initial playback position in seconds.
+    pub const fn position(mut self, position: f64) -> Self {
+        self.position = position;
+        self
+    }
+
+    /// The total duration in seconds.
+    pub const fn duration(mut self, duration: f64) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// The function used to format positions (in seconds) in the hover
+    /// tooltip.
+    ///
+    /// By default this is set to [`default_scrubber_format`].
+    pub fn format<F: Fn(f64) -> String + 'static>(mut self, f: F) -> Self {
+        self.format = Box::new(f);
+        self
+    }
+
+    pub fn build(self, window_cx: &mut WindowContext<'_, A>) -> Scrubber {
+        let ScrubberBuilder {
+            seek_action,
+            position,
+            duration,
+            format,
+            class,
+            z_index,
+            rect,
+            manually_hidden,
+            disabled,
+            scissor_rect,
+            key,
+        } = self;
+
+        let duration = duration.max(0.0);
+
+        let shared_state = Rc::new(RefCell::new(SharedState {
+            position: position.clamp(0.0, duration),
+            duration,
+            disabled,
+            gesturing: false,
+        }));
+
+        let el = ElementBuilder::new(ScrubberElement {
+            shared_state: Rc::clone(&shared_state),
+            seek_action,
+            format,
+            scrub_position: None,
+            hovered: false,
+            tooltip_text: String::new(),
+        })
+        .builder_values(z_index, scissor_rect, class, window_cx)
+        .key(key)
+        .rect(rect)
+        .hidden(manually_hidden)
+        .flags(ElementFlags::PAINTS | ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS)
+        .build(window_cx);
+
+        Scrubber { el, shared_state }
+    }
+}
+
+/// The span of the track along which the center of the playhead moves.
+fn track_span(bounds: Rect, style: &ScrubberStyle) -> (f32, f32) {
+    let inset = (style.playhead_size * 0.5).min(bounds.width() * 0.5);
+    (bounds.min_x() + inset, bounds.max_x() - inset)
+}
+
+/// A thin bar with a draggable playhead for seeking.
+struct ScrubberElement<A: Clone + 'static> {
+    shared_state: Rc<RefCell<SharedState>>,
+    seek_action: Option<Box<dyn FnMut(ScrubberUpdate) -> A>>,
+    format: Box<dyn Fn(f64) -> String>,
+    /// The position under the pointer while the user is scrubbing.
+    scrub_position: Option<f64>,
+    hovered: bool,
+    tooltip_text: String,
+}
+
+impl<A: Clone + 'static> ScrubberElement<A> {
+    fn position_at(&self, x: f32, duration: f64, bounds: Rect, style: &ScrubberStyle) -> f64 {
+        let (min_x, max_x) = track_span(bounds, style);
+
+        if max_x <= min_x {
+            return 0.0;
+        }
+
+        let normal = ((x - min_x) / (max_x - min_x)).clamp(0.0, 1.0);
+        normal as f64 * duration
+    }
+
+    fn show_timestamp(&mut self, position: f64, cx: &mut ElementContext<'_, A>) {
+        let text = (self.format)(position);
+
+        if self.tooltip_text != text {
+            let align = cx
+                .res
+                .style_system
+                .get::<ScrubberStyle>(cx.class())
+                .tooltip_align;
+            cx.show_tooltip(TooltipData::new(text.clone(), align), true);
+            self.tooltip_text = text;
+        }
+    }
+
+    fn send_seek(
+        &mut self,
+        position: f64,
+        gesture_state: GestureState,
+        cx: &mut ElementContext<'_, A>,
+    ) {
+        if let Some(action) = self.seek_action.as_mut() {
+            cx.send_action((action)(ScrubberUpdate {
+                position,
+                gesture_state,
+            }))
+            .unwrap();
+        }
+    }
+
+    fn finish_gesture(&mut self, shared_state: &mut SharedState, cx: &mut ElementContext<'_, A>) {
+        if let Some(position) = self.scrub_position.take() {
+            shared_state.gesturing = false;
+            shared_state.position = position;
+
+            cx.release_pointer();
+            self.send_seek(position, GestureState::GestureFinished, cx);
+            cx.request_repaint();
+        }
+    }
+}
+
+impl<A: Clone + 'static> Element<A> for ScrubberElement<A> {
+    fn on_event(
+        &mut self,
+        event: ElementEvent,
+        cx: &mut ElementContext<'_, A>,
+    ) -> EventCaptureStatus {
+        let shared_state_rc = Rc::clone(&self.shared_state);
+        let mut shared_state = RefCell::borrow_mut(&shared_state_rc);
+
+        match event {
+            ElementEvent::CustomStateChanged => {
+                if shared_state.disabled {
+                    self.finish_gesture(&mut shared_state, cx);
+                    self.hovered = false;
+                }
+
+                cx.request_repaint();
+            }
+            ElementEvent::StyleChanged => {
+                cx.request_repaint();
+            }
+            ElementEvent::Pointer(PointerEvent::Moved { position, .. }) => {
+                if shared_state.disabled {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                if !self.hovered {
+                    self.hovered = true;
+                    cx.request_repaint();
+                }
+
+                let style = cx.res.style_system.get::<ScrubberStyle>(cx.class());
+                let pointer_position =
+                    self.position_at(position.x, shared_state.duration, cx.rect(), style);
+
+                if let Some(scrub_position) = self.scrub_position {
+                    if scrub_position != pointer_position {
+                        self.scrub_position = Some(pointer_position);
+                        self.send_seek(pointer_position, GestureState::Gesturing, cx);
+                        cx.request_repaint();
+                    }
+                }
+
+                self.show_timestamp(pointer_position, cx);
+
+                return EventCaptureStatus::Captured;
+            }
+            ElementEvent::Pointer(PointerEvent::PointerLeft) => {
+                if self.hovered {
+                    self.hovered = false;
+                    self.tooltip_text.clear();
+                    cx.request_repaint();
+                }
+            }
+            ElementEvent::Pointer(PointerEvent::ButtonJustPressed {
+                position, button, ..
+            }) => {
+                if shared_state.disabled || button != PointerButton::Primary {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                let style = cx.res.style_system.get::<ScrubberStyle>(cx.class());
+                let pointer_position =
+                    self.position_at(position.x, shared_state.duration, cx.rect(), style);
+
+                self.scrub_position = Some(pointer_position);
+                shared_state.gesturing = true;
+
+                cx.capture_pointer();
+                self.send_seek(pointer_position, GestureState::GestureStarted, cx);
+                self.show_timestamp(pointer_position, cx);
+                cx.request_repaint();
+
+                return EventCaptureStatus::Captured;
+            }
+            ElementEvent::Pointer(PointerEvent::ButtonJustReleased { button, .. }) => {
+                if button == PointerButton::Primary && self.scrub_position.is_some() {
+                    self.finish_gesture(&mut shared_state, cx);
+                    return EventCaptureStatus::Captured;
+                }
+            }
+            ElementEvent::Focus(false) | ElementEvent::Hidden => {
+                self.finish_gesture(&mut shared_state, cx);
+            }
+            _ => {}
+        }
+
+        EventCaptureStatus::NotCaptured
+    }
+
+    fn render(&mut self, cx: RenderContext, primitives: &mut PrimitiveGroup) {
+        let shared_state = RefCell::borrow(&self.shared_state);
+        let style = cx.res.style_system.get::<ScrubberStyle>(cx.class);
+
+        let bounds = Rect::from_size(cx.bounds_size);
+        let (min_x, max_x) = track_span(bounds, style);
+        let center_y = bounds.center().y;
+
+        let track = Rect::new(
+            Point::new(min_x, center_y - (style.track_height * 0.5)),
+            Size::new((max_x - min_x).max(0.0), style.track_height),
+        );
+
+        if !style.track_quad.is_transparent() {
+            primitives.add(
+                style
+                    .track_quad
+                    .create_primitive_with_opacity(track, cx.opacity),
+            );
+        }
+
+        let position = self.scrub_position.unwrap_or(shared_state.position);
+        let normal = if shared_state.duration > 0.0 {
+            (position / shared_state.duration).clamp(0.0, 1.0) as f32
+        } else {
+            0.0
+        };
+        let playhead_x = min_x + (track.width() * normal);
+
+        let progress_quad = if shared_state.disabled {
+            style
+                .progress_quad_disabled
+                .as_ref()
+                .unwrap_or(&style.progress_quad)
+        } else {
+            &style.progress_quad
+        };
+
+        if playhead_x > min_x && !progress_quad.is_transparent() {
+            primitives.add(progress_quad.create_primitive_with_opacity(
+                Rect::new(track.origin, Size::new(playhead_x - min_x, track.height())),
+                cx.opacity,
+            ));
+        }
+
+        let show_playhead = !shared_state.disabled
+            && (!style.playhead_on_hover_only || self.hovered || self.scrub_position.is_some());
+
+        if show_playhead && !style.playhead_quad.is_transparent() {
+            primitives.set_z_index(1);
+            primitives.add(style.playhead_quad.create_primitive_with_opacity(
+                centered_rect(
+                    Point::new(playhead_x, center_y),
+                    Size::new(style.playhead_size, style.playhead_size),
+                ),
+                cx.opacity,
+            ));
+        }
+    }
+}
+
+struct SharedState {
+    position: f64,
+    duration: f64,
+    disabled: bool,
+    gesturing: bool,
+}
+
+/// A handle to a [`ScrubberElement`], a thin progress bar with a draggable
+/// playhead for seeking in a piece of media.
+///
+/// Clicking the bar seeks to that position, and dragging it scrubs through
+/// the media. Hovering the bar shows the timestamp under the pointer in a
+/// tooltip. While the user is scrubbing, calls to [`Scrubber::set_position`]
+/// do not move the playhead.
+#[element_handle]
+#[element_handle_class]
+#[element_handle_set_rect]
+#[element_handle_layout_aligned]
+pub struct Scrubber {
+    shared_state: Rc<RefCell<SharedState>>,
+}
+
+impl Scrubber {
+    pub fn builder<A: Clone + 'static>() -> ScrubberBuilder<A> {
+        ScrubberBuilder::default()
+    }
+
+    /// Set the playback position in seconds.
+    ///
+    /// Returns `true` if the position has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn set_position(&mut self, position: f64) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        let position = position.clamp(0.0, shared_state.duration);
+
+        if shared_state.position != position {
+            shared_state.position = position;
+
+            if !shared_state.gesturing {
+                self.el.notify_custom_state_change();
+            }
+
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn position(&self) -> f64 {
+        RefCell::borrow(&self.shared_state).position
+    }
+
+    /// Set the total duration in seconds. The position is clamped to the new
+    /// duration.
+    ///
+    /// Returns `true` if the duration has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn set_duration(&mut self, duration: f64) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        let duration = duration.max(0.0);
+
+        if shared_state.duration != duration {
+            shared_state.duration = duration;
+            shared_state.position = shared_state.position.min(duration);
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn duration(&self) -> f64 {
+        RefCell::borrow(&self.shared_state).duration
+    }
+
+    /// Whether or not the user is currently scrubbing.
+    pub fn is_gesturing(&self) -> bool {
+        RefCell::borrow(&self.shared_state).gesturing
+    }
+
+    pub fn disabled(&self) -> bool {
+        RefCell::borrow(&self.shared_state).disabled
+    }
+
+    /// Set the disabled state of this element.
+    ///
+    /// Returns `true` if the disabled state has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn set_disabled(&mut self, disabled: bool) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if shared_state.disabled != disabled {
+            shared_state.disabled = disabled;
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+}
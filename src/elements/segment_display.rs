@@ -0,0 +1,675 @@
+use std::cell::{Ref, RefCell};
+use std::rc::Rc;
+
+use crate::derive::*;
+use crate::prelude::*;
+use crate::vg::quad::{SolidQuadBuilder, SolidQuadPrimitive};
+
+/// How the characters of a [`SegmentDisplay`] are drawn.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SegmentDisplayMode {
+    /// Each character is drawn with seven bar segments, like an LED or LCD
+    /// readout.
+    #[default]
+    SevenSegment,
+    /// Each character is drawn on a 5x7 grid of dots.
+    DotMatrix,
+}
+
+/// The style of a [`SegmentDisplay`] element.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SegmentDisplayStyle {
+    pub back_quad: QuadStyle,
+
+    pub mode: SegmentDisplayMode,
+
+    /// The size of a single character cell.
+    ///
+    /// By default this is set to `Size::new(14.0, 24.0)`.
+    pub digit_size: Size,
+    /// The spacing between character cells.
+    ///
+    /// By default this is set to `4.0`.
+    pub digit_spacing: f32,
+    /// The thickness of a segment in `SegmentDisplayMode::SevenSegment`
+    /// mode. This is also the size of the dots of `.` and `:` characters
+    /// in that mode.
+    ///
+    /// By default this is set to `3.0`.
+    pub segment_thickness: f32,
+    /// The gap between adjacent segments (in `SegmentDisplayMode::SevenSegment`
+    /// mode) or between adjacent dots (in `SegmentDisplayMode::DotMatrix`
+    /// mode).
+    ///
+    /// By default this is set to `1.0`.
+    pub segment_gap: f32,
+
+    /// The color of lit segments.
+    pub segment_color: RGBA8,
+    pub segment_color_disabled: DisabledColor,
+    /// The color of unlit "ghost" segments. Set this to
+    /// `color::TRANSPARENT` to hide unlit segments.
+    pub ghost_color: RGBA8,
+
+    /// The padding between the edges of the element and the characters.
+    ///
+    /// By default this is set to `padding_all_same(6.0)`.
+    pub padding: Padding,
+}
+
+impl Default for SegmentDisplayStyle {
+    fn default() -> Self {
+        Self {
+            back_quad: QuadStyle {
+                bg: Background::Solid(gray(14)),
+                border: BorderStyle {
+                    radius: radius(3.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            mode: SegmentDisplayMode::SevenSegment,
+            digit_size: Size::new(14.0, 24.0),
+            digit_spacing: 4.0,
+            segment_thickness: 3.0,
+            segment_gap: 1.0,
+            segment_color: rgb(255, 150, 40),
+            segment_color_disabled: Default::default(),
+            ghost_color: rgba(255, 150, 40, 22),
+            padding: padding_all_same(6.0),
+        }
+    }
+}
+
+impl ElementStyle for SegmentDisplayStyle {
+    const ID: &'static str = "segdisp";
+
+    fn default_dark_style() -> Self {
+        Self::default()
+    }
+
+    fn default_light_style() -> Self {
+        Self::default()
+    }
+
+    fn with_auto_disabled(&self, derivation: DisabledDerivation) -> Option<Self> {
+        let mut style = self.clone();
+        style.segment_color_disabled.derive_auto(derivation);
+        Some(style)
+    }
+}
+
+#[element_builder]
+#[element_builder_class]
+#[element_builder_rect]
+#[element_builder_hidden]
+#[element_builder_disabled]
+#[derive(Default)]
+pub struct SegmentDisplayBuilder {
+    pub text: String,
+    pub min_digits: usize,
+}
+
+impl SegmentDisplayBuilder {
+    /// The text to display.
+    ///
+    /// Digits, `-`, `_`, `.`, `:`, and space are supported in both modes. A
+    /// limited set of letters (such as the hex digits and note names) is
+    /// also supported. Unsupported characters are drawn as blank cells.
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.text = text.into();
+        self
+    }
+
+    /// The minimum number of character cells (not counting `.` and `:`). If
+    /// the text is shorter, then it is padded with blank cells on the left
+    /// (which still show their ghost segments).
+    ///
+    /// By default this is set to `0`.
+    pub const fn min_digits(mut self, min_digits: usize) -> Self {
+        self.min_digits = min_digits;
+        self
+    }
+
+    pub fn build<A: Clone + 'static>(self, window_cx: &mut WindowContext<'_, A>) -> SegmentDisplay {
+        let SegmentDisplayBuilder {
+            text,
+            min_digits,
+            class,
+            z_index,
+            rect,
+            manually_hidden,
+            disabled,
+            scissor_rect,
+            key,
+        } = self;
+
+        let shared_state = Rc::new(RefCell::new(SharedState {
+            cells: parse_cells(&text, min_digits),
+            text,
+            min_digits,
+            disabled,
+        }));
+
+        let el = ElementBuilder::new(SegmentDisplayElement {
+            shared_state: Rc::clone(&shared_state),
+        })
+        .builder_values(z_index, scissor_rect, class, window_cx)
+        .key(key)
+        .rect(rect)
+        .hidden(manually_hidden)
+        .flags(ElementFlags::PAINTS)
+        .build(window_cx);
+
+        SegmentDisplay { el, shared_state }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Cell {
+    Glyph(char),
+    Dot,
+    Colon,
+}
+
+fn parse_cells(text: &str, min_digits: usize) -> Vec<Cell> {
+    let cells: Vec<Cell> = text
+        .chars()
+        .map(|c| match c {
+            '.' | ',' => Cell::Dot,
+            ':' => Cell::Colon,
+            c => Cell::Glyph(c),
+        })
+        .collect();
+
+    let num_glyphs = cells
+        .iter()
+        .filter(|cell| matches!(cell, Cell::Glyph(_)))
+        .count();
+
+    std::iter::repeat(Cell::Glyph(' '))
+        .take(min_digits.saturating_sub(num_glyphs))
+        .chain(cells)
+        .collect()
+}
+
+const SEG_A: u8 = 1 << 0;
+const SEG_B: u8 = 1 << 1;
+const SEG_C: u8 = 1 << 2;
+const SEG_D: u8 = 1 << 3;
+const SEG_E: u8 = 1 << 4;
+const SEG_F: u8 = 1 << 5;
+const SEG_G: u8 = 1 << 6;
+
+/// The lit segments of a character, where bit 0 is the top segment and the
+/// remaining segments go clockwise, with the middle segment last.
+fn seven_segment_mask(c: char) -> u8 {
+    match c {
+        '0' | 'O' => 0x3F,
+        '1' => 0x06,
+        '2' => 0x5B,
+        '3' => 0x4F,
+        '4' => 0x66,
+        '5' | 'S' | 's' => 0x6D,
+        '6' => 0x7D,
+        '7' => 0x07,
+        '8' => 0x7F,
+        '9' => 0x6F,
+        'A' | 'a' => 0x77,
+        'B' | 'b' => 0x7C,
+        'C' => 0x39,
+        'c' => 0x58,
+        'D' | 'd' => 0x5E,
+        'E' | 'e' => 0x79,
+        'F' | 'f' => 0x71,
+        'G' | 'g' => 0x3D,
+        'H' => 0x76,
+        'h' => 0x74,
+        'I' | 'i' => 0x30,
+        'J' | 'j' => 0x1E,
+        'L' | 'l' => 0x38,
+        'N' | 'n' => 0x54,
+        'o' => 0x5C,
+        'P' | 'p' => 0x73,
+        'R' | 'r' => 0x50,
+        'T' | 't' => 0x78,
+        'U' => 0x3E,
+        'u' => 0x1C,
+        'Y' | 'y' => 0x6E,
+        '-' => 0x40,
+        '_' => 0x08,
+        '=' => 0x48,
+        '°' => 0x63,
+        _ => 0,
+    }
+}
+
+/// The rows of a character on a 5x7 dot grid (from top to bottom), where
+/// bit 4 is the leftmost dot.
+fn dot_matrix_rows(c: char) -> [u8; 7] {
+    match c {
+        '0' => [0x0E, 0x11, 0x13, 0x15, 0x19, 0x11, 0x0E],
+        '1' => [0x04, 0x0C, 0x04, 0x04, 0x04, 0x04, 0x0E],
+        '2' => [0x0E, 0x11, 0x01, 0x02, 0x04, 0x08, 0x1F],
+        '3' => [0x1F, 0x02, 0x04, 0x02, 0x01, 0x11, 0x0E],
+        '4' => [0x02, 0x06, 0x0A, 0x12, 0x1F, 0x02, 0x02],
+        '5' => [0x1F, 0x10, 0x1E, 0x01, 0x01, 0x11, 0x0E],
+        '6' => [0x06, 0x08, 0x10, 0x1E, 0x11, 0x11, 0x0E],
+        '7' => [0x1F, 0x01, 0x02, 0x04, 0x08, 0x08, 0x08],
+        '8' => [0x0E, 0x11, 0x11, 0x0E, 0x11, 0x11, 0x0E],
+        '9' => [0x0E, 0x11, 0x11, 0x0F, 0x01, 0x02, 0x0C],
+        'A' | 'a' => [0x0E, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11],
+        'B' => [0x1E, 0x11, 0x11, 0x1E, 0x11, 0x11, 0x1E],
+        'b' => [0x10, 0x10, 0x16, 0x19, 0x11, 0x11, 0x1E],
+        'C' | 'c' => [0x0E, 0x11, 0x10, 0x10, 0x10, 0x11, 0x0E],
+        'D' | 'd' => [0x1C, 0x12, 0x11, 0x11, 0x11, 0x12, 0x1C],
+        'E' | 'e' => [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x1F],
+        'F' | 'f' => [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x10],
+        'G' | 'g' => [0x0E, 0x11, 0x10, 0x17, 0x11, 0x11, 0x0F],
+        'H' | 'h' => [0x11, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11],
+        'I' | 'i' => [0x0E, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0E],
+        'L' | 'l' => [0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x1F],
+        'N' | 'n' => [0x11, 0x11, 0x19, 0x15, 0x13, 0x11, 0x11],
+        'O' | 'o' => [0x0E, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E],
+        'P' | 'p' => [0x1E, 0x11, 0x11, 0x1E, 0x10, 0x10, 0x10],
+        'R' | 'r' => [0x1E, 0x11, 0x11, 0x1E, 0x14, 0x12, 0x11],
+        'S' | 's' => [0x0F, 0x10, 0x10, 0x0E, 0x01, 0x01, 0x1E],
+        'T' | 't' => [0x1F, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04],
+        'U' | 'u' => [0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E],
+        '#' => [0x0A, 0x0A, 0x1F, 0x0A, 0x1F, 0x0A, 0x0A],
+        '-' => [0x00, 0x00, 0x00, 0x1F, 0x00, 0x00, 0x00],
+        '+' => [0x00, 0x04, 0x04, 0x1F, 0x04, 0x04, 0x00],
+        '_' => [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1F],
+        '=' => [0x00, 0x00, 0x1F, 0x00, 0x1F, 0x00, 0x00],
+        '°' => [0x0C, 0x12, 0x12, 0x0C, 0x00, 0x00, 0x00],
+        _ => [0; 7],
+    }
+}
+
+const DOT_COLUMNS: usize = 5;
+const DOT_ROWS: usize = 7;
+
+fn cell_width(cell: Cell, style: &SegmentDisplayStyle) -> f32 {
+    match (cell, style.mode) {
+        (Cell::Glyph(_), _) => style.digit_size.width,
+        (_, SegmentDisplayMode::SevenSegment) => style.segment_thickness,
+        (_, SegmentDisplayMode::DotMatrix) => style.digit_size.width / DOT_COLUMNS as f32,
+    }
+}
+
+fn content_size(cells: &[Cell], style: &SegmentDisplayStyle) -> Size {
+    if cells.is_empty() {
+        return Size::new(0.0, style.digit_size.height);
+    }
+
+    let width = cells
+        .iter()
+        .map(|cell| cell_width(*cell, style))
+        .sum::<f32>()
+        + (style.digit_spacing * (cells.len() - 1) as f32);
+
+    Size::new(width, style.digit_size.height)
+}
+
+/// A numeric readout drawn with seven-segment or dot-matrix characters.
+struct SegmentDisplayElement {
+    shared_state: Rc<RefCell<SharedState>>,
+}
+
+impl SegmentDisplayElement {
+    fn add_seven_segment(
+        &self,
+        cell: Cell,
+        origin: Point,
+        style: &SegmentDisplayStyle,
+        on_color: RGBA8,
+        opacity: f32,
+        quads: &mut Vec<SolidQuadPrimitive>,
+    ) {
+        let t = style.segment_thickness;
+        let w = style.digit_size.width;
+        let h = style.digit_size.height;
+
+        let mut add = |rect: Rect, color: RGBA8| {
+            if color.a == 0 || rect.is_empty() {
+                return;
+            }
+
+            quads.push(
+                SolidQuadBuilder::new(rect.size)
+                    .bg_color(apply_opacity(color, opacity))
+                    .position(rect.origin)
+                    .into(),
+            );
+        };
+
+        match cell {
+            Cell::Glyph(c) => {
+                let mask = seven_segment_mask(c);
+                let g = style.segment_gap;
+                let half = h * 0.5;
+
+                let horizontal_width = (w - (2.0 * t) - (2.0 * g)).max(0.0);
+                let vertical_height = (half - (t * 0.5) - (2.0 * g)).max(0.0);
+
+                let segments = [
+                    (
+                        SEG_A,
+                        Rect::new(Point::new(t + g, 0.0), Size::new(horizontal_width, t)),
+                    ),
+                    (
+                        SEG_B,
+                        Rect::new(
+                            Point::new(w - t, (t * 0.5) + g),
+                            Size::new(t, vertical_height),
+                        ),
+                    ),
+                    (
+                        SEG_C,
+                        Rect::new(Point::new(w - t, half + g), Size::new(t, vertical_height)),
+                    ),
+                    (
+                        SEG_D,
+                        Rect::new(Point::new(t + g, h - t), Size::new(horizontal_width, t)),
+                    ),
+                    (
+                        SEG_E,
+                        Rect::new(Point::new(0.0, half + g), Size::new(t, vertical_height)),
+                    ),
+                    (
+                        SEG_F,
+                        Rect::new(
+                            Point::new(0.0, (t * 0.5) + g),
+                            Size::new(t, vertical_height),
+                        ),
+                    ),
+                    (
+                        SEG_G,
+                        Rect::new(
+                            Point::new(t + g, half - (t * 0.5)),
+                            Size::new(horizontal_width, t),
+                        ),
+                    ),
+                ];
+
+                for (segment, rect) in segments {
+                    let color = if mask & segment != 0 {
+                        on_color
+                    } else {
+                        style.ghost_color
+                    };
+
+                    add(rect.translate(origin.to_vector()), color);
+                }
+            }
+            Cell::Dot => {
+                add(
+                    Rect::new(Point::new(origin.x, origin.y + h - t), Size::new(t, t)),
+                    on_color,
+                );
+            }
+            Cell::Colon => {
+                for y in [h / 3.0, h * 2.0 / 3.0] {
+                    add(
+                        Rect::new(
+                            Point::new(origin.x, origin.y + y - (t * 0.5)),
+                            Size::new(t, t),
+                        ),
+                        on_color,
+                    );
+                }
+            }
+        }
+    }
+
+    fn add_dot_matrix(
+        &self,
+        cell: Cell,
+        origin: Point,
+        style: &SegmentDisplayStyle,
+        on_color: RGBA8,
+        opacity: f32,
+        quads: &mut Vec<SolidQuadPrimitive>,
+    ) {
+        let pitch = Size::new(
+            style.digit_size.width / DOT_COLUMNS as f32,
+            style.digit_size.height / DOT_ROWS as f32,
+        );
+        let dot_size = (pitch.width.min(pitch.height) - style.segment_gap).max(0.0);
+        if dot_size <= 0.0 {
+            return;
+        }
+
+        let mut add = |column: usize, row: usize, color: RGBA8| {
+            if color.a == 0 {
+                return;
+            }
+
+            let center = Point::new(
+                origin.x + (pitch.width * (column as f32 + 0.5)),
+                origin.y + (pitch.height * (row as f32 + 0.5)),
+            );
+
+            quads.push(
+                SolidQuadBuilder::new(Size::new(dot_size, dot_size))
+                    .bg_color(apply_opacity(color, opacity))
+                    .position(Point::new(
+                        center.x - (dot_size * 0.5),
+                        center.y - (dot_size * 0.5),
+                    ))
+                    .into(),
+            );
+        };
+
+        match cell {
+            Cell::Glyph(c) => {
+                let rows = dot_matrix_rows(c);
+
+                for (row, bits) in rows.iter().enumerate() {
+                    for column in 0..DOT_COLUMNS {
+                        let lit = bits & (1 << (DOT_COLUMNS - 1 - column)) != 0;
+                        add(column, row, if lit { on_color } else { style.ghost_color });
+                    }
+                }
+            }
+            Cell::Dot => add(0, DOT_ROWS - 1, on_color),
+            Cell::Colon => {
+                add(0, 2, on_color);
+                add(0, 4, on_color);
+            }
+        }
+    }
+}
+
+impl<A: Clone + 'static> Element<A> for SegmentDisplayElement {
+    fn on_event(
+        &mut self,
+        event: ElementEvent,
+        cx: &mut ElementContext<'_, A>,
+    ) -> EventCaptureStatus {
+        match event {
+            ElementEvent::CustomStateChanged | ElementEvent::StyleChanged => {
+                cx.request_repaint();
+            }
+            _ => {}
+        }
+
+        EventCaptureStatus::NotCaptured
+    }
+
+    fn render(&mut self, cx: RenderContext, primitives: &mut PrimitiveGroup) {
+        let shared_state = RefCell::borrow(&self.shared_state);
+        let style = cx.res.style_system.get::<SegmentDisplayStyle>(cx.class);
+
+        let bounds = Rect::from_size(cx.bounds_size);
+
+        if !style.back_quad.is_transparent() {
+            primitives.add(
+                style
+                    .back_quad
+                    .create_primitive_with_opacity(bounds, cx.opacity),
+            );
+        }
+
+        let on_color = if shared_state.disabled {
+            style.segment_color_disabled.get(style.segment_color)
+        } else {
+            style.segment_color
+        };
+
+        // Right-align the characters like a hardware readout.
+        let content_size = content_size(&shared_state.cells, style);
+        let mut x =
+            (bounds.max_x() - style.padding.right - content_size.width).max(style.padding.left);
+        let y = bounds.center().y - (content_size.height * 0.5);
+
+        let mut quads: Vec<SolidQuadPrimitive> = Vec::new();
+
+        for cell in shared_state.cells.iter().copied() {
+            let origin = Point::new(x, y);
+
+            match style.mode {
+                SegmentDisplayMode::SevenSegment => {
+                    self.add_seven_segment(cell, origin, style, on_color, cx.opacity, &mut quads)
+                }
+                SegmentDisplayMode::DotMatrix => {
+                    self.add_dot_matrix(cell, origin, style, on_color, cx.opacity, &mut quads)
+                }
+            }
+
+            x += cell_width(cell, style) + style.digit_spacing;
+        }
+
+        primitives.set_z_index(1);
+        primitives.add_solid_quad_batch(quads);
+    }
+}
+
+struct SharedState {
+    text: String,
+    min_digits: usize,
+    cells: Vec<Cell>,
+    disabled: bool,
+}
+
+/// A handle to a [`SegmentDisplayElement`], a numeric readout (i.e. for a
+/// tuner or a clock) drawn with seven-segment or dot-matrix characters.
+///
+/// The characters are drawn with quads, so no font is needed. Use classes
+/// to switch between colors (i.e. a red class for clipping values).
+#[element_handle]
+#[element_handle_class]
+#[element_handle_set_rect]
+pub struct SegmentDisplay {
+    shared_state: Rc<RefCell<SharedState>>,
+}
+
+impl SegmentDisplay {
+    pub fn builder() -> SegmentDisplayBuilder {
+        SegmentDisplayBuilder::default()
+    }
+
+    /// The size needed to show the current text (including padding).
+    pub fn desired_size(&self, res: &mut ResourceCtx) -> Size {
+        let style = res.style_system.get::<SegmentDisplayStyle>(self.el.class());
+        let size = content_size(&RefCell::borrow(&self.shared_state).cells, style);
+
+        Size::new(
+            size.width + style.padding.left + style.padding.right,
+            size.height + style.padding.top + style.padding.bottom,
+        )
+    }
+
+    /// Set the text to display.
+    ///
+    /// Returns `true` if the text has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed.
+    /// However, this method still involves a string comparison so you may want
+    /// to call this method sparingly.
+    pub fn set_text<T: AsRef<str> + Into<String>>(&mut self, text: T) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if shared_state.text.as_str() != text.as_ref() {
+            shared_state.text = text.into();
+            shared_state.cells = parse_cells(&shared_state.text, shared_state.min_digits);
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Display a number with the given number of digits after the decimal
+    /// point.
+    ///
+    /// Returns `true` if the displayed text has changed.
+    pub fn set_number(&mut self, value: f64, precision: usize) -> bool {
+        self.set_text(format!("{:.*}", precision, value))
+    }
+
+    pub fn text<'a>(&'a self) -> Ref<'a, str> {
+        Ref::map(RefCell::borrow(&self.shared_state), |s| s.text.as_str())
+    }
+
+    /// Set the minimum number of character cells. See
+    /// [`SegmentDisplayBuilder::min_digits`].
+    ///
+    /// Returns `true` if the value has changed.
+    pub fn set_min_digits(&mut self, min_digits: usize) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if shared_state.min_digits != min_digits {
+            shared_state.min_digits = min_digits;
+            shared_state.cells = parse_cells(&shared_state.text, min_digits);
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn disabled(&self) -> bool {
+        RefCell::borrow(&self.shared_state).disabled
+    }
+
+    /// Set the disabled state of this element.
+    ///
+    /// Returns `true` if the disabled state has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn set_disabled(&mut self, disabled: bool) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if shared_state.disabled != disabled {
+            shared_state.disabled = disabled;
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Layout out the element (with the top-left corner of the bounds set to `origin`).
+    ///
+    /// Returns `true` if the layout has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn layout(&mut self, origin: Point, res: &mut ResourceCtx) -> bool {
+        let size = self.desired_size(res);
+        self.el.set_rect(Rect::new(origin, size))
+    }
+
+    /// Layout out the element aligned to the given point.
+    ///
+    /// Returns `true` if the layout has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn layout_aligned(&mut self, point: Point, align: Align2, res: &mut ResourceCtx) -> bool {
+        let size = self.desired_size(res);
+        self.el.set_rect(align.align_rect_to_point(point, size))
+    }
+}
@@ -87,10 +87,12 @@ impl SeparatorBuilder {
             rect,
             manually_hidden,
             scissor_rect,
+            key,
         } = self;
 
         let el = ElementBuilder::new(SeparatorElement { vertical })
             .builder_values(z_index, scissor_rect, class, window_cx)
+            .key(key)
             .rect(rect)
             .hidden(manually_hidden)
             .flags(ElementFlags::PAINTS)
@@ -143,7 +145,9 @@ impl<A: Clone + 'static> Element<A> for SeparatorElement {
             Rect::new(Point::new(x, 0.0), Size::new(span, cx.bounds_size.height))
         };
 
-        primitives.add(style.quad_style.create_primitive(rect));
+        style
+            .quad_style
+            .add_primitives_with_opacity(rect, cx.opacity, primitives);
     }
 }
 
@@ -0,0 +1,537 @@
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::num::NonZeroU64;
+use std::rc::Rc;
+
+use bytemuck::{Pod, Zeroable};
+use rootvg::buffer::Buffer;
+use rootvg::pipeline::{
+    CustomPipeline, CustomPipelinePrimitive, CustomPrimitive, DefaultConstantUniforms,
+};
+use rootvg::PrimitiveGroup;
+
+use crate::derive::*;
+use crate::math::{PhysicalSizeI32, ScaleFactor};
+use crate::prelude::*;
+
+/// The maximum size of the uniforms of a [`ShaderQuad`] in bytes.
+pub const MAX_UNIFORMS_SIZE: usize = 256;
+
+/// The WGSL code which is prepended to the fragment shader of every
+/// [`ShaderQuad`].
+///
+/// It declares the `ShaderQuadInput` struct which is passed to `fs_main`:
+///
+/// * `clip_position` - The position of the fragment in physical pixels
+/// (`@builtin(position)`).
+/// * `uv` - The position of the fragment within the quad, from `(0, 0)` at
+/// the top-left corner to `(1, 1)` at the bottom-right corner.
+/// * `size` - The size of the quad in logical points.
+pub const SHADER_PRELUDE: &str = r#"
+struct ShaderQuadGlobals {
+    screen_size_recip: vec2f,
+    scale_factor: f32,
+}
+
+@group(0) @binding(0) var<uniform> shader_quad_globals: ShaderQuadGlobals;
+
+struct ShaderQuadVertexInput {
+    @builtin(vertex_index) vertex_index: u32,
+    @location(0) pos: vec2f,
+    @location(1) size: vec2f,
+}
+
+struct ShaderQuadInput {
+    @builtin(position) clip_position: vec4f,
+    @location(0) uv: vec2f,
+    @location(1) size: vec2f,
+}
+
+@vertex
+fn vs_main(input: ShaderQuadVertexInput) -> ShaderQuadInput {
+    var out: ShaderQuadInput;
+
+    let uv = vec2f(f32(input.vertex_index & 1u), f32(input.vertex_index >> 1u));
+    let screen_pos = (input.pos + (uv * input.size)) * shader_quad_globals.scale_factor;
+
+    out.clip_position = vec4f(
+        (screen_pos.x * shader_quad_globals.screen_size_recip.x) - 1.0,
+        1.0 - (screen_pos.y * shader_quad_globals.screen_size_recip.y),
+        0.0,
+        1.0
+    );
+    out.uv = uv;
+    out.size = input.size;
+
+    return out;
+}
+"#;
+
+/// A builder for a [`ShaderQuad`].
+///
+/// * `shader_id` - A unique name for the shader. Each shader is compiled
+/// once per window, and all quads with the same ID share the same pipeline.
+/// * `fragment_shader` - The WGSL source of the fragment shader (see
+/// [`ShaderQuad`]).
+/// * `uniforms` - The initial uniforms.
+pub fn shader_quad<U: Pod>(
+    shader_id: &'static str,
+    fragment_shader: impl Into<Cow<'static, str>>,
+    uniforms: U,
+) -> ShaderQuadBuilder<U> {
+    ShaderQuadBuilder {
+        shader_id,
+        fragment_shader: fragment_shader.into(),
+        uniforms,
+        class: None,
+        rect: Rect::default(),
+        manually_hidden: false,
+        z_index: None,
+        scissor_rect: None,
+        key: None,
+    }
+}
+
+#[element_builder]
+#[element_builder_class]
+#[element_builder_rect]
+#[element_builder_hidden]
+pub struct ShaderQuadBuilder<U: Pod> {
+    pub shader_id: &'static str,
+    pub fragment_shader: Cow<'static, str>,
+    pub uniforms: U,
+}
+
+impl<U: Pod> ShaderQuadBuilder<U> {
+    /// Build the element.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the size of `U` is greater than [`MAX_UNIFORMS_SIZE`].
+    pub fn build<A: Clone + 'static>(self, window_cx: &mut WindowContext<'_, A>) -> ShaderQuad<U> {
+        let ShaderQuadBuilder {
+            shader_id,
+            fragment_shader,
+            uniforms,
+            class,
+            z_index,
+            rect,
+            manually_hidden,
+            scissor_rect,
+            key,
+        } = self;
+
+        assert!(
+            std::mem::size_of::<U>() <= MAX_UNIFORMS_SIZE,
+            "the uniforms of a ShaderQuad can be at most {} bytes",
+            MAX_UNIFORMS_SIZE
+        );
+
+        let shared_state = Rc::new(RefCell::new(uniforms_to_bytes(&uniforms)));
+
+        let el = ElementBuilder::new(ShaderQuadInternal {
+            shader_id,
+            fragment_shader,
+            uniforms_size: std::mem::size_of::<U>(),
+            shared_state: Rc::clone(&shared_state),
+        })
+        .builder_values(z_index, scissor_rect, class, window_cx)
+        .key(key)
+        .rect(rect)
+        .hidden(manually_hidden)
+        .flags(ElementFlags::PAINTS)
+        .build(window_cx);
+
+        ShaderQuad {
+            el,
+            shared_state,
+            uniforms,
+        }
+    }
+}
+
+struct ShaderQuadInternal {
+    shader_id: &'static str,
+    fragment_shader: Cow<'static, str>,
+    uniforms_size: usize,
+    shared_state: Rc<RefCell<Rc<[u8]>>>,
+}
+
+impl<A: Clone + 'static> Element<A> for ShaderQuadInternal {
+    fn on_event(
+        &mut self,
+        event: ElementEvent,
+        cx: &mut ElementContext<'_, A>,
+    ) -> EventCaptureStatus {
+        if let ElementEvent::CustomStateChanged = event {
+            cx.request_repaint();
+        }
+
+        EventCaptureStatus::NotCaptured
+    }
+
+    fn render(&mut self, cx: RenderContext, primitives: &mut PrimitiveGroup) {
+        let pipeline_id = cx.custom_pipelines.get_id(
+            self.shader_id,
+            || {
+                ShaderQuadPipeline::new(
+                    self.shader_id,
+                    &self.fragment_shader,
+                    self.uniforms_size,
+                    cx.device,
+                    cx.texture_format,
+                    cx.multisample,
+                )
+            },
+            cx.vg,
+        );
+
+        primitives.add_custom_primitive(CustomPrimitive::new(
+            ShaderQuadPrimitive {
+                position: [0.0, 0.0],
+                size: cx.bounds_size.into(),
+                uniforms: Rc::clone(&RefCell::borrow(&self.shared_state)),
+            },
+            pipeline_id,
+        ));
+    }
+}
+
+/// A quad whose color is computed by a user-provided WGSL fragment shader.
+///
+/// This is a lighter-weight alternative to
+/// [`WindowContext::add_render_pass`] for animated backgrounds, scopes, and
+/// other visualizations. The quad is drawn with the rest of the elements,
+/// and it is clipped to its scissor rect like any other element.
+///
+/// The fragment shader is appended to [`SHADER_PRELUDE`], and it must
+/// declare an entry point called `fs_main` which takes a `ShaderQuadInput`.
+/// The uniforms of the quad are bound to `@group(1) @binding(0)`, and their
+/// layout in WGSL must match the layout of `U`:
+///
+/// ```wgsl
+/// struct Uniforms {
+///     color: vec4f,
+///     time: f32,
+/// }
+///
+/// @group(1) @binding(0) var<uniform> uniforms: Uniforms;
+///
+/// @fragment
+/// fn fs_main(in: ShaderQuadInput) -> @location(0) vec4f {
+///     let wave = 0.5 + 0.5 * sin(in.uv.x * 20.0 + uniforms.time);
+///     return uniforms.color * wave;
+/// }
+/// ```
+///
+/// Invalid shaders cause a panic when the quad is first rendered.
+#[element_handle]
+#[element_handle_class]
+#[element_handle_set_rect]
+#[element_handle_layout_aligned]
+pub struct ShaderQuad<U: Pod> {
+    shared_state: Rc<RefCell<Rc<[u8]>>>,
+    uniforms: U,
+}
+
+impl<U: Pod> ShaderQuad<U> {
+    pub fn builder(
+        shader_id: &'static str,
+        fragment_shader: impl Into<Cow<'static, str>>,
+        uniforms: U,
+    ) -> ShaderQuadBuilder<U> {
+        shader_quad(shader_id, fragment_shader, uniforms)
+    }
+
+    pub fn uniforms(&self) -> U {
+        self.uniforms
+    }
+
+    /// Set the uniforms of the shader (i.e. once every frame to animate the
+    /// quad).
+    ///
+    /// Returns `true` if the uniforms have changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn set_uniforms(&mut self, uniforms: U) -> bool {
+        if bytemuck::bytes_of(&self.uniforms) == bytemuck::bytes_of(&uniforms) {
+            return false;
+        }
+
+        self.uniforms = uniforms;
+        *RefCell::borrow_mut(&self.shared_state) = uniforms_to_bytes(&uniforms);
+        self.el.notify_custom_state_change();
+        true
+    }
+}
+
+fn uniforms_to_bytes<U: Pod>(uniforms: &U) -> Rc<[u8]> {
+    Rc::from(bytemuck::bytes_of(uniforms))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct ShaderQuadPrimitive {
+    position: [f32; 2],
+    size: [f32; 2],
+    uniforms: Rc<[u8]>,
+}
+
+#[repr(C)]
+#[derive(Default, Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+struct ShaderQuadInstance {
+    position: [f32; 2],
+    size: [f32; 2],
+}
+
+const INITIAL_INSTANCES: usize = 4;
+
+struct ShaderQuadPipeline {
+    pipeline: wgpu::RenderPipeline,
+
+    constants_buffer: wgpu::Buffer,
+    constants_bind_group: wgpu::BindGroup,
+
+    uniforms_layout: wgpu::BindGroupLayout,
+    uniforms_buffer: wgpu::Buffer,
+    uniforms_bind_group: wgpu::BindGroup,
+    /// The size of the uniforms binding, padded to 16 bytes.
+    uniforms_binding_size: u64,
+    /// The distance between the uniforms of two quads in the uniforms buffer.
+    uniforms_stride: u64,
+    uniforms_capacity: usize,
+
+    instance_buffer: Buffer<ShaderQuadInstance>,
+}
+
+impl ShaderQuadPipeline {
+    fn new(
+        shader_id: &str,
+        fragment_shader: &str,
+        uniforms_size: usize,
+        device: &wgpu::Device,
+        format: wgpu::TextureFormat,
+        multisample: wgpu::MultisampleState,
+    ) -> Self {
+        let (constants_layout, constants_buffer, constants_bind_group) =
+            DefaultConstantUniforms::layout_buffer_and_bind_group(device);
+
+        let uniforms_binding_size = (uniforms_size.max(1) as u64).next_multiple_of(16);
+        let uniforms_stride = uniforms_binding_size.next_multiple_of(u64::from(
+            device.limits().min_uniform_buffer_offset_alignment,
+        ));
+
+        let uniforms_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("yarrow shader quad uniforms layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: true,
+                    min_binding_size: NonZeroU64::new(uniforms_binding_size),
+                },
+                count: None,
+            }],
+        });
+
+        let (uniforms_buffer, uniforms_bind_group) = create_uniforms_buffer(
+            device,
+            &uniforms_layout,
+            uniforms_binding_size,
+            uniforms_stride,
+            INITIAL_INSTANCES,
+        );
+
+        let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("yarrow shader quad pipeline layout"),
+            push_constant_ranges: &[],
+            bind_group_layouts: &[&constants_layout, &uniforms_layout],
+        });
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some(shader_id),
+            source: wgpu::ShaderSource::Wgsl(
+                format!("{}\n{}", SHADER_PRELUDE, fragment_shader).into(),
+            ),
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("yarrow shader quad pipeline"),
+            layout: Some(&layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: "vs_main",
+                buffers: &[wgpu::VertexBufferLayout {
+                    array_stride: std::mem::size_of::<ShaderQuadInstance>() as u64,
+                    step_mode: wgpu::VertexStepMode::Instance,
+                    attributes: &wgpu::vertex_attr_array!(
+                        // Position
+                        0 => Float32x2,
+                        // Size
+                        1 => Float32x2,
+                    ),
+                }],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: "fs_main",
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleStrip,
+                ..Default::default()
+            },
+            depth_stencil: None,
+            multisample,
+            multiview: None,
+            cache: None,
+        });
+
+        let instance_buffer = Buffer::new(
+            device,
+            "yarrow shader quad instance buffer",
+            INITIAL_INSTANCES,
+            wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+        );
+
+        Self {
+            pipeline,
+            constants_buffer,
+            constants_bind_group,
+            uniforms_layout,
+            uniforms_buffer,
+            uniforms_bind_group,
+            uniforms_binding_size,
+            uniforms_stride,
+            uniforms_capacity: INITIAL_INSTANCES,
+            instance_buffer,
+        }
+    }
+}
+
+fn create_uniforms_buffer(
+    device: &wgpu::Device,
+    layout: &wgpu::BindGroupLayout,
+    binding_size: u64,
+    stride: u64,
+    capacity: usize,
+) -> (wgpu::Buffer, wgpu::BindGroup) {
+    let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("yarrow shader quad uniforms buffer"),
+        size: stride * capacity as u64,
+        usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+
+    let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("yarrow shader quad uniforms bind group"),
+        layout,
+        entries: &[wgpu::BindGroupEntry {
+            binding: 0,
+            resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+                buffer: &buffer,
+                offset: 0,
+                size: NonZeroU64::new(binding_size),
+            }),
+        }],
+    });
+
+    (buffer, bind_group)
+}
+
+impl CustomPipeline for ShaderQuadPipeline {
+    fn prepare(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        screen_size: PhysicalSizeI32,
+        scale_factor: ScaleFactor,
+        primitives: &[CustomPipelinePrimitive],
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        DefaultConstantUniforms::prepare_buffer(
+            &self.constants_buffer,
+            screen_size,
+            scale_factor,
+            queue,
+        );
+
+        if primitives.is_empty() {
+            return Ok(());
+        }
+
+        if primitives.len() > self.uniforms_capacity {
+            self.uniforms_capacity = primitives.len().next_power_of_two();
+
+            (self.uniforms_buffer, self.uniforms_bind_group) = create_uniforms_buffer(
+                device,
+                &self.uniforms_layout,
+                self.uniforms_binding_size,
+                self.uniforms_stride,
+                self.uniforms_capacity,
+            );
+        }
+
+        let stride = self.uniforms_stride as usize;
+        let mut uniforms = vec![0u8; stride * primitives.len()];
+
+        let instances: Vec<ShaderQuadInstance> = primitives
+            .iter()
+            .enumerate()
+            .map(|(i, p)| {
+                let primitive = p.primitive.downcast_ref::<ShaderQuadPrimitive>().unwrap();
+
+                let len = primitive
+                    .uniforms
+                    .len()
+                    .min(self.uniforms_binding_size as usize);
+                uniforms[i * stride..i * stride + len].copy_from_slice(&primitive.uniforms[..len]);
+
+                ShaderQuadInstance {
+                    position: [
+                        primitive.position[0] + p.offset.x,
+                        primitive.position[1] + p.offset.y,
+                    ],
+                    size: primitive.size,
+                }
+            })
+            .collect();
+
+        queue.write_buffer(&self.uniforms_buffer, 0, &uniforms);
+
+        self.instance_buffer
+            .expand_to_fit_new_size(device, primitives.len());
+        self.instance_buffer.write(queue, 0, &instances);
+
+        Ok(())
+    }
+
+    fn render_primitive<'pass>(
+        &'pass self,
+        primitive_index: usize,
+        render_pass: &mut wgpu::RenderPass<'pass>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, &self.constants_bind_group, &[]);
+        render_pass.set_bind_group(
+            1,
+            &self.uniforms_bind_group,
+            &[(primitive_index as u64 * self.uniforms_stride) as u32],
+        );
+
+        render_pass.set_vertex_buffer(
+            0,
+            self.instance_buffer
+                .slice(primitive_index..primitive_index + 1),
+        );
+        render_pass.draw(0..4, 0..1);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,298 @@
+use std::any::Any;
+
+use crate::prelude::*;
+use crate::widget::WidgetPart;
+
+/// A slot of a [`StatusBar`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StatusBarSlot {
+    /// Items are laid out from the left edge of the bar.
+    Left,
+    /// Items are laid out as a group in the center of the bar.
+    Center,
+    /// Items are laid out from the right edge of the bar.
+    Right,
+}
+
+/// The ID of an item in a [`StatusBar`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StatusBarItemID(u64);
+
+/// A part which can be placed in a [`StatusBar`].
+///
+/// This is automatically implemented for every [`WidgetPart`].
+pub trait StatusBarPart: WidgetPart + Any {
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+    fn as_part_mut(&mut self) -> &mut dyn WidgetPart;
+}
+
+impl<T: WidgetPart + Any> StatusBarPart for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn as_part_mut(&mut self) -> &mut dyn WidgetPart {
+        self
+    }
+}
+
+struct StatusBarItem {
+    id: StatusBarItemID,
+    slot: StatusBarSlot,
+    width: f32,
+    part: Box<dyn StatusBarPart>,
+}
+
+/// A bar which docks to the bottom of a window, with left, center, and right
+/// slots for items such as labels, small meters, and buttons.
+///
+/// The status bar owns its items. Use [`StatusBar::item_mut`] to get a typed
+/// handle to an item in order to update it.
+///
+/// ```ignore
+/// let mut status_bar = StatusBar::new(24.0, ClassID::default(), cx);
+/// let cpu_label = status_bar.push(
+///     StatusBarSlot::Right,
+///     Label::builder().text("CPU 0%").build(cx),
+///     80.0,
+/// );
+///
+/// // In the layout function of the window:
+/// status_bar.dock(window_cx.logical_size());
+///
+/// // Later:
+/// if let Some(label) = status_bar.item_mut::<Label>(cpu_label) {
+///     label.set_text(Some("CPU 12%"), window_cx.res);
+/// }
+/// ```
+pub struct StatusBar {
+    core: WidgetCore,
+    background: QuadElement,
+    items: Vec<StatusBarItem>,
+    height: f32,
+    padding: f32,
+    spacing: f32,
+    next_id: u64,
+}
+
+impl StatusBar {
+    /// Create a new status bar with the given height in points.
+    ///
+    /// `background_class` is the class of the [`QuadElement`] drawn behind
+    /// the items.
+    pub fn new<A: Clone + 'static>(
+        height: f32,
+        background_class: ClassID,
+        window_cx: &mut WindowContext<'_, A>,
+    ) -> Self {
+        let background = QuadElement::builder()
+            .class(background_class)
+            .build(window_cx);
+
+        let mut new_self = Self {
+            core: WidgetCore::default(),
+            background,
+            items: Vec::new(),
+            height,
+            padding: 6.0,
+            spacing: 8.0,
+            next_id: 0,
+        };
+
+        new_self.dock(window_cx.logical_size());
+        new_self
+    }
+
+    /// The padding between the edges of the bar and the left and right
+    /// slots in points.
+    ///
+    /// By default this is set to `6.0`.
+    pub fn with_padding(mut self, padding: f32) -> Self {
+        self.padding = padding;
+        self.layout();
+        self
+    }
+
+    /// The spacing between items in the same slot in points.
+    ///
+    /// By default this is set to `8.0`.
+    pub fn with_spacing(mut self, spacing: f32) -> Self {
+        self.spacing = spacing;
+        self.layout();
+        self
+    }
+
+    /// Dock the bar to the bottom of a window with the given size and lay
+    /// out all slots.
+    ///
+    /// Call this from the layout function of the window whenever the window
+    /// is resized.
+    ///
+    /// Returns `true` if the rectangle of the bar has changed.
+    pub fn dock(&mut self, window_size: Size) -> bool {
+        let height = self.height.min(window_size.height).max(0.0);
+
+        Widget::set_rect(
+            self,
+            Rect::new(
+                Point::new(0.0, window_size.height - height),
+                Size::new(window_size.width, height),
+            ),
+        )
+    }
+
+    /// Add an item to the end of the given slot (for the right slot, this is
+    /// the item furthest to the left).
+    ///
+    /// `width` is the width of the item in points. The item fills the full
+    /// height of the bar.
+    pub fn push<T: WidgetPart + 'static>(
+        &mut self,
+        slot: StatusBarSlot,
+        mut part: T,
+        width: f32,
+    ) -> StatusBarItemID {
+        let id = StatusBarItemID(self.next_id);
+        self.next_id += 1;
+
+        part.set_hidden(self.hidden());
+        part.set_disabled(self.disabled());
+
+        self.items.push(StatusBarItem {
+            id,
+            slot,
+            width: width.max(0.0),
+            part: Box::new(part),
+        });
+
+        self.layout();
+        id
+    }
+
+    /// Remove an item from the bar. This drops the item, which also removes
+    /// its elements from the view.
+    ///
+    /// Returns `true` if the item existed.
+    pub fn remove(&mut self, id: StatusBarItemID) -> bool {
+        let Some(i) = self.items.iter().position(|item| item.id == id) else {
+            return false;
+        };
+
+        self.items.remove(i);
+        self.layout();
+        true
+    }
+
+    /// Get a typed reference to an item.
+    ///
+    /// Returns `None` if the item does not exist or if it is not of type `T`.
+    pub fn item<T: WidgetPart + 'static>(&self, id: StatusBarItemID) -> Option<&T> {
+        self.items
+            .iter()
+            .find(|item| item.id == id)
+            .and_then(|item| item.part.as_any().downcast_ref::<T>())
+    }
+
+    /// Get a typed mutable reference to an item.
+    ///
+    /// Returns `None` if the item does not exist or if it is not of type `T`.
+    pub fn item_mut<T: WidgetPart + 'static>(&mut self, id: StatusBarItemID) -> Option<&mut T> {
+        self.items
+            .iter_mut()
+            .find(|item| item.id == id)
+            .and_then(|item| item.part.as_any_mut().downcast_mut::<T>())
+    }
+
+    /// Set the width of an item in points.
+    ///
+    /// Returns `true` if the width has changed.
+    pub fn set_item_width(&mut self, id: StatusBarItemID, width: f32) -> bool {
+        let width = width.max(0.0);
+
+        let Some(item) = self.items.iter_mut().find(|item| item.id == id) else {
+            return false;
+        };
+
+        if item.width == width {
+            return false;
+        }
+
+        item.width = width;
+        self.layout();
+        true
+    }
+
+    /// Set the height of the bar in points.
+    ///
+    /// The new height is applied on the next call to [`StatusBar::dock`].
+    pub fn set_height(&mut self, height: f32) {
+        self.height = height;
+    }
+
+    pub fn height(&self) -> f32 {
+        self.height
+    }
+
+    /// The background quad element of the bar.
+    pub fn background_mut(&mut self) -> &mut QuadElement {
+        &mut self.background
+    }
+}
+
+impl Widget for StatusBar {
+    fn core(&self) -> &WidgetCore {
+        &self.core
+    }
+
+    fn core_mut(&mut self) -> &mut WidgetCore {
+        &mut self.core
+    }
+
+    fn for_each_part(&mut self, f: &mut dyn FnMut(&mut dyn WidgetPart, PartLayout)) {
+        (f)(&mut self.background, PartLayout::fill());
+
+        let spacing = self.spacing;
+
+        let mut left_x = self.padding;
+        let mut right_x = -self.padding;
+        let mut center_x = -(self
+            .items
+            .iter()
+            .filter(|item| item.slot == StatusBarSlot::Center)
+            .map(|item| item.width + spacing)
+            .sum::<f32>()
+            - spacing)
+            .max(0.0)
+            * 0.5;
+
+        for item in self.items.iter_mut() {
+            let layout = match item.slot {
+                StatusBarSlot::Left => {
+                    let layout = PartLayout::fill_height(Align2::CENTER_LEFT, item.width)
+                        .offset(Vector::new(left_x, 0.0));
+                    left_x += item.width + spacing;
+                    layout
+                }
+                StatusBarSlot::Center => {
+                    let layout = PartLayout::fill_height(Align2::CENTER, item.width)
+                        .offset(Vector::new(center_x + (item.width * 0.5), 0.0));
+                    center_x += item.width + spacing;
+                    layout
+                }
+                StatusBarSlot::Right => {
+                    let layout = PartLayout::fill_height(Align2::CENTER_RIGHT, item.width)
+                        .offset(Vector::new(right_x, 0.0));
+                    right_x -= item.width + spacing;
+                    layout
+                }
+            };
+
+            (f)(item.part.as_part_mut(), layout);
+        }
+    }
+}
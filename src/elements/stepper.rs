@@ -0,0 +1,886 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::derive::*;
+use crate::prelude::*;
+use crate::vg::quad::{SolidQuadBuilder, SolidQuadPrimitive};
+use crate::vg::text::{RcTextBuffer, TextPrimitive};
+use crate::widget::WidgetPart;
+
+/// The style of a [`Stepper`] element.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StepperStyle {
+    pub back_quad: QuadStyle,
+
+    /// The padding around the progress header and the navigation footer.
+    ///
+    /// By default this is set to `padding_all_same(8.0)`.
+    pub padding: Padding,
+
+    /// The height of the progress header at the top of the stepper.
+    ///
+    /// By default this is set to `48.0`.
+    pub header_height: f32,
+    /// The height of the navigation footer (containing the back and next
+    /// buttons) at the bottom of the stepper.
+    ///
+    /// By default this is set to `40.0`.
+    pub footer_height: f32,
+
+    /// The diameter of the numbered step circles in the progress header.
+    ///
+    /// By default this is set to `22.0`.
+    pub step_circle_size: f32,
+    /// The width of the lines connecting the step circles.
+    ///
+    /// By default this is set to `2.0`.
+    pub step_line_width: f32,
+    /// The spacing between a step circle and its title.
+    ///
+    /// By default this is set to `4.0`.
+    pub step_title_spacing: f32,
+
+    /// The color of steps which have been completed.
+    pub step_color_complete: RGBA8,
+    /// The color of the current step.
+    pub step_color_current: RGBA8,
+    /// The color of steps which have not been reached yet.
+    pub step_color_upcoming: RGBA8,
+
+    /// The properties of the number inside of each step circle.
+    pub number_text_properties: TextProperties,
+    pub number_color: RGBA8,
+
+    /// The properties of the title below each step circle.
+    pub title_text_properties: TextProperties,
+    pub title_color: RGBA8,
+    pub title_color_current: Option<RGBA8>,
+
+    /// The size of the back and next buttons.
+    ///
+    /// By default this is set to `Size::new(80.0, 24.0)`.
+    pub button_size: Size,
+    pub button_quad: QuadStyle,
+    pub button_quad_hover: Option<QuadStyle>,
+    pub button_text_properties: TextProperties,
+    pub button_text_color: RGBA8,
+    pub button_text_color_disabled: DisabledColor,
+}
+
+impl Default for StepperStyle {
+    fn default() -> Self {
+        Self {
+            back_quad: QuadStyle::TRANSPARENT,
+            padding: padding_all_same(8.0),
+            header_height: 48.0,
+            footer_height: 40.0,
+            step_circle_size: 22.0,
+            step_line_width: 2.0,
+            step_title_spacing: 4.0,
+            step_color_complete: rgb(80, 160, 230),
+            step_color_current: rgb(80, 160, 230),
+            step_color_upcoming: gray(70),
+            number_text_properties: TextProperties::default(),
+            number_color: color::WHITE,
+            title_text_properties: TextProperties::default(),
+            title_color: gray(160),
+            title_color_current: Some(color::WHITE),
+            button_size: Size::new(80.0, 24.0),
+            button_quad: QuadStyle {
+                bg: Background::Solid(gray(50)),
+                border: BorderStyle {
+                    radius: radius(4.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            button_quad_hover: Some(QuadStyle {
+                bg: Background::Solid(gray(62)),
+                border: BorderStyle {
+                    radius: radius(4.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }),
+            button_text_properties: TextProperties::default(),
+            button_text_color: color::WHITE,
+            button_text_color_disabled: Default::default(),
+        }
+    }
+}
+
+impl ElementStyle for StepperStyle {
+    const ID: &'static str = "stepper";
+
+    fn default_dark_style() -> Self {
+        Self::default()
+    }
+
+    fn default_light_style() -> Self {
+        Self {
+            step_color_upcoming: gray(200),
+            title_color: gray(90),
+            title_color_current: Some(color::BLACK),
+            button_quad: QuadStyle {
+                bg: Background::Solid(gray(220)),
+                border: BorderStyle {
+                    radius: radius(4.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            button_quad_hover: Some(QuadStyle {
+                bg: Background::Solid(gray(205)),
+                border: BorderStyle {
+                    radius: radius(4.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }),
+            button_text_color: color::BLACK,
+            ..Default::default()
+        }
+    }
+
+    fn with_auto_disabled(&self, derivation: DisabledDerivation) -> Option<Self> {
+        let mut style = self.clone();
+        style.button_text_color_disabled.derive_auto(derivation);
+        Some(style)
+    }
+}
+
+#[element_builder]
+#[element_builder_class]
+#[element_builder_rect]
+#[element_builder_hidden]
+#[element_builder_disabled]
+pub struct StepperBuilder<A: Clone + 'static> {
+    pub step_changed_action: Option<Box<dyn FnMut(usize) -> A>>,
+    pub completed_action: Option<A>,
+    pub validate_next: Option<Box<dyn FnMut(usize) -> bool>>,
+    pub validate_back: Option<Box<dyn FnMut(usize) -> bool>>,
+    pub steps: Vec<String>,
+    pub step: usize,
+    pub back_text: Option<String>,
+    pub next_text: Option<String>,
+    pub finish_text: Option<String>,
+}
+
+impl<A: Clone + 'static> Default for StepperBuilder<A> {
+    fn default() -> Self {
+        Self {
+            step_changed_action: None,
+            completed_action: None,
+            validate_next: None,
+            validate_back: None,
+            steps: Vec::new(),
+            step: 0,
+            back_text: None,
+            next_text: None,
+            finish_text: None,
+            class: None,
+            z_index: None,
+            rect: Rect::default(),
+            manually_hidden: false,
+            disabled: false,
+            scissor_rect: None,
+            key: None,
+        }
+    }
+}
+
+impl<A: Clone + 'static> StepperBuilder<A> {
+    /// The action sent when the user moves to a different step.
+    pub fn on_step_changed<F: FnMut(usize) -> A + 'static>(mut self, f: F) -> Self {
+        self.step_changed_action = Some(Box::new(f));
+        self
+    }
+
+    /// The action sent when the user presses the finish button on the last
+    /// step.
+    pub fn on_completed(mut self, action: A) -> Self {
+        self.completed_action = Some(action);
+        self
+    }
+
+    /// A callback which is called with the index of the current step when
+    /// the user presses the next (or finish) button. If it returns `false`,
+    /// then the stepper stays on the current step.
+    pub fn validate_next<F: FnMut(usize) -> bool + 'static>(mut self, f: F) -> Self {
+        self.validate_next = Some(Box::new(f));
+        self
+    }
+
+    /// A callback which is called with the index of the current step when
+    /// the user presses the back button. If it returns `false`, then the
+    /// stepper stays on the current step.
+    pub fn validate_back<F: FnMut(usize) -> bool + 'static>(mut self, f: F) -> Self {
+        self.validate_back = Some(Box::new(f));
+        self
+    }
+
+    /// The titles of the steps shown in the progress header.
+    pub fn steps<S: Into<String>>(mut self, steps: impl IntoIterator<Item = S>) -> Self {
+        self.steps = steps.into_iter().map(|s| s.into()).collect();
+        self
+    }
+
+    /// The initial step.
+    ///
+    /// By default this is set to `0`.
+    pub const fn step(mut self, step: usize) -> Self {
+        self.step = step;
+        self
+    }
+
+    /// The text of the back button.
+    ///
+    /// By default this is set to the translation of
+    /// [`BuiltinString::StepperBack`] (`"Back"` in English).
+    pub fn back_text(mut self, text: impl Into<String>) -> Self {
+        self.back_text = Some(text.into());
+        self
+    }
+
+    /// The text of the next button.
+    ///
+    /// By default this is set to the translation of
+    /// [`BuiltinString::StepperNext`] (`"Next"` in English).
+    pub fn next_text(mut self, text: impl Into<String>) -> Self {
+        self.next_text = Some(text.into());
+        self
+    }
+
+    /// The text of the next button on the last step.
+    ///
+    /// By default this is set to the translation of
+    /// [`BuiltinString::StepperFinish`] (`"Finish"` in English).
+    pub fn finish_text(mut self, text: impl Into<String>) -> Self {
+        self.finish_text = Some(text.into());
+        self
+    }
+
+    pub fn build(self, window_cx: &mut WindowContext<'_, A>) -> Stepper {
+        let StepperBuilder {
+            step_changed_action,
+            completed_action,
+            validate_next,
+            validate_back,
+            steps,
+            step,
+            back_text,
+            next_text,
+            finish_text,
+            class,
+            z_index,
+            rect,
+            manually_hidden,
+            disabled,
+            scissor_rect,
+            key,
+        } = self;
+
+        let style = window_cx
+            .res
+            .style_system
+            .get::<StepperStyle>(window_cx.builder_class(class));
+
+        let step = step.min(steps.len().saturating_sub(1));
+
+        let locale = &window_cx.res.locale;
+        let back_text =
+            back_text.unwrap_or_else(|| locale.builtin(BuiltinString::StepperBack).into_owned());
+        let next_text =
+            next_text.unwrap_or_else(|| locale.builtin(BuiltinString::StepperNext).into_owned());
+        let finish_text = finish_text
+            .unwrap_or_else(|| locale.builtin(BuiltinString::StepperFinish).into_owned());
+
+        let text_buffers = TextBuffers::new(
+            &steps,
+            [&back_text, &next_text, &finish_text],
+            style,
+            &mut window_cx.res.font_system,
+        );
+
+        let shared_state = Rc::new(RefCell::new(SharedState {
+            steps,
+            step,
+            button_texts: [back_text, next_text, finish_text],
+            texts_changed: false,
+            disabled,
+        }));
+
+        let el = ElementBuilder::new(StepperElement {
+            shared_state: Rc::clone(&shared_state),
+            step_changed_action,
+            completed_action,
+            validate_next,
+            validate_back,
+            text_buffers,
+            hovered: None,
+        })
+        .builder_values(z_index, scissor_rect, class, window_cx)
+        .key(key)
+        .rect(rect)
+        .hidden(manually_hidden)
+        .flags(ElementFlags::PAINTS | ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS)
+        .build(window_cx);
+
+        Stepper { el, shared_state }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NavButton {
+    Back,
+    Next,
+}
+
+struct TextBuffers {
+    titles: Vec<RcTextBuffer>,
+    numbers: Vec<RcTextBuffer>,
+    /// The back, next, and finish button texts.
+    buttons: [RcTextBuffer; 3],
+}
+
+impl TextBuffers {
+    fn new(
+        steps: &[String],
+        button_texts: [&str; 3],
+        style: &StepperStyle,
+        font_system: &mut FontSystem,
+    ) -> Self {
+        let mut new_buffer = |text: &str, properties: TextProperties| {
+            RcTextBuffer::new(text, properties, None, None, false, font_system)
+        };
+
+        Self {
+            titles: steps
+                .iter()
+                .map(|title| new_buffer(title, style.title_text_properties))
+                .collect(),
+            numbers: (1..=steps.len())
+                .map(|n| new_buffer(&n.to_string(), style.number_text_properties))
+                .collect(),
+            buttons: button_texts.map(|text| new_buffer(text, style.button_text_properties)),
+        }
+    }
+}
+
+struct Layout {
+    header: Rect,
+    page_area: Rect,
+    back_button: Rect,
+    next_button: Rect,
+}
+
+impl Layout {
+    fn new(bounds: Rect, style: &StepperStyle) -> Self {
+        let header_height = style.header_height.max(0.0).min(bounds.height());
+        let footer_height = style
+            .footer_height
+            .max(0.0)
+            .min(bounds.height() - header_height);
+
+        let header = Rect::new(bounds.origin, Size::new(bounds.width(), header_height));
+        let page_area = Rect::new(
+            Point::new(bounds.min_x(), header.max_y()),
+            Size::new(
+                bounds.width(),
+                bounds.height() - header_height - footer_height,
+            ),
+        );
+
+        let button_y = page_area.max_y() + ((footer_height - style.button_size.height) * 0.5);
+        let back_button = Rect::new(
+            Point::new(bounds.min_x() + style.padding.left, button_y),
+            style.button_size,
+        );
+        let next_button = Rect::new(
+            Point::new(
+                bounds.max_x() - style.padding.right - style.button_size.width,
+                button_y,
+            ),
+            style.button_size,
+        );
+
+        Self {
+            header,
+            page_area,
+            back_button,
+            next_button,
+        }
+    }
+
+    /// The center of the circle of the given step in the progress header.
+    fn step_center(&self, i: usize, num_steps: usize, style: &StepperStyle) -> Point {
+        let inner_width = (self.header.width() - style.padding.left - style.padding.right).max(0.0);
+        let segment_width = inner_width / num_steps.max(1) as f32;
+
+        Point::new(
+            self.header.min_x() + style.padding.left + (segment_width * (i as f32 + 0.5)),
+            self.header.min_y() + style.padding.top + (style.step_circle_size * 0.5),
+        )
+    }
+}
+
+/// A sequence of steps with a progress header and back/next navigation.
+struct StepperElement<A: Clone + 'static> {
+    shared_state: Rc<RefCell<SharedState>>,
+    step_changed_action: Option<Box<dyn FnMut(usize) -> A>>,
+    completed_action: Option<A>,
+    validate_next: Option<Box<dyn FnMut(usize) -> bool>>,
+    validate_back: Option<Box<dyn FnMut(usize) -> bool>>,
+    text_buffers: TextBuffers,
+    hovered: Option<NavButton>,
+}
+
+impl<A: Clone + 'static> StepperElement<A> {
+    fn update_text_buffers(&mut self, shared_state: &SharedState, cx: &mut ElementContext<'_, A>) {
+        let style = cx.res.style_system.get::<StepperStyle>(cx.class());
+        let [back_text, next_text, finish_text] = &shared_state.button_texts;
+
+        self.text_buffers = TextBuffers::new(
+            &shared_state.steps,
+            [back_text, next_text, finish_text],
+            style,
+            &mut cx.res.font_system,
+        );
+    }
+
+    fn on_nav_button(
+        &mut self,
+        shared_state: &mut SharedState,
+        button: NavButton,
+        cx: &mut ElementContext<'_, A>,
+    ) {
+        let step = shared_state.step;
+
+        match button {
+            NavButton::Back => {
+                if step == 0 {
+                    return;
+                }
+
+                if let Some(validate) = self.validate_back.as_mut() {
+                    if !(validate)(step) {
+                        return;
+                    }
+                }
+
+                shared_state.step = step - 1;
+            }
+            NavButton::Next => {
+                if let Some(validate) = self.validate_next.as_mut() {
+                    if !(validate)(step) {
+                        return;
+                    }
+                }
+
+                if step + 1 >= shared_state.steps.len() {
+                    if let Some(action) = self.completed_action.clone() {
+                        cx.send_action(action).unwrap();
+                    }
+                    return;
+                }
+
+                shared_state.step = step + 1;
+            }
+        }
+
+        if let Some(action) = self.step_changed_action.as_mut() {
+            cx.send_action((action)(shared_state.step)).unwrap();
+        }
+
+        cx.request_repaint();
+    }
+}
+
+impl<A: Clone + 'static> Element<A> for StepperElement<A> {
+    fn on_event(
+        &mut self,
+        event: ElementEvent,
+        cx: &mut ElementContext<'_, A>,
+    ) -> EventCaptureStatus {
+        let shared_state_rc = Rc::clone(&self.shared_state);
+        let mut shared_state = RefCell::borrow_mut(&shared_state_rc);
+
+        match event {
+            ElementEvent::CustomStateChanged => {
+                if std::mem::take(&mut shared_state.texts_changed) {
+                    self.update_text_buffers(&shared_state, cx);
+                }
+
+                if shared_state.disabled {
+                    self.hovered = None;
+                }
+
+                cx.request_repaint();
+            }
+            ElementEvent::StyleChanged => {
+                self.update_text_buffers(&shared_state, cx);
+                cx.request_repaint();
+            }
+            ElementEvent::Pointer(PointerEvent::Moved { position, .. }) => {
+                if shared_state.disabled {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                let style = cx.res.style_system.get::<StepperStyle>(cx.class());
+                let layout = Layout::new(cx.rect(), style);
+
+                let hovered = if shared_state.step > 0 && layout.back_button.contains(position) {
+                    Some(NavButton::Back)
+                } else if !shared_state.steps.is_empty() && layout.next_button.contains(position) {
+                    Some(NavButton::Next)
+                } else {
+                    None
+                };
+
+                if self.hovered != hovered {
+                    self.hovered = hovered;
+                    cx.request_repaint();
+                }
+
+                if hovered.is_some() {
+                    cx.cursor_icon = CursorIcon::Pointer;
+                    return EventCaptureStatus::Captured;
+                }
+            }
+            ElementEvent::Pointer(PointerEvent::PointerLeft) => {
+                if self.hovered.take().is_some() {
+                    cx.request_repaint();
+                }
+            }
+            ElementEvent::Pointer(PointerEvent::ButtonJustPressed {
+                position, button, ..
+            }) => {
+                if shared_state.disabled || button != PointerButton::Primary {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                let style = cx.res.style_system.get::<StepperStyle>(cx.class());
+                let layout = Layout::new(cx.rect(), style);
+
+                let nav_button = if layout.back_button.contains(position) {
+                    NavButton::Back
+                } else if layout.next_button.contains(position) {
+                    NavButton::Next
+                } else {
+                    return EventCaptureStatus::NotCaptured;
+                };
+
+                if !shared_state.steps.is_empty() {
+                    self.on_nav_button(&mut shared_state, nav_button, cx);
+                }
+
+                return EventCaptureStatus::Captured;
+            }
+            _ => {}
+        }
+
+        EventCaptureStatus::NotCaptured
+    }
+
+    fn render(&mut self, cx: RenderContext, primitives: &mut PrimitiveGroup) {
+        let shared_state = RefCell::borrow(&self.shared_state);
+        let style = cx.res.style_system.get::<StepperStyle>(cx.class);
+
+        let bounds = Rect::from_size(cx.bounds_size);
+        let layout = Layout::new(bounds, style);
+
+        if !style.back_quad.is_transparent() {
+            primitives.add(
+                style
+                    .back_quad
+                    .create_primitive_with_opacity(bounds, cx.opacity),
+            );
+        }
+
+        let num_steps = shared_state.steps.len();
+        if num_steps == 0 {
+            return;
+        }
+
+        let step_color = |i: usize| {
+            if i < shared_state.step {
+                style.step_color_complete
+            } else if i == shared_state.step {
+                style.step_color_current
+            } else {
+                style.step_color_upcoming
+            }
+        };
+
+        // Progress header
+
+        let circle_radius = style.step_circle_size * 0.5;
+        let mut lines: Vec<SolidQuadPrimitive> = Vec::with_capacity(num_steps);
+        let mut texts: Vec<TextPrimitive> = Vec::with_capacity(num_steps * 2);
+
+        for i in 0..num_steps {
+            let center = layout.step_center(i, num_steps, style);
+
+            if i + 1 < num_steps {
+                let next_center = layout.step_center(i + 1, num_steps, style);
+                let line_width = next_center.x - center.x - style.step_circle_size;
+
+                if line_width > 0.0 {
+                    let line_color = if i < shared_state.step {
+                        style.step_color_complete
+                    } else {
+                        style.step_color_upcoming
+                    };
+
+                    lines.push(
+                        SolidQuadBuilder::new(Size::new(line_width, style.step_line_width))
+                            .bg_color(apply_opacity(line_color, cx.opacity))
+                            .position(Point::new(
+                                center.x + circle_radius,
+                                center.y - (style.step_line_width * 0.5),
+                            ))
+                            .into(),
+                    );
+                }
+            }
+
+            primitives.add(
+                QuadStyle {
+                    bg: Background::Solid(step_color(i)),
+                    border: BorderStyle {
+                        radius: Radius::CIRCLE,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                }
+                .create_primitive_with_opacity(
+                    centered_rect(
+                        center,
+                        Size::new(style.step_circle_size, style.step_circle_size),
+                    ),
+                    cx.opacity,
+                ),
+            );
+
+            if let Some(buffer) = self.text_buffers.numbers.get(i) {
+                let size = buffer.measure();
+                texts.push(TextPrimitive::new(
+                    buffer.clone(),
+                    center - (size * 0.5).to_vector(),
+                    apply_opacity(style.number_color, cx.opacity),
+                    None,
+                ));
+            }
+
+            if let Some(buffer) = self.text_buffers.titles.get(i) {
+                let size = buffer.measure();
+                let title_color = if i == shared_state.step {
+                    style.title_color_current.unwrap_or(style.title_color)
+                } else {
+                    style.title_color
+                };
+
+                texts.push(TextPrimitive::new(
+                    buffer.clone(),
+                    Point::new(
+                        center.x - (size.width * 0.5),
+                        center.y + circle_radius + style.step_title_spacing,
+                    ),
+                    apply_opacity(title_color, cx.opacity),
+                    None,
+                ));
+            }
+        }
+
+        primitives.add_solid_quad_batch(lines);
+
+        // Navigation footer
+
+        let is_last_step = shared_state.step + 1 >= num_steps;
+        for (button, rect, buffer, enabled) in [
+            (
+                NavButton::Back,
+                layout.back_button,
+                &self.text_buffers.buttons[0],
+                shared_state.step > 0,
+            ),
+            (
+                NavButton::Next,
+                layout.next_button,
+                if is_last_step {
+                    &self.text_buffers.buttons[2]
+                } else {
+                    &self.text_buffers.buttons[1]
+                },
+                true,
+            ),
+        ] {
+            let enabled = enabled && !shared_state.disabled;
+
+            let quad_style = if enabled && self.hovered == Some(button) {
+                style
+                    .button_quad_hover
+                    .as_ref()
+                    .unwrap_or(&style.button_quad)
+            } else {
+                &style.button_quad
+            };
+
+            if !quad_style.is_transparent() {
+                primitives.add(quad_style.create_primitive_with_opacity(rect, cx.opacity));
+            }
+
+            let text_color = if enabled {
+                style.button_text_color
+            } else {
+                style
+                    .button_text_color_disabled
+                    .get(style.button_text_color)
+            };
+
+            let size = buffer.measure();
+            texts.push(TextPrimitive::new(
+                buffer.clone(),
+                rect.center() - (size * 0.5).to_vector(),
+                apply_opacity(text_color, cx.opacity),
+                None,
+            ));
+        }
+
+        primitives.set_z_index(1);
+        primitives.add_text_batch(texts);
+    }
+}
+
+struct SharedState {
+    steps: Vec<String>,
+    step: usize,
+    /// The back, next, and finish button texts.
+    button_texts: [String; 3],
+    texts_changed: bool,
+    disabled: bool,
+}
+
+/// A handle to a [`StepperElement`], a sequence of steps (i.e. a first-run
+/// setup or an export flow) with a progress header and back/next buttons.
+///
+/// The pages of the steps are regular elements (or [widgets](crate::widget))
+/// owned by the application. After the step has changed, call
+/// [`Stepper::sync_pages`] to lay out the current page in the area between
+/// the header and the buttons and to hide all other pages.
+#[element_handle]
+#[element_handle_class]
+#[element_handle_set_rect]
+#[element_handle_layout_aligned]
+pub struct Stepper {
+    shared_state: Rc<RefCell<SharedState>>,
+}
+
+impl Stepper {
+    pub fn builder<A: Clone + 'static>() -> StepperBuilder<A> {
+        StepperBuilder::default()
+    }
+
+    /// The area of the pages (between the progress header and the
+    /// navigation buttons).
+    pub fn page_rect(&self, res: &mut ResourceCtx) -> Rect {
+        let style = res.style_system.get::<StepperStyle>(self.el.class());
+        Layout::new(self.el.rect(), style).page_area
+    }
+
+    /// Lay out the page of the current step in [`Stepper::page_rect`] and
+    /// hide the pages of all other steps.
+    ///
+    /// `pages[i]` is the page of step `i`.
+    pub fn sync_pages(&self, pages: &mut [&mut dyn WidgetPart], res: &mut ResourceCtx) {
+        let page_rect = self.page_rect(res);
+        let step = self.step();
+
+        for (i, page) in pages.iter_mut().enumerate() {
+            if i == step {
+                page.set_rect(page_rect);
+                page.set_hidden(self.el.manually_hidden());
+            } else {
+                page.set_hidden(true);
+            }
+        }
+    }
+
+    /// Set the current step (bypassing the validation callbacks).
+    ///
+    /// Returns `true` if the step has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn set_step(&mut self, step: usize) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        let step = step.min(shared_state.steps.len().saturating_sub(1));
+
+        if shared_state.step != step {
+            shared_state.step = step;
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn step(&self) -> usize {
+        RefCell::borrow(&self.shared_state).step
+    }
+
+    /// Set the titles of the steps. The current step is clamped to the new
+    /// number of steps.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed.
+    /// However, this method still involves string comparisons so you may want
+    /// to call this method sparingly.
+    pub fn set_steps<S: AsRef<str> + Into<String>>(&mut self, steps: Vec<S>) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if shared_state.steps.len() == steps.len()
+            && shared_state
+                .steps
+                .iter()
+                .zip(steps.iter())
+                .all(|(a, b)| a == b.as_ref())
+        {
+            return false;
+        }
+
+        shared_state.steps = steps.into_iter().map(|s| s.into()).collect();
+        shared_state.step = shared_state
+            .step
+            .min(shared_state.steps.len().saturating_sub(1));
+        shared_state.texts_changed = true;
+        self.el.notify_custom_state_change();
+        true
+    }
+
+    pub fn num_steps(&self) -> usize {
+        RefCell::borrow(&self.shared_state).steps.len()
+    }
+
+    pub fn disabled(&self) -> bool {
+        RefCell::borrow(&self.shared_state).disabled
+    }
+
+    /// Set the disabled state of this element.
+    ///
+    /// Returns `true` if the disabled state has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn set_disabled(&mut self, disabled: bool) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if shared_state.disabled != disabled {
+            shared_state.disabled = disabled;
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+}
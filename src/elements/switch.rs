@@ -110,6 +110,25 @@ impl ElementStyle for SwitchStyle {
     fn default_light_style() -> Self {
         Self::default()
     }
+
+    fn with_auto_disabled(&self, derivation: DisabledDerivation) -> Option<Self> {
+        let mut style = self.clone();
+        style
+            .outer_border_color_off_disabled
+            .derive_auto(derivation);
+        style.outer_border_color_on_disabled.derive_auto(derivation);
+        style.off_bg_disabled.derive_auto(derivation);
+        style.on_bg_disabled.derive_auto(derivation);
+        style.slider_bg_off_disabled.derive_auto(derivation);
+        style.slider_bg_on_disabled.derive_auto(derivation);
+        style
+            .slider_border_color_off_disabled
+            .derive_auto(derivation);
+        style
+            .slider_border_color_on_disabled
+            .derive_auto(derivation);
+        Some(style)
+    }
 }
 
 #[element_builder]
@@ -146,6 +165,7 @@ impl<A: Clone + 'static> SwitchBuilder<A> {
             rect,
             manually_hidden,
             scissor_rect,
+            key,
         } = self;
 
         let style = window_cx
@@ -167,6 +187,7 @@ impl<A: Clone + 'static> SwitchBuilder<A> {
             cursor_icon,
         })
         .builder_values(z_index, scissor_rect, class, window_cx)
+        .key(key)
         .rect(rect)
         .hidden(manually_hidden)
         .flags(ElementFlags::PAINTS | ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS)
@@ -372,6 +393,7 @@ impl<A: Clone + 'static> Element<A> for SwitchElement<A> {
                         },
                         width: style.outer_border_width,
                         radius: style.rounding.into(),
+                        edges: BorderEdges::NONE,
                     },
                     flags: style.quad_flags,
                 },
@@ -393,6 +415,7 @@ impl<A: Clone + 'static> Element<A> for SwitchElement<A> {
                         },
                         width: style.slider_border_width,
                         radius: style.rounding.into(),
+                        edges: BorderEdges::NONE,
                     },
                     flags: style.quad_flags,
                 },
@@ -407,6 +430,7 @@ impl<A: Clone + 'static> Element<A> for SwitchElement<A> {
                         color: bg_border_color,
                         width: style.outer_border_width,
                         radius: style.rounding.into(),
+                        edges: BorderEdges::NONE,
                     },
                     flags: style.quad_flags,
                 },
@@ -422,6 +446,7 @@ impl<A: Clone + 'static> Element<A> for SwitchElement<A> {
                             style.slider_border_width
                         },
                         radius: style.rounding.into(),
+                        edges: BorderEdges::NONE,
                     },
                     flags: style.quad_flags,
                 },
@@ -447,9 +472,9 @@ impl<A: Clone + 'static> Element<A> for SwitchElement<A> {
             )
         };
 
-        primitives.add(bg_quad_style.create_primitive(bg_bounds));
+        primitives.add(bg_quad_style.create_primitive_with_opacity(bg_bounds, cx.opacity));
         primitives.set_z_index(1);
-        primitives.add(slider_quad_style.create_primitive(slider_bounds));
+        primitives.add(slider_quad_style.create_primitive_with_opacity(slider_bounds, cx.opacity));
     }
 }
 
@@ -39,6 +39,12 @@ impl ElementStyle for TabStyle {
     fn default_light_style() -> Self {
         todo!()
     }
+
+    fn with_auto_disabled(&self, derivation: DisabledDerivation) -> Option<Self> {
+        let mut style = self.clone();
+        style.toggle_btn_style.derive_auto_disabled(derivation);
+        Some(style)
+    }
 }
 
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
@@ -51,8 +57,21 @@ pub enum IndicatorLinePlacement {
     Right,
 }
 
+impl IndicatorLinePlacement {
+    /// The equivalent placement with the left and right sides swapped (i.e.
+    /// for right-to-left languages).
+    pub fn mirrored(self) -> Self {
+        match self {
+            Self::Left => Self::Right,
+            Self::Right => Self::Left,
+            _ => self,
+        }
+    }
+}
+
 #[element_builder]
 #[element_builder_class]
+#[element_builder_tab_index]
 #[element_builder_rect]
 #[element_builder_hidden]
 #[element_builder_disabled]
@@ -174,14 +193,17 @@ impl<A: Clone + 'static> TabBuilder<A> {
             icon_offset,
             text_icon_layout,
             class,
+            tab_index,
             on_indicator_line_placement,
             z_index,
             rect,
             manually_hidden,
             disabled,
             scissor_rect,
+            key,
         } = self;
 
+        let reduce_motion = window_cx.res.style_system.reduce_motion();
         let style = window_cx
             .res
             .style_system
@@ -200,22 +222,47 @@ impl<A: Clone + 'static> TabBuilder<A> {
                 disabled,
                 text_icon_layout,
                 &style.toggle_btn_style,
+                reduce_motion,
                 &mut window_cx.res.font_system,
+                &mut window_cx.res.text_layout_cache,
             ),
             tooltip_inner: TooltipInner::new(tooltip_data),
         }));
 
-        let el = ElementBuilder::new(TabElement {
+        let text_direction = window_cx.text_direction();
+        RefCell::borrow_mut(&shared_state).inner.set_text_direction(
+            text_direction,
+            &style.toggle_btn_style,
+            &mut window_cx.res.font_system,
+            &mut window_cx.res.text_layout_cache,
+        );
+
+        let on_indicator_line_placement = if text_direction.is_rtl() {
+            on_indicator_line_placement.mirrored()
+        } else {
+            on_indicator_line_placement
+        };
+
+        let mut el = ElementBuilder::new(TabElement {
             shared_state: Rc::clone(&shared_state),
             action,
             on_indicator_line_placement,
             cursor_icon,
         })
         .builder_values(z_index, scissor_rect, class, window_cx)
+        .key(key)
+        .tab_index(tab_index)
         .rect(rect)
         .hidden(manually_hidden)
-        .flags(ElementFlags::PAINTS | ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS)
+        .flags(
+            ElementFlags::PAINTS
+                | ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS
+                | ElementFlags::LISTENS_TO_FOCUS_CHANGE
+                | ElementFlags::FOCUSABLE
+                | ElementFlags::DRAWS_OWN_FOCUS_RING,
+        )
         .build(window_cx);
+        el.set_focusable(!disabled);
 
         Tab { el, shared_state }
     }
@@ -237,17 +284,53 @@ impl<A: Clone + 'static> Element<A> for TabElement<A> {
     ) -> EventCaptureStatus {
         let mut shared_state = RefCell::borrow_mut(&self.shared_state);
 
-        shared_state
-            .tooltip_inner
-            .handle_event(&event, shared_state.inner.disabled(), cx);
+        shared_state.tooltip_inner.handle_event_with_auto_text(
+            &event,
+            shared_state.inner.disabled(),
+            shared_state.inner.truncated_text(),
+            cx,
+        );
 
         match event {
             ElementEvent::CustomStateChanged => {
                 cx.request_repaint();
+                cx.set_animating(shared_state.inner.is_transitioning());
             }
             ElementEvent::StyleChanged => {
                 let style = cx.res.style_system.get::<TabStyle>(cx.class());
                 self.cursor_icon = style.toggle_btn_style.cursor_icon;
+                let transitions = style.toggle_btn_style.transitions;
+                let reduce_motion = cx.res.style_system.reduce_motion();
+                shared_state
+                    .inner
+                    .set_transitions(transitions, reduce_motion);
+            }
+            ElementEvent::Animation { delta_seconds } => {
+                let animating = shared_state.inner.advance_transition(delta_seconds);
+                cx.set_animating(animating);
+                cx.request_repaint();
+            }
+            ElementEvent::Focus(has_focus) => {
+                if shared_state.inner.set_focused(has_focus) {
+                    cx.request_repaint();
+                    cx.set_animating(shared_state.inner.is_transitioning());
+                }
+            }
+            ElementEvent::Activate => {
+                if shared_state.inner.disabled() {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                if shared_state.inner.set_toggled(true) {
+                    if let Some(action) = &self.action {
+                        cx.send_action(action.clone()).unwrap();
+                    }
+
+                    cx.request_repaint();
+                    cx.set_animating(shared_state.inner.is_transitioning());
+                }
+
+                return EventCaptureStatus::Captured;
             }
             ElementEvent::Pointer(PointerEvent::Moved { .. }) => {
                 if shared_state.inner.state() == ButtonState::Disabled {
@@ -258,11 +341,12 @@ impl<A: Clone + 'static> Element<A> for TabElement<A> {
                     cx.cursor_icon = cursor_icon;
                 }
 
-                if shared_state.inner.state() == ButtonState::Idle {
+                if shared_state.inner.state().is_resting() {
                     let needs_repaint = shared_state.inner.set_state(ButtonState::Hovered);
 
                     if needs_repaint {
                         cx.request_repaint();
+                        cx.set_animating(shared_state.inner.is_transitioning());
                     }
                 }
 
@@ -272,10 +356,12 @@ impl<A: Clone + 'static> Element<A> for TabElement<A> {
                 if shared_state.inner.state() == ButtonState::Hovered
                     || shared_state.inner.state() == ButtonState::Down
                 {
-                    let needs_repaint = shared_state.inner.set_state(ButtonState::Idle);
+                    let resting_state = shared_state.inner.resting_state();
+                    let needs_repaint = shared_state.inner.set_state(resting_state);
 
                     if needs_repaint {
                         cx.request_repaint();
+                        cx.set_animating(shared_state.inner.is_transitioning());
                     }
 
                     return EventCaptureStatus::Captured;
@@ -283,14 +369,12 @@ impl<A: Clone + 'static> Element<A> for TabElement<A> {
             }
             ElementEvent::Pointer(PointerEvent::ButtonJustPressed { button, .. }) => {
                 if button == PointerButton::Primary
-                    && (shared_state.inner.state() == ButtonState::Idle
+                    && (shared_state.inner.state().is_resting()
                         || shared_state.inner.state() == ButtonState::Hovered)
                 {
                     shared_state.inner.set_state(ButtonState::Down);
 
-                    if !shared_state.inner.toggled {
-                        shared_state.inner.toggled = true;
-
+                    if shared_state.inner.set_toggled(true) {
                         if let Some(action) = &self.action {
                             cx.send_action(action.clone()).unwrap();
                         }
@@ -299,6 +383,7 @@ impl<A: Clone + 'static> Element<A> for TabElement<A> {
                     }
 
                     cx.request_repaint();
+                    cx.set_animating(shared_state.inner.is_transitioning());
                     return EventCaptureStatus::Captured;
                 }
             }
@@ -312,13 +397,14 @@ impl<A: Clone + 'static> Element<A> for TabElement<A> {
                     let new_state = if cx.is_point_within_visible_bounds(position) {
                         ButtonState::Hovered
                     } else {
-                        ButtonState::Idle
+                        shared_state.inner.resting_state()
                     };
 
                     let needs_repaint = shared_state.inner.set_state(new_state);
 
                     if needs_repaint {
                         cx.request_repaint();
+                        cx.set_animating(shared_state.inner.is_transitioning());
                     }
 
                     return EventCaptureStatus::Captured;
@@ -335,16 +421,30 @@ impl<A: Clone + 'static> Element<A> for TabElement<A> {
 
         let style: &TabStyle = cx.res.style_system.get(cx.class);
 
+        let bounds = Rect::from_size(cx.bounds_size);
+
         let label_primitives = shared_state.inner.render(
-            Rect::from_size(cx.bounds_size),
+            bounds,
             &style.toggle_btn_style,
+            cx.opacity,
             &mut cx.res.font_system,
+            &mut cx.res.text_layout_cache,
+            &cx.res.icon_font_system,
         );
 
+        #[cfg(feature = "image")]
+        shared_state
+            .inner
+            .label_style(&style.toggle_btn_style)
+            .back_quad
+            .add_image_primitives(bounds, &cx.res.image_system, primitives);
+
         if let Some(quad_primitive) = label_primitives.bg_quad {
             primitives.add(quad_primitive);
         }
 
+        label_primitives.bg_overlay.add_to(primitives);
+
         if let Some(p) = label_primitives.text {
             primitives.set_z_index(1);
             primitives.add_text(p);
@@ -356,7 +456,7 @@ impl<A: Clone + 'static> Element<A> for TabElement<A> {
 
         if style.on_indicator_line_width > 0.0
             && !style.on_indicator_line_style.is_transparent()
-            && shared_state.inner.toggled
+            && shared_state.inner.toggled()
         {
             primitives.set_z_index(1);
 
@@ -397,7 +497,22 @@ impl<A: Clone + 'static> Element<A> for TabElement<A> {
                 ),
             };
 
-            primitives.add(style.on_indicator_line_style.create_primitive(line_rect));
+            primitives.add(
+                style
+                    .on_indicator_line_style
+                    .create_primitive_with_opacity(line_rect, cx.opacity),
+            );
+        }
+
+        let radius = style.toggle_btn_style.back_border_radius;
+        if let Some(p) = shared_state.inner.focus_ring_primitive(
+            bounds,
+            radius,
+            cx.res.style_system.focus_ring(),
+            cx.opacity,
+        ) {
+            primitives.set_z_index(2);
+            primitives.add(p);
         }
     }
 }
@@ -410,6 +525,7 @@ struct SharedState {
 /// A handle to a [`TabElement`].
 #[element_handle]
 #[element_handle_class]
+#[element_handle_tab_index]
 #[element_handle_set_rect]
 #[element_handle_set_tooltip]
 pub struct Tab {
@@ -429,8 +545,7 @@ impl Tab {
     pub fn set_toggled(&mut self, toggled: bool) -> bool {
         let mut shared_state = RefCell::borrow_mut(&self.shared_state);
 
-        if shared_state.inner.toggled != toggled {
-            shared_state.inner.toggled = toggled;
+        if shared_state.inner.set_toggled(toggled) {
             self.el.notify_custom_state_change();
             true
         } else {
@@ -439,7 +554,7 @@ impl Tab {
     }
 
     pub fn toggled(&self) -> bool {
-        RefCell::borrow(&self.shared_state).inner.toggled
+        RefCell::borrow(&self.shared_state).inner.toggled()
     }
 
     /// Returns the size of the padded background rectangle if it were to
@@ -473,12 +588,17 @@ impl Tab {
     ) -> bool {
         let mut shared_state = RefCell::borrow_mut(&self.shared_state);
 
-        if shared_state.inner.set_text(text, &mut res.font_system, || {
-            res.style_system
-                .get::<TabStyle>(self.el.class())
-                .toggle_btn_style
-                .text_properties
-        }) {
+        if shared_state.inner.set_text(
+            text,
+            &mut res.font_system,
+            &mut res.text_layout_cache,
+            || {
+                res.style_system
+                    .get::<TabStyle>(self.el.class())
+                    .toggle_btn_style
+                    .text_properties
+            },
+        ) {
             self.el.notify_custom_state_change();
             true
         } else {
@@ -600,13 +720,15 @@ impl Tab {
     /// so this method is relatively cheap to call frequently.
     pub fn set_disabled(&mut self, disabled: bool) -> bool {
         let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+        self.el.set_focusable(!disabled);
 
         if disabled && shared_state.inner.state() != ButtonState::Disabled {
             shared_state.inner.set_state(ButtonState::Disabled);
             self.el.notify_custom_state_change();
             true
         } else if !disabled && shared_state.inner.state() == ButtonState::Disabled {
-            shared_state.inner.set_state(ButtonState::Idle);
+            let resting_state = shared_state.inner.resting_state();
+            shared_state.inner.set_state(resting_state);
             self.el.notify_custom_state_change();
             true
         } else {
@@ -816,6 +938,11 @@ impl TabGroup {
         self.tabs[selected_index].set_toggled(true);
     }
 
+    /// The index of the selected tab.
+    pub fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
     pub fn bounds(&self) -> Rect {
         self.bounds
     }
@@ -31,6 +31,7 @@ impl<A: Clone + 'static> FloatingTextInputBuilder<A> {
             max_characters: 256,
             z_index: Default::default(),
             scissor_rect: Default::default(),
+            key: Default::default(),
             class: Default::default(),
             rect: Default::default(),
         }
@@ -91,6 +92,7 @@ impl<A: Clone + 'static> FloatingTextInputBuilder<A> {
             z_index,
             rect,
             scissor_rect,
+            key,
         } = self;
 
         let style = window_cx
@@ -124,6 +126,7 @@ impl<A: Clone + 'static> FloatingTextInputBuilder<A> {
             hovered: false,
         })
         .builder_values(z_index, scissor_rect, class, window_cx)
+        .key(key)
         .rect(rect)
         .hidden(true)
         .flags(
@@ -299,6 +302,7 @@ impl<A: Clone + 'static> Element<A> for FloatingTextInputElement<A> {
             Rect::from_size(cx.bounds_size),
             shared_state.text_offset,
             self.hovered,
+            cx.opacity,
         );
 
         if let Some(back_quad) = p.back_quad.take() {
@@ -111,10 +111,18 @@ impl ElementStyle for IconTextInputStyle {
             ..Default::default()
         }
     }
+
+    fn with_auto_disabled(&self, derivation: DisabledDerivation) -> Option<Self> {
+        let mut style = self.clone();
+        style.icon_color_disabled.derive_auto(derivation);
+        style.text_input.derive_auto_disabled(derivation);
+        Some(style)
+    }
 }
 
 #[element_builder]
 #[element_builder_class]
+#[element_builder_tab_index]
 #[element_builder_rect]
 #[element_builder_hidden]
 #[element_builder_disabled]
@@ -151,6 +159,7 @@ impl<A: Clone + 'static> IconTextInputBuilder<A> {
             max_characters: 256,
             z_index: Default::default(),
             scissor_rect: Default::default(),
+            key: Default::default(),
             class: Default::default(),
             rect: Default::default(),
             manually_hidden: Default::default(),
@@ -220,6 +229,10 @@ impl<A: Clone + 'static> IconTextInputBuilder<A> {
     }
 
     /// If set the `true`, then text will be displayed in "password mode".
+    ///
+    /// In password mode the characters are masked, and the contents cannot be
+    /// copied or cut to the clipboard while they are masked. Use
+    /// `show_password` on the handle to reveal the text.
     pub const fn password_mode(mut self, do_use: bool) -> Self {
         self.password_mode = do_use;
         self
@@ -250,10 +263,12 @@ impl<A: Clone + 'static> IconTextInputBuilder<A> {
             max_characters,
             disabled,
             class,
+            tab_index,
             z_index,
             rect,
             manually_hidden,
             scissor_rect,
+            key,
         } = self;
 
         let style = window_cx
@@ -285,7 +300,7 @@ impl<A: Clone + 'static> IconTextInputBuilder<A> {
             tooltip_inner: TooltipInner::new(tooltip_data),
         }));
 
-        let el = ElementBuilder::new(IconTextInputElement {
+        let mut el = ElementBuilder::new(IconTextInputElement {
             shared_state: Rc::clone(&shared_state),
             action,
             right_click_action,
@@ -295,6 +310,8 @@ impl<A: Clone + 'static> IconTextInputBuilder<A> {
             hovered: false,
         })
         .builder_values(z_index, scissor_rect, class, window_cx)
+        .key(key)
+        .tab_index(tab_index)
         .rect(rect)
         .hidden(manually_hidden)
         .flags(
@@ -304,9 +321,11 @@ impl<A: Clone + 'static> IconTextInputBuilder<A> {
                 | ElementFlags::LISTENS_TO_TEXT_COMPOSITION_WHEN_FOCUSED
                 | ElementFlags::LISTENS_TO_KEYS_WHEN_FOCUSED
                 | ElementFlags::LISTENS_TO_SIZE_CHANGE
-                | ElementFlags::LISTENS_TO_FOCUS_CHANGE,
+                | ElementFlags::LISTENS_TO_FOCUS_CHANGE
+                | ElementFlags::FOCUSABLE,
         )
         .build(window_cx);
+        el.set_focusable(!disabled);
 
         IconTextInput { el, shared_state }
     }
@@ -448,6 +467,7 @@ impl<A: Clone + 'static> Element<A> for IconTextInputElement<A> {
             Rect::from_size(cx.bounds_size),
             shared_state.text_offset,
             self.hovered,
+            cx.opacity,
         );
 
         if let Some(back_quad) = p.back_quad.take() {
@@ -486,6 +506,7 @@ struct SharedState {
 /// A handle to a [`IconTextInputElement`]
 #[element_handle]
 #[element_handle_class]
+#[element_handle_tab_index]
 #[element_handle_set_rect]
 #[element_handle_set_tooltip]
 pub struct IconTextInput {
@@ -572,9 +593,10 @@ impl IconTextInput {
     /// so this method is relatively cheap to call frequently.
     pub fn set_disabled(&mut self, disabled: bool) -> bool {
         let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+        self.el.set_focusable(!disabled);
 
         if shared_state.inner.disabled != disabled {
-            shared_state.inner.disabled = true;
+            shared_state.inner.disabled = disabled;
             self.el.notify_custom_state_change();
             true
         } else {
@@ -635,6 +657,30 @@ impl IconTextInput {
             false
         }
     }
+
+    /// Toggle between showing and hiding the password. This has no effect if
+    /// the element wasn't created with password mode enabled.
+    ///
+    /// Returns `true` if the password is now shown.
+    pub fn toggle_show_password(&mut self) -> bool {
+        let show = !self.password_shown();
+        self.show_password(show);
+        self.password_shown()
+    }
+
+    /// Returns `true` if the password is currently being shown (unmasked).
+    ///
+    /// This will always return `false` if the element wasn't created with
+    /// password mode enabled.
+    pub fn password_shown(&self) -> bool {
+        let shared_state = RefCell::borrow(&self.shared_state);
+        shared_state.inner.password_mode() && shared_state.inner.show_password
+    }
+
+    /// Returns `true` if this element was created with password mode enabled.
+    pub fn password_mode(&self) -> bool {
+        RefCell::borrow(&self.shared_state).inner.password_mode()
+    }
 }
 
 struct LayoutResult {
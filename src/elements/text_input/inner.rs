@@ -1,6 +1,7 @@
 use smallvec::SmallVec;
-use std::time::{Duration, Instant};
+use std::time::Duration;
 use unicode_segmentation::UnicodeSegmentation;
+use web_time::Instant;
 
 use crate::clipboard::{Clipboard, ClipboardKind};
 use crate::prelude::*;
@@ -193,6 +194,18 @@ impl Default for TextInputStyle {
     }
 }
 
+impl TextInputStyle {
+    /// Derive every disabled-state property set to `Auto` with the given
+    /// derivation (see
+    /// [`StyleSystem::set_auto_disabled`](crate::style::StyleSystem::set_auto_disabled)).
+    pub fn derive_auto_disabled(&mut self, derivation: DisabledDerivation) {
+        self.text_color_disabled.derive_auto(derivation);
+        self.text_color_placeholder_disabled.derive_auto(derivation);
+        self.back_bg_disabled.derive_auto(derivation);
+        self.back_border_color_disabled.derive_auto(derivation);
+    }
+}
+
 impl ElementStyle for TextInputStyle {
     const ID: &'static str = "txtinpt";
 
@@ -206,6 +219,12 @@ impl ElementStyle for TextInputStyle {
             ..Default::default()
         }
     }
+
+    fn with_auto_disabled(&self, derivation: DisabledDerivation) -> Option<Self> {
+        let mut style = self.clone();
+        style.derive_auto_disabled(derivation);
+        Some(style)
+    }
 }
 
 #[derive(Default, Debug, Clone, Copy, PartialEq)]
@@ -1020,6 +1039,9 @@ impl TextInputInner {
     ) {
         for action in self.queued_actions.drain(..) {
             match action {
+                // Don't allow the masked contents of a password to be copied
+                // out of the input.
+                TextInputAction::Cut | TextInputAction::Copy if self.is_masked() => {}
                 TextInputAction::Cut => {
                     self.buffer.with_editor_mut(
                         |editor, font_system| -> EditorBorrowStatus {
@@ -1240,6 +1262,7 @@ impl TextInputInner {
         bounds: Rect,
         text_offset: Vector,
         hovered: bool,
+        opacity: f32,
     ) -> TextInputPrimitives {
         let mut primitives = TextInputPrimitives {
             back_quad: None,
@@ -1257,12 +1280,14 @@ impl TextInputInner {
                         .get(style.back_border_color),
                     width: style.back_border_width,
                     radius: style.back_border_radius,
+                    edges: BorderEdges::NONE,
                 },
                 flags: style.quad_flags,
             };
 
             if !quad_style.is_transparent() {
-                primitives.back_quad = Some(quad_style.create_primitive(bounds));
+                primitives.back_quad =
+                    Some(quad_style.create_primitive_with_opacity(bounds, opacity));
             }
         } else if self.focused {
             let bg = style.back_bg_focused.unwrap_or(style.back_bg);
@@ -1280,10 +1305,11 @@ impl TextInputInner {
                                 .unwrap_or(style.back_border_color),
                             width: border_width,
                             radius: style.back_border_radius,
+                            edges: BorderEdges::NONE,
                         },
                         flags: style.quad_flags,
                     }
-                    .create_primitive(bounds),
+                    .create_primitive_with_opacity(bounds, opacity),
                 );
             }
         } else if hovered {
@@ -1302,10 +1328,11 @@ impl TextInputInner {
                                 .unwrap_or(style.back_border_color),
                             width: border_width,
                             radius: style.back_border_radius,
+                            edges: BorderEdges::NONE,
                         },
                         flags: style.quad_flags,
                     }
-                    .create_primitive(bounds),
+                    .create_primitive_with_opacity(bounds, opacity),
                 );
             }
         } else {
@@ -1317,10 +1344,11 @@ impl TextInputInner {
                             color: style.back_border_color,
                             width: style.back_border_width,
                             radius: style.back_border_radius,
+                            edges: BorderEdges::NONE,
                         },
                         flags: style.quad_flags,
                     }
-                    .create_primitive(bounds),
+                    .create_primitive_with_opacity(bounds, opacity),
                 );
             }
         }
@@ -1355,7 +1383,7 @@ impl TextInputInner {
                                 start_x - (style.cursor_width * 0.5) + bounds.min_x(),
                                 highlight_y + bounds.min_y(),
                             ))
-                            .bg_color(style.highlight_bg_color)
+                            .bg_color(apply_opacity(style.highlight_bg_color, opacity))
                             .flags(style.quad_flags)
                             .into(),
                     );
@@ -1389,7 +1417,7 @@ impl TextInputInner {
                 pos: self.text_bounds_rect.origin + text_offset
                     - Point::new(scroll_x, 0.0).to_vector()
                     + bounds.origin.to_vector(),
-                color,
+                color: apply_opacity(color, opacity),
                 clipping_bounds: Some(Rect::new(
                     Point::new(scroll_x, 0.0) + bounds.origin.to_vector(),
                     self.text_bounds_rect.size,
@@ -1418,7 +1446,7 @@ impl TextInputInner {
                 primitives.text = Some(TextPrimitive {
                     buffer: Some(placeholder_buffer.clone()),
                     pos: self.text_bounds_rect.origin + text_offset + bounds.origin.to_vector(),
-                    color,
+                    color: apply_opacity(color, opacity),
                     clipping_bounds: Some(Rect::new(bounds.origin, self.text_bounds_rect.size)),
                     #[cfg(feature = "svg-icons")]
                     icons: SmallVec::new(),
@@ -1437,11 +1465,12 @@ impl TextInputInner {
                         .round(),
                         highlight_y + bounds.min_y(),
                     ))
-                    .bg_color(
+                    .bg_color(apply_opacity(
                         style
                             .cursor_color
                             .unwrap_or(style.text_color_focused.unwrap_or(style.text_color)),
-                    )
+                        opacity,
+                    ))
                     .flags(style.quad_flags)
                     .into(),
             );
@@ -1450,6 +1479,17 @@ impl TextInputInner {
         primitives
     }
 
+    /// Whether or not this input was created with password mode enabled.
+    pub fn password_mode(&self) -> bool {
+        self.password_buffer.is_some()
+    }
+
+    /// Whether or not the text is currently being displayed as a masked
+    /// password.
+    pub fn is_masked(&self) -> bool {
+        self.password_buffer.is_some() && !self.show_password
+    }
+
     pub fn disabled(&self) -> bool {
         self.disabled
     }
@@ -0,0 +1,591 @@
+use std::cell::{Ref, RefCell};
+use std::rc::Rc;
+
+use crate::derive::*;
+use crate::prelude::*;
+use crate::vg::text::{RcTextBuffer, TextPrimitive};
+
+/// The style of a [`TitleBar`] element.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TitleBarStyle {
+    pub back_quad: QuadStyle,
+
+    /// The height of the title bar when it is docked with [`TitleBar::dock`].
+    ///
+    /// By default this is set to `30.0`.
+    pub height: f32,
+
+    pub title_text_properties: TextProperties,
+    pub title_color: RGBA8,
+    /// Whether the title is centered in the bar. If `false`, then the title
+    /// is aligned to the left.
+    ///
+    /// By default this is set to `false`.
+    pub title_centered: bool,
+    /// The padding to the left of the title (when it is aligned to the left).
+    ///
+    /// By default this is set to `10.0`.
+    pub title_padding_left: f32,
+
+    /// The width of each of the window control buttons. The buttons fill
+    /// the full height of the bar.
+    ///
+    /// By default this is set to `44.0`.
+    pub button_width: f32,
+    pub button_quad_hover: QuadStyle,
+    pub button_quad_down: Option<QuadStyle>,
+    /// The hover quad of the close button. If this is `None`, then
+    /// `button_quad_hover` will be used.
+    pub close_button_quad_hover: Option<QuadStyle>,
+    pub button_text_properties: TextProperties,
+    pub button_text_color: RGBA8,
+    pub close_button_text_color_hover: Option<RGBA8>,
+
+    /// The glyph of the minimize button.
+    ///
+    /// By default this is set to `"\u{2013}"` ("–").
+    pub minimize_text: String,
+    /// The glyph of the maximize button.
+    ///
+    /// By default this is set to `"\u{25A1}"` ("□").
+    pub maximize_text: String,
+    /// The glyph of the close button.
+    ///
+    /// By default this is set to `"\u{00D7}"` ("×").
+    pub close_text: String,
+}
+
+impl Default for TitleBarStyle {
+    fn default() -> Self {
+        Self {
+            back_quad: QuadStyle {
+                bg: Background::Solid(gray(30)),
+                ..Default::default()
+            },
+            height: 30.0,
+            title_text_properties: TextProperties::default(),
+            title_color: gray(200),
+            title_centered: false,
+            title_padding_left: 10.0,
+            button_width: 44.0,
+            button_quad_hover: QuadStyle {
+                bg: Background::Solid(gray(55)),
+                ..Default::default()
+            },
+            button_quad_down: Some(QuadStyle {
+                bg: Background::Solid(gray(70)),
+                ..Default::default()
+            }),
+            close_button_quad_hover: Some(QuadStyle {
+                bg: Background::Solid(rgb(196, 43, 28)),
+                ..Default::default()
+            }),
+            button_text_properties: TextProperties::default(),
+            button_text_color: gray(200),
+            close_button_text_color_hover: Some(color::WHITE),
+            minimize_text: String::from("\u{2013}"),
+            maximize_text: String::from("\u{25A1}"),
+            close_text: String::from("\u{00D7}"),
+        }
+    }
+}
+
+impl ElementStyle for TitleBarStyle {
+    const ID: &'static str = "titlebar";
+
+    fn default_dark_style() -> Self {
+        Self::default()
+    }
+
+    fn default_light_style() -> Self {
+        Self {
+            back_quad: QuadStyle {
+                bg: Background::Solid(gray(235)),
+                ..Default::default()
+            },
+            title_color: gray(40),
+            button_quad_hover: QuadStyle {
+                bg: Background::Solid(gray(215)),
+                ..Default::default()
+            },
+            button_quad_down: Some(QuadStyle {
+                bg: Background::Solid(gray(200)),
+                ..Default::default()
+            }),
+            button_text_color: gray(40),
+            ..Default::default()
+        }
+    }
+}
+
+#[element_builder]
+#[element_builder_class]
+#[element_builder_rect]
+#[element_builder_hidden]
+pub struct TitleBarBuilder {
+    pub title: String,
+    pub show_minimize: bool,
+    pub show_maximize: bool,
+    pub show_close: bool,
+}
+
+impl Default for TitleBarBuilder {
+    fn default() -> Self {
+        Self {
+            title: String::new(),
+            show_minimize: true,
+            show_maximize: true,
+            show_close: true,
+            class: None,
+            z_index: None,
+            rect: Rect::default(),
+            manually_hidden: false,
+            scissor_rect: None,
+            key: None,
+        }
+    }
+}
+
+impl TitleBarBuilder {
+    /// The title shown in the bar.
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// Whether to show the minimize button.
+    ///
+    /// By default this is set to `true`.
+    pub const fn show_minimize(mut self, show: bool) -> Self {
+        self.show_minimize = show;
+        self
+    }
+
+    /// Whether to show the maximize button. This also controls whether
+    /// double-clicking the bar maximizes the window.
+    ///
+    /// By default this is set to `true`.
+    pub const fn show_maximize(mut self, show: bool) -> Self {
+        self.show_maximize = show;
+        self
+    }
+
+    /// Whether to show the close button.
+    ///
+    /// By default this is set to `true`.
+    pub const fn show_close(mut self, show: bool) -> Self {
+        self.show_close = show;
+        self
+    }
+
+    pub fn build<A: Clone + 'static>(self, window_cx: &mut WindowContext<'_, A>) -> TitleBar {
+        let TitleBarBuilder {
+            title,
+            show_minimize,
+            show_maximize,
+            show_close,
+            class,
+            z_index,
+            rect,
+            manually_hidden,
+            scissor_rect,
+            key,
+        } = self;
+
+        let style = window_cx
+            .res
+            .style_system
+            .get::<TitleBarStyle>(window_cx.builder_class(class));
+
+        let text_buffers = TextBuffers::new(&title, style, &mut window_cx.res.font_system);
+
+        let shared_state = Rc::new(RefCell::new(SharedState {
+            title,
+            title_changed: false,
+            buttons: ButtonsShown {
+                minimize: show_minimize,
+                maximize: show_maximize,
+                close: show_close,
+            },
+        }));
+
+        let el = ElementBuilder::new(TitleBarElement {
+            shared_state: Rc::clone(&shared_state),
+            text_buffers,
+            hovered: None,
+            pressed: None,
+        })
+        .builder_values(z_index, scissor_rect, class, window_cx)
+        .key(key)
+        .rect(rect)
+        .hidden(manually_hidden)
+        .flags(ElementFlags::PAINTS | ElementFlags::LISTENS_TO_POINTER_INSIDE_BOUNDS)
+        .build(window_cx);
+
+        TitleBar { el, shared_state }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TitleBarButton {
+    Minimize,
+    Maximize,
+    Close,
+}
+
+impl TitleBarButton {
+    fn window_control(&self) -> WindowControl {
+        match self {
+            Self::Minimize => WindowControl::Minimize,
+            Self::Maximize => WindowControl::ToggleMaximize,
+            Self::Close => WindowControl::Close,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ButtonsShown {
+    minimize: bool,
+    maximize: bool,
+    close: bool,
+}
+
+impl ButtonsShown {
+    /// The shown buttons and their rectangles, from right to left.
+    fn layout(&self, bounds: Rect, button_width: f32) -> Vec<(TitleBarButton, Rect)> {
+        let mut buttons = Vec::with_capacity(3);
+        let mut x = bounds.max_x();
+
+        for (button, shown) in [
+            (TitleBarButton::Close, self.close),
+            (TitleBarButton::Maximize, self.maximize),
+            (TitleBarButton::Minimize, self.minimize),
+        ] {
+            if shown {
+                x -= button_width;
+                buttons.push((
+                    button,
+                    Rect::new(
+                        Point::new(x, bounds.min_y()),
+                        Size::new(button_width, bounds.height()),
+                    ),
+                ));
+            }
+        }
+
+        buttons
+    }
+}
+
+struct TextBuffers {
+    title: RcTextBuffer,
+    minimize: RcTextBuffer,
+    maximize: RcTextBuffer,
+    close: RcTextBuffer,
+}
+
+impl TextBuffers {
+    fn new(title: &str, style: &TitleBarStyle, font_system: &mut FontSystem) -> Self {
+        let mut new_buffer = |text: &str, properties: TextProperties| {
+            RcTextBuffer::new(text, properties, None, None, false, font_system)
+        };
+
+        Self {
+            title: new_buffer(title, style.title_text_properties),
+            minimize: new_buffer(&style.minimize_text, style.button_text_properties),
+            maximize: new_buffer(&style.maximize_text, style.button_text_properties),
+            close: new_buffer(&style.close_text, style.button_text_properties),
+        }
+    }
+
+    fn button(&self, button: TitleBarButton) -> &RcTextBuffer {
+        match button {
+            TitleBarButton::Minimize => &self.minimize,
+            TitleBarButton::Maximize => &self.maximize,
+            TitleBarButton::Close => &self.close,
+        }
+    }
+}
+
+/// A title bar for windows without decorations.
+struct TitleBarElement {
+    shared_state: Rc<RefCell<SharedState>>,
+    text_buffers: TextBuffers,
+    hovered: Option<TitleBarButton>,
+    pressed: Option<TitleBarButton>,
+}
+
+impl TitleBarElement {
+    fn button_at(
+        &self,
+        position: Point,
+        buttons: ButtonsShown,
+        bounds: Rect,
+        style: &TitleBarStyle,
+    ) -> Option<TitleBarButton> {
+        buttons
+            .layout(bounds, style.button_width)
+            .into_iter()
+            .find(|(_, rect)| rect.contains(position))
+            .map(|(button, _)| button)
+    }
+}
+
+impl<A: Clone + 'static> Element<A> for TitleBarElement {
+    fn on_event(
+        &mut self,
+        event: ElementEvent,
+        cx: &mut ElementContext<'_, A>,
+    ) -> EventCaptureStatus {
+        let shared_state_rc = Rc::clone(&self.shared_state);
+        let mut shared_state = RefCell::borrow_mut(&shared_state_rc);
+
+        match event {
+            ElementEvent::CustomStateChanged => {
+                if std::mem::take(&mut shared_state.title_changed) {
+                    let style = cx.res.style_system.get::<TitleBarStyle>(cx.class());
+                    self.text_buffers.title = RcTextBuffer::new(
+                        &shared_state.title,
+                        style.title_text_properties,
+                        None,
+                        None,
+                        false,
+                        &mut cx.res.font_system,
+                    );
+                }
+
+                cx.request_repaint();
+            }
+            ElementEvent::StyleChanged => {
+                let style = cx.res.style_system.get::<TitleBarStyle>(cx.class());
+                self.text_buffers =
+                    TextBuffers::new(&shared_state.title, style, &mut cx.res.font_system);
+                cx.request_repaint();
+            }
+            ElementEvent::Pointer(PointerEvent::Moved { position, .. }) => {
+                let style = cx.res.style_system.get::<TitleBarStyle>(cx.class());
+                let hovered = self.button_at(position, shared_state.buttons, cx.rect(), style);
+
+                if self.hovered != hovered {
+                    self.hovered = hovered;
+                    cx.request_repaint();
+                }
+
+                return EventCaptureStatus::Captured;
+            }
+            ElementEvent::Pointer(PointerEvent::PointerLeft) => {
+                if self.hovered.take().is_some() || self.pressed.take().is_some() {
+                    cx.request_repaint();
+                }
+            }
+            ElementEvent::Pointer(PointerEvent::ButtonJustPressed {
+                position,
+                button,
+                click_count,
+                ..
+            }) => {
+                if button != PointerButton::Primary {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                let style = cx.res.style_system.get::<TitleBarStyle>(cx.class());
+
+                if let Some(button) =
+                    self.button_at(position, shared_state.buttons, cx.rect(), style)
+                {
+                    self.pressed = Some(button);
+                    cx.request_repaint();
+                } else if click_count == 2 && shared_state.buttons.maximize {
+                    cx.request_window_control(WindowControl::ToggleMaximize);
+                } else {
+                    cx.request_window_control(WindowControl::StartDragMove);
+                }
+
+                return EventCaptureStatus::Captured;
+            }
+            ElementEvent::Pointer(PointerEvent::ButtonJustReleased {
+                position, button, ..
+            }) => {
+                if button != PointerButton::Primary {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                if let Some(pressed) = self.pressed.take() {
+                    let style = cx.res.style_system.get::<TitleBarStyle>(cx.class());
+
+                    if self.button_at(position, shared_state.buttons, cx.rect(), style)
+                        == Some(pressed)
+                    {
+                        cx.request_window_control(pressed.window_control());
+                    }
+
+                    cx.request_repaint();
+                    return EventCaptureStatus::Captured;
+                }
+            }
+            _ => {}
+        }
+
+        EventCaptureStatus::NotCaptured
+    }
+
+    fn render(&mut self, cx: RenderContext, primitives: &mut PrimitiveGroup) {
+        let shared_state = RefCell::borrow(&self.shared_state);
+        let style = cx.res.style_system.get::<TitleBarStyle>(cx.class);
+
+        let bounds = Rect::from_size(cx.bounds_size);
+
+        if !style.back_quad.is_transparent() {
+            primitives.add(
+                style
+                    .back_quad
+                    .create_primitive_with_opacity(bounds, cx.opacity),
+            );
+        }
+
+        let buttons = shared_state.buttons.layout(bounds, style.button_width);
+        let buttons_min_x = buttons
+            .last()
+            .map(|(_, rect)| rect.min_x())
+            .unwrap_or(bounds.max_x());
+
+        let mut texts: Vec<TextPrimitive> = Vec::with_capacity(buttons.len() + 1);
+
+        let title_size = self.text_buffers.title.measure();
+        let title_x = if style.title_centered {
+            bounds.center().x - (title_size.width * 0.5)
+        } else {
+            style.title_padding_left
+        };
+        let title_clip_width = (buttons_min_x - title_x).max(0.0);
+        if title_clip_width > 0.0 {
+            texts.push(TextPrimitive::new(
+                self.text_buffers.title.clone(),
+                Point::new(title_x, bounds.center().y - (title_size.height * 0.5)),
+                apply_opacity(style.title_color, cx.opacity),
+                Some(Rect::from_size(Size::new(
+                    title_clip_width,
+                    title_size.height,
+                ))),
+            ));
+        }
+
+        for (button, rect) in buttons {
+            let hovered = self.hovered == Some(button);
+            let pressed = self.pressed == Some(button);
+
+            let quad_style = if button == TitleBarButton::Close && (hovered || pressed) {
+                style
+                    .close_button_quad_hover
+                    .as_ref()
+                    .or(Some(&style.button_quad_hover))
+            } else if pressed {
+                style
+                    .button_quad_down
+                    .as_ref()
+                    .or(Some(&style.button_quad_hover))
+            } else if hovered {
+                Some(&style.button_quad_hover)
+            } else {
+                None
+            };
+
+            if let Some(quad_style) = quad_style {
+                if !quad_style.is_transparent() {
+                    primitives.add(quad_style.create_primitive_with_opacity(rect, cx.opacity));
+                }
+            }
+
+            let text_color = if button == TitleBarButton::Close && (hovered || pressed) {
+                style
+                    .close_button_text_color_hover
+                    .unwrap_or(style.button_text_color)
+            } else {
+                style.button_text_color
+            };
+
+            let buffer = self.text_buffers.button(button);
+            let size = buffer.measure();
+            texts.push(TextPrimitive::new(
+                buffer.clone(),
+                rect.center() - (size * 0.5).to_vector(),
+                apply_opacity(text_color, cx.opacity),
+                None,
+            ));
+        }
+
+        primitives.set_z_index(1);
+        primitives.add_text_batch(texts);
+    }
+}
+
+struct SharedState {
+    title: String,
+    title_changed: bool,
+    buttons: ButtonsShown,
+}
+
+/// A handle to a [`TitleBarElement`], a themed title bar for windows created
+/// with `decorations: false` in their [`WindowConfig`].
+///
+/// Dragging the empty area of the bar moves the window, double-clicking it
+/// maximizes or restores the window, and the minimize, maximize, and close
+/// buttons control the window directly. Closing the window goes through the
+/// same path as [`AppContext::close_window`].
+#[element_handle]
+#[element_handle_class]
+#[element_handle_set_rect]
+pub struct TitleBar {
+    shared_state: Rc<RefCell<SharedState>>,
+}
+
+impl TitleBar {
+    pub fn builder() -> TitleBarBuilder {
+        TitleBarBuilder::default()
+    }
+
+    /// Dock the bar to the top of a window with the given size, using the
+    /// height from the [`TitleBarStyle`].
+    ///
+    /// Call this from the layout function of the window whenever the window
+    /// is resized.
+    ///
+    /// Returns `true` if the rectangle of the bar has changed.
+    pub fn dock(&mut self, window_size: Size, res: &mut ResourceCtx) -> bool {
+        let height = res
+            .style_system
+            .get::<TitleBarStyle>(self.el.class())
+            .height
+            .min(window_size.height)
+            .max(0.0);
+
+        self.el.set_rect(Rect::new(
+            Point::zero(),
+            Size::new(window_size.width, height),
+        ))
+    }
+
+    /// Set the title shown in the bar.
+    ///
+    /// Returns `true` if the title has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed.
+    /// However, this method still involves a string comparison so you may want
+    /// to call this method sparingly.
+    pub fn set_title<T: AsRef<str> + Into<String>>(&mut self, title: T) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if shared_state.title.as_str() != title.as_ref() {
+            shared_state.title = title.into();
+            shared_state.title_changed = true;
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn title<'a>(&'a self) -> Ref<'a, str> {
+        Ref::map(RefCell::borrow(&self.shared_state), |s| s.title.as_str())
+    }
+}
@@ -3,8 +3,9 @@ use std::rc::Rc;
 
 use crate::derive::*;
 use crate::prelude::*;
+use crate::vg::text::{RcTextBuffer, TextPrimitive};
 
-use super::label::LabelInner;
+use super::label::{LabelInner, LabelPrimitives};
 
 /// Tooltip data assigned to an element
 #[derive(Default, Debug, Clone, PartialEq)]
@@ -13,6 +14,8 @@ pub struct TooltipData {
     pub text: String,
     /// Where to align the tooltip relative to this element
     pub align: Align2,
+    /// An optional keyboard shortcut to show right-aligned next to the text
+    pub shortcut: Option<KeyChord>,
 }
 
 impl TooltipData {
@@ -24,8 +27,18 @@ impl TooltipData {
         Self {
             text: text.into(),
             align,
+            shortcut: None,
         }
     }
+
+    /// Show the given keyboard shortcut right-aligned next to the text.
+    ///
+    /// To show the shortcut registered with an action, use
+    /// `AppContext::shortcut_for_action`.
+    pub fn with_shortcut(mut self, shortcut: Option<KeyChord>) -> Self {
+        self.shortcut = shortcut;
+        self
+    }
 }
 
 /// A struct that can be used by elements to simplify tooltip handling
@@ -57,23 +70,49 @@ impl TooltipInner {
                 state_changed = true;
             }
         } else if let Some(text) = text {
-            self.data = Some(TooltipData {
-                text: text.into(),
-                align,
-            });
+            self.data = Some(TooltipData::new(text, align));
             state_changed = true;
         }
 
         state_changed
     }
 
+    /// Set the keyboard shortcut to show next to the tooltip text.
+    ///
+    /// This has no effect if no tooltip text is set.
+    ///
+    /// Returns `true` if the shortcut has changed.
+    pub fn set_shortcut(&mut self, shortcut: Option<KeyChord>) -> bool {
+        if let Some(data) = &mut self.data {
+            if data.shortcut != shortcut {
+                data.shortcut = shortcut;
+                return true;
+            }
+        }
+
+        false
+    }
+
     pub fn handle_event<A: Clone + 'static>(
         &self,
         event: &ElementEvent,
         disabled: bool,
         cx: &mut ElementContext<'_, A>,
     ) {
-        if disabled || self.data.is_none() {
+        self.handle_event_with_auto_text(event, disabled, None, cx);
+    }
+
+    /// The same as [`TooltipInner::handle_event`], except that if no tooltip
+    /// data is set, then a tooltip with `auto_text` is shown instead (i.e.
+    /// the full text of a truncated label).
+    pub fn handle_event_with_auto_text<A: Clone + 'static>(
+        &self,
+        event: &ElementEvent,
+        disabled: bool,
+        auto_text: Option<&str>,
+        cx: &mut ElementContext<'_, A>,
+    ) {
+        if disabled || (self.data.is_none() && auto_text.is_none()) {
             return;
         }
 
@@ -84,7 +123,11 @@ impl TooltipInner {
                 }
             }
             ElementEvent::Pointer(PointerEvent::HoverTimeout { .. }) => {
-                cx.show_tooltip(self.data.clone().unwrap(), true);
+                let data = self.data.clone().unwrap_or_else(|| {
+                    TooltipData::new(auto_text.unwrap_or_default(), Align2::TOP_CENTER)
+                });
+
+                cx.show_tooltip(data, true);
             }
             _ => {}
         }
@@ -113,9 +156,35 @@ pub struct TooltipStyle {
     ///
     /// By default this is set to `QuadStyle::TRANSPARENT`.
     pub back_quad: QuadStyle,
+
+    /// The properties of the keyboard shortcut text.
+    ///
+    /// If this is `None`, then `text_properties` will be used.
+    ///
+    /// By default this is set to `None`.
+    pub shortcut_text_properties: Option<TextProperties>,
+    /// The color of the keyboard shortcut text.
+    ///
+    /// If this is `None`, then `text_color` with half opacity will be used.
+    ///
+    /// By default this is set to `None`.
+    pub shortcut_color: Option<RGBA8>,
+    /// The spacing between the text and the keyboard shortcut.
+    ///
+    /// By default this is set to `12.0`.
+    pub shortcut_spacing: f32,
 }
 
 impl TooltipStyle {
+    pub fn shortcut_color(&self) -> RGBA8 {
+        self.shortcut_color.unwrap_or(RGBA8::new(
+            self.text_color.r,
+            self.text_color.g,
+            self.text_color.b,
+            self.text_color.a / 2,
+        ))
+    }
+
     pub fn label_style(&self) -> LabelStyle {
         LabelStyle {
             text_properties: self.text_properties.clone(),
@@ -134,6 +203,9 @@ impl Default for TooltipStyle {
             text_color: color::WHITE,
             text_padding: Padding::default(),
             back_quad: QuadStyle::TRANSPARENT,
+            shortcut_text_properties: None,
+            shortcut_color: None,
+            shortcut_spacing: 12.0,
         }
     }
 }
@@ -168,6 +240,7 @@ impl TooltipBuilder {
             element_padding: Padding::new(10.0, 10.0, 10.0, 10.0),
             z_index: None,
             scissor_rect: None,
+            key: None,
         }
     }
 
@@ -193,6 +266,7 @@ impl TooltipBuilder {
             element_padding,
             z_index,
             scissor_rect,
+            key,
         } = self;
 
         let style: &TooltipStyle = window_cx
@@ -211,7 +285,9 @@ impl TooltipBuilder {
                 Default::default(),
                 &style.label_style(),
                 &mut window_cx.res.font_system,
+                &mut window_cx.res.text_layout_cache,
             ),
+            shortcut: None,
             show_with_info: None,
         }));
 
@@ -220,6 +296,7 @@ impl TooltipBuilder {
             element_padding,
         })
         .builder_values(z_index, scissor_rect, class, window_cx)
+        .key(key)
         .hidden(true)
         .flags(ElementFlags::PAINTS)
         .build(window_cx);
@@ -246,11 +323,12 @@ impl<A: Clone + 'static> Element<A> for TooltipElement {
                 let mut shared_state = RefCell::borrow_mut(&self.shared_state);
                 let SharedState {
                     inner,
+                    shortcut,
                     show_with_info,
                 } = &mut *shared_state;
 
                 if let Some((element_rect, align)) = show_with_info.take() {
-                    let size = inner.desired_size(|| {
+                    let mut size = inner.desired_size(|| {
                         cx.res
                             .style_system
                             .get::<TooltipStyle>(cx.class())
@@ -258,6 +336,18 @@ impl<A: Clone + 'static> Element<A> for TooltipElement {
                             .padding_info()
                     });
 
+                    if let Some(shortcut) = shortcut {
+                        let style = cx.res.style_system.get::<TooltipStyle>(cx.class());
+                        let shortcut_size = shortcut.measure();
+
+                        size.width += style.shortcut_spacing + shortcut_size.width;
+                        size.height = size.height.max(
+                            shortcut_size.height
+                                + style.text_padding.top
+                                + style.text_padding.bottom,
+                        );
+                    }
+
                     let origin =
                         align.align_floating_element(element_rect, size, self.element_padding);
 
@@ -288,27 +378,87 @@ impl<A: Clone + 'static> Element<A> for TooltipElement {
 
     fn render(&mut self, cx: RenderContext, primitives: &mut PrimitiveGroup) {
         let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+        let SharedState {
+            inner, shortcut, ..
+        } = &mut *shared_state;
         let style: &TooltipStyle = cx.res.style_system.get(cx.class);
 
-        let label_primitives = shared_state.inner.render(
-            Rect::from_size(cx.bounds_size),
-            &style.label_style(),
+        let bounds = Rect::from_size(cx.bounds_size);
+
+        let Some(shortcut) = shortcut else {
+            let label_primitives = inner.render(
+                bounds,
+                &style.label_style(),
+                cx.opacity,
+                &mut cx.res.font_system,
+                &mut cx.res.text_layout_cache,
+                &cx.res.icon_font_system,
+            );
+
+            add_label_primitives(label_primitives, primitives);
+            return;
+        };
+
+        // The background quad is drawn behind both the text and the shortcut,
+        // so the label is only given the area to the left of the shortcut.
+        if !style.back_quad.is_transparent() {
+            primitives.add(
+                style
+                    .back_quad
+                    .create_primitive_with_opacity(bounds, cx.opacity),
+            );
+        }
+
+        let shortcut_size = shortcut.measure();
+        let shortcut_x = bounds.max_x() - style.text_padding.right - shortcut_size.width;
+
+        let label_style = LabelStyle {
+            back_quad: QuadStyle::TRANSPARENT,
+            ..style.label_style()
+        };
+        let label_primitives = inner.render(
+            Rect::new(
+                bounds.origin,
+                Size::new(
+                    (shortcut_x - style.shortcut_spacing + style.text_padding.right).max(0.0),
+                    bounds.height(),
+                ),
+            ),
+            &label_style,
+            cx.opacity,
             &mut cx.res.font_system,
+            &mut cx.res.text_layout_cache,
+            &cx.res.icon_font_system,
         );
 
-        if let Some(quad_primitive) = label_primitives.bg_quad {
-            primitives.add(quad_primitive);
-        }
+        add_label_primitives(label_primitives, primitives);
 
-        if let Some(text_primitive) = label_primitives.text {
-            primitives.set_z_index(1);
-            primitives.add_text(text_primitive);
-        }
+        primitives.set_z_index(1);
+        primitives.add_text(TextPrimitive::new(
+            shortcut.clone(),
+            Point::new(shortcut_x, bounds.center().y - (shortcut_size.height * 0.5)),
+            apply_opacity(style.shortcut_color(), cx.opacity),
+            None,
+        ));
+    }
+}
+
+fn add_label_primitives(label_primitives: LabelPrimitives, primitives: &mut PrimitiveGroup) {
+    if let Some(quad_primitive) = label_primitives.bg_quad {
+        primitives.add(quad_primitive);
+    }
+
+    label_primitives.bg_overlay.add_to(primitives);
+
+    if let Some(text_primitive) = label_primitives.text {
+        primitives.set_z_index(1);
+        primitives.add_text(text_primitive);
     }
 }
 
 struct SharedState {
     inner: LabelInner,
+    shortcut: Option<RcTextBuffer>,
     show_with_info: Option<(Rect, Align2)>,
 }
 
@@ -330,16 +480,48 @@ impl Tooltip {
         align: Align2,
         element_bounds: Rect,
         res: &mut ResourceCtx,
+    ) {
+        self.show_with_shortcut(text, None, align, element_bounds, res);
+    }
+
+    /// The same as [`Tooltip::show`], except that the given keyboard
+    /// shortcut is shown right-aligned next to the text (i.e. the
+    /// `shortcut` of a [`TooltipInfo`]).
+    pub fn show_with_shortcut<T: AsRef<str> + Into<String>>(
+        &mut self,
+        text: T,
+        shortcut: Option<KeyChord>,
+        align: Align2,
+        element_bounds: Rect,
+        res: &mut ResourceCtx,
     ) {
         let mut shared_state = RefCell::borrow_mut(&self.shared_state);
 
-        shared_state
-            .inner
-            .set_text(Some(text), &mut res.font_system, || {
+        shared_state.shortcut = shortcut.map(|shortcut| {
+            let style = res.style_system.get::<TooltipStyle>(self.el.class());
+
+            RcTextBuffer::new(
+                &shortcut.to_string(),
+                style
+                    .shortcut_text_properties
+                    .unwrap_or(style.text_properties),
+                None,
+                None,
+                false,
+                &mut res.font_system,
+            )
+        });
+
+        shared_state.inner.set_text(
+            Some(text),
+            &mut res.font_system,
+            &mut res.text_layout_cache,
+            || {
                 res.style_system
                     .get::<TooltipStyle>(self.el.class())
                     .text_properties
-            });
+            },
+        );
 
         shared_state.show_with_info = Some((element_bounds, align));
 
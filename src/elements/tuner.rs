@@ -0,0 +1,823 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::derive::*;
+use crate::prelude::*;
+use crate::vg::quad::{SolidQuadBuilder, SolidQuadPrimitive};
+use crate::vg::text::{Metrics, RcTextBuffer, TextPrimitive};
+
+/// How the pitch deviation of a [`Tuner`] is shown.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TunerMode {
+    /// A needle which moves across a scale from -50 to +50 cents.
+    #[default]
+    Needle,
+    /// A band of stripes which scrolls to the left when the pitch is flat
+    /// and to the right when it is sharp, and stands still when in tune.
+    Strobe,
+}
+
+/// The style of a [`Tuner`] element.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TunerStyle {
+    pub back_quad: QuadStyle,
+
+    /// The padding between the edges of the element and its contents.
+    ///
+    /// By default this is set to `padding_all_same(8.0)`.
+    pub padding: Padding,
+    /// The vertical spacing between the note name, the meter, and the cents
+    /// readout.
+    ///
+    /// By default this is set to `6.0`.
+    pub spacing: f32,
+
+    pub note_text_properties: TextProperties,
+    pub cents_text_properties: TextProperties,
+    pub cents_text_color: RGBA8,
+
+    /// The height of the needle scale or strobe band.
+    ///
+    /// By default this is set to `24.0`.
+    pub meter_height: f32,
+    pub meter_back_quad: QuadStyle,
+    pub tick_color: RGBA8,
+    /// The width of the needle.
+    ///
+    /// By default this is set to `3.0`.
+    pub needle_width: f32,
+    /// The color of the area of the needle scale which counts as in tune.
+    pub in_tune_zone_color: RGBA8,
+    /// The width of a single stripe of the strobe band.
+    ///
+    /// By default this is set to `8.0`.
+    pub strobe_stripe_width: f32,
+
+    /// The maximum deviation in cents (in either direction) which counts as
+    /// in tune.
+    ///
+    /// By default this is set to `3.0`.
+    pub in_tune_threshold: f32,
+    /// The maximum deviation in cents (in either direction) which counts as
+    /// close to being in tune.
+    ///
+    /// By default this is set to `15.0`.
+    pub close_threshold: f32,
+
+    /// The color of the note name, needle, and strobe when in tune.
+    pub in_tune_color: RGBA8,
+    /// The color of the note name, needle, and strobe when close to being in
+    /// tune.
+    pub close_color: RGBA8,
+    /// The color of the note name, needle, and strobe when out of tune.
+    pub out_of_tune_color: RGBA8,
+    /// The color of the note name when no pitch is detected.
+    pub inactive_color: RGBA8,
+}
+
+impl Default for TunerStyle {
+    fn default() -> Self {
+        Self {
+            back_quad: QuadStyle {
+                bg: Background::Solid(gray(20)),
+                border: BorderStyle {
+                    radius: radius(4.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            padding: padding_all_same(8.0),
+            spacing: 6.0,
+            note_text_properties: TextProperties {
+                metrics: Metrics {
+                    font_size: 28.0,
+                    line_height: 34.0,
+                },
+                ..Default::default()
+            },
+            cents_text_properties: TextProperties::default(),
+            cents_text_color: gray(170),
+            meter_height: 24.0,
+            meter_back_quad: QuadStyle {
+                bg: Background::Solid(gray(10)),
+                border: BorderStyle {
+                    radius: radius(2.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            tick_color: gray(90),
+            needle_width: 3.0,
+            in_tune_zone_color: rgba(80, 220, 100, 40),
+            strobe_stripe_width: 8.0,
+            in_tune_threshold: 3.0,
+            close_threshold: 15.0,
+            in_tune_color: rgb(80, 220, 100),
+            close_color: rgb(240, 200, 60),
+            out_of_tune_color: rgb(240, 80, 60),
+            inactive_color: gray(90),
+        }
+    }
+}
+
+impl ElementStyle for TunerStyle {
+    const ID: &'static str = "tuner";
+
+    fn default_dark_style() -> Self {
+        Self::default()
+    }
+
+    fn default_light_style() -> Self {
+        Self {
+            back_quad: QuadStyle {
+                bg: Background::Solid(gray(235)),
+                border: BorderStyle {
+                    radius: radius(4.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            cents_text_color: gray(80),
+            meter_back_quad: QuadStyle {
+                bg: Background::Solid(gray(250)),
+                border: BorderStyle {
+                    radius: radius(2.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            tick_color: gray(170),
+            in_tune_color: rgb(30, 160, 60),
+            close_color: rgb(200, 150, 20),
+            out_of_tune_color: rgb(210, 50, 40),
+            inactive_color: gray(170),
+            ..Default::default()
+        }
+    }
+}
+
+#[element_builder]
+#[element_builder_class]
+#[element_builder_rect]
+#[element_builder_hidden]
+pub struct TunerBuilder {
+    pub mode: TunerMode,
+    pub pitch: Option<f32>,
+    pub reference_pitch: f32,
+    pub needle_speed: f32,
+    pub strobe_speed: f32,
+}
+
+impl Default for TunerBuilder {
+    fn default() -> Self {
+        Self {
+            mode: TunerMode::default(),
+            pitch: None,
+            reference_pitch: 440.0,
+            needle_speed: 20.0,
+            strobe_speed: 4.0,
+            class: None,
+            z_index: None,
+            rect: Rect::default(),
+            manually_hidden: false,
+            scissor_rect: None,
+            key: None,
+        }
+    }
+}
+
+impl TunerBuilder {
+    /// How the pitch deviation is shown.
+    ///
+    /// By default this is set to `TunerMode::Needle`.
+    pub const fn mode(mut self, mode: TunerMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// The initial detected pitch in Hz, or `None` if no pitch is detected.
+    pub const fn pitch(mut self, pitch: Option<f32>) -> Self {
+        self.pitch = pitch;
+        self
+    }
+
+    /// The pitch of A4 in Hz.
+    ///
+    /// By default this is set to `440.0`.
+    pub const fn reference_pitch(mut self, reference_pitch: f32) -> Self {
+        self.reference_pitch = reference_pitch;
+        self
+    }
+
+    /// How quickly the needle follows the detected pitch. Higher values are
+    /// faster. Set this to `0.0` to move the needle instantly.
+    ///
+    /// By default this is set to `20.0`.
+    pub const fn needle_speed(mut self, speed: f32) -> Self {
+        self.needle_speed = speed;
+        self
+    }
+
+    /// How fast the strobe band scrolls in points per second per cent of
+    /// deviation.
+    ///
+    /// By default this is set to `4.0`.
+    pub const fn strobe_speed(mut self, speed: f32) -> Self {
+        self.strobe_speed = speed;
+        self
+    }
+
+    pub fn build<A: Clone + 'static>(self, window_cx: &mut WindowContext<'_, A>) -> Tuner {
+        let TunerBuilder {
+            mode,
+            pitch,
+            reference_pitch,
+            needle_speed,
+            strobe_speed,
+            class,
+            z_index,
+            rect,
+            manually_hidden,
+            scissor_rect,
+            key,
+        } = self;
+
+        let style = window_cx
+            .res
+            .style_system
+            .get::<TunerStyle>(window_cx.builder_class(class));
+
+        let reading = pitch.and_then(|pitch| Reading::new(pitch, reference_pitch));
+        let (note_text, cents_text) = readout_texts(reading);
+
+        let note_buffer = RcTextBuffer::new(
+            &note_text,
+            style.note_text_properties,
+            None,
+            None,
+            false,
+            &mut window_cx.res.font_system,
+        );
+        let cents_buffer = RcTextBuffer::new(
+            &cents_text,
+            style.cents_text_properties,
+            None,
+            None,
+            false,
+            &mut window_cx.res.font_system,
+        );
+
+        let shared_state = Rc::new(RefCell::new(SharedState {
+            mode,
+            pitch,
+            reference_pitch,
+        }));
+
+        let el = ElementBuilder::new(TunerElement {
+            shared_state: Rc::clone(&shared_state),
+            needle_speed,
+            strobe_speed,
+            reading,
+            needle_cents: reading.map(|r| r.cents).unwrap_or(0.0),
+            strobe_phase: 0.0,
+            note_text,
+            cents_text,
+            note_buffer,
+            cents_buffer,
+            animating: false,
+        })
+        .builder_values(z_index, scissor_rect, class, window_cx)
+        .key(key)
+        .rect(rect)
+        .hidden(manually_hidden)
+        .flags(ElementFlags::PAINTS)
+        .build(window_cx);
+
+        Tuner { el, shared_state }
+    }
+}
+
+const NOTE_NAMES: [&str; 12] = [
+    "C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B",
+];
+
+/// A detected pitch relative to the nearest note.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Reading {
+    /// The MIDI note number of the nearest note.
+    note: i32,
+    /// The deviation from the nearest note in cents (in the range
+    /// `[-50.0, 50.0]`).
+    cents: f32,
+}
+
+impl Reading {
+    fn new(pitch: f32, reference_pitch: f32) -> Option<Self> {
+        if !(pitch > 0.0) || !(reference_pitch > 0.0) || !pitch.is_finite() {
+            return None;
+        }
+
+        let midi = 69.0 + (12.0 * (pitch / reference_pitch).log2());
+        let note = midi.round();
+
+        Some(Self {
+            note: note as i32,
+            cents: (midi - note) * 100.0,
+        })
+    }
+}
+
+fn readout_texts(reading: Option<Reading>) -> (String, String) {
+    let Some(reading) = reading else {
+        return (String::from("-"), String::new());
+    };
+
+    let name = NOTE_NAMES[reading.note.rem_euclid(12) as usize];
+    let octave = reading.note.div_euclid(12) - 1;
+
+    let cents = reading.cents.round() as i32;
+    let cents_text = if cents == 0 {
+        String::from("0 ¢")
+    } else {
+        format!("{:+} ¢", cents)
+    };
+
+    (format!("{}{}", name, octave), cents_text)
+}
+
+struct Layout {
+    note_area: Rect,
+    meter: Rect,
+    cents_area: Rect,
+}
+
+impl Layout {
+    fn new(bounds: Rect, note_height: f32, cents_height: f32, style: &TunerStyle) -> Self {
+        let content = layout_inner_rect(style.padding, bounds)
+            .unwrap_or(Rect::new(bounds.center(), Size::zero()));
+
+        let note_area = Rect::new(content.origin, Size::new(content.width(), note_height));
+        let meter = Rect::new(
+            Point::new(content.min_x(), note_area.max_y() + style.spacing),
+            Size::new(content.width(), style.meter_height),
+        );
+        let cents_area = Rect::new(
+            Point::new(content.min_x(), meter.max_y() + style.spacing),
+            Size::new(content.width(), cents_height),
+        );
+
+        Self {
+            note_area,
+            meter,
+            cents_area,
+        }
+    }
+}
+
+/// A tuner display showing the detected pitch.
+struct TunerElement {
+    shared_state: Rc<RefCell<SharedState>>,
+    needle_speed: f32,
+    strobe_speed: f32,
+    reading: Option<Reading>,
+    needle_cents: f32,
+    strobe_phase: f32,
+    note_text: String,
+    cents_text: String,
+    note_buffer: RcTextBuffer,
+    cents_buffer: RcTextBuffer,
+    animating: bool,
+}
+
+impl TunerElement {
+    fn update_reading<A: Clone + 'static>(
+        &mut self,
+        shared_state: &SharedState,
+        cx: &mut ElementContext<'_, A>,
+    ) {
+        self.reading = shared_state
+            .pitch
+            .and_then(|pitch| Reading::new(pitch, shared_state.reference_pitch));
+
+        if self.needle_speed <= 0.0 {
+            self.needle_cents = self.reading.map(|r| r.cents).unwrap_or(0.0);
+        }
+
+        let (note_text, cents_text) = readout_texts(self.reading);
+        let style = cx.res.style_system.get::<TunerStyle>(cx.class());
+
+        if self.note_text != note_text {
+            self.note_buffer = RcTextBuffer::new(
+                &note_text,
+                style.note_text_properties,
+                None,
+                None,
+                false,
+                &mut cx.res.font_system,
+            );
+            self.note_text = note_text;
+        }
+
+        if self.cents_text != cents_text {
+            self.cents_buffer = RcTextBuffer::new(
+                &cents_text,
+                style.cents_text_properties,
+                None,
+                None,
+                false,
+                &mut cx.res.font_system,
+            );
+            self.cents_text = cents_text;
+        }
+
+        self.update_animating(shared_state, cx);
+    }
+
+    fn target_needle_cents(&self) -> f32 {
+        self.reading.map(|r| r.cents).unwrap_or(0.0)
+    }
+
+    fn update_animating<A: Clone + 'static>(
+        &mut self,
+        shared_state: &SharedState,
+        cx: &mut ElementContext<'_, A>,
+    ) {
+        let animating = match shared_state.mode {
+            TunerMode::Needle => self.needle_cents != self.target_needle_cents(),
+            TunerMode::Strobe => self.reading.is_some() && self.strobe_speed != 0.0,
+        };
+
+        if self.animating != animating {
+            self.animating = animating;
+            cx.set_animating(animating);
+        }
+    }
+
+    fn state_color(&self, style: &TunerStyle) -> RGBA8 {
+        match self.reading {
+            None => style.inactive_color,
+            Some(reading) => {
+                let deviation = reading.cents.abs();
+
+                if deviation <= style.in_tune_threshold {
+                    style.in_tune_color
+                } else if deviation <= style.close_threshold {
+                    style.close_color
+                } else {
+                    style.out_of_tune_color
+                }
+            }
+        }
+    }
+}
+
+impl<A: Clone + 'static> Element<A> for TunerElement {
+    fn on_event(
+        &mut self,
+        event: ElementEvent,
+        cx: &mut ElementContext<'_, A>,
+    ) -> EventCaptureStatus {
+        let shared_state_rc = Rc::clone(&self.shared_state);
+        let shared_state = RefCell::borrow(&shared_state_rc);
+
+        match event {
+            ElementEvent::Init | ElementEvent::CustomStateChanged => {
+                self.update_reading(&shared_state, cx);
+                cx.request_repaint();
+            }
+            ElementEvent::StyleChanged => {
+                // Force the text buffers to be rebuilt with the new properties.
+                self.note_text.clear();
+                self.cents_text.clear();
+                self.update_reading(&shared_state, cx);
+                cx.request_repaint();
+            }
+            ElementEvent::Animation { delta_seconds } => {
+                if !self.animating {
+                    return EventCaptureStatus::NotCaptured;
+                }
+
+                let delta_seconds = delta_seconds as f32;
+
+                match shared_state.mode {
+                    TunerMode::Needle => {
+                        let target = self.target_needle_cents();
+                        let t = 1.0 - (-delta_seconds * self.needle_speed).exp();
+                        self.needle_cents += (target - self.needle_cents) * t;
+
+                        if (target - self.needle_cents).abs() < 0.01 {
+                            self.needle_cents = target;
+                        }
+                    }
+                    TunerMode::Strobe => {
+                        if let Some(reading) = self.reading {
+                            let style = cx.res.style_system.get::<TunerStyle>(cx.class());
+                            let period = (style.strobe_stripe_width * 2.0).max(1.0);
+
+                            self.strobe_phase = (self.strobe_phase
+                                + (reading.cents * self.strobe_speed * delta_seconds))
+                                .rem_euclid(period);
+                        }
+                    }
+                }
+
+                self.update_animating(&shared_state, cx);
+                cx.request_repaint();
+            }
+            _ => {}
+        }
+
+        EventCaptureStatus::NotCaptured
+    }
+
+    fn render(&mut self, cx: RenderContext, primitives: &mut PrimitiveGroup) {
+        let shared_state = RefCell::borrow(&self.shared_state);
+        let style = cx.res.style_system.get::<TunerStyle>(cx.class);
+
+        let bounds = Rect::from_size(cx.bounds_size);
+
+        if !style.back_quad.is_transparent() {
+            primitives.add(
+                style
+                    .back_quad
+                    .create_primitive_with_opacity(bounds, cx.opacity),
+            );
+        }
+
+        let note_size = self.note_buffer.measure();
+        let cents_size = self.cents_buffer.measure();
+        let layout = Layout::new(bounds, note_size.height, cents_size.height, style);
+        let meter = layout.meter;
+
+        let state_color = self.state_color(style);
+
+        if !style.meter_back_quad.is_transparent() {
+            primitives.add(
+                style
+                    .meter_back_quad
+                    .create_primitive_with_opacity(meter, cx.opacity),
+            );
+        }
+
+        let mut quads: Vec<SolidQuadPrimitive> = Vec::new();
+        let mut add_quad = |rect: Rect, color: RGBA8| {
+            if let Some(rect) = rect.intersection(&meter) {
+                if color.a != 0 && !rect.is_empty() {
+                    quads.push(
+                        SolidQuadBuilder::new(rect.size)
+                            .bg_color(apply_opacity(color, cx.opacity))
+                            .position(rect.origin)
+                            .into(),
+                    );
+                }
+            }
+        };
+
+        let cents_to_x = |cents: f32| meter.center().x + (cents / 50.0) * (meter.width() * 0.5);
+
+        match shared_state.mode {
+            TunerMode::Needle => {
+                let zone_min_x = cents_to_x(-style.in_tune_threshold);
+                let zone_max_x = cents_to_x(style.in_tune_threshold);
+                add_quad(
+                    Rect::new(
+                        Point::new(zone_min_x, meter.min_y()),
+                        Size::new(zone_max_x - zone_min_x, meter.height()),
+                    ),
+                    style.in_tune_zone_color,
+                );
+
+                for i in -5..=5 {
+                    let x = cents_to_x(i as f32 * 10.0);
+                    let tick_height = if i == 0 || i.abs() == 5 {
+                        meter.height() * 0.5
+                    } else {
+                        meter.height() * 0.25
+                    };
+
+                    add_quad(
+                        Rect::new(
+                            Point::new(x - 0.5, meter.max_y() - tick_height),
+                            Size::new(1.0, tick_height),
+                        ),
+                        style.tick_color,
+                    );
+                }
+
+                if self.reading.is_some() {
+                    let x = cents_to_x(self.needle_cents.clamp(-50.0, 50.0));
+                    add_quad(
+                        Rect::new(
+                            Point::new(x - (style.needle_width * 0.5), meter.min_y()),
+                            Size::new(style.needle_width, meter.height()),
+                        ),
+                        state_color,
+                    );
+                }
+            }
+            TunerMode::Strobe => {
+                if self.reading.is_some() && style.strobe_stripe_width > 0.0 {
+                    let period = style.strobe_stripe_width * 2.0;
+                    let mut x = meter.min_x() - period + self.strobe_phase;
+
+                    while x < meter.max_x() {
+                        add_quad(
+                            Rect::new(
+                                Point::new(x, meter.min_y()),
+                                Size::new(style.strobe_stripe_width, meter.height()),
+                            ),
+                            state_color,
+                        );
+                        x += period;
+                    }
+                }
+            }
+        }
+
+        primitives.set_z_index(1);
+        primitives.add_solid_quad_batch(quads);
+
+        primitives.add_text_batch(vec![
+            TextPrimitive::new(
+                self.note_buffer.clone(),
+                Point::new(
+                    layout.note_area.center().x - (note_size.width * 0.5),
+                    layout.note_area.min_y(),
+                ),
+                apply_opacity(state_color, cx.opacity),
+                None,
+            ),
+            TextPrimitive::new(
+                self.cents_buffer.clone(),
+                Point::new(
+                    layout.cents_area.center().x - (cents_size.width * 0.5),
+                    layout.cents_area.min_y(),
+                ),
+                apply_opacity(style.cents_text_color, cx.opacity),
+                None,
+            ),
+        ]);
+    }
+}
+
+struct SharedState {
+    mode: TunerMode,
+    pitch: Option<f32>,
+    reference_pitch: f32,
+}
+
+/// A handle to a [`TunerElement`], a tuner display showing the nearest note
+/// to a detected pitch, its deviation in cents, and a needle or strobe
+/// meter which is colored by how far the pitch is from being in tune.
+///
+/// The pitch is detected by the application (i.e. on the audio thread) and
+/// is provided with [`Tuner::set_pitch`], which is cheap enough to call on
+/// every frame.
+#[element_handle]
+#[element_handle_class]
+#[element_handle_set_rect]
+pub struct Tuner {
+    shared_state: Rc<RefCell<SharedState>>,
+}
+
+impl Tuner {
+    pub fn builder() -> TunerBuilder {
+        TunerBuilder::default()
+    }
+
+    /// Set the detected pitch in Hz, or `None` if no pitch is detected.
+    ///
+    /// Returns `true` if the pitch has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn set_pitch(&mut self, pitch: Option<f32>) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if shared_state.pitch != pitch {
+            shared_state.pitch = pitch;
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn pitch(&self) -> Option<f32> {
+        RefCell::borrow(&self.shared_state).pitch
+    }
+
+    /// Set the pitch of A4 in Hz.
+    ///
+    /// Returns `true` if the reference pitch has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn set_reference_pitch(&mut self, reference_pitch: f32) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if shared_state.reference_pitch != reference_pitch {
+            shared_state.reference_pitch = reference_pitch;
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn reference_pitch(&self) -> f32 {
+        RefCell::borrow(&self.shared_state).reference_pitch
+    }
+
+    /// The MIDI note number of the note nearest to the detected pitch, and
+    /// the deviation from that note in cents.
+    ///
+    /// Returns `None` if no pitch is detected.
+    pub fn reading(&self) -> Option<(i32, f32)> {
+        let shared_state = RefCell::borrow(&self.shared_state);
+
+        shared_state
+            .pitch
+            .and_then(|pitch| Reading::new(pitch, shared_state.reference_pitch))
+            .map(|reading| (reading.note, reading.cents))
+    }
+
+    /// Set how the pitch deviation is shown.
+    ///
+    /// Returns `true` if the mode has changed.
+    ///
+    /// This will *NOT* trigger an element update unless the value has changed,
+    /// so this method is relatively cheap to call frequently.
+    pub fn set_mode(&mut self, mode: TunerMode) -> bool {
+        let mut shared_state = RefCell::borrow_mut(&self.shared_state);
+
+        if shared_state.mode != mode {
+            shared_state.mode = mode;
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn mode(&self) -> TunerMode {
+        RefCell::borrow(&self.shared_state).mode
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reading() {
+        let reading = Reading::new(440.0, 440.0).unwrap();
+        assert_eq!(reading.note, 69);
+        assert!(reading.cents.abs() < 0.01);
+
+        // One octave up.
+        assert_eq!(Reading::new(880.0, 440.0).unwrap().note, 81);
+
+        // Middle C.
+        let reading = Reading::new(261.63, 440.0).unwrap();
+        assert_eq!(reading.note, 60);
+        assert!(reading.cents.abs() < 0.1);
+
+        // A sharp A4 (about +19.6 cents).
+        let reading = Reading::new(445.0, 440.0).unwrap();
+        assert_eq!(reading.note, 69);
+        assert!((reading.cents - 19.56).abs() < 0.1);
+
+        // A flat A#4 rounds to the nearest note.
+        let reading = Reading::new(460.0, 440.0).unwrap();
+        assert_eq!(reading.note, 70);
+        assert!(reading.cents < 0.0 && reading.cents >= -50.0);
+
+        // A different reference pitch.
+        let reading = Reading::new(432.0, 432.0).unwrap();
+        assert_eq!(reading.note, 69);
+        assert!(reading.cents.abs() < 0.01);
+    }
+
+    #[test]
+    fn test_reading_invalid_pitch() {
+        assert!(Reading::new(0.0, 440.0).is_none());
+        assert!(Reading::new(-440.0, 440.0).is_none());
+        assert!(Reading::new(f32::NAN, 440.0).is_none());
+        assert!(Reading::new(f32::INFINITY, 440.0).is_none());
+        assert!(Reading::new(440.0, 0.0).is_none());
+    }
+
+    #[test]
+    fn test_readout_texts() {
+        assert_eq!(
+            readout_texts(Reading::new(440.0, 440.0)),
+            (String::from("A4"), String::from("0 ¢"))
+        );
+        assert_eq!(
+            readout_texts(Reading::new(445.0, 440.0)),
+            (String::from("A4"), String::from("+20 ¢"))
+        );
+        assert_eq!(readout_texts(None), (String::from("-"), String::new()));
+    }
+}
@@ -2,7 +2,7 @@ use smallvec::SmallVec;
 use smol_str::{SmolStr, ToSmolStr};
 use std::cell::{Ref, RefCell};
 use std::ops::Range;
-use std::rc::Rc;
+use std::rc::{Rc, Weak};
 
 use crate::derive::*;
 use crate::prelude::*;
@@ -224,6 +224,9 @@ pub struct ParamElementTooltipInfo {
     pub param_info: ParamInfo,
     pub rect: Rect,
     pub tooltip_align: Align2,
+    /// The default tooltip text for the parameter, formatted and translated
+    /// using [`ResourceCtx::locale`].
+    pub default_text: String,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -279,6 +282,7 @@ impl<A: Clone + 'static> VirtualSliderBuilder<A> {
             manually_hidden: false,
             disabled: false,
             scissor_rect: None,
+            key: None,
         }
     }
 
@@ -387,6 +391,7 @@ impl<A: Clone + 'static> VirtualSliderBuilder<A> {
             manually_hidden,
             disabled,
             scissor_rect,
+            key,
         } = self;
 
         let style = window_cx
@@ -442,6 +447,7 @@ impl<A: Clone + 'static> VirtualSliderBuilder<A> {
             global_render_cache_id,
         })
         .builder_values(z_index, scissor_rect, class, window_cx)
+        .key(key)
         .rect(rect)
         .hidden(manually_hidden)
         .flags(flags)
@@ -875,10 +881,14 @@ impl<A: Clone + 'static, R: VirtualSliderRenderer + 'static> Element<A>
 
                 if cx.rect().contains(position) {
                     if let Some(f) = self.on_tooltip_request.as_mut() {
+                        let param_info = inner.param_info();
+                        let default_text = default_tooltip_text(&param_info, &cx.res.locale);
+
                         cx.send_action((f)(ParamElementTooltipInfo {
-                            param_info: inner.param_info(),
+                            param_info,
                             rect: cx.rect(),
                             tooltip_align: self.tooltip_align,
+                            default_text,
                         }))
                         .unwrap();
                     }
@@ -1023,6 +1033,69 @@ struct SharedState<R: VirtualSliderRenderer + 'static> {
     queued_new_val: Option<ParamValue>,
 }
 
+/// A non-owning link to a [`VirtualSlider`], which can be used to update
+/// the value of the element from elsewhere (i.e. a [`ParamStore`]).
+///
+/// [`ParamStore`]: crate::param_store::ParamStore
+pub struct VirtualSliderLink<R: VirtualSliderRenderer + 'static> {
+    shared_state: Weak<RefCell<SharedState<R>>>,
+    el: ElementRef,
+}
+
+impl<R: VirtualSliderRenderer + 'static> VirtualSliderLink<R> {
+    /// The ID of the parameter of the linked element, or `None` if the
+    /// element has been dropped.
+    pub fn param_id(&self) -> Option<SmolStr> {
+        self.shared_state
+            .upgrade()
+            .map(|shared_state| RefCell::borrow(&shared_state).inner.param_id.clone())
+    }
+
+    /// Set the normalized value of the linked element.
+    ///
+    /// Returns `true` if the value has changed.
+    pub fn set_normal_value(&mut self, new_normal: f64) -> bool {
+        let Some(shared_state) = self.shared_state.upgrade() else {
+            return false;
+        };
+        let mut shared_state = RefCell::borrow_mut(&shared_state);
+
+        if shared_state.inner.normal_value() != new_normal {
+            shared_state.queued_new_val = Some(ParamValue::Normal(new_normal));
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Set the default normalized value of the linked element.
+    ///
+    /// Returns `true` if the value has changed.
+    pub fn set_default_normal(&mut self, new_normal: f64) -> bool {
+        let Some(shared_state) = self.shared_state.upgrade() else {
+            return false;
+        };
+        let mut shared_state = RefCell::borrow_mut(&shared_state);
+
+        if shared_state.inner.set_default_normal(new_normal) {
+            shared_state.needs_repaint = true;
+            self.el.notify_custom_state_change();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns `false` if both the handle and the element have been dropped.
+    ///
+    /// Links do not keep the element alive, so any number of links to the
+    /// same element can exist at once.
+    pub fn is_alive(&self) -> bool {
+        self.shared_state.strong_count() > 0
+    }
+}
+
 /// A handle to a [`VirtualSliderElement`].
 #[element_handle]
 #[element_handle_class]
@@ -1062,6 +1135,45 @@ impl<R: VirtualSliderRenderer> VirtualSlider<R> {
         }
     }
 
+    /// Smoothly animate the normalized value of the parameter to `new_normal`.
+    ///
+    /// Starting a new animation replaces the previous one. Use
+    /// `el.stop_animation(Some(AnimatedPropertyKind::NormalValue))` to stop it.
+    pub fn animate_normal_value(&mut self, new_normal: f64, transition: Transition) {
+        self.animate_normal_value_inner(new_normal, transition, None);
+    }
+
+    /// Smoothly animate the normalized value of the parameter to `new_normal`,
+    /// and then send the given action once the animation has finished.
+    pub fn animate_normal_value_then<A: Clone + 'static>(
+        &mut self,
+        new_normal: f64,
+        transition: Transition,
+        on_complete: A,
+    ) {
+        self.animate_normal_value_inner(new_normal, transition, Some(Box::new(on_complete)));
+    }
+
+    fn animate_normal_value_inner(
+        &mut self,
+        new_normal: f64,
+        transition: Transition,
+        on_complete: Option<Box<dyn std::any::Any>>,
+    ) {
+        let from = self.normal_value();
+        let to = new_normal.clamp(0.0, 1.0);
+        let mut link = self.link();
+
+        self.el.start_tween(
+            AnimatedPropertyKind::NormalValue,
+            transition,
+            Box::new(move |t| {
+                link.set_normal_value(from + ((to - from) * t as f64));
+            }),
+            on_complete,
+        );
+    }
+
     /// Set the stepped value of the parameter. This does nothing if the parameter
     /// is not stepped.
     ///
@@ -1170,6 +1282,17 @@ impl<R: VirtualSliderRenderer> VirtualSlider<R> {
         RefCell::borrow(&self.shared_state).inner.param_info()
     }
 
+    /// Create a non-owning link to this element, which can be used to update
+    /// its value from elsewhere (i.e. a [`ParamStore`]).
+    ///
+    /// [`ParamStore`]: crate::param_store::ParamStore
+    pub fn link(&self) -> VirtualSliderLink<R> {
+        VirtualSliderLink {
+            shared_state: Rc::downgrade(&self.shared_state),
+            el: self.el.to_ref(),
+        }
+    }
+
     pub fn normal_value(&self) -> f64 {
         RefCell::borrow(&self.shared_state).inner.normal_value()
     }
@@ -1304,3 +1427,12 @@ impl<R: VirtualSliderRenderer> VirtualSlider<R> {
         RefCell::borrow(&self.shared_state).disabled
     }
 }
+
+fn default_tooltip_text(param_info: &ParamInfo, locale: &Locale) -> String {
+    if let Some(stepped_value) = param_info.stepped_value {
+        locale.format_number(stepped_value.value as f64, 0)
+    } else {
+        let value = locale.format_number(param_info.normal_value * 100.0, 1);
+        locale.builtin_with_args(BuiltinString::ParamValueTooltip, &[("value", &value)])
+    }
+}
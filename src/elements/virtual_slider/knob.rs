@@ -7,6 +7,7 @@ use std::{any::Any, rc::Rc};
 use crate::{
     layout::SizeType,
     prelude::{ElementRenderCache, ElementStyle, RenderContext},
+    style::DisabledDerivation,
 };
 
 use super::{
@@ -61,6 +62,28 @@ impl ElementStyle for KnobStyle {
     fn default_light_style() -> Self {
         todo!()
     }
+
+    fn with_auto_disabled(&self, derivation: DisabledDerivation) -> Option<Self> {
+        let mut style = self.clone();
+
+        if let KnobBackStyle::Quad(s) = &mut style.back {
+            s.derive_auto_disabled(derivation);
+        }
+
+        match &mut style.notch {
+            KnobNotchStyle::Quad(s) => s.derive_auto_disabled(derivation),
+            #[cfg(feature = "mesh")]
+            KnobNotchStyle::Line(s) => s.derive_auto_disabled(derivation),
+            KnobNotchStyle::None => {}
+        }
+
+        #[cfg(feature = "tessellation")]
+        if let KnobMarkersStyle::Arc(s) = &mut style.markers {
+            s.derive_auto_disabled(derivation);
+        }
+
+        Some(style)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -178,19 +201,24 @@ impl VirtualSliderRenderer for KnobRenderer {
 
         match &style.back {
             KnobBackStyle::Quad(s) => {
-                primitives.add(s.create_primitive(info.state, back_bounds));
+                primitives.add(s.create_primitive_with_opacity(
+                    info.state,
+                    back_bounds,
+                    cx.opacity,
+                ));
             }
             KnobBackStyle::None => {}
         }
 
         match &style.markers {
             KnobMarkersStyle::Dots(s) => {
-                s.add_primitives(
+                s.add_primitives_with_opacity(
                     &info.markers,
                     back_bounds,
                     info.bipolar,
                     info.stepped_value.map(|s| s.num_steps),
                     style.angle_range,
+                    cx.opacity,
                     primitives,
                 );
             }
@@ -243,11 +271,12 @@ impl VirtualSliderRenderer for KnobRenderer {
                     .unwrap_or(info.normal_value) as f32;
 
                 primitives.set_z_index(1);
-                primitives.add(s.create_primitive(
+                primitives.add(s.create_primitive_with_opacity(
                     normal_val,
                     style.angle_range,
                     info.state,
                     back_bounds,
+                    cx.opacity,
                 ));
             }
             #[cfg(feature = "mesh")]
@@ -17,7 +17,7 @@ use rootvg::gradient::PackedGradient;
 use crate::{
     elements::virtual_slider::VirtualSliderState,
     layout::SizeType,
-    style::{Background, ClassID, DisabledBackground},
+    style::{Background, ClassID, DisabledBackground, DisabledDerivation},
     theme::DEFAULT_ACCENT_COLOR,
 };
 
@@ -87,6 +87,11 @@ impl KnobMarkersArcStyle {
 
         let fill_style = match &back_bg {
             Background::Solid(c) => FillStyle::Solid((*c).into()),
+            // Radial gradients are not supported on strokes.
+            Background::Radial(g) => FillStyle::Solid(g.outer_color().into()),
+            // Images are not supported on strokes.
+            #[cfg(feature = "image")]
+            Background::NineSlice(_) => FillStyle::Solid(rootvg::color::TRANSPARENT.into()),
             #[cfg(feature = "gradient")]
             Background::Gradient(g) => {
                 let full_radius = radius + half_width;
@@ -176,6 +181,11 @@ impl KnobMarkersArcStyle {
 
         let fill_style = match &bg {
             Background::Solid(c) => FillStyle::Solid((*c).into()),
+            // Radial gradients are not supported on strokes.
+            Background::Radial(g) => FillStyle::Solid(g.outer_color().into()),
+            // Images are not supported on strokes.
+            #[cfg(feature = "image")]
+            Background::NineSlice(_) => FillStyle::Solid(rootvg::color::TRANSPARENT.into()),
             #[cfg(feature = "gradient")]
             Background::Gradient(g) => {
                 let full_radius = radius + half_width;
@@ -202,6 +212,14 @@ impl KnobMarkersArcStyle {
             .stroke(&arc_path, stroke)
             .into_primitive()
     }
+
+    /// Derive every disabled-state property set to `Auto` with the given
+    /// derivation (see
+    /// [`StyleSystem::set_auto_disabled`](crate::style::StyleSystem::set_auto_disabled)).
+    pub fn derive_auto_disabled(&mut self, derivation: DisabledDerivation) {
+        self.fill_bg_disabled.derive_auto(derivation);
+        self.back_bg_disabled.derive_auto(derivation);
+    }
 }
 
 #[derive(Default)]
@@ -51,6 +51,27 @@ impl KnobMarkersDotStyle {
         num_quantized_steps: Option<u32>,
         angle_range: KnobAngleRange,
         primitives: &mut PrimitiveGroup,
+    ) {
+        self.add_primitives_with_opacity(
+            markers,
+            back_bounds,
+            bipolar,
+            num_quantized_steps,
+            angle_range,
+            1.0,
+            primitives,
+        );
+    }
+
+    pub fn add_primitives_with_opacity(
+        &self,
+        markers: &ParamMarkersConfig,
+        back_bounds: Rect,
+        bipolar: bool,
+        num_quantized_steps: Option<u32>,
+        angle_range: KnobAngleRange,
+        opacity: f32,
+        primitives: &mut PrimitiveGroup,
     ) {
         let primary_center_offset =
             ((back_bounds.width() + self.primary_size) * 0.5) + self.primary_padding;
@@ -97,7 +118,7 @@ impl KnobMarkersDotStyle {
                 Size::new(size, size),
             );
 
-            match quad_style.create_primitive(bounds) {
+            match quad_style.create_primitive_with_opacity(bounds, opacity) {
                 QuadPrimitive::Solid(s) => primitives.add_solid_quad(s),
                 #[cfg(feature = "gradient")]
                 QuadPrimitive::Gradient(s) => primitives.add_gradient_quad(s),
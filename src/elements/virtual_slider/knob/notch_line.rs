@@ -7,7 +7,11 @@ use rootvg::{
 #[cfg(feature = "gradient")]
 use rootvg::{gradient::Gradient, mesh::GradientMeshPrimitive};
 
-use crate::{elements::virtual_slider::VirtualSliderState, layout::SizeType, style::DisabledColor};
+use crate::{
+    elements::virtual_slider::VirtualSliderState,
+    layout::SizeType,
+    style::{DisabledColor, DisabledDerivation},
+};
 
 #[cfg(feature = "gradient")]
 use crate::style::DisabledGradient;
@@ -59,6 +63,17 @@ impl KnobNotchStyleLine {
     pub fn create_primitives(&self, back_size: f32) -> KnobNotchLinePrimitives {
         KnobNotchLinePrimitives::new(self, back_size)
     }
+
+    /// Derive every disabled-state property set to `Auto` with the given
+    /// derivation (see
+    /// [`StyleSystem::set_auto_disabled`](crate::style::StyleSystem::set_auto_disabled)).
+    pub fn derive_auto_disabled(&mut self, derivation: DisabledDerivation) {
+        match &mut self.bg {
+            KnobNotchStyleLineBg::Solid { disabled, .. } => disabled.derive_auto(derivation),
+            #[cfg(feature = "gradient")]
+            KnobNotchStyleLineBg::Gradient { disabled, .. } => disabled.derive_auto(derivation),
+        }
+    }
 }
 
 impl Default for KnobNotchStyleLine {
@@ -9,7 +9,10 @@ use rootvg::{
 use crate::{
     elements::virtual_slider::VirtualSliderState,
     layout::SizeType,
-    style::{Background, BorderStyle, DisabledBackground, DisabledColor, QuadStyle},
+    style::{
+        Background, BorderEdges, BorderStyle, DisabledBackground, DisabledColor,
+        DisabledDerivation, QuadStyle,
+    },
 };
 
 use super::KnobAngleRange;
@@ -46,6 +49,7 @@ impl KnobBackStyleQuad {
                     color: self.border_color,
                     width: self.border_width,
                     radius: Radius::CIRCLE,
+                    edges: BorderEdges::NONE,
                 },
                 flags: self.quad_flags,
             },
@@ -55,6 +59,7 @@ impl KnobBackStyleQuad {
                     color: self.border_color_hover.unwrap_or(self.border_color),
                     width: self.border_width_hover.unwrap_or(self.border_width),
                     radius: Radius::CIRCLE,
+                    edges: BorderEdges::NONE,
                 },
                 flags: self.quad_flags,
             },
@@ -68,6 +73,7 @@ impl KnobBackStyleQuad {
                         .unwrap_or(self.border_color_hover.unwrap_or(self.border_color)),
                     width: self.border_width_hover.unwrap_or(self.border_width),
                     radius: Radius::CIRCLE,
+                    edges: BorderEdges::NONE,
                 },
                 flags: self.quad_flags,
             },
@@ -77,6 +83,7 @@ impl KnobBackStyleQuad {
                     color: self.border_color_disabled.get(self.border_color),
                     width: self.border_width,
                     radius: Radius::CIRCLE,
+                    edges: BorderEdges::NONE,
                 },
                 flags: self.quad_flags,
             },
@@ -84,8 +91,17 @@ impl KnobBackStyleQuad {
     }
 
     pub fn create_primitive(&self, state: VirtualSliderState, bounds: Rect) -> QuadPrimitive {
+        self.create_primitive_with_opacity(state, bounds, 1.0)
+    }
+
+    pub fn create_primitive_with_opacity(
+        &self,
+        state: VirtualSliderState,
+        bounds: Rect,
+        opacity: f32,
+    ) -> QuadPrimitive {
         let quad_style = self.quad_style(state);
-        quad_style.create_primitive(bounds)
+        quad_style.create_primitive_with_opacity(bounds, opacity)
     }
 
     pub fn back_bounds(&self, element_size: Size) -> Rect {
@@ -106,6 +122,14 @@ impl KnobBackStyleQuad {
             }
         }
     }
+
+    /// Derive every disabled-state property set to `Auto` with the given
+    /// derivation (see
+    /// [`StyleSystem::set_auto_disabled`](crate::style::StyleSystem::set_auto_disabled)).
+    pub fn derive_auto_disabled(&mut self, derivation: DisabledDerivation) {
+        self.bg_disabled.derive_auto(derivation);
+        self.border_color_disabled.derive_auto(derivation);
+    }
 }
 
 impl Default for KnobBackStyleQuad {
@@ -164,6 +188,7 @@ impl KnobNotchStyleQuad {
                     color: self.border_color,
                     width: self.border_width,
                     radius: Radius::CIRCLE,
+                    edges: BorderEdges::NONE,
                 },
                 flags: self.quad_flags,
             },
@@ -173,6 +198,7 @@ impl KnobNotchStyleQuad {
                     color: self.border_color_hover.unwrap_or(self.border_color),
                     width: self.border_width_hover.unwrap_or(self.border_width),
                     radius: Radius::CIRCLE,
+                    edges: BorderEdges::NONE,
                 },
                 flags: self.quad_flags,
             },
@@ -186,6 +212,7 @@ impl KnobNotchStyleQuad {
                         .unwrap_or(self.border_color_hover.unwrap_or(self.border_color)),
                     width: self.border_width_hover.unwrap_or(self.border_width),
                     radius: Radius::CIRCLE,
+                    edges: BorderEdges::NONE,
                 },
                 flags: self.quad_flags,
             },
@@ -195,6 +222,7 @@ impl KnobNotchStyleQuad {
                     color: self.border_color_disabled.get(self.border_color),
                     width: self.border_width,
                     radius: Radius::CIRCLE,
+                    edges: BorderEdges::NONE,
                 },
                 flags: self.quad_flags,
             },
@@ -207,6 +235,17 @@ impl KnobNotchStyleQuad {
         angle_range: KnobAngleRange,
         state: VirtualSliderState,
         back_bounds: Rect,
+    ) -> QuadPrimitive {
+        self.create_primitive_with_opacity(normal_val, angle_range, state, back_bounds, 1.0)
+    }
+
+    pub fn create_primitive_with_opacity(
+        &self,
+        normal_val: f32,
+        angle_range: KnobAngleRange,
+        state: VirtualSliderState,
+        back_bounds: Rect,
+        opacity: f32,
     ) -> QuadPrimitive {
         let quad_style = self.quad_style(state);
 
@@ -232,7 +271,15 @@ impl KnobNotchStyleQuad {
             Size::new(notch_size, notch_size),
         );
 
-        quad_style.create_primitive(notch_bounds)
+        quad_style.create_primitive_with_opacity(notch_bounds, opacity)
+    }
+
+    /// Derive every disabled-state property set to `Auto` with the given
+    /// derivation (see
+    /// [`StyleSystem::set_auto_disabled`](crate::style::StyleSystem::set_auto_disabled)).
+    pub fn derive_auto_disabled(&mut self, derivation: DisabledDerivation) {
+        self.bg_disabled.derive_auto(derivation);
+        self.border_color_disabled.derive_auto(derivation);
     }
 }
 
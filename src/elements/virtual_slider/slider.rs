@@ -11,7 +11,10 @@ use crate::{
     element_system::element::RenderContext,
     layout::{Padding, SizeType},
     prelude::ElementStyle,
-    style::{Background, BorderStyle, DisabledBackground, DisabledColor, QuadStyle},
+    style::{
+        Background, BorderEdges, BorderStyle, DisabledBackground, DisabledColor,
+        DisabledDerivation, QuadStyle,
+    },
 };
 
 use super::{
@@ -39,6 +42,14 @@ impl ElementStyle for SliderStyle {
     fn default_light_style() -> Self {
         todo!()
     }
+
+    fn with_auto_disabled(&self, derivation: DisabledDerivation) -> Option<Self> {
+        let mut style = self.clone();
+        match &mut style {
+            Self::Modern(s) => s.derive_auto_disabled(derivation),
+        }
+        Some(style)
+    }
 }
 
 struct SliderStyleModernState {
@@ -126,6 +137,7 @@ impl SliderStyleModern {
                         radius: self
                             .back_border_radius_hover
                             .unwrap_or(self.back_border_radius),
+                        edges: BorderEdges::NONE,
                     },
                     flags: self.quad_flags,
                 },
@@ -144,6 +156,7 @@ impl SliderStyleModern {
                         radius: self
                             .handle_border_radius_hover
                             .unwrap_or(self.handle_border_radius),
+                        edges: BorderEdges::NONE,
                     },
                     flags: self.quad_flags,
                 },
@@ -179,6 +192,7 @@ impl SliderStyleModern {
                         radius: self
                             .back_border_radius_hover
                             .unwrap_or(self.back_border_radius),
+                        edges: BorderEdges::NONE,
                     },
                     flags: self.quad_flags,
                 },
@@ -194,6 +208,7 @@ impl SliderStyleModern {
                         radius: self
                             .handle_border_radius_hover
                             .unwrap_or(self.handle_border_radius),
+                        edges: BorderEdges::NONE,
                     },
                     flags: self.quad_flags,
                 },
@@ -221,6 +236,7 @@ impl SliderStyleModern {
                         color: self.back_border_color,
                         width: self.back_border_width,
                         radius: self.back_border_radius,
+                        edges: BorderEdges::NONE,
                     },
                     flags: self.quad_flags,
                 },
@@ -230,6 +246,7 @@ impl SliderStyleModern {
                         color: self.handle_border_color,
                         width: self.handle_border_width,
                         radius: self.handle_border_radius,
+                        edges: BorderEdges::NONE,
                     },
                     flags: self.quad_flags,
                 },
@@ -253,6 +270,7 @@ impl SliderStyleModern {
                         color: self.back_border_color_disabled.get(self.back_border_color),
                         width: self.back_border_width,
                         radius: self.back_border_radius,
+                        edges: BorderEdges::NONE,
                     },
                     flags: self.quad_flags,
                 },
@@ -264,6 +282,7 @@ impl SliderStyleModern {
                             .get(self.handle_border_color),
                         width: self.handle_border_width,
                         radius: self.handle_border_radius,
+                        edges: BorderEdges::NONE,
                     },
                     flags: self.quad_flags,
                 },
@@ -282,6 +301,17 @@ impl SliderStyleModern {
             },
         }
     }
+
+    /// Derive every disabled-state property set to `Auto` with the given
+    /// derivation (see
+    /// [`StyleSystem::set_auto_disabled`](crate::style::StyleSystem::set_auto_disabled)).
+    pub fn derive_auto_disabled(&mut self, derivation: DisabledDerivation) {
+        self.back_bg_disabled.derive_auto(derivation);
+        self.back_border_color_disabled.derive_auto(derivation);
+        self.handle_bg_disabled.derive_auto(derivation);
+        self.handle_border_color_disabled.derive_auto(derivation);
+        self.fill_bg_disabled.derive_auto(derivation);
+    }
 }
 
 impl Default for SliderStyleModern {
@@ -390,45 +420,59 @@ impl VirtualSliderRenderer for SliderRenderer {
                     );
 
                     if r.back {
-                        primitives.add(
-                            style_state
-                                .back_quad
-                                .create_primitive(Rect::from_size(cx.bounds_size)),
-                        );
+                        primitives.add(style_state.back_quad.create_primitive_with_opacity(
+                            Rect::from_size(cx.bounds_size),
+                            cx.opacity,
+                        ));
                     }
 
                     if let Some(fill_rect) = r.fill {
                         let fill_rect = to_horizontal(fill_rect);
 
                         primitives.set_z_index(1);
-                        primitives.add(style_state.fill_quad.create_primitive(fill_rect));
+                        primitives.add(
+                            style_state
+                                .fill_quad
+                                .create_primitive_with_opacity(fill_rect, cx.opacity),
+                        );
                     }
 
                     if let Some(handle_rect) = r.handle {
                         let handle_rect = to_horizontal(handle_rect);
 
                         primitives.set_z_index(2);
-                        primitives.add(style_state.handle_quad.create_primitive(handle_rect));
+                        primitives.add(
+                            style_state
+                                .handle_quad
+                                .create_primitive_with_opacity(handle_rect, cx.opacity),
+                        );
                     }
                 } else {
                     let r = ModerStyleRects::new(cx.bounds_size, style, &style_state, info);
 
                     if r.back {
-                        primitives.add(
-                            style_state
-                                .back_quad
-                                .create_primitive(Rect::from_size(cx.bounds_size)),
-                        );
+                        primitives.add(style_state.back_quad.create_primitive_with_opacity(
+                            Rect::from_size(cx.bounds_size),
+                            cx.opacity,
+                        ));
                     }
 
                     if let Some(fill_rect) = r.fill {
                         primitives.set_z_index(1);
-                        primitives.add(style_state.fill_quad.create_primitive(fill_rect));
+                        primitives.add(
+                            style_state
+                                .fill_quad
+                                .create_primitive_with_opacity(fill_rect, cx.opacity),
+                        );
                     }
 
                     if let Some(handle_rect) = r.handle {
                         primitives.set_z_index(2);
-                        primitives.add(style_state.handle_quad.create_primitive(handle_rect));
+                        primitives.add(
+                            style_state
+                                .handle_quad
+                                .create_primitive_with_opacity(handle_rect, cx.opacity),
+                        );
                     }
                 }
             }
@@ -1,7 +1,15 @@
+use std::any::Any;
+use std::fmt;
+use std::rc::Rc;
+
 pub use keyboard_types::{Code, CompositionEvent, KeyState, Location, Modifiers};
 use rootvg::math::Vector;
 
-use crate::{math::Point, window::OpenWindowError};
+use crate::{
+    element_system::{ElementID, ElementTransform},
+    math::{Point, Rect},
+    window::OpenWindowError,
+};
 
 #[derive(Debug)]
 pub enum AppWindowEvent {
@@ -12,7 +20,36 @@ pub enum AppWindowEvent {
     WindowHidden,
     WindowFocused,
     WindowUnfocused,
+    /// The window moved to a different monitor, or the properties of its
+    /// monitor (i.e. the refresh rate) changed. See `WindowContext::monitor`.
+    MonitorChanged,
     OpenWindowFailed(OpenWindowError),
+    /// A message should be announced to assistive technology (see
+    /// `WindowContext::announce`).
+    ///
+    /// Forward this to the accessibility integration of the application
+    /// (i.e. a screen reader bridge) to have it spoken.
+    Announcement(Announcement),
+}
+
+/// How urgently an [`Announcement`] should be spoken, equivalent to the
+/// `aria-live` property of a live region.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AnnouncePriority {
+    /// Wait until the user is idle before announcing the message.
+    #[default]
+    Polite,
+    /// Interrupt the user to announce the message immediately (i.e. for
+    /// errors).
+    Assertive,
+}
+
+/// A transient message for assistive technology (i.e. "Preset loaded" or
+/// "Recording started") which is independent of anything shown on screen.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Announcement {
+    pub message: String,
+    pub priority: AnnouncePriority,
 }
 
 pub(crate) enum CanvasEvent {
@@ -32,7 +69,15 @@ pub(crate) enum CanvasEvent {
 #[derive(Debug, Clone, PartialEq)]
 pub enum ElementEvent {
     CustomStateChanged,
-    Animation { delta_seconds: f64 },
+    Animation {
+        delta_seconds: f64,
+    },
+    /// A single frame tick requested with `ElementContext::request_animation`
+    /// or `ElementHandle::request_animation`.
+    Tick {
+        /// The time in seconds since the previous frame tick.
+        delta_time: f64,
+    },
     Hidden,
     Shown,
     StyleChanged,
@@ -45,6 +90,20 @@ pub enum ElementEvent {
     Focus(bool),
     ClickedOff,
     Init,
+    Drag(DragEvent),
+    /// The element has been activated with keyboard or controller
+    /// navigation while it has focus (i.e. by pressing Enter or Space, or
+    /// with `NavigationInput::Activate`).
+    ///
+    /// Return `EventCaptureStatus::Captured` if the element handled it.
+    Activate,
+    /// The rectangle of the element this element is anchored to (see
+    /// `ElementHandle::set_anchor`) has changed, or the anchor has just been
+    /// set.
+    ///
+    /// The rectangle is in the same coordinates as `ElementHandle::set_rect`
+    /// (relative to the target's scissoring rectangle).
+    AnchorRectChanged(Rect),
 }
 
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
@@ -61,6 +120,9 @@ pub struct KeyboardEvent {
     pub modifiers: Modifiers,
     /// True if the key is currently auto-repeated.
     pub repeat: bool,
+    /// The platform-specific scan code of the physical key, if the backend
+    /// provides one.
+    pub scan_code: Option<u32>,
     /// Events with this flag should be ignored in a text editor
     /// and instead composition events should be used.
     pub is_composing: bool,
@@ -197,6 +259,17 @@ pub enum PointerEvent {
     HoverTimeout {
         position: Point,
     },
+    /// A pointer button has been held down for the long-press duration
+    /// without the pointer moving.
+    ///
+    /// This is mainly useful for touch input, where a right click is not
+    /// available to open context menus.
+    LongPress {
+        position: Point,
+        button: PointerButton,
+        pointer_type: PointerType,
+        modifiers: Modifiers,
+    },
     ScrollWheelTimeout,
     PointerLeft,
 }
@@ -209,10 +282,148 @@ impl PointerEvent {
             Self::ButtonJustReleased { position, .. } => *position,
             Self::ScrollWheel { position, .. } => *position,
             Self::HoverTimeout { position } => *position,
+            Self::LongPress { position, .. } => *position,
             Self::ScrollWheelTimeout => Point::zero(),
             Self::PointerLeft => Point::zero(),
         }
     }
+
+    /// Map the position (and the movement delta, if any) of this event from
+    /// window coordinates into the untransformed bounding rectangle of an
+    /// element with the given transform.
+    pub(crate) fn inverse_transform(&mut self, transform: &ElementTransform, bounds: Rect) {
+        let Some(inverse) = transform.to_transform(bounds).inverse() else {
+            return;
+        };
+
+        match self {
+            Self::Moved {
+                position, delta, ..
+            } => {
+                *position = inverse.transform_point(*position);
+                if let Some(delta) = delta {
+                    *delta = inverse.transform_vector(*delta);
+                }
+            }
+            Self::ButtonJustPressed { position, .. }
+            | Self::ButtonJustReleased { position, .. }
+            | Self::ScrollWheel { position, .. }
+            | Self::HoverTimeout { position }
+            | Self::LongPress { position, .. } => {
+                *position = inverse.transform_point(*position);
+            }
+            Self::ScrollWheelTimeout | Self::PointerLeft => {}
+        }
+    }
+}
+
+/// A directional navigation input, i.e. from a gamepad or a hardware
+/// controller.
+///
+/// Feed these to [`WindowContext::navigate`](crate::WindowContext::navigate)
+/// to move focus spatially between focusable elements.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NavigationInput {
+    Up,
+    Down,
+    Left,
+    Right,
+    /// Send `ElementEvent::Activate` to the focused element.
+    Activate,
+    /// Move focus to the next element in the keyboard navigation order.
+    Next,
+    /// Move focus to the previous element in the keyboard navigation order.
+    Previous,
+}
+
+/// The data being dragged in a drag-and-drop operation.
+///
+/// This is a cheaply-clonable reference counted pointer to data of any type.
+#[derive(Clone)]
+pub struct DragPayload(Rc<dyn Any>);
+
+impl DragPayload {
+    pub fn new<T: Any>(data: T) -> Self {
+        Self(Rc::new(data))
+    }
+
+    /// Get the data if it is of type `T`.
+    pub fn get<T: Any>(&self) -> Option<&T> {
+        self.0.downcast_ref()
+    }
+
+    /// Returns `true` if the data is of type `T`.
+    pub fn is<T: Any>(&self) -> bool {
+        self.0.is::<T>()
+    }
+}
+
+impl fmt::Debug for DragPayload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("DragPayload").finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for DragPayload {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// An event in a drag-and-drop operation.
+///
+/// The `Entered`, `Moved`, `Left`, and `Dropped` events are only sent to
+/// elements with the `ElementFlags::ACCEPTS_DROPS` flag. The `Ended` event
+/// is sent to the element that started the drag.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DragEvent {
+    /// The pointer dragging a payload has entered the bounds of this element.
+    ///
+    /// Return `EventCaptureStatus::Captured` to accept the payload.
+    Entered {
+        position: Point,
+        payload: DragPayload,
+    },
+    /// The pointer dragging a payload has moved within the bounds of this
+    /// element.
+    ///
+    /// Return `EventCaptureStatus::Captured` to accept the payload.
+    Moved {
+        position: Point,
+        payload: DragPayload,
+    },
+    /// The pointer dragging a payload has left the bounds of this element, or
+    /// the drag was cancelled.
+    Left,
+    /// The payload was dropped onto this element.
+    ///
+    /// Return `EventCaptureStatus::Captured` if the drop was handled.
+    Dropped {
+        position: Point,
+        payload: DragPayload,
+    },
+    /// The drag that this element started has ended.
+    Ended {
+        /// Whether or not the payload was dropped onto an element which
+        /// handled it.
+        dropped: bool,
+    },
+}
+
+/// An input event which is about to be routed to the elements in a window.
+///
+/// See `WindowContext::set_event_filter`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InputEvent<'a> {
+    Pointer {
+        event: &'a PointerEvent,
+        /// The topmost element under the pointer which listens to pointer
+        /// events (if any), i.e. the element which would receive the event
+        /// first if it is not consumed.
+        hit_element: Option<ElementID>,
+    },
+    Keyboard(&'a KeyboardEvent),
+    TextComposition(&'a CompositionEvent),
 }
 
 /// Whether or not the event was captured by this element.
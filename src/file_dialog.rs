@@ -0,0 +1,280 @@
+//! Native file open/save dialogs whose results are delivered as actions.
+//!
+//! ```ignore
+//! cx.show_file_dialog(
+//!     FileDialogKind::OpenFile,
+//!     FileDialogConfig::new()
+//!         .title("Load Sample")
+//!         .filter("Audio", &["wav", "flac", "ogg"]),
+//!     MyAction::SampleDialogClosed,
+//! );
+//! ```
+//!
+//! The dialog is shown without blocking the GUI thread, so the view keeps
+//! updating while it is open. Native dialogs require the `file-dialog`
+//! feature and are not available on the web. The `FileBrowser` element can
+//! be shown instead where native dialogs are not available or not desired
+//! (i.e. in a plugin host which doesn't allow them).
+
+use std::path::{Path, PathBuf};
+#[cfg(all(feature = "file-dialog", not(target_family = "wasm")))]
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+#[cfg(all(feature = "file-dialog", not(target_family = "wasm")))]
+use crate::action_queue::ActionSender;
+
+/// The kind of file dialog to show.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FileDialogKind {
+    /// Select a single existing file.
+    #[default]
+    OpenFile,
+    /// Select one or more existing files.
+    OpenFiles,
+    /// Select a single folder.
+    PickFolder,
+    /// Choose the path of a file to save to.
+    SaveFile,
+}
+
+/// The result of a file dialog.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileDialogResult {
+    /// The user selected one or more paths. This is never empty.
+    Selected(Vec<PathBuf>),
+    /// The user closed the dialog without selecting anything.
+    Cancelled,
+}
+
+impl FileDialogResult {
+    /// The first selected path, or `None` if the dialog was cancelled.
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            Self::Selected(paths) => paths.first().map(|p| p.as_path()),
+            Self::Cancelled => None,
+        }
+    }
+
+    /// All selected paths, or an empty `Vec` if the dialog was cancelled.
+    pub fn into_paths(self) -> Vec<PathBuf> {
+        match self {
+            Self::Selected(paths) => paths,
+            Self::Cancelled => Vec::new(),
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        *self == Self::Cancelled
+    }
+}
+
+/// A named group of file extensions shown in a file dialog (i.e.
+/// "Audio (*.wav, *.flac)").
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileFilter {
+    pub name: String,
+    /// The extensions without the leading dot (i.e. `"wav"`).
+    pub extensions: Vec<String>,
+}
+
+impl FileFilter {
+    pub fn new(name: impl Into<String>, extensions: &[&str]) -> Self {
+        Self {
+            name: name.into(),
+            extensions: extensions.iter().map(|e| e.to_string()).collect(),
+        }
+    }
+
+    /// Returns `true` if the path has one of the extensions of this filter
+    /// (ignoring case).
+    pub fn matches(&self, path: &Path) -> bool {
+        let Some(extension) = path.extension().and_then(|e| e.to_str()) else {
+            return false;
+        };
+
+        self.extensions
+            .iter()
+            .any(|e| e == "*" || e.eq_ignore_ascii_case(extension))
+    }
+}
+
+/// The configuration of a file dialog.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct FileDialogConfig {
+    /// The title of the dialog window.
+    ///
+    /// By default this is set to `None` (the title chosen by the OS).
+    pub title: Option<String>,
+    /// The directory the dialog starts in.
+    ///
+    /// By default this is set to `None` (the directory chosen by the OS).
+    pub directory: Option<PathBuf>,
+    /// The file name filled in when the dialog opens. This is only used for
+    /// [`FileDialogKind::SaveFile`].
+    ///
+    /// By default this is set to `None`.
+    pub file_name: Option<String>,
+    /// The file filters the user can choose from. If this is empty, then
+    /// all files are shown.
+    ///
+    /// By default this is empty.
+    pub filters: Vec<FileFilter>,
+}
+
+impl FileDialogConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    pub fn directory(mut self, directory: impl Into<PathBuf>) -> Self {
+        self.directory = Some(directory.into());
+        self
+    }
+
+    pub fn file_name(mut self, file_name: impl Into<String>) -> Self {
+        self.file_name = Some(file_name.into());
+        self
+    }
+
+    /// Add a file filter (see [`FileFilter::new`]).
+    pub fn filter(mut self, name: impl Into<String>, extensions: &[&str]) -> Self {
+        self.filters.push(FileFilter::new(name, extensions));
+        self
+    }
+
+    /// Returns `true` if the path matches any of the filters, or if there
+    /// are no filters.
+    pub fn matches(&self, path: &Path) -> bool {
+        self.filters.is_empty() || self.filters.iter().any(|f| f.matches(path))
+    }
+}
+
+/// Show a native file dialog without blocking the calling thread.
+///
+/// Once the dialog is closed, the action returned by `on_result` is sent
+/// with the given sender. Prefer `AppContext::show_file_dialog`, which also
+/// keeps the tick timer running so that the action is processed right away.
+///
+/// This requires the `file-dialog` feature.
+#[cfg(all(feature = "file-dialog", not(target_family = "wasm")))]
+pub fn show_file_dialog<A, F>(
+    kind: FileDialogKind,
+    config: FileDialogConfig,
+    action_sender: ActionSender<A>,
+    on_result: F,
+) where
+    A: Clone + Send + 'static,
+    F: FnOnce(FileDialogResult) -> A + Send + 'static,
+{
+    show_file_dialog_inner(kind, config, action_sender, on_result, None);
+}
+
+#[cfg(all(feature = "file-dialog", not(target_family = "wasm")))]
+pub(crate) fn show_file_dialog_inner<A, F>(
+    kind: FileDialogKind,
+    config: FileDialogConfig,
+    mut action_sender: ActionSender<A>,
+    on_result: F,
+    pending_counter: Option<Arc<AtomicUsize>>,
+) where
+    A: Clone + Send + 'static,
+    F: FnOnce(FileDialogResult) -> A + Send + 'static,
+{
+    let mut dialog = rfd::AsyncFileDialog::new();
+    if let Some(title) = &config.title {
+        dialog = dialog.set_title(title);
+    }
+    if let Some(directory) = &config.directory {
+        dialog = dialog.set_directory(directory);
+    }
+    if let Some(file_name) = &config.file_name {
+        dialog = dialog.set_file_name(file_name);
+    }
+    for filter in config.filters.iter() {
+        dialog = dialog.add_filter(&filter.name, &filter.extensions);
+    }
+
+    // The dialog is created on the calling (GUI) thread, which is required
+    // on some platforms, and then awaited on a separate thread.
+    let future: std::pin::Pin<Box<dyn std::future::Future<Output = Vec<PathBuf>> + Send>> =
+        match kind {
+            FileDialogKind::OpenFile => {
+                let f = dialog.pick_file();
+                Box::pin(async move {
+                    f.await
+                        .map(|h| h.path().to_path_buf())
+                        .into_iter()
+                        .collect()
+                })
+            }
+            FileDialogKind::OpenFiles => {
+                let f = dialog.pick_files();
+                Box::pin(async move {
+                    f.await
+                        .unwrap_or_default()
+                        .iter()
+                        .map(|h| h.path().to_path_buf())
+                        .collect()
+                })
+            }
+            FileDialogKind::PickFolder => {
+                let f = dialog.pick_folder();
+                Box::pin(async move {
+                    f.await
+                        .map(|h| h.path().to_path_buf())
+                        .into_iter()
+                        .collect()
+                })
+            }
+            FileDialogKind::SaveFile => {
+                let f = dialog.save_file();
+                Box::pin(async move {
+                    f.await
+                        .map(|h| h.path().to_path_buf())
+                        .into_iter()
+                        .collect()
+                })
+            }
+        };
+
+    if let Some(counter) = &pending_counter {
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+    let thread_pending_counter = pending_counter.clone();
+
+    let spawn_result = std::thread::Builder::new()
+        .name("yarrow file dialog".into())
+        .spawn(move || {
+            let paths = pollster::block_on(future);
+
+            let result = if paths.is_empty() {
+                FileDialogResult::Cancelled
+            } else {
+                FileDialogResult::Selected(paths)
+            };
+
+            if action_sender.send((on_result)(result)).is_err() {
+                log::debug!("file dialog closed after the application stopped");
+            }
+
+            if let Some(counter) = &thread_pending_counter {
+                counter.fetch_sub(1, Ordering::Relaxed);
+            }
+        });
+
+    if let Err(e) = spawn_result {
+        log::error!("Failed to spawn file dialog thread: {}", e);
+
+        if let Some(counter) = &pending_counter {
+            counter.fetch_sub(1, Ordering::Relaxed);
+        }
+    }
+}
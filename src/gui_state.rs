@@ -0,0 +1,151 @@
+//! Persistable GUI state (window size, splitter positions, selected tabs,
+//! scroll offsets, collapsed sections).
+//!
+//! All state is gathered into a single [`GuiState`] struct. With the `serde`
+//! feature enabled it can be serialized with any serde format (i.e. into the
+//! state chunk of a plugin) and restored when the GUI is reopened.
+//!
+//! Elements are identified by application-defined string keys, so that the
+//! state stays valid across different builds of the application.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::elements::resize_handle::ResizeHandle;
+use crate::elements::scroll_area::ScrollArea;
+use crate::elements::tab::TabGroup;
+use crate::math::{Size, Vector};
+use crate::window::ScaleFactorConfig;
+use crate::{AppContext, WindowContext, WindowID};
+
+/// Persistable GUI state.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct GuiState {
+    /// The logical size of the window in points (width, height).
+    pub window_size: Option<[f32; 2]>,
+    /// The scale factor configuration of the window.
+    pub scale_factor: ScaleFactorConfig,
+    /// The UI zoom factor.
+    pub ui_zoom: f32,
+    /// The current span of each splitter (resize handle).
+    pub splitter_positions: BTreeMap<String, f32>,
+    /// The index of the selected tab of each tab group.
+    pub selected_tabs: BTreeMap<String, usize>,
+    /// The scroll offset of each scroll area (x, y).
+    pub scroll_offsets: BTreeMap<String, [f32; 2]>,
+    /// The keys of all collapsed sections.
+    pub collapsed_sections: BTreeSet<String>,
+}
+
+impl Default for GuiState {
+    fn default() -> Self {
+        Self {
+            window_size: None,
+            scale_factor: ScaleFactorConfig::default(),
+            ui_zoom: 1.0,
+            splitter_positions: BTreeMap::new(),
+            selected_tabs: BTreeMap::new(),
+            scroll_offsets: BTreeMap::new(),
+            collapsed_sections: BTreeSet::new(),
+        }
+    }
+}
+
+impl GuiState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Store the size, scale factor, and zoom factor of the given window.
+    pub fn save_window<A: Clone + 'static>(&mut self, window_cx: &WindowContext<'_, A>) {
+        let size = window_cx.logical_size();
+
+        self.window_size = Some([size.width, size.height]);
+        self.scale_factor = window_cx.scale_factor_config();
+        self.ui_zoom = window_cx.ui_zoom();
+    }
+
+    /// Restore the size, scale factor, and zoom factor of the given window.
+    pub fn restore_window<A: Clone + 'static>(&self, window_id: WindowID, cx: &mut AppContext<A>) {
+        cx.set_scale_factor_config(window_id, self.scale_factor);
+        cx.set_ui_zoom(self.ui_zoom);
+
+        if let Some([width, height]) = self.window_size {
+            cx.resize_window(window_id, Size::new(width, height));
+        }
+    }
+
+    /// Store the span of a splitter.
+    pub fn save_splitter(&mut self, key: impl Into<String>, handle: &ResizeHandle) {
+        self.splitter_positions
+            .insert(key.into(), handle.current_span());
+    }
+
+    /// Restore the span of a splitter.
+    ///
+    /// Returns `false` if no state was stored for the given key.
+    pub fn restore_splitter(&self, key: &str, handle: &mut ResizeHandle) -> bool {
+        if let Some(span) = self.splitter_positions.get(key) {
+            handle.set_span(*span);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Store the selected tab of a tab group.
+    pub fn save_tab_group(&mut self, key: impl Into<String>, tab_group: &TabGroup) {
+        self.selected_tabs
+            .insert(key.into(), tab_group.selected_index());
+    }
+
+    /// Restore the selected tab of a tab group.
+    ///
+    /// Returns `false` if no state was stored for the given key.
+    pub fn restore_tab_group(&self, key: &str, tab_group: &mut TabGroup) -> bool {
+        if let Some(index) = self.selected_tabs.get(key) {
+            tab_group.updated_selected(*index);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// The index of the selected tab stored for the given key.
+    pub fn selected_tab(&self, key: &str) -> Option<usize> {
+        self.selected_tabs.get(key).copied()
+    }
+
+    /// Store the scroll offset of a scroll area.
+    pub fn save_scroll_area(&mut self, key: impl Into<String>, scroll_area: &ScrollArea) {
+        let offset = scroll_area.scroll_offset();
+        self.scroll_offsets.insert(key.into(), [offset.x, offset.y]);
+    }
+
+    /// Restore the scroll offset of a scroll area.
+    ///
+    /// Returns `false` if no state was stored for the given key.
+    pub fn restore_scroll_area(&self, key: &str, scroll_area: &mut ScrollArea) -> bool {
+        if let Some([x, y]) = self.scroll_offsets.get(key) {
+            scroll_area.set_scroll_offset(Vector::new(*x, *y));
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Set whether the section with the given key is collapsed.
+    pub fn set_collapsed(&mut self, key: impl Into<String>, collapsed: bool) {
+        if collapsed {
+            self.collapsed_sections.insert(key.into());
+        } else {
+            self.collapsed_sections.remove(&key.into());
+        }
+    }
+
+    /// Returns `true` if the section with the given key is collapsed.
+    pub fn is_collapsed(&self, key: &str) -> bool {
+        self.collapsed_sections.contains(key)
+    }
+}
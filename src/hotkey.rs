@@ -0,0 +1,163 @@
+//! System-wide keyboard shortcuts for standalone applications (i.e. a
+//! shortcut which starts a loopback recording while another application is
+//! focused).
+//!
+//! When a registered hotkey is pressed, its action is sent through the
+//! normal action queue (see `AppContext::register_global_hotkey`).
+//!
+//! Global hotkeys are not supported on Wayland. This requires the
+//! `global-hotkey` feature.
+
+use global_hotkey::hotkey::HotKey;
+use global_hotkey::{GlobalHotKeyEvent, GlobalHotKeyManager, HotKeyState};
+use keyboard_types::{Code, Modifiers};
+use rustc_hash::FxHashMap;
+
+use crate::action_queue::ActionSender;
+use crate::shortcut::KeyChord;
+
+#[derive(thiserror::Error, Debug)]
+pub enum GlobalHotkeyError {
+    #[error("The key {0} is not supported as a global hotkey")]
+    UnsupportedKey(Code),
+    #[error("{0}")]
+    Os(#[from] global_hotkey::Error),
+}
+
+/// The ID of a registered global hotkey.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GlobalHotkeyID(u32);
+
+/// A key combination which triggers a global hotkey.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Hotkey {
+    pub modifiers: Modifiers,
+    /// The physical key.
+    pub code: Code,
+}
+
+impl Hotkey {
+    pub fn new(modifiers: Modifiers, code: Code) -> Self {
+        Self { modifiers, code }
+    }
+
+    fn to_hotkey(self) -> Result<HotKey, GlobalHotkeyError> {
+        // `global-hotkey` uses a different version of `keyboard-types`, so
+        // convert through the names of the keys.
+        let code = self
+            .code
+            .to_string()
+            .parse()
+            .map_err(|_| GlobalHotkeyError::UnsupportedKey(self.code))?;
+
+        let mut modifiers = global_hotkey::hotkey::Modifiers::empty();
+        if self.modifiers.contains(Modifiers::SHIFT) {
+            modifiers |= global_hotkey::hotkey::Modifiers::SHIFT;
+        }
+        if self.modifiers.contains(Modifiers::CONTROL) {
+            modifiers |= global_hotkey::hotkey::Modifiers::CONTROL;
+        }
+        if self.modifiers.contains(Modifiers::ALT) {
+            modifiers |= global_hotkey::hotkey::Modifiers::ALT;
+        }
+        if self.modifiers.contains(Modifiers::META) {
+            modifiers |= global_hotkey::hotkey::Modifiers::META;
+        }
+
+        Ok(HotKey::new(
+            (!modifiers.is_empty()).then_some(modifiers),
+            code,
+        ))
+    }
+}
+
+impl From<Hotkey> for KeyChord {
+    fn from(hotkey: Hotkey) -> Self {
+        KeyChord::new(hotkey.modifiers, hotkey.code)
+    }
+}
+
+pub(crate) struct GlobalHotkeys<A: Clone + 'static> {
+    manager: Option<GlobalHotKeyManager>,
+    registered: FxHashMap<u32, RegisteredHotkey<A>>,
+}
+
+struct RegisteredHotkey<A> {
+    hotkey: HotKey,
+    chord: KeyChord,
+    action: A,
+}
+
+impl<A: Clone + 'static> GlobalHotkeys<A> {
+    pub fn new() -> Self {
+        Self {
+            manager: None,
+            registered: FxHashMap::default(),
+        }
+    }
+
+    pub fn register(
+        &mut self,
+        hotkey: Hotkey,
+        action: A,
+    ) -> Result<GlobalHotkeyID, GlobalHotkeyError> {
+        let chord = KeyChord::from(hotkey);
+        let hotkey = hotkey.to_hotkey()?;
+
+        let manager = match &mut self.manager {
+            Some(manager) => manager,
+            None => self.manager.insert(GlobalHotKeyManager::new()?),
+        };
+
+        manager.register(hotkey)?;
+        self.registered.insert(
+            hotkey.id(),
+            RegisteredHotkey {
+                hotkey,
+                chord,
+                action,
+            },
+        );
+
+        Ok(GlobalHotkeyID(hotkey.id()))
+    }
+
+    pub fn unregister(&mut self, id: GlobalHotkeyID) -> Result<(), GlobalHotkeyError> {
+        let Some(registered) = self.registered.remove(&id.0) else {
+            return Ok(());
+        };
+
+        if let Some(manager) = &self.manager {
+            manager.unregister(registered.hotkey)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.registered.is_empty()
+    }
+
+    /// Send the actions for all hotkeys pressed since the last call.
+    pub fn poll_events(&mut self, action_sender: &mut ActionSender<A>) {
+        while let Ok(event) = GlobalHotKeyEvent::receiver().try_recv() {
+            if event.state != HotKeyState::Pressed {
+                continue;
+            }
+
+            if let Some(registered) = self.registered.get(&event.id) {
+                let _ = action_sender.send(registered.action.clone());
+            }
+        }
+    }
+}
+
+impl<A: Clone + PartialEq + 'static> GlobalHotkeys<A> {
+    /// The chord of the hotkey registered with the given action (if any).
+    pub fn chord_for_action(&self, action: &A) -> Option<KeyChord> {
+        self.registered
+            .values()
+            .find(|registered| &registered.action == action)
+            .map(|registered| registered.chord)
+    }
+}
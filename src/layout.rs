@@ -41,6 +41,68 @@ pub enum LayoutDirection {
     Vertical,
 }
 
+/// The direction in which text and horizontally laid-out content flows.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TextDirection {
+    #[default]
+    LeftToRight,
+    /// Used by languages such as Arabic and Hebrew. Content which is laid out
+    /// horizontally is mirrored.
+    RightToLeft,
+}
+
+impl TextDirection {
+    pub fn is_rtl(&self) -> bool {
+        *self == Self::RightToLeft
+    }
+
+    /// Swap `Align::Start` and `Align::End` if this is right-to-left.
+    pub fn mirror_align(&self, align: Align) -> Align {
+        match (self, align) {
+            (Self::RightToLeft, Align::Start) => Align::End,
+            (Self::RightToLeft, Align::End) => Align::Start,
+            _ => align,
+        }
+    }
+
+    /// Mirror the horizontal alignment if this is right-to-left.
+    pub fn mirror_align2(&self, align: Align2) -> Align2 {
+        Align2 {
+            horizontal: self.mirror_align(align.horizontal),
+            vertical: align.vertical,
+        }
+    }
+
+    /// Swap the left and right padding if this is right-to-left.
+    pub fn mirror_padding(&self, padding: Padding) -> Padding {
+        if self.is_rtl() {
+            Padding::new(padding.top, padding.left, padding.bottom, padding.right)
+        } else {
+            padding
+        }
+    }
+
+    /// Mirror the given rectangle horizontally inside of `container` if this
+    /// is right-to-left.
+    ///
+    /// This can be used to write a layout once in left-to-right coordinates
+    /// and have it automatically flipped for right-to-left languages.
+    pub fn mirror_rect(&self, rect: Rect, container: Rect) -> Rect {
+        if self.is_rtl() {
+            Rect::new(
+                Point::new(
+                    container.max_x() - (rect.max_x() - container.min_x()),
+                    rect.min_y(),
+                ),
+                rect.size,
+            )
+        } else {
+            rect
+        }
+    }
+}
+
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Align {
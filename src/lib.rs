@@ -13,26 +13,51 @@
 // ---------------------------------------------------------------------------------
 
 pub mod action_queue;
+pub mod action_router;
 mod application;
+#[cfg(all(feature = "clap", not(target_family = "wasm")))]
+pub mod clap;
 pub mod clipboard;
 pub(crate) mod cursor_icon;
 pub(crate) mod element_system;
 pub mod elements;
 pub mod event;
+pub mod file_dialog;
+pub mod gui_state;
+#[cfg(all(feature = "global-hotkey", not(target_family = "wasm")))]
+pub mod hotkey;
 pub mod layout;
+pub mod locale;
+pub mod param_store;
 pub mod prelude;
+pub mod shortcut;
+pub mod signal;
 pub(crate) mod stmpsc_queue;
 pub mod style;
+#[cfg(all(feature = "headless", not(target_family = "wasm")))]
+pub mod testing;
 pub mod theme;
+#[cfg(all(feature = "tray", not(target_family = "wasm")))]
+pub mod tray;
+pub mod undo;
+mod view;
+pub mod widget;
 pub mod window;
 
 pub use action_queue::action_channel;
 pub use application::{AppConfig, AppContext, Application};
 pub use cursor_icon::CursorIcon;
-pub use element_system::{ScissorRectID, TooltipInfo};
-pub use window::{WindowContext, WindowID, MAIN_WINDOW};
+pub use element_system::{
+    ColorVisionDeficiency, CustomRenderPass, ElementID, ElementKey, ElementRef, ElementTransform,
+    FrameStats, FrameTiming, RenderPassContext, ScissorRectID, TooltipInfo,
+};
+pub use window::{WindowContext, WindowControl, WindowID, MAIN_WINDOW};
 pub use yarrow_derive as derive;
 
+#[cfg(feature = "custom-shaders")]
+pub use bytemuck;
+#[cfg(feature = "backdrop-blur")]
+pub use element_system::BackdropBlur;
 #[cfg(feature = "custom-shaders")]
 pub use element_system::CustomPipelines;
 
@@ -40,7 +65,7 @@ pub use rootvg as vg;
 pub use rootvg::math;
 
 pub use window::run_blocking;
-#[cfg(feature = "baseview")]
+#[cfg(all(feature = "baseview", not(target_family = "wasm")))]
 pub use window::run_parented;
 
 pub use derive_where;
@@ -0,0 +1,238 @@
+use std::borrow::Cow;
+
+/// A backend which translates strings into the user's language (i.e. a
+/// wrapper around a Fluent or gettext catalog).
+pub trait Translator {
+    /// Translate the string with the given key, or return `None` to use the
+    /// default (English) text.
+    fn translate(&self, key: &str) -> Option<String>;
+}
+
+/// A string used by the built-in elements.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BuiltinString {
+    /// The default tooltip of a parameter element. `{value}` is replaced with
+    /// the formatted normalized value in percent.
+    ParamValueTooltip,
+    /// The error shown when text entered into a number field could not be
+    /// parsed.
+    InvalidNumber,
+    /// The error shown when a number entered into a number field is out of
+    /// range. `{min}` and `{max}` are replaced with the formatted bounds.
+    NumberOutOfRange,
+    /// The default text of the back button in a stepper.
+    StepperBack,
+    /// The default text of the next button in a stepper.
+    StepperNext,
+    /// The default text of the next button on the last step of a stepper.
+    StepperFinish,
+    /// The header and toggle button text of a notification panel.
+    Notifications,
+    /// The text of the button which clears all notifications in a
+    /// notification panel.
+    ClearAllNotifications,
+}
+
+impl BuiltinString {
+    pub const ALL: [Self; 8] = [
+        Self::ParamValueTooltip,
+        Self::InvalidNumber,
+        Self::NumberOutOfRange,
+        Self::StepperBack,
+        Self::StepperNext,
+        Self::StepperFinish,
+        Self::Notifications,
+        Self::ClearAllNotifications,
+    ];
+
+    /// The key passed to [`Translator::translate`].
+    pub const fn key(&self) -> &'static str {
+        match self {
+            Self::ParamValueTooltip => "yarrow-param-value-tooltip",
+            Self::InvalidNumber => "yarrow-invalid-number",
+            Self::NumberOutOfRange => "yarrow-number-out-of-range",
+            Self::StepperBack => "yarrow-stepper-back",
+            Self::StepperNext => "yarrow-stepper-next",
+            Self::StepperFinish => "yarrow-stepper-finish",
+            Self::Notifications => "yarrow-notifications",
+            Self::ClearAllNotifications => "yarrow-clear-all-notifications",
+        }
+    }
+
+    /// The text used when no translation is available.
+    pub const fn default_text(&self) -> &'static str {
+        match self {
+            Self::ParamValueTooltip => "{value}%",
+            Self::InvalidNumber => "Not a valid number",
+            Self::NumberOutOfRange => "Must be between {min} and {max}",
+            Self::StepperBack => "Back",
+            Self::StepperNext => "Next",
+            Self::StepperFinish => "Finish",
+            Self::Notifications => "Notifications",
+            Self::ClearAllNotifications => "Clear all",
+        }
+    }
+}
+
+/// The locale used by the built-in elements for translated strings and for
+/// formatting and parsing numbers.
+pub struct Locale {
+    /// The character which separates the integer part of a number from the
+    /// fractional part.
+    ///
+    /// By default this is set to `'.'`.
+    pub decimal_separator: char,
+    /// The character which groups the digits of the integer part of a number
+    /// into thousands, or `None` to not group digits.
+    ///
+    /// By default this is set to `None`.
+    pub thousands_separator: Option<char>,
+    translator: Option<Box<dyn Translator>>,
+}
+
+impl Locale {
+    /// Set the backend used to translate strings, or `None` to use the
+    /// default (English) text.
+    ///
+    /// Note, elements which have already been built are not updated. Rebuild
+    /// the GUI (or update the text of the affected elements) after changing
+    /// the language.
+    pub fn set_translator(&mut self, translator: Option<Box<dyn Translator>>) {
+        self.translator = translator;
+    }
+
+    pub fn has_translator(&self) -> bool {
+        self.translator.is_some()
+    }
+
+    /// Translate the string with the given key, falling back to `default`
+    /// if there is no translator or it has no translation for the key.
+    pub fn translate<'a>(&self, key: &str, default: &'a str) -> Cow<'a, str> {
+        self.translator
+            .as_ref()
+            .and_then(|t| t.translate(key))
+            .map(Cow::Owned)
+            .unwrap_or(Cow::Borrowed(default))
+    }
+
+    /// Get the translated text of a string used by the built-in elements.
+    pub fn builtin(&self, s: BuiltinString) -> Cow<'static, str> {
+        self.translate(s.key(), s.default_text())
+    }
+
+    /// Get the translated text of a string used by the built-in elements,
+    /// replacing each `{name}` placeholder with its value.
+    pub fn builtin_with_args(&self, s: BuiltinString, args: &[(&str, &str)]) -> String {
+        let mut text = self.builtin(s).into_owned();
+        for (name, value) in args.iter() {
+            text = text.replace(&format!("{{{}}}", name), value);
+        }
+        text
+    }
+
+    /// Format a number with the given number of digits after the decimal
+    /// separator.
+    pub fn format_number(&self, value: f64, precision: usize) -> String {
+        let s = format!("{:.*}", precision, value);
+
+        let (sign, s) = match s.strip_prefix('-') {
+            Some(s) => ("-", s),
+            None => ("", s.as_str()),
+        };
+        let (int_part, frac_part) = match s.split_once('.') {
+            Some((i, f)) => (i, Some(f)),
+            None => (s, None),
+        };
+
+        let mut res = String::with_capacity(s.len() + 4);
+        res.push_str(sign);
+
+        if let Some(sep) = self.thousands_separator {
+            for (i, c) in int_part.chars().enumerate() {
+                if i > 0 && (int_part.len() - i) % 3 == 0 {
+                    res.push(sep);
+                }
+                res.push(c);
+            }
+        } else {
+            res.push_str(int_part);
+        }
+
+        if let Some(frac_part) = frac_part {
+            res.push(self.decimal_separator);
+            res.push_str(frac_part);
+        }
+
+        res
+    }
+
+    /// Parse a number entered by the user.
+    ///
+    /// The locale's decimal separator is accepted as well as `'.'` (unless
+    /// `'.'` is the thousands separator). Thousands separators and whitespace
+    /// are ignored.
+    pub fn parse_number(&self, text: &str) -> Option<f64> {
+        let text = text.trim();
+
+        let normalized: String = text
+            .chars()
+            .filter(|c| Some(*c) != self.thousands_separator && !c.is_whitespace())
+            .map(|c| if c == self.decimal_separator { '.' } else { c })
+            .collect();
+
+        normalized.parse::<f64>().ok().filter(|v| v.is_finite())
+    }
+
+    /// Parse a number entered by the user (i.e. into the text entry opened
+    /// by a parameter element) and check that it lies within the given
+    /// range.
+    ///
+    /// * `precision` - The number of digits after the decimal separator used
+    /// when formatting the bounds in the error message.
+    ///
+    /// On failure, this returns a translated error message which can be shown
+    /// to the user.
+    pub fn parse_number_in_range(
+        &self,
+        text: &str,
+        min: f64,
+        max: f64,
+        precision: usize,
+    ) -> Result<f64, String> {
+        let Some(value) = self.parse_number(text) else {
+            return Err(self.builtin(BuiltinString::InvalidNumber).into_owned());
+        };
+
+        if value < min || value > max {
+            let min = self.format_number(min, precision);
+            let max = self.format_number(max, precision);
+
+            return Err(self.builtin_with_args(
+                BuiltinString::NumberOutOfRange,
+                &[("min", &min), ("max", &max)],
+            ));
+        }
+
+        Ok(value)
+    }
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Self {
+            decimal_separator: '.',
+            thousands_separator: None,
+            translator: None,
+        }
+    }
+}
+
+impl std::fmt::Debug for Locale {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Locale")
+            .field("decimal_separator", &self.decimal_separator)
+            .field("thousands_separator", &self.thousands_separator)
+            .field("has_translator", &self.translator.is_some())
+            .finish()
+    }
+}
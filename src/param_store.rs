@@ -0,0 +1,446 @@
+//! A store of parameter definitions and values which keeps any number of
+//! parameter elements (knobs, sliders) in sync.
+//!
+//! Updates from the host go through [`ParamStore::set`] (or
+//! [`ParamStore::set_normal`]), which fans the new value out to every bound
+//! element. Gestures from the GUI go through [`ParamStore::handle_update`],
+//! which fans the new value out in the same way and returns a [`ParamEvent`]
+//! to forward to the host.
+
+use std::fmt::Debug;
+use std::ops::RangeInclusive;
+use std::rc::Rc;
+
+use rustc_hash::FxHashMap;
+use smol_str::{SmolStr, ToSmolStr};
+
+use crate::elements::virtual_slider::{
+    param_normal_to_quantized, param_quantized_to_normal, GestureState, ParamUpdate, VirtualSlider,
+    VirtualSliderBuilder, VirtualSliderLink, VirtualSliderRenderer,
+};
+
+/// The definition of a parameter in a [`ParamStore`].
+#[derive(Clone)]
+pub struct ParamDef {
+    /// The parameter ID
+    pub id: SmolStr,
+    /// The range of the plain (un-normalized) value
+    pub range: RangeInclusive<f64>,
+    /// The default plain value
+    pub default: f64,
+    /// The number of steps (if this parameter is stepped)
+    pub num_steps: Option<u32>,
+    /// The function used to format a plain value into a string
+    ///
+    /// If this is `None`, then the value will be formatted with two decimal
+    /// places.
+    pub formatter: Option<Rc<dyn Fn(f64) -> String>>,
+}
+
+impl ParamDef {
+    pub fn new(id: impl ToSmolStr, range: RangeInclusive<f64>, default: f64) -> Self {
+        Self {
+            id: id.to_smolstr(),
+            range,
+            default,
+            num_steps: None,
+            formatter: None,
+        }
+    }
+
+    /// The number of steps of this parameter.
+    ///
+    /// By default this is set to `None` (not stepped).
+    pub const fn steps(mut self, num_steps: u32) -> Self {
+        self.num_steps = Some(num_steps);
+        self
+    }
+
+    /// The function used to format a plain value into a string.
+    pub fn formatter<F: Fn(f64) -> String + 'static>(mut self, f: F) -> Self {
+        self.formatter = Some(Rc::new(f));
+        self
+    }
+
+    /// Convert a plain value into a normalized value in the range `[0.0, 1.0]`.
+    pub fn plain_to_normal(&self, value: f64) -> f64 {
+        let (min, max) = (*self.range.start(), *self.range.end());
+        let normal = if max > min {
+            ((value - min) / (max - min)).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+
+        self.snap_normal(normal)
+    }
+
+    /// Convert a normalized value in the range `[0.0, 1.0]` into a plain value.
+    pub fn normal_to_plain(&self, normal: f64) -> f64 {
+        let (min, max) = (*self.range.start(), *self.range.end());
+        min + (self.snap_normal(normal.clamp(0.0, 1.0)) * (max - min))
+    }
+
+    /// Format the given plain value into a string.
+    pub fn format(&self, value: f64) -> String {
+        if let Some(formatter) = &self.formatter {
+            (formatter)(value)
+        } else {
+            format!("{:.2}", value)
+        }
+    }
+
+    fn snap_normal(&self, normal: f64) -> f64 {
+        if let Some(num_steps) = self.num_steps {
+            param_quantized_to_normal(param_normal_to_quantized(normal, num_steps), num_steps)
+        } else {
+            normal
+        }
+    }
+}
+
+impl Debug for ParamDef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ParamDef")
+            .field("id", &self.id)
+            .field("range", &self.range)
+            .field("default", &self.default)
+            .field("num_steps", &self.num_steps)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A change to a parameter caused by the user gesturing on a bound element.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParamEvent {
+    /// The parameter ID
+    pub id: SmolStr,
+    /// The new normalized value in the range `[0.0, 1.0]`
+    pub normal_value: f64,
+    /// The new plain value
+    pub value: f64,
+    /// The current state of gesturing (dragging)
+    ///
+    /// If this event is not the result of the user gesturing (i.e. the
+    /// element was reset to its default value), then this will be `None`.
+    pub gesture_state: Option<GestureState>,
+}
+
+/// An element which can be bound to a parameter in a [`ParamStore`].
+pub trait ParamBindingTarget {
+    /// Set the normalized value of the element.
+    fn set_normal_value(&mut self, normal: f64);
+    /// Set the default normalized value of the element.
+    fn set_default_normal(&mut self, normal: f64);
+    /// Returns `false` if the element has been dropped and this binding
+    /// should be removed.
+    fn is_alive(&self) -> bool;
+}
+
+impl<R: VirtualSliderRenderer + 'static> ParamBindingTarget for VirtualSliderLink<R> {
+    fn set_normal_value(&mut self, normal: f64) {
+        VirtualSliderLink::set_normal_value(self, normal);
+    }
+
+    fn set_default_normal(&mut self, normal: f64) {
+        VirtualSliderLink::set_default_normal(self, normal);
+    }
+
+    fn is_alive(&self) -> bool {
+        VirtualSliderLink::is_alive(self)
+    }
+}
+
+struct ParamEntry {
+    def: ParamDef,
+    normal_value: f64,
+    bindings: Vec<Box<dyn ParamBindingTarget>>,
+}
+
+impl ParamEntry {
+    fn fan_out(&mut self) {
+        self.bindings.retain(|b| b.is_alive());
+
+        for binding in self.bindings.iter_mut() {
+            binding.set_normal_value(self.normal_value);
+        }
+    }
+}
+
+/// A store which owns parameter definitions and values, and keeps any number
+/// of bound parameter elements in sync.
+#[derive(Default)]
+pub struct ParamStore {
+    params: FxHashMap<SmolStr, ParamEntry>,
+}
+
+impl ParamStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a parameter definition to the store. The parameter will be set to
+    /// its default value.
+    ///
+    /// If a parameter with the same ID already exists, then it will be
+    /// replaced (while keeping its bindings).
+    pub fn add(&mut self, def: ParamDef) {
+        let normal_value = def.plain_to_normal(def.default);
+        let default_normal = normal_value;
+
+        let bindings = self
+            .params
+            .remove(&def.id)
+            .map(|entry| entry.bindings)
+            .unwrap_or_default();
+
+        let mut entry = ParamEntry {
+            def,
+            normal_value,
+            bindings,
+        };
+
+        for binding in entry.bindings.iter_mut() {
+            binding.set_default_normal(default_normal);
+        }
+        entry.fan_out();
+
+        self.params.insert(entry.def.id.clone(), entry);
+    }
+
+    /// The definition of the parameter with the given ID.
+    pub fn def(&self, id: &str) -> Option<&ParamDef> {
+        self.params.get(id).map(|entry| &entry.def)
+    }
+
+    /// Apply the definition of the parameter to a knob or slider builder
+    /// (the parameter ID is taken from the builder).
+    ///
+    /// This sets the current value, the default value, and the number of
+    /// steps of the element.
+    pub fn configure<A: Clone + 'static>(
+        &self,
+        builder: VirtualSliderBuilder<A>,
+    ) -> VirtualSliderBuilder<A> {
+        let Some(entry) = self.params.get(&builder.param_id) else {
+            log::warn!("No parameter with ID {} in param store", &builder.param_id);
+            return builder;
+        };
+
+        builder
+            .normal_value(entry.normal_value)
+            .default_normal(entry.def.plain_to_normal(entry.def.default))
+            .num_quantized_steps(entry.def.num_steps)
+    }
+
+    /// Bind a knob or slider to the parameter with the given ID, so that it
+    /// is updated whenever the value in the store changes. Any number of
+    /// elements can be bound to the same parameter.
+    ///
+    /// The element is immediately set to the current value in the store.
+    /// The binding is removed automatically once the element is dropped.
+    ///
+    /// Returns `false` if no parameter with the given ID exists.
+    pub fn bind<R: VirtualSliderRenderer + 'static>(
+        &mut self,
+        id: &str,
+        element: &VirtualSlider<R>,
+    ) -> bool {
+        self.bind_target(id, Box::new(element.link()))
+    }
+
+    /// Bind a custom target to the parameter with the given ID.
+    ///
+    /// Returns `false` if no parameter with the given ID exists.
+    pub fn bind_target(&mut self, id: &str, mut target: Box<dyn ParamBindingTarget>) -> bool {
+        let Some(entry) = self.params.get_mut(id) else {
+            return false;
+        };
+
+        target.set_default_normal(entry.def.plain_to_normal(entry.def.default));
+        target.set_normal_value(entry.normal_value);
+        entry.bindings.push(target);
+
+        true
+    }
+
+    /// Set the plain value of a parameter (i.e. from the host), and update
+    /// all bound elements.
+    ///
+    /// Returns `false` if no parameter with the given ID exists.
+    pub fn set(&mut self, id: &str, value: f64) -> bool {
+        let Some(entry) = self.params.get_mut(id) else {
+            return false;
+        };
+
+        entry.normal_value = entry.def.plain_to_normal(value);
+        entry.fan_out();
+
+        true
+    }
+
+    /// Set the normalized value of a parameter (i.e. from the host), and
+    /// update all bound elements.
+    ///
+    /// Returns `false` if no parameter with the given ID exists.
+    pub fn set_normal(&mut self, id: &str, normal: f64) -> bool {
+        let Some(entry) = self.params.get_mut(id) else {
+            return false;
+        };
+
+        entry.normal_value = entry.def.snap_normal(normal.clamp(0.0, 1.0));
+        entry.fan_out();
+
+        true
+    }
+
+    /// The plain value of a parameter.
+    pub fn value(&self, id: &str) -> Option<f64> {
+        self.params
+            .get(id)
+            .map(|entry| entry.def.normal_to_plain(entry.normal_value))
+    }
+
+    /// The normalized value of a parameter.
+    pub fn normal_value(&self, id: &str) -> Option<f64> {
+        self.params.get(id).map(|entry| entry.normal_value)
+    }
+
+    /// The current value of a parameter formatted as a string.
+    pub fn format(&self, id: &str) -> Option<String> {
+        self.params.get(id).map(|entry| {
+            entry
+                .def
+                .format(entry.def.normal_to_plain(entry.normal_value))
+        })
+    }
+
+    /// Handle an update from a bound element (i.e. the `on_gesture` action of
+    /// a knob or slider).
+    ///
+    /// This updates the value in the store and every element bound to the
+    /// same parameter, and returns the event to forward to the host. The
+    /// element which sent the update is written to as well. This is a no-op
+    /// if it already has the new value, and otherwise snaps it to the value
+    /// stored (i.e. for a stepped parameter).
+    ///
+    /// Returns `None` if no parameter with the given ID exists.
+    pub fn handle_update(&mut self, update: &ParamUpdate) -> Option<ParamEvent> {
+        let entry = self.params.get_mut(&update.param_info.id)?;
+
+        entry.normal_value = entry.def.snap_normal(update.param_info.normal_value);
+        entry.fan_out();
+
+        Some(ParamEvent {
+            id: entry.def.id.clone(),
+            normal_value: entry.normal_value,
+            value: entry.def.normal_to_plain(entry.normal_value),
+            gesture_state: update.gesture_state,
+        })
+    }
+
+    /// An iterator over all of the parameter definitions in the store.
+    pub fn defs(&self) -> impl Iterator<Item = &ParamDef> {
+        self.params.values().map(|entry| &entry.def)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::rc::{Rc, Weak};
+
+    use crate::elements::virtual_slider::ParamInfo;
+
+    use super::*;
+
+    #[derive(Default)]
+    struct MockState {
+        normal_value: f64,
+        num_writes: usize,
+    }
+
+    struct MockTarget(Weak<RefCell<MockState>>);
+
+    impl ParamBindingTarget for MockTarget {
+        fn set_normal_value(&mut self, normal: f64) {
+            if let Some(state) = self.0.upgrade() {
+                let mut state = state.borrow_mut();
+                if state.normal_value != normal {
+                    state.normal_value = normal;
+                    state.num_writes += 1;
+                }
+            }
+        }
+
+        fn set_default_normal(&mut self, _normal: f64) {}
+
+        fn is_alive(&self) -> bool {
+            self.0.strong_count() > 0
+        }
+    }
+
+    fn bind_mock(store: &mut ParamStore, id: &str) -> Rc<RefCell<MockState>> {
+        let state = Rc::new(RefCell::new(MockState::default()));
+        assert!(store.bind_target(id, Box::new(MockTarget(Rc::downgrade(&state)))));
+        state
+    }
+
+    fn update(id: &str, normal_value: f64) -> ParamUpdate {
+        ParamUpdate {
+            param_info: ParamInfo {
+                id: id.into(),
+                normal_value,
+                stepped_value: None,
+            },
+            gesture_state: None,
+        }
+    }
+
+    #[test]
+    fn test_dropped_bindings_are_removed() {
+        let mut store = ParamStore::new();
+        store.add(ParamDef::new("gain", 0.0..=1.0, 0.0));
+
+        let a = bind_mock(&mut store, "gain");
+        let b = bind_mock(&mut store, "gain");
+        drop(b);
+
+        store.set("gain", 0.5);
+
+        assert_eq!(store.params["gain"].bindings.len(), 1);
+        assert_eq!(a.borrow().normal_value, 0.5);
+    }
+
+    #[test]
+    fn test_handle_update_updates_every_binding() {
+        let mut store = ParamStore::new();
+        store.add(ParamDef::new("gain", 0.0..=1.0, 0.0));
+
+        let source = bind_mock(&mut store, "gain");
+        let other = bind_mock(&mut store, "gain");
+
+        // The source element already has the value it sent.
+        source.borrow_mut().normal_value = 0.25;
+
+        let event = store.handle_update(&update("gain", 0.25)).unwrap();
+
+        assert_eq!(event.normal_value, 0.25);
+        assert_eq!(source.borrow().num_writes, 0);
+        assert_eq!(other.borrow().normal_value, 0.25);
+        assert_eq!(other.borrow().num_writes, 1);
+    }
+
+    #[test]
+    fn test_handle_update_snaps_source_of_stepped_param() {
+        let mut store = ParamStore::new();
+        store.add(ParamDef::new("mode", 0.0..=4.0, 0.0).steps(5));
+
+        let source = bind_mock(&mut store, "mode");
+        source.borrow_mut().normal_value = 0.3;
+
+        let event = store.handle_update(&update("mode", 0.3)).unwrap();
+
+        assert_eq!(event.value, 1.0);
+        assert_eq!(source.borrow().normal_value, event.normal_value);
+    }
+}
@@ -1,25 +1,64 @@
-pub use crate::action_queue::{ActionReceiver, ActionSender};
+pub use crate::action_queue::{ActionReceiver, ActionSender, ActionSource};
+pub use crate::action_router::ActionRouter;
 pub use crate::application::*;
 pub use crate::cursor_icon::*;
 pub use crate::element_system::{
     element::{
-        Element, ElementBuilder, ElementContext, ElementFlags, ElementHandle, ElementRenderCache,
-        ElementStyle, RenderContext,
+        AnimatedProperty, AnimatedPropertyKind, DragGhost, Element, ElementBuilder, ElementContext,
+        ElementFlags, ElementHandle, ElementID, ElementKey, ElementRef, ElementRenderCache,
+        ElementStyle, ElementTransform, RenderContext,
     },
+    ColorVisionDeficiency, CustomRenderPass, FrameStats, FrameTiming, RenderPassContext,
     ScissorRectID, TooltipInfo,
 };
-pub use crate::elements::button::{Button, ButtonStyle};
+pub use crate::elements::ab_compare::{AbCompare, AbSlot};
+pub use crate::elements::badge::{Badge, BadgeStyle};
+pub use crate::elements::button::{Button, ButtonStyle, FocusedStyle};
+pub use crate::elements::canvas::{Canvas, CanvasStyle};
+pub use crate::elements::carousel::{Carousel, CarouselStyle};
 pub use crate::elements::click_area::ClickArea;
+pub use crate::elements::custom::{custom_element, CustomElement, SimpleElement};
+pub use crate::elements::drag_value::{DragValue, DragValueStyle};
 pub use crate::elements::drop_down_menu::{DropDownMenu, DropDownMenuStyle, MenuEntry};
+pub use crate::elements::editable_label::{EditableLabel, EditableLabelStyle};
+#[cfg(feature = "custom-shaders")]
+pub use crate::elements::external_texture::{ExternalTexture, TextureFit};
+pub use crate::elements::file_browser::{FileBrowser, FileBrowserEntry, FileBrowserStyle};
+pub use crate::elements::hyperlink::{Hyperlink, HyperlinkStyle, UnderlineMode};
 #[cfg(feature = "svg-icons")]
 pub use crate::elements::icon::{Icon, IconStyle};
-pub use crate::elements::label::{Label, LabelStyle, TextIconLayout};
+pub use crate::elements::label::{Label, LabelStyle, TextIconLayout, TextTruncation};
+pub use crate::elements::lazy_region::{LazyContents, LazyRegion};
+pub use crate::elements::log_view::{LogLine, LogView, LogViewStyle};
+pub use crate::elements::markdown::{Markdown, MarkdownStyle};
+pub use crate::elements::minimap::{Minimap, MinimapItem, MinimapStyle};
+#[cfg(feature = "tessellation")]
+pub use crate::elements::node_graph::{
+    Cable, GraphNode, NodeGraph, NodeGraphEdit, NodeGraphStyle, NodeID, NodePort, PortRef,
+};
+pub use crate::elements::notification_panel::{
+    NotificationID, NotificationPanel, NotificationPanelClasses,
+};
 pub use crate::elements::paragraph::{Paragraph, ParagraphStyle};
+#[cfg(feature = "tessellation")]
+pub use crate::elements::plot::{Plot, PlotScale, PlotStyle, SeriesID, SeriesKind};
 pub use crate::elements::quad::QuadElement;
 pub use crate::elements::radio_button::{RadioButton, RadioButtonGroup, RadioButtonStyle};
+#[cfg(feature = "svg-icons")]
+pub use crate::elements::rating::{Rating, RatingStyle};
 pub use crate::elements::resize_handle::{ResizeHandle, ResizeHandleLayout, ResizeHandleStyle};
-pub use crate::elements::scroll_area::{ScrollArea, ScrollBarStyle};
+pub use crate::elements::scroll_area::{KineticScrollConfig, ScrollArea, ScrollBarStyle};
+pub use crate::elements::scrubber::{
+    default_scrubber_format, Scrubber, ScrubberStyle, ScrubberUpdate,
+};
+pub use crate::elements::segment_display::{
+    SegmentDisplay, SegmentDisplayMode, SegmentDisplayStyle,
+};
 pub use crate::elements::separator::{Separator, SeparatorSizeType, SeparatorStyle};
+#[cfg(feature = "custom-shaders")]
+pub use crate::elements::shader_quad::ShaderQuad;
+pub use crate::elements::status_bar::{StatusBar, StatusBarItemID, StatusBarSlot};
+pub use crate::elements::stepper::{Stepper, StepperStyle};
 pub use crate::elements::switch::{Switch, SwitchStyle};
 pub use crate::elements::tab::{IndicatorLinePlacement, Tab, TabGroup, TabGroupOption, TabStyle};
 pub use crate::elements::text_input::{
@@ -27,8 +66,10 @@ pub use crate::elements::text_input::{
 };
 #[cfg(feature = "svg-icons")]
 pub use crate::elements::text_input::{IconTextInput, IconTextInputStyle};
+pub use crate::elements::title_bar::{TitleBar, TitleBarStyle};
 pub use crate::elements::toggle_button::{ToggleButton, ToggleButtonStyle};
 pub use crate::elements::tooltip::{Tooltip, TooltipData, TooltipInner, TooltipStyle};
+pub use crate::elements::tuner::{Tuner, TunerMode, TunerStyle};
 #[cfg(feature = "tessellation")]
 pub use crate::elements::virtual_slider::knob::KnobMarkersArcStyle;
 pub use crate::elements::virtual_slider::knob::{
@@ -46,17 +87,24 @@ pub use crate::elements::virtual_slider::{
     param_normal_to_quantized, param_quantized_to_normal, AutomationInfo, GestureState,
     ParamElementTooltipInfo, ParamInfo, ParamMarker, ParamMarkersConfig, ParamOpenTextEntryInfo,
     ParamRightClickInfo, ParamUpdate, ParamValue, ParamerMarkerType, SteppedValue, VirtualSlider,
-    VirtualSliderConfig,
+    VirtualSliderConfig, VirtualSliderLink,
 };
 pub use crate::event::*;
+pub use crate::file_dialog::{FileDialogConfig, FileDialogKind, FileDialogResult, FileFilter};
+pub use crate::gui_state::GuiState;
 pub use crate::layout::*;
+pub use crate::locale::{BuiltinString, Locale, Translator};
 pub use crate::math::{
     degrees, point, radians, rect, size, vector, Angle, Box2D, PhysicalPoint, PhysicalPointI32,
     PhysicalPointU32, PhysicalRect, PhysicalRectI32, PhysicalRectU32, PhysicalSize,
     PhysicalSizeI32, PhysicalSizeU32, Point, PointI32, Rect, RectI32, Rotation, Scale, SideOffsets,
     Size, SizeI32, Transform, Translation, Vector, ZIndex,
 };
+pub use crate::param_store::{ParamBindingTarget, ParamDef, ParamEvent, ParamStore};
+pub use crate::shortcut::KeyChord;
+pub use crate::signal::{Bindings, Signal};
 pub use crate::style::*;
+pub use crate::undo::{UndoShortcut, UndoStack, UndoStep};
 pub use crate::vg::color::{
     self, gray, gray_a, hex, hex_a, rgb, rgba, BLACK, RGBA8, TRANSPARENT, WHITE,
 };
@@ -68,6 +116,7 @@ pub use crate::vg::text::{
     Style as FontStyle, TextProperties, Weight, Wrap,
 };
 pub use crate::vg::PrimitiveGroup;
+pub use crate::widget::{PartLayout, Widget, WidgetCore, WidgetPart};
 pub use crate::window::*;
 #[cfg(feature = "custom-shaders")]
 pub use crate::CustomPipelines;
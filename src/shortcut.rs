@@ -0,0 +1,242 @@
+//! Key chords for displaying keyboard shortcuts in menus and tooltips, and
+//! the registry of in-app keyboard shortcuts (see
+//! `AppContext::register_shortcut`).
+
+use std::fmt;
+
+use keyboard_types::{Code, KeyState, Modifiers};
+
+use crate::event::KeyboardEvent;
+
+/// A key combination, such as `Ctrl+S`.
+///
+/// When displayed, the chord is formatted with the conventions of the
+/// current platform (i.e. `⇧⌘S` on MacOS and `Ctrl+Shift+S` elsewhere).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyChord {
+    pub modifiers: Modifiers,
+    /// The physical key.
+    pub code: Code,
+}
+
+impl KeyChord {
+    pub const fn new(modifiers: Modifiers, code: Code) -> Self {
+        Self { modifiers, code }
+    }
+
+    /// A chord with the platform's primary command modifier (`Cmd` on MacOS,
+    /// `Ctrl` elsewhere).
+    pub const fn command(code: Code) -> Self {
+        Self::new(COMMAND_MODIFIER, code)
+    }
+
+    /// A chord with the platform's primary command modifier and `Shift`.
+    pub const fn command_shift(code: Code) -> Self {
+        Self::new(COMMAND_MODIFIER.union(Modifiers::SHIFT), code)
+    }
+
+    /// Returns `true` if the given modifiers and key match this chord
+    /// exactly.
+    pub fn matches(&self, modifiers: Modifiers, code: Code) -> bool {
+        self.code == code && self.modifiers == (modifiers & CHORD_MODIFIERS)
+    }
+}
+
+/// The in-app keyboard shortcuts registered with
+/// `AppContext::register_shortcut`.
+pub(crate) struct Shortcuts<A> {
+    registered: Vec<(KeyChord, A)>,
+}
+
+impl<A: Clone> Shortcuts<A> {
+    pub fn new() -> Self {
+        Self {
+            registered: Vec::new(),
+        }
+    }
+
+    /// Register the given chord, replacing the action of the chord if it was
+    /// already registered.
+    pub fn register(&mut self, chord: KeyChord, action: A) {
+        if let Some((_, registered_action)) = self.registered.iter_mut().find(|(c, _)| *c == chord)
+        {
+            *registered_action = action;
+        } else {
+            self.registered.push((chord, action));
+        }
+    }
+
+    /// Returns `false` if the chord was not registered.
+    pub fn unregister(&mut self, chord: KeyChord) -> bool {
+        let len = self.registered.len();
+        self.registered.retain(|(c, _)| *c != chord);
+        self.registered.len() != len
+    }
+
+    /// The action of the shortcut triggered by the given keyboard event (if
+    /// any).
+    pub fn action_for_event(&self, event: &KeyboardEvent) -> Option<A> {
+        if event.state != KeyState::Down {
+            return None;
+        }
+
+        self.registered
+            .iter()
+            .find(|(chord, _)| chord.matches(event.modifiers, event.code))
+            .map(|(_, action)| action.clone())
+    }
+}
+
+impl<A: Clone + PartialEq> Shortcuts<A> {
+    /// The chord of the first shortcut registered with the given action (if
+    /// any).
+    pub fn chord_for_action(&self, action: &A) -> Option<KeyChord> {
+        self.registered
+            .iter()
+            .find(|(_, registered_action)| registered_action == action)
+            .map(|(chord, _)| *chord)
+    }
+}
+
+#[cfg(target_os = "macos")]
+const COMMAND_MODIFIER: Modifiers = Modifiers::META;
+#[cfg(not(target_os = "macos"))]
+const COMMAND_MODIFIER: Modifiers = Modifiers::CONTROL;
+
+/// The modifiers which are considered when matching a chord. Lock keys are
+/// ignored.
+const CHORD_MODIFIERS: Modifiers = Modifiers::CONTROL
+    .union(Modifiers::ALT)
+    .union(Modifiers::SHIFT)
+    .union(Modifiers::META);
+
+impl fmt::Display for KeyChord {
+    #[cfg(target_os = "macos")]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // MacOS lists the modifiers in this order without any separators.
+        if self.modifiers.contains(Modifiers::CONTROL) {
+            f.write_str("\u{2303}")?;
+        }
+        if self.modifiers.contains(Modifiers::ALT) {
+            f.write_str("\u{2325}")?;
+        }
+        if self.modifiers.contains(Modifiers::SHIFT) {
+            f.write_str("\u{21E7}")?;
+        }
+        if self.modifiers.contains(Modifiers::META) {
+            f.write_str("\u{2318}")?;
+        }
+
+        write_key_name(self.code, f)
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        #[cfg(target_os = "windows")]
+        const META_NAME: &str = "Win";
+        #[cfg(not(target_os = "windows"))]
+        const META_NAME: &str = "Super";
+
+        for (modifier, name) in [
+            (Modifiers::CONTROL, "Ctrl"),
+            (Modifiers::ALT, "Alt"),
+            (Modifiers::SHIFT, "Shift"),
+            (Modifiers::META, META_NAME),
+        ] {
+            if self.modifiers.contains(modifier) {
+                write!(f, "{name}+")?;
+            }
+        }
+
+        write_key_name(self.code, f)
+    }
+}
+
+fn write_key_name(code: Code, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let name = match code {
+        Code::KeyA => "A",
+        Code::KeyB => "B",
+        Code::KeyC => "C",
+        Code::KeyD => "D",
+        Code::KeyE => "E",
+        Code::KeyF => "F",
+        Code::KeyG => "G",
+        Code::KeyH => "H",
+        Code::KeyI => "I",
+        Code::KeyJ => "J",
+        Code::KeyK => "K",
+        Code::KeyL => "L",
+        Code::KeyM => "M",
+        Code::KeyN => "N",
+        Code::KeyO => "O",
+        Code::KeyP => "P",
+        Code::KeyQ => "Q",
+        Code::KeyR => "R",
+        Code::KeyS => "S",
+        Code::KeyT => "T",
+        Code::KeyU => "U",
+        Code::KeyV => "V",
+        Code::KeyW => "W",
+        Code::KeyX => "X",
+        Code::KeyY => "Y",
+        Code::KeyZ => "Z",
+        Code::Digit0 | Code::Numpad0 => "0",
+        Code::Digit1 | Code::Numpad1 => "1",
+        Code::Digit2 | Code::Numpad2 => "2",
+        Code::Digit3 | Code::Numpad3 => "3",
+        Code::Digit4 | Code::Numpad4 => "4",
+        Code::Digit5 | Code::Numpad5 => "5",
+        Code::Digit6 | Code::Numpad6 => "6",
+        Code::Digit7 | Code::Numpad7 => "7",
+        Code::Digit8 | Code::Numpad8 => "8",
+        Code::Digit9 | Code::Numpad9 => "9",
+        Code::Minus | Code::NumpadSubtract => "-",
+        Code::Equal => "=",
+        Code::NumpadAdd => "+",
+        Code::NumpadMultiply => "*",
+        Code::NumpadDivide | Code::Slash => "/",
+        Code::BracketLeft => "[",
+        Code::BracketRight => "]",
+        Code::Backslash => "\\",
+        Code::Semicolon => ";",
+        Code::Quote => "'",
+        Code::Backquote => "`",
+        Code::Comma => ",",
+        Code::Period | Code::NumpadDecimal => ".",
+        Code::Space => "Space",
+        Code::ArrowUp => "\u{2191}",
+        Code::ArrowDown => "\u{2193}",
+        Code::ArrowLeft => "\u{2190}",
+        Code::ArrowRight => "\u{2192}",
+        #[cfg(target_os = "macos")]
+        Code::Enter | Code::NumpadEnter => "\u{21A9}",
+        #[cfg(target_os = "macos")]
+        Code::Backspace => "\u{232B}",
+        #[cfg(target_os = "macos")]
+        Code::Delete => "\u{2326}",
+        #[cfg(target_os = "macos")]
+        Code::Escape => "\u{238B}",
+        #[cfg(target_os = "macos")]
+        Code::Tab => "\u{21E5}",
+        #[cfg(not(target_os = "macos"))]
+        Code::Enter | Code::NumpadEnter => "Enter",
+        #[cfg(not(target_os = "macos"))]
+        Code::Backspace => "Backspace",
+        #[cfg(not(target_os = "macos"))]
+        Code::Delete => "Del",
+        #[cfg(not(target_os = "macos"))]
+        Code::Escape => "Esc",
+        #[cfg(not(target_os = "macos"))]
+        Code::Tab => "Tab",
+        Code::PageUp => "PgUp",
+        Code::PageDown => "PgDn",
+        Code::Home => "Home",
+        Code::End => "End",
+        Code::Insert => "Ins",
+        // Function keys and any other keys use the name of the key code
+        // (i.e. "F5").
+        code => return write!(f, "{code}"),
+    };
+
+    f.write_str(name)
+}
@@ -0,0 +1,240 @@
+//! Observable values which element handles can be bound to.
+//!
+//! Instead of diffing the application state and calling the matching setters
+//! on every handle after each action, the state can be stored in [`Signal`]s
+//! and each handle property bound to a signal once with [`Bindings::bind`].
+//! Calling [`Bindings::sync`] then only calls the setters of properties whose
+//! signals have changed.
+//!
+//! ```ignore
+//! struct MyApp {
+//!     volume: Signal<f64>,
+//!     muted: Signal<bool>,
+//!     view: MainView,
+//!     bindings: Bindings<MainView>,
+//! }
+//!
+//! bindings.bind(&volume, |view, v, _res| {
+//!     view.volume_knob.set_normal_value(*v);
+//! });
+//! bindings.bind(&muted, |view, muted, res| {
+//!     view.mute_btn.set_toggled(*muted);
+//!     view.status_label.set_text(Some(if *muted { "Muted" } else { "" }), res);
+//! });
+//!
+//! // In `on_action_emitted`:
+//! self.muted.set(true);
+//! self.bindings.sync(&mut self.view, &mut cx.res);
+//! ```
+
+use std::cell::{Cell, Ref, RefCell};
+use std::fmt::Debug;
+use std::rc::Rc;
+
+use crate::prelude::ResourceCtx;
+
+/// An observable value.
+///
+/// Cloning a signal creates another reference to the same value.
+pub struct Signal<T> {
+    shared: Rc<SignalShared<T>>,
+}
+
+struct SignalShared<T> {
+    value: RefCell<T>,
+    version: Cell<u64>,
+}
+
+impl<T> Signal<T> {
+    pub fn new(value: T) -> Self {
+        Self {
+            shared: Rc::new(SignalShared {
+                value: RefCell::new(value),
+                version: Cell::new(0),
+            }),
+        }
+    }
+
+    /// Get a copy of the current value.
+    pub fn get(&self) -> T
+    where
+        T: Clone,
+    {
+        RefCell::borrow(&self.shared.value).clone()
+    }
+
+    /// Borrow the current value.
+    pub fn borrow<'a>(&'a self) -> Ref<'a, T> {
+        RefCell::borrow(&self.shared.value)
+    }
+
+    /// Set the value.
+    ///
+    /// Returns `true` if the value has changed. Bound properties are only
+    /// updated if the value has changed.
+    pub fn set(&self, value: T) -> bool
+    where
+        T: PartialEq,
+    {
+        let mut v = RefCell::borrow_mut(&self.shared.value);
+
+        if *v != value {
+            *v = value;
+            self.mark_changed();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Modify the value in place. This always marks the signal as changed.
+    pub fn update<F: FnOnce(&mut T)>(&self, f: F) {
+        (f)(&mut RefCell::borrow_mut(&self.shared.value));
+        self.mark_changed();
+    }
+
+    /// A counter which is incremented every time the value changes.
+    pub fn version(&self) -> u64 {
+        self.shared.version.get()
+    }
+
+    /// Returns `true` if both signals reference the same value.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.shared, &other.shared)
+    }
+
+    fn mark_changed(&self) {
+        self.shared
+            .version
+            .set(self.shared.version.get().wrapping_add(1));
+    }
+}
+
+impl<T> Clone for Signal<T> {
+    fn clone(&self) -> Self {
+        Self {
+            shared: Rc::clone(&self.shared),
+        }
+    }
+}
+
+impl<T: Default> Default for Signal<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T: Debug> Debug for Signal<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Signal")
+            .field(&*RefCell::borrow(&self.shared.value))
+            .finish()
+    }
+}
+
+impl<T> From<T> for Signal<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+trait AnyBinding<V> {
+    fn sync(&mut self, view: &mut V, res: &mut ResourceCtx, force: bool) -> bool;
+}
+
+struct SignalBinding<T, F> {
+    signal: Signal<T>,
+    synced_version: Option<u64>,
+    apply: F,
+}
+
+impl<V, T, F> AnyBinding<V> for SignalBinding<T, F>
+where
+    F: FnMut(&mut V, &T, &mut ResourceCtx),
+{
+    fn sync(&mut self, view: &mut V, res: &mut ResourceCtx, force: bool) -> bool {
+        let version = self.signal.version();
+
+        if !force && self.synced_version == Some(version) {
+            return false;
+        }
+
+        self.synced_version = Some(version);
+        (self.apply)(view, &self.signal.borrow(), res);
+
+        true
+    }
+}
+
+/// A collection of bindings between [`Signal`]s and the properties of the
+/// element handles stored in `V` (i.e. a struct of handles declared with
+/// [`view!`](crate::view)).
+pub struct Bindings<V> {
+    bindings: Vec<Box<dyn AnyBinding<V>>>,
+}
+
+impl<V> Bindings<V> {
+    pub fn new() -> Self {
+        Self {
+            bindings: Vec::new(),
+        }
+    }
+
+    /// Bind a signal to one or more handle properties.
+    ///
+    /// `apply` is called with the current value on the next call to
+    /// [`Bindings::sync`], and again every time the value of the signal
+    /// changes after that.
+    pub fn bind<T, F>(&mut self, signal: &Signal<T>, apply: F)
+    where
+        T: 'static,
+        F: FnMut(&mut V, &T, &mut ResourceCtx) + 'static,
+    {
+        self.bindings.push(Box::new(SignalBinding {
+            signal: signal.clone(),
+            synced_version: None,
+            apply,
+        }));
+    }
+
+    /// Update all bound properties whose signals have changed since the last
+    /// sync.
+    ///
+    /// Returns `true` if any properties were updated.
+    pub fn sync(&mut self, view: &mut V, res: &mut ResourceCtx) -> bool {
+        self.sync_inner(view, res, false)
+    }
+
+    /// Update all bound properties, regardless of whether or not their
+    /// signals have changed (i.e. after the view was rebuilt).
+    pub fn sync_all(&mut self, view: &mut V, res: &mut ResourceCtx) {
+        self.sync_inner(view, res, true);
+    }
+
+    /// Remove all bindings.
+    pub fn clear(&mut self) {
+        self.bindings.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.bindings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bindings.is_empty()
+    }
+
+    fn sync_inner(&mut self, view: &mut V, res: &mut ResourceCtx, force: bool) -> bool {
+        let mut updated = false;
+        for binding in self.bindings.iter_mut() {
+            updated |= binding.sync(view, res, force);
+        }
+        updated
+    }
+}
+
+impl<V> Default for Bindings<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
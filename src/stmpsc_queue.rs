@@ -4,7 +4,10 @@ use std::rc::Rc;
 
 /// Construct a single-threaded only unbounded mpsc queue.
 pub(crate) fn single_thread_mpsc_queue<T>(initial_capacity: usize) -> (Sender<T>, Receiver<T>) {
-    let queue = Rc::new(RefCell::new(VecDeque::with_capacity(initial_capacity)));
+    let queue = Rc::new(RefCell::new(Queue {
+        msgs: VecDeque::with_capacity(initial_capacity),
+        next_seq: 0,
+    }));
 
     (
         Sender {
@@ -14,20 +17,38 @@ pub(crate) fn single_thread_mpsc_queue<T>(initial_capacity: usize) -> (Sender<T>
     )
 }
 
+struct Queue<T> {
+    /// The messages along with the sequence number they were sent with.
+    msgs: VecDeque<(u64, T)>,
+    next_seq: u64,
+}
+
+impl<T> Queue<T> {
+    fn next_seq(&mut self) -> u64 {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        seq
+    }
+}
+
 /// The sending end of a single-threaded only unbounded mpsc queue.
 pub(crate) struct Sender<T> {
-    queue: Rc<RefCell<VecDeque<T>>>,
+    queue: Rc<RefCell<Queue<T>>>,
 }
 
 impl<T> Sender<T> {
     #[inline]
     pub fn send(&mut self, msg: T) {
-        RefCell::borrow_mut(&self.queue).push_back(msg);
+        let mut queue = RefCell::borrow_mut(&self.queue);
+        let seq = queue.next_seq();
+        queue.msgs.push_back((seq, msg));
     }
 
     #[inline]
     pub fn send_to_front(&mut self, msg: T) {
-        RefCell::borrow_mut(&self.queue).push_front(msg);
+        let mut queue = RefCell::borrow_mut(&self.queue);
+        let seq = queue.next_seq();
+        queue.msgs.push_front((seq, msg));
     }
 }
 
@@ -41,12 +62,93 @@ impl<T> Clone for Sender<T> {
 
 /// The receiving end of a single-threaded only unbounded mpsc queue.
 pub(crate) struct Receiver<T> {
-    queue: Rc<RefCell<VecDeque<T>>>,
+    queue: Rc<RefCell<Queue<T>>>,
 }
 
 impl<T> Receiver<T> {
     #[inline]
     pub fn try_recv(&mut self) -> Option<T> {
-        RefCell::borrow_mut(&self.queue).pop_front()
+        RefCell::borrow_mut(&self.queue)
+            .msgs
+            .pop_front()
+            .map(|(_, msg)| msg)
+    }
+
+    /// The sequence number that the next message sent to this queue will
+    /// have. Sequence numbers always increase, even when messages are
+    /// received in between.
+    #[inline]
+    pub fn next_seq(&self) -> u64 {
+        RefCell::borrow(&self.queue).next_seq
+    }
+
+    /// Remove the messages still in the queue which were sent with a sequence
+    /// number of at least `since` and for which `f` returns `false`.
+    ///
+    /// The messages are visited from the back of the queue to the front.
+    pub fn retain_since_rev(&mut self, since: u64, mut f: impl FnMut(&T) -> bool) {
+        let mut queue = RefCell::borrow_mut(&self.queue);
+
+        let keep: Vec<bool> = queue
+            .msgs
+            .iter()
+            .rev()
+            .map(|(seq, msg)| *seq < since || f(msg))
+            .collect();
+
+        let mut keep = keep.into_iter().rev();
+        queue.msgs.retain(|_| keep.next().unwrap_or(true));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn drain(receiver: &mut Receiver<u32>) -> Vec<u32> {
+        std::iter::from_fn(|| receiver.try_recv()).collect()
+    }
+
+    #[test]
+    fn test_retain_since_after_receiving() {
+        let (mut sender, mut receiver) = single_thread_mpsc_queue(8);
+
+        sender.send(0);
+        let since = receiver.next_seq();
+        sender.send(1);
+        sender.send(2);
+
+        // Receive some of the messages in between, as happens when the queue
+        // is flushed during a batch.
+        assert_eq!(receiver.try_recv(), Some(0));
+        assert_eq!(receiver.try_recv(), Some(1));
+
+        sender.send(3);
+        sender.send(4);
+
+        receiver.retain_since_rev(since, |msg| *msg % 2 == 0);
+
+        assert_eq!(drain(&mut receiver), vec![2, 4]);
+    }
+
+    #[test]
+    fn test_retain_since_keeps_older_messages() {
+        let (mut sender, mut receiver) = single_thread_mpsc_queue(8);
+
+        sender.send(1);
+        sender.send(3);
+        let since = receiver.next_seq();
+        sender.send(5);
+        sender.send_to_front(7);
+        sender.send(8);
+
+        let mut visited = Vec::new();
+        receiver.retain_since_rev(since, |msg| {
+            visited.push(*msg);
+            *msg % 2 == 0
+        });
+
+        assert_eq!(visited, vec![8, 5, 7]);
+        assert_eq!(drain(&mut receiver), vec![1, 3, 8]);
     }
 }
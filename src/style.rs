@@ -1,23 +1,54 @@
 use rootvg::color;
-use rootvg::math::Rect;
+use rootvg::math::{Point, Rect, Size};
 use rootvg::quad::{QuadFlags, QuadPrimitive, SolidQuad};
+use rootvg::PrimitiveGroup;
+use smallvec::SmallVec;
 
 #[cfg(feature = "gradient")]
 use rootvg::quad::GradientQuad;
 
 use crate::prelude::ElementStyle;
-use crate::theme::DEFAULT_DISABLED_ALPHA_MULTIPLIER;
+use crate::theme::{DEFAULT_ACCENT_COLOR, DEFAULT_DISABLED_ALPHA_MULTIPLIER};
 use crate::vg::color::RGBA8;
 use crate::vg::quad::{Border, Radius};
 
 #[cfg(feature = "gradient")]
 use crate::vg::gradient::Gradient;
 
+mod disabled;
+mod font_fallback;
+mod gradient;
+mod icon_font;
+#[cfg(feature = "image")]
+mod image_atlas;
+#[cfg(feature = "image")]
+mod image_loader;
+#[cfg(feature = "image")]
+mod nine_slice;
 mod style_system;
+mod text_layout_cache;
+mod transition;
 
 pub type IconID = u16;
 
+pub use disabled::{desaturate, DisabledDerivation};
+pub use font_fallback::FontFallback;
+#[cfg(feature = "gradient")]
+pub use gradient::{background_linear_gradient, linear_gradient};
+pub use gradient::{background_radial_gradient, GradientStop, RadialGradient, MAX_GRADIENT_STOPS};
+pub use icon_font::{FontIcon, IconFontSystem};
+#[cfg(feature = "image")]
+pub use image_atlas::{ImageAtlasBuilder, ImageAtlasError, DEFAULT_IMAGE_ATLAS_SIZE};
+#[cfg(feature = "image")]
+pub use image_loader::{DecodedImage, ImageLoadError, ImageLoader};
+#[cfg(feature = "image")]
+pub use nine_slice::{background_nine_slice, ImageID, ImageSystem, NineSlice};
 pub use style_system::{ClassID, StyleSystem, CLASS_DEFAULT, CLASS_MENU, CLASS_PANEL};
+pub use text_layout_cache::{TextLayoutCache, DEFAULT_TEXT_LAYOUT_CACHE_CAPACITY};
+pub use transition::{
+    lerp_color, transition_ms, Easing, StyleTransitionState, StyleTransitions, Transition,
+    TransitionProgress,
+};
 
 /// The scale of an icon, used to make icons look more consistent.
 ///
@@ -53,7 +84,22 @@ pub struct BorderStyle {
     pub width: f32,
 
     /// The radius of the border in logical points.
+    ///
+    /// Each corner can have its own radius (i.e. to create joined button
+    /// groups with flat inner corners). See [`radius_left`], [`radius_right`],
+    /// [`radius_top`], and [`radius_bottom`] for helpers.
     pub radius: Radius,
+
+    /// Optional per-edge overrides for the width and color of the border.
+    ///
+    /// If any edge is overriden, then the border is drawn as four separate
+    /// straight lines instead of a single stroked outline, with edges that
+    /// are not overriden using `color` and `width`. Note the edges do not
+    /// follow the corner radius, so this works best when the corners
+    /// touching an overriden edge are square.
+    ///
+    /// By default this is set to `BorderEdges::NONE`.
+    pub edges: BorderEdges,
 }
 
 impl BorderStyle {
@@ -61,6 +107,7 @@ impl BorderStyle {
         color: rootvg::color::TRANSPARENT,
         width: 0.0,
         radius: Radius::ZERO,
+        edges: BorderEdges::NONE,
     };
 
     pub const fn new(color: RGBA8, width: f32, radius: Radius) -> Self {
@@ -68,6 +115,7 @@ impl BorderStyle {
             color,
             width,
             radius,
+            edges: BorderEdges::NONE,
         }
     }
 
@@ -76,14 +124,141 @@ impl BorderStyle {
             color: rootvg::color::TRANSPARENT,
             width: 0.0,
             radius,
+            edges: BorderEdges::NONE,
         }
     }
 
+    pub const fn with_edges(mut self, edges: BorderEdges) -> Self {
+        self.edges = edges;
+        self
+    }
+
+    pub fn is_transparent(&self) -> bool {
+        if self.edges.is_none() {
+            self.width == 0.0 || self.color == rootvg::color::TRANSPARENT
+        } else {
+            self.edge(Edge::Top).is_transparent()
+                && self.edge(Edge::Right).is_transparent()
+                && self.edge(Edge::Bottom).is_transparent()
+                && self.edge(Edge::Left).is_transparent()
+        }
+    }
+
+    /// The resolved width and color of the given edge.
+    pub fn edge(&self, edge: Edge) -> BorderEdge {
+        self.edges.get(edge).unwrap_or(BorderEdge {
+            color: self.color,
+            width: self.width,
+        })
+    }
+
+    pub fn multiply_alpha(&mut self, multiplier: f32) {
+        self.color = color::multiply_alpha(self.color, multiplier);
+        self.edges.multiply_alpha(multiplier);
+    }
+}
+
+/// An edge of a rectangle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Edge {
+    Top,
+    Right,
+    Bottom,
+    Left,
+}
+
+/// The width and color of a single edge of a border.
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BorderEdge {
+    /// The color of the edge.
+    pub color: RGBA8,
+
+    /// The width of the edge in logical points.
+    pub width: f32,
+}
+
+impl BorderEdge {
+    pub const NONE: Self = Self {
+        color: rootvg::color::TRANSPARENT,
+        width: 0.0,
+    };
+
+    pub const fn new(color: RGBA8, width: f32) -> Self {
+        Self { color, width }
+    }
+
     pub fn is_transparent(&self) -> bool {
         self.width == 0.0 || self.color == rootvg::color::TRANSPARENT
     }
 }
 
+/// Per-edge overrides for a [`BorderStyle`].
+///
+/// An edge set to `None` uses the width and color of the [`BorderStyle`].
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BorderEdges {
+    pub top: Option<BorderEdge>,
+    pub right: Option<BorderEdge>,
+    pub bottom: Option<BorderEdge>,
+    pub left: Option<BorderEdge>,
+}
+
+impl BorderEdges {
+    pub const NONE: Self = Self {
+        top: None,
+        right: None,
+        bottom: None,
+        left: None,
+    };
+
+    /// Returns `true` if no edges are overriden.
+    pub fn is_none(&self) -> bool {
+        self.top.is_none() && self.right.is_none() && self.bottom.is_none() && self.left.is_none()
+    }
+
+    pub fn get(&self, edge: Edge) -> Option<BorderEdge> {
+        match edge {
+            Edge::Top => self.top,
+            Edge::Right => self.right,
+            Edge::Bottom => self.bottom,
+            Edge::Left => self.left,
+        }
+    }
+
+    /// Returns a copy with the given function applied to the color of every
+    /// overriden edge.
+    pub fn map_colors(&self, f: impl Fn(RGBA8) -> RGBA8) -> Self {
+        let map = |e: Option<BorderEdge>| {
+            e.map(|e| BorderEdge {
+                color: (f)(e.color),
+                width: e.width,
+            })
+        };
+
+        Self {
+            top: map(self.top),
+            right: map(self.right),
+            bottom: map(self.bottom),
+            left: map(self.left),
+        }
+    }
+
+    pub fn multiply_alpha(&mut self, multiplier: f32) {
+        for e in [
+            &mut self.top,
+            &mut self.right,
+            &mut self.bottom,
+            &mut self.left,
+        ] {
+            if let Some(e) = e {
+                e.color = color::multiply_alpha(e.color, multiplier);
+            }
+        }
+    }
+}
+
 /// An alias for `BorderStyle::new(color, width, radius)`
 pub const fn border(color: RGBA8, width: f32, radius: Radius) -> BorderStyle {
     BorderStyle::new(color, width, radius)
@@ -94,6 +269,43 @@ pub const fn border_radius_only(radius: Radius) -> BorderStyle {
     BorderStyle::from_radius(radius)
 }
 
+/// A radius with a different value for each corner.
+pub const fn radius_corners(
+    top_left: f32,
+    top_right: f32,
+    bottom_right: f32,
+    bottom_left: f32,
+) -> Radius {
+    Radius {
+        top_left,
+        top_right,
+        bottom_right,
+        bottom_left,
+    }
+}
+
+/// A radius with only the left corners rounded (i.e. the first button in
+/// a horizontal button group).
+pub const fn radius_left(radius: f32) -> Radius {
+    radius_corners(radius, 0.0, 0.0, radius)
+}
+
+/// A radius with only the right corners rounded (i.e. the last button in
+/// a horizontal button group).
+pub const fn radius_right(radius: f32) -> Radius {
+    radius_corners(0.0, radius, radius, 0.0)
+}
+
+/// A radius with only the top corners rounded (i.e. a tab).
+pub const fn radius_top(radius: f32) -> Radius {
+    radius_corners(radius, radius, 0.0, 0.0)
+}
+
+/// A radius with only the bottom corners rounded.
+pub const fn radius_bottom(radius: f32) -> Radius {
+    radius_corners(0.0, 0.0, radius, radius)
+}
+
 /*
 #[derive(Default, Debug, Clone, Copy, PartialEq)]
 pub struct ShadowStyle {
@@ -140,6 +352,42 @@ impl Default for QuadStyle {
     }
 }
 
+/// The primitives drawn on top of a quad, created with
+/// [`QuadStyle::create_overlay_primitives`].
+#[derive(Debug, Clone)]
+pub struct QuadOverlay {
+    radial: Option<RadialOverlay>,
+    edges: SmallVec<[QuadPrimitive; 4]>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct RadialOverlay {
+    gradient: RadialGradient,
+    bounds: Rect,
+    inset: f32,
+    corner_radius: Radius,
+    flags: QuadFlags,
+}
+
+impl QuadOverlay {
+    /// Add these primitives to the given primitive group.
+    pub fn add_to(self, primitives: &mut PrimitiveGroup) {
+        if let Some(radial) = self.radial {
+            radial.gradient.add_primitives(
+                radial.bounds,
+                radial.inset,
+                radial.corner_radius,
+                radial.flags,
+                primitives,
+            );
+        }
+
+        for p in self.edges {
+            primitives.add(p);
+        }
+    }
+}
+
 impl QuadStyle {
     pub const TRANSPARENT: Self = Self {
         bg: Background::Solid(rootvg::color::TRANSPARENT),
@@ -163,14 +411,35 @@ impl QuadStyle {
         self.bg.is_transparent() && self.border.is_transparent()
     }
 
+    /// Create the primitive for this quad.
     pub fn create_primitive(&self, bounds: Rect) -> QuadPrimitive {
-        match &self.bg {
+        self.create_primitive_with_opacity(bounds, 1.0)
+    }
+
+    /// Create the primitive for this quad with the given opacity applied.
+    ///
+    /// * `opacity` - The opacity of the element in the range `[0.0, 1.0]`
+    /// which is multiplied into the colors (see `RenderContext::opacity`).
+    pub fn create_primitive_with_opacity(&self, bounds: Rect, opacity: f32) -> QuadPrimitive {
+        let style = self.with_opacity(opacity);
+
+        // Per-edge borders are drawn separately in `create_edge_primitives`.
+        let border = if style.border.edges.is_none() {
+            style.border.into()
+        } else {
+            Border {
+                width: 0.0,
+                ..style.border.into()
+            }
+        };
+
+        match &style.bg {
             Background::Solid(bg_color) => QuadPrimitive::Solid(
                 SolidQuad {
                     bounds,
                     bg_color: (*bg_color).into(),
-                    border: self.border.into(),
-                    flags: self.flags,
+                    border,
+                    flags: style.flags,
                     //shadow: self.shadow.into(),
                 }
                 .into(),
@@ -180,22 +449,246 @@ impl QuadStyle {
                 GradientQuad {
                     bounds,
                     bg_gradient: *bg_gradient,
-                    border: self.border.into(),
-                    flags: self.flags,
+                    border,
+                    flags: style.flags,
+                }
+                .into(),
+            ),
+            // The gradient is drawn separately in `create_overlay_primitives`.
+            // When it is tessellated it covers the whole quad, otherwise the
+            // area outside of its radius is filled here.
+            Background::Radial(bg_gradient) => QuadPrimitive::Solid(
+                SolidQuad {
+                    bounds,
+                    bg_color: if cfg!(feature = "tessellation") {
+                        rootvg::color::TRANSPARENT
+                    } else {
+                        bg_gradient.outer_color()
+                    }
+                    .into(),
+                    border,
+                    flags: style.flags,
+                }
+                .into(),
+            ),
+            // The image is drawn separately in `create_image_primitives`.
+            #[cfg(feature = "image")]
+            Background::NineSlice(_) => QuadPrimitive::Solid(
+                SolidQuad {
+                    bounds,
+                    bg_color: rootvg::color::TRANSPARENT.into(),
+                    border,
+                    flags: style.flags,
                 }
                 .into(),
             ),
         }
     }
 
+    /// Create the image primitives for a [`NineSlice`] background.
+    ///
+    /// Returns an empty list if the background is not a nine-slice or its
+    /// image is not registered in `images`.
+    #[cfg(feature = "image")]
+    pub fn create_image_primitives(
+        &self,
+        bounds: Rect,
+        images: &ImageSystem,
+    ) -> SmallVec<[rootvg::image::ImagePrimitive; 9]> {
+        if let Background::NineSlice(nine_slice) = &self.bg {
+            nine_slice.create_primitives(bounds, images)
+        } else {
+            SmallVec::new()
+        }
+    }
+
+    /// Add the image primitives for a [`NineSlice`] background (if any) to the
+    /// given primitive group.
+    #[cfg(feature = "image")]
+    pub fn add_image_primitives(
+        &self,
+        bounds: Rect,
+        images: &ImageSystem,
+        primitives: &mut PrimitiveGroup,
+    ) {
+        for p in self.create_image_primitives(bounds, images) {
+            primitives.add(p);
+        }
+    }
+
+    /// Create the primitives that are drawn on top of the primitive returned
+    /// by [`QuadStyle::create_primitive`] (a radial gradient background and
+    /// any per-edge borders).
+    pub fn create_overlay_primitives(&self, bounds: Rect) -> QuadOverlay {
+        self.create_overlay_primitives_with_opacity(bounds, 1.0)
+    }
+
+    /// Create the primitives that are drawn on top of the primitive returned
+    /// by [`QuadStyle::create_primitive_with_opacity`], with the given opacity
+    /// applied.
+    pub fn create_overlay_primitives_with_opacity(
+        &self,
+        bounds: Rect,
+        opacity: f32,
+    ) -> QuadOverlay {
+        let style = self.with_opacity(opacity);
+
+        let radial = if let Background::Radial(bg_gradient) = &style.bg {
+            let inset = if style.border.edges.is_none() {
+                style.border.width
+            } else {
+                [Edge::Top, Edge::Right, Edge::Bottom, Edge::Left]
+                    .iter()
+                    .map(|e| style.border.edge(*e).width)
+                    .fold(0.0, f32::max)
+            };
+
+            Some(RadialOverlay {
+                gradient: *bg_gradient,
+                bounds,
+                inset,
+                corner_radius: style.border.radius,
+                flags: style.flags,
+            })
+        } else {
+            None
+        };
+
+        QuadOverlay {
+            radial,
+            edges: style.edge_primitives(bounds),
+        }
+    }
+
+    /// Create the primitives for the per-edge borders (if any edges are
+    /// overriden in [`BorderStyle::edges`]).
+    ///
+    /// These should be added on top of the primitive returned by
+    /// [`QuadStyle::create_primitive`].
+    pub fn create_edge_primitives(&self, bounds: Rect) -> SmallVec<[QuadPrimitive; 4]> {
+        self.edge_primitives(bounds)
+    }
+
+    /// Create the primitives for the per-edge borders with the given opacity
+    /// applied.
+    pub fn create_edge_primitives_with_opacity(
+        &self,
+        bounds: Rect,
+        opacity: f32,
+    ) -> SmallVec<[QuadPrimitive; 4]> {
+        self.with_opacity(opacity).edge_primitives(bounds)
+    }
+
+    fn edge_primitives(&self, bounds: Rect) -> SmallVec<[QuadPrimitive; 4]> {
+        let mut primitives = SmallVec::new();
+
+        if self.border.edges.is_none() {
+            return primitives;
+        }
+
+        let top = self.border.edge(Edge::Top);
+        let right = self.border.edge(Edge::Right);
+        let bottom = self.border.edge(Edge::Bottom);
+        let left = self.border.edge(Edge::Left);
+
+        let mut add_edge = |edge: BorderEdge, rect: Rect| {
+            if !edge.is_transparent() {
+                primitives.push(QuadPrimitive::Solid(
+                    SolidQuad {
+                        bounds: rect,
+                        bg_color: edge.color.into(),
+                        border: Border::default(),
+                        flags: self.flags,
+                    }
+                    .into(),
+                ));
+            }
+        };
+
+        // The top and bottom edges span the full width, and the left and right
+        // edges fill the space in between so that overlapping corners are not
+        // drawn twice.
+        add_edge(
+            top,
+            Rect::new(bounds.origin, Size::new(bounds.width(), top.width)),
+        );
+        add_edge(
+            bottom,
+            Rect::new(
+                Point::new(bounds.min_x(), bounds.max_y() - bottom.width),
+                Size::new(bounds.width(), bottom.width),
+            ),
+        );
+
+        let side_height = (bounds.height() - top.width - bottom.width).max(0.0);
+        add_edge(
+            left,
+            Rect::new(
+                Point::new(bounds.min_x(), bounds.min_y() + top.width),
+                Size::new(left.width, side_height),
+            ),
+        );
+        add_edge(
+            right,
+            Rect::new(
+                Point::new(bounds.max_x() - right.width, bounds.min_y() + top.width),
+                Size::new(right.width, side_height),
+            ),
+        );
+
+        primitives
+    }
+
+    /// Returns `true` if this quad completely fills its bounds with opaque
+    /// content, meaning it has a solid opaque background and square corners
+    /// (see `ElementFlags::OPAQUE`).
+    pub fn is_opaque(&self) -> bool {
+        let Background::Solid(bg) = self.bg else {
+            return false;
+        };
+
+        let r = self.border.radius;
+        let square_corners = r.top_left <= 0.0
+            && r.top_right <= 0.0
+            && r.bottom_right <= 0.0
+            && r.bottom_left <= 0.0;
+
+        bg.a == 255 && square_corners
+    }
+
+    /// Add the primitives for this quad (including any radial gradient rings
+    /// and per-edge borders) to the given primitive group.
+    pub fn add_primitives(&self, bounds: Rect, primitives: &mut PrimitiveGroup) {
+        self.add_primitives_with_opacity(bounds, 1.0, primitives);
+    }
+
+    /// Add the primitives for this quad to the given primitive group with the
+    /// given opacity applied.
+    pub fn add_primitives_with_opacity(
+        &self,
+        bounds: Rect,
+        opacity: f32,
+        primitives: &mut PrimitiveGroup,
+    ) {
+        primitives.add(self.create_primitive_with_opacity(bounds, opacity));
+        self.create_overlay_primitives_with_opacity(bounds, opacity)
+            .add_to(primitives);
+    }
+
     pub fn multiply_alpha(&mut self, multiplier: f32) {
-        match &mut self.bg {
-            Background::Solid(c) => *c = color::multiply_alpha(*c, multiplier),
-            #[cfg(feature = "gradient")]
-            Background::Gradient(g) => g.multiply_alpha(multiplier),
+        self.bg.multiply_alpha(multiplier);
+        self.border.multiply_alpha(multiplier);
+    }
+
+    /// A copy of this style with the given opacity applied.
+    fn with_opacity(&self, opacity: f32) -> Self {
+        let mut style = *self;
+
+        if opacity < 1.0 {
+            style.multiply_alpha(opacity);
         }
 
-        self.border.color = color::multiply_alpha(self.border.color, multiplier);
+        style
     }
 }
 
@@ -203,11 +696,100 @@ impl ElementStyle for QuadStyle {
     const ID: &'static str = "qd";
 }
 
+/// Multiply the alpha of the given color by the opacity of an element (see
+/// `RenderContext::opacity`).
+///
+/// The `_with_opacity` methods of [`QuadStyle`] and [`FocusRingStyle`]
+/// already apply the opacity, so this only needs to be used for colors
+/// which are passed directly to primitives (i.e. the color of a
+/// `TextPrimitive`).
+pub fn apply_opacity(c: RGBA8, opacity: f32) -> RGBA8 {
+    if opacity < 1.0 {
+        color::multiply_alpha(c, opacity)
+    } else {
+        c
+    }
+}
+
 /// An alias for `QuadStyle::new(color, width, radius)`
 pub const fn quad_style(bg: Background, border: BorderStyle) -> QuadStyle {
     QuadStyle::new(bg, border)
 }
 
+/// The style of the ring drawn around an element when it has keyboard focus.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FocusRingStyle {
+    /// The color of the ring.
+    pub color: RGBA8,
+    /// The width of the ring in logical points.
+    ///
+    /// By default this is set to `2.0`.
+    pub width: f32,
+    /// The distance between the ring and the bounds of the element in
+    /// logical points.
+    ///
+    /// By default this is set to `2.0`.
+    pub offset: f32,
+}
+
+impl Default for FocusRingStyle {
+    fn default() -> Self {
+        Self {
+            color: DEFAULT_ACCENT_COLOR,
+            width: 2.0,
+            offset: 2.0,
+        }
+    }
+}
+
+impl FocusRingStyle {
+    /// Create the primitive for a focus ring around the given bounds.
+    ///
+    /// * `radius` - The border radius of the element. The radius of the ring
+    /// is expanded by `offset` so that it stays concentric with the element.
+    pub fn create_primitive(&self, bounds: Rect, radius: Radius) -> QuadPrimitive {
+        self.create_primitive_with_opacity(bounds, radius, 1.0)
+    }
+
+    /// Create the primitive for a focus ring around the given bounds with the
+    /// given opacity applied.
+    ///
+    /// * `opacity` - The opacity of the element (see `RenderContext::opacity`).
+    pub fn create_primitive_with_opacity(
+        &self,
+        bounds: Rect,
+        radius: Radius,
+        opacity: f32,
+    ) -> QuadPrimitive {
+        let o = self.offset + self.width;
+
+        let expand = |r: f32| if r > 0.0 { r + o } else { 0.0 };
+
+        QuadPrimitive::Solid(
+            SolidQuad {
+                bounds: Rect::new(
+                    Point::new(bounds.min_x() - o, bounds.min_y() - o),
+                    Size::new(bounds.width() + (o * 2.0), bounds.height() + (o * 2.0)),
+                ),
+                bg_color: rootvg::color::TRANSPARENT.into(),
+                border: Border {
+                    color: apply_opacity(self.color, opacity).into(),
+                    width: self.width,
+                    radius: radius_corners(
+                        expand(radius.top_left),
+                        expand(radius.top_right),
+                        expand(radius.bottom_right),
+                        expand(radius.bottom_left),
+                    ),
+                },
+                flags: QuadFlags::SNAP_ALL_TO_NEAREST_PIXEL,
+            }
+            .into(),
+        )
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum QuadStyleDisabled {
     /// Use a multipler on the alpha channel for all colors.
@@ -229,17 +811,23 @@ pub enum Background {
     Solid(RGBA8),
     #[cfg(feature = "gradient")]
     Gradient(Gradient),
+    Radial(RadialGradient),
+    /// A bitmap image stretched over the quad (see [`NineSlice`]).
+    #[cfg(feature = "image")]
+    NineSlice(NineSlice),
 }
 
 impl Background {
     pub const TRANSPARENT: Self = Self::Solid(rootvg::color::TRANSPARENT);
 
     pub fn is_transparent(&self) -> bool {
-        #[allow(irrefutable_let_patterns)]
-        if let Self::Solid(color) = self {
-            *color == rootvg::color::TRANSPARENT
-        } else {
-            false
+        match self {
+            Self::Solid(color) => *color == rootvg::color::TRANSPARENT,
+            #[cfg(feature = "gradient")]
+            Self::Gradient(_) => false,
+            Self::Radial(g) => g.is_transparent(),
+            #[cfg(feature = "image")]
+            Self::NineSlice(_) => false,
         }
     }
 
@@ -248,6 +836,10 @@ impl Background {
             Self::Solid(c) => *c = color::multiply_alpha(*c, multiplier),
             #[cfg(feature = "gradient")]
             Self::Gradient(g) => g.multiply_alpha(multiplier),
+            Self::Radial(g) => g.multiply_alpha(multiplier),
+            // Images cannot be tinted.
+            #[cfg(feature = "image")]
+            Self::NineSlice(_) => {}
         }
     }
 }
@@ -306,26 +898,45 @@ pub const fn background_gray(v: u8) -> Background {
 /// How to style a color property when an element is disabled.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum DisabledColor {
+    /// Derive the color from the property color using the style system's
+    /// [`DisabledDerivation`] (see [`StyleSystem::set_auto_disabled`]).
+    ///
+    /// If automatic derivation is not enabled, then this is the same as
+    /// `DisabledColor::AlphaMultiplier(DEFAULT_DISABLED_ALPHA_MULTIPLIER)`.
+    Auto,
     /// Use a multiplier on the alpha channel of the property color.
     AlphaMultiplier(f32),
     /// Override the poperty color with a custom color.
     Custom(RGBA8),
+    /// Derive the color from the property color using the given derivation.
+    Derive(DisabledDerivation),
 }
 
 impl DisabledColor {
     pub fn get(&self, property_color: RGBA8) -> RGBA8 {
         match self {
+            DisabledColor::Auto => {
+                color::multiply_alpha(property_color, DEFAULT_DISABLED_ALPHA_MULTIPLIER)
+            }
             DisabledColor::AlphaMultiplier(multiplier) => {
                 color::multiply_alpha(property_color, *multiplier)
             }
             DisabledColor::Custom(color) => *color,
+            DisabledColor::Derive(d) => d.apply_color(property_color),
+        }
+    }
+
+    /// Replace `DisabledColor::Auto` with `DisabledColor::Derive(derivation)`.
+    pub fn derive_auto(&mut self, derivation: DisabledDerivation) {
+        if let DisabledColor::Auto = self {
+            *self = DisabledColor::Derive(derivation);
         }
     }
 }
 
 impl Default for DisabledColor {
     fn default() -> Self {
-        Self::AlphaMultiplier(DEFAULT_DISABLED_ALPHA_MULTIPLIER)
+        Self::Auto
     }
 }
 
@@ -333,22 +944,51 @@ impl Default for DisabledColor {
 /// How to style a gradient property when an element is disabled.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum DisabledGradient {
+    /// Derive the gradient from the property gradient using the style
+    /// system's [`DisabledDerivation`] (see [`StyleSystem::set_auto_disabled`]).
+    ///
+    /// If automatic derivation is not enabled, then this is the same as
+    /// `DisabledGradient::AlphaMultiplier(DEFAULT_DISABLED_ALPHA_MULTIPLIER)`.
+    Auto,
     /// Use a multiplier on the alpha channels of the property gradient.
     AlphaMultiplier(f32),
     /// Override the poperty gradient with a custom gradient.
     Custom(Gradient),
+    /// Derive the gradient from the property gradient using the given
+    /// derivation.
+    Derive(DisabledDerivation),
 }
 
 #[cfg(feature = "gradient")]
 impl DisabledGradient {
     pub fn get(&self, property_gradient: Gradient) -> Gradient {
         match self {
+            DisabledGradient::Auto => {
+                let mut g = property_gradient;
+                g.multiply_alpha(DEFAULT_DISABLED_ALPHA_MULTIPLIER);
+                g
+            }
             DisabledGradient::AlphaMultiplier(multiplier) => {
                 let mut g = property_gradient;
                 g.multiply_alpha(*multiplier);
                 g
             }
             DisabledGradient::Custom(g) => *g,
+            // Gradient stops cannot be desaturated, so only the alpha is
+            // reduced.
+            DisabledGradient::Derive(d) => {
+                let mut g = property_gradient;
+                g.multiply_alpha(d.alpha_multiplier);
+                g
+            }
+        }
+    }
+
+    /// Replace `DisabledGradient::Auto` with
+    /// `DisabledGradient::Derive(derivation)`.
+    pub fn derive_auto(&mut self, derivation: DisabledDerivation) {
+        if let DisabledGradient::Auto = self {
+            *self = DisabledGradient::Derive(derivation);
         }
     }
 }
@@ -356,35 +996,58 @@ impl DisabledGradient {
 #[cfg(feature = "gradient")]
 impl Default for DisabledGradient {
     fn default() -> Self {
-        Self::AlphaMultiplier(DEFAULT_DISABLED_ALPHA_MULTIPLIER)
+        Self::Auto
     }
 }
 
 /// How to style a background property when an element is disabled.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum DisabledBackground {
+    /// Derive the background from the property background using the style
+    /// system's [`DisabledDerivation`] (see [`StyleSystem::set_auto_disabled`]).
+    ///
+    /// If automatic derivation is not enabled, then this is the same as
+    /// `DisabledBackground::AlphaMultiplier(DEFAULT_DISABLED_ALPHA_MULTIPLIER)`.
+    Auto,
     /// Use a multiplier on the alpha channels of the property background.
     AlphaMultiplier(f32),
     /// Override the poperty background with a custom background.
     Custom(Background),
+    /// Derive the background from the property background using the given
+    /// derivation.
+    Derive(DisabledDerivation),
 }
 
 impl DisabledBackground {
     pub fn get(&self, property_bg: Background) -> Background {
         match self {
+            DisabledBackground::Auto => {
+                let mut bg = property_bg;
+                bg.multiply_alpha(DEFAULT_DISABLED_ALPHA_MULTIPLIER);
+                bg
+            }
             DisabledBackground::AlphaMultiplier(multiplier) => {
                 let mut bg = property_bg;
                 bg.multiply_alpha(*multiplier);
                 bg
             }
             DisabledBackground::Custom(bg) => *bg,
+            DisabledBackground::Derive(d) => d.apply_background(property_bg),
+        }
+    }
+
+    /// Replace `DisabledBackground::Auto` with
+    /// `DisabledBackground::Derive(derivation)`.
+    pub fn derive_auto(&mut self, derivation: DisabledDerivation) {
+        if let DisabledBackground::Auto = self {
+            *self = DisabledBackground::Derive(derivation);
         }
     }
 }
 
 impl Default for DisabledBackground {
     fn default() -> Self {
-        Self::AlphaMultiplier(DEFAULT_DISABLED_ALPHA_MULTIPLIER)
+        Self::Auto
     }
 }
 
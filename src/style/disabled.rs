@@ -0,0 +1,74 @@
+use rootvg::color::{self, RGBA8};
+
+use crate::theme::DEFAULT_DISABLED_ALPHA_MULTIPLIER;
+
+use super::Background;
+
+/// How to automatically derive the disabled state of a style from its idle
+/// state.
+///
+/// This is used by any style property set to `DisabledColor::Auto` or
+/// `DisabledBackground::Auto` (the default) when enabled with
+/// [`StyleSystem::set_auto_disabled`](super::StyleSystem::set_auto_disabled).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DisabledDerivation {
+    /// How much to desaturate colors in the range `[0.0, 1.0]`, where `0.0`
+    /// leaves colors untouched and `1.0` turns them fully gray.
+    ///
+    /// By default this is set to `0.6`.
+    pub desaturate: f32,
+    /// The multiplier applied to the alpha channel of colors.
+    ///
+    /// By default this is set to `DEFAULT_DISABLED_ALPHA_MULTIPLIER`.
+    pub alpha_multiplier: f32,
+}
+
+impl Default for DisabledDerivation {
+    fn default() -> Self {
+        Self {
+            desaturate: 0.6,
+            alpha_multiplier: DEFAULT_DISABLED_ALPHA_MULTIPLIER,
+        }
+    }
+}
+
+impl DisabledDerivation {
+    pub fn apply_color(&self, c: RGBA8) -> RGBA8 {
+        color::multiply_alpha(desaturate(c, self.desaturate), self.alpha_multiplier)
+    }
+
+    pub fn apply_background(&self, bg: Background) -> Background {
+        match bg {
+            Background::Solid(c) => Background::Solid(self.apply_color(c)),
+            // Gradient stops cannot be desaturated, so only the alpha is
+            // reduced.
+            #[cfg(feature = "gradient")]
+            Background::Gradient(mut g) => {
+                g.multiply_alpha(self.alpha_multiplier);
+                Background::Gradient(g)
+            }
+            Background::Radial(mut g) => {
+                for stop in g.stops.iter_mut().flatten() {
+                    stop.color = self.apply_color(stop.color);
+                }
+                Background::Radial(g)
+            }
+            #[cfg(feature = "image")]
+            Background::NineSlice(n) => Background::NineSlice(n),
+        }
+    }
+}
+
+/// Desaturate a color by the given amount in the range `[0.0, 1.0]`.
+pub fn desaturate(c: RGBA8, amount: f32) -> RGBA8 {
+    let amount = amount.clamp(0.0, 1.0);
+    if amount == 0.0 {
+        return c;
+    }
+
+    let luma = (0.299 * c.r as f32) + (0.587 * c.g as f32) + (0.114 * c.b as f32);
+    let l = |v: u8| (v as f32 + ((luma - v as f32) * amount)).round() as u8;
+
+    RGBA8::new(l(c.r), l(c.g), l(c.b), c.a)
+}
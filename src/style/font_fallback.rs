@@ -0,0 +1,132 @@
+use rootvg::text::glyphon::fontdb::{Family, Query, ID};
+use rootvg::text::{FontSystem, TextProperties};
+use rustc_hash::FxHashMap;
+
+/// Chains of fallback fonts which are used when the primary font of some
+/// text does not contain all of its characters (i.e. CJK characters or
+/// symbols).
+///
+/// Without a chain, missing characters fall back to whichever system font
+/// happens to contain them (or are drawn as "tofu" boxes if none do). With a
+/// chain, the first font in the chain which contains the missing characters
+/// is used as the primary font of the text instead.
+///
+/// A chain can be set for a specific primary font family, so styles can
+/// override the default chain by selecting a primary font which has its
+/// own chain.
+#[derive(Default, Debug, Clone)]
+pub struct FontFallback {
+    default_chain: Vec<&'static str>,
+    chains: FxHashMap<&'static str, Vec<&'static str>>,
+}
+
+impl FontFallback {
+    /// The chain used for text whose primary font family has no chain of
+    /// its own.
+    pub fn default_chain(&self) -> &[&'static str] {
+        &self.default_chain
+    }
+
+    /// The chain used for text whose primary font is the given family, if
+    /// one was set.
+    pub fn chain_for(&self, primary_family: &str) -> Option<&[&'static str]> {
+        self.chains.get(primary_family).map(|c| c.as_slice())
+    }
+
+    pub(crate) fn set_default_chain(&mut self, chain: Vec<&'static str>) {
+        self.default_chain = chain;
+    }
+
+    pub(crate) fn set_chain_for(&mut self, primary_family: &'static str, chain: Vec<&'static str>) {
+        if chain.is_empty() {
+            self.chains.remove(primary_family);
+        } else {
+            self.chains.insert(primary_family, chain);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.default_chain.is_empty() && self.chains.is_empty()
+    }
+
+    /// Returns the text properties to shape the given text with, or `None`
+    /// if the primary font can be used as is.
+    pub(crate) fn resolve(
+        &self,
+        text: &str,
+        properties: &TextProperties,
+        font_system: &mut FontSystem,
+    ) -> Option<TextProperties> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let attrs = &properties.attrs;
+
+        let chain = match attrs.family {
+            Family::Name(name) => self
+                .chain_for(name)
+                .unwrap_or(self.default_chain.as_slice()),
+            _ => &self.default_chain,
+        };
+        if chain.is_empty() {
+            return None;
+        }
+
+        let query_face = |font_system: &FontSystem, family: Family| {
+            font_system.db().query(&Query {
+                families: &[family],
+                weight: attrs.weight,
+                stretch: attrs.stretch,
+                style: attrs.style,
+            })
+        };
+
+        let primary = query_face(font_system, attrs.family);
+
+        let missing: Vec<char> = text
+            .chars()
+            .filter(|c| !c.is_whitespace() && !c.is_control())
+            .filter(|c| !primary.is_some_and(|id| face_contains(font_system, id, *c)))
+            .collect();
+        if missing.is_empty() {
+            return None;
+        }
+
+        // Use the first font in the chain which contains all of the missing
+        // characters, or else the one which contains the most of them.
+        let mut best: Option<(&'static str, usize)> = None;
+        for &family_name in chain.iter() {
+            let Some(id) = query_face(font_system, Family::Name(family_name)) else {
+                continue;
+            };
+
+            let num_found = missing
+                .iter()
+                .filter(|c| face_contains(font_system, id, **c))
+                .count();
+
+            if num_found == missing.len() {
+                best = Some((family_name, num_found));
+                break;
+            }
+
+            if num_found > best.map(|(_, n)| n).unwrap_or(0) {
+                best = Some((family_name, num_found));
+            }
+        }
+
+        best.map(|(family_name, _)| {
+            let mut properties = properties.clone();
+            properties.attrs = properties.attrs.family(Family::Name(family_name));
+            properties
+        })
+    }
+}
+
+fn face_contains(font_system: &mut FontSystem, id: ID, c: char) -> bool {
+    font_system
+        .get_font(id)
+        .map(|font| font.as_swash().charmap().map(c) != 0)
+        .unwrap_or(false)
+}
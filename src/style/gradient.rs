@@ -0,0 +1,530 @@
+use rootvg::color::{self, RGBA8};
+use rootvg::math::{Point, Rect, Size};
+use rootvg::quad::{QuadFlags, QuadPrimitive, Radius, SolidQuad};
+use rootvg::PrimitiveGroup;
+use smallvec::SmallVec;
+
+#[cfg(feature = "gradient")]
+use rootvg::gradient::{Gradient, LinearGradient};
+#[cfg(feature = "gradient")]
+use rootvg::math::Angle;
+#[cfg(feature = "tessellation")]
+use rootvg::tessellation::{
+    fill::{Fill, FillRule, FillStyle},
+    path::PathBuilder,
+    Tessellator,
+};
+
+use crate::vg::quad::Border;
+
+use super::{lerp_color, radius_corners, Background};
+
+/// The maximum number of color stops that can be used in a single gradient.
+///
+/// If more stops than this are given, then the gradient is resampled to this
+/// many evenly spaced stops.
+pub const MAX_GRADIENT_STOPS: usize = 8;
+
+/// The number of rings used to approximate a radial gradient when the
+/// `tessellation` feature is disabled.
+#[cfg(not(feature = "tessellation"))]
+const RADIAL_GRADIENT_RINGS: usize = 32;
+
+/// The approximate width of each band of a tessellated radial gradient in
+/// logical points.
+#[cfg(feature = "tessellation")]
+const RADIAL_GRADIENT_BAND_WIDTH: f32 = 1.5;
+/// The maximum number of bands in a tessellated radial gradient.
+#[cfg(feature = "tessellation")]
+const RADIAL_GRADIENT_MAX_BANDS: usize = 96;
+/// The number of segments each band of a tessellated radial gradient is
+/// split into around the center.
+#[cfg(feature = "tessellation")]
+const RADIAL_GRADIENT_SEGMENTS: usize = 64;
+/// The number of points used to approximate each rounded corner of the area
+/// a tessellated radial gradient is clipped to.
+#[cfg(feature = "tessellation")]
+const CORNER_SEGMENTS: usize = 6;
+
+/// A color stop in a gradient.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GradientStop {
+    /// The offset of this stop in the range `[0.0, 1.0]`.
+    pub offset: f32,
+    /// The color of this stop.
+    pub color: RGBA8,
+}
+
+impl GradientStop {
+    pub const fn new(offset: f32, color: RGBA8) -> Self {
+        Self { offset, color }
+    }
+}
+
+/// Sort the given stops and reduce them to at most [`MAX_GRADIENT_STOPS`]
+/// stops.
+fn pack_stops(stops: &[GradientStop]) -> [Option<GradientStop>; MAX_GRADIENT_STOPS] {
+    let mut sorted: SmallVec<[GradientStop; MAX_GRADIENT_STOPS]> = stops
+        .iter()
+        .map(|s| GradientStop::new(s.offset.clamp(0.0, 1.0), s.color))
+        .collect();
+    sorted.sort_by(|a, b| a.offset.total_cmp(&b.offset));
+
+    let mut packed = [None; MAX_GRADIENT_STOPS];
+
+    if sorted.len() <= MAX_GRADIENT_STOPS {
+        for (p, s) in packed.iter_mut().zip(sorted.iter()) {
+            *p = Some(*s);
+        }
+    } else {
+        let first = sorted[0].offset;
+        let last = sorted[sorted.len() - 1].offset;
+
+        for (i, p) in packed.iter_mut().enumerate() {
+            let offset = first + ((last - first) * i as f32 / (MAX_GRADIENT_STOPS - 1) as f32);
+            *p = Some(GradientStop::new(offset, sample_stops(&sorted, offset)));
+        }
+    }
+
+    packed
+}
+
+/// Sample the color at `t` from a list of stops sorted by offset.
+fn sample_stops(stops: &[GradientStop], t: f32) -> RGBA8 {
+    let Some(first) = stops.first() else {
+        return color::TRANSPARENT;
+    };
+
+    if t <= first.offset {
+        return first.color;
+    }
+
+    for w in stops.windows(2) {
+        let (a, b) = (w[0], w[1]);
+
+        if t <= b.offset {
+            let span = b.offset - a.offset;
+            let t = if span > 0.0 {
+                (t - a.offset) / span
+            } else {
+                1.0
+            };
+
+            return lerp_color(a.color, b.color, t);
+        }
+    }
+
+    stops[stops.len() - 1].color
+}
+
+/// Create a linear gradient with the given angle and list of color stops.
+///
+/// The stops do not need to be sorted. If there are more than
+/// [`MAX_GRADIENT_STOPS`] stops, then the gradient is resampled.
+#[cfg(feature = "gradient")]
+pub fn linear_gradient(angle: Angle, stops: &[GradientStop]) -> Gradient {
+    let mut gradient = LinearGradient::new(angle);
+
+    for stop in pack_stops(stops).into_iter().flatten() {
+        gradient = gradient.add_stop(stop.offset, stop.color);
+    }
+
+    Gradient::Linear(gradient)
+}
+
+/// An alias for `Background::Gradient(linear_gradient(angle, stops))`
+#[cfg(feature = "gradient")]
+pub fn background_linear_gradient(angle: Angle, stops: &[GradientStop]) -> Background {
+    Background::Gradient(linear_gradient(angle, stops))
+}
+
+/// An alias for `Background::Radial(RadialGradient::new(stops))`
+pub fn background_radial_gradient(stops: &[GradientStop]) -> Background {
+    Background::Radial(RadialGradient::new(stops))
+}
+
+/// A circular gradient that radiates outwards from a center point.
+///
+/// The area outside of the gradient's radius is filled with the color of the
+/// last stop.
+///
+/// Note, the renderer does not natively support radial gradients. With the
+/// `tessellation` feature enabled, they are drawn as thin bands of solid
+/// color clipped to the quad (including its rounded corners). Otherwise they
+/// are approximated with a series of concentric rings drawn on top of a quad
+/// filled with [`RadialGradient::outer_color`], so translucent stops blend
+/// with the outer color and the corners outside of the radius are filled
+/// with the outer color.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RadialGradient {
+    /// The center of the gradient, where `(0.0, 0.0)` is the top-left corner
+    /// and `(1.0, 1.0)` is the bottom-right corner of the quad.
+    ///
+    /// By default this is set to `(0.5, 0.5)`.
+    pub center: Point,
+    /// The radius of the gradient as a fraction of the distance from the
+    /// center to the closest edge of the quad.
+    ///
+    /// By default this is set to `1.0`.
+    pub radius: f32,
+    /// The color stops of the gradient, sorted by offset.
+    pub stops: [Option<GradientStop>; MAX_GRADIENT_STOPS],
+}
+
+impl RadialGradient {
+    /// Create a new radial gradient from the given list of color stops.
+    ///
+    /// The stops do not need to be sorted. If there are more than
+    /// [`MAX_GRADIENT_STOPS`] stops, then the gradient is resampled.
+    pub fn new(stops: &[GradientStop]) -> Self {
+        Self {
+            center: Point::new(0.5, 0.5),
+            radius: 1.0,
+            stops: pack_stops(stops),
+        }
+    }
+
+    pub fn with_center(mut self, center: Point) -> Self {
+        self.center = center;
+        self
+    }
+
+    pub fn with_radius(mut self, radius: f32) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Sample the color of this gradient at the normalized distance `t`
+    /// from the center.
+    pub fn sample(&self, t: f32) -> RGBA8 {
+        let stops: SmallVec<[GradientStop; MAX_GRADIENT_STOPS]> =
+            self.stops.iter().flatten().copied().collect();
+        sample_stops(&stops, t)
+    }
+
+    /// The color of the area outside of the gradient's radius.
+    pub fn outer_color(&self) -> RGBA8 {
+        self.stops
+            .iter()
+            .flatten()
+            .last()
+            .map(|s| s.color)
+            .unwrap_or(color::TRANSPARENT)
+    }
+
+    pub fn is_transparent(&self) -> bool {
+        self.stops.iter().flatten().all(|s| s.color.a == 0)
+    }
+
+    pub fn multiply_alpha(&mut self, multiplier: f32) {
+        for stop in self.stops.iter_mut().flatten() {
+            stop.color = color::multiply_alpha(stop.color, multiplier);
+        }
+    }
+
+    /// Add the primitives that draw this gradient to the given group.
+    ///
+    /// These should be added on top of the primitive created by
+    /// [`QuadStyle::create_primitive`](super::QuadStyle::create_primitive).
+    ///
+    /// * `inset` - The amount to shrink the gradient by so that it does not
+    /// overlap the border of the quad.
+    /// * `corner_radius` - The radius of the corners of the quad.
+    pub fn add_primitives(
+        &self,
+        bounds: Rect,
+        inset: f32,
+        corner_radius: Radius,
+        flags: QuadFlags,
+        primitives: &mut PrimitiveGroup,
+    ) {
+        let area = bounds.inflate(-inset, -inset);
+        if area.width() <= 0.0 || area.height() <= 0.0 {
+            return;
+        }
+
+        let center = Point::new(
+            bounds.min_x() + (bounds.width() * self.center.x),
+            bounds.min_y() + (bounds.height() * self.center.y),
+        );
+
+        let closest_edge = (center.x - area.min_x())
+            .min(area.max_x() - center.x)
+            .min(center.y - area.min_y())
+            .min(area.max_y() - center.y);
+
+        let full_radius = (closest_edge * self.radius).max(0.0);
+
+        #[cfg(feature = "tessellation")]
+        {
+            let _ = flags;
+            self.add_bands(area, center, full_radius, inset, corner_radius, primitives);
+        }
+
+        #[cfg(not(feature = "tessellation"))]
+        {
+            let _ = corner_radius;
+            self.add_rings(center, full_radius, flags, primitives);
+        }
+    }
+
+    /// Draw the gradient as bands of solid color between `0.0` and the
+    /// farthest point of `area`, clipped to the rounded rectangle of `area`.
+    ///
+    /// The bands share their edges exactly, so translucent stops are not
+    /// blended with each other.
+    #[cfg(feature = "tessellation")]
+    fn add_bands(
+        &self,
+        area: Rect,
+        center: Point,
+        full_radius: f32,
+        inset: f32,
+        corner_radius: Radius,
+        primitives: &mut PrimitiveGroup,
+    ) {
+        let clip = rounded_rect_polygon(area, corner_radius, inset);
+
+        let farthest = clip
+            .iter()
+            .map(|p| (*p - center).length())
+            .fold(0.0, f32::max);
+
+        // Make sure the outer band covers the clip area even though its arcs
+        // are approximated with chords.
+        let outer_radius =
+            farthest / (std::f32::consts::PI / RADIAL_GRADIENT_SEGMENTS as f32).cos() + 1.0;
+
+        let num_bands = ((full_radius / RADIAL_GRADIENT_BAND_WIDTH).ceil() as usize)
+            .clamp(1, RADIAL_GRADIENT_MAX_BANDS);
+        let step = 1.0 / num_bands as f32;
+
+        let mut bands: Vec<(f32, f32, RGBA8)> = Vec::with_capacity(num_bands + 1);
+        if full_radius > 0.0 {
+            bands.extend((0..num_bands).map(|i| {
+                (
+                    full_radius * i as f32 * step,
+                    full_radius * (i + 1) as f32 * step,
+                    self.sample((i as f32 + 0.5) * step),
+                )
+            }));
+        }
+        if outer_radius > full_radius {
+            bands.push((full_radius, outer_radius, self.outer_color()));
+        }
+
+        for (inner, outer, color) in bands {
+            if color.a == 0 {
+                continue;
+            }
+
+            let mut path = PathBuilder::new();
+            let mut is_empty = true;
+
+            for segment in 0..RADIAL_GRADIENT_SEGMENTS {
+                let a0 = std::f32::consts::TAU * segment as f32 / RADIAL_GRADIENT_SEGMENTS as f32;
+                let a1 =
+                    std::f32::consts::TAU * (segment + 1) as f32 / RADIAL_GRADIENT_SEGMENTS as f32;
+                let at =
+                    |r: f32, a: f32| Point::new(center.x + r * a.cos(), center.y + r * a.sin());
+
+                let clipped = clip_convex_polygon(
+                    &[at(inner, a0), at(outer, a0), at(outer, a1), at(inner, a1)],
+                    &clip,
+                );
+                if clipped.len() < 3 {
+                    continue;
+                }
+
+                path = path.move_to(clipped[0]);
+                for p in clipped.iter().skip(1) {
+                    path = path.line_to(*p);
+                }
+                path = path.close();
+                is_empty = false;
+            }
+
+            if is_empty {
+                continue;
+            }
+
+            if let Some(mesh) = Tessellator::new()
+                .fill(
+                    &path.build(),
+                    Fill {
+                        style: FillStyle::Solid(color.into()),
+                        rule: FillRule::NonZero,
+                    },
+                )
+                .into_primitive()
+            {
+                primitives.add_mesh(mesh);
+            }
+        }
+    }
+
+    /// Draw the gradient as non-overlapping rings inside of `full_radius`.
+    #[cfg(not(feature = "tessellation"))]
+    fn add_rings(
+        &self,
+        center: Point,
+        full_radius: f32,
+        flags: QuadFlags,
+        primitives: &mut PrimitiveGroup,
+    ) {
+        if full_radius <= 0.0 {
+            return;
+        }
+
+        let step = 1.0 / RADIAL_GRADIENT_RINGS as f32;
+
+        for i in 0..RADIAL_GRADIENT_RINGS {
+            let color = self.sample((i as f32 + 0.5) * step);
+            if color.a == 0 {
+                continue;
+            }
+
+            let inner = full_radius * i as f32 * step;
+            let outer = full_radius * (i + 1) as f32 * step;
+
+            // Every ring but the innermost one is drawn as a border so that
+            // the rings do not overlap.
+            let (bg_color, border) = if i == 0 {
+                (
+                    color,
+                    Border {
+                        radius: radius_corners(outer, outer, outer, outer),
+                        ..Default::default()
+                    },
+                )
+            } else {
+                (
+                    color::TRANSPARENT,
+                    Border {
+                        color: color.into(),
+                        width: outer - inner,
+                        radius: radius_corners(outer, outer, outer, outer),
+                    },
+                )
+            };
+
+            primitives.add(QuadPrimitive::Solid(
+                SolidQuad {
+                    bounds: Rect::new(
+                        Point::new(center.x - outer, center.y - outer),
+                        Size::new(outer * 2.0, outer * 2.0),
+                    ),
+                    bg_color: bg_color.into(),
+                    border,
+                    flags,
+                }
+                .into(),
+            ));
+        }
+    }
+}
+
+/// The points of a rounded rectangle in clockwise order (with the y axis
+/// pointing down).
+#[cfg(feature = "tessellation")]
+fn rounded_rect_polygon(rect: Rect, radius: Radius, inset: f32) -> SmallVec<[Point; 32]> {
+    let max_radius = rect.width().min(rect.height()) * 0.5;
+    let r = |r: f32| (r - inset).clamp(0.0, max_radius);
+
+    // The center of each corner's arc, its radius, and the angle it starts at.
+    let corners = [
+        (
+            Point::new(rect.min_x(), rect.min_y()),
+            r(radius.top_left),
+            (1.0, 1.0),
+            std::f32::consts::PI,
+        ),
+        (
+            Point::new(rect.max_x(), rect.min_y()),
+            r(radius.top_right),
+            (-1.0, 1.0),
+            std::f32::consts::PI * 1.5,
+        ),
+        (
+            Point::new(rect.max_x(), rect.max_y()),
+            r(radius.bottom_right),
+            (-1.0, -1.0),
+            0.0,
+        ),
+        (
+            Point::new(rect.min_x(), rect.max_y()),
+            r(radius.bottom_left),
+            (1.0, -1.0),
+            std::f32::consts::PI * 0.5,
+        ),
+    ];
+
+    let mut points = SmallVec::new();
+
+    for (corner, r, (dx, dy), start) in corners {
+        if r <= 0.0 {
+            points.push(corner);
+            continue;
+        }
+
+        let arc_center = Point::new(corner.x + r * dx, corner.y + r * dy);
+
+        for i in 0..=CORNER_SEGMENTS {
+            let a = start + std::f32::consts::FRAC_PI_2 * i as f32 / CORNER_SEGMENTS as f32;
+            points.push(Point::new(
+                arc_center.x + r * a.cos(),
+                arc_center.y + r * a.sin(),
+            ));
+        }
+    }
+
+    points
+}
+
+/// Clip a polygon to a convex polygon in clockwise order (with the y axis
+/// pointing down) using the Sutherland-Hodgman algorithm.
+#[cfg(feature = "tessellation")]
+fn clip_convex_polygon(subject: &[Point], clip: &[Point]) -> SmallVec<[Point; 16]> {
+    let mut output: SmallVec<[Point; 16]> = subject.iter().copied().collect();
+
+    for (i, a) in clip.iter().enumerate() {
+        let b = clip[(i + 1) % clip.len()];
+        if output.is_empty() {
+            break;
+        }
+        if *a == b {
+            continue;
+        }
+
+        let inside = |p: Point| ((b.x - a.x) * (p.y - a.y)) - ((b.y - a.y) * (p.x - a.x)) >= 0.0;
+        let intersect = |p: Point, q: Point| -> Point {
+            let d1 = q - p;
+            let d2 = b - *a;
+            let denom = (d1.x * d2.y) - (d1.y * d2.x);
+            if denom.abs() <= f32::EPSILON {
+                return q;
+            }
+            let t = (((a.x - p.x) * d2.y) - ((a.y - p.y) * d2.x)) / denom;
+            p + (d1 * t)
+        };
+
+        let input = std::mem::take(&mut output);
+        for (j, current) in input.iter().enumerate() {
+            let prev = input[(j + input.len() - 1) % input.len()];
+
+            match (inside(*current), inside(prev)) {
+                (true, true) => output.push(*current),
+                (true, false) => {
+                    output.push(intersect(prev, *current));
+                    output.push(*current);
+                }
+                (false, true) => output.push(intersect(prev, *current)),
+                (false, false) => {}
+            }
+        }
+    }
+
+    output
+}
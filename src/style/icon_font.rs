@@ -0,0 +1,83 @@
+use rootvg::math::Vector;
+use rustc_hash::FxHashMap;
+
+use super::IconID;
+
+/// A glyph in an icon font (i.e. a codepoint in the private-use area of a
+/// font like Material Icons or Font Awesome).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FontIcon {
+    /// The family name of the icon font (see `ResourceCtx::register_font`).
+    pub family: &'static str,
+    /// The codepoint of the glyph.
+    pub codepoint: char,
+    /// An offset used to correct the position of the glyph within the
+    /// icon's bounds.
+    ///
+    /// By default this is set to `Vector::zero()`.
+    pub offset: Vector,
+    /// A scaling factor used to make the glyph fill the icon's bounds,
+    /// since the glyphs in most icon fonts are smaller than the font size.
+    ///
+    /// By default this is set to `1.0`.
+    pub scale: f32,
+}
+
+impl FontIcon {
+    pub const fn new(family: &'static str, codepoint: char) -> Self {
+        Self {
+            family,
+            codepoint,
+            offset: Vector::new(0.0, 0.0),
+            scale: 1.0,
+        }
+    }
+
+    pub const fn with_offset(mut self, offset: Vector) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    pub const fn with_scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+}
+
+/// A registry which maps [`IconID`]s to glyphs in icon fonts.
+///
+/// Elements which display an icon (i.e. a label or a button) will draw the
+/// glyph from the icon font if its `IconID` is registered here, otherwise
+/// they will draw the SVG icon with that ID.
+#[derive(Default, Debug, Clone)]
+pub struct IconFontSystem {
+    icons: FxHashMap<IconID, FontIcon>,
+}
+
+impl IconFontSystem {
+    /// Register a glyph for the given icon ID.
+    ///
+    /// Returns the glyph that was previously registered with this ID (if any).
+    pub fn add(&mut self, id: impl Into<IconID>, icon: FontIcon) -> Option<FontIcon> {
+        self.icons.insert(id.into(), icon)
+    }
+
+    /// Register the glyphs of a single icon font.
+    pub fn add_all(
+        &mut self,
+        family: &'static str,
+        icons: impl IntoIterator<Item = (IconID, char)>,
+    ) {
+        for (id, codepoint) in icons {
+            self.icons.insert(id, FontIcon::new(family, codepoint));
+        }
+    }
+
+    pub fn remove(&mut self, id: impl Into<IconID>) -> Option<FontIcon> {
+        self.icons.remove(&id.into())
+    }
+
+    pub fn get(&self, id: IconID) -> Option<&FontIcon> {
+        self.icons.get(&id)
+    }
+}
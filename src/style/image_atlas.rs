@@ -0,0 +1,184 @@
+use rootvg::image::RcTexture;
+use rootvg::math::{Point, Rect, Size};
+
+use super::{ImageID, ImageSystem};
+
+/// The default width and height of an [`ImageAtlasBuilder`] in pixels.
+pub const DEFAULT_IMAGE_ATLAS_SIZE: u32 = 1024;
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq)]
+pub enum ImageAtlasError {
+    #[error("There is not enough space left in the atlas for an image of size {width}x{height}")]
+    AtlasFull { width: u32, height: u32 },
+    #[error("Expected {expected} bytes of RGBA pixel data, got {got}")]
+    InvalidDataLength { expected: usize, got: usize },
+}
+
+struct Shelf {
+    y: u32,
+    height: u32,
+    next_x: u32,
+}
+
+/// Packs many small images (i.e. icons or pre-rasterized SVGs) into a single
+/// RGBA texture.
+///
+/// Images which share the same texture are drawn in a single batch, so this
+/// can greatly reduce the number of texture binds in icon-heavy UIs.
+///
+/// Once all images have been added, create a texture from the pixels returned
+/// by [`ImageAtlasBuilder::pixels`] and register it with
+/// [`ImageAtlasBuilder::finish`].
+pub struct ImageAtlasBuilder {
+    width: u32,
+    height: u32,
+    padding: u32,
+    pixels: Vec<u8>,
+    shelves: Vec<Shelf>,
+    regions: Vec<(ImageID, Rect)>,
+}
+
+impl ImageAtlasBuilder {
+    /// Create a new empty atlas with the given size in pixels.
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            padding: 1,
+            pixels: vec![0; width as usize * height as usize * 4],
+            shelves: Vec::new(),
+            regions: Vec::new(),
+        }
+    }
+
+    /// The number of transparent pixels to leave between images, which keeps
+    /// neighboring images from bleeding into each other when filtered.
+    ///
+    /// By default this is set to `1`.
+    pub fn with_padding(mut self, padding: u32) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Copy the given image into the atlas.
+    ///
+    /// * `rgba` - The pixels of the image in RGBA8 format, row by row.
+    ///
+    /// Returns the region of the atlas the image was placed in.
+    pub fn add(
+        &mut self,
+        id: impl Into<ImageID>,
+        width: u32,
+        height: u32,
+        rgba: &[u8],
+    ) -> Result<Rect, ImageAtlasError> {
+        let expected = width as usize * height as usize * 4;
+        if rgba.len() != expected {
+            return Err(ImageAtlasError::InvalidDataLength {
+                expected,
+                got: rgba.len(),
+            });
+        }
+
+        let Some((x, y)) = self.allocate(width, height) else {
+            return Err(ImageAtlasError::AtlasFull { width, height });
+        };
+
+        let row_bytes = width as usize * 4;
+        for row in 0..height as usize {
+            let src = row * row_bytes;
+            let dst = ((y as usize + row) * self.width as usize + x as usize) * 4;
+            self.pixels[dst..dst + row_bytes].copy_from_slice(&rgba[src..src + row_bytes]);
+        }
+
+        let region = Rect::new(
+            Point::new(x as f32, y as f32),
+            Size::new(width as f32, height as f32),
+        );
+        self.regions.push((id.into(), region));
+
+        Ok(region)
+    }
+
+    /// The size of the atlas in pixels.
+    pub fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    /// The pixels of the atlas in RGBA8 format, row by row.
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// The number of images in the atlas.
+    pub fn len(&self) -> usize {
+        self.regions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.regions.is_empty()
+    }
+
+    /// Register every image in this atlas with the given image system.
+    ///
+    /// * `texture` - The texture created from [`ImageAtlasBuilder::pixels`].
+    pub fn finish(self, texture: RcTexture, images: &mut ImageSystem) {
+        for (id, region) in self.regions {
+            images.add_region(id, texture.clone(), region);
+        }
+    }
+
+    /// Find a free spot using a simple shelf packing algorithm.
+    fn allocate(&mut self, width: u32, height: u32) -> Option<(u32, u32)> {
+        let padded_width = width + self.padding;
+        let padded_height = height + self.padding;
+
+        if width > self.width {
+            return None;
+        }
+
+        // Use the shelf which wastes the least amount of vertical space.
+        let mut best: Option<usize> = None;
+        for (i, shelf) in self.shelves.iter().enumerate() {
+            let fits = shelf.height >= padded_height && shelf.next_x + width <= self.width;
+            let is_better = match best {
+                Some(b) => shelf.height < self.shelves[b].height,
+                None => true,
+            };
+
+            if fits && is_better {
+                best = Some(i);
+            }
+        }
+
+        if let Some(i) = best {
+            let shelf = &mut self.shelves[i];
+            let x = shelf.next_x;
+            shelf.next_x += padded_width;
+            return Some((x, shelf.y));
+        }
+
+        let y = self
+            .shelves
+            .last()
+            .map(|shelf| shelf.y + shelf.height)
+            .unwrap_or(0);
+        if y + height > self.height {
+            return None;
+        }
+
+        self.shelves.push(Shelf {
+            y,
+            height: padded_height,
+            next_x: padded_width,
+        });
+
+        Some((0, y))
+    }
+}
+
+impl Default for ImageAtlasBuilder {
+    fn default() -> Self {
+        Self::new(DEFAULT_IMAGE_ATLAS_SIZE, DEFAULT_IMAGE_ATLAS_SIZE)
+    }
+}
@@ -0,0 +1,177 @@
+#[cfg(not(target_family = "wasm"))]
+use std::{sync::mpsc, thread::JoinHandle};
+
+use rustc_hash::FxHashMap;
+
+use super::ImageID;
+
+/// The pixels of an image which was decoded by an [`ImageLoader`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedImage {
+    pub width: u32,
+    pub height: u32,
+    /// The pixels of the image in RGBA8 format, row by row.
+    pub rgba: Vec<u8>,
+}
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq)]
+pub enum ImageLoadError {
+    #[error("Failed to decode image: {0}")]
+    DecodeFailed(String),
+    #[error("The image loader thread has stopped")]
+    WorkerStopped,
+}
+
+#[cfg(not(target_family = "wasm"))]
+type DecodeFn = Box<dyn FnOnce() -> Result<DecodedImage, ImageLoadError> + Send>;
+
+/// Decodes and rasterizes images on a worker thread, keeping the GUI thread
+/// responsive while loading large artwork (i.e. in a preset browser).
+///
+/// On the web, where threads are not available, images are decoded
+/// immediately on the GUI thread instead.
+///
+/// When an image has finished loading, `Application::on_image_loaded` is
+/// called on the GUI thread. The application then registers a texture
+/// created from the pixels with the [`ImageSystem`], and every window is
+/// redrawn. Until then, elements using the image draw the placeholder image
+/// set with [`ImageSystem::set_placeholder`] (if any).
+///
+/// [`ImageSystem`]: super::ImageSystem
+/// [`ImageSystem::set_placeholder`]: super::ImageSystem::set_placeholder
+#[derive(Default)]
+pub struct ImageLoader {
+    #[cfg(not(target_family = "wasm"))]
+    worker: Option<Worker>,
+    /// The ID of the latest request for each pending image.
+    pending: FxHashMap<ImageID, u64>,
+    #[cfg(not(target_family = "wasm"))]
+    next_request_id: u64,
+    /// Threads are not available on the web, so images are decoded
+    /// immediately and stored here until they are taken.
+    #[cfg(target_family = "wasm")]
+    decoded: Vec<(ImageID, Result<DecodedImage, ImageLoadError>)>,
+}
+
+#[cfg(not(target_family = "wasm"))]
+struct Worker {
+    job_sender: mpsc::Sender<(ImageID, u64, DecodeFn)>,
+    result_receiver: mpsc::Receiver<(ImageID, u64, Result<DecodedImage, ImageLoadError>)>,
+    _thread: JoinHandle<()>,
+}
+
+impl ImageLoader {
+    /// Decode an image on the worker thread.
+    ///
+    /// * `decode` - The function which decodes the image, i.e. by reading a
+    /// PNG file or by rasterizing an SVG.
+    ///
+    /// If an image with the same ID is already loading, then the result of
+    /// the previous request is discarded.
+    pub fn load<F>(&mut self, id: impl Into<ImageID>, decode: F)
+    where
+        F: FnOnce() -> Result<DecodedImage, ImageLoadError> + Send + 'static,
+    {
+        let id: ImageID = id.into();
+
+        #[cfg(target_family = "wasm")]
+        {
+            self.decoded.retain(|(decoded_id, _)| *decoded_id != id);
+            self.decoded.push((id, (decode)()));
+        }
+
+        #[cfg(not(target_family = "wasm"))]
+        self.send_to_worker(id, Box::new(decode));
+    }
+
+    #[cfg(not(target_family = "wasm"))]
+    fn send_to_worker(&mut self, id: ImageID, decode: DecodeFn) {
+        let request_id = self.next_request_id;
+        self.next_request_id += 1;
+
+        let worker = self.worker.get_or_insert_with(spawn_worker);
+
+        if worker.job_sender.send((id, request_id, decode)).is_ok() {
+            self.pending.insert(id, request_id);
+        } else {
+            log::error!("Failed to send image {} to the image loader thread", id);
+        }
+    }
+
+    /// Returns `true` if the image with the given ID is currently loading.
+    pub fn is_pending(&self, id: ImageID) -> bool {
+        self.pending.contains_key(&id)
+    }
+
+    /// The number of images which are currently loading.
+    pub fn num_pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Take all of the images which have finished loading since the last
+    /// call.
+    pub(crate) fn take_loaded(&mut self) -> Vec<(ImageID, Result<DecodedImage, ImageLoadError>)> {
+        #[cfg(target_family = "wasm")]
+        {
+            std::mem::take(&mut self.decoded)
+        }
+
+        #[cfg(not(target_family = "wasm"))]
+        self.take_loaded_from_worker()
+    }
+
+    #[cfg(not(target_family = "wasm"))]
+    fn take_loaded_from_worker(&mut self) -> Vec<(ImageID, Result<DecodedImage, ImageLoadError>)> {
+        let Some(worker) = &self.worker else {
+            return Vec::new();
+        };
+
+        let mut loaded = Vec::new();
+        loop {
+            match worker.result_receiver.try_recv() {
+                Ok((id, request_id, result)) => {
+                    // Discard the results of outdated requests.
+                    if self.pending.get(&id) == Some(&request_id) {
+                        self.pending.remove(&id);
+                        loaded.push((id, result));
+                    }
+                }
+                Err(mpsc::TryRecvError::Empty) => break,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    loaded.extend(
+                        self.pending
+                            .drain()
+                            .map(|(id, _)| (id, Err(ImageLoadError::WorkerStopped))),
+                    );
+                    self.worker = None;
+                    break;
+                }
+            }
+        }
+
+        loaded
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn spawn_worker() -> Worker {
+    let (job_sender, job_receiver) = mpsc::channel::<(ImageID, u64, DecodeFn)>();
+    let (result_sender, result_receiver) = mpsc::channel();
+
+    let thread = std::thread::Builder::new()
+        .name("yarrow image loader".into())
+        .spawn(move || {
+            while let Ok((id, request_id, decode)) = job_receiver.recv() {
+                if result_sender.send((id, request_id, (decode)())).is_err() {
+                    break;
+                }
+            }
+        })
+        .unwrap();
+
+    Worker {
+        job_sender,
+        result_receiver,
+        _thread: thread,
+    }
+}
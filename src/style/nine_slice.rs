@@ -0,0 +1,231 @@
+use rootvg::image::{ImagePrimitive, RcTexture};
+use rootvg::math::{Point, Rect, Size};
+use rustc_hash::FxHashMap;
+use smallvec::SmallVec;
+
+use crate::layout::Padding;
+
+use super::Background;
+
+pub type ImageID = u16;
+
+/// A background which stretches a bitmap image over a quad without
+/// distorting its edges.
+///
+/// The image is split into nine regions by the given insets. The corners are
+/// drawn at their original size, the edges are stretched along one axis, and
+/// the center is stretched along both axes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NineSlice {
+    /// The ID of the image in the [`ImageSystem`].
+    pub image: ImageID,
+    /// The size of the top, right, bottom, and left regions of the source
+    /// image in pixels.
+    pub insets: Padding,
+    /// The scale at which the corners and edges are drawn (i.e. use `0.5` for
+    /// artwork drawn at 2x resolution).
+    ///
+    /// By default this is set to `1.0`.
+    pub scale: f32,
+    /// Whether or not to draw the center region.
+    ///
+    /// By default this is set to `true`.
+    pub fill_center: bool,
+}
+
+impl NineSlice {
+    pub const fn new(image: ImageID, insets: Padding) -> Self {
+        Self {
+            image,
+            insets,
+            scale: 1.0,
+            fill_center: true,
+        }
+    }
+
+    pub const fn with_scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    pub const fn with_fill_center(mut self, fill_center: bool) -> Self {
+        self.fill_center = fill_center;
+        self
+    }
+
+    /// Create the image primitives for this nine-slice.
+    ///
+    /// Returns an empty list if the image is not registered in `images`.
+    pub fn create_primitives(
+        &self,
+        bounds: Rect,
+        images: &ImageSystem,
+    ) -> SmallVec<[ImagePrimitive; 9]> {
+        let mut primitives = SmallVec::new();
+
+        let Some(image) = images.get(self.image) else {
+            return primitives;
+        };
+
+        let src = image.region.size;
+        let src_origin = image.region.origin;
+        let ins = &self.insets;
+
+        // If the quad is smaller than the corners, shrink the corners
+        // proportionally so they don't overlap.
+        let fit = |a: f32, b: f32, available: f32| -> f32 {
+            let total = (a + b) * self.scale;
+            if total > available && total > 0.0 {
+                available / total
+            } else {
+                1.0
+            }
+        };
+        let fit_x = fit(ins.left, ins.right, bounds.width()) * self.scale;
+        let fit_y = fit(ins.top, ins.bottom, bounds.height()) * self.scale;
+
+        let src_cols = [0.0, ins.left, src.width - ins.right, src.width];
+        let src_rows = [0.0, ins.top, src.height - ins.bottom, src.height];
+        let dst_cols = [
+            bounds.min_x(),
+            bounds.min_x() + (ins.left * fit_x),
+            bounds.max_x() - (ins.right * fit_x),
+            bounds.max_x(),
+        ];
+        let dst_rows = [
+            bounds.min_y(),
+            bounds.min_y() + (ins.top * fit_y),
+            bounds.max_y() - (ins.bottom * fit_y),
+            bounds.max_y(),
+        ];
+
+        for row in 0..3 {
+            for col in 0..3 {
+                if row == 1 && col == 1 && !self.fill_center {
+                    continue;
+                }
+
+                let dst = Rect::new(
+                    Point::new(dst_cols[col], dst_rows[row]),
+                    Size::new(
+                        dst_cols[col + 1] - dst_cols[col],
+                        dst_rows[row + 1] - dst_rows[row],
+                    ),
+                );
+                let uv = Rect::new(
+                    Point::new(src_origin.x + src_cols[col], src_origin.y + src_rows[row]),
+                    Size::new(
+                        src_cols[col + 1] - src_cols[col],
+                        src_rows[row + 1] - src_rows[row],
+                    ),
+                );
+
+                if dst.is_empty() || uv.is_empty() {
+                    continue;
+                }
+
+                primitives.push(ImagePrimitive::new_with(
+                    image.texture.clone(),
+                    dst.origin,
+                    Some(dst.size),
+                    Some(uv),
+                    None,
+                ));
+            }
+        }
+
+        primitives
+    }
+}
+
+/// An alias for `Background::NineSlice(NineSlice::new(image, insets))`
+pub const fn background_nine_slice(image: ImageID, insets: Padding) -> Background {
+    Background::NineSlice(NineSlice::new(image, insets))
+}
+
+#[derive(Debug, Clone)]
+struct ImageEntry {
+    texture: RcTexture,
+    /// The region of the texture containing the image in pixels.
+    region: Rect,
+}
+
+/// A registry which maps [`ImageID`]s to textures used by [`NineSlice`]
+/// backgrounds.
+///
+/// Multiple images can share a single texture (see [`ImageAtlasBuilder`]),
+/// in which case they are drawn in a single batch.
+///
+/// [`ImageAtlasBuilder`]: super::ImageAtlasBuilder
+#[derive(Default, Debug, Clone)]
+pub struct ImageSystem {
+    images: FxHashMap<ImageID, ImageEntry>,
+    placeholder: Option<ImageID>,
+}
+
+impl ImageSystem {
+    /// Register a texture with the given ID.
+    ///
+    /// * `size` - The size of the texture in pixels.
+    pub fn add(&mut self, id: impl Into<ImageID>, texture: RcTexture, size: Size) {
+        self.add_region(id, texture, Rect::from_size(size));
+    }
+
+    /// Register a region of a texture (i.e. an image in an atlas) with the
+    /// given ID.
+    ///
+    /// * `region` - The region of the texture containing the image in pixels.
+    pub fn add_region(&mut self, id: impl Into<ImageID>, texture: RcTexture, region: Rect) {
+        self.images
+            .insert(id.into(), ImageEntry { texture, region });
+    }
+
+    pub fn remove(&mut self, id: impl Into<ImageID>) -> Option<RcTexture> {
+        self.images.remove(&id.into()).map(|e| e.texture)
+    }
+
+    pub fn contains(&self, id: ImageID) -> bool {
+        self.images.contains_key(&id)
+    }
+
+    /// Set the image which is drawn in place of images which are not
+    /// registered (i.e. while they are still being loaded by an
+    /// [`ImageLoader`]).
+    ///
+    /// [`ImageLoader`]: super::ImageLoader
+    pub fn set_placeholder(&mut self, id: Option<ImageID>) {
+        self.placeholder = id;
+    }
+
+    pub fn placeholder(&self) -> Option<ImageID> {
+        self.placeholder
+    }
+
+    /// The size of the image with the given ID in pixels.
+    pub fn size(&self, id: ImageID) -> Option<Size> {
+        self.images.get(&id).map(|e| e.region.size)
+    }
+
+    /// Create a primitive which draws the whole image with the given ID
+    /// stretched over `bounds`.
+    ///
+    /// Returns `None` if the image is not registered.
+    pub fn create_primitive(&self, id: ImageID, bounds: Rect) -> Option<ImagePrimitive> {
+        let image = self.get(id)?;
+
+        Some(ImagePrimitive::new_with(
+            image.texture.clone(),
+            bounds.origin,
+            Some(bounds.size),
+            Some(image.region),
+            None,
+        ))
+    }
+
+    fn get(&self, id: ImageID) -> Option<&ImageEntry> {
+        self.images
+            .get(&id)
+            .or_else(|| self.images.get(&self.placeholder?))
+    }
+}
@@ -5,12 +5,23 @@ use ahash::AHashMap;
 
 use crate::element_system::element::ElementStyle;
 
+use super::{DisabledDerivation, FocusRingStyle};
+
 pub type ClassID = u16;
 
 pub const CLASS_DEFAULT: ClassID = 0;
 pub const CLASS_MENU: ClassID = ClassID::MAX;
 pub const CLASS_PANEL: ClassID = ClassID::MAX - 1;
 
+/// Clones a base style and applies the override to it.
+type ApplyOverride = Rc<dyn Fn(&dyn Any) -> Rc<dyn Any>>;
+
+/// A class whose styles are layered on top of another class.
+struct DerivedClass {
+    base_class: ClassID,
+    overrides: AHashMap<&'static str, ApplyOverride>,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct Key {
     element_type_id: &'static str,
@@ -20,6 +31,15 @@ struct Key {
 
 pub struct StyleSystem {
     styles: AHashMap<Key, Rc<dyn Any>>,
+    /// The styles with the auto disabled derivation applied.
+    auto_disabled_styles: AHashMap<Key, Rc<dyn Any>>,
+    focus_ring: Option<FocusRingStyle>,
+    auto_disabled: Option<DisabledDerivation>,
+    reduce_motion: bool,
+    derived_classes: AHashMap<ClassID, DerivedClass>,
+    next_derived_class: ClassID,
+    /// The IDs of removed derived classes which can be reused.
+    free_derived_classes: Vec<ClassID>,
     pub(crate) use_dark_theme: bool,
 }
 
@@ -27,6 +47,13 @@ impl StyleSystem {
     pub fn new(use_dark_theme: bool) -> Self {
         Self {
             styles: AHashMap::default(),
+            auto_disabled_styles: AHashMap::default(),
+            focus_ring: Some(FocusRingStyle::default()),
+            auto_disabled: None,
+            reduce_motion: false,
+            derived_classes: AHashMap::default(),
+            next_derived_class: CLASS_PANEL - 1,
+            free_derived_classes: Vec::new(),
             use_dark_theme,
         }
     }
@@ -35,10 +62,75 @@ impl StyleSystem {
         self.use_dark_theme
     }
 
+    /// The style of the ring drawn around elements that have keyboard focus.
+    ///
+    /// If this returns `None`, then no focus ring is drawn.
+    pub fn focus_ring(&self) -> Option<&FocusRingStyle> {
+        self.focus_ring.as_ref()
+    }
+
+    /// Set the style of the ring drawn around elements that have keyboard
+    /// focus. Set to `None` to disable focus rings.
+    ///
+    /// Note, elements will not be repainted until the next time they are
+    /// marked dirty.
+    pub fn set_focus_ring(&mut self, focus_ring: Option<FocusRingStyle>) {
+        self.focus_ring = focus_ring;
+    }
+
+    /// How disabled-state styles are automatically derived from idle-state
+    /// styles, or `None` if automatic derivation is disabled.
+    pub fn auto_disabled(&self) -> Option<DisabledDerivation> {
+        self.auto_disabled
+    }
+
+    /// Automatically derive the disabled state of any style property that
+    /// doesn't explicitly define one (i.e. any property set to
+    /// `DisabledColor::Auto` or `DisabledBackground::Auto`, the default).
+    ///
+    /// Set to `None` to only apply an alpha multiplier of
+    /// `DEFAULT_DISABLED_ALPHA_MULTIPLIER` to these properties.
+    ///
+    /// Note, elements will not be repainted until the next time they are
+    /// marked dirty.
+    pub fn set_auto_disabled(&mut self, derivation: Option<DisabledDerivation>) {
+        self.auto_disabled = derivation;
+        self.auto_disabled_styles.clear();
+    }
+
+    /// Returns `true` if the user prefers reduced motion.
+    pub fn reduce_motion(&self) -> bool {
+        self.reduce_motion
+    }
+
+    /// Set whether the user prefers reduced motion (i.e. as read from the
+    /// accessibility settings of the OS).
+    ///
+    /// When enabled, style transitions (including the sliding indicators of
+    /// toggle buttons and tabs) and kinetic scrolling snap to their final
+    /// state instead of being animated. Custom elements should check this
+    /// before starting their own animations.
+    ///
+    /// Note, built-in elements only pick up this setting when they receive
+    /// an `ElementEvent::StyleChanged` event (`AppContext::set_reduce_motion`
+    /// sends this to all elements).
+    pub fn set_reduce_motion(&mut self, reduce_motion: bool) {
+        self.reduce_motion = reduce_motion;
+    }
+
     /// Insert a new style with the given class ID for the given element type.
     ///
     /// Returns `true` if this style existed before and has been overwritten.
     pub fn add<T: ElementStyle>(&mut self, class: ClassID, is_dark_theme: bool, style: T) -> bool {
+        if self.derived_classes.contains_key(&class) {
+            log::warn!(
+                "Adding a style to class {} which is a derived class, use StyleSystem::set_override instead",
+                class
+            );
+        }
+
+        self.invalidate_derived(T::ID);
+
         self.styles
             .insert(
                 Key {
@@ -55,6 +147,8 @@ impl StyleSystem {
     ///
     /// Returns `true` if the style existed.
     pub fn remove<T: ElementStyle>(&mut self, class: ClassID, is_dark_theme: bool) -> bool {
+        self.invalidate_derived(T::ID);
+
         self.styles
             .remove(&Key {
                 element_type_id: T::ID,
@@ -69,22 +163,7 @@ impl StyleSystem {
     /// If the style doesn't exist in the system, the default style will be
     /// inserted and returned.
     pub fn get<T: ElementStyle>(&mut self, class: ClassID) -> &T {
-        let key = Key {
-            element_type_id: T::ID,
-            class,
-            is_dark_theme: self.use_dark_theme,
-        };
-
-        let entry = self.styles.entry(key);
-        let entry = entry.or_insert_with(|| {
-            Rc::new(if self.use_dark_theme {
-                T::default_dark_style()
-            } else {
-                T::default_light_style()
-            })
-        });
-
-        entry.downcast_ref().unwrap()
+        self.auto_disabled_entry::<T>(class).downcast_ref().unwrap()
     }
 
     /// Get an Rc pointer to the style from the system.
@@ -98,21 +177,209 @@ impl StyleSystem {
     /// If the style doesn't exist in the system, the default style will be
     /// inserted and returned.
     pub fn get_rc<T: ElementStyle>(&mut self, class: ClassID) -> Rc<dyn Any> {
+        Rc::clone(self.auto_disabled_entry::<T>(class))
+    }
+
+    /// Create a new class which uses the same styles as `base_class`.
+    ///
+    /// Individual style properties can then be overriden for this class with
+    /// [`StyleSystem::set_override`]. This is useful for changing a few
+    /// properties of a single element (i.e. the accent color of one knob)
+    /// without needing to define a whole new style.
+    ///
+    /// The overrides are layered on top of the base class, so any changes to
+    /// the styles of the base class (including changing the theme) will be
+    /// reflected in the derived class.
+    ///
+    /// Note, derived class IDs are allocated from the unused IDs counting
+    /// down from `CLASS_PANEL`, and IDs of removed derived classes are
+    /// reused. An ID which already has a style added with
+    /// [`StyleSystem::add`] is skipped, so add the styles of your own
+    /// classes before deriving classes.
+    ///
+    /// Returns `None` if every class ID is in use. Remove derived classes
+    /// which are no longer needed with [`StyleSystem::remove_derived_class`].
+    pub fn derive_class(&mut self, base_class: ClassID) -> Option<ClassID> {
+        let class = self.allocate_derived_class()?;
+
+        self.derived_classes.insert(
+            class,
+            DerivedClass {
+                base_class,
+                overrides: AHashMap::default(),
+            },
+        );
+
+        Some(class)
+    }
+
+    /// Override the properties of the style of type `T` in a class created
+    /// with [`StyleSystem::derive_class`].
+    ///
+    /// The given closure is applied to a copy of the base class's style.
+    /// This replaces any previous override of type `T` for this class.
+    ///
+    /// Returns `false` if `class` is not a derived class.
+    ///
+    /// Note, elements will not be repainted until the next time they are
+    /// marked dirty (i.e. by setting their class).
+    pub fn set_override<T: ElementStyle + Clone>(
+        &mut self,
+        class: ClassID,
+        f: impl Fn(&mut T) + 'static,
+    ) -> bool {
+        let Some(derived) = self.derived_classes.get_mut(&class) else {
+            return false;
+        };
+
+        derived.overrides.insert(
+            T::ID,
+            Rc::new(move |base: &dyn Any| -> Rc<dyn Any> {
+                let mut style: T = base.downcast_ref::<T>().unwrap().clone();
+                (f)(&mut style);
+                Rc::new(style)
+            }),
+        );
+
+        self.invalidate_derived(T::ID);
+
+        true
+    }
+
+    /// A shorthand for creating a derived class with
+    /// [`StyleSystem::derive_class`] and overriding the style of type `T`
+    /// with [`StyleSystem::set_override`].
+    ///
+    /// Returns `None` if every class ID is in use.
+    pub fn override_class<T: ElementStyle + Clone>(
+        &mut self,
+        base_class: ClassID,
+        f: impl Fn(&mut T) + 'static,
+    ) -> Option<ClassID> {
+        let class = self.derive_class(base_class)?;
+        self.set_override(class, f);
+        Some(class)
+    }
+
+    /// Remove a class created with [`StyleSystem::derive_class`].
+    ///
+    /// Returns `true` if the class existed.
+    pub fn remove_derived_class(&mut self, class: ClassID) -> bool {
+        if self.derived_classes.remove(&class).is_none() {
+            return false;
+        }
+
+        self.styles.retain(|k, _| k.class != class);
+        self.auto_disabled_styles.retain(|k, _| k.class != class);
+
+        self.free_derived_classes.push(class);
+
+        true
+    }
+
+    fn allocate_derived_class(&mut self) -> Option<ClassID> {
+        while let Some(class) = self.free_derived_classes.pop() {
+            if !self.class_in_use(class) {
+                return Some(class);
+            }
+        }
+
+        while self.next_derived_class > CLASS_DEFAULT {
+            let class = self.next_derived_class;
+            self.next_derived_class -= 1;
+
+            if !self.class_in_use(class) {
+                return Some(class);
+            }
+        }
+
+        None
+    }
+
+    /// Whether the given class ID is a derived class or has any styles added
+    /// to it.
+    fn class_in_use(&self, class: ClassID) -> bool {
+        self.derived_classes.contains_key(&class) || self.styles.keys().any(|k| k.class == class)
+    }
+
+    fn entry<T: ElementStyle>(&mut self, class: ClassID) -> &Rc<dyn Any> {
+        let key = Key {
+            element_type_id: T::ID,
+            class,
+            is_dark_theme: self.use_dark_theme,
+        };
+
+        if !self.styles.contains_key(&key) {
+            let style = self.resolve::<T>(class);
+            self.styles.insert(key, style);
+        }
+
+        self.styles.get(&key).unwrap()
+    }
+
+    /// Same as [`StyleSystem::entry`], but with the auto disabled derivation
+    /// applied (if it is enabled).
+    fn auto_disabled_entry<T: ElementStyle>(&mut self, class: ClassID) -> &Rc<dyn Any> {
+        let Some(derivation) = self.auto_disabled else {
+            return self.entry::<T>(class);
+        };
+
         let key = Key {
             element_type_id: T::ID,
             class,
             is_dark_theme: self.use_dark_theme,
         };
 
-        let entry = self.styles.entry(key);
-        let entry = entry.or_insert_with(|| {
-            Rc::new(if self.use_dark_theme {
+        if !self.auto_disabled_styles.contains_key(&key) {
+            let base = Rc::clone(self.entry::<T>(class));
+            let derived = base
+                .downcast_ref::<T>()
+                .unwrap()
+                .with_auto_disabled(derivation);
+            let style: Rc<dyn Any> = match derived {
+                Some(style) => Rc::new(style),
+                None => base,
+            };
+            self.auto_disabled_styles.insert(key, style);
+        }
+
+        self.auto_disabled_styles.get(&key).unwrap()
+    }
+
+    fn resolve<T: ElementStyle>(&mut self, class: ClassID) -> Rc<dyn Any> {
+        let Some(derived) = self.derived_classes.get(&class) else {
+            return Rc::new(if self.use_dark_theme {
                 T::default_dark_style()
             } else {
                 T::default_light_style()
-            })
-        });
+            });
+        };
 
-        Rc::clone(&entry)
+        let base_class = derived.base_class;
+        let apply = derived.overrides.get(T::ID).cloned();
+
+        let base = Rc::clone(self.entry::<T>(base_class));
+
+        if let Some(apply) = apply {
+            (apply)(base.as_ref())
+        } else {
+            base
+        }
+    }
+
+    /// Remove the cached styles of type `T` for all derived classes so they
+    /// are recomputed from their base classes.
+    fn invalidate_derived(&mut self, element_type_id: &'static str) {
+        self.auto_disabled_styles
+            .retain(|k, _| k.element_type_id != element_type_id);
+
+        if self.derived_classes.is_empty() {
+            return;
+        }
+
+        let derived_classes = &self.derived_classes;
+        self.styles.retain(|k, _| {
+            k.element_type_id != element_type_id || !derived_classes.contains_key(&k.class)
+        });
     }
 }
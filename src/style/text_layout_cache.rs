@@ -0,0 +1,206 @@
+use std::collections::VecDeque;
+use std::hash::{Hash, Hasher};
+
+use rootvg::text::{FontSystem, RcTextBuffer, TextProperties};
+use rustc_hash::{FxHashMap, FxHasher};
+use smallvec::SmallVec;
+
+use super::FontFallback;
+
+/// The default maximum number of text layouts kept in a [`TextLayoutCache`].
+pub const DEFAULT_TEXT_LAYOUT_CACHE_CAPACITY: usize = 4096;
+
+struct CacheEntry {
+    text: String,
+    properties: TextProperties,
+    max_width: Option<f32>,
+    buffer: RcTextBuffer,
+    last_used: u64,
+}
+
+/// A cache of shaped text buffers keyed by their content, their properties,
+/// and their maximum width.
+///
+/// Elements which display text that doesn't change often (i.e. labels and
+/// buttons) get their text buffers from this cache, so repeated text (i.e.
+/// the labels of 32 identical channel strips) is only shaped once.
+///
+/// Buffers returned by this cache are shared and must not be modified.
+///
+/// The cache is cleared automatically when a font is registered, when the
+/// scale factor of a window changes, or when the font fallback chains change.
+/// Call [`TextLayoutCache::clear`] after modifying the font system in any
+/// other way.
+pub struct TextLayoutCache {
+    entries: FxHashMap<u64, SmallVec<[CacheEntry; 1]>>,
+    /// The keys of the entries in the order they were used, along with the
+    /// value of `counter` at that time. An item is stale (and skipped when
+    /// evicting) if its entry has been used again since.
+    lru_order: VecDeque<(u64, u64)>,
+    len: usize,
+    capacity: usize,
+    counter: u64,
+    font_fallback: FontFallback,
+}
+
+impl TextLayoutCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: FxHashMap::default(),
+            lru_order: VecDeque::new(),
+            len: 0,
+            capacity: capacity.max(1),
+            counter: 0,
+            font_fallback: FontFallback::default(),
+        }
+    }
+
+    /// Get the shaped buffer for the given text, or shape it and add it to
+    /// the cache if it doesn't exist.
+    pub fn get(
+        &mut self,
+        text: &str,
+        properties: &TextProperties,
+        max_width: Option<f32>,
+        font_system: &mut FontSystem,
+    ) -> RcTextBuffer {
+        self.counter += 1;
+
+        let key = cache_key(text, properties, max_width);
+
+        if let Some(bucket) = self.entries.get_mut(&key) {
+            if let Some(entry) = bucket.iter_mut().find(|entry| {
+                entry.max_width == max_width
+                    && entry.text == text
+                    && entry.properties == *properties
+            }) {
+                entry.last_used = self.counter;
+                let buffer = entry.buffer.clone();
+                self.mark_used(key);
+                return buffer;
+            }
+        }
+
+        if self.len >= self.capacity {
+            self.evict_least_recently_used();
+        }
+
+        let shaped_properties = self
+            .font_fallback
+            .resolve(text, properties, font_system)
+            .unwrap_or_else(|| properties.clone());
+
+        let buffer =
+            RcTextBuffer::new(text, shaped_properties, max_width, None, false, font_system);
+
+        self.entries.entry(key).or_default().push(CacheEntry {
+            text: text.to_string(),
+            properties: properties.clone(),
+            max_width,
+            buffer: buffer.clone(),
+            last_used: self.counter,
+        });
+        self.len += 1;
+        self.mark_used(key);
+
+        buffer
+    }
+
+    /// Remove all entries from the cache.
+    ///
+    /// Buffers which are currently in use by elements are not affected, but
+    /// new requests will be shaped again.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.lru_order.clear();
+        self.len = 0;
+    }
+
+    /// The chains of fallback fonts used when shaping text.
+    pub fn font_fallback(&self) -> &FontFallback {
+        &self.font_fallback
+    }
+
+    /// Modify the chains of fallback fonts used when shaping text.
+    ///
+    /// This clears the cache.
+    pub(crate) fn font_fallback_mut(&mut self) -> &mut FontFallback {
+        self.clear();
+        &mut self.font_fallback
+    }
+
+    /// The number of text layouts in the cache.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Set the maximum number of text layouts kept in the cache.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity.max(1);
+
+        while self.len > self.capacity {
+            self.evict_least_recently_used();
+        }
+    }
+
+    fn mark_used(&mut self, key: u64) {
+        self.lru_order.push_back((key, self.counter));
+
+        // Drop the stale items once they outnumber the live ones, so that
+        // repeated hits don't grow the queue without bound.
+        if self.lru_order.len() > (self.len * 2).max(64) {
+            let mut live: Vec<(u64, u64)> = self
+                .entries
+                .iter()
+                .flat_map(|(key, bucket)| bucket.iter().map(move |entry| (*key, entry.last_used)))
+                .collect();
+            live.sort_unstable_by_key(|(_, last_used)| *last_used);
+
+            self.lru_order = live.into();
+        }
+    }
+
+    fn evict_least_recently_used(&mut self) {
+        while let Some((key, last_used)) = self.lru_order.pop_front() {
+            let Some(bucket) = self.entries.get_mut(&key) else {
+                continue;
+            };
+            let Some(index) = bucket.iter().position(|entry| entry.last_used == last_used) else {
+                continue;
+            };
+
+            bucket.swap_remove(index);
+            if bucket.is_empty() {
+                self.entries.remove(&key);
+            }
+
+            self.len -= 1;
+            return;
+        }
+    }
+}
+
+impl Default for TextLayoutCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_TEXT_LAYOUT_CACHE_CAPACITY)
+    }
+}
+
+fn cache_key(text: &str, properties: &TextProperties, max_width: Option<f32>) -> u64 {
+    let mut hasher = FxHasher::default();
+
+    text.hash(&mut hasher);
+    properties.metrics.font_size.to_bits().hash(&mut hasher);
+    properties.metrics.line_height.to_bits().hash(&mut hasher);
+    max_width.map(f32::to_bits).hash(&mut hasher);
+
+    hasher.finish()
+}
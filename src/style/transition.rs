@@ -0,0 +1,391 @@
+use std::time::Duration;
+
+use rootvg::color::RGBA8;
+use smallvec::SmallVec;
+
+use super::{Background, BorderStyle, QuadStyle};
+
+/// An easing curve used to animate a style transition.
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Easing {
+    #[default]
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+}
+
+impl Easing {
+    /// Map the given linear progress in the range `[0.0, 1.0]` to the eased
+    /// progress.
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+
+        match self {
+            Self::Linear => t,
+            Self::EaseIn => t * t * t,
+            Self::EaseOut => {
+                let t = 1.0 - t;
+                1.0 - (t * t * t)
+            }
+            Self::EaseInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    let t = -2.0 * t + 2.0;
+                    1.0 - (t * t * t * 0.5)
+                }
+            }
+        }
+    }
+}
+
+/// The duration and easing curve of a style transition.
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Transition {
+    /// The duration of the transition.
+    pub duration: Duration,
+    /// The easing curve of the transition.
+    ///
+    /// By default this is set to `Easing::Linear`.
+    pub easing: Easing,
+}
+
+impl Transition {
+    pub const fn new(duration: Duration, easing: Easing) -> Self {
+        Self { duration, easing }
+    }
+
+    /// The eased progress of this transition after `elapsed_seconds`.
+    pub fn progress(&self, elapsed_seconds: f64) -> f32 {
+        let duration = self.duration.as_secs_f64();
+
+        if duration <= 0.0 {
+            1.0
+        } else {
+            self.easing.apply((elapsed_seconds / duration) as f32)
+        }
+    }
+}
+
+/// An alias for `Transition::new(Duration::from_millis(millis), easing)`
+pub const fn transition_ms(millis: u64, easing: Easing) -> Transition {
+    Transition::new(Duration::from_millis(millis), easing)
+}
+
+/// How to animate the changes in a style when the state of an element
+/// changes (i.e. Idle -> Hovered).
+///
+/// A property group set to `None` will snap to its new value instead of
+/// being animated.
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StyleTransitions {
+    /// The transition of the background.
+    pub background: Option<Transition>,
+    /// The transition of the border color and width.
+    pub border: Option<Transition>,
+    /// The transition of the text and icon colors.
+    pub text: Option<Transition>,
+}
+
+impl StyleTransitions {
+    pub const NONE: Self = Self {
+        background: None,
+        border: None,
+        text: None,
+    };
+
+    /// Use the same transition for all property groups.
+    pub const fn all(transition: Transition) -> Self {
+        Self {
+            background: Some(transition),
+            border: Some(transition),
+            text: Some(transition),
+        }
+    }
+
+    /// Returns `true` if no property groups are animated.
+    pub fn is_none(&self) -> bool {
+        self.background.is_none() && self.border.is_none() && self.text.is_none()
+    }
+
+    fn longest_duration(&self) -> f64 {
+        [self.background, self.border, self.text]
+            .iter()
+            .flatten()
+            .map(|t| t.duration.as_secs_f64())
+            .fold(0.0, f64::max)
+    }
+}
+
+/// The eased progress of each property group in an active transition,
+/// where `0.0` is the old style and `1.0` is the new style.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TransitionProgress {
+    pub background: f32,
+    pub border: f32,
+    pub text: f32,
+}
+
+impl TransitionProgress {
+    pub const DONE: Self = Self {
+        background: 1.0,
+        border: 1.0,
+        text: 1.0,
+    };
+}
+
+/// The maximum number of interrupted transitions which are kept track of. If
+/// a transition is interrupted more times than this while it is still in
+/// progress, then the oldest one is dropped.
+const MAX_INTERRUPTED: usize = 3;
+
+/// The state of an in-progress style transition from a previous state
+/// of type `K` (i.e. a `ButtonState`).
+///
+/// If a transition is interrupted by another state change, then the new
+/// transition starts from the style which was displayed at that moment (see
+/// [`StyleTransitionState::from_style`]), so the style never jumps.
+///
+/// This is meant to be used by the reusable "inner" structs of elements.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StyleTransitionState<K: Copy + PartialEq> {
+    from: Option<K>,
+    /// The states which were being transitioned to when a transition was
+    /// interrupted (oldest first), along with the progress they had reached.
+    interrupted: SmallVec<[(K, TransitionProgress); MAX_INTERRUPTED]>,
+    elapsed_seconds: f64,
+}
+
+impl<K: Copy + PartialEq> Default for StyleTransitionState<K> {
+    fn default() -> Self {
+        Self {
+            from: None,
+            interrupted: SmallVec::new(),
+            elapsed_seconds: 0.0,
+        }
+    }
+}
+
+impl<K: Copy + PartialEq> StyleTransitionState<K> {
+    /// Start a new transition from the state `from`.
+    ///
+    /// If a transition to `from` is still in progress, then the new
+    /// transition starts from the current interpolated style instead.
+    ///
+    /// If `transitions` has no animated property groups or `reduce_motion`
+    /// is `true` (see `StyleSystem::reduce_motion`), then this does nothing.
+    pub fn start(&mut self, from: K, transitions: &StyleTransitions, reduce_motion: bool) {
+        if transitions.is_none() || reduce_motion {
+            self.stop();
+            return;
+        }
+
+        let progress = self.progress(transitions);
+
+        if progress == TransitionProgress::DONE {
+            self.from = Some(from);
+            self.interrupted.clear();
+        } else {
+            if self.interrupted.len() == MAX_INTERRUPTED {
+                self.from = Some(self.interrupted.remove(0).0);
+            }
+
+            self.interrupted.push((from, progress));
+        }
+
+        self.elapsed_seconds = 0.0;
+    }
+
+    /// Advance the transition by the given amount of time.
+    ///
+    /// If `reduce_motion` is `true` (see `StyleSystem::reduce_motion`), then
+    /// the transition is finished immediately.
+    ///
+    /// Returns `true` if the transition is still in progress.
+    pub fn advance(
+        &mut self,
+        delta_seconds: f64,
+        transitions: &StyleTransitions,
+        reduce_motion: bool,
+    ) -> bool {
+        if self.from.is_none() {
+            return false;
+        }
+
+        self.elapsed_seconds += delta_seconds;
+
+        if self.elapsed_seconds >= transitions.longest_duration() || reduce_motion {
+            self.stop();
+        }
+
+        self.from.is_some()
+    }
+
+    /// Stop the current transition (if there is one).
+    pub fn stop(&mut self) {
+        self.from = None;
+        self.interrupted.clear();
+    }
+
+    /// The style this transition is animating from, or `None` if no
+    /// transition is in progress.
+    ///
+    /// * `style` - Returns the style of the given state.
+    /// * `transition_from` - Interpolates from the second style to the first
+    ///   style using the given progress (i.e. `LabelStyle::transition_from`).
+    pub fn from_style<S>(
+        &self,
+        style: impl Fn(K) -> S,
+        transition_from: impl Fn(S, &S, &TransitionProgress) -> S,
+    ) -> Option<S> {
+        let mut from_style = style(self.from?);
+
+        for (state, progress) in self.interrupted.iter() {
+            from_style = transition_from(style(*state), &from_style, progress);
+        }
+
+        Some(from_style)
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.from.is_some()
+    }
+
+    pub fn progress(&self, transitions: &StyleTransitions) -> TransitionProgress {
+        if self.from.is_none() {
+            return TransitionProgress::DONE;
+        }
+
+        let p = |t: Option<Transition>| t.map(|t| t.progress(self.elapsed_seconds)).unwrap_or(1.0);
+
+        TransitionProgress {
+            background: p(transitions.background),
+            border: p(transitions.border),
+            text: p(transitions.text),
+        }
+    }
+}
+
+/// Linearly interpolate between two colors.
+pub fn lerp_color(a: RGBA8, b: RGBA8, t: f32) -> RGBA8 {
+    if t <= 0.0 {
+        return a;
+    } else if t >= 1.0 {
+        return b;
+    }
+
+    let l = |a: u8, b: u8| (a as f32 + ((b as f32 - a as f32) * t)).round() as u8;
+
+    RGBA8::new(l(a.r, b.r), l(a.g, b.g), l(a.b, b.b), l(a.a, b.a))
+}
+
+fn lerp_f32(a: f32, b: f32, t: f32) -> f32 {
+    a + ((b - a) * t)
+}
+
+impl Background {
+    /// Linearly interpolate between two backgrounds.
+    ///
+    /// Only solid backgrounds can be interpolated. Other backgrounds will
+    /// snap to `other` once `t` reaches `0.5`.
+    pub fn lerp(&self, other: &Self, t: f32) -> Self {
+        if let (Self::Solid(a), Self::Solid(b)) = (self, other) {
+            return Self::Solid(lerp_color(*a, *b, t));
+        }
+
+        if t < 0.5 {
+            *self
+        } else {
+            *other
+        }
+    }
+}
+
+impl BorderStyle {
+    /// Linearly interpolate the color and width of two borders.
+    ///
+    /// The radius and per-edge overrides are taken from `other`.
+    pub fn lerp(&self, other: &Self, t: f32) -> Self {
+        Self {
+            color: lerp_color(self.color, other.color, t),
+            width: lerp_f32(self.width, other.width, t),
+            radius: other.radius,
+            edges: other.edges,
+        }
+    }
+}
+
+impl QuadStyle {
+    /// Interpolate between two quad styles using the given transition
+    /// progress.
+    pub fn transition_from(&self, from: &Self, progress: &TransitionProgress) -> Self {
+        Self {
+            bg: from.bg.lerp(&self.bg, progress.background),
+            border: from.border.lerp(&self.border, progress.border),
+            flags: self.flags,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRANSITIONS: StyleTransitions =
+        StyleTransitions::all(Transition::new(Duration::from_millis(1000), Easing::Linear));
+
+    fn lerp(to: f32, from: &f32, progress: &TransitionProgress) -> f32 {
+        lerp_f32(*from, to, progress.background)
+    }
+
+    /// The displayed value when animating to `target`, where the style of a
+    /// state is its value.
+    fn value(transition: &StyleTransitionState<u8>, target: u8) -> f32 {
+        let style = |state: u8| f32::from(state);
+
+        match transition.from_style(style, lerp) {
+            Some(from) => lerp(style(target), &from, &transition.progress(&TRANSITIONS)),
+            None => style(target),
+        }
+    }
+
+    #[test]
+    fn test_interrupted_transition_does_not_jump() {
+        let mut transition = StyleTransitionState::default();
+
+        // 0 -> 4
+        transition.start(0, &TRANSITIONS, false);
+        transition.advance(0.5, &TRANSITIONS, false);
+        assert_eq!(value(&transition, 4), 2.0);
+
+        // Reverse halfway through: 4 -> 0
+        transition.start(4, &TRANSITIONS, false);
+        assert_eq!(value(&transition, 0), 2.0);
+
+        transition.advance(0.5, &TRANSITIONS, false);
+        assert_eq!(value(&transition, 0), 1.0);
+
+        // Interrupt again: 0 -> 8
+        transition.start(0, &TRANSITIONS, false);
+        assert_eq!(value(&transition, 8), 1.0);
+
+        assert!(!transition.advance(1.0, &TRANSITIONS, false));
+        assert_eq!(value(&transition, 8), 8.0);
+    }
+
+    #[test]
+    fn test_interrupt_after_finished_starts_from_state() {
+        let mut transition = StyleTransitionState::default();
+
+        transition.start(0, &TRANSITIONS, false);
+        assert!(!transition.advance(1.0, &TRANSITIONS, false));
+
+        transition.start(4, &TRANSITIONS, false);
+        assert_eq!(value(&transition, 0), 4.0);
+        assert_eq!(transition.interrupted.len(), 0);
+    }
+}
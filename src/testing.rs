@@ -0,0 +1,561 @@
+//! A harness for testing elements end to end by driving a headless view with
+//! simulated pointer and keyboard input.
+//!
+//! ```ignore
+//! let mut harness = TestHarness::<MyAction>::new(Size::new(200.0, 200.0))?;
+//!
+//! let mut knob = Knob::builder("gain")
+//!     .on_gesture(MyAction::Gain)
+//!     .rect(rect(10.0, 10.0, 50.0, 50.0))
+//!     .build(&mut harness.cx());
+//!
+//! harness.drag(
+//!     Point::new(35.0, 35.0),
+//!     Point::new(35.0, 15.0),
+//!     10,
+//!     PointerButton::Primary,
+//! );
+//!
+//! let actions = harness.take_actions();
+//! assert!(matches!(
+//!     actions.first(),
+//!     Some(MyAction::Gain(ParamUpdate {
+//!         gesture_state: Some(GestureState::GestureStarted),
+//!         ..
+//!     }))
+//! ));
+//! assert!(knob.normal_value() > 0.0);
+//! ```
+//!
+//! With the `snapshot` feature enabled, rendered frames can also be compared
+//! against reference png images to catch visual regressions (see
+//! [`TestHarness::assert_snapshot`]).
+//!
+//! This requires the `headless` feature.
+
+use std::fmt::Debug;
+use std::time::Duration;
+
+use keyboard_types::{Code, CompositionEvent, CompositionState, KeyState, Modifiers};
+
+use crate::action_queue::{action_channel, ActionReceiver, ActionSender};
+use crate::element_system::element::ElementHandle;
+use crate::element_system::ElementID;
+use crate::event::{KeyboardEvent, PointerButton, WheelDeltaType};
+use crate::math::{Point, ScaleFactor, Size};
+use crate::prelude::ResourceCtx;
+use crate::window::{
+    CapturedFrame, HeadlessError, HeadlessWindow, WindowConfig, WindowContext, MAIN_WINDOW,
+};
+use crate::CursorIcon;
+
+#[cfg(feature = "snapshot")]
+mod snapshot;
+#[cfg(feature = "snapshot")]
+pub use snapshot::{
+    check_snapshot, compare_frames, FrameDiff, SnapshotConfig, SnapshotError,
+    UPDATE_SNAPSHOTS_ENV_VAR,
+};
+
+/// The default amount of time that [`TestHarness::advance`] advances
+/// animations by in a single frame.
+pub const DEFAULT_FRAME_DURATION: Duration = Duration::from_millis(16);
+
+/// Drives a headless view with simulated input and collects the actions
+/// emitted by its elements.
+///
+/// All input methods process any pending updates both before and after the
+/// input is sent, so the state of elements and handles can be inspected
+/// directly after each call.
+pub struct TestHarness<A: Clone + 'static> {
+    window: HeadlessWindow<A>,
+    res: ResourceCtx,
+    action_sender: ActionSender<A>,
+    action_receiver: ActionReceiver<A>,
+    actions: Vec<A>,
+    modifiers: Modifiers,
+}
+
+impl<A: Clone + 'static> TestHarness<A> {
+    /// Create a new test harness with a view of the given logical size, a
+    /// scale factor of `1.0`, and the default dark theme.
+    pub fn new(size: Size) -> Result<Self, HeadlessError> {
+        Self::with_config(
+            &WindowConfig {
+                size,
+                ..Default::default()
+            },
+            ScaleFactor::from(1.0f64),
+            ResourceCtx::new(true),
+        )
+    }
+
+    /// Create a new test harness with a custom configuration.
+    pub fn with_config(
+        config: &WindowConfig,
+        scale_factor: ScaleFactor,
+        mut res: ResourceCtx,
+    ) -> Result<Self, HeadlessError> {
+        let (action_sender, action_receiver) = action_channel();
+
+        let window = HeadlessWindow::new(
+            MAIN_WINDOW,
+            config,
+            scale_factor,
+            action_sender.clone(),
+            &mut res,
+        )?;
+
+        Ok(Self {
+            window,
+            res,
+            action_sender,
+            action_receiver,
+            actions: Vec::new(),
+            modifiers: Modifiers::empty(),
+        })
+    }
+
+    /// The context used to build and modify elements.
+    pub fn cx(&mut self) -> WindowContext<'_, A> {
+        self.window.context(
+            &mut self.res,
+            &mut self.action_sender,
+            &mut self.action_receiver,
+        )
+    }
+
+    /// The global resource context.
+    pub fn res(&mut self) -> &mut ResourceCtx {
+        &mut self.res
+    }
+
+    /// Process all pending updates to the elements and collect the emitted
+    /// actions.
+    pub fn update(&mut self) {
+        while self.window.state_mut().process_updates(&mut self.res) {}
+
+        self.actions.extend(self.action_receiver.try_iter());
+    }
+
+    /// Set the modifier keys which are held down for all following input.
+    pub fn set_modifiers(&mut self, modifiers: Modifiers) {
+        self.modifiers = modifiers;
+        self.window.state_mut().set_modifiers(modifiers);
+    }
+
+    /// Move the pointer to the given position in logical points.
+    pub fn move_pointer(&mut self, position: Point) {
+        self.update();
+
+        let state = self.window.state_mut();
+        let physical_pos = crate::math::to_physical_point(position, state.scale_factor);
+        state.handle_pointer_moved(physical_pos, &mut self.res);
+
+        self.update();
+    }
+
+    /// Move the pointer outside of the view.
+    pub fn pointer_left(&mut self) {
+        self.update();
+        self.window.state_mut().handle_pointer_left(&mut self.res);
+        self.update();
+    }
+
+    /// Press the given pointer button at the current pointer position.
+    pub fn press(&mut self, button: PointerButton) {
+        self.update();
+        self.window
+            .state_mut()
+            .handle_mouse_button(button, true, &mut self.res);
+        self.update();
+    }
+
+    /// Release the given pointer button at the current pointer position.
+    pub fn release(&mut self, button: PointerButton) {
+        self.update();
+        self.window
+            .state_mut()
+            .handle_mouse_button(button, false, &mut self.res);
+        self.update();
+    }
+
+    /// Move the pointer to the given position and click the primary button.
+    pub fn click(&mut self, position: Point) {
+        self.move_pointer(position);
+        self.press(PointerButton::Primary);
+        self.release(PointerButton::Primary);
+    }
+
+    /// Move the pointer to the given position and double-click the primary
+    /// button.
+    pub fn double_click(&mut self, position: Point) {
+        self.click(position);
+        self.press(PointerButton::Primary);
+        self.release(PointerButton::Primary);
+    }
+
+    /// Press the given button at `from`, move the pointer to `to` in the
+    /// given number of steps, and then release the button.
+    pub fn drag(&mut self, from: Point, to: Point, steps: usize, button: PointerButton) {
+        self.move_pointer(from);
+        self.press(button);
+
+        let steps = steps.max(1);
+        for i in 1..=steps {
+            let t = i as f32 / steps as f32;
+            self.move_pointer(from.lerp(to, t));
+        }
+
+        self.release(button);
+    }
+
+    /// Move the pointer to the given position and scroll the mouse wheel.
+    pub fn scroll(&mut self, position: Point, delta: WheelDeltaType) {
+        self.move_pointer(position);
+        self.window
+            .state_mut()
+            .handle_mouse_wheel(delta, &mut self.res);
+        self.update();
+    }
+
+    /// Send a key down event with the current modifiers.
+    pub fn key_down(&mut self, code: Code) {
+        self.key_event(code, KeyState::Down);
+    }
+
+    /// Send a key up event with the current modifiers.
+    pub fn key_up(&mut self, code: Code) {
+        self.key_event(code, KeyState::Up);
+    }
+
+    /// Press and release a key with the current modifiers.
+    pub fn key_press(&mut self, code: Code) {
+        self.key_down(code);
+        self.key_up(code);
+    }
+
+    /// Press and release a key while holding the given modifiers.
+    pub fn key_press_with_modifiers(&mut self, code: Code, modifiers: Modifiers) {
+        let prev_modifiers = self.modifiers;
+
+        self.set_modifiers(modifiers);
+        self.key_press(code);
+        self.set_modifiers(prev_modifiers);
+    }
+
+    /// Type the given text into the focused element.
+    pub fn type_text(&mut self, text: &str) {
+        self.update();
+
+        let state = self.window.state_mut();
+        state.handle_text_composition_event(
+            CompositionEvent {
+                state: CompositionState::Start,
+                data: String::new(),
+            },
+            &mut self.res,
+        );
+        state.handle_text_composition_event(
+            CompositionEvent {
+                state: CompositionState::End,
+                data: text.into(),
+            },
+            &mut self.res,
+        );
+
+        self.update();
+    }
+
+    /// Advance animations by a single frame of the given duration.
+    pub fn advance(&mut self, dt: Duration) {
+        self.update();
+        self.window
+            .state_mut()
+            .on_animation_tick(dt.as_secs_f64(), &mut self.res);
+        self.update();
+    }
+
+    /// Advance animations in frames of `DEFAULT_FRAME_DURATION` until no
+    /// elements are animating anymore, or until `max_duration` has passed.
+    ///
+    /// Returns `true` if all animations have finished.
+    pub fn advance_until_idle(&mut self, max_duration: Duration) -> bool {
+        let mut elapsed = Duration::ZERO;
+
+        while self.window.state().has_active_animations() {
+            if elapsed >= max_duration {
+                return false;
+            }
+
+            self.advance(DEFAULT_FRAME_DURATION);
+            elapsed += DEFAULT_FRAME_DURATION;
+        }
+
+        true
+    }
+
+    /// The actions which have been emitted since the last call to
+    /// [`TestHarness::take_actions`].
+    pub fn actions(&self) -> &[A] {
+        &self.actions
+    }
+
+    /// Take all actions which have been emitted since the last call to this
+    /// method.
+    pub fn take_actions(&mut self) -> Vec<A> {
+        self.update();
+        std::mem::take(&mut self.actions)
+    }
+
+    /// Clear all collected actions.
+    pub fn clear_actions(&mut self) {
+        self.update();
+        self.actions.clear();
+    }
+
+    /// Panics if the given action has not been emitted since the last call
+    /// to [`TestHarness::take_actions`].
+    #[track_caller]
+    pub fn assert_emitted(&self, action: &A)
+    where
+        A: PartialEq + Debug,
+    {
+        assert!(
+            self.actions.contains(action),
+            "expected action {:?} to be emitted, emitted actions: {:?}",
+            action,
+            &self.actions
+        );
+    }
+
+    /// Panics if any actions have been emitted since the last call to
+    /// [`TestHarness::take_actions`].
+    #[track_caller]
+    pub fn assert_no_actions(&self)
+    where
+        A: Debug,
+    {
+        assert!(
+            self.actions.is_empty(),
+            "expected no actions to be emitted, emitted actions: {:?}",
+            &self.actions
+        );
+    }
+
+    /// Returns `true` if the pointer is currently hovering over the element.
+    pub fn is_hovered(&self, element: &ElementHandle) -> bool {
+        self.window
+            .state()
+            .element_system
+            .element_is_hovered(element)
+    }
+
+    /// Returns `true` if the element currently has focus.
+    pub fn is_focused(&self, element: &ElementHandle) -> bool {
+        self.focused_element() == Some(element.id())
+    }
+
+    /// The element which currently has focus (if any).
+    pub fn focused_element(&self) -> Option<ElementID> {
+        self.window.state().element_system.focused_element()
+    }
+
+    /// The cursor icon requested by the element under the pointer.
+    pub fn cursor_icon(&self) -> CursorIcon {
+        self.window.state().element_system.cursor_icon()
+    }
+
+    /// Process all pending updates, render the view, and return the rendered
+    /// pixels.
+    pub fn render(&mut self) -> CapturedFrame {
+        self.update();
+        self.window.render(&mut self.res)
+    }
+
+    fn key_event(&mut self, code: Code, state: KeyState) {
+        self.update();
+
+        let modifiers = self.modifiers;
+        self.window.state_mut().handle_keyboard_event(
+            KeyboardEvent {
+                state,
+                code,
+                modifiers,
+                ..Default::default()
+            },
+            &mut self.res,
+        );
+
+        self.update();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element_system::element::ElementTransform;
+    use crate::elements::button::Button;
+    use crate::elements::virtual_slider::knob::Knob;
+    use crate::elements::virtual_slider::{GestureState, ParamUpdate};
+    use crate::math::Rect;
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum Action {
+        Select(u32),
+        Gain(ParamUpdate),
+    }
+
+    /// Panics if there is no GPU adapter available to run the test. The tests
+    /// which use this are ignored by default, so run them with
+    /// `cargo test --features headless -- --ignored`.
+    fn harness() -> TestHarness<Action> {
+        TestHarness::new(Size::new(200.0, 200.0))
+            .unwrap_or_else(|e| panic!("failed to create headless test harness: {}", e))
+    }
+
+    fn rect(x: f32, y: f32, width: f32, height: f32) -> Rect {
+        Rect::new(Point::new(x, y), Size::new(width, height))
+    }
+
+    #[test]
+    #[ignore = "requires a GPU adapter"]
+    fn test_button_click() {
+        let mut harness = harness();
+
+        let _button = Button::builder()
+            .text("Ok")
+            .on_select(Action::Select(0))
+            .rect(rect(10.0, 10.0, 80.0, 30.0))
+            .build(&mut harness.cx());
+
+        harness.click(Point::new(50.0, 25.0));
+        assert_eq!(harness.take_actions(), vec![Action::Select(0)]);
+
+        harness.click(Point::new(150.0, 150.0));
+        harness.assert_no_actions();
+    }
+
+    #[test]
+    #[ignore = "requires a GPU adapter"]
+    fn test_click_scaled_button_outside_layout_bounds() {
+        let mut harness = harness();
+
+        let mut button = Button::builder()
+            .text("Ok")
+            .on_select(Action::Select(0))
+            .rect(rect(60.0, 60.0, 40.0, 20.0))
+            .build(&mut harness.cx());
+
+        // Scaled around its center, the button covers (40, 50) to (120, 90).
+        button.set_transform(Some(ElementTransform::scale(2.0, 2.0)));
+        harness.update();
+
+        harness.click(Point::new(45.0, 70.0));
+        assert_eq!(harness.take_actions(), vec![Action::Select(0)]);
+
+        harness.click(Point::new(130.0, 70.0));
+        harness.assert_no_actions();
+    }
+
+    #[test]
+    #[ignore = "requires a GPU adapter"]
+    fn test_flush_during_batch() {
+        let mut harness = harness();
+
+        let mut button = Button::builder()
+            .text("Ok")
+            .on_select(Action::Select(0))
+            .rect(rect(10.0, 10.0, 40.0, 20.0))
+            .build(&mut harness.cx());
+        harness.update();
+
+        harness.window.state_mut().element_system.begin_batch();
+
+        button.set_rect(rect(60.0, 10.0, 40.0, 20.0));
+        // Process the modifications in the middle of the batch.
+        harness.update();
+
+        button.set_rect(rect(110.0, 10.0, 40.0, 20.0));
+        button.set_rect(rect(10.0, 110.0, 40.0, 20.0));
+
+        harness.window.state_mut().element_system.end_batch();
+        harness.update();
+
+        harness.click(Point::new(130.0, 20.0));
+        harness.assert_no_actions();
+
+        harness.click(Point::new(30.0, 120.0));
+        assert_eq!(harness.take_actions(), vec![Action::Select(0)]);
+    }
+
+    #[test]
+    #[ignore = "requires a GPU adapter"]
+    fn test_disabled_button_is_skipped_in_tab_order() {
+        let mut harness = harness();
+
+        let mut first = Button::builder()
+            .text("First")
+            .on_select(Action::Select(0))
+            .rect(rect(10.0, 10.0, 80.0, 30.0))
+            .disabled(true)
+            .build(&mut harness.cx());
+        let mut second = Button::builder()
+            .text("Second")
+            .on_select(Action::Select(1))
+            .rect(rect(10.0, 50.0, 80.0, 30.0))
+            .build(&mut harness.cx());
+
+        harness.key_press(Code::Tab);
+        assert_eq!(harness.focused_element(), Some(second.id()));
+
+        harness.key_press(Code::Enter);
+        assert_eq!(harness.take_actions(), vec![Action::Select(1)]);
+
+        // Disabling the focused element releases focus.
+        second.set_disabled(true);
+        harness.update();
+        assert_eq!(harness.focused_element(), None);
+
+        first.set_disabled(false);
+        harness.key_press(Code::Tab);
+        assert_eq!(harness.focused_element(), Some(first.id()));
+    }
+
+    #[test]
+    #[ignore = "requires a GPU adapter"]
+    fn test_knob_gesture() {
+        let mut harness = harness();
+
+        let knob = Knob::builder("gain")
+            .on_gesture(Action::Gain)
+            .rect(rect(10.0, 10.0, 50.0, 50.0))
+            .build(&mut harness.cx());
+
+        harness.drag(
+            Point::new(35.0, 35.0),
+            Point::new(35.0, 15.0),
+            10,
+            PointerButton::Primary,
+        );
+
+        let gesture_states: Vec<Option<GestureState>> = harness
+            .take_actions()
+            .into_iter()
+            .map(|action| match action {
+                Action::Gain(update) => update.gesture_state,
+                action => panic!("unexpected action {:?}", action),
+            })
+            .collect();
+
+        assert_eq!(
+            gesture_states.first(),
+            Some(&Some(GestureState::GestureStarted))
+        );
+        assert_eq!(
+            gesture_states.last(),
+            Some(&Some(GestureState::GestureFinished))
+        );
+        assert!(knob.normal_value() > 0.0);
+    }
+}
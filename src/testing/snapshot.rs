@@ -0,0 +1,308 @@
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+
+use crate::window::CapturedFrame;
+
+use super::TestHarness;
+
+/// If this environment variable is set, then [`check_snapshot`] overwrites
+/// the reference images instead of comparing against them.
+pub const UPDATE_SNAPSHOTS_ENV_VAR: &str = "YARROW_UPDATE_SNAPSHOTS";
+
+/// The maximum possible value of the YIQ color delta.
+const MAX_YIQ_DELTA: f32 = 35215.0;
+
+#[derive(thiserror::Error, Debug)]
+pub enum SnapshotError {
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+    #[error("Failed to decode png: {0}")]
+    Decode(#[from] png::DecodingError),
+    #[error("Failed to encode png: {0}")]
+    Encode(#[from] png::EncodingError),
+    #[error("Unsupported png format in {0}, reference images must be 8-bit RGBA")]
+    UnsupportedFormat(PathBuf),
+    #[error("No reference image found at {reference}, the rendered frame was written to {actual} (set YARROW_UPDATE_SNAPSHOTS=1 to accept it)")]
+    MissingReference { reference: PathBuf, actual: PathBuf },
+    #[error("Frame size {actual:?} does not match reference size {expected:?} of {reference}")]
+    SizeMismatch {
+        reference: PathBuf,
+        expected: (u32, u32),
+        actual: (u32, u32),
+    },
+    #[error("{differing_pixels} pixels differ from reference {reference} (max allowed: {max_differing_pixels}), see {diff} for the differences")]
+    Mismatch {
+        reference: PathBuf,
+        diff: PathBuf,
+        differing_pixels: usize,
+        max_differing_pixels: usize,
+    },
+}
+
+/// The tolerances used when comparing a frame against a reference image.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SnapshotConfig {
+    /// The perceptual color difference in the range `[0.0, 1.0]` above which
+    /// two pixels are considered different.
+    ///
+    /// Small values are needed to absorb differences in anti-aliasing and
+    /// rounding between GPUs and drivers.
+    ///
+    /// By default this is set to `0.1`.
+    pub threshold: f32,
+    /// The number of pixels that are allowed to differ before the comparison
+    /// fails.
+    ///
+    /// By default this is set to `0`.
+    pub max_differing_pixels: usize,
+}
+
+impl Default for SnapshotConfig {
+    fn default() -> Self {
+        Self {
+            threshold: 0.1,
+            max_differing_pixels: 0,
+        }
+    }
+}
+
+/// The result of comparing two frames with [`compare_frames`].
+#[derive(Debug, Clone)]
+pub struct FrameDiff {
+    /// The number of pixels whose difference exceeds the threshold.
+    pub differing_pixels: usize,
+    /// The largest perceptual difference of any pixel in the range
+    /// `[0.0, 1.0]`.
+    pub max_delta: f32,
+    /// An image which highlights the differing pixels in red on top of a
+    /// faded grayscale copy of the reference.
+    pub diff_image: CapturedFrame,
+}
+
+impl CapturedFrame {
+    /// Encode the frame as an 8-bit RGBA png file.
+    pub fn save_png(&self, path: impl AsRef<Path>) -> Result<(), SnapshotError> {
+        let path = path.as_ref();
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let writer = BufWriter::new(File::create(path)?);
+
+        let mut encoder = png::Encoder::new(writer, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.pixels)?;
+        writer.finish()?;
+
+        Ok(())
+    }
+
+    /// Decode an 8-bit RGBA png file.
+    pub fn load_png(path: impl AsRef<Path>) -> Result<Self, SnapshotError> {
+        let path = path.as_ref();
+
+        let decoder = png::Decoder::new(BufReader::new(File::open(path)?));
+        let mut reader = decoder.read_info()?;
+
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels)?;
+
+        if info.color_type != png::ColorType::Rgba || info.bit_depth != png::BitDepth::Eight {
+            return Err(SnapshotError::UnsupportedFormat(path.to_path_buf()));
+        }
+
+        pixels.truncate(info.buffer_size());
+
+        Ok(Self {
+            width: info.width,
+            height: info.height,
+            pixels,
+        })
+    }
+}
+
+/// Compare two frames of the same size pixel by pixel.
+///
+/// Pixels are compared by their perceptual difference in the YIQ color
+/// space (after blending them onto a white background), so that changes in
+/// brightness are weighted more heavily than small changes in hue.
+///
+/// Returns `None` if the frames differ in size.
+pub fn compare_frames(
+    expected: &CapturedFrame,
+    actual: &CapturedFrame,
+    threshold: f32,
+) -> Option<FrameDiff> {
+    if expected.width != actual.width || expected.height != actual.height {
+        return None;
+    }
+
+    let mut differing_pixels = 0;
+    let mut max_delta: f32 = 0.0;
+    let mut diff_pixels = Vec::with_capacity(expected.pixels.len());
+
+    for (e, a) in expected
+        .pixels
+        .chunks_exact(4)
+        .zip(actual.pixels.chunks_exact(4))
+    {
+        let delta = if e == a { 0.0 } else { color_delta(e, a) };
+        max_delta = max_delta.max(delta);
+
+        if delta > threshold {
+            differing_pixels += 1;
+            diff_pixels.extend_from_slice(&[255, 0, 0, 255]);
+        } else {
+            let [y, _, _] = rgb_to_yiq(blend_with_white(e));
+            let faded = (255.0 - (255.0 - y) * 0.1) as u8;
+            diff_pixels.extend_from_slice(&[faded, faded, faded, 255]);
+        }
+    }
+
+    Some(FrameDiff {
+        differing_pixels,
+        max_delta,
+        diff_image: CapturedFrame {
+            width: expected.width,
+            height: expected.height,
+            pixels: diff_pixels,
+        },
+    })
+}
+
+/// Compare a rendered frame against the reference png at `reference`.
+///
+/// * If the environment variable [`UPDATE_SNAPSHOTS_ENV_VAR`] is set, then
+/// the reference is (over)written with the frame instead.
+/// * If the reference does not exist, then the frame is written next to it
+/// with a `.new.png` extension so it can be reviewed and accepted.
+/// * If the frame does not match, then the frame is written with a
+/// `.new.png` extension and an image highlighting the differences is written
+/// with a `.diff.png` extension.
+pub fn check_snapshot(
+    frame: &CapturedFrame,
+    reference: impl AsRef<Path>,
+    config: SnapshotConfig,
+) -> Result<(), SnapshotError> {
+    let reference = reference.as_ref();
+    let actual_path = reference.with_extension("new.png");
+    let diff_path = reference.with_extension("diff.png");
+
+    if std::env::var_os(UPDATE_SNAPSHOTS_ENV_VAR).is_some() {
+        frame.save_png(reference)?;
+        remove_if_exists(&actual_path)?;
+        remove_if_exists(&diff_path)?;
+        return Ok(());
+    }
+
+    if !reference.exists() {
+        frame.save_png(&actual_path)?;
+        return Err(SnapshotError::MissingReference {
+            reference: reference.to_path_buf(),
+            actual: actual_path,
+        });
+    }
+
+    let expected = CapturedFrame::load_png(reference)?;
+
+    let Some(diff) = compare_frames(&expected, frame, config.threshold) else {
+        frame.save_png(&actual_path)?;
+        return Err(SnapshotError::SizeMismatch {
+            reference: reference.to_path_buf(),
+            expected: (expected.width, expected.height),
+            actual: (frame.width, frame.height),
+        });
+    };
+
+    if diff.differing_pixels > config.max_differing_pixels {
+        frame.save_png(&actual_path)?;
+        diff.diff_image.save_png(&diff_path)?;
+        return Err(SnapshotError::Mismatch {
+            reference: reference.to_path_buf(),
+            diff: diff_path,
+            differing_pixels: diff.differing_pixels,
+            max_differing_pixels: config.max_differing_pixels,
+        });
+    }
+
+    remove_if_exists(&actual_path)?;
+    remove_if_exists(&diff_path)?;
+
+    Ok(())
+}
+
+impl<A: Clone + 'static> TestHarness<A> {
+    /// Render the view and compare it against the reference png at
+    /// `reference` with the default tolerances.
+    ///
+    /// See [`check_snapshot`] for more details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rendered frame does not match the reference.
+    #[track_caller]
+    pub fn assert_snapshot(&mut self, reference: impl AsRef<Path>) {
+        self.assert_snapshot_with_config(reference, SnapshotConfig::default());
+    }
+
+    /// Render the view and compare it against the reference png at
+    /// `reference` with the given tolerances.
+    ///
+    /// See [`check_snapshot`] for more details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rendered frame does not match the reference.
+    #[track_caller]
+    pub fn assert_snapshot_with_config(
+        &mut self,
+        reference: impl AsRef<Path>,
+        config: SnapshotConfig,
+    ) {
+        let frame = self.render();
+
+        if let Err(e) = check_snapshot(&frame, reference, config) {
+            panic!("snapshot test failed: {}", e);
+        }
+    }
+}
+
+fn remove_if_exists(path: &Path) -> std::io::Result<()> {
+    match std::fs::remove_file(path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+/// The perceptual difference between two RGBA pixels in the range
+/// `[0.0, 1.0]`.
+fn color_delta(a: &[u8], b: &[u8]) -> f32 {
+    let [y1, i1, q1] = rgb_to_yiq(blend_with_white(a));
+    let [y2, i2, q2] = rgb_to_yiq(blend_with_white(b));
+
+    let y = y1 - y2;
+    let i = i1 - i2;
+    let q = q1 - q2;
+
+    ((0.5053 * y * y + 0.299 * i * i + 0.1957 * q * q) / MAX_YIQ_DELTA).min(1.0)
+}
+
+fn blend_with_white(pixel: &[u8]) -> [f32; 3] {
+    let alpha = pixel[3] as f32 / 255.0;
+    let blend = |c: u8| 255.0 + (c as f32 - 255.0) * alpha;
+
+    [blend(pixel[0]), blend(pixel[1]), blend(pixel[2])]
+}
+
+fn rgb_to_yiq([r, g, b]: [f32; 3]) -> [f32; 3] {
+    [
+        r * 0.29889531 + g * 0.58662247 + b * 0.11448223,
+        r * 0.59597799 - g * 0.27417610 - b * 0.32180189,
+        r * 0.21147017 - g * 0.52261711 + b * 0.31114694,
+    ]
+}
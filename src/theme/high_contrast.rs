@@ -0,0 +1,587 @@
+//! A high-contrast theme for users with low vision.
+//!
+//! All text/background and border/background pairs in this theme meet the
+//! WCAG AAA contrast ratio of 7:1 (see
+//! [`contrast_ratio`](super::contrast_ratio)), and every interactive element
+//! shows a thick focus ring when it has keyboard focus.
+
+use rootvg::{quad::QuadFlags, text::Metrics};
+
+use crate::prelude::*;
+
+use super::{contrast_ratio, WCAG_AAA_CONTRAST_RATIO};
+
+pub const TEXT_PADDING: Padding = padding_vh(6.0, 7.0);
+pub const ICON_PADDING: Padding = padding_vh(4.0, 5.0);
+pub const TEXT_ICON_SPACING: f32 = -8.0;
+
+/// 21:1 on `BG_COLOR`
+pub const TEXT_COLOR: RGBA8 = gray(255);
+/// 12.5:1 on `BG_COLOR`
+pub const TEXT_COLOR_DIMMED: RGBA8 = gray(200);
+/// 16.0:1 on `ACCENT_COLOR`
+pub const TEXT_COLOR_ON_ACCENT: RGBA8 = gray(0);
+
+pub const BG_COLOR: RGBA8 = gray(0);
+/// 14.7:1 against `TEXT_COLOR`
+pub const BG_HOVER_COLOR: RGBA8 = gray(40);
+
+/// 16.0:1 on `BG_COLOR`
+pub const ACCENT_COLOR: RGBA8 = RGBA8::new(255, 225, 0, 255);
+pub const ACCENT_HOVER_COLOR: RGBA8 = RGBA8::new(255, 240, 110, 255);
+
+/// 12.7:1 on `BG_COLOR`
+pub const FOCUS_COLOR: RGBA8 = RGBA8::new(0, 220, 255, 255);
+
+pub const BORDER_COLOR: RGBA8 = gray(255);
+pub const BORDER_COLOR_HOVER: RGBA8 = ACCENT_COLOR;
+
+pub const BORDER_WIDTH: f32 = 2.0;
+pub const BORDER_RADIUS: f32 = 2.0;
+pub const FOCUS_RING_WIDTH: f32 = 3.0;
+
+pub fn focus_ring() -> FocusRingStyle {
+    FocusRingStyle {
+        color: FOCUS_COLOR,
+        width: FOCUS_RING_WIDTH,
+        offset: 2.0,
+    }
+}
+
+fn focused() -> FocusedStyle {
+    FocusedStyle {
+        back_border_color: Some(FOCUS_COLOR),
+        ..Default::default()
+    }
+}
+
+fn text_properties(config: &Config) -> TextProperties {
+    TextProperties {
+        metrics: config.text_metrics,
+        attrs: config.text_attrs,
+        ..Default::default()
+    }
+}
+
+pub fn button(config: &Config) -> ButtonStyle {
+    ButtonStyle {
+        text_properties: text_properties(config),
+        text_padding: TEXT_PADDING,
+        icon_padding: ICON_PADDING,
+        default_icon_size: config.default_icon_size,
+        text_icon_spacing: TEXT_ICON_SPACING,
+        text_color: TEXT_COLOR,
+        back_bg: background(BG_COLOR),
+        back_bg_hover: Some(background(BG_HOVER_COLOR)),
+        back_border_color: BORDER_COLOR,
+        back_border_color_hover: Some(BORDER_COLOR_HOVER),
+        back_border_width: BORDER_WIDTH,
+        back_border_radius: config.radius.into(),
+        cursor_icon: Some(CursorIcon::Pointer),
+        focused: focused(),
+        ..Default::default()
+    }
+}
+
+pub fn menu_button(config: &Config) -> ButtonStyle {
+    ButtonStyle {
+        text_properties: text_properties(config),
+        text_padding: TEXT_PADDING,
+        icon_padding: ICON_PADDING,
+        default_icon_size: config.default_icon_size,
+        text_icon_spacing: TEXT_ICON_SPACING,
+        text_color: TEXT_COLOR,
+        text_color_hover: Some(TEXT_COLOR_ON_ACCENT),
+        back_bg_hover: Some(background(config.accent())),
+        back_border_radius: config.radius.into(),
+        cursor_icon: Some(CursorIcon::Pointer),
+        focused: focused(),
+        ..Default::default()
+    }
+}
+
+pub fn toggle_button(config: &Config) -> ToggleButtonStyle {
+    ToggleButtonStyle {
+        text_properties: text_properties(config),
+        text_padding: TEXT_PADDING,
+        icon_padding: ICON_PADDING,
+        text_icon_spacing: TEXT_ICON_SPACING,
+        default_icon_size: config.default_icon_size,
+        text_color: TEXT_COLOR,
+        text_color_on: Some(TEXT_COLOR_ON_ACCENT),
+        back_bg: background(BG_COLOR),
+        back_bg_on: Some(background(config.accent())),
+        back_bg_off_hover: Some(background(BG_HOVER_COLOR)),
+        back_bg_on_hover: Some(background(config.accent_hover())),
+        back_border_color: BORDER_COLOR,
+        back_border_color_on: Some(config.accent()),
+        back_border_color_off_hover: Some(BORDER_COLOR_HOVER),
+        back_border_width: BORDER_WIDTH,
+        back_border_radius: config.radius.into(),
+        cursor_icon: Some(CursorIcon::Pointer),
+        focused: focused(),
+        ..Default::default()
+    }
+}
+
+pub fn switch(config: &Config) -> SwitchStyle {
+    SwitchStyle {
+        outer_border_width: BORDER_WIDTH,
+        outer_border_color_off: BORDER_COLOR,
+        outer_border_color_off_hover: Some(BORDER_COLOR_HOVER),
+        off_bg: background(BG_COLOR),
+        on_bg: Some(background(config.accent())),
+        off_bg_hover: Some(background(BG_HOVER_COLOR)),
+        on_bg_hover: Some(background(config.accent_hover())),
+        slider_bg_off: background(TEXT_COLOR),
+        slider_bg_on: Some(background(TEXT_COLOR_ON_ACCENT)),
+        cursor_icon: Some(CursorIcon::Pointer),
+        ..Default::default()
+    }
+}
+
+pub fn radio_btn(config: &Config) -> RadioButtonStyle {
+    RadioButtonStyle {
+        outer_border_width: BORDER_WIDTH,
+        outer_border_color_off: BORDER_COLOR,
+        outer_border_color_off_hover: Some(BORDER_COLOR_HOVER),
+        off_bg: background(BG_COLOR),
+        on_bg: Some(background(config.accent())),
+        off_bg_hover: Some(background(BG_HOVER_COLOR)),
+        on_bg_hover: Some(background(config.accent_hover())),
+        dot_padding: 6.0,
+        dot_bg: background(TEXT_COLOR_ON_ACCENT),
+        cursor_icon: Some(CursorIcon::Pointer),
+        ..Default::default()
+    }
+}
+
+pub fn resize_handle() -> ResizeHandleStyle {
+    ResizeHandleStyle {
+        drag_handle_color_hover: Some(ACCENT_COLOR),
+        drag_handle_width_hover: Some(4.0),
+        ..Default::default()
+    }
+}
+
+pub fn scroll_bar() -> ScrollBarStyle {
+    ScrollBarStyle {
+        slider_bg: background(TEXT_COLOR_DIMMED),
+        slider_bg_content_hover: Some(background(TEXT_COLOR)),
+        slider_bg_slider_hover: Some(background(ACCENT_COLOR)),
+        radius: 8.0.into(),
+        ..Default::default()
+    }
+}
+
+pub fn text_input(config: &Config) -> TextInputStyle {
+    TextInputStyle {
+        text_properties: text_properties(config),
+        placeholder_text_attrs: Some(config.text_attrs.style(rootvg::text::Style::Italic)),
+        text_color: TEXT_COLOR,
+        text_color_placeholder: Some(TEXT_COLOR_DIMMED),
+        text_color_highlighted: Some(TEXT_COLOR_ON_ACCENT),
+        highlight_bg_color: config.accent(),
+        padding: Padding::new(6.0, 6.0, 6.0, 6.0),
+        highlight_padding: Padding::new(1.0, 0.0, 0.0, 0.0),
+        back_bg: background(BG_COLOR),
+        back_border_color: BORDER_COLOR,
+        back_border_color_hover: Some(BORDER_COLOR_HOVER),
+        back_border_color_focused: Some(FOCUS_COLOR),
+        back_border_width: BORDER_WIDTH,
+        back_border_radius: config.radius.into(),
+        ..Default::default()
+    }
+}
+
+#[cfg(feature = "svg-icons")]
+pub fn icon_text_input(config: &Config) -> IconTextInputStyle {
+    IconTextInputStyle {
+        text_input: text_input(config),
+        default_icon_size: config.default_icon_size,
+        icon_padding: padding(0.0, 0.0, 0.0, 5.0),
+        ..Default::default()
+    }
+}
+
+pub fn tab(config: &Config) -> TabStyle {
+    TabStyle {
+        toggle_btn_style: ToggleButtonStyle {
+            text_properties: text_properties(config),
+            text_padding: TEXT_PADDING,
+            icon_padding: ICON_PADDING,
+            default_icon_size: config.default_icon_size,
+            text_icon_spacing: TEXT_ICON_SPACING,
+            text_color: TEXT_COLOR,
+            back_bg: background(BG_COLOR),
+            back_bg_off_hover: Some(background(BG_HOVER_COLOR)),
+            back_bg_on_hover: Some(background(BG_HOVER_COLOR)),
+            cursor_icon: Some(CursorIcon::Pointer),
+            focused: focused(),
+            ..Default::default()
+        },
+        on_indicator_line_style: QuadStyle {
+            bg: background(config.accent()),
+            border: BorderStyle::default(),
+            flags: QuadFlags::SNAP_ALL_TO_NEAREST_PIXEL,
+        },
+        on_indicator_line_width: 4.0,
+        ..Default::default()
+    }
+}
+
+pub fn tooltip(config: &Config) -> TooltipStyle {
+    TooltipStyle {
+        text_properties: text_properties(config),
+        text_color: TEXT_COLOR,
+        text_padding: TEXT_PADDING,
+        back_quad: QuadStyle {
+            bg: background(BG_COLOR),
+            border: border(BORDER_COLOR, BORDER_WIDTH, config.radius.into()),
+            flags: QuadFlags::SNAP_ALL_TO_NEAREST_PIXEL,
+        },
+        // Don't dim the shortcut hints in the high contrast theme.
+        shortcut_color: Some(TEXT_COLOR),
+        ..Default::default()
+    }
+}
+
+pub fn separator() -> SeparatorStyle {
+    SeparatorStyle {
+        quad_style: QuadStyle {
+            bg: background(TEXT_COLOR_DIMMED),
+            border: BorderStyle::default(),
+            flags: QuadFlags::SNAP_ALL_TO_NEAREST_PIXEL,
+        },
+        ..Default::default()
+    }
+}
+
+pub fn dropdown_menu(config: &Config) -> DropDownMenuStyle {
+    DropDownMenuStyle {
+        text_properties: text_properties(config),
+        icon_size: config.default_icon_size,
+        text_color: TEXT_COLOR,
+        text_color_hover: Some(TEXT_COLOR_ON_ACCENT),
+        back_quad: QuadStyle {
+            bg: background(BG_COLOR),
+            border: border(BORDER_COLOR, BORDER_WIDTH, config.radius.into()),
+            flags: QuadFlags::SNAP_ALL_TO_NEAREST_PIXEL,
+        },
+        entry_bg_quad_hover: QuadStyle {
+            bg: background(config.accent()),
+            border: border_radius_only(config.radius.into()),
+            flags: QuadFlags::SNAP_ALL_TO_NEAREST_PIXEL,
+        },
+        outer_padding: 3.0,
+        left_icon_padding: padding_vh(0.0, 4.0),
+        left_text_padding: padding_vh(5.0, 10.0),
+        left_text_icon_spacing: TEXT_ICON_SPACING,
+        right_text_padding: padding(0.0, 10.0, 0.0, 30.0),
+        divider_color: TEXT_COLOR_DIMMED,
+        divider_width: 1.0,
+        divider_padding: 2.0,
+        cursor_icon: Some(CursorIcon::Pointer),
+        ..Default::default()
+    }
+}
+
+pub fn label(config: &Config) -> LabelStyle {
+    LabelStyle {
+        text_properties: text_properties(config),
+        text_color: TEXT_COLOR,
+        default_icon_size: config.default_icon_size,
+        ..Default::default()
+    }
+}
+
+pub fn hyperlink(config: &Config) -> HyperlinkStyle {
+    HyperlinkStyle {
+        text_properties: text_properties(config),
+        text_color: config.accent(),
+        text_color_hover: Some(config.accent_hover()),
+        text_color_disabled: Some(TEXT_COLOR_DIMMED),
+        // Don't rely on color alone to distinguish links from text.
+        underline: UnderlineMode::Always,
+        underline_thickness: 2.0,
+        ..Default::default()
+    }
+}
+
+pub fn editable_label(config: &Config) -> EditableLabelStyle {
+    EditableLabelStyle {
+        label: label(config),
+        text_input: TextInputStyle {
+            padding: Padding::default(),
+            ..text_input(config)
+        },
+    }
+}
+
+pub fn paragraph(config: &Config) -> ParagraphStyle {
+    ParagraphStyle {
+        text_properties: TextProperties {
+            metrics: config.text_metrics,
+            attrs: config.text_attrs,
+            wrap: rootvg::text::Wrap::WordOrGlyph,
+            shaping: rootvg::text::Shaping::Advanced,
+            ..Default::default()
+        },
+        text_color: TEXT_COLOR,
+        ..Default::default()
+    }
+}
+
+pub fn markdown(config: &Config) -> MarkdownStyle {
+    MarkdownStyle {
+        text_properties: TextProperties {
+            metrics: config.text_metrics,
+            attrs: config.text_attrs,
+            wrap: rootvg::text::Wrap::WordOrGlyph,
+            shaping: rootvg::text::Shaping::Advanced,
+            ..Default::default()
+        },
+        text_color: TEXT_COLOR,
+        link_color: config.accent(),
+        ..Default::default()
+    }
+}
+
+pub fn log_view(config: &Config) -> LogViewStyle {
+    LogViewStyle {
+        text_properties: TextProperties {
+            metrics: config.text_metrics,
+            attrs: config.text_attrs.family(Family::Monospace),
+            wrap: rootvg::text::Wrap::None,
+            ..Default::default()
+        },
+        info_color: TEXT_COLOR,
+        ..Default::default()
+    }
+}
+
+pub fn panel() -> QuadStyle {
+    QuadStyle {
+        bg: background(BG_COLOR),
+        border: Default::default(),
+        flags: QuadFlags::SNAP_ALL_TO_NEAREST_PIXEL,
+    }
+}
+
+pub fn slider_style_modern(config: &Config) -> SliderStyleModern {
+    SliderStyleModern {
+        back_bg: background(BG_COLOR),
+        back_border_color: BORDER_COLOR,
+        back_border_color_hover: Some(BORDER_COLOR_HOVER),
+        back_border_width: BORDER_WIDTH,
+        back_border_radius: config.radius.into(),
+        handle_bg: background(TEXT_COLOR),
+        handle_bg_hover: Some(background(config.accent_hover())),
+        handle_border_radius: config.radius.into(),
+        handle_border_color: BG_COLOR,
+        handle_border_width: 1.0,
+        fill_bg: background(config.accent()),
+        fill_bg_hover: Some(background(config.accent_hover())),
+        handle_height: SizeType::FixedPoints(8.0),
+        handle_padding: Padding::new(3.0, 3.0, 3.0, 3.0),
+        fill_padding: Padding::new(4.0, 6.0, 4.0, 6.0),
+        ..Default::default()
+    }
+}
+
+pub fn knob_style(config: &Config) -> KnobStyle {
+    KnobStyle {
+        back: KnobBackStyle::Quad(KnobBackStyleQuad {
+            bg: background(BG_COLOR),
+            bg_hover: Some(background(BG_HOVER_COLOR)),
+            border_color: BORDER_COLOR,
+            border_color_hover: Some(BORDER_COLOR_HOVER),
+            border_width: BORDER_WIDTH,
+            size: SizeType::Scale(0.7),
+            ..Default::default()
+        }),
+        notch: KnobNotchStyle::Quad(KnobNotchStyleQuad {
+            bg: background(TEXT_COLOR),
+            bg_hover: Some(background(config.accent())),
+            ..Default::default()
+        }),
+        markers: {
+            #[cfg(feature = "tessellation")]
+            {
+                KnobMarkersStyle::Arc(KnobMarkersArcStyle {
+                    fill_bg: background(config.accent()),
+                    fill_bg_hover: Some(background(config.accent_hover())),
+                    back_bg: background(BG_HOVER_COLOR),
+                    ..Default::default()
+                })
+            }
+
+            #[cfg(not(feature = "tessellation"))]
+            {
+                KnobMarkersStyle::None
+            }
+        },
+        ..Default::default()
+    }
+}
+
+pub struct Config {
+    /// The accent color
+    ///
+    /// If this color doesn't meet the WCAG AAA contrast ratio against the
+    /// background and the text drawn on top of it, then [`ACCENT_COLOR`] is
+    /// used instead (see [`Config::accent`]).
+    pub accent_color: RGBA8,
+    /// The accent color when hovered
+    ///
+    /// If this color doesn't meet the WCAG AAA contrast ratio against the
+    /// background and the text drawn on top of it, then
+    /// [`ACCENT_HOVER_COLOR`] is used instead (see [`Config::accent_hover`]).
+    pub accent_color_hover: RGBA8,
+    pub radius: f32,
+    pub text_metrics: Metrics,
+    pub text_attrs: Attrs<'static>,
+    pub default_icon_size: f32,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            accent_color: ACCENT_COLOR,
+            accent_color_hover: ACCENT_HOVER_COLOR,
+            radius: BORDER_RADIUS,
+            text_metrics: Metrics {
+                font_size: 16.0,
+                line_height: 19.0,
+            },
+            text_attrs: Attrs::new(),
+            default_icon_size: crate::theme::DEFAULT_ICON_SIZE,
+        }
+    }
+}
+
+impl Config {
+    /// The accent color used by the theme.
+    ///
+    /// This is `accent_color` if it meets the WCAG AAA contrast ratio, and
+    /// [`ACCENT_COLOR`] otherwise.
+    pub fn accent(&self) -> RGBA8 {
+        if meets_accent_contrast(self.accent_color) {
+            self.accent_color
+        } else {
+            ACCENT_COLOR
+        }
+    }
+
+    /// The hovered accent color used by the theme.
+    ///
+    /// This is `accent_color_hover` if it meets the WCAG AAA contrast ratio,
+    /// and [`ACCENT_HOVER_COLOR`] otherwise.
+    pub fn accent_hover(&self) -> RGBA8 {
+        if meets_accent_contrast(self.accent_color_hover) {
+            self.accent_color_hover
+        } else {
+            ACCENT_HOVER_COLOR
+        }
+    }
+}
+
+/// The accent color is used both as a background for `TEXT_COLOR_ON_ACCENT`,
+/// and as a text and border color on top of `BG_COLOR` and `BG_HOVER_COLOR`.
+fn meets_accent_contrast(color: RGBA8) -> bool {
+    [TEXT_COLOR_ON_ACCENT, BG_COLOR, BG_HOVER_COLOR]
+        .into_iter()
+        .all(|c| contrast_ratio(color, c) >= WCAG_AAA_CONTRAST_RATIO)
+}
+
+/// Load the high-contrast theme for both the dark and light variants of all
+/// default element classes.
+///
+/// This also sets the focus ring of the style system.
+pub fn load(config: Config, res: &mut ResourceCtx) {
+    for dark in [true, false] {
+        let s = &mut res.style_system;
+
+        s.add(ClassID::default(), dark, button(&config));
+        s.add(ClassID::default(), dark, toggle_button(&config));
+        s.add(ClassID::default(), dark, switch(&config));
+        s.add(ClassID::default(), dark, radio_btn(&config));
+        s.add(ClassID::default(), dark, resize_handle());
+        s.add(ClassID::default(), dark, scroll_bar());
+        s.add(ClassID::default(), dark, text_input(&config));
+        s.add(ClassID::default(), dark, tab(&config));
+        s.add(ClassID::default(), dark, tooltip(&config));
+        s.add(ClassID::default(), dark, separator());
+        s.add(ClassID::default(), dark, dropdown_menu(&config));
+        s.add(ClassID::default(), dark, label(&config));
+        s.add(ClassID::default(), dark, paragraph(&config));
+        s.add(ClassID::default(), dark, hyperlink(&config));
+        s.add(ClassID::default(), dark, editable_label(&config));
+        s.add(ClassID::default(), dark, markdown(&config));
+        s.add(ClassID::default(), dark, log_view(&config));
+        s.add(CLASS_PANEL, dark, panel());
+        s.add(CLASS_MENU, dark, menu_button(&config));
+        s.add(
+            ClassID::default(),
+            dark,
+            SliderStyle::Modern(slider_style_modern(&config)),
+        );
+        s.add(ClassID::default(), dark, knob_style(&config));
+
+        #[cfg(feature = "svg-icons")]
+        s.add(ClassID::default(), dark, icon_text_input(&config));
+    }
+
+    res.style_system.set_focus_ring(Some(focus_ring()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_aaa(fg: RGBA8, bg: RGBA8) {
+        let ratio = contrast_ratio(fg, bg);
+        assert!(
+            ratio >= WCAG_AAA_CONTRAST_RATIO,
+            "contrast ratio of {:?} on {:?} is {:.2}",
+            fg,
+            bg,
+            ratio
+        );
+    }
+
+    #[test]
+    fn test_text_and_border_colors_meet_aaa() {
+        let config = Config::default();
+
+        for bg in [BG_COLOR, BG_HOVER_COLOR] {
+            assert_aaa(TEXT_COLOR, bg);
+            assert_aaa(TEXT_COLOR_DIMMED, bg);
+            assert_aaa(BORDER_COLOR, bg);
+            assert_aaa(BORDER_COLOR_HOVER, bg);
+            assert_aaa(FOCUS_COLOR, bg);
+            assert_aaa(config.accent(), bg);
+            assert_aaa(config.accent_hover(), bg);
+        }
+
+        for bg in [config.accent(), config.accent_hover()] {
+            assert_aaa(TEXT_COLOR_ON_ACCENT, bg);
+        }
+    }
+
+    #[test]
+    fn test_low_contrast_accent_color_is_replaced() {
+        let config = Config {
+            accent_color: crate::theme::DEFAULT_ACCENT_COLOR,
+            accent_color_hover: crate::theme::DEFAULT_ACCENT_HOVER_COLOR,
+            ..Default::default()
+        };
+
+        assert_eq!(config.accent(), ACCENT_COLOR);
+        assert_eq!(config.accent_hover(), ACCENT_HOVER_COLOR);
+
+        let style = menu_button(&config);
+        assert_eq!(style.back_bg_hover, Some(background(ACCENT_COLOR)));
+        assert_aaa(style.text_color_hover.unwrap(), ACCENT_COLOR);
+    }
+}
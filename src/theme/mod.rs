@@ -1,8 +1,64 @@
 use rootvg::color::RGBA8;
 
+pub mod high_contrast;
 pub mod yarrow_dark;
 
 pub const DEFAULT_ACCENT_COLOR: RGBA8 = RGBA8::new(179, 123, 95, 255);
 pub const DEFAULT_ACCENT_HOVER_COLOR: RGBA8 = RGBA8::new(200, 137, 106, 255);
 pub const DEFAULT_DISABLED_ALPHA_MULTIPLIER: f32 = 0.5;
 pub const DEFAULT_ICON_SIZE: f32 = 20.0;
+
+/// The minimum contrast ratio for normal text to meet WCAG level AA.
+pub const WCAG_AA_CONTRAST_RATIO: f32 = 4.5;
+/// The minimum contrast ratio for normal text to meet WCAG level AAA.
+pub const WCAG_AAA_CONTRAST_RATIO: f32 = 7.0;
+
+/// The relative luminance of a color as defined by WCAG 2.x (ignoring
+/// alpha).
+pub fn relative_luminance(c: RGBA8) -> f32 {
+    let channel = |v: u8| {
+        let v = v as f32 / 255.0;
+        if v <= 0.03928 {
+            v / 12.92
+        } else {
+            ((v + 0.055) / 1.055).powf(2.4)
+        }
+    };
+
+    (0.2126 * channel(c.r)) + (0.7152 * channel(c.g)) + (0.0722 * channel(c.b))
+}
+
+/// The WCAG 2.x contrast ratio between two colors in the range
+/// `[1.0, 21.0]` (ignoring alpha).
+pub fn contrast_ratio(a: RGBA8, b: RGBA8) -> f32 {
+    let la = relative_luminance(a);
+    let lb = relative_luminance(b);
+
+    (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contrast_ratio() {
+        let black = RGBA8::new(0, 0, 0, 255);
+        let white = RGBA8::new(255, 255, 255, 255);
+        let gray = RGBA8::new(118, 118, 118, 255);
+
+        assert!((contrast_ratio(black, white) - 21.0).abs() < 0.01);
+        assert!((contrast_ratio(white, white) - 1.0).abs() < 0.0001);
+        assert_eq!(contrast_ratio(white, gray), contrast_ratio(gray, white));
+
+        // #767676 is the lightest gray which passes AA on a white background.
+        assert!(contrast_ratio(white, gray) >= WCAG_AA_CONTRAST_RATIO);
+        assert!(contrast_ratio(white, gray) < WCAG_AAA_CONTRAST_RATIO);
+
+        // Alpha is ignored.
+        assert_eq!(
+            contrast_ratio(RGBA8::new(0, 0, 0, 0), white),
+            contrast_ratio(black, white)
+        );
+    }
+}
@@ -306,6 +306,35 @@ pub fn label(config: &Config) -> LabelStyle {
     }
 }
 
+pub fn hyperlink(config: &Config) -> HyperlinkStyle {
+    HyperlinkStyle {
+        text_properties: TextProperties {
+            metrics: config.text_metrics,
+            attrs: config.text_attrs,
+            ..Default::default()
+        },
+        text_color: config.accent_color,
+        text_color_hover: Some(config.accent_color_hover),
+        text_color_disabled: Some(RGBA8::new(
+            config.accent_color.r,
+            config.accent_color.g,
+            config.accent_color.b,
+            100,
+        )),
+        ..Default::default()
+    }
+}
+
+pub fn editable_label(config: &Config) -> EditableLabelStyle {
+    EditableLabelStyle {
+        label: label(config),
+        text_input: TextInputStyle {
+            padding: Padding::default(),
+            ..text_input(config)
+        },
+    }
+}
+
 pub fn paragraph(config: &Config) -> ParagraphStyle {
     ParagraphStyle {
         text_properties: TextProperties {
@@ -320,6 +349,34 @@ pub fn paragraph(config: &Config) -> ParagraphStyle {
     }
 }
 
+pub fn markdown(config: &Config) -> MarkdownStyle {
+    MarkdownStyle {
+        text_properties: TextProperties {
+            metrics: config.text_metrics,
+            attrs: config.text_attrs,
+            wrap: rootvg::text::Wrap::WordOrGlyph,
+            shaping: rootvg::text::Shaping::Advanced,
+            ..Default::default()
+        },
+        text_color: TEXT_COLOR,
+        link_color: config.accent_color,
+        ..Default::default()
+    }
+}
+
+pub fn log_view(config: &Config) -> LogViewStyle {
+    LogViewStyle {
+        text_properties: TextProperties {
+            metrics: config.text_metrics,
+            attrs: config.text_attrs.family(Family::Monospace),
+            wrap: rootvg::text::Wrap::None,
+            ..Default::default()
+        },
+        info_color: TEXT_COLOR,
+        ..Default::default()
+    }
+}
+
 pub fn panel() -> QuadStyle {
     QuadStyle {
         bg: background(PANEL_BG_COLOR),
@@ -476,6 +533,14 @@ pub fn load(config: Config, res: &mut ResourceCtx) {
         .add(ClassID::default(), true, label(&config));
     res.style_system
         .add(ClassID::default(), true, paragraph(&config));
+    res.style_system
+        .add(ClassID::default(), true, hyperlink(&config));
+    res.style_system
+        .add(ClassID::default(), true, editable_label(&config));
+    res.style_system
+        .add(ClassID::default(), true, markdown(&config));
+    res.style_system
+        .add(ClassID::default(), true, log_view(&config));
     res.style_system.add(CLASS_PANEL, true, panel());
     res.style_system.add(CLASS_MENU, true, menu_button(&config));
     res.style_system.add(
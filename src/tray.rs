@@ -0,0 +1,245 @@
+//! A system tray icon with a menu for standalone applications (i.e. a
+//! metronome which keeps running while its window is closed).
+//!
+//! Clicks on the icon and selected menu items are mapped to actions and sent
+//! through the normal action queue (see `AppContext::create_tray`).
+//!
+//! On Linux this uses `libappindicator`, which requires a running GTK event
+//! loop. This requires the `tray` feature.
+
+use tray_icon::menu::{CheckMenuItem, Menu, MenuEvent, MenuItem, PredefinedMenuItem, Submenu};
+use tray_icon::{Icon, TrayIcon, TrayIconBuilder, TrayIconEvent};
+
+use crate::action_queue::ActionSender;
+
+#[derive(thiserror::Error, Debug)]
+pub enum TrayError {
+    #[error("Invalid tray icon: {0}")]
+    InvalidIcon(#[from] tray_icon::BadIcon),
+    #[error("Failed to build tray menu: {0}")]
+    Menu(#[from] tray_icon::menu::Error),
+    #[error("Failed to create tray icon: {0}")]
+    TrayIcon(#[from] tray_icon::Error),
+}
+
+/// The image of a tray icon.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrayIconImage {
+    pub width: u32,
+    pub height: u32,
+    /// The pixels of the image in row-major order, with four bytes (red,
+    /// green, blue, and alpha) per pixel.
+    pub rgba: Vec<u8>,
+}
+
+impl TrayIconImage {
+    fn to_icon(&self) -> Result<Icon, TrayError> {
+        Icon::from_rgba(self.rgba.clone(), self.width, self.height).map_err(Into::into)
+    }
+}
+
+/// An entry in the menu of a tray icon.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TrayMenuEntry {
+    /// An item which sends [`TrayEvent::MenuItem`] with the given ID when it
+    /// is selected.
+    Item {
+        id: String,
+        label: String,
+        enabled: bool,
+    },
+    /// An item with a checkmark. The checkmark is toggled by the OS when the
+    /// item is selected.
+    Check {
+        id: String,
+        label: String,
+        enabled: bool,
+        checked: bool,
+    },
+    /// A nested menu.
+    Submenu {
+        label: String,
+        enabled: bool,
+        entries: Vec<TrayMenuEntry>,
+    },
+    Separator,
+}
+
+impl TrayMenuEntry {
+    pub fn item(id: impl Into<String>, label: impl Into<String>) -> Self {
+        Self::Item {
+            id: id.into(),
+            label: label.into(),
+            enabled: true,
+        }
+    }
+
+    pub fn check(id: impl Into<String>, label: impl Into<String>, checked: bool) -> Self {
+        Self::Check {
+            id: id.into(),
+            label: label.into(),
+            enabled: true,
+            checked,
+        }
+    }
+
+    pub fn submenu(label: impl Into<String>, entries: Vec<TrayMenuEntry>) -> Self {
+        Self::Submenu {
+            label: label.into(),
+            enabled: true,
+            entries,
+        }
+    }
+}
+
+/// The configuration of a tray icon.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrayConfig {
+    pub icon: TrayIconImage,
+    /// The text shown when hovering over the icon.
+    ///
+    /// By default this is set to `None`.
+    pub tooltip: Option<String>,
+    /// The entries of the menu shown when the icon is clicked (or
+    /// right-clicked on some platforms). If this is empty, then no menu is
+    /// shown.
+    ///
+    /// By default this is empty.
+    pub menu: Vec<TrayMenuEntry>,
+}
+
+impl TrayConfig {
+    pub fn new(icon: TrayIconImage) -> Self {
+        Self {
+            icon,
+            tooltip: None,
+            menu: Vec::new(),
+        }
+    }
+}
+
+/// An event from a tray icon.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TrayEvent {
+    /// The menu item with the given ID was selected.
+    MenuItem(String),
+    /// The icon was clicked with the primary mouse button.
+    Clicked,
+    /// The icon was double-clicked with the primary mouse button.
+    ///
+    /// Currently this is only sent on Windows.
+    DoubleClicked,
+}
+
+pub(crate) struct TrayState<A: Clone + 'static> {
+    icon: TrayIcon,
+    on_event: Box<dyn FnMut(TrayEvent) -> A>,
+}
+
+impl<A: Clone + 'static> TrayState<A> {
+    pub fn new(
+        config: TrayConfig,
+        on_event: Box<dyn FnMut(TrayEvent) -> A>,
+    ) -> Result<Self, TrayError> {
+        let mut builder = TrayIconBuilder::new().with_icon(config.icon.to_icon()?);
+
+        if let Some(tooltip) = &config.tooltip {
+            builder = builder.with_tooltip(tooltip);
+        }
+
+        if !config.menu.is_empty() {
+            let menu = Menu::new();
+            for entry in config.menu.iter() {
+                append_entry(entry, |item| menu.append(item))?;
+            }
+
+            builder = builder.with_menu(Box::new(menu));
+        }
+
+        Ok(Self {
+            icon: builder.build()?,
+            on_event,
+        })
+    }
+
+    pub fn set_tooltip(&mut self, tooltip: Option<&str>) -> Result<(), TrayError> {
+        self.icon.set_tooltip(tooltip).map_err(Into::into)
+    }
+
+    pub fn set_icon(&mut self, icon: &TrayIconImage) -> Result<(), TrayError> {
+        self.icon
+            .set_icon(Some(icon.to_icon()?))
+            .map_err(Into::into)
+    }
+
+    pub fn set_visible(&mut self, visible: bool) -> Result<(), TrayError> {
+        self.icon.set_visible(visible).map_err(Into::into)
+    }
+
+    /// Send the actions for all tray events received since the last call.
+    pub fn poll_events(&mut self, action_sender: &mut ActionSender<A>) {
+        while let Ok(event) = MenuEvent::receiver().try_recv() {
+            let action = (self.on_event)(TrayEvent::MenuItem(event.id.0));
+            let _ = action_sender.send(action);
+        }
+
+        while let Ok(event) = TrayIconEvent::receiver().try_recv() {
+            let event = match event {
+                TrayIconEvent::Click {
+                    button: tray_icon::MouseButton::Left,
+                    button_state: tray_icon::MouseButtonState::Up,
+                    ..
+                } => TrayEvent::Clicked,
+                TrayIconEvent::DoubleClick {
+                    button: tray_icon::MouseButton::Left,
+                    ..
+                } => TrayEvent::DoubleClicked,
+                _ => continue,
+            };
+
+            let _ = action_sender.send((self.on_event)(event));
+        }
+    }
+}
+
+fn append_entry(
+    entry: &TrayMenuEntry,
+    mut append: impl FnMut(&dyn tray_icon::menu::IsMenuItem) -> tray_icon::menu::Result<()>,
+) -> Result<(), TrayError> {
+    match entry {
+        TrayMenuEntry::Item { id, label, enabled } => {
+            append(&MenuItem::with_id(id.as_str(), label, *enabled, None))?;
+        }
+        TrayMenuEntry::Check {
+            id,
+            label,
+            enabled,
+            checked,
+        } => {
+            append(&CheckMenuItem::with_id(
+                id.as_str(),
+                label,
+                *enabled,
+                *checked,
+                None,
+            ))?;
+        }
+        TrayMenuEntry::Submenu {
+            label,
+            enabled,
+            entries,
+        } => {
+            let submenu = Submenu::new(label, *enabled);
+            for entry in entries.iter() {
+                append_entry(entry, |item| submenu.append(item))?;
+            }
+
+            append(&submenu)?;
+        }
+        TrayMenuEntry::Separator => {
+            append(&PredefinedMenuItem::separator())?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,523 @@
+//! An undo/redo stack for parameter gestures and arbitrary user commands.
+//!
+//! Parameter gestures are recorded by passing every [`ParamUpdate`] emitted
+//! by knobs and sliders to [`UndoStack::record_param_update`]. All of the
+//! updates of a single gesture (from `GestureStarted` to `GestureFinished`)
+//! are coalesced into a single undo entry.
+//!
+//! The stack needs to know the value of a parameter from before a gesture
+//! in order to undo it, so the baseline of every parameter must be set
+//! before its first update is recorded (see
+//! [`UndoStack::seed_param_baselines`] and [`UndoStack::set_param_baseline`]).
+//!
+//! Other edits can be recorded as commands with a pair of apply/revert
+//! closures (see [`UndoStack::push`] and [`UndoStack::execute`]).
+//!
+//! The undo and redo shortcuts of the platform can be registered with
+//! `AppContext::register_undo_shortcuts`.
+
+use std::collections::VecDeque;
+
+use keyboard_types::{Code, KeyState};
+use rustc_hash::FxHashMap;
+use smol_str::SmolStr;
+
+use crate::elements::virtual_slider::{GestureState, ParamUpdate};
+use crate::event::KeyboardEvent;
+use crate::param_store::ParamStore;
+use crate::shortcut::KeyChord;
+
+/// The default maximum number of entries in an [`UndoStack`].
+pub const DEFAULT_UNDO_LIMIT: usize = 256;
+
+type CommandFn<C> = Box<dyn FnMut(&mut C)>;
+
+enum UndoEntry<C> {
+    Param {
+        id: SmolStr,
+        from_normal: f64,
+        to_normal: f64,
+    },
+    Command {
+        label: String,
+        apply: CommandFn<C>,
+        revert: CommandFn<C>,
+    },
+}
+
+struct PendingGesture {
+    id: SmolStr,
+    /// `None` if the value from before the gesture is unknown.
+    from_normal: Option<f64>,
+    to_normal: f64,
+}
+
+/// The result of undoing or redoing an entry in an [`UndoStack`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum UndoStep {
+    /// A parameter was changed.
+    ///
+    /// The application should apply the new value to the parameter (i.e.
+    /// with [`ParamStore::set_normal`](crate::param_store::ParamStore::set_normal))
+    /// and forward it to the host.
+    Param { id: SmolStr, normal_value: f64 },
+    /// A command was applied or reverted.
+    Command { label: String },
+}
+
+/// A keyboard shortcut for undoing or redoing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UndoShortcut {
+    /// `Ctrl+Z` (`Cmd+Z` on MacOS)
+    Undo,
+    /// `Ctrl+Shift+Z` or `Ctrl+Y` (`Cmd+Shift+Z` on MacOS)
+    Redo,
+}
+
+const UNDO_CHORDS: [KeyChord; 1] = [KeyChord::command(Code::KeyZ)];
+#[cfg(target_os = "macos")]
+const REDO_CHORDS: [KeyChord; 1] = [KeyChord::command_shift(Code::KeyZ)];
+#[cfg(not(target_os = "macos"))]
+const REDO_CHORDS: [KeyChord; 2] = [
+    KeyChord::command_shift(Code::KeyZ),
+    KeyChord::command(Code::KeyY),
+];
+
+impl UndoShortcut {
+    /// Returns the undo/redo shortcut triggered by the given keyboard event
+    /// (if any).
+    pub fn from_keyboard_event(event: &KeyboardEvent) -> Option<Self> {
+        if event.state != KeyState::Down {
+            return None;
+        }
+
+        [Self::Undo, Self::Redo].into_iter().find(|shortcut| {
+            shortcut
+                .key_chords()
+                .iter()
+                .any(|chord| chord.matches(event.modifiers, event.code))
+        })
+    }
+
+    /// The key chord of this shortcut (i.e. for showing it in an "Undo" menu
+    /// entry).
+    pub const fn key_chord(&self) -> KeyChord {
+        self.key_chords()[0]
+    }
+
+    /// All of the key chords which trigger this shortcut, starting with the
+    /// one returned by [`UndoShortcut::key_chord`].
+    pub const fn key_chords(&self) -> &'static [KeyChord] {
+        match self {
+            Self::Undo => &UNDO_CHORDS,
+            Self::Redo => &REDO_CHORDS,
+        }
+    }
+}
+
+/// An undo/redo stack for parameter gestures and arbitrary user commands.
+///
+/// `C` is the context passed to the apply/revert closures of commands (i.e.
+/// the application state).
+pub struct UndoStack<C = ()> {
+    undo_entries: VecDeque<UndoEntry<C>>,
+    redo_entries: Vec<UndoEntry<C>>,
+    limit: usize,
+
+    pending_gestures: Vec<PendingGesture>,
+    /// The last known normalized value of every recorded parameter.
+    param_values: FxHashMap<SmolStr, f64>,
+}
+
+impl<C> UndoStack<C> {
+    /// Create a new undo stack with the given maximum number of entries.
+    /// Once the limit is reached, the oldest entries are discarded.
+    pub fn new(limit: usize) -> Self {
+        Self {
+            undo_entries: VecDeque::new(),
+            redo_entries: Vec::new(),
+            limit: limit.max(1),
+            pending_gestures: Vec::new(),
+            param_values: FxHashMap::default(),
+        }
+    }
+
+    /// Record an update from a knob or slider (i.e. the `on_gesture` action).
+    ///
+    /// All updates of a single gesture are coalesced into a single entry
+    /// which is pushed once the gesture has finished. Updates which are not
+    /// part of a gesture (i.e. resetting to the default value) are pushed
+    /// immediately.
+    ///
+    /// The first update of a gesture may already contain movement, so the
+    /// value from before the gesture is taken from the baseline of the
+    /// parameter (the last recorded value, or the value set with
+    /// [`UndoStack::set_param_baseline`] or
+    /// [`UndoStack::seed_param_baselines`]). If a parameter has no baseline,
+    /// then no entry is recorded for it and a warning is logged.
+    pub fn record_param_update(&mut self, update: &ParamUpdate) {
+        let id = &update.param_info.id;
+        let new_normal = update.param_info.normal_value;
+        let prev_normal = self.param_values.insert(id.clone(), new_normal);
+
+        let pending_i = self.pending_gestures.iter().position(|g| &g.id == id);
+
+        match update.gesture_state {
+            Some(GestureState::GestureStarted) | Some(GestureState::Gesturing) => {
+                if let Some(i) = pending_i {
+                    self.pending_gestures[i].to_normal = new_normal;
+                } else {
+                    self.pending_gestures.push(PendingGesture {
+                        id: id.clone(),
+                        from_normal: prev_normal,
+                        to_normal: new_normal,
+                    });
+                }
+            }
+            Some(GestureState::GestureFinished) => {
+                let from_normal = if let Some(i) = pending_i {
+                    self.pending_gestures.swap_remove(i).from_normal
+                } else {
+                    prev_normal
+                };
+
+                self.push_param_entry(id, from_normal, new_normal);
+            }
+            None => {
+                if let Some(i) = pending_i {
+                    self.pending_gestures[i].to_normal = new_normal;
+                } else {
+                    self.push_param_entry(id, prev_normal, new_normal);
+                }
+            }
+        }
+    }
+
+    /// Set the last known normalized value of a parameter without recording
+    /// an entry (i.e. when the value was changed by the host).
+    ///
+    /// This is used as the starting value of the next recorded gesture.
+    pub fn set_param_baseline(&mut self, id: &str, normal_value: f64) {
+        self.param_values.insert(SmolStr::new(id), normal_value);
+    }
+
+    /// Set the baseline of every parameter in the given store to its current
+    /// value (see [`UndoStack::set_param_baseline`]).
+    ///
+    /// Call this after adding the parameters to the store and before
+    /// recording any updates.
+    pub fn seed_param_baselines(&mut self, store: &ParamStore) {
+        for def in store.defs() {
+            if let Some(normal_value) = store.normal_value(&def.id) {
+                self.param_values.insert(def.id.clone(), normal_value);
+            }
+        }
+    }
+
+    /// Record a command which has already been applied.
+    ///
+    /// * `label` - A human-readable description of the command (i.e. for
+    /// an "Undo <label>" menu entry)
+    /// * `apply` - Applies the command again when it is redone
+    /// * `revert` - Reverts the command when it is undone
+    pub fn push<A, R>(&mut self, label: impl Into<String>, apply: A, revert: R)
+    where
+        A: FnMut(&mut C) + 'static,
+        R: FnMut(&mut C) + 'static,
+    {
+        self.push_entry(UndoEntry::Command {
+            label: label.into(),
+            apply: Box::new(apply),
+            revert: Box::new(revert),
+        });
+    }
+
+    /// Apply a command and record it.
+    ///
+    /// See [`UndoStack::push`] for more details.
+    pub fn execute<A, R>(&mut self, cx: &mut C, label: impl Into<String>, mut apply: A, revert: R)
+    where
+        A: FnMut(&mut C) + 'static,
+        R: FnMut(&mut C) + 'static,
+    {
+        (apply)(cx);
+        self.push(label, apply, revert);
+    }
+
+    /// Undo the most recent entry.
+    ///
+    /// Returns `None` if there is nothing to undo.
+    pub fn undo(&mut self, cx: &mut C) -> Option<UndoStep> {
+        let mut entry = self.undo_entries.pop_back()?;
+
+        let step = match &mut entry {
+            UndoEntry::Param {
+                id, from_normal, ..
+            } => self.param_step(id, *from_normal),
+            UndoEntry::Command { label, revert, .. } => {
+                (revert)(cx);
+                UndoStep::Command {
+                    label: label.clone(),
+                }
+            }
+        };
+
+        self.redo_entries.push(entry);
+
+        Some(step)
+    }
+
+    /// Redo the most recently undone entry.
+    ///
+    /// Returns `None` if there is nothing to redo.
+    pub fn redo(&mut self, cx: &mut C) -> Option<UndoStep> {
+        let mut entry = self.redo_entries.pop()?;
+
+        let step = match &mut entry {
+            UndoEntry::Param { id, to_normal, .. } => self.param_step(id, *to_normal),
+            UndoEntry::Command { label, apply, .. } => {
+                (apply)(cx);
+                UndoStep::Command {
+                    label: label.clone(),
+                }
+            }
+        };
+
+        self.undo_entries.push_back(entry);
+
+        Some(step)
+    }
+
+    /// Undo or redo if the given keyboard event is an undo/redo shortcut
+    /// (see [`UndoShortcut`]).
+    ///
+    /// Applications usually don't need to call this, since the shortcuts can
+    /// be registered with `AppContext::register_undo_shortcuts` to send
+    /// actions which call [`UndoStack::undo`] and [`UndoStack::redo`]. This
+    /// is useful for handling the shortcuts in a custom element instead.
+    pub fn handle_keyboard_event(&mut self, event: &KeyboardEvent, cx: &mut C) -> Option<UndoStep> {
+        match UndoShortcut::from_keyboard_event(event)? {
+            UndoShortcut::Undo => self.undo(cx),
+            UndoShortcut::Redo => self.redo(cx),
+        }
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo_entries.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo_entries.is_empty()
+    }
+
+    /// The label of the entry which would be undone next.
+    ///
+    /// For parameter entries this is the ID of the parameter.
+    pub fn undo_label(&self) -> Option<&str> {
+        self.undo_entries.back().map(|e| e.label())
+    }
+
+    /// The label of the entry which would be redone next.
+    ///
+    /// For parameter entries this is the ID of the parameter.
+    pub fn redo_label(&self) -> Option<&str> {
+        self.redo_entries.last().map(|e| e.label())
+    }
+
+    /// Remove all entries.
+    pub fn clear(&mut self) {
+        self.undo_entries.clear();
+        self.redo_entries.clear();
+        self.pending_gestures.clear();
+    }
+
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Set the maximum number of entries. Once the limit is reached, the
+    /// oldest entries are discarded.
+    pub fn set_limit(&mut self, limit: usize) {
+        self.limit = limit.max(1);
+
+        while self.undo_entries.len() > self.limit {
+            self.undo_entries.pop_front();
+        }
+    }
+
+    fn push_param_entry(&mut self, id: &SmolStr, from_normal: Option<f64>, to_normal: f64) {
+        let Some(from_normal) = from_normal else {
+            log::warn!(
+                "Parameter {} has no undo baseline, use UndoStack::seed_param_baselines or UndoStack::set_param_baseline",
+                id
+            );
+            return;
+        };
+
+        if from_normal == to_normal {
+            return;
+        }
+
+        self.push_entry(UndoEntry::Param {
+            id: id.clone(),
+            from_normal,
+            to_normal,
+        });
+    }
+
+    fn push_entry(&mut self, entry: UndoEntry<C>) {
+        self.redo_entries.clear();
+
+        if self.undo_entries.len() >= self.limit {
+            self.undo_entries.pop_front();
+        }
+        self.undo_entries.push_back(entry);
+    }
+
+    fn param_step(&mut self, id: &SmolStr, normal_value: f64) -> UndoStep {
+        self.param_values.insert(id.clone(), normal_value);
+
+        UndoStep::Param {
+            id: id.clone(),
+            normal_value,
+        }
+    }
+}
+
+impl<C> Default for UndoStack<C> {
+    fn default() -> Self {
+        Self::new(DEFAULT_UNDO_LIMIT)
+    }
+}
+
+impl<C> UndoEntry<C> {
+    fn label(&self) -> &str {
+        match self {
+            Self::Param { id, .. } => id.as_str(),
+            Self::Command { label, .. } => label.as_str(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elements::virtual_slider::ParamInfo;
+    use crate::param_store::ParamDef;
+
+    fn update(id: &str, normal_value: f64, gesture_state: Option<GestureState>) -> ParamUpdate {
+        ParamUpdate {
+            param_info: ParamInfo {
+                id: SmolStr::new(id),
+                normal_value,
+                stepped_value: None,
+            },
+            gesture_state,
+        }
+    }
+
+    fn param_step(id: &str, normal_value: f64) -> Option<UndoStep> {
+        Some(UndoStep::Param {
+            id: SmolStr::new(id),
+            normal_value,
+        })
+    }
+
+    #[test]
+    fn test_gesture_is_coalesced() {
+        let mut stack: UndoStack = UndoStack::default();
+        stack.set_param_baseline("gain", 0.5);
+
+        // The first update of the gesture already contains movement.
+        stack.record_param_update(&update("gain", 0.55, Some(GestureState::GestureStarted)));
+        stack.record_param_update(&update("gain", 0.6, Some(GestureState::Gesturing)));
+        stack.record_param_update(&update("gain", 0.7, Some(GestureState::Gesturing)));
+        assert!(!stack.can_undo());
+
+        stack.record_param_update(&update("gain", 0.7, Some(GestureState::GestureFinished)));
+
+        assert_eq!(stack.undo(&mut ()), param_step("gain", 0.5));
+        assert!(!stack.can_undo());
+        assert_eq!(stack.redo(&mut ()), param_step("gain", 0.7));
+    }
+
+    #[test]
+    fn test_update_without_gesture() {
+        let mut stack: UndoStack = UndoStack::default();
+        stack.set_param_baseline("gain", 0.3);
+
+        stack.record_param_update(&update("gain", 0.5, None));
+
+        assert_eq!(stack.undo(&mut ()), param_step("gain", 0.3));
+    }
+
+    #[test]
+    fn test_baseline_from_param_store() {
+        let mut store = ParamStore::new();
+        store.add(ParamDef::new("gain", 0.0..=10.0, 2.5));
+
+        let mut stack: UndoStack = UndoStack::default();
+        stack.seed_param_baselines(&store);
+
+        stack.record_param_update(&update("gain", 0.4, Some(GestureState::GestureStarted)));
+        stack.record_param_update(&update("gain", 0.6, Some(GestureState::GestureFinished)));
+
+        assert_eq!(stack.undo(&mut ()), param_step("gain", 0.25));
+    }
+
+    #[test]
+    fn test_no_entry_without_baseline() {
+        let mut stack: UndoStack = UndoStack::default();
+
+        stack.record_param_update(&update("gain", 0.4, Some(GestureState::GestureStarted)));
+        stack.record_param_update(&update("gain", 0.6, Some(GestureState::GestureFinished)));
+        stack.record_param_update(&update("mix", 0.2, None));
+
+        assert!(!stack.can_undo());
+
+        // The recorded values are used as the baseline of the next update.
+        stack.record_param_update(&update("mix", 0.8, None));
+        assert_eq!(stack.undo(&mut ()), param_step("mix", 0.2));
+    }
+
+    #[test]
+    fn test_limit() {
+        let mut stack: UndoStack = UndoStack::new(2);
+        stack.set_param_baseline("gain", 0.0);
+
+        stack.record_param_update(&update("gain", 0.1, None));
+        stack.record_param_update(&update("gain", 0.2, None));
+        stack.record_param_update(&update("gain", 0.3, None));
+
+        assert_eq!(stack.undo(&mut ()), param_step("gain", 0.2));
+        assert_eq!(stack.undo(&mut ()), param_step("gain", 0.1));
+        assert_eq!(stack.undo(&mut ()), None);
+
+        stack.redo(&mut ());
+        stack.redo(&mut ());
+        stack.set_limit(1);
+        assert_eq!(stack.undo(&mut ()), param_step("gain", 0.2));
+        assert!(!stack.can_undo());
+    }
+
+    #[test]
+    fn test_new_entry_clears_redo() {
+        let mut stack: UndoStack<Vec<&'static str>> = UndoStack::default();
+        let mut log = Vec::new();
+
+        stack.execute(&mut log, "a", |l| l.push("a"), |l| l.retain(|s| *s != "a"));
+        stack.execute(&mut log, "b", |l| l.push("b"), |l| l.retain(|s| *s != "b"));
+
+        assert_eq!(
+            stack.undo(&mut log),
+            Some(UndoStep::Command { label: "b".into() })
+        );
+        assert_eq!(log, vec!["a"]);
+        assert!(stack.can_redo());
+
+        stack.push("c", |_| {}, |_| {});
+        assert!(!stack.can_redo());
+        assert_eq!(stack.redo(&mut log), None);
+        assert_eq!(stack.undo_label(), Some("c"));
+    }
+}
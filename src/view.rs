@@ -0,0 +1,157 @@
+/// Declare a struct of element handles along with a `build` method which
+/// constructs all of them in one go.
+///
+/// Each field is declared as `name: Type = builder`, where `builder` is an
+/// element builder (the `.build(window_cx)` call is added automatically).
+/// Fields can be nested inside of scopes which set the z index, scissoring
+/// rectangle, style class, or text direction of every element inside of
+/// them:
+///
+/// * `z_index(z) { ... }`
+/// * `scissor_rect(id) { ... }`
+/// * `class(class_id) { ... }`
+/// * `text_direction(direction) { ... }`
+///
+/// Note that scopes are *not* followed by a comma, and the comma after the
+/// last field in a scope is optional.
+///
+/// ```no_run
+/// use yarrow::prelude::*;
+///
+/// #[derive(Clone)]
+/// enum MyAction {
+///     EnabledToggled(bool),
+///     NameChanged(String),
+///     Ok,
+/// }
+///
+/// const SETTINGS_SCROLL_AREA: ScissorRectID = ScissorRectID(1);
+///
+/// yarrow::view! {
+///     /// The handles of the main window.
+///     pub struct MainView for MyAction {
+///         pub title: Label = Label::builder().text("Settings"),
+///
+///         scissor_rect(SETTINGS_SCROLL_AREA) {
+///             z_index(1) {
+///                 /// Enables the effect.
+///                 pub enabled_switch: Switch = Switch::builder()
+///                     .on_toggled(MyAction::EnabledToggled),
+///                 #[allow(dead_code)]
+///                 name_input: TextInput = TextInput::builder()
+///                     .on_changed(MyAction::NameChanged)
+///             }
+///         }
+///
+///         class(CLASS_MENU) {
+///             text_direction(TextDirection::RightToLeft) {
+///                 pub ok_btn: Button = Button::builder()
+///                     .text("Ok")
+///                     .on_select(MyAction::Ok)
+///             }
+///         }
+///
+///         pub status: Label = Label::builder().text("Ready")
+///     }
+/// }
+///
+/// fn build_main_view(window_cx: &mut WindowContext<'_, MyAction>) -> MainView {
+///     MainView::build(window_cx)
+/// }
+/// ```
+///
+/// The elements are built in the order they are declared.
+#[macro_export]
+macro_rules! view {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident for $action:ty {
+            $($body:tt)*
+        }
+    ) => {
+        $crate::view!(@struct [$(#[$meta])* $vis struct $name] [] $($body)*);
+
+        impl $name {
+            /// Build all of the elements in this view.
+            $vis fn build(window_cx: &mut $crate::WindowContext<'_, $action>) -> Self {
+                $crate::view!(@build window_cx; $($body)*);
+                $crate::view!(@init [] $($body)*)
+            }
+        }
+    };
+
+    // --- Declare the struct ---------------------------------------------
+
+    (@struct [$($head:tt)*] [$($fields:tt)*]) => {
+        $($head)* {
+            $($fields)*
+        }
+    };
+    (@struct [$($head:tt)*] [$($fields:tt)*] , $($rest:tt)*) => {
+        $crate::view!(@struct [$($head)*] [$($fields)*] $($rest)*);
+    };
+    (@struct [$($head:tt)*] [$($fields:tt)*]
+        $scope:ident ($($args:tt)*) { $($inner:tt)* } $($rest:tt)*
+    ) => {
+        // The comma ends the last field in the scope if it has none.
+        $crate::view!(@struct [$($head)*] [$($fields)*] $($inner)* , $($rest)*);
+    };
+    (@struct [$($head:tt)*] [$($fields:tt)*]
+        $(#[$fmeta:meta])* $fvis:vis $field:ident : $ty:ty = $builder:expr $(, $($rest:tt)*)?
+    ) => {
+        $crate::view!(@struct [$($head)*] [$($fields)* $(#[$fmeta])* $fvis $field: $ty,] $($($rest)*)?);
+    };
+
+    // --- Build the elements ---------------------------------------------
+
+    (@build $cx:ident;) => {};
+    (@build $cx:ident; z_index($z:expr) { $($inner:tt)* } $($rest:tt)*) => {
+        $cx.push_z_index($z);
+        $crate::view!(@build $cx; $($inner)*);
+        $cx.pop_z_index();
+        $crate::view!(@build $cx; $($rest)*);
+    };
+    (@build $cx:ident; scissor_rect($s:expr) { $($inner:tt)* } $($rest:tt)*) => {
+        $cx.push_scissor_rect($s);
+        $crate::view!(@build $cx; $($inner)*);
+        $cx.pop_scissor_rect();
+        $crate::view!(@build $cx; $($rest)*);
+    };
+    (@build $cx:ident; class($c:expr) { $($inner:tt)* } $($rest:tt)*) => {
+        $cx.push_class($c);
+        $crate::view!(@build $cx; $($inner)*);
+        $cx.pop_class();
+        $crate::view!(@build $cx; $($rest)*);
+    };
+    (@build $cx:ident; text_direction($d:expr) { $($inner:tt)* } $($rest:tt)*) => {
+        $cx.push_text_direction($d);
+        $crate::view!(@build $cx; $($inner)*);
+        $cx.pop_text_direction();
+        $crate::view!(@build $cx; $($rest)*);
+    };
+    (@build $cx:ident;
+        $(#[$fmeta:meta])* $fvis:vis $field:ident : $ty:ty = $builder:expr $(, $($rest:tt)*)?
+    ) => {
+        let $field: $ty = $builder.build($cx);
+        $crate::view!(@build $cx; $($($rest)*)?);
+    };
+
+    // --- Construct the struct -------------------------------------------
+
+    (@init [$($fields:ident)*]) => {
+        Self { $($fields),* }
+    };
+    (@init [$($fields:ident)*] , $($rest:tt)*) => {
+        $crate::view!(@init [$($fields)*] $($rest)*)
+    };
+    (@init [$($fields:ident)*]
+        $scope:ident ($($args:tt)*) { $($inner:tt)* } $($rest:tt)*
+    ) => {
+        $crate::view!(@init [$($fields)*] $($inner)* , $($rest)*)
+    };
+    (@init [$($fields:ident)*]
+        $(#[$fmeta:meta])* $fvis:vis $field:ident : $ty:ty = $builder:expr $(, $($rest:tt)*)?
+    ) => {
+        $crate::view!(@init [$($fields)* $field] $($($rest)*)?)
+    };
+}
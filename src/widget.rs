@@ -0,0 +1,395 @@
+//! Composite widgets built from multiple elements.
+//!
+//! A widget is a struct which owns the handles of several elements (i.e. a
+//! label, a knob, a value readout, and a modulation ring) and exposes them
+//! as a single unit with a single rect and a single hidden/disabled state.
+//!
+//! ```ignore
+//! pub struct ParamWidget {
+//!     core: WidgetCore,
+//!     label: Label,
+//!     knob: Knob,
+//!     readout: Label,
+//! }
+//!
+//! impl ParamWidget {
+//!     pub fn new<A: Clone + 'static>(
+//!         name: &str,
+//!         rect: Rect,
+//!         cx: &mut WindowContext<'_, A>,
+//!     ) -> Self {
+//!         let mut widget = Self {
+//!             core: WidgetCore::new(rect),
+//!             label: Label::builder().text(name).build(cx),
+//!             knob: Knob::builder(name).build(cx),
+//!             readout: Label::builder().build(cx),
+//!         };
+//!         widget.sync();
+//!         widget
+//!     }
+//! }
+//!
+//! impl Widget for ParamWidget {
+//!     fn core(&self) -> &WidgetCore {
+//!         &self.core
+//!     }
+//!
+//!     fn core_mut(&mut self) -> &mut WidgetCore {
+//!         &mut self.core
+//!     }
+//!
+//!     fn for_each_part(&mut self, f: &mut dyn FnMut(&mut dyn WidgetPart, PartLayout)) {
+//!         (f)(&mut self.label, PartLayout::fill_width(Align2::TOP_CENTER, 16.0));
+//!         (f)(
+//!             &mut self.knob,
+//!             PartLayout::fixed(Align2::CENTER, Size::new(40.0, 40.0)),
+//!         );
+//!         (f)(&mut self.readout, PartLayout::fill_width(Align2::BOTTOM_CENTER, 16.0));
+//!     }
+//! }
+//! ```
+
+use crate::elements::button::Button;
+use crate::elements::click_area::ClickArea;
+use crate::elements::custom::CustomElement;
+use crate::elements::editable_label::EditableLabel;
+use crate::elements::hyperlink::Hyperlink;
+use crate::elements::label::Label;
+use crate::elements::log_view::LogView;
+use crate::elements::markdown::Markdown;
+use crate::elements::paragraph::Paragraph;
+use crate::elements::quad::QuadElement;
+use crate::elements::radio_button::RadioButton;
+use crate::elements::scroll_area::ScrollArea;
+use crate::elements::separator::Separator;
+use crate::elements::switch::Switch;
+use crate::elements::tab::Tab;
+use crate::elements::text_input::TextInput;
+use crate::elements::toggle_button::ToggleButton;
+use crate::elements::virtual_slider::{VirtualSlider, VirtualSliderRenderer};
+use crate::layout::{Align, Align2, SizeType};
+use crate::math::{Point, Rect, Size, Vector};
+
+/// An element (or nested widget) which can be part of a [`Widget`].
+pub trait WidgetPart {
+    /// Set the bounding rectangle of this part.
+    ///
+    /// Returns `true` if the rectangle has changed.
+    fn set_rect(&mut self, rect: Rect) -> bool;
+
+    /// Returns `true` if the hidden state has changed.
+    fn set_hidden(&mut self, hidden: bool) -> bool;
+
+    /// Set the disabled state of this part. Parts which cannot be disabled
+    /// (i.e. labels) ignore this.
+    ///
+    /// Returns `true` if the disabled state has changed.
+    #[allow(unused)]
+    fn set_disabled(&mut self, disabled: bool) -> bool {
+        false
+    }
+}
+
+/// Where a part is placed within the bounding rectangle of its [`Widget`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PartLayout {
+    /// Where to align the part within the bounds of the widget.
+    pub align: Align2,
+    /// The width of the part (`SizeType::Scale` is relative to the width of
+    /// the widget).
+    pub width: SizeType,
+    /// The height of the part (`SizeType::Scale` is relative to the height
+    /// of the widget).
+    pub height: SizeType,
+    /// An additional offset applied after aligning the part.
+    pub offset: Vector,
+}
+
+impl PartLayout {
+    pub const fn new(align: Align2, width: SizeType, height: SizeType) -> Self {
+        Self {
+            align,
+            width,
+            height,
+            offset: Vector::new(0.0, 0.0),
+        }
+    }
+
+    /// A part with a fixed size.
+    pub const fn fixed(align: Align2, size: Size) -> Self {
+        Self::new(
+            align,
+            SizeType::FixedPoints(size.width),
+            SizeType::FixedPoints(size.height),
+        )
+    }
+
+    /// A part which fills the full width of the widget.
+    pub const fn fill_width(align: Align2, height: f32) -> Self {
+        Self::new(align, SizeType::Scale(1.0), SizeType::FixedPoints(height))
+    }
+
+    /// A part which fills the full height of the widget.
+    pub const fn fill_height(align: Align2, width: f32) -> Self {
+        Self::new(align, SizeType::FixedPoints(width), SizeType::Scale(1.0))
+    }
+
+    /// A part which fills the whole widget.
+    pub const fn fill() -> Self {
+        Self::new(Align2::TOP_LEFT, SizeType::Scale(1.0), SizeType::Scale(1.0))
+    }
+
+    pub const fn offset(mut self, offset: Vector) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// The rectangle of the part within the given widget bounds.
+    pub fn rect(&self, bounds: Rect) -> Rect {
+        let size = Size::new(
+            self.width.points(bounds.width()),
+            self.height.points(bounds.height()),
+        );
+
+        let x = match self.align.horizontal {
+            Align::Start => bounds.min_x(),
+            Align::Center => bounds.min_x() + (bounds.width() * 0.5),
+            Align::End => bounds.max_x(),
+        };
+        let y = match self.align.vertical {
+            Align::Start => bounds.min_y(),
+            Align::Center => bounds.min_y() + (bounds.height() * 0.5),
+            Align::End => bounds.max_y(),
+        };
+
+        self.align
+            .align_rect_to_point(Point::new(x, y) + self.offset, size)
+    }
+}
+
+/// The state shared by all parts of a [`Widget`].
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct WidgetCore {
+    rect: Rect,
+    hidden: bool,
+    disabled: bool,
+}
+
+impl WidgetCore {
+    pub fn new(rect: Rect) -> Self {
+        Self {
+            rect,
+            hidden: false,
+            disabled: false,
+        }
+    }
+
+    pub const fn hidden(mut self, hidden: bool) -> Self {
+        self.hidden = hidden;
+        self
+    }
+
+    pub const fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+}
+
+/// A composite of multiple elements which is laid out, hidden, and disabled
+/// as a single unit.
+///
+/// Implementors only need to store a [`WidgetCore`] and list their parts in
+/// [`Widget::for_each_part`]. See the [module docs](self) for an example.
+pub trait Widget {
+    fn core(&self) -> &WidgetCore;
+
+    fn core_mut(&mut self) -> &mut WidgetCore;
+
+    /// Call `f` with every part of this widget and its layout.
+    fn for_each_part(&mut self, f: &mut dyn FnMut(&mut dyn WidgetPart, PartLayout));
+
+    /// The bounding rectangle of this widget.
+    fn rect(&self) -> Rect {
+        self.core().rect
+    }
+
+    fn hidden(&self) -> bool {
+        self.core().hidden
+    }
+
+    fn disabled(&self) -> bool {
+        self.core().disabled
+    }
+
+    /// Set the bounding rectangle of this widget and lay out its parts.
+    ///
+    /// Returns `true` if the rectangle has changed.
+    fn set_rect(&mut self, rect: Rect) -> bool {
+        if self.core().rect == rect {
+            return false;
+        }
+
+        self.core_mut().rect = rect;
+        self.layout();
+        true
+    }
+
+    /// Set the position of this widget and lay out its parts.
+    ///
+    /// Returns `true` if the position has changed.
+    fn set_pos(&mut self, pos: Point) -> bool {
+        let size = self.core().rect.size;
+        self.set_rect(Rect::new(pos, size))
+    }
+
+    /// Hide or show all parts of this widget.
+    ///
+    /// Returns `true` if the hidden state has changed.
+    fn set_hidden(&mut self, hidden: bool) -> bool {
+        if self.core().hidden == hidden {
+            return false;
+        }
+
+        self.core_mut().hidden = hidden;
+        self.for_each_part(&mut |part, _| {
+            part.set_hidden(hidden);
+        });
+        true
+    }
+
+    /// Set the disabled state of all parts of this widget.
+    ///
+    /// Returns `true` if the disabled state has changed.
+    fn set_disabled(&mut self, disabled: bool) -> bool {
+        if self.core().disabled == disabled {
+            return false;
+        }
+
+        self.core_mut().disabled = disabled;
+        self.for_each_part(&mut |part, _| {
+            part.set_disabled(disabled);
+        });
+        true
+    }
+
+    /// Lay out all parts within the current bounding rectangle.
+    fn layout(&mut self) {
+        let rect = self.core().rect;
+        self.for_each_part(&mut |part, layout| {
+            part.set_rect(layout.rect(rect));
+        });
+    }
+
+    /// Apply the current rect, hidden state, and disabled state to all
+    /// parts (i.e. after the parts have been built or a part was replaced).
+    fn sync(&mut self) {
+        let WidgetCore {
+            rect,
+            hidden,
+            disabled,
+        } = *self.core();
+
+        self.for_each_part(&mut |part, layout| {
+            part.set_rect(layout.rect(rect));
+            part.set_hidden(hidden);
+            part.set_disabled(disabled);
+        });
+    }
+}
+
+/// Widgets can be nested inside of other widgets.
+impl<W: Widget> WidgetPart for W {
+    fn set_rect(&mut self, rect: Rect) -> bool {
+        Widget::set_rect(self, rect)
+    }
+
+    fn set_hidden(&mut self, hidden: bool) -> bool {
+        Widget::set_hidden(self, hidden)
+    }
+
+    fn set_disabled(&mut self, disabled: bool) -> bool {
+        Widget::set_disabled(self, disabled)
+    }
+}
+
+macro_rules! impl_widget_part {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl WidgetPart for $ty {
+                fn set_rect(&mut self, rect: Rect) -> bool {
+                    <$ty>::set_rect(self, rect)
+                }
+
+                fn set_hidden(&mut self, hidden: bool) -> bool {
+                    <$ty>::set_hidden(self, hidden)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_widget_part_with_disabled {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl WidgetPart for $ty {
+                fn set_rect(&mut self, rect: Rect) -> bool {
+                    <$ty>::set_rect(self, rect)
+                }
+
+                fn set_hidden(&mut self, hidden: bool) -> bool {
+                    <$ty>::set_hidden(self, hidden)
+                }
+
+                fn set_disabled(&mut self, disabled: bool) -> bool {
+                    <$ty>::set_disabled(self, disabled)
+                }
+            }
+        )*
+    };
+}
+
+impl_widget_part!(Label, LogView, Markdown, Paragraph, QuadElement, Separator);
+
+impl_widget_part_with_disabled!(
+    Button,
+    ClickArea,
+    EditableLabel,
+    Hyperlink,
+    RadioButton,
+    ScrollArea,
+    Switch,
+    Tab,
+    TextInput,
+    ToggleButton,
+);
+
+#[cfg(feature = "svg-icons")]
+impl_widget_part!(crate::elements::icon::Icon);
+#[cfg(feature = "svg-icons")]
+impl_widget_part_with_disabled!(crate::elements::text_input::IconTextInput);
+
+impl<T> WidgetPart for CustomElement<T> {
+    fn set_rect(&mut self, rect: Rect) -> bool {
+        CustomElement::set_rect(self, rect)
+    }
+
+    fn set_hidden(&mut self, hidden: bool) -> bool {
+        CustomElement::set_hidden(self, hidden)
+    }
+}
+
+impl<R: VirtualSliderRenderer> WidgetPart for VirtualSlider<R> {
+    fn set_rect(&mut self, rect: Rect) -> bool {
+        VirtualSlider::set_rect(self, rect)
+    }
+
+    fn set_hidden(&mut self, hidden: bool) -> bool {
+        VirtualSlider::set_hidden(self, hidden)
+    }
+
+    fn set_disabled(&mut self, disabled: bool) -> bool {
+        let changed = self.disabled() != disabled;
+        VirtualSlider::set_disabled(self, disabled);
+        changed
+    }
+}
@@ -2,15 +2,21 @@ use keyboard_types::{CompositionEvent, Modifiers};
 use rootvg::color::PackedSrgb;
 use rootvg::math::{Rect, RectI32};
 use rootvg::surface::{DefaultSurface, DefaultSurfaceConfig};
-use std::time::{Duration, Instant};
+use std::ops::RangeInclusive;
+use std::time::Duration;
+use web_time::Instant;
 
 use crate::action_queue::ActionSender;
 use crate::clipboard::Clipboard;
-use crate::element_system::ElementSystem;
+use crate::element_system::{
+    ColorVisionDeficiency, CustomRenderPass, ElementID, ElementKey, ElementRef, ElementSystem,
+    FrameStats, StackPosition,
+};
 use crate::event::{
-    CanvasEvent, EventCaptureStatus, KeyboardEvent, PointerButton, PointerEvent, PointerType,
-    WheelDeltaType,
+    AnnouncePriority, Announcement, CanvasEvent, EventCaptureStatus, InputEvent, KeyboardEvent,
+    NavigationInput, PointerButton, PointerEvent, PointerType, WheelDeltaType,
 };
+use crate::layout::TextDirection;
 use crate::math::{
     to_logical_size_i32, PhysicalPoint, PhysicalSizeI32, Point, ScaleFactor, Size, Vector, ZIndex,
 };
@@ -18,31 +24,39 @@ use crate::prelude::{ActionReceiver, ElementBuilder, ElementHandle, ResourceCtx}
 use crate::style::ClassID;
 use crate::{CursorIcon, ScissorRectID, TooltipInfo};
 
+mod double_click;
+pub use double_click::DoubleClickConfig;
+
+#[cfg(all(feature = "headless", not(target_family = "wasm")))]
+mod headless;
+#[cfg(all(feature = "headless", not(target_family = "wasm")))]
+pub use headless::{HeadlessError, HeadlessWindow};
+
 #[cfg(feature = "winit")]
 mod winit_backend;
 #[cfg(feature = "winit")]
-use winit_backend as windowing_backend;
+pub use winit_backend::run_blocking;
 
-#[cfg(feature = "baseview")]
+#[cfg(all(feature = "baseview", not(target_family = "wasm")))]
 mod baseview_backend;
-#[cfg(feature = "baseview")]
-use baseview_backend as windowing_backend;
-
-#[cfg(feature = "baseview")]
-pub use windowing_backend::run_parented;
-pub use windowing_backend::{run_blocking, OpenWindowError};
+#[cfg(all(
+    feature = "baseview",
+    not(feature = "winit"),
+    not(target_family = "wasm")
+))]
+pub use baseview_backend::run_blocking;
+#[cfg(all(feature = "baseview", not(target_family = "wasm")))]
+pub use baseview_backend::{run_parented, ParentWindow, ParentedWindowHandle};
 
 pub type WindowID = u32;
 
 pub const MAIN_WINDOW: WindowID = 0;
 
-// TODO: Get click intervals from OS.
-const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(300);
-
 #[derive(Clone, Copy)]
 struct PointerBtnState {
     is_down: bool,
     prev_down_instant: Option<Instant>,
+    prev_down_position: Point,
     click_count: usize,
 }
 
@@ -51,6 +65,7 @@ impl Default for PointerBtnState {
         Self {
             is_down: false,
             prev_down_instant: None,
+            prev_down_position: Point::zero(),
             click_count: 1,
         }
     }
@@ -69,6 +84,24 @@ impl PointerLockState {
     }
 }
 
+/// A request from an element to control the window it belongs to.
+///
+/// This is mainly used to implement custom window decorations for windows
+/// created with `decorations: false`. The backend may choose to ignore
+/// requests it does not support.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WindowControl {
+    /// Start moving the window with the pointer. This should be requested
+    /// while the primary pointer button is held down.
+    StartDragMove,
+    /// Minimize the window.
+    Minimize,
+    /// Maximize the window, or restore it if it is already maximized.
+    ToggleMaximize,
+    /// Close the window.
+    Close,
+}
+
 pub(crate) trait WindowBackend {
     fn set_pointer_position(
         &mut self,
@@ -88,6 +121,8 @@ pub(crate) trait WindowBackend {
     ) -> Result<(), ()>;
     fn set_minimized(&mut self, window_id: WindowID, minimized: bool);
     fn set_maximized(&mut self, window_id: WindowID, maximized: bool);
+    fn is_maximized(&mut self, window_id: WindowID) -> bool;
+    fn drag_window(&mut self, window_id: WindowID);
     fn focus_window(&mut self, window_id: WindowID);
     fn set_window_title(&mut self, window_id: WindowID, title: String);
     fn create_window<A: Clone + 'static>(
@@ -97,6 +132,14 @@ pub(crate) trait WindowBackend {
         action_sender: &ActionSender<A>,
         res: &mut ResourceCtx,
     ) -> Result<WindowState<A>, OpenWindowError>;
+    fn create_popup_window<A: Clone + 'static>(
+        &mut self,
+        window_id: WindowID,
+        config: &PopupWindowConfig,
+        parent_scale_factor: ScaleFactor,
+        action_sender: &ActionSender<A>,
+        res: &mut ResourceCtx,
+    ) -> Result<WindowState<A>, OpenWindowError>;
     fn close_window(&mut self, window_id: WindowID);
 }
 
@@ -110,6 +153,18 @@ pub(crate) struct WindowState<A: Clone + 'static> {
     pub(crate) scale_factor: ScaleFactor,
     pub(crate) scale_factor_recip: f32,
     pub(crate) pointer_lock_state: PointerLockState,
+    /// Whether this is a popup window which should be closed when it loses
+    /// focus.
+    pub(crate) close_on_focus_lost: bool,
+    pub(crate) size_constraints: SizeConstraints,
+    /// A resize requested with `WindowContext::request_resize`.
+    pub(crate) requested_resize: Option<Size>,
+    pub(crate) present_mode: PresentMode,
+    /// Whether the surface was configured for per-pixel transparency.
+    pub(crate) transparent: bool,
+    /// The monitor the window is currently on, if known.
+    pub(crate) monitor: Option<MonitorInfo>,
+    pub(crate) text_direction: TextDirection,
 
     renderer: rootvg::Canvas,
     surface: Option<DefaultSurface<'static>>,
@@ -118,7 +173,12 @@ pub(crate) struct WindowState<A: Clone + 'static> {
     physical_size: PhysicalSizeI32,
     system_scale_factor: ScaleFactor,
     scale_factor_config: ScaleFactorConfig,
+    ui_zoom: f32,
     pointer_btn_states: [PointerBtnState; 5],
+    /// The thresholds set in `WindowConfig::double_click`. If this is `None`,
+    /// then the thresholds are queried from the OS on each press so that the
+    /// distance follows the current scale factor.
+    double_click_config: Option<DoubleClickConfig>,
 
     modifiers: Modifiers,
     current_cursor_icon: CursorIcon,
@@ -130,20 +190,61 @@ impl<A: Clone + 'static> WindowState<A> {
             return;
         }
 
-        let scale_factor = self
-            .scale_factor_config
-            .scale_factor(new_system_scale_factor);
+        let scale_factor = self.scale_factor_for(new_system_scale_factor);
 
         self.physical_size = new_size;
+        self.system_scale_factor = new_system_scale_factor;
         self.logical_size = to_logical_size_i32(new_size, scale_factor);
         self.scale_factor = scale_factor;
         self.scale_factor_recip = scale_factor.recip();
 
         self.element_system.resize(new_size, scale_factor);
-        self.surface
-            .as_mut()
-            .unwrap()
-            .resize(new_size, scale_factor);
+        if let Some(surface) = &mut self.surface {
+            surface.resize(new_size, scale_factor);
+
+            // Resizing the surface resets it to the default configuration.
+            if self.present_mode != PresentMode::default()
+                || self.transparent
+                || self.element_system.partial_redraw()
+            {
+                configure_surface(
+                    surface,
+                    new_size,
+                    self.present_mode,
+                    self.transparent,
+                    self.element_system.partial_redraw(),
+                );
+            }
+        }
+    }
+
+    pub fn set_present_mode(&mut self, present_mode: PresentMode) {
+        if self.present_mode == present_mode {
+            return;
+        }
+        self.present_mode = present_mode;
+
+        if let Some(surface) = &mut self.surface {
+            configure_surface(
+                surface,
+                self.physical_size,
+                present_mode,
+                self.transparent,
+                self.element_system.partial_redraw(),
+            );
+        }
+    }
+
+    /// The scale factor this window would have with the given system scale
+    /// factor, taking the scale factor config and the UI zoom into account.
+    pub fn scale_factor_for(&self, system_scale_factor: ScaleFactor) -> ScaleFactor {
+        let scale_factor = self.scale_factor_config.scale_factor(system_scale_factor);
+
+        if self.ui_zoom == 1.0 {
+            scale_factor
+        } else {
+            ScaleFactor::from(scale_factor.0 as f64 * self.ui_zoom as f64)
+        }
     }
 
     pub fn set_scale_factor_config(&mut self, config: ScaleFactorConfig) -> Option<Size> {
@@ -152,9 +253,24 @@ impl<A: Clone + 'static> WindowState<A> {
         }
         self.scale_factor_config = config;
 
-        let scale_factor = self
-            .scale_factor_config
-            .scale_factor(self.system_scale_factor);
+        self.update_scale_factor()
+    }
+
+    /// Set the UI zoom factor, which is applied on top of the scale factor.
+    ///
+    /// Returns the logical size the window should be resized to (in order
+    /// to keep its logical size), or `None` if the scale factor didn't change.
+    pub fn set_ui_zoom(&mut self, zoom: f32) -> Option<Size> {
+        if self.ui_zoom == zoom {
+            return None;
+        }
+        self.ui_zoom = zoom;
+
+        self.update_scale_factor()
+    }
+
+    fn update_scale_factor(&mut self) -> Option<Size> {
+        let scale_factor = self.scale_factor_for(self.system_scale_factor);
 
         if self.scale_factor == scale_factor {
             return None;
@@ -164,12 +280,12 @@ impl<A: Clone + 'static> WindowState<A> {
 
         self.scale_factor = scale_factor;
         self.scale_factor_recip = scale_factor.recip();
+        self.logical_size = to_logical_size_i32(self.physical_size, scale_factor);
 
         self.element_system.resize(self.physical_size, scale_factor);
-        self.surface
-            .as_mut()
-            .unwrap()
-            .resize(self.physical_size, scale_factor);
+        if let Some(surface) = &mut self.surface {
+            surface.resize(self.physical_size, scale_factor);
+        }
 
         Some(logical_size)
     }
@@ -316,12 +432,20 @@ impl<A: Clone + 'static> WindowState<A> {
             JustUnpressed,
         }
 
+        let position = self.prev_pointer_pos.unwrap_or(Point::zero());
+        let double_click_config = self
+            .double_click_config
+            .unwrap_or_else(|| DoubleClickConfig::from_os(self.scale_factor));
+
         let (state, click_count) = {
             let btn_state = &mut self.pointer_btn_states[button as usize];
 
             let s = if !btn_state.is_down && is_down {
                 if let Some(prev_down_instant) = btn_state.prev_down_instant.take() {
-                    if prev_down_instant.elapsed() < DOUBLE_CLICK_INTERVAL {
+                    if prev_down_instant.elapsed() <= double_click_config.interval
+                        && (position - btn_state.prev_down_position).length()
+                            <= double_click_config.distance
+                    {
                         btn_state.click_count += 1;
                     } else {
                         btn_state.click_count = 1;
@@ -329,6 +453,7 @@ impl<A: Clone + 'static> WindowState<A> {
                 }
 
                 btn_state.prev_down_instant = Some(Instant::now());
+                btn_state.prev_down_position = position;
 
                 State::JustPressed
             } else if btn_state.is_down && !is_down {
@@ -342,8 +467,6 @@ impl<A: Clone + 'static> WindowState<A> {
             (s, btn_state.click_count)
         };
 
-        let position = self.prev_pointer_pos.unwrap_or(Point::zero());
-
         match state {
             State::JustPressed => {
                 self.element_system.handle_event(
@@ -419,8 +542,34 @@ impl<A: Clone + 'static> WindowState<A> {
         action_sender: &'b mut ActionSender<A>,
         action_receiver: &'b mut ActionReceiver<A>,
     ) -> WindowContext<'b, A> {
+        let gpu = self
+            .surface
+            .as_ref()
+            .map(|surface| (&surface.device, &surface.queue, surface.format()));
+
+        self.context_with_gpu(res, action_sender, action_receiver, gpu)
+    }
+
+    /// Construct a context which renders frame captures with the given device,
+    /// queue, and texture format.
+    pub(crate) fn context_with_gpu<'b>(
+        &'b mut self,
+        res: &'b mut ResourceCtx,
+        action_sender: &'b mut ActionSender<A>,
+        action_receiver: &'b mut ActionReceiver<A>,
+        gpu: Option<(&'b wgpu::Device, &'b wgpu::Queue, wgpu::TextureFormat)>,
+    ) -> WindowContext<'b, A> {
+        let capture_target = gpu.map(|(device, queue, format)| FrameCaptureTarget {
+            device,
+            queue,
+            format,
+            multisample: self.multisample,
+            renderer: &mut self.renderer,
+        });
+
         WindowContext {
             element_system: &mut self.element_system,
+            capture_target,
             res,
             clipboard: &mut self.clipboard,
             action_sender,
@@ -428,11 +577,17 @@ impl<A: Clone + 'static> WindowState<A> {
             z_index_stack: Vec::new(),
             scissor_rect_stack: Vec::new(),
             class_stack: Vec::new(),
+            text_direction_stack: Vec::new(),
+            default_text_direction: &mut self.text_direction,
             logical_size: self.logical_size,
             physical_size: self.physical_size,
             scale_factor: self.scale_factor,
             system_scale_factor: self.system_scale_factor,
             scale_factor_config: self.scale_factor_config,
+            ui_zoom: self.ui_zoom,
+            size_constraints: &self.size_constraints,
+            requested_resize: &mut self.requested_resize,
+            monitor: self.monitor.as_ref(),
         }
     }
 
@@ -449,6 +604,10 @@ impl<A: Clone + 'static> WindowState<A> {
         self.element_system.pointer_lock_request()
     }
 
+    pub fn new_window_control_requests(&mut self) -> Vec<WindowControl> {
+        self.element_system.take_window_control_requests()
+    }
+
     pub fn on_theme_changed(&mut self, res: &mut ResourceCtx) {
         self.element_system
             .on_theme_changed(res, &mut self.clipboard);
@@ -462,6 +621,24 @@ impl<A: Clone + 'static> WindowState<A> {
     pub fn needs_repaint(&self) -> bool {
         self.element_system.needs_repaint()
     }
+
+    pub fn has_active_animations(&self) -> bool {
+        self.element_system.has_active_animations()
+    }
+
+    pub fn has_pending_timeouts(&self) -> bool {
+        self.element_system.has_pending_timeouts()
+    }
+
+    /// Regenerate the primitives of every element on the next frame.
+    pub fn mark_all_dirty(&mut self) {
+        self.element_system.mark_all_dirty();
+    }
+
+    /// Take the messages which should be announced to assistive technology.
+    pub fn take_announcements(&mut self) -> Vec<Announcement> {
+        self.element_system.take_announcements()
+    }
 }
 
 impl<A: Clone + 'static> Drop for WindowState<A> {
@@ -500,6 +677,80 @@ pub struct WindowConfig {
     pub hover_timeout_duration: Duration,
 
     pub scroll_wheel_timeout_duration: Duration,
+
+    /// The duration a pointer button must be held down without moving before
+    /// elements receive the `PointerEvent::LongPress` event. Set to `None` to
+    /// disable long-press events.
+    ///
+    /// By default this is set to 0.5 seconds.
+    pub long_press_duration: Option<Duration>,
+
+    /// The thresholds used to count consecutive clicks (see the `click_count`
+    /// field in `PointerEvent::ButtonJustPressed`).
+    ///
+    /// If this is `None`, then the thresholds set in the OS will be used.
+    ///
+    /// By default this is set to `None`.
+    pub double_click: Option<DoubleClickConfig>,
+
+    /// Constraints on the size of the window.
+    ///
+    /// By default the window has no size constraints.
+    pub size_constraints: SizeConstraints,
+
+    /// If `true`, then only the regions of the window which have changed since
+    /// the last frame will be re-rendered, and frames where nothing has changed
+    /// will be skipped entirely. This can greatly reduce GPU usage for views
+    /// where only a small portion (i.e. a meter) is animating.
+    ///
+    /// This assumes that elements do not paint outside of their bounds and that
+    /// the clear color is opaque. The surface is configured with `COPY_DST`
+    /// usage in addition to the usage in `surface_config`. If the frame
+    /// texture still lacks `COPY_DST`, then the whole window is redrawn on
+    /// every frame where something changed.
+    ///
+    /// By default this is set to `false`.
+    pub partial_redraw: bool,
+
+    /// How rendered frames are presented to the display. This can be changed
+    /// later with `AppContext::set_present_mode`.
+    ///
+    /// By default this is set to `PresentMode::Vsync`.
+    pub present_mode: PresentMode,
+
+    /// Whether the window should have the decorations provided by the OS
+    /// (i.e. the title bar and the border).
+    ///
+    /// By default this is set to `true`.
+    pub decorations: bool,
+
+    /// If `true`, then the alpha channel of the rendered frame is used as the
+    /// opacity of the window, allowing for windows with rounded corners or
+    /// drop shadows (i.e. floating tool palettes and splash screens). Set the
+    /// `clear_color` to a fully transparent color and usually set
+    /// `decorations` to `false` when using this.
+    ///
+    /// This is only supported by the winit backend, and it is not supported
+    /// on Windows. Partial redraw is disabled for transparent windows.
+    ///
+    /// By default this is set to `false`.
+    pub transparent: bool,
+
+    /// The default direction of text and horizontally laid-out content in
+    /// this window. This can be overriden for parts of the window with
+    /// `WindowContext::push_text_direction`.
+    ///
+    /// By default this is set to `TextDirection::LeftToRight`.
+    pub text_direction: TextDirection,
+
+    /// The ID of the `<canvas>` element in the web page to render this
+    /// window into. If this is `None`, then a new canvas is appended to the
+    /// body of the page.
+    ///
+    /// This only has an effect when compiling for `wasm32-unknown-unknown`.
+    ///
+    /// By default this is set to `None`.
+    pub canvas_id: Option<String>,
 }
 
 impl Default for WindowConfig {
@@ -515,10 +766,380 @@ impl Default for WindowConfig {
             preallocate_for_this_many_elements: 0,
             hover_timeout_duration: Duration::from_millis(500),
             scroll_wheel_timeout_duration: Duration::from_millis(250),
+            long_press_duration: Some(Duration::from_millis(500)),
+            double_click: None,
+            size_constraints: SizeConstraints::default(),
+            partial_redraw: false,
+            present_mode: PresentMode::default(),
+            decorations: true,
+            transparent: false,
+            text_direction: TextDirection::default(),
+            canvas_id: None,
+        }
+    }
+}
+
+/// How rendered frames are presented to the display.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PresentMode {
+    /// Wait for the vertical blank of the display before presenting a frame.
+    /// This prevents tearing and limits the frame rate to the refresh rate of
+    /// the display.
+    #[default]
+    Vsync,
+    /// Present frames as soon as they are ready. This has the lowest latency,
+    /// but it may cause tearing. If the platform doesn't support this, then
+    /// `Mailbox` or `Vsync` will be used instead.
+    NoVsync,
+    /// Present frames without tearing, replacing any frame still waiting to be
+    /// presented with the newest one. This has lower latency than `Vsync` at
+    /// the cost of higher GPU usage.
+    ///
+    /// Not all platforms support this mode (notably many Linux drivers and
+    /// older versions of Windows). Prefer `NoVsync` if unsure.
+    Mailbox,
+}
+
+impl PresentMode {
+    fn to_wgpu(self) -> wgpu::PresentMode {
+        match self {
+            Self::Vsync => wgpu::PresentMode::AutoVsync,
+            Self::NoVsync => wgpu::PresentMode::AutoNoVsync,
+            Self::Mailbox => wgpu::PresentMode::Mailbox,
+        }
+    }
+}
+
+/// Reconfigure the surface to use the given present mode, and optionally
+/// make it transparent.
+///
+/// The configuration the surface was created with from the user's
+/// `DefaultSurfaceConfig` (i.e. the frame latency and view formats) is kept.
+/// If `partial_redraw` is `true`, then `COPY_DST` usage is requested so that
+/// the backbuffer can be copied into the frame.
+pub(crate) fn configure_surface(
+    surface: &mut DefaultSurface<'_>,
+    physical_size: PhysicalSizeI32,
+    present_mode: PresentMode,
+    transparent: bool,
+    partial_redraw: bool,
+) {
+    let mut config = surface.surface_config.clone();
+
+    config.usage = if partial_redraw {
+        config.usage | wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_DST
+    } else {
+        config.usage | wgpu::TextureUsages::RENDER_ATTACHMENT
+    };
+    config.width = physical_size.width.max(1) as u32;
+    config.height = physical_size.height.max(1) as u32;
+    config.present_mode = present_mode.to_wgpu();
+    if transparent {
+        config.alpha_mode = transparent_alpha_mode();
+    }
+
+    surface.surface.configure(&surface.device, &config);
+
+    // Store the configuration so that it is reused when the surface is resized.
+    surface.surface_config = config;
+}
+
+/// The alpha mode used for transparent windows on this platform.
+///
+/// The capabilities of the surface can't be queried here, so this relies on
+/// the modes which the graphics backends of each platform support. Window
+/// transparency isn't supported by the swapchains on Windows, so the alpha
+/// channel is ignored there.
+fn transparent_alpha_mode() -> wgpu::CompositeAlphaMode {
+    if cfg!(any(target_os = "macos", target_os = "ios")) {
+        wgpu::CompositeAlphaMode::PostMultiplied
+    } else if cfg!(target_os = "windows") {
+        log::warn!("Yarrow: transparent windows are not supported on Windows");
+        wgpu::CompositeAlphaMode::Auto
+    } else {
+        wgpu::CompositeAlphaMode::PreMultiplied
+    }
+}
+
+/// Information about a monitor.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonitorInfo {
+    /// The human-readable name of the monitor, if available.
+    pub name: Option<String>,
+    /// The position of the top-left corner of the monitor in physical pixels,
+    /// relative to the top-left corner of the desktop.
+    pub position: PhysicalPoint,
+    /// The size of the monitor in physical pixels.
+    pub size: PhysicalSizeI32,
+    /// The scale factor the OS uses for this monitor.
+    pub scale_factor: ScaleFactor,
+    /// The refresh rate of the monitor in millihertz, if available.
+    pub refresh_rate_millihertz: Option<u32>,
+}
+
+impl MonitorInfo {
+    /// The refresh rate of the monitor in hertz, if available.
+    pub fn refresh_rate_hz(&self) -> Option<f64> {
+        self.refresh_rate_millihertz
+            .map(|millihertz| millihertz as f64 / 1_000.0)
+    }
+
+    /// The duration of a single frame on this monitor, if the refresh rate
+    /// is available.
+    pub fn frame_duration(&self) -> Option<Duration> {
+        self.refresh_rate_millihertz
+            .filter(|millihertz| *millihertz > 0)
+            .map(|millihertz| Duration::from_secs_f64(1_000.0 / millihertz as f64))
+    }
+}
+
+/// Constraints on the logical size of a window.
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SizeConstraints {
+    /// The minimum size of the window in logical points.
+    pub min_size: Option<Size>,
+    /// The maximum size of the window in logical points.
+    pub max_size: Option<Size>,
+    /// If this is `Some`, then the window will keep this ratio of width to
+    /// height when it is resized.
+    pub aspect_ratio: Option<f32>,
+    /// A list of discrete sizes in logical points (i.e. `100%`, `150%`, and
+    /// `200%` of the default size of a plugin GUI).
+    ///
+    /// If this is not empty, then the window can only be one of these sizes,
+    /// and any other size is snapped to the nearest one.
+    pub presets: Vec<Size>,
+}
+
+impl SizeConstraints {
+    /// Returns the size closest to the given size which satisfies these
+    /// constraints.
+    pub fn constrain(&self, size: Size) -> Size {
+        if let Some(preset) = self.presets.iter().min_by(|a, b| {
+            let dist_a = (a.width - size.width).abs() + (a.height - size.height).abs();
+            let dist_b = (b.width - size.width).abs() + (b.height - size.height).abs();
+            dist_a.total_cmp(&dist_b)
+        }) {
+            return *preset;
+        }
+
+        let mut size = self.clamp(size);
+
+        if let Some(aspect_ratio) = self.aspect_ratio {
+            if aspect_ratio > 0.0 {
+                size.height = size.width / aspect_ratio;
+                size = self.clamp(size);
+                size.width = size.height * aspect_ratio;
+            }
+        }
+
+        size
+    }
+
+    fn clamp(&self, mut size: Size) -> Size {
+        if let Some(min_size) = self.min_size {
+            size.width = size.width.max(min_size.width);
+            size.height = size.height.max(min_size.height);
+        }
+        if let Some(max_size) = self.max_size {
+            size.width = size.width.min(max_size.width);
+            size.height = size.height.min(max_size.height);
+        }
+
+        size
+    }
+}
+
+/// A frame of a view rendered into RGBA pixels.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapturedFrame {
+    /// The width of the frame in physical pixels.
+    pub width: u32,
+    /// The height of the frame in physical pixels.
+    pub height: u32,
+    /// The pixels of the frame in row-major order, with four bytes (red,
+    /// green, blue, and alpha) per pixel.
+    pub pixels: Vec<u8>,
+}
+
+impl CapturedFrame {
+    /// Copy the contents of a texture (which must have the `COPY_SRC` usage)
+    /// into a new frame. This blocks until the GPU has finished.
+    pub(crate) fn read_texture(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        texture: &wgpu::Texture,
+    ) -> Self {
+        let width = texture.width();
+        let height = texture.height();
+
+        // Rows in the staging buffer must be aligned to `COPY_BYTES_PER_ROW_ALIGNMENT`.
+        let unpadded_bytes_per_row = width * 4;
+        let padded_bytes_per_row = unpadded_bytes_per_row
+            .div_ceil(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT)
+            * wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("yarrow frame capture buffer"),
+            size: padded_bytes_per_row as u64 * height as u64,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder =
+            device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+        encoder.copy_texture_to_buffer(
+            texture.as_image_copy(),
+            wgpu::ImageCopyBuffer {
+                buffer: &buffer,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_bytes_per_row),
+                    rows_per_image: Some(height),
+                },
+            },
+            texture.size(),
+        );
+        queue.submit(Some(encoder.finish()));
+
+        let slice = buffer.slice(..);
+        slice.map_async(wgpu::MapMode::Read, |_| {});
+        device.poll(wgpu::Maintain::Wait);
+
+        let mut pixels = Vec::with_capacity((unpadded_bytes_per_row * height) as usize);
+        {
+            let data = slice.get_mapped_range();
+            for row in data.chunks(padded_bytes_per_row as usize) {
+                pixels.extend_from_slice(&row[..unpadded_bytes_per_row as usize]);
+            }
+        }
+        buffer.unmap();
+
+        if matches!(
+            texture.format(),
+            wgpu::TextureFormat::Bgra8Unorm | wgpu::TextureFormat::Bgra8UnormSrgb
+        ) {
+            for pixel in pixels.chunks_exact_mut(4) {
+                pixel.swap(0, 2);
+            }
+        }
+
+        Self {
+            width,
+            height,
+            pixels,
         }
     }
 }
 
+/// The configuration of a lightweight popup window (i.e. for a dropdown menu,
+/// a context menu, or a tooltip) which can extend beyond the bounds of its
+/// parent window.
+///
+/// See [`AppContext::open_popup_window`](crate::AppContext::open_popup_window).
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PopupWindowConfig {
+    /// The window that this popup belongs to.
+    pub parent: WindowID,
+    /// The position of the top-left corner of the popup in logical points,
+    /// relative to the top-left corner of the parent window's content area.
+    ///
+    /// This may lie outside of the parent window.
+    pub position: Point,
+    /// The size of the popup in logical points.
+    pub size: Size,
+    /// Whether or not the popup should be closed automatically when it loses
+    /// focus (i.e. when the user clicks outside of it).
+    ///
+    /// By default this is set to `true`.
+    pub close_on_focus_lost: bool,
+    /// Whether or not the popup should take focus when it is opened.
+    ///
+    /// By default this is set to `true`.
+    pub focus_on_creation: bool,
+    pub surface_config: DefaultSurfaceConfig,
+    /// The clear color.
+    pub clear_color: PackedSrgb,
+    /// Whether the popup has per-pixel transparency (i.e. for rounded corners
+    /// or drop shadows). See `WindowConfig::transparent`.
+    ///
+    /// By default this is set to `false`.
+    pub transparent: bool,
+    /// The thresholds used to count consecutive clicks. If this is `None`,
+    /// then the thresholds set in the OS will be used.
+    ///
+    /// By default this is set to `None`.
+    pub double_click: Option<DoubleClickConfig>,
+}
+
+impl Default for PopupWindowConfig {
+    fn default() -> Self {
+        Self {
+            parent: MAIN_WINDOW,
+            position: Point::zero(),
+            size: Size::new(200.0, 100.0),
+            close_on_focus_lost: true,
+            focus_on_creation: true,
+            surface_config: DefaultSurfaceConfig::default(),
+            clear_color: PackedSrgb::BLACK,
+            transparent: false,
+            double_click: None,
+        }
+    }
+}
+
+impl PopupWindowConfig {
+    /// The equivalent configuration for a regular window.
+    pub(crate) fn window_config(&self) -> WindowConfig {
+        WindowConfig {
+            title: String::new(),
+            size: self.size,
+            resizable: false,
+            surface_config: self.surface_config.clone(),
+            focus_on_creation: self.focus_on_creation,
+            clear_color: self.clear_color,
+            decorations: false,
+            transparent: self.transparent,
+            double_click: self.double_click,
+            ..Default::default()
+        }
+    }
+}
+
+/// Where a popup requested with
+/// [`AppContext::open_popup_window`](crate::AppContext::open_popup_window)
+/// will be shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PopupKind {
+    /// The popup will be shown in its own native window. Build its elements
+    /// once the `AppWindowEvent::WindowOpened` event is received for the
+    /// popup's window ID.
+    NativeWindow,
+    /// Native popup windows are not supported by the platform or host, so no
+    /// window was opened. Show the popup as an overlay in the parent window
+    /// instead (i.e. with `DropDownMenu::open` or a `Tooltip`).
+    InWindowOverlay,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum OpenWindowError {
+    #[cfg(feature = "winit")]
+    #[error("{0}")]
+    OsError(#[from] winit::error::OsError),
+    #[cfg(feature = "winit")]
+    #[error("{0}")]
+    NotSupported(#[from] winit::error::NotSupportedError),
+    #[error("{0}")]
+    SurfaceError(#[from] rootvg::surface::NewSurfaceError),
+    #[error("Parent window {0} does not exist")]
+    ParentWindowNotFound(WindowID),
+    #[error("The windowing backend does not support multiple windows")]
+    MultiWindowNotSupported,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum WindowCloseRequest {
     DoNotCloseYet,
@@ -553,11 +1174,26 @@ pub struct WindowContext<'a, A: Clone + 'static> {
     z_index_stack: Vec<ZIndex>,
     scissor_rect_stack: Vec<ScissorRectID>,
     class_stack: Vec<ClassID>,
+    text_direction_stack: Vec<TextDirection>,
+    default_text_direction: &'a mut TextDirection,
     logical_size: Size,
     physical_size: PhysicalSizeI32,
     scale_factor: ScaleFactor,
     scale_factor_config: ScaleFactorConfig,
     system_scale_factor: ScaleFactor,
+    ui_zoom: f32,
+    size_constraints: &'a SizeConstraints,
+    requested_resize: &'a mut Option<Size>,
+    capture_target: Option<FrameCaptureTarget<'a>>,
+    monitor: Option<&'a MonitorInfo>,
+}
+
+struct FrameCaptureTarget<'a> {
+    device: &'a wgpu::Device,
+    queue: &'a wgpu::Queue,
+    format: wgpu::TextureFormat,
+    multisample: wgpu::MultisampleState,
+    renderer: &'a mut rootvg::Canvas,
 }
 
 impl<'a, A: Clone + 'static> WindowContext<'a, A> {
@@ -581,6 +1217,93 @@ impl<'a, A: Clone + 'static> WindowContext<'a, A> {
         self.scale_factor_config
     }
 
+    /// The UI zoom factor set with `AppContext::set_ui_zoom`.
+    pub fn ui_zoom(&self) -> f32 {
+        self.ui_zoom
+    }
+
+    pub fn size_constraints(&self) -> &SizeConstraints {
+        self.size_constraints
+    }
+
+    /// The monitor this window is currently on, or `None` if it is not known
+    /// (i.e. when using the baseview backend).
+    pub fn monitor(&self) -> Option<&MonitorInfo> {
+        self.monitor
+    }
+
+    /// Request to resize the window to the given logical size.
+    ///
+    /// The size is adjusted to satisfy the window's [`SizeConstraints`].
+    pub fn request_resize(&mut self, logical_size: Size) {
+        *self.requested_resize = Some(self.size_constraints.constrain(logical_size));
+    }
+
+    /// Request to resize the window to the size preset with the given index
+    /// in [`SizeConstraints::presets`].
+    ///
+    /// Returns `false` if no preset exists at that index.
+    pub fn request_size_preset(&mut self, index: usize) -> bool {
+        if let Some(size) = self.size_constraints.presets.get(index) {
+            *self.requested_resize = Some(*size);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Render the current state of the view and return its pixels (i.e. for
+    /// attaching a screenshot to a bug report or saving a snapshot of the UI
+    /// along with a preset).
+    ///
+    /// This blocks until the GPU has finished rendering. Returns `None` if
+    /// the window does not have a surface to render with.
+    pub fn capture_frame(&mut self) -> Option<CapturedFrame> {
+        let target = self.capture_target.as_mut()?;
+
+        let texture = target.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("yarrow frame capture texture"),
+            size: wgpu::Extent3d {
+                width: self.physical_size.width.max(1) as u32,
+                height: self.physical_size.height.max(1) as u32,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: target.format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        self.element_system.render_to_texture(
+            &view,
+            target.device,
+            target.queue,
+            target.format,
+            target.multisample,
+            target.renderer,
+            self.res,
+        );
+
+        Some(CapturedFrame::read_texture(
+            target.device,
+            target.queue,
+            &texture,
+        ))
+    }
+
+    /// The wgpu device and queue which this window renders with, i.e. for
+    /// creating textures which are displayed in an `ExternalTexture` element.
+    ///
+    /// Returns `None` if the window does not have a surface to render with.
+    pub fn gpu(&self) -> Option<(&wgpu::Device, &wgpu::Queue)> {
+        self.capture_target
+            .as_ref()
+            .map(|target| (target.device, target.queue))
+    }
+
     /// Get the current z index from the stack (peek)
     pub fn z_index(&self) -> ZIndex {
         self.z_index_stack.last().copied().unwrap_or_default()
@@ -596,6 +1319,45 @@ impl<'a, A: Clone + 'static> WindowContext<'a, A> {
         self.class_stack.last().map(|s| *s).unwrap_or_default()
     }
 
+    /// Get the current text direction from the stack (peek)
+    ///
+    /// If the stack is empty, then the default text direction of the window
+    /// is returned.
+    pub fn text_direction(&self) -> TextDirection {
+        self.text_direction_stack
+            .last()
+            .copied()
+            .unwrap_or(*self.default_text_direction)
+    }
+
+    /// Set the default text direction of this window.
+    ///
+    /// This only affects elements which are built after this is called.
+    pub fn set_default_text_direction(&mut self, text_direction: TextDirection) {
+        *self.default_text_direction = text_direction;
+    }
+
+    /// Push a text direction onto the stack
+    pub fn push_text_direction(&mut self, text_direction: TextDirection) {
+        self.text_direction_stack.push(text_direction);
+    }
+
+    /// Pop a text direction from the stack
+    pub fn pop_text_direction(&mut self) -> Option<TextDirection> {
+        self.text_direction_stack.pop()
+    }
+
+    pub fn with_text_direction<T, F: FnOnce(&mut Self) -> T>(
+        &mut self,
+        text_direction: TextDirection,
+        f: F,
+    ) -> T {
+        self.push_text_direction(text_direction);
+        let r = (f)(self);
+        self.pop_text_direction();
+        r
+    }
+
     /// Push a z index onto the stack
     pub fn push_z_index(&mut self, z_index: ZIndex) {
         self.z_index_stack.push(z_index)
@@ -683,12 +1445,35 @@ impl<'a, A: Clone + 'static> WindowContext<'a, A> {
             .add_element(element_builder, &mut self.res, &mut self.clipboard)
     }
 
+    /// Handle a directional navigation input (i.e. from a gamepad or a hardware
+    /// controller).
+    ///
+    /// The directional inputs move focus spatially between focusable elements,
+    /// and `NavigationInput::Activate` sends `ElementEvent::Activate` to the
+    /// focused element.
+    ///
+    /// Returns `true` if the input was handled.
+    pub fn navigate(&mut self, input: NavigationInput) -> bool {
+        self.element_system
+            .navigate(input, &mut self.res, &mut self.clipboard)
+    }
+
+    /// Move keyboard focus to the next focusable element (or the previous
+    /// element if `backwards` is `true`), as if the user pressed Tab or
+    /// Shift+Tab.
+    ///
+    /// Returns `false` if there are no visible focusable elements.
+    pub fn focus_next(&mut self, backwards: bool) -> bool {
+        self.element_system
+            .focus_next(backwards, &mut self.res, &mut self.clipboard)
+    }
+
     pub fn set_clear_color(&mut self, color: impl Into<PackedSrgb>) {
-        self.element_system.clear_color = color.into()
+        self.element_system.set_clear_color(color.into());
     }
 
     pub fn clear_color(&self) -> PackedSrgb {
-        self.element_system.clear_color
+        self.element_system.clear_color()
     }
 
     pub fn set_tooltip_actions<S, H>(&mut self, on_show_tooltip: S, on_hide_tooltip: H)
@@ -700,6 +1485,33 @@ impl<'a, A: Clone + 'static> WindowContext<'a, A> {
             .set_tooltip_actions(on_show_tooltip, on_hide_tooltip)
     }
 
+    /// Set a filter which sees every pointer, keyboard, and text composition
+    /// event in this window before it is routed to any element.
+    ///
+    /// If the filter returns `EventCaptureStatus::Captured`, then the event
+    /// is consumed and no element will receive it. This is useful for things
+    /// like global overlays, tutorials, or a "MIDI learn" mode which
+    /// intercepts clicks on any parameter element.
+    ///
+    /// Pointer events include the topmost element under the pointer, so the
+    /// filter can tell which element would have received the event (i.e. to
+    /// look up the parameter of a clicked knob). If the filter consumes a
+    /// pointer move, then the hover state of the elements is still updated.
+    ///
+    /// Only one filter can be set per window. Setting a new filter replaces
+    /// the previous one.
+    pub fn set_event_filter<F>(&mut self, filter: F)
+    where
+        F: FnMut(InputEvent, &mut ActionSender<A>) -> EventCaptureStatus + 'static,
+    {
+        self.element_system.set_event_filter(filter)
+    }
+
+    /// Remove the event filter set with [`WindowContext::set_event_filter`].
+    pub fn clear_event_filter(&mut self) {
+        self.element_system.clear_event_filter()
+    }
+
     /// Get the current rectangle of the given scissoring rectangle.
     ///
     /// If a scissoring rectangle with the given ID does not exist, then
@@ -740,6 +1552,25 @@ impl<'a, A: Clone + 'static> WindowContext<'a, A> {
             .update_scissor_rect(scissor_rect_id, new_rect, new_scroll_offset)
     }
 
+    /// Get the current zoom factor of the given scissoring rectangle.
+    ///
+    /// If a scissoring rectangle with the given ID does not exist, then
+    /// one will be created.
+    pub fn scissor_rect_zoom(&mut self, scissor_rect_id: ScissorRectID) -> f32 {
+        self.element_system.scissor_rect_zoom(scissor_rect_id)
+    }
+
+    /// Set the zoom factor of the given scissoring rectangle. The offsets
+    /// (relative to the origin of the scissoring rectangle) and the sizes of
+    /// all elements assigned to it are multiplied by this factor.
+    ///
+    /// If `scissor_rect_id == ScissorRectID::DEFAULT`, then this
+    /// will do nothing.
+    pub fn set_scissor_rect_zoom(&mut self, scissor_rect_id: ScissorRectID, zoom: f32) {
+        self.element_system
+            .set_scissor_rect_zoom(scissor_rect_id, zoom)
+    }
+
     /// Returns the bounding rectangle of the given element, accounting for scroll offset.
     ///
     /// If the element has been dropped, then this will return `None`.
@@ -751,9 +1582,198 @@ impl<'a, A: Clone + 'static> WindowContext<'a, A> {
         self.element_system.element_is_hovered(element)
     }
 
+    /// Returns `true` if the element with the given ID has not been dropped.
+    pub fn element_exists(&self, element_id: ElementID) -> bool {
+        self.element_system.element_exists(element_id)
+    }
+
+    /// Look up an element by the key that was assigned to it in its builder.
+    ///
+    /// Returns `None` if no element with that key exists (or if it has been
+    /// dropped).
+    pub fn element_by_key(&self, key: impl Into<ElementKey>) -> Option<ElementRef> {
+        self.element_system.element_by_key(&key.into())
+    }
+
+    /// The number of element instances in this window.
+    pub fn num_elements(&self) -> usize {
+        self.element_system.num_elements()
+    }
+
+    /// Move the given group of elements above all other elements whose z
+    /// index lies in `band` (i.e. when a floating panel or a node is clicked).
+    ///
+    /// The group is moved as a whole, so the z indexes of its elements
+    /// relative to each other are kept (i.e. the background of a panel and
+    /// the elements on top of it). If there is no room left at the top of the
+    /// band, then the z indexes of the other elements in the band are
+    /// compacted while keeping their order.
+    ///
+    /// The z index cached in the handles of the restacked elements is updated
+    /// as well, so `ElementHandle::z_index` and `ElementHandle::set_z_index`
+    /// keep working as expected afterwards.
+    ///
+    /// Returns `true` if the z index of any element has changed.
+    pub fn bring_to_front(&mut self, group: &[ElementID], band: RangeInclusive<ZIndex>) -> bool {
+        self.element_system.restack_elements(
+            group,
+            band,
+            StackPosition::Front,
+            self.res,
+            self.clipboard,
+        )
+    }
+
+    /// Move the given group of elements below all other elements whose z
+    /// index lies in `band`.
+    ///
+    /// See [`WindowContext::bring_to_front`] for how the group is moved.
+    ///
+    /// Returns `true` if the z index of any element has changed.
+    pub fn send_to_back(&mut self, group: &[ElementID], band: RangeInclusive<ZIndex>) -> bool {
+        self.element_system.restack_elements(
+            group,
+            band,
+            StackPosition::Back,
+            self.res,
+            self.clipboard,
+        )
+    }
+
+    /// Move the given group of elements directly above the `other` element,
+    /// and below any elements in `band` which were above `other`.
+    ///
+    /// If `other` belongs to a group as well, then pass the topmost element
+    /// of that group. Nothing happens if the z index of `other` does not lie
+    /// in `band`.
+    ///
+    /// See [`WindowContext::bring_to_front`] for how the group is moved.
+    ///
+    /// Returns `true` if the z index of any element has changed.
+    pub fn raise_above(
+        &mut self,
+        group: &[ElementID],
+        other: ElementID,
+        band: RangeInclusive<ZIndex>,
+    ) -> bool {
+        self.element_system.restack_elements(
+            group,
+            band,
+            StackPosition::Above(other),
+            self.res,
+            self.clipboard,
+        )
+    }
+
+    /// Run the given closure as a single batch of element updates.
+    ///
+    /// Updates to elements are always deferred until the closure returns, but
+    /// without batching each call to a handle method (i.e. syncing 100
+    /// parameters from the host) sends its own update to the element. Inside
+    /// a batch, repeated updates of the same kind to the same element are
+    /// coalesced so each element only handles the final state once.
+    ///
+    /// Batches can be nested.
+    pub fn batch<R>(&mut self, f: impl FnOnce(&mut Self) -> R) -> R {
+        self.element_system.begin_batch();
+        let r = f(self);
+        self.element_system.end_batch();
+        r
+    }
+
+    /// Statistics about the last frame which was presented in this window.
+    pub fn frame_stats(&self) -> &FrameStats {
+        self.element_system.frame_stats()
+    }
+
+    pub fn show_stats_overlay(&self) -> bool {
+        self.element_system.show_stats_overlay()
+    }
+
+    /// Show or hide an overlay in the top-left corner of the window which
+    /// displays the [`FrameStats`] of the previous frame.
+    ///
+    /// Note that the window is redrawn continuously while the overlay is
+    /// shown.
+    pub fn set_show_stats_overlay(&mut self, show: bool) {
+        self.element_system.set_show_stats_overlay(show);
+    }
+
+    pub fn view_focus_ring_enabled(&self) -> bool {
+        self.element_system.view_focus_ring_enabled()
+    }
+
+    /// Enable or disable the focus ring drawn by the view.
+    ///
+    /// When enabled, the view draws a focus ring around the focused element
+    /// whenever focus was moved to it with keyboard navigation, unless the
+    /// element draws its own (see `ElementFlags::DRAWS_OWN_FOCUS_RING`). This
+    /// makes it possible to add keyboard navigation to custom elements
+    /// without changing how they render.
+    ///
+    /// The ring uses the style set with `StyleSystem::set_focus_ring`.
+    ///
+    /// By default this is disabled.
+    pub fn set_view_focus_ring_enabled(&mut self, enabled: bool) {
+        self.element_system.set_view_focus_ring_enabled(enabled);
+    }
+
+    /// Queue a transient message (i.e. "Preset loaded" or "Recording
+    /// started") to be announced, independent of anything shown on screen.
+    ///
+    /// Yarrow does not integrate with any screen reader itself. The message
+    /// is only delivered to the application as an
+    /// `AppWindowEvent::Announcement` event, and the application has to
+    /// forward it to assistive technology itself.
+    pub fn announce(&mut self, message: impl Into<String>, priority: AnnouncePriority) {
+        self.element_system.announce(Announcement {
+            message: message.into(),
+            priority,
+        });
+    }
+
+    pub fn color_vision_simulation(&self) -> Option<ColorVisionDeficiency> {
+        self.element_system.color_vision_simulation()
+    }
+
+    /// Simulate the given type of color vision deficiency over the whole
+    /// rendered frame of this window, or `None` to disable the simulation.
+    ///
+    /// This is a debugging aid for checking that colors remain
+    /// distinguishable. Note that partial redraws are disabled while the
+    /// simulation is active.
+    pub fn set_color_vision_simulation(&mut self, deficiency: Option<ColorVisionDeficiency>) {
+        self.element_system.set_color_vision_simulation(deficiency);
+    }
+
     pub fn auto_hide_tooltip(&mut self) {
         self.element_system.auto_hide_tooltip()
     }
+
+    /// Draw a custom wgpu render pass inside the rect of the given
+    /// placeholder element (i.e. a [`QuadElement`](crate::elements::quad::QuadElement)
+    /// or a [`ClickArea`](crate::elements::click_area::ClickArea) for
+    /// receiving pointer input).
+    ///
+    /// Elements with a lower z index than the placeholder element are drawn
+    /// below the render pass, and all other elements are drawn on top of it.
+    /// The render pass is only drawn while the placeholder element is visible,
+    /// and it is removed when the placeholder element is dropped. This
+    /// replaces any render pass previously added for the same element.
+    ///
+    /// Note that partial redraws are disabled while a render pass is visible.
+    pub fn add_render_pass(&mut self, element_id: ElementID, pass: impl CustomRenderPass) {
+        self.element_system
+            .add_render_pass(element_id, Box::new(pass));
+    }
+
+    /// Remove the render pass which was added for the given placeholder
+    /// element.
+    ///
+    /// Returns `true` if the element had a render pass.
+    pub fn remove_render_pass(&mut self, element_id: ElementID) -> bool {
+        self.element_system.remove_render_pass(element_id)
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -14,12 +14,13 @@ use rootvg::text::FontSystem;
 use std::error::Error;
 use std::num::{NonZeroIsize, NonZeroU32};
 use std::ptr::NonNull;
+use std::sync::{Arc, Mutex};
 
 mod convert;
 
 use super::{
-    LinuxBackendType, ScaleFactorConfig, WindowBackend, WindowConfig, WindowID, WindowState,
-    MAIN_WINDOW,
+    configure_surface, LinuxBackendType, OpenWindowError, PopupWindowConfig, PresentMode,
+    ScaleFactorConfig, WindowBackend, WindowConfig, WindowID, WindowState, MAIN_WINDOW,
 };
 use crate::action_queue::ActionSender;
 use crate::application::Application;
@@ -73,19 +74,19 @@ impl<'a, 'b> WindowBackend for BaseviewWindowBackend<'a, 'b> {
         if window_id == MAIN_WINDOW {
             self.main_window.set_mouse_cursor(match icon {
                 CursorIcon::Default => MouseCursor::Default,
-                CursorIcon::ContextMenu => todo!("ContextMenu"),
+                CursorIcon::ContextMenu => MouseCursor::Default,
                 CursorIcon::Help => MouseCursor::Help,
                 CursorIcon::Pointer => MouseCursor::Hand,
-                CursorIcon::Progress => todo!("Progress"),
-                CursorIcon::Wait => todo!("Wait"),
-                CursorIcon::Cell => todo!("Cell"),
+                CursorIcon::Progress => MouseCursor::PtrWorking,
+                CursorIcon::Wait => MouseCursor::Working,
+                CursorIcon::Cell => MouseCursor::Cell,
                 CursorIcon::Crosshair => MouseCursor::Crosshair,
                 CursorIcon::Text => MouseCursor::Text,
-                CursorIcon::VerticalText => todo!("VerticalText"),
+                CursorIcon::VerticalText => MouseCursor::VerticalText,
                 CursorIcon::Alias => MouseCursor::Alias,
                 CursorIcon::Copy => MouseCursor::Copy,
                 CursorIcon::Move => MouseCursor::Move,
-                CursorIcon::NoDrop => todo!("NoDrop"),
+                CursorIcon::NoDrop => MouseCursor::PtrNotAllowed,
                 CursorIcon::NotAllowed => MouseCursor::NotAllowed,
                 CursorIcon::Grab => MouseCursor::Hand,
                 CursorIcon::Grabbing => MouseCursor::HandGrabbing,
@@ -135,6 +136,14 @@ impl<'a, 'b> WindowBackend for BaseviewWindowBackend<'a, 'b> {
         // Baseview does not support maximizing the window yet.
     }
 
+    fn is_maximized(&mut self, _window_id: WindowID) -> bool {
+        false
+    }
+
+    fn drag_window(&mut self, _window_id: WindowID) {
+        // Baseview does not support moving the window yet.
+    }
+
     fn focus_window(&mut self, window_id: WindowID) {
         if window_id == MAIN_WINDOW {
             self.main_window.focus();
@@ -156,6 +165,18 @@ impl<'a, 'b> WindowBackend for BaseviewWindowBackend<'a, 'b> {
         Err(OpenWindowError::MultiWindowNotSupported)
     }
 
+    fn create_popup_window<A: Clone + 'static>(
+        &mut self,
+        _window_id: WindowID,
+        _config: &PopupWindowConfig,
+        _parent_scale_factor: ScaleFactor,
+        _action_sender: &ActionSender<A>,
+        _res: &mut ResourceCtx,
+    ) -> Result<WindowState<A>, OpenWindowError> {
+        // Baseview does not support multiple windows yet.
+        Err(OpenWindowError::MultiWindowNotSupported)
+    }
+
     fn close_window(&mut self, window_id: WindowID) {
         if window_id == MAIN_WINDOW {
             self.main_window.close();
@@ -165,6 +186,19 @@ impl<'a, 'b> WindowBackend for BaseviewWindowBackend<'a, 'b> {
 
 struct BaseviewAppHandlerInner {
     first_resize: bool,
+    /// The size and visibility requested by the host when the window is
+    /// embedded in a parent window.
+    host_requests: Option<Arc<Mutex<HostRequests>>>,
+    /// Whether the host has hidden the embedded window.
+    hidden_by_host: bool,
+}
+
+/// Requests from the host of an embedded window which are applied on the
+/// next frame.
+#[derive(Default)]
+struct HostRequests {
+    size: Option<Size>,
+    visible: Option<bool>,
 }
 
 struct BaseviewAppHandler<A: Application> {
@@ -210,11 +244,18 @@ impl<A: Application> BaseviewAppHandler<A> {
             config,
             res,
             linux_backend_type,
+            // Baseview does not support multiple windows yet, so popups are
+            // shown as in-window overlays.
+            false,
         )?;
 
         Ok(Self {
             app_handler,
-            inner: BaseviewAppHandlerInner { first_resize: true },
+            inner: BaseviewAppHandlerInner {
+                first_resize: true,
+                host_requests: None,
+                hidden_by_host: false,
+            },
         })
     }
 
@@ -228,7 +269,46 @@ impl<A: Application> BaseviewAppHandler<A> {
 
 impl<A: Application> BaseviewWindowHandler for BaseviewAppHandler<A> {
     fn on_frame(&mut self, window: &mut BaseviewWindow) {
-        self.app_handler.on_tick();
+        if let Some(host_requests) = &self.inner.host_requests {
+            let mut host_requests = host_requests.lock().unwrap();
+
+            if let Some(size) = host_requests.size.take() {
+                window.resize(baseview::Size {
+                    width: size.width as f64,
+                    height: size.height as f64,
+                });
+            }
+
+            if let Some(visible) = host_requests.visible.take() {
+                if visible == self.inner.hidden_by_host {
+                    self.inner.hidden_by_host = !visible;
+
+                    let res = &mut self.app_handler.cx.res;
+                    if visible {
+                        self.app_handler.cx.main_window.handle_window_shown(res);
+                    } else {
+                        self.app_handler.cx.main_window.handle_window_hidden(res);
+                    }
+                }
+            }
+        }
+
+        if self.inner.hidden_by_host {
+            // Keep processing actions, but don't render while the host has
+            // hidden the window.
+            self.process_updates(window);
+            return;
+        }
+
+        // The host drives the frame rate, so skip ticks to honor the frame
+        // rate limit.
+        let throttled = self
+            .app_handler
+            .min_tick_interval()
+            .is_some_and(|min| self.app_handler.prev_tick_instant.elapsed() < min);
+        if !throttled {
+            self.app_handler.on_tick();
+        }
         self.process_updates(window);
 
         if let Err(e) = self
@@ -248,7 +328,6 @@ impl<A: Application> BaseviewWindowHandler for BaseviewAppHandler<A> {
     ) -> baseview::EventStatus {
         let mut process_updates = true;
 
-        // TODO:
         match event {
             baseview::Event::Mouse(mouse_event) => match mouse_event {
                 baseview::MouseEvent::CursorMoved {
@@ -326,22 +405,11 @@ impl<A: Application> BaseviewWindowHandler for BaseviewAppHandler<A> {
                     .cx
                     .main_window
                     .handle_pointer_left(&mut self.app_handler.cx.res),
-                baseview::MouseEvent::DragEntered {
-                    position,
-                    modifiers,
-                    data,
-                } => (),
-                baseview::MouseEvent::DragMoved {
-                    position,
-                    modifiers,
-                    data,
-                } => (),
-                baseview::MouseEvent::DragLeft => (),
-                baseview::MouseEvent::DragDropped {
-                    position,
-                    modifiers,
-                    data,
-                } => (),
+                // OS-level drag-and-drop of files is not supported yet.
+                baseview::MouseEvent::DragEntered { .. }
+                | baseview::MouseEvent::DragMoved { .. }
+                | baseview::MouseEvent::DragLeft
+                | baseview::MouseEvent::DragDropped { .. } => (),
             },
             baseview::Event::Keyboard(keyboard_event) => {
                 let key_event = self::convert::convert_keyboard_event(&keyboard_event);
@@ -385,11 +453,8 @@ impl<A: Application> BaseviewWindowHandler for BaseviewAppHandler<A> {
                 }
 
                 if !captured {
-                    self.app_handler.user_app.on_keyboard_event(
-                        key_event,
-                        MAIN_WINDOW,
-                        &mut self.app_handler.cx,
-                    );
+                    self.app_handler
+                        .handle_uncaptured_keyboard_event(key_event, MAIN_WINDOW);
                 }
             }
             baseview::Event::Window(window_event) => match window_event {
@@ -463,12 +528,6 @@ impl<A: Application> BaseviewWindowHandler for BaseviewAppHandler<A> {
     }
 }
 
-#[derive(thiserror::Error, Debug)]
-pub enum OpenWindowError {
-    #[error("Baseview does not yet support multiple windows")]
-    MultiWindowNotSupported,
-}
-
 pub fn run_blocking<A: Application + 'static>(app_config: AppConfig) -> Result<(), Box<dyn Error>> {
     let options = WindowOpenOptions {
         title: app_config.main_window_config.title.clone(),
@@ -490,10 +549,63 @@ pub fn run_blocking<A: Application + 'static>(app_config: AppConfig) -> Result<(
     Ok(())
 }
 
-pub fn run_parented<P: HasRawWindowHandle, A: Application + 'static, B>(
+/// A raw handle to a window owned by someone else (i.e. the editor window
+/// provided by a plugin host) which Yarrow can embed its window into.
+#[derive(Debug, Clone, Copy)]
+pub struct ParentWindow(pub raw_window_handle::RawWindowHandle);
+
+unsafe impl HasRawWindowHandle for ParentWindow {
+    fn raw_window_handle(&self) -> raw_window_handle::RawWindowHandle {
+        self.0
+    }
+}
+
+/// A handle to a Yarrow window which is embedded in a parent window.
+///
+/// The size of an embedded window is controlled by the host, so use
+/// [`ParentedWindowHandle::set_size`] whenever the host resizes the
+/// parent window.
+pub struct ParentedWindowHandle {
+    handle: WindowHandle,
+    host_requests: Arc<Mutex<HostRequests>>,
+}
+
+impl ParentedWindowHandle {
+    /// Resize the embedded window to the given logical size.
+    ///
+    /// The resize will happen on the next frame.
+    pub fn set_size(&self, logical_size: Size) {
+        self.host_requests.lock().unwrap().size = Some(logical_size);
+    }
+
+    /// Notify the embedded window that the host has shown or hidden it.
+    ///
+    /// While hidden, actions are still processed but nothing is rendered.
+    /// The change will happen on the next frame.
+    pub fn set_visible(&self, visible: bool) {
+        self.host_requests.lock().unwrap().visible = Some(visible);
+    }
+
+    /// Close the embedded window.
+    pub fn close(&mut self) {
+        self.handle.close();
+    }
+
+    /// Returns `true` if the embedded window is still open.
+    pub fn is_open(&self) -> bool {
+        self.handle.is_open()
+    }
+}
+
+/// Open the main window as a child of the given parent window (i.e. the
+/// editor window provided by a plugin host).
+///
+/// The size of the window is given by `app_config.main_window_config.size`
+/// and is then controlled by the host with [`ParentedWindowHandle::set_size`].
+pub fn run_parented<P: HasRawWindowHandle, A: Application + 'static>(
     parent: &P,
     app_config: AppConfig,
-) -> Result<WindowHandle, Box<dyn Error>> {
+) -> Result<ParentedWindowHandle, Box<dyn Error>> {
     let options = WindowOpenOptions {
         title: app_config.main_window_config.title.clone(),
         scale: match app_config.main_window_config.scale_factor {
@@ -506,14 +618,21 @@ pub fn run_parented<P: HasRawWindowHandle, A: Application + 'static, B>(
         ),
     };
 
-    Ok(BaseviewWindow::open_parented(
-        parent,
-        options,
-        move |window: &mut BaseviewWindow| {
+    let host_requests = Arc::new(Mutex::new(HostRequests::default()));
+    let host_requests_clone = Arc::clone(&host_requests);
+
+    let handle =
+        BaseviewWindow::open_parented(parent, options, move |window: &mut BaseviewWindow| {
             // TODO: get rid of unwrap once baseview supports erros on build closures.
-            BaseviewAppHandler::<A>::new(app_config, window).unwrap()
-        },
-    ))
+            let mut app_handler = BaseviewAppHandler::<A>::new(app_config, window).unwrap();
+            app_handler.inner.host_requests = Some(host_requests_clone);
+            app_handler
+        });
+
+    Ok(ParentedWindowHandle {
+        handle,
+        host_requests,
+    })
 }
 
 fn new_window<A: Application>(
@@ -581,9 +700,18 @@ fn new_window<A: Application>(
 
     let physical_size = PhysicalSizeI32::new(config.size.width as i32, config.size.height as i32);
 
-    let surface = unsafe {
+    let mut surface = unsafe {
         DefaultSurface::new_unsafe(physical_size, scale_factor, target, config.surface_config)?
     };
+    if config.present_mode != PresentMode::default() || config.partial_redraw {
+        configure_surface(
+            &mut surface,
+            physical_size,
+            config.present_mode,
+            false,
+            config.partial_redraw,
+        );
+    }
 
     let canvas_config = surface.canvas_config();
 
@@ -603,6 +731,8 @@ fn new_window<A: Application>(
             preallocate_for_this_many_elements: config.preallocate_for_this_many_elements,
             hover_timeout_duration: config.hover_timeout_duration,
             scroll_wheel_timeout_duration: config.scroll_wheel_timeout_duration,
+            long_press_duration: config.long_press_duration,
+            partial_redraw: config.partial_redraw,
         },
         action_sender,
         MAIN_WINDOW,
@@ -621,13 +751,22 @@ fn new_window<A: Application>(
         scale_factor_recip: scale_factor.recip(),
         system_scale_factor: 1.0.into(),
         scale_factor_config: config.scale_factor,
+        ui_zoom: 1.0,
         queued_pointer_position: None,
         queued_pointer_delta: None,
         prev_pointer_pos: None,
         pointer_btn_states: [PointerBtnState::default(); 5],
+        double_click_config: config.double_click,
         modifiers: Modifiers::empty(),
         current_cursor_icon: CursorIcon::Default,
         pointer_lock_state: PointerLockState::NotLocked,
+        close_on_focus_lost: false,
+        size_constraints: config.size_constraints,
+        requested_resize: None,
+        present_mode: config.present_mode,
+        transparent: false,
+        monitor: None,
+        text_direction: config.text_direction,
         clipboard,
     })
 }
@@ -11,6 +11,8 @@ pub fn convert_keyboard_event(event: &keyboard_types::KeyboardEvent) -> Keyboard
         location: event.location,
         modifiers: event.modifiers,
         repeat: event.repeat,
+        // baseview does not expose scan codes.
+        scan_code: None,
         is_composing: event.is_composing,
     }
 }
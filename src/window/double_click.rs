@@ -0,0 +1,116 @@
+use std::time::Duration;
+
+use crate::math::ScaleFactor;
+
+/// The thresholds used to determine whether consecutive presses of a pointer
+/// button count as a double-click (or triple-click, etc.)
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DoubleClickConfig {
+    /// The maximum time between two consecutive presses.
+    pub interval: Duration,
+    /// The maximum distance in logical points the pointer can move between
+    /// two consecutive presses.
+    pub distance: f32,
+}
+
+impl DoubleClickConfig {
+    /// The fallback thresholds used when they cannot be queried from the OS.
+    pub const FALLBACK: Self = Self {
+        interval: Duration::from_millis(400),
+        distance: 4.0,
+    };
+
+    /// Query the double-click thresholds set in the OS for a window with the
+    /// given scale factor.
+    ///
+    /// The OS reports the distance in physical pixels, so it is converted into
+    /// logical points using the scale factor.
+    ///
+    /// Currently this is only supported on Windows. On other platforms the
+    /// typical platform defaults are returned.
+    pub fn from_os(scale_factor: ScaleFactor) -> Self {
+        os::double_click_config(scale_factor)
+    }
+}
+
+impl Default for DoubleClickConfig {
+    /// The thresholds set in the OS with a scale factor of `1.0`.
+    fn default() -> Self {
+        Self::from_os(ScaleFactor::from(1.0f64))
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod os {
+    use std::time::Duration;
+
+    use super::DoubleClickConfig;
+    use crate::math::ScaleFactor;
+
+    const SM_CXDOUBLECLK: i32 = 36;
+    const SM_CYDOUBLECLK: i32 = 37;
+
+    #[link(name = "user32")]
+    extern "system" {
+        fn GetDoubleClickTime() -> u32;
+        fn GetSystemMetrics(index: i32) -> i32;
+    }
+
+    pub fn double_click_config(scale_factor: ScaleFactor) -> DoubleClickConfig {
+        // SAFETY: These functions take no pointers and have no preconditions.
+        let (time_ms, cx, cy) = unsafe {
+            (
+                GetDoubleClickTime(),
+                GetSystemMetrics(SM_CXDOUBLECLK),
+                GetSystemMetrics(SM_CYDOUBLECLK),
+            )
+        };
+
+        let mut config = DoubleClickConfig::FALLBACK;
+
+        if time_ms > 0 {
+            config.interval = Duration::from_millis(time_ms as u64);
+        }
+
+        // The metrics describe the full size of the rectangle centered on the
+        // first click in physical pixels.
+        let size = cx.max(cy);
+        if size > 0 && scale_factor.0 > 0.0 {
+            config.distance = size as f32 * 0.5 / scale_factor.0;
+        }
+
+        config
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod os {
+    use std::time::Duration;
+
+    use super::DoubleClickConfig;
+    use crate::math::ScaleFactor;
+
+    pub fn double_click_config(_scale_factor: ScaleFactor) -> DoubleClickConfig {
+        // The default value of `NSEvent.doubleClickInterval`.
+        DoubleClickConfig {
+            interval: Duration::from_millis(500),
+            ..DoubleClickConfig::FALLBACK
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+mod os {
+    use super::DoubleClickConfig;
+    use crate::math::ScaleFactor;
+
+    pub fn double_click_config(_scale_factor: ScaleFactor) -> DoubleClickConfig {
+        // Matches the defaults of GTK's `gtk-double-click-time` and
+        // `gtk-double-click-distance` settings.
+        DoubleClickConfig {
+            distance: 5.0,
+            ..DoubleClickConfig::FALLBACK
+        }
+    }
+}
@@ -0,0 +1,229 @@
+use keyboard_types::Modifiers;
+
+use crate::action_queue::{ActionReceiver, ActionSender};
+use crate::clipboard::{Clipboard, State as ClipboardState};
+use crate::element_system::{ElementSystem, ElementSystemConfig};
+use crate::math::{PhysicalSizeI32, ScaleFactor, Size};
+use crate::prelude::ResourceCtx;
+use crate::CursorIcon;
+
+use super::{
+    CapturedFrame, PointerBtnState, PointerLockState, WindowConfig, WindowContext, WindowID,
+    WindowState,
+};
+
+#[cfg(feature = "web-colors")]
+const TEXTURE_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8Unorm;
+#[cfg(not(feature = "web-colors"))]
+const TEXTURE_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8UnormSrgb;
+
+#[derive(thiserror::Error, Debug)]
+pub enum HeadlessError {
+    #[error("Could not find a suitable GPU adapter")]
+    NoAdapter,
+    #[error("{0}")]
+    RequestDevice(#[from] wgpu::RequestDeviceError),
+}
+
+/// A view which is rendered into an offscreen texture instead of an OS
+/// window (i.e. for screenshot tests in CI or for generating thumbnails
+/// of presets on a server).
+///
+/// Elements are added with [`HeadlessWindow::context`] just like with a
+/// regular window, and [`HeadlessWindow::render`] returns the rendered
+/// pixels.
+pub struct HeadlessWindow<A: Clone + 'static> {
+    state: WindowState<A>,
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    texture: wgpu::Texture,
+}
+
+impl<A: Clone + 'static> HeadlessWindow<A> {
+    /// Create a new headless window with the size given in the config.
+    ///
+    /// The `title`, `resizable`, and `focus_on_creation` fields of the config
+    /// are ignored.
+    pub fn new(
+        window_id: WindowID,
+        config: &WindowConfig,
+        scale_factor: ScaleFactor,
+        action_sender: ActionSender<A>,
+        res: &mut ResourceCtx,
+    ) -> Result<Self, HeadlessError> {
+        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor::default());
+
+        let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference: wgpu::PowerPreference::default(),
+            compatible_surface: None,
+            force_fallback_adapter: false,
+        }))
+        .ok_or(HeadlessError::NoAdapter)?;
+
+        let (device, queue) =
+            pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor::default(), None))?;
+
+        let scale_factor = config.scale_factor.scale_factor(scale_factor);
+        let physical_size: PhysicalSizeI32 =
+            crate::math::to_physical_size(config.size, scale_factor)
+                .round()
+                .cast();
+
+        let texture = create_texture(&device, physical_size);
+
+        let canvas_config = rootvg::CanvasConfig::default();
+
+        let renderer = rootvg::Canvas::new(
+            &device,
+            &queue,
+            TEXTURE_FORMAT,
+            canvas_config,
+            &mut res.font_system,
+        );
+
+        let element_system = ElementSystem::new(
+            physical_size,
+            scale_factor,
+            ElementSystemConfig {
+                clear_color: config.clear_color,
+                preallocate_for_this_many_elements: config.preallocate_for_this_many_elements,
+                hover_timeout_duration: config.hover_timeout_duration,
+                scroll_wheel_timeout_duration: config.scroll_wheel_timeout_duration,
+                long_press_duration: config.long_press_duration,
+                partial_redraw: config.partial_redraw,
+            },
+            action_sender,
+            window_id,
+        );
+
+        let state = WindowState {
+            element_system,
+            renderer,
+            surface: None,
+            multisample: canvas_config.multisample,
+            logical_size: crate::math::to_logical_size_i32(physical_size, scale_factor),
+            physical_size,
+            scale_factor,
+            scale_factor_recip: scale_factor.recip(),
+            system_scale_factor: scale_factor,
+            scale_factor_config: config.scale_factor,
+            ui_zoom: 1.0,
+            queued_pointer_position: None,
+            queued_pointer_delta: None,
+            prev_pointer_pos: None,
+            pointer_btn_states: [PointerBtnState::default(); 5],
+            double_click_config: config.double_click,
+            modifiers: Modifiers::empty(),
+            current_cursor_icon: CursorIcon::Default,
+            pointer_lock_state: PointerLockState::NotLocked,
+            close_on_focus_lost: false,
+            size_constraints: config.size_constraints.clone(),
+            requested_resize: None,
+            present_mode: config.present_mode,
+            transparent: false,
+            monitor: None,
+            text_direction: config.text_direction,
+            clipboard: Clipboard {
+                state: ClipboardState::Unavailable,
+            },
+        };
+
+        Ok(Self {
+            state,
+            device,
+            queue,
+            texture,
+        })
+    }
+
+    pub fn context<'a>(
+        &'a mut self,
+        res: &'a mut ResourceCtx,
+        action_sender: &'a mut ActionSender<A>,
+        action_receiver: &'a mut ActionReceiver<A>,
+    ) -> WindowContext<'a, A> {
+        self.state.context_with_gpu(
+            res,
+            action_sender,
+            action_receiver,
+            Some((&self.device, &self.queue, TEXTURE_FORMAT)),
+        )
+    }
+
+    pub fn logical_size(&self) -> Size {
+        self.state.logical_size()
+    }
+
+    pub(crate) fn state_mut(&mut self) -> &mut WindowState<A> {
+        &mut self.state
+    }
+
+    pub(crate) fn state(&self) -> &WindowState<A> {
+        &self.state
+    }
+
+    pub fn physical_size(&self) -> PhysicalSizeI32 {
+        self.state.physical_size
+    }
+
+    pub fn scale_factor(&self) -> ScaleFactor {
+        self.state.scale_factor
+    }
+
+    /// Resize the view to the given logical size.
+    pub fn resize(&mut self, logical_size: Size) {
+        let physical_size: PhysicalSizeI32 =
+            crate::math::to_physical_size(logical_size, self.state.scale_factor)
+                .round()
+                .cast();
+
+        if physical_size == self.state.physical_size {
+            return;
+        }
+
+        self.texture = create_texture(&self.device, physical_size);
+        self.state
+            .set_size(physical_size, self.state.system_scale_factor);
+    }
+
+    /// Process all pending updates to the elements, render the view, and
+    /// return the rendered pixels.
+    ///
+    /// This blocks until the GPU has finished rendering.
+    pub fn render(&mut self, res: &mut ResourceCtx) -> CapturedFrame {
+        while self.state.process_updates(res) {}
+
+        let view = self
+            .texture
+            .create_view(&wgpu::TextureViewDescriptor::default());
+
+        self.state.element_system.render_to_texture(
+            &view,
+            &self.device,
+            &self.queue,
+            TEXTURE_FORMAT,
+            self.state.multisample,
+            &mut self.state.renderer,
+            res,
+        );
+
+        CapturedFrame::read_texture(&self.device, &self.queue, &self.texture)
+    }
+}
+
+fn create_texture(device: &wgpu::Device, physical_size: PhysicalSizeI32) -> wgpu::Texture {
+    device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("yarrow headless texture"),
+        size: wgpu::Extent3d {
+            width: physical_size.width.max(1) as u32,
+            height: physical_size.height.max(1) as u32,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: TEXTURE_FORMAT,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+        view_formats: &[],
+    })
+}
@@ -4,7 +4,8 @@ use rootvg::text::FontSystem;
 use rustc_hash::FxHashMap;
 use std::error::Error;
 use std::sync::Arc;
-use std::time::{Duration, Instant};
+use std::time::Duration;
+use web_time::Instant;
 use winit::application::ApplicationHandler as WinitApplicationHandler;
 use winit::dpi::{PhysicalPosition, PhysicalSize};
 use winit::event::{
@@ -12,7 +13,9 @@ use winit::event::{
     WindowEvent as WinitWindowEvent,
 };
 use winit::event_loop::{ActiveEventLoop, ControlFlow, EventLoop};
-use winit::window::{CursorGrabMode, Window as WinitWindow, WindowId as WinitWindowId};
+use winit::window::{
+    CursorGrabMode, Window as WinitWindow, WindowId as WinitWindowId, WindowLevel,
+};
 
 use crate::action_queue::ActionSender;
 use crate::application::{Application, TimerInterval};
@@ -25,8 +28,10 @@ use crate::window::{WindowID, MAIN_WINDOW};
 use crate::AppConfig;
 
 use super::{
-    Clipboard, CursorIcon, ElementSystem, LinuxBackendType, PointerBtnState, PointerLockState,
-    ScaleFactorConfig, WindowBackend, WindowCloseRequest, WindowConfig, WindowState,
+    configure_surface, Clipboard, CursorIcon, ElementSystem, LinuxBackendType, MonitorInfo,
+    OpenWindowError, PointerBtnState, PointerLockState, PopupWindowConfig, PresentMode,
+    ScaleFactorConfig, SizeConstraints, WindowBackend, WindowCloseRequest, WindowConfig,
+    WindowState,
 };
 
 mod convert;
@@ -176,17 +181,13 @@ impl<'a> WindowBackend for WinitWindowBackend<'a> {
         scale_factor: ScaleFactor,
     ) -> Result<(), ()> {
         if let Some(window_handle) = self.inner.windows.get(&window_id) {
-            if window_handle
-                .request_inner_size(PhysicalSize::new(
-                    logical_size.width * scale_factor,
-                    logical_size.height * scale_factor,
-                ))
-                .is_some()
-            {
-                Ok(())
-            } else {
-                Err(())
-            }
+            // This returns `None` if the request was sent to the OS
+            // asynchronously, which is not an error.
+            let _ = window_handle.request_inner_size(PhysicalSize::new(
+                logical_size.width * scale_factor,
+                logical_size.height * scale_factor,
+            ));
+            Ok(())
         } else {
             Err(())
         }
@@ -204,6 +205,22 @@ impl<'a> WindowBackend for WinitWindowBackend<'a> {
         }
     }
 
+    fn is_maximized(&mut self, window_id: WindowID) -> bool {
+        self.inner
+            .windows
+            .get(&window_id)
+            .map(|window_handle| window_handle.is_maximized())
+            .unwrap_or(false)
+    }
+
+    fn drag_window(&mut self, window_id: WindowID) {
+        if let Some(window_handle) = self.inner.windows.get(&window_id) {
+            if let Err(e) = window_handle.drag_window() {
+                log::warn!("Failed to start dragging window {}: {}", window_id, e);
+            }
+        }
+    }
+
     fn focus_window(&mut self, window_id: WindowID) {
         if let Some(window_handle) = self.inner.windows.get(&window_id) {
             window_handle.focus_window()
@@ -223,7 +240,7 @@ impl<'a> WindowBackend for WinitWindowBackend<'a> {
         action_sender: &ActionSender<A>,
         res: &mut ResourceCtx,
     ) -> Result<WindowState<A>, OpenWindowError> {
-        match create_window(window_id, config, self.event_loop, action_sender, res) {
+        match create_window(window_id, config, None, self.event_loop, action_sender, res) {
             Ok((window_handle, window_state)) => {
                 self.inner
                     .winit_id_to_window_id_map
@@ -236,6 +253,53 @@ impl<'a> WindowBackend for WinitWindowBackend<'a> {
         }
     }
 
+    fn create_popup_window<A: Clone + 'static>(
+        &mut self,
+        window_id: WindowID,
+        config: &PopupWindowConfig,
+        parent_scale_factor: ScaleFactor,
+        action_sender: &ActionSender<A>,
+        res: &mut ResourceCtx,
+    ) -> Result<WindowState<A>, OpenWindowError> {
+        let Some(parent_handle) = self.inner.windows.get(&config.parent) else {
+            return Err(OpenWindowError::ParentWindowNotFound(config.parent));
+        };
+
+        // Convert the position relative to the parent window into screen
+        // coordinates.
+        let parent_position = parent_handle.inner_position()?;
+        let position = PhysicalPosition::new(
+            parent_position.x + (config.position.x * parent_scale_factor).round() as i32,
+            parent_position.y + (config.position.y * parent_scale_factor).round() as i32,
+        );
+
+        let popup = PopupPlacement {
+            position,
+            parent: Arc::clone(parent_handle),
+        };
+
+        match create_window(
+            window_id,
+            &config.window_config(),
+            Some(popup),
+            self.event_loop,
+            action_sender,
+            res,
+        ) {
+            Ok((window_handle, mut window_state)) => {
+                window_state.close_on_focus_lost = config.close_on_focus_lost;
+
+                self.inner
+                    .winit_id_to_window_id_map
+                    .insert(window_handle.id(), window_id);
+                self.inner.windows.insert(window_id, window_handle);
+
+                Ok(window_state)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
     fn close_window(&mut self, window_id: WindowID) {
         if let Some(window_handle) = self.inner.windows.remove(&window_id) {
             self.inner
@@ -253,6 +317,8 @@ struct PreMainWindowData {
 }
 
 struct WinitAppHandlerInner {
+    tick_timer_interval: TimerInterval,
+    pointer_debounce_timer_interval: TimerInterval,
     tick_interval: Duration,
     pointer_debounce_interval: Duration,
     prev_cursor_debounce_instant: Instant,
@@ -263,6 +329,58 @@ struct WinitAppHandlerInner {
     windows: FxHashMap<WindowID, Arc<winit::window::Window>>,
 
     tick_wait_cancelled: bool,
+    ticks_paused: bool,
+}
+
+impl WinitAppHandlerInner {
+    /// Update the timer intervals which depend on the refresh rate of the
+    /// display.
+    fn update_timer_intervals(&mut self, millihertz: u32) {
+        let interval = |timer_interval: TimerInterval| match timer_interval {
+            TimerInterval::Fixed(interval) => interval,
+            TimerInterval::PercentageOfFrameRate(percentage) => {
+                Duration::from_secs_f64(percentage * 1_000.0 / millihertz.max(1) as f64)
+            }
+        };
+
+        self.tick_interval = interval(self.tick_timer_interval);
+        self.pointer_debounce_interval = interval(self.pointer_debounce_timer_interval);
+    }
+
+    /// Query the monitor the window is on. Returns `true` if it changed.
+    fn refresh_monitor<A: Clone + 'static>(
+        &mut self,
+        window_id: WindowID,
+        window_state: &mut WindowState<A>,
+    ) -> bool {
+        let Some(window) = self.windows.get(&window_id) else {
+            return false;
+        };
+
+        let monitor = monitor_info(window);
+        if window_state.monitor == monitor {
+            return false;
+        }
+
+        let refresh_rate = |monitor: &Option<MonitorInfo>| {
+            monitor
+                .as_ref()
+                .and_then(|monitor| monitor.refresh_rate_millihertz)
+        };
+
+        // Animations are driven by the main window, so adapt the tick rate to
+        // the refresh rate of its monitor.
+        if window_id == MAIN_WINDOW && refresh_rate(&window_state.monitor) != refresh_rate(&monitor)
+        {
+            if let Some(millihertz) = refresh_rate(&monitor) {
+                self.update_timer_intervals(millihertz);
+            }
+        }
+
+        window_state.monitor = monitor;
+
+        true
+    }
 }
 
 struct WinitAppHandler<A: Application> {
@@ -278,6 +396,8 @@ impl<A: Application> WinitAppHandler<A> {
         Ok(Self {
             app_handler: None,
             inner: WinitAppHandlerInner {
+                tick_timer_interval: config.tick_timer_interval,
+                pointer_debounce_timer_interval: config.pointer_debounce_interval,
                 tick_interval: Duration::default(),
                 pointer_debounce_interval: Duration::default(),
                 prev_cursor_debounce_instant: Instant::now(),
@@ -286,6 +406,7 @@ impl<A: Application> WinitAppHandler<A> {
                 winit_id_to_window_id_map: FxHashMap::default(),
                 windows: FxHashMap::default(),
                 tick_wait_cancelled: false,
+                ticks_paused: false,
             },
             pre_main_window_data: Some(PreMainWindowData {
                 config,
@@ -294,6 +415,14 @@ impl<A: Application> WinitAppHandler<A> {
                     font_system: FontSystem::new(),
                     #[cfg(feature = "svg-icons")]
                     svg_icon_system: Default::default(),
+                    icon_font_system: Default::default(),
+                    #[cfg(feature = "image")]
+                    image_system: Default::default(),
+                    #[cfg(feature = "image")]
+                    image_loader: Default::default(),
+                    text_layout_cache: Default::default(),
+                    locale: Default::default(),
+                    font_families: Default::default(),
                 },
             }),
         })
@@ -343,6 +472,7 @@ impl<A: Application> WinitApplicationHandler for WinitAppHandler<A> {
             let (window_handle, main_window_state) = match create_window(
                 MAIN_WINDOW,
                 &data.config.main_window_config,
+                None,
                 event_loop,
                 &action_sender,
                 &mut data.res,
@@ -399,18 +529,7 @@ impl<A: Application> WinitApplicationHandler for WinitAppHandler<A> {
                 60_000
             };
 
-            self.inner.tick_interval = match data.config.tick_timer_interval {
-                TimerInterval::Fixed(interval) => interval,
-                TimerInterval::PercentageOfFrameRate(percentage) => {
-                    Duration::from_secs_f64(percentage * 1_000.0 / millihertz as f64)
-                }
-            };
-            self.inner.pointer_debounce_interval = match data.config.pointer_debounce_interval {
-                TimerInterval::Fixed(interval) => interval,
-                TimerInterval::PercentageOfFrameRate(percentage) => {
-                    Duration::from_secs_f64(percentage * 1_000.0 / millihertz as f64)
-                }
-            };
+            self.inner.update_timer_intervals(millihertz);
 
             self.inner
                 .winit_id_to_window_id_map
@@ -446,6 +565,10 @@ impl<A: Application> WinitApplicationHandler for WinitAppHandler<A> {
             )))]
             let linux_backend_type = None;
 
+            // Wayland does not allow windows to position themselves in screen
+            // coordinates, so popups are shown as in-window overlays instead.
+            let popup_windows_supported = linux_backend_type != Some(LinuxBackendType::Wayland);
+
             let app_handler = match AppHandler::new(
                 main_window_state,
                 action_sender,
@@ -453,6 +576,7 @@ impl<A: Application> WinitApplicationHandler for WinitAppHandler<A> {
                 data.config,
                 data.res,
                 linux_backend_type,
+                popup_windows_supported,
             ) {
                 Ok(a) => a,
                 Err(e) => {
@@ -562,6 +686,16 @@ impl<A: Application> WinitApplicationHandler for WinitAppHandler<A> {
                 window_state.set_size(new_size, scale_factor);
                 window_handle.request_redraw();
 
+                // The OS cannot enforce the aspect ratio or the size presets, so
+                // correct the size if it doesn't satisfy them.
+                let logical_size = window_state.logical_size();
+                let constrained_size = window_state.size_constraints.constrain(logical_size);
+                if (constrained_size.width - logical_size.width).abs() >= 1.0
+                    || (constrained_size.height - logical_size.height).abs() >= 1.0
+                {
+                    window_state.requested_resize = Some(constrained_size);
+                }
+
                 app_handler.user_app.on_window_event(
                     AppWindowEvent::WindowResized,
                     window_id,
@@ -572,8 +706,11 @@ impl<A: Application> WinitApplicationHandler for WinitAppHandler<A> {
                 scale_factor,
                 mut inner_size_writer,
             } => {
+                // Keep the logical size of the window when it is moved to a
+                // monitor with a different scale factor.
+                let new_scale_factor = window_state.scale_factor_for(scale_factor.into());
                 let new_size: PhysicalSizeI32 =
-                    crate::math::to_physical_size(window_state.logical_size(), scale_factor.into())
+                    crate::math::to_physical_size(window_state.logical_size(), new_scale_factor)
                         .round()
                         .cast();
                 let new_inner_size = winit::dpi::PhysicalSize {
@@ -585,14 +722,46 @@ impl<A: Application> WinitApplicationHandler for WinitAppHandler<A> {
                 }
 
                 window_state.set_size(new_size, scale_factor.into());
+                app_handler.cx.res.text_layout_cache.clear();
+
+                apply_size_constraints(
+                    self.inner.windows.get(&window_id).unwrap(),
+                    &window_state.size_constraints,
+                    window_state.scale_factor,
+                );
+
+                // A change in scale factor usually means the window moved
+                // to a different monitor.
+                let monitor_changed = self.inner.refresh_monitor(window_id, window_state);
 
                 app_handler.user_app.on_window_event(
                     AppWindowEvent::WindowResized,
                     window_id,
                     &mut app_handler.cx,
                 );
+
+                if monitor_changed {
+                    app_handler.user_app.on_window_event(
+                        AppWindowEvent::MonitorChanged,
+                        window_id,
+                        &mut app_handler.cx,
+                    );
+                }
+            }
+            WinitWindowEvent::Moved(_) => {
+                process_updates = false;
+
+                if self.inner.refresh_monitor(window_id, window_state) {
+                    app_handler.user_app.on_window_event(
+                        AppWindowEvent::MonitorChanged,
+                        window_id,
+                        &mut app_handler.cx,
+                    );
+                }
             }
             WinitWindowEvent::Focused(focused) => {
+                let close_popup = !focused && window_state.close_on_focus_lost;
+
                 let event = if focused {
                     window_state.handle_window_focused(&mut app_handler.cx.res);
                     AppWindowEvent::WindowFocused
@@ -604,6 +773,10 @@ impl<A: Application> WinitApplicationHandler for WinitAppHandler<A> {
                 app_handler
                     .user_app
                     .on_window_event(event, window_id, &mut app_handler.cx);
+
+                if close_popup {
+                    app_handler.cx.close_window(window_id);
+                }
             }
             WinitWindowEvent::Occluded(hidden) => {
                 let event = if hidden {
@@ -733,11 +906,7 @@ impl<A: Application> WinitApplicationHandler for WinitAppHandler<A> {
                 }
 
                 if !captured {
-                    app_handler.user_app.on_keyboard_event(
-                        key_event,
-                        window_id,
-                        &mut app_handler.cx,
-                    );
+                    app_handler.handle_uncaptured_keyboard_event(key_event, window_id);
                 }
             }
             _ => (),
@@ -793,20 +962,46 @@ impl<A: Application> WinitApplicationHandler for WinitAppHandler<A> {
     }
 
     fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
+        let Some(app_handler) = &mut self.app_handler else {
+            return;
+        };
+
+        if !app_handler.needs_ticks() {
+            // Nothing is animating, so pause the tick timer until it is needed again.
+            self.inner.ticks_paused = true;
+
+            match self.inner.requested_cursor_debounce_resume {
+                Some(pointer_resume_instant) if pointer_resume_instant > Instant::now() => {
+                    event_loop.set_control_flow(ControlFlow::WaitUntil(pointer_resume_instant));
+                }
+                _ => event_loop.set_control_flow(ControlFlow::Wait),
+            }
+
+            return;
+        }
+
+        let tick_interval = match app_handler.min_tick_interval() {
+            Some(min_tick_interval) => self.inner.tick_interval.max(min_tick_interval),
+            None => self.inner.tick_interval,
+        };
+
+        if self.inner.ticks_paused {
+            // Tick right away, and make sure animations don't jump ahead by the
+            // time spent idle.
+            self.inner.ticks_paused = false;
+            self.inner.tick_wait_cancelled = false;
+            app_handler.prev_tick_instant = Instant::now() - tick_interval;
+        }
+
         if !self.inner.tick_wait_cancelled {
             let now = Instant::now();
 
-            let Some(app_handler) = &mut self.app_handler else {
-                return;
-            };
-
-            let mut next_instant = if app_handler.prev_tick_instant + self.inner.tick_interval > now
-            {
-                app_handler.prev_tick_instant + self.inner.tick_interval
+            let mut next_instant = if app_handler.prev_tick_instant + tick_interval > now {
+                app_handler.prev_tick_instant + tick_interval
             } else {
                 app_handler.on_tick();
 
-                now + self.inner.tick_interval
+                now + tick_interval
             };
 
             if let Some(pointer_resume_instant) = self.inner.requested_cursor_debounce_resume {
@@ -823,12 +1018,11 @@ impl<A: Application> WinitApplicationHandler for WinitAppHandler<A> {
     }
 }
 
-#[derive(thiserror::Error, Debug)]
-pub enum OpenWindowError {
-    #[error("{0}")]
-    OsError(#[from] winit::error::OsError),
-    #[error("{0}")]
-    SurfaceError(#[from] rootvg::surface::NewSurfaceError),
+/// The placement of a popup window relative to its parent.
+struct PopupPlacement {
+    /// The position of the popup in screen coordinates.
+    position: PhysicalPosition<i32>,
+    parent: Arc<WinitWindow>,
 }
 
 pub fn run_blocking<A: Application>(config: AppConfig) -> Result<(), Box<dyn Error>>
@@ -836,14 +1030,31 @@ where
     A::Action: Send,
 {
     let event_loop = EventLoop::new()?;
-    let mut app_handler = WinitAppHandler::<A>::new(config)?;
 
-    event_loop.run_app(&mut app_handler).map_err(Into::into)
+    #[cfg(not(target_family = "wasm"))]
+    {
+        let mut app_handler = WinitAppHandler::<A>::new(config)?;
+
+        event_loop.run_app(&mut app_handler).map_err(Into::into)
+    }
+
+    #[cfg(target_family = "wasm")]
+    {
+        use winit::platform::web::EventLoopExtWebSys;
+
+        let app_handler = WinitAppHandler::<A>::new(config)?;
+
+        // On the web the event loop is driven by the browser, so this returns
+        // immediately instead of blocking.
+        event_loop.spawn_app(app_handler);
+        Ok(())
+    }
 }
 
 fn create_window<A: Clone + 'static>(
     id: WindowID,
     config: &WindowConfig,
+    popup: Option<PopupPlacement>,
     event_loop: &ActiveEventLoop,
     action_sender: &ActionSender<A>,
     res: &mut ResourceCtx,
@@ -852,7 +1063,9 @@ fn create_window<A: Clone + 'static>(
     let mut attributes = WinitWindow::default_attributes()
         .with_title(config.title.clone())
         .with_resizable(config.resizable)
-        .with_active(config.focus_on_creation);
+        .with_active(config.focus_on_creation)
+        .with_decorations(config.decorations)
+        .with_transparent(config.transparent);
 
     match config.scale_factor {
         ScaleFactorConfig::System => {
@@ -896,6 +1109,60 @@ fn create_window<A: Clone + 'static>(
         }
     }
 
+    #[cfg(target_family = "wasm")]
+    {
+        use winit::platform::web::WindowAttributesExtWebSys;
+
+        attributes = match config.canvas_id.as_deref().and_then(find_canvas) {
+            Some(canvas) => attributes.with_canvas(Some(canvas)),
+            None => attributes.with_append(true),
+        };
+
+        // Allow the canvas to receive keyboard focus so that key events are
+        // delivered to the window.
+        attributes = attributes.with_focusable(true);
+    }
+
+    if let Some(popup) = &popup {
+        attributes = attributes
+            .with_decorations(false)
+            .with_window_level(WindowLevel::AlwaysOnTop)
+            .with_position(popup.position);
+
+        // Attach the popup to its parent so that it moves and minimizes along
+        // with it on platforms which support child windows.
+        use raw_window_handle_06::HasWindowHandle;
+        if let Ok(parent_handle) = popup.parent.window_handle() {
+            // SAFETY:
+            // A reference-counted handle to the parent window is stored in the
+            // backend for as long as the popup is open.
+            attributes = unsafe { attributes.with_parent_window(Some(parent_handle.as_raw())) };
+        }
+
+        #[cfg(all(
+            any(
+                target_os = "linux",
+                target_os = "freebsd",
+                target_os = "openbsd",
+                target_os = "netbsd",
+                target_os = "dragonfly"
+            ),
+            not(target_family = "wasm")
+        ))]
+        {
+            use winit::platform::x11::{WindowAttributesExtX11, WindowType};
+
+            attributes = attributes.with_x11_window_type(vec![WindowType::PopupMenu]);
+        }
+
+        #[cfg(target_os = "windows")]
+        {
+            use winit::platform::windows::WindowAttributesExtWindows;
+
+            attributes = attributes.with_skip_taskbar(true);
+        }
+    }
+
     let window = event_loop.create_window(attributes).map(|w| Arc::new(w))?;
 
     // Might fix an issue in MacOS with wgpu
@@ -909,12 +1176,24 @@ fn create_window<A: Clone + 'static>(
 
     let scale_factor = config.scale_factor.scale_factor(system_scale_factor);
 
-    let surface = DefaultSurface::new(
+    apply_size_constraints(&window, &config.size_constraints, scale_factor);
+
+    let mut surface = DefaultSurface::new(
         physical_size,
         scale_factor,
         Arc::clone(&window),
         config.surface_config.clone(),
     )?;
+    let partial_redraw = config.partial_redraw && !config.transparent;
+    if config.present_mode != PresentMode::default() || config.transparent || partial_redraw {
+        configure_surface(
+            &mut surface,
+            physical_size,
+            config.present_mode,
+            config.transparent,
+            partial_redraw,
+        );
+    }
 
     let canvas_config = surface.canvas_config();
 
@@ -934,6 +1213,8 @@ fn create_window<A: Clone + 'static>(
             preallocate_for_this_many_elements: config.preallocate_for_this_many_elements,
             hover_timeout_duration: config.hover_timeout_duration,
             scroll_wheel_timeout_duration: config.scroll_wheel_timeout_duration,
+            long_press_duration: config.long_press_duration,
+            partial_redraw,
         },
         action_sender.clone(),
         id,
@@ -954,18 +1235,79 @@ fn create_window<A: Clone + 'static>(
             scale_factor_recip: scale_factor.recip(),
             system_scale_factor,
             scale_factor_config: config.scale_factor,
+            ui_zoom: 1.0,
             queued_pointer_position: None,
             queued_pointer_delta: None,
             prev_pointer_pos: None,
             pointer_btn_states: [PointerBtnState::default(); 5],
+            double_click_config: config.double_click,
             modifiers: Modifiers::empty(),
             current_cursor_icon: CursorIcon::Default,
             pointer_lock_state: PointerLockState::NotLocked,
+            close_on_focus_lost: false,
+            size_constraints: config.size_constraints.clone(),
+            requested_resize: None,
+            present_mode: config.present_mode,
+            transparent: config.transparent,
+            monitor: monitor_info(&window),
+            text_direction: config.text_direction,
             clipboard,
         },
     ))
 }
 
+#[cfg(target_family = "wasm")]
+fn find_canvas(id: &str) -> Option<web_sys::HtmlCanvasElement> {
+    use wasm_bindgen::JsCast;
+
+    let canvas = web_sys::window()?
+        .document()?
+        .get_element_by_id(id)
+        .and_then(|element| element.dyn_into::<web_sys::HtmlCanvasElement>().ok());
+
+    if canvas.is_none() {
+        log::warn!(
+            "Could not find a canvas element with the ID \"{}\", appending a new canvas to the page instead",
+            id
+        );
+    }
+
+    canvas
+}
+
+fn monitor_info(window: &WinitWindow) -> Option<MonitorInfo> {
+    let monitor = window.current_monitor()?;
+    let position = monitor.position();
+    let size = monitor.size();
+
+    Some(MonitorInfo {
+        name: monitor.name(),
+        position: PhysicalPoint::new(position.x as f32, position.y as f32),
+        size: PhysicalSizeI32::new(size.width as i32, size.height as i32),
+        scale_factor: monitor.scale_factor().into(),
+        refresh_rate_millihertz: monitor.refresh_rate_millihertz(),
+    })
+}
+
+/// Set the size limits enforced by the OS. The aspect ratio and size presets
+/// are enforced when the window is resized.
+fn apply_size_constraints(
+    window: &WinitWindow,
+    constraints: &SizeConstraints,
+    scale_factor: ScaleFactor,
+) {
+    let to_physical = |size: Size| -> PhysicalSize<u32> {
+        let size: PhysicalSizeI32 = crate::math::to_physical_size(size, scale_factor)
+            .round()
+            .cast();
+        PhysicalSize::new(size.width.max(1) as u32, size.height.max(1) as u32)
+    };
+
+    window.set_min_inner_size(constraints.min_size.map(to_physical));
+    window.set_max_inner_size(constraints.max_size.map(to_physical));
+}
+
+#[cfg(not(target_family = "wasm"))]
 fn new_clipboard(window_handle: &Arc<WinitWindow>) -> Clipboard {
     // SAFETY:
     // A reference-counted handle to the window is stored in `WindowState`,
@@ -978,3 +1320,13 @@ fn new_clipboard(window_handle: &Arc<WinitWindow>) -> Clipboard {
 
     Clipboard { state }
 }
+
+#[cfg(target_family = "wasm")]
+fn new_clipboard(_window_handle: &Arc<WinitWindow>) -> Clipboard {
+    Clipboard {
+        state: crate::clipboard::State::InProcess {
+            standard: String::new(),
+            primary: String::new(),
+        },
+    }
+}
@@ -134,10 +134,39 @@ pub fn convert_keyboard_event(
         location,
         modifiers,
         repeat: event.repeat,
+        scan_code: scan_code(event.physical_key),
         is_composing: event.text.is_some(),
     }
 }
 
+#[cfg(any(
+    target_os = "windows",
+    target_os = "macos",
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+))]
+fn scan_code(key: PhysicalKey) -> Option<u32> {
+    use winit::platform::scancode::PhysicalKeyExtScancode;
+
+    key.to_scancode()
+}
+
+#[cfg(not(any(
+    target_os = "windows",
+    target_os = "macos",
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+)))]
+fn scan_code(_key: PhysicalKey) -> Option<u32> {
+    None
+}
+
 fn convert_physical_key(key: PhysicalKey, location: KeyLocation) -> (Code, NativeKey) {
     match key {
         PhysicalKey::Code(code) => {
@@ -49,6 +49,16 @@ pub fn element_builder(_args: TokenStream, input: TokenStream) -> TokenStream {
                         })
                         .unwrap(),
                 );
+
+                fields.named.push(
+                    syn::Field::parse_named
+                        .parse2(quote! {
+                            /// An optional user-defined ID of the element, which can be used to look
+                            /// up the element later with `WindowContext::element_by_key`.
+                            pub key: Option<#crate_name::ElementKey>
+                        })
+                        .unwrap(),
+                );
             }
 
             quote! {
@@ -72,6 +82,13 @@ pub fn element_builder(_args: TokenStream, input: TokenStream) -> TokenStream {
                         self.scissor_rect = Some(scissor_rect);
                         self
                     }
+
+                    /// An optional user-defined ID of the element, which can be used to look
+                    /// up the element later with `WindowContext::element_by_key`.
+                    pub fn key(mut self, key: impl Into<#crate_name::ElementKey>) -> Self {
+                        self.key = Some(key.into());
+                        self
+                    }
                 }
             }
             .into()
@@ -132,6 +149,56 @@ pub fn element_builder_class(_args: TokenStream, input: TokenStream) -> TokenStr
     }
 }
 
+#[proc_macro_attribute]
+pub fn element_builder_tab_index(_args: TokenStream, input: TokenStream) -> TokenStream {
+    let mut ast = parse_macro_input!(input as DeriveInput);
+    let name = ast.ident.clone();
+    let generics = ast.generics.clone();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    match &mut ast.data {
+        syn::Data::Struct(ref mut struct_data) => {
+            if let syn::Fields::Named(ref mut fields) = struct_data.fields {
+                fields.named.push(
+                    syn::Field::parse_named
+                        .parse2(quote! {
+                            /// The explicit position of this element in the keyboard navigation
+                            /// order.
+                            ///
+                            /// If this method is not used, then the element will be visited in
+                            /// reading order after all elements with an explicit tab index.
+                            pub tab_index: Option<u16>
+                        })
+                        .unwrap(),
+                );
+            }
+
+            quote! {
+                #ast
+
+                impl #impl_generics #name #ty_generics #where_clause {
+                    /// The explicit position of this element in the keyboard navigation
+                    /// order.
+                    ///
+                    /// If this method is not used, then the element will be visited in
+                    /// reading order after all elements with an explicit tab index.
+                    pub const fn tab_index(mut self, tab_index: u16) -> Self {
+                        self.tab_index = Some(tab_index);
+                        self
+                    }
+                }
+            }
+            .into()
+        }
+        _ => syn::Error::new(
+            ast.span(),
+            "`element_builder_tab_index` has to be used with structs ",
+        )
+        .to_compile_error()
+        .into(),
+    }
+}
+
 #[proc_macro_attribute]
 pub fn element_builder_rect(_args: TokenStream, input: TokenStream) -> TokenStream {
     let mut ast = parse_macro_input!(input as DeriveInput);
@@ -312,6 +379,24 @@ pub fn element_builder_tooltip(_args: TokenStream, input: TokenStream) -> TokenS
                         self.tooltip_data = Some(#crate_name::elements::tooltip::TooltipData::new(text, align));
                         self
                     }
+
+                    /// Show a tooltip with a keyboard shortcut hint when the user hovers over
+                    /// this element
+                    ///
+                    /// * `text` - The tooltip text
+                    /// * `shortcut` - The keyboard shortcut to show right-aligned next to the text
+                    /// * `align` - Where to align the tooltip relative to this element
+                    pub fn tooltip_with_shortcut(
+                        mut self,
+                        text: impl Into<String>,
+                        shortcut: Option<#crate_name::shortcut::KeyChord>,
+                        align: #crate_name::layout::Align2,
+                    ) -> Self {
+                        self.tooltip_data = Some(
+                            #crate_name::elements::tooltip::TooltipData::new(text, align).with_shortcut(shortcut),
+                        );
+                        self
+                    }
                 }
             }
             .into()
@@ -472,6 +557,44 @@ pub fn element_handle(_args: TokenStream, input: TokenStream) -> TokenStream {
                         self.el.set_hidden(hidden)
                     }
 
+                    /// The transform applied to how this element instance is drawn.
+                    ///
+                    /// This is cached directly in the handle so this is very cheap to call frequently.
+                    pub fn transform(&self) -> Option<#crate_name::ElementTransform> {
+                        self.el.transform()
+                    }
+
+                    /// Rotate and/or scale how this element instance is drawn around a pivot
+                    /// point, or `None` to draw it normally.
+                    ///
+                    /// See `ElementHandle::set_transform` for more details.
+                    ///
+                    /// Returns `true` if the transform has changed.
+                    ///
+                    /// This will *NOT* trigger an element update unless the value has changed,
+                    /// so this method is very cheap to call frequently.
+                    pub fn set_transform(&mut self, transform: Option<#crate_name::ElementTransform>) -> bool {
+                        self.el.set_transform(transform)
+                    }
+
+                    /// Remove this element instance from the view without dropping the handle.
+                    ///
+                    /// The element is removed once the current batch of updates has been
+                    /// processed. All methods of this handle stay safe to call afterwards.
+                    pub fn destroy(&mut self) {
+                        self.el.destroy()
+                    }
+
+                    /// Returns `true` if this element instance has been destroyed.
+                    pub fn is_destroyed(&self) -> bool {
+                        self.el.is_destroyed()
+                    }
+
+                    /// The ID of this element instance.
+                    pub fn id(&self) -> #crate_name::ElementID {
+                        self.el.id()
+                    }
+
                     /// Get the actual bounding rectangle of this element, accounting for the offset
                     /// introduced by its assigned scissoring rectangle.
                     pub fn rect_in_window<A_: Clone + 'static>(&self, cx: &#crate_name::WindowContext<'_, A_>) -> Rect {
@@ -673,6 +796,50 @@ pub fn element_handle_class(_args: TokenStream, input: TokenStream) -> TokenStre
     }
 }
 
+#[proc_macro_attribute]
+pub fn element_handle_tab_index(_args: TokenStream, input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    let name = ast.ident.clone();
+    let generics = ast.generics.clone();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    match &ast.data {
+        syn::Data::Struct(_) => {
+            quote! {
+                #ast
+
+                impl #impl_generics #name #ty_generics #where_clause {
+                    /// The explicit position of this element in the keyboard navigation
+                    /// order.
+                    ///
+                    /// This is cached directly in the handle so this is very cheap to call frequently.
+                    pub fn tab_index(&self) -> Option<u16> {
+                        self.el.tab_index()
+                    }
+
+                    /// Set the explicit position of this element in the keyboard navigation
+                    /// order.
+                    ///
+                    /// Returns `true` if the tab index has changed.
+                    ///
+                    /// This will *NOT* trigger an element update unless the value has changed,
+                    /// so this method is relatively cheap to call frequently.
+                    pub fn set_tab_index(&mut self, tab_index: Option<u16>) -> bool {
+                        self.el.set_tab_index(tab_index)
+                    }
+                }
+            }
+            .into()
+        }
+        _ => syn::Error::new(
+            ast.span(),
+            "`element_handle_tab_index` has to be used with structs ",
+        )
+        .to_compile_error()
+        .into(),
+    }
+}
+
 #[proc_macro_attribute]
 pub fn element_handle_layout_aligned(_args: TokenStream, input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
@@ -743,6 +910,20 @@ pub fn element_handle_set_tooltip(_args: TokenStream, input: TokenStream) -> Tok
                             false
                         }
                     }
+
+                    /// Set the keyboard shortcut to show next to the tooltip text
+                    ///
+                    /// This has no effect if this element has no tooltip text.
+                    ///
+                    /// Returns `true` if the shortcut has changed.
+                    pub fn set_tooltip_shortcut(&mut self, shortcut: Option<#crate_name::shortcut::KeyChord>) -> bool {
+                        if RefCell::borrow_mut(&self.shared_state).tooltip_inner.set_shortcut(shortcut) {
+                            self.el.notify_custom_state_change();
+                            true
+                        } else {
+                            false
+                        }
+                    }
                 }
             }
             .into()